@@ -8,11 +8,13 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::Context;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use chrono::{Days, NaiveDate};
 use object_store::path::Path;
 use object_store::DynObjectStore;
 use serde::Serialize;
 use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::task::JoinHandle;
 use tracing::{error, info};
 
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
@@ -22,19 +24,24 @@ use sui_storage::object_store::util::{copy_file, path_to_filesystem};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::analytics_metrics::AnalyticsMetrics;
+use crate::delta_table::DeltaTableLog;
 use crate::handlers::AnalyticsHandler;
 use crate::writers::AnalyticsWriter;
 use crate::{
     join_paths, AnalyticsIndexerConfig, FileMetadata, MaxCheckpointReader, ParquetSchema,
-    EPOCH_DIR_PREFIX,
+    TableFormat, EPOCH_DIR_PREFIX,
 };
 
 struct State<S: Serialize + ParquetSchema> {
     current_epoch: u64,
     current_checkpoint_range: Range<u64>,
+    /// UTC date of the first checkpoint in the current range
+    current_date: Option<NaiveDate>,
     last_commit_instant: Instant,
     num_checkpoint_iterations: u64,
     writer: Box<dyn AnalyticsWriter<S>>,
+    /// Task uploading the cut files, None once its error was reported
+    sync_task: Option<JoinHandle<Result<()>>>,
 }
 
 pub struct AnalyticsProcessor<S: Serialize + ParquetSchema> {
@@ -50,6 +57,7 @@ pub struct AnalyticsProcessor<S: Serialize + ParquetSchema> {
 }
 
 const CHECK_FILE_SIZE_ITERATION_CYCLE: u64 = 50;
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
 
 #[async_trait::async_trait]
 impl<S: Serialize + ParquetSchema + 'static> Worker for AnalyticsProcessor<S> {
@@ -60,6 +68,9 @@ impl<S: Serialize + ParquetSchema + 'static> Worker for AnalyticsProcessor<S> {
         let epoch: u64 = checkpoint_data.checkpoint_summary.epoch();
         let checkpoint_num: u64 = *checkpoint_data.checkpoint_summary.sequence_number();
         let timestamp: u64 = checkpoint_data.checkpoint_summary.data().timestamp_ms;
+        let date = NaiveDate::default()
+            .checked_add_days(Days::new(timestamp / MILLIS_PER_DAY))
+            .context("Checkpoint timestamp overflow")?;
         info!("Processing checkpoint {checkpoint_num}, epoch {epoch}, timestamp {timestamp}");
         let mut state = self.state.lock().await;
         if epoch > state.current_epoch {
@@ -79,7 +90,10 @@ impl<S: Serialize + ParquetSchema + 'static> Worker for AnalyticsProcessor<S> {
             || (state.last_commit_instant.elapsed().as_secs() > self.config.time_interval_s)
             || (state.num_checkpoint_iterations % CHECK_FILE_SIZE_ITERATION_CYCLE == 0
                 && state.writer.file_size()?.unwrap_or(0)
-                    > self.config.max_file_size_mb * 1024 * 1024);
+                    > self.config.max_file_size_mb * 1024 * 1024)
+            // files of delta tables are partitioned by date
+            || (self.config.table_format == TableFormat::Delta
+                && state.current_date.is_some_and(|current| current != date));
         if cut_new_files {
             self.cut(&mut state).await?;
             self.reset(&mut state)?;
@@ -88,6 +102,7 @@ impl<S: Serialize + ParquetSchema + 'static> Worker for AnalyticsProcessor<S> {
             .total_received
             .with_label_values(&[self.name()])
             .inc();
+        state.current_date.get_or_insert(date);
         self.handler.process_checkpoint(checkpoint_data).await?;
         let rows = self.handler.read().await?;
         state.writer.write(&rows)?;
@@ -122,11 +137,25 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         let name: String = handler.name().parse()?;
         let checkpoint_dir = config.checkpoint_dir.clone();
         let cloned_metrics = metrics.clone();
-        tokio::task::spawn(Self::start_syncing_with_remote(
+        let table_log = match config.table_format {
+            TableFormat::Files => None,
+            TableFormat::Delta => Some(
+                DeltaTableLog::open(
+                    remote_object_store.clone(),
+                    join_paths(
+                        config.remote_store_path_prefix.clone(),
+                        &config.file_type.dir_prefix(),
+                    ),
+                )
+                .await?,
+            ),
+        };
+        let sync_task = tokio::task::spawn(Self::start_syncing_with_remote(
             remote_object_store,
             local_object_store.clone(),
             checkpoint_dir,
             config.remote_store_path_prefix.clone(),
+            table_log,
             receiver,
             kill_receiver,
             cloned_metrics,
@@ -142,9 +171,11 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         let state = State {
             current_epoch: 0,
             current_checkpoint_range: next_checkpoint_seq_num..next_checkpoint_seq_num,
+            current_date: None,
             last_commit_instant: Instant::now(),
             num_checkpoint_iterations: 0,
             writer,
+            sync_task: Some(sync_task),
         };
         Ok(Self {
             handler,
@@ -170,13 +201,28 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                 self.config.file_format,
                 state.current_epoch,
                 state.current_checkpoint_range.clone(),
+                state.current_date,
             );
-            self.sender.send(file_metadata).await?;
+            if self.sender.send(file_metadata).await.is_err() {
+                // The upload task only stops receiving files when it fails
+                return Err(Self::sync_error(state).await);
+            }
             tokio::task::yield_now().await;
         }
         Ok(())
     }
 
+    async fn sync_error(state: &mut State<S>) -> anyhow::Error {
+        match state.sync_task.take() {
+            Some(sync_task) => match sync_task.await {
+                Ok(Err(e)) => e,
+                Ok(Ok(())) => anyhow!("Upload sync loop terminated"),
+                Err(e) => e.into(),
+            },
+            None => anyhow!("Upload sync loop failed"),
+        }
+    }
+
     fn update_to_next_epoch(&self, epoch: u64, state: &mut State<S>) {
         state.current_epoch = epoch;
     }
@@ -210,7 +256,8 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
 
     fn reset_checkpoint_range(&self, state: &mut State<S>) {
         state.current_checkpoint_range =
-            state.current_checkpoint_range.end..state.current_checkpoint_range.end
+            state.current_checkpoint_range.end..state.current_checkpoint_range.end;
+        state.current_date = None;
     }
 
     fn reset_last_commit_ts(&self, state: &mut State<S>) {
@@ -222,6 +269,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         local_object_store: Arc<DynObjectStore>,
         local_staging_root_dir: PathBuf,
         remote_store_path_prefix: Option<Path>,
+        mut table_log: Option<DeltaTableLog>,
         mut file_recv: mpsc::Receiver<FileMetadata>,
         mut recv: oneshot::Receiver<()>,
         metrics: AnalyticsMetrics,
@@ -236,13 +284,16 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                         let checkpoint_seq_num = file_metadata.checkpoint_seq_range.end;
                         Self::sync_file_to_remote(
                                 local_staging_root_dir.clone(),
-                                file_metadata.file_path(),
+                                &file_metadata,
                                 remote_store_path_prefix.clone(),
                                 local_object_store.clone(),
-                                remote_object_store.clone()
+                                remote_object_store.clone(),
+                                table_log.as_mut(),
                             )
                             .await
-                            .expect("Syncing checkpoint should not fail");
+                            .with_context(|| {
+                                format!("Failed to sync {name} file with checkpoints {:?}", file_metadata.checkpoint_seq_range)
+                            })?;
                         metrics.last_uploaded_checkpoint.with_label_values(&[&name]).set(checkpoint_seq_num as i64);
                     } else {
                         info!("Terminating upload sync loop");
@@ -283,15 +334,148 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
 
     async fn sync_file_to_remote(
         dir: PathBuf,
-        path: Path,
+        file_metadata: &FileMetadata,
         prefix: Option<Path>,
         from: Arc<DynObjectStore>,
         to: Arc<DynObjectStore>,
+        table_log: Option<&mut DeltaTableLog>,
     ) -> Result<()> {
+        let path = file_metadata.file_path();
         let remote_dest = join_paths(prefix, &path);
         info!("Syncing file to remote: {:?}", &remote_dest);
         copy_file(&path, &remote_dest, &from, &to).await?;
-        fs::remove_file(path_to_filesystem(dir, &path)?)?;
+        let local_path = path_to_filesystem(dir, &path)?;
+        // Files only become visible to table readers once fully uploaded
+        if let Some(table_log) = table_log {
+            table_log.commit_file(file_metadata, &local_path).await?;
+        }
+        fs::remove_file(local_path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::checkpoint_handler::CheckpointHandler;
+    use crate::tables::CheckpointEntry;
+    use crate::{FileFormat, FileType};
+    use clap::Parser;
+    use prometheus::Registry;
+    use simulacrum::Simulacrum;
+    use sui_types::base_types::EpochId;
+    use sui_types::storage::ReadStore;
+
+    /// Drops the rows, every flush producing a file
+    struct TestWriter;
+
+    impl<S: Serialize + ParquetSchema> AnalyticsWriter<S> for TestWriter {
+        fn file_format(&self) -> Result<FileFormat> {
+            Ok(FileFormat::PARQUET)
+        }
+
+        fn write(&mut self, _rows: &[S]) -> Result<()> {
+            Ok(())
+        }
+
+        fn flush(&mut self, _end_checkpoint_seq_num: u64) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn reset(&mut self, _epoch_num: EpochId, _start_checkpoint_seq_num: u64) -> Result<()> {
+            Ok(())
+        }
+
+        fn file_size(&self) -> Result<Option<u64>> {
+            Ok(None)
+        }
+    }
+
+    fn test_processor(
+        table_format: &str,
+        next_checkpoint_seq_num: CheckpointSequenceNumber,
+    ) -> (
+        AnalyticsProcessor<CheckpointEntry>,
+        mpsc::Receiver<FileMetadata>,
+    ) {
+        let config = AnalyticsIndexerConfig::parse_from([
+            "sui-analytics-indexer",
+            "--rest-url",
+            "http://localhost:9000",
+            "--file-type",
+            "checkpoint",
+            "--table-format",
+            table_format,
+        ]);
+        let (sender, receiver) = mpsc::channel(100);
+        let state = State {
+            current_epoch: 0,
+            current_checkpoint_range: next_checkpoint_seq_num..next_checkpoint_seq_num,
+            current_date: None,
+            last_commit_instant: Instant::now(),
+            num_checkpoint_iterations: 0,
+            writer: Box::new(TestWriter),
+            sync_task: None,
+        };
+        let processor = AnalyticsProcessor {
+            handler: Box::new(CheckpointHandler::new()),
+            state: Mutex::new(state),
+            metrics: AnalyticsMetrics::new(&Registry::new()),
+            config,
+            sender,
+            kill_sender: oneshot::channel().0,
+            max_checkpoint_sender: oneshot::channel().0,
+        };
+        (processor, receiver)
+    }
+
+    #[tokio::test]
+    async fn test_cut_files_on_date_change() -> Result<()> {
+        let mut sim = Simulacrum::new();
+        let mut checkpoints = vec![];
+        for advance in [
+            Duration::ZERO,
+            Duration::from_secs(1),
+            Duration::from_millis(MILLIS_PER_DAY),
+        ] {
+            sim.advance_clock(advance);
+            let checkpoint = sim.create_checkpoint();
+            let contents = sim
+                .get_checkpoint_contents_by_digest(&checkpoint.content_digest)
+                .unwrap();
+            checkpoints.push(sim.get_checkpoint_data(checkpoint, contents)?);
+        }
+        let first_checkpoint = *checkpoints[0].checkpoint_summary.sequence_number();
+        let first_date = chrono::DateTime::from_timestamp_millis(
+            checkpoints[0].checkpoint_summary.data().timestamp_ms as i64,
+        )
+        .unwrap()
+        .date_naive();
+
+        // Files of delta tables don't span two dates
+        let (processor, mut receiver) = test_processor("delta", first_checkpoint);
+        for checkpoint in &checkpoints[..2] {
+            processor.process_checkpoint(checkpoint).await?;
+        }
+        assert!(receiver.try_recv().is_err());
+        processor.process_checkpoint(&checkpoints[2]).await?;
+        assert_eq!(
+            receiver.try_recv()?,
+            FileMetadata::new(
+                FileType::Checkpoint,
+                FileFormat::CSV,
+                0,
+                first_checkpoint..first_checkpoint + 2,
+                Some(first_date),
+            )
+        );
+
+        // Plain files are only cut by size, time or checkpoint count
+        let (processor, mut receiver) = test_processor("files", first_checkpoint);
+        for checkpoint in &checkpoints {
+            processor.process_checkpoint(checkpoint).await?;
+        }
+        assert!(receiver.try_recv().is_err());
         Ok(())
     }
 }
@@ -0,0 +1,807 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Maintains a Delta Lake transaction log next to the files uploaded for a table, so that query
+//! engines can read a consistent snapshot of the table while ingestion keeps adding files.
+//!
+//! Every uploaded file is committed as a new log version (`_delta_log/<version>.json`) once it is
+//! fully written to the remote store. The table is partitioned by epoch and by the UTC date of its
+//! checkpoints, and a new schema is committed whenever the columns of the uploaded files change.
+//! Every `CHECKPOINT_INTERVAL` versions the state of the table is written to a log checkpoint, so
+//! readers don't have to replay the whole log, and the commits it makes redundant are removed.
+//!
+//! Indexing resumes after the last committed file, so a crash between the upload of a file and
+//! its commit leaves at worst an unreferenced file in the table directory, which readers ignore.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use arrow::datatypes::{DataType, Field, Fields, Schema};
+use arrow::json::{LineDelimitedWriter, ReaderBuilder};
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::{DynObjectStore, PutMode, PutPayload};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{info, warn};
+
+use crate::FileMetadata;
+
+const DELTA_LOG_DIR: &str = "_delta_log";
+const EPOCH_PARTITION_COLUMN: &str = "epoch";
+const DATE_PARTITION_COLUMN: &str = "date";
+const LAST_CHECKPOINT_FILE: &str = "_last_checkpoint";
+/// Number of versions between two checkpoints of the log, the default of Delta Lake writers
+const CHECKPOINT_INTERVAL: u64 = 10;
+const CHECKPOINT_BATCH_SIZE: usize = 1024;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    id: String,
+    format: Format,
+    schema_string: String,
+    partition_columns: Vec<String>,
+    #[serde(default)]
+    configuration: BTreeMap<String, String>,
+    created_time: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+struct Format {
+    provider: String,
+    #[serde(default)]
+    options: BTreeMap<String, String>,
+}
+
+/// The actions of a commit or a checkpoint this writer needs to rebuild the state of the table
+#[derive(Deserialize)]
+struct Action {
+    #[serde(rename = "metaData")]
+    metadata: Option<Metadata>,
+    add: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LastCheckpoint {
+    version: u64,
+    /// Number of actions in the checkpoint
+    size: u64,
+}
+
+pub struct DeltaTableLog {
+    store: Arc<DynObjectStore>,
+    /// Directory of the table, the files of the table are referenced relative to it
+    table_root: Path,
+    /// Latest committed version of the log, None for a new table
+    version: Option<u64>,
+    /// Latest committed table metadata
+    metadata: Option<Metadata>,
+    /// Add actions of the files in the table, written to the checkpoints of the log
+    files: Vec<Value>,
+    /// Version of the latest checkpoint of the log
+    last_checkpoint: Option<u64>,
+}
+
+impl DeltaTableLog {
+    /// Open the log of the table at `table_root`, creating it on the first commit.
+    pub async fn open(store: Arc<DynObjectStore>, table_root: Path) -> Result<Self> {
+        let log_dir = table_root.child(DELTA_LOG_DIR);
+        let last_checkpoint = match store.get(&log_dir.child(LAST_CHECKPOINT_FILE)).await {
+            Ok(result) => {
+                let last_checkpoint: LastCheckpoint =
+                    serde_json::from_slice(&result.bytes().await?)?;
+                Some(last_checkpoint.version)
+            }
+            Err(object_store::Error::NotFound { .. }) => None,
+            Err(e) => return Err(e.into()),
+        };
+        let mut versions: Vec<u64> = store
+            .list_with_delimiter(Some(&log_dir))
+            .await?
+            .objects
+            .into_iter()
+            .filter_map(|object| {
+                object
+                    .location
+                    .filename()?
+                    .strip_suffix(".json")?
+                    .parse()
+                    .ok()
+            })
+            // Commits up to the checkpoint may have been removed already
+            .filter(|version| last_checkpoint.map_or(true, |checkpoint| *version > checkpoint))
+            .collect();
+        versions.sort_unstable();
+
+        let mut log = Self {
+            store,
+            table_root,
+            version: last_checkpoint,
+            metadata: None,
+            files: vec![],
+            last_checkpoint,
+        };
+        if let Some(checkpoint) = last_checkpoint {
+            let bytes = log
+                .store
+                .get(&Self::checkpoint_path(&log_dir, checkpoint))
+                .await?
+                .bytes()
+                .await?;
+            for action in read_checkpoint(bytes)? {
+                log.apply(action);
+            }
+        }
+        for version in versions {
+            let bytes = log
+                .store
+                .get(&Self::commit_path(&log_dir, version))
+                .await?
+                .bytes()
+                .await?;
+            for line in bytes.split(|c| *c == b'\n') {
+                if !line.is_empty() {
+                    log.apply(serde_json::from_slice(line)?);
+                }
+            }
+            log.version = Some(version);
+        }
+        info!(
+            "Opened delta table log at {} with version {:?}",
+            log.table_root, log.version
+        );
+        Ok(log)
+    }
+
+    pub fn version(&self) -> Option<u64> {
+        self.version
+    }
+
+    /// The checkpoint following those of the committed files. Indexing resumes from it rather
+    /// than from the last uploaded file, so that a file uploaded but not committed before a crash
+    /// is written and committed again.
+    pub fn next_checkpoint(&self) -> u64 {
+        self.files
+            .iter()
+            .filter_map(|add| checkpoint_range_end(add["path"].as_str()?))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Commit the uploaded file described by `file_metadata` to the table, reading its schema and
+    /// row count from the local copy at `local_path`. Returns the committed version, or the
+    /// current one if the file is already part of the table.
+    pub async fn commit_file(
+        &mut self,
+        file_metadata: &FileMetadata,
+        local_path: &std::path::Path,
+    ) -> Result<u64> {
+        let file_path = file_metadata.file_path();
+        let relative_path = file_path
+            .prefix_match(&file_metadata.file_type.dir_prefix())
+            .ok_or_else(|| anyhow!("File {} is not in its table directory", file_path))?
+            .map(|part| part.as_ref().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        if let Some(version) = self.version.filter(|_| {
+            self.files
+                .iter()
+                .any(|add| add["path"].as_str() == Some(relative_path.as_str()))
+        }) {
+            info!("{} is already committed to the table", file_path);
+            return Ok(version);
+        }
+
+        let size = std::fs::metadata(local_path)?.len();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(local_path)?)?;
+        let num_records = reader.metadata().file_metadata().num_rows();
+        let schema_string = delta_schema(reader.schema())?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut actions = vec![];
+        if self.version.is_none() {
+            actions.push(protocol());
+        }
+        let metadata = match &self.metadata {
+            Some(metadata) if metadata.schema_string == schema_string => None,
+            Some(metadata) => Some(Metadata {
+                schema_string,
+                ..metadata.clone()
+            }),
+            None => Some(Metadata {
+                id: uuid::Uuid::new_v4().to_string(),
+                format: Format {
+                    provider: "parquet".to_string(),
+                    options: BTreeMap::new(),
+                },
+                schema_string,
+                partition_columns: vec![
+                    EPOCH_PARTITION_COLUMN.to_string(),
+                    DATE_PARTITION_COLUMN.to_string(),
+                ],
+                configuration: BTreeMap::new(),
+                created_time: now,
+            }),
+        };
+        if let Some(metadata) = &metadata {
+            actions.push(json!({ "metaData": metadata }));
+        }
+
+        let date = file_metadata
+            .date
+            .ok_or_else(|| anyhow!("File {} has no partition date", file_path))?;
+        let add = json!({
+            "path": relative_path,
+            "partitionValues": {
+                EPOCH_PARTITION_COLUMN: file_metadata.epoch_num.to_string(),
+                DATE_PARTITION_COLUMN: date.format("%Y-%m-%d").to_string(),
+            },
+            "size": size,
+            "modificationTime": now,
+            "dataChange": true,
+            "stats": json!({"numRecords": num_records}).to_string(),
+        });
+        actions.push(json!({ "add": add }));
+        actions.push(json!({
+            "commitInfo": {
+                "timestamp": now,
+                "operation": "WRITE",
+                "operationParameters": {"mode": "Append"},
+                "engineInfo": "sui-analytics-indexer",
+            }
+        }));
+
+        let mut commit = vec![];
+        for action in actions {
+            serde_json::to_writer(&mut commit, &action)?;
+            commit.push(b'\n');
+        }
+        let version = self.version.map_or(0, |version| version + 1);
+        let commit_path = Self::commit_path(&self.table_root.child(DELTA_LOG_DIR), version);
+        // A commit must never overwrite another one. Fall back to a plain put on stores without
+        // conditional writes, this indexer being the only writer of its tables.
+        let payload = PutPayload::from(commit);
+        match self
+            .store
+            .put_opts(&commit_path, payload.clone(), PutMode::Create.into())
+            .await
+        {
+            Err(object_store::Error::NotImplemented) => {
+                self.store.put(&commit_path, payload).await?;
+            }
+            result => {
+                result?;
+            }
+        }
+        info!(
+            "Committed {} as version {} of the table",
+            file_path, version
+        );
+        self.version = Some(version);
+        self.apply(Action {
+            metadata,
+            add: Some(add),
+        });
+        // The commit is done, a missing checkpoint only makes the log slower to read
+        if version > 0 && version % CHECKPOINT_INTERVAL == 0 {
+            if let Err(e) = self.checkpoint(version).await {
+                warn!(
+                    "Failed to checkpoint version {} of the table: {:?}",
+                    version, e
+                );
+            }
+        }
+        Ok(version)
+    }
+
+    fn apply(&mut self, action: Action) {
+        if let Some(metadata) = action.metadata {
+            self.metadata = Some(metadata);
+        }
+        if let Some(add) = action.add {
+            self.files.push(add);
+        }
+    }
+
+    /// Write the state of the table at `version` to a checkpoint, then remove the log entries
+    /// made redundant by the previous checkpoint. Entries following the previous checkpoint are
+    /// kept for readers which listed the log before this checkpoint.
+    async fn checkpoint(&mut self, version: u64) -> Result<()> {
+        let metadata = self
+            .metadata
+            .as_ref()
+            .ok_or_else(|| anyhow!("No metadata to checkpoint"))?;
+        let mut actions = vec![protocol(), json!({ "metaData": metadata })];
+        actions.extend(self.files.iter().map(|add| json!({ "add": add })));
+
+        let log_dir = self.table_root.child(DELTA_LOG_DIR);
+        self.store
+            .put(
+                &Self::checkpoint_path(&log_dir, version),
+                write_checkpoint(&actions)?.into(),
+            )
+            .await?;
+        let last_checkpoint = LastCheckpoint {
+            version,
+            size: actions.len() as u64,
+        };
+        self.store
+            .put(
+                &log_dir.child(LAST_CHECKPOINT_FILE),
+                serde_json::to_vec(&last_checkpoint)?.into(),
+            )
+            .await?;
+        info!("Checkpointed version {} of the table", version);
+
+        let Some(previous) = self.last_checkpoint.replace(version) else {
+            return Ok(());
+        };
+        for object in self
+            .store
+            .list_with_delimiter(Some(&log_dir))
+            .await?
+            .objects
+        {
+            let Some(filename) = object.location.filename() else {
+                continue;
+            };
+            let expired = if let Some(commit) = filename.strip_suffix(".json") {
+                commit.parse().is_ok_and(|commit: u64| commit <= previous)
+            } else if let Some(checkpoint) = filename.strip_suffix(".checkpoint.parquet") {
+                checkpoint
+                    .parse()
+                    .is_ok_and(|checkpoint: u64| checkpoint < previous)
+            } else {
+                false
+            };
+            if expired {
+                self.store.delete(&object.location).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn commit_path(log_dir: &Path, version: u64) -> Path {
+        log_dir.child(format!("{:020}.json", version))
+    }
+
+    fn checkpoint_path(log_dir: &Path, version: u64) -> Path {
+        log_dir.child(format!("{:020}.checkpoint.parquet", version))
+    }
+}
+
+/// The end of the checkpoint range of a file of the table, from its `<start>_<end>.<suffix>` name
+fn checkpoint_range_end(path: &str) -> Option<u64> {
+    let filename = path.rsplit('/').next()?;
+    let (range, _suffix) = filename.split_once('.')?;
+    let (_start, end) = range.split_once('_')?;
+    end.parse().ok()
+}
+
+fn protocol() -> Value {
+    json!({
+        "protocol": {"minReaderVersion": 1, "minWriterVersion": 2}
+    })
+}
+
+/// Schema of the parquet checkpoints of the log, one nullable column per action type of which
+/// every row sets exactly one.
+fn checkpoint_schema() -> Schema {
+    fn action(name: &str, fields: Vec<Field>) -> Field {
+        Field::new(name, DataType::Struct(Fields::from(fields)), true)
+    }
+    fn string_map(name: &str) -> Field {
+        let entries = Field::new(
+            "key_value",
+            DataType::Struct(Fields::from(vec![
+                Field::new("key", DataType::Utf8, false),
+                Field::new("value", DataType::Utf8, true),
+            ])),
+            false,
+        );
+        Field::new(name, DataType::Map(Arc::new(entries), false), true)
+    }
+    let string = |name: &str| Field::new(name, DataType::Utf8, true);
+    let long = |name: &str| Field::new(name, DataType::Int64, true);
+    let int = |name: &str| Field::new(name, DataType::Int32, true);
+    let boolean = |name: &str| Field::new(name, DataType::Boolean, true);
+    let string_list = |name: &str| {
+        Field::new(
+            name,
+            DataType::List(Arc::new(Field::new("element", DataType::Utf8, true))),
+            true,
+        )
+    };
+
+    Schema::new(vec![
+        action(
+            "txn",
+            vec![string("appId"), long("version"), long("lastUpdated")],
+        ),
+        action(
+            "add",
+            vec![
+                string("path"),
+                string_map("partitionValues"),
+                long("size"),
+                long("modificationTime"),
+                boolean("dataChange"),
+                string("stats"),
+            ],
+        ),
+        action(
+            "remove",
+            vec![
+                string("path"),
+                long("deletionTimestamp"),
+                boolean("dataChange"),
+            ],
+        ),
+        action(
+            "metaData",
+            vec![
+                string("id"),
+                string("name"),
+                string("description"),
+                action("format", vec![string("provider"), string_map("options")]),
+                string("schemaString"),
+                string_list("partitionColumns"),
+                string_map("configuration"),
+                long("createdTime"),
+            ],
+        ),
+        action(
+            "protocol",
+            vec![int("minReaderVersion"), int("minWriterVersion")],
+        ),
+    ])
+}
+
+fn write_checkpoint(actions: &[Value]) -> Result<Vec<u8>> {
+    let schema = Arc::new(checkpoint_schema());
+    let mut decoder = ReaderBuilder::new(schema.clone())
+        .with_batch_size(CHECKPOINT_BATCH_SIZE)
+        .build_decoder()?;
+    let mut writer = ArrowWriter::try_new(vec![], schema, None)?;
+    for chunk in actions.chunks(CHECKPOINT_BATCH_SIZE) {
+        decoder.serialize(chunk)?;
+        if let Some(batch) = decoder.flush()? {
+            writer.write(&batch)?;
+        }
+    }
+    Ok(writer.into_inner()?)
+}
+
+fn read_checkpoint(bytes: Bytes) -> Result<Vec<Action>> {
+    let mut actions = vec![];
+    for batch in ParquetRecordBatchReaderBuilder::try_new(bytes)?.build()? {
+        // Null columns are left out, leaving a single action per line
+        let mut writer = LineDelimitedWriter::new(vec![]);
+        writer.write(&batch?)?;
+        writer.finish()?;
+        for line in writer.into_inner().split(|c| *c == b'\n') {
+            if !line.is_empty() {
+                actions.push(serde_json::from_slice(line)?);
+            }
+        }
+    }
+    Ok(actions)
+}
+
+/// The Delta schema of a table whose files have the given arrow schema, with the partition
+/// columns added if they are not stored in the files.
+fn delta_schema(schema: &Schema) -> Result<String> {
+    let mut fields = vec![];
+    for field in schema.fields() {
+        let data_type = match field.data_type() {
+            // Delta has no unsigned types, query engines read parquet uint64 as decimal(20,0)
+            DataType::UInt64 => "decimal(20,0)",
+            DataType::Int64 => "long",
+            DataType::Utf8 => "string",
+            DataType::Boolean => "boolean",
+            data_type => return Err(anyhow!("Unsupported column type {}", data_type)),
+        };
+        fields.push(json!({
+            "name": field.name(),
+            "type": data_type,
+            "nullable": field.is_nullable(),
+            "metadata": {},
+        }));
+    }
+    for (name, data_type) in [
+        (EPOCH_PARTITION_COLUMN, "long"),
+        (DATE_PARTITION_COLUMN, "date"),
+    ] {
+        if schema.field_with_name(name).is_err() {
+            fields.push(json!({
+                "name": name,
+                "type": data_type,
+                "nullable": true,
+                "metadata": {},
+            }));
+        }
+    }
+    Ok(json!({"type": "struct", "fields": fields}).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileFormat, FileType};
+    use arrow_array::{ArrayRef, RecordBatch, StringArray, UInt64Array};
+    use object_store::local::LocalFileSystem;
+    use parquet::arrow::ArrowWriter;
+    use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+
+    fn write_parquet(path: &std::path::Path, columns: Vec<(&str, ArrayRef)>) {
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        let mut writer =
+            ArrowWriter::try_new(std::fs::File::create(path).unwrap(), batch.schema(), None)
+                .unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    fn file_metadata(epoch_num: u64, checkpoint_seq_range: std::ops::Range<u64>) -> FileMetadata {
+        FileMetadata::new(
+            FileType::Checkpoint,
+            FileFormat::PARQUET,
+            epoch_num,
+            checkpoint_seq_range,
+            NaiveDate::from_ymd_opt(2024, 5, 1),
+        )
+    }
+
+    async fn read_actions(store: &DynObjectStore, version: u64) -> Vec<serde_json::Value> {
+        let path = DeltaTableLog::commit_path(&Path::from("checkpoints/_delta_log"), version);
+        let bytes = store.get(&path).await.unwrap().bytes().await.unwrap();
+        bytes
+            .split(|c| *c == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_commit_files_with_schema_evolution() {
+        let local_dir = tempfile::tempdir().unwrap();
+        let remote_dir = tempfile::tempdir().unwrap();
+        let store: Arc<DynObjectStore> =
+            Arc::new(LocalFileSystem::new_with_prefix(remote_dir.path()).unwrap());
+        let table_root = FileType::Checkpoint.dir_prefix();
+        let mut log = DeltaTableLog::open(store.clone(), table_root.clone())
+            .await
+            .unwrap();
+        assert_eq!(log.version(), None);
+
+        let first = local_dir.path().join("first.parquet");
+        write_parquet(
+            &first,
+            vec![
+                (
+                    "sequence_number",
+                    Arc::new(UInt64Array::from(vec![0, 1])) as ArrayRef,
+                ),
+                ("epoch", Arc::new(UInt64Array::from(vec![0, 0])) as ArrayRef),
+            ],
+        );
+        assert_eq!(
+            log.commit_file(&file_metadata(0, 0..2), &first)
+                .await
+                .unwrap(),
+            0
+        );
+        let actions = read_actions(store.as_ref(), 0).await;
+        assert!(actions[0].get("protocol").is_some());
+        let metadata = &actions[1]["metaData"];
+        assert_eq!(metadata["partitionColumns"], json!(["epoch", "date"]));
+        let schema: Value =
+            serde_json::from_str(metadata["schemaString"].as_str().unwrap()).unwrap();
+        assert_eq!(schema["fields"][0]["name"], "sequence_number");
+        assert_eq!(schema["fields"][0]["type"], "decimal(20,0)");
+        let add = &actions[2]["add"];
+        assert_eq!(add["path"], "epoch_0/0_2.parquet");
+        assert_eq!(
+            add["partitionValues"],
+            json!({"epoch": "0", "date": "2024-05-01"})
+        );
+
+        // Same schema, no new metadata
+        assert_eq!(
+            log.commit_file(&file_metadata(0, 2..4), &first)
+                .await
+                .unwrap(),
+            1
+        );
+        let actions = read_actions(store.as_ref(), 1).await;
+        assert!(actions
+            .iter()
+            .all(|action| action.get("metaData").is_none()));
+
+        // A new column is committed as a new schema, kept when the log is reopened
+        let second = local_dir.path().join("second.parquet");
+        write_parquet(
+            &second,
+            vec![
+                (
+                    "sequence_number",
+                    Arc::new(UInt64Array::from(vec![4])) as ArrayRef,
+                ),
+                ("epoch", Arc::new(UInt64Array::from(vec![1])) as ArrayRef),
+                (
+                    "digest",
+                    Arc::new(StringArray::from(vec!["abc"])) as ArrayRef,
+                ),
+            ],
+        );
+        let mut log = DeltaTableLog::open(store.clone(), table_root)
+            .await
+            .unwrap();
+        assert_eq!(log.version(), Some(1));
+        assert_eq!(
+            log.commit_file(&file_metadata(1, 4..5), &second)
+                .await
+                .unwrap(),
+            2
+        );
+        let actions = read_actions(store.as_ref(), 2).await;
+        let new_metadata = &actions[0]["metaData"];
+        assert_eq!(new_metadata["id"], metadata["id"]);
+        assert!(new_metadata["schemaString"]
+            .as_str()
+            .unwrap()
+            .contains("digest"));
+        assert_eq!(
+            log.metadata.as_ref().unwrap().schema_string,
+            new_metadata["schemaString"].as_str().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checkpoints() {
+        let local_dir = tempfile::tempdir().unwrap();
+        let remote_dir = tempfile::tempdir().unwrap();
+        let store: Arc<DynObjectStore> =
+            Arc::new(LocalFileSystem::new_with_prefix(remote_dir.path()).unwrap());
+        let table_root = FileType::Checkpoint.dir_prefix();
+        let log_dir = table_root.child(DELTA_LOG_DIR);
+        let mut log = DeltaTableLog::open(store.clone(), table_root.clone())
+            .await
+            .unwrap();
+
+        let file = local_dir.path().join("file.parquet");
+        write_parquet(
+            &file,
+            vec![(
+                "sequence_number",
+                Arc::new(UInt64Array::from(vec![0])) as ArrayRef,
+            )],
+        );
+        for version in 0..=2 * CHECKPOINT_INTERVAL {
+            log.commit_file(&file_metadata(0, version..version + 1), &file)
+                .await
+                .unwrap();
+        }
+
+        let last_checkpoint: LastCheckpoint = serde_json::from_slice(
+            &store
+                .get(&log_dir.child(LAST_CHECKPOINT_FILE))
+                .await
+                .unwrap()
+                .bytes()
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(last_checkpoint.version, 2 * CHECKPOINT_INTERVAL);
+        // protocol, metadata and one add action per file
+        assert_eq!(last_checkpoint.size, 2 * CHECKPOINT_INTERVAL + 3);
+
+        // The previous checkpoint and the commits following it are kept, older entries removed
+        let mut entries: Vec<_> = store
+            .list_with_delimiter(Some(&log_dir))
+            .await
+            .unwrap()
+            .objects
+            .into_iter()
+            .map(|object| object.location.filename().unwrap().to_string())
+            .collect();
+        entries.sort();
+        let mut expected = vec![
+            format!("{:020}.checkpoint.parquet", CHECKPOINT_INTERVAL),
+            format!("{:020}.checkpoint.parquet", 2 * CHECKPOINT_INTERVAL),
+        ];
+        expected.extend(
+            (CHECKPOINT_INTERVAL + 1..=2 * CHECKPOINT_INTERVAL)
+                .map(|version| format!("{:020}.json", version)),
+        );
+        expected.push(LAST_CHECKPOINT_FILE.to_string());
+        expected.sort();
+        assert_eq!(entries, expected);
+
+        // The state of the table is rebuilt from the checkpoint
+        let reopened = DeltaTableLog::open(store.clone(), table_root.clone())
+            .await
+            .unwrap();
+        assert_eq!(reopened.version(), Some(2 * CHECKPOINT_INTERVAL));
+        assert_eq!(reopened.last_checkpoint, Some(2 * CHECKPOINT_INTERVAL));
+        assert_eq!(reopened.metadata, log.metadata);
+        assert_eq!(reopened.files, log.files);
+
+        // and from the commits following it
+        log.commit_file(&file_metadata(0, 100..101), &file)
+            .await
+            .unwrap();
+        let reopened = DeltaTableLog::open(store, table_root).await.unwrap();
+        assert_eq!(reopened.version(), Some(2 * CHECKPOINT_INTERVAL + 1));
+        assert_eq!(reopened.files.len() as u64, 2 * CHECKPOINT_INTERVAL + 2);
+        assert_eq!(reopened.files, log.files);
+    }
+
+    #[tokio::test]
+    async fn test_recommit_file_uploaded_before_crash() {
+        let local_dir = tempfile::tempdir().unwrap();
+        let remote_dir = tempfile::tempdir().unwrap();
+        let store: Arc<DynObjectStore> =
+            Arc::new(LocalFileSystem::new_with_prefix(remote_dir.path()).unwrap());
+        let table_root = FileType::Checkpoint.dir_prefix();
+        let mut log = DeltaTableLog::open(store.clone(), table_root.clone())
+            .await
+            .unwrap();
+
+        let file = local_dir.path().join("file.parquet");
+        write_parquet(
+            &file,
+            vec![(
+                "sequence_number",
+                Arc::new(UInt64Array::from(vec![0])) as ArrayRef,
+            )],
+        );
+        let committed = file_metadata(0, 0..2);
+        store
+            .put(&committed.file_path(), std::fs::read(&file).unwrap().into())
+            .await
+            .unwrap();
+        log.commit_file(&committed, &file).await.unwrap();
+
+        // The next file is uploaded, and the indexer crashes before committing it
+        let uncommitted = file_metadata(0, 2..4);
+        store
+            .put(
+                &uncommitted.file_path(),
+                std::fs::read(&file).unwrap().into(),
+            )
+            .await
+            .unwrap();
+        let remote_store_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(remote_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        assert_eq!(
+            crate::read_store_for_checkpoint(remote_store_config, FileType::Checkpoint, None)
+                .await
+                .unwrap(),
+            4
+        );
+
+        // After a restart, indexing resumes from the last committed file instead
+        let mut log = DeltaTableLog::open(store.clone(), table_root.clone())
+            .await
+            .unwrap();
+        assert_eq!(log.next_checkpoint(), 2);
+        assert_eq!(log.commit_file(&uncommitted, &file).await.unwrap(), 1);
+        assert_eq!(log.next_checkpoint(), 4);
+
+        // Committing a file of the table again leaves the log unchanged
+        assert_eq!(log.commit_file(&uncommitted, &file).await.unwrap(), 1);
+        assert_eq!(log.commit_file(&committed, &file).await.unwrap(), 1);
+        let reopened = DeltaTableLog::open(store, table_root).await.unwrap();
+        assert_eq!(reopened.version(), Some(1));
+        assert_eq!(reopened.files.len(), 2);
+    }
+}
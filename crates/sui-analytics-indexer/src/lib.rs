@@ -6,6 +6,7 @@ use std::path::PathBuf;
 
 use anyhow::{anyhow, Result};
 use arrow_array::{Array, Int32Array};
+use chrono::NaiveDate;
 use clap::*;
 use gcp_bigquery_client::model::query_request::QueryRequest;
 use gcp_bigquery_client::Client;
@@ -29,6 +30,7 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::analytics_metrics::AnalyticsMetrics;
 use crate::analytics_processor::AnalyticsProcessor;
+use crate::delta_table::DeltaTableLog;
 use crate::handlers::checkpoint_handler::CheckpointHandler;
 use crate::handlers::df_handler::DynamicFieldHandler;
 use crate::handlers::event_handler::EventHandler;
@@ -50,6 +52,7 @@ use crate::writers::AnalyticsWriter;
 
 pub mod analytics_metrics;
 pub mod analytics_processor;
+pub mod delta_table;
 pub mod errors;
 mod handlers;
 mod package_store;
@@ -160,6 +163,9 @@ pub struct AnalyticsIndexerConfig {
     pub report_sf_max_table_checkpoint: bool,
     #[clap(long, default_value = None, global = true)]
     pub package_id_filter: Option<String>,
+    // Table format to maintain over the uploaded files i.e. plain files or a delta table
+    #[clap(long, value_enum, default_value = "files", global = true)]
+    pub table_format: TableFormat,
 }
 
 #[async_trait::async_trait]
@@ -305,6 +311,15 @@ impl FileFormat {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Serialize, Deserialize)]
+pub enum TableFormat {
+    /// Files partitioned by epoch, without any table metadata
+    Files,
+    /// Delta Lake table partitioned by epoch and date, files are committed to its transaction log
+    /// as they get uploaded. Requires the parquet file format.
+    Delta,
+}
+
 #[derive(
     Copy,
     Clone,
@@ -462,6 +477,8 @@ pub struct FileMetadata {
     pub file_format: FileFormat,
     pub epoch_num: u64,
     pub checkpoint_seq_range: Range<u64>,
+    /// UTC date of the first checkpoint in the file
+    pub date: Option<NaiveDate>,
 }
 
 impl FileMetadata {
@@ -470,12 +487,14 @@ impl FileMetadata {
         file_format: FileFormat,
         epoch_num: u64,
         checkpoint_seq_range: Range<u64>,
+        date: Option<NaiveDate>,
     ) -> FileMetadata {
         FileMetadata {
             file_type,
             file_format,
             epoch_num,
             checkpoint_seq_range,
+            date,
         }
     }
 
@@ -887,6 +906,14 @@ pub async fn get_starting_checkpoint_seq_num(
 ) -> Result<u64> {
     let checkpoint = if let Some(starting_checkpoint_seq_num) = config.starting_checkpoint_seq_num {
         starting_checkpoint_seq_num
+    } else if config.table_format == TableFormat::Delta {
+        // Files are only part of a delta table once committed to its log
+        DeltaTableLog::open(
+            config.remote_store_config.make()?,
+            join_paths(config.remote_store_path_prefix, &file_type.dir_prefix()),
+        )
+        .await?
+        .next_checkpoint()
     } else {
         read_store_for_checkpoint(
             config.remote_store_config.clone(),
@@ -902,6 +929,9 @@ pub async fn make_analytics_processor(
     config: AnalyticsIndexerConfig,
     metrics: AnalyticsMetrics,
 ) -> Result<Processor> {
+    if config.table_format == TableFormat::Delta && config.file_format != FileFormat::PARQUET {
+        return Err(anyhow!("Delta tables require the parquet file format"));
+    }
     match config.file_type {
         FileType::Checkpoint => make_checkpoint_processor(config, metrics).await,
         FileType::Object => make_object_processor(config, metrics).await,
@@ -28,7 +28,7 @@ use sui_types::messages_checkpoint::{
 use sui_types::storage::WriteStore;
 use tokio::sync::oneshot::Sender;
 use tokio::sync::{oneshot, Mutex};
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Debug)]
 pub struct ArchiveReaderMetrics {
@@ -91,6 +91,28 @@ impl ArchiveReaderBalancer {
         let checkpoints: Result<Vec<CheckpointSequenceNumber>> = checkpoints.into_iter().collect();
         checkpoints.map(|vec| vec.into_iter().min())
     }
+    /// Return the latest checkpoint available in any of the archives, if any has checkpoints
+    pub async fn latest_available_checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        let mut latest_checkpoint = None;
+        for reader in self.readers.iter() {
+            if let Ok(checkpoint) = reader.latest_available_checkpoint().await {
+                latest_checkpoint = latest_checkpoint.max(Some(checkpoint));
+            }
+        }
+        latest_checkpoint
+    }
+    /// Refresh the manifests of all archives, instead of waiting for their periodic sync
+    pub async fn sync_manifests(&self) {
+        for reader in self.readers.iter() {
+            if let Err(err) = reader.sync_manifest_once().await {
+                warn!(
+                    "Failed to sync manifest of remote store: {:?} with error: {:?}",
+                    reader.remote_store_identifier(),
+                    err
+                );
+            }
+        }
+    }
     pub async fn pick_one_random(
         &self,
         checkpoint_range: Range<CheckpointSequenceNumber>,
@@ -414,41 +436,11 @@ impl ArchiveReader {
     where
         S: WriteStore + Clone,
     {
-        let manifest = self.manifest.lock().await.clone();
-
-        let latest_available_checkpoint = manifest
-            .next_checkpoint_seq_num()
-            .checked_sub(1)
-            .context("Checkpoint seq num underflow")?;
-
-        if checkpoint_range.start > latest_available_checkpoint {
-            return Err(anyhow!(
-                "Latest available checkpoint is: {}",
-                latest_available_checkpoint
-            ));
-        }
-
-        let files: Vec<(FileMetadata, FileMetadata)> = self.verify_manifest(manifest).await?;
-
-        let start_index = match files.binary_search_by_key(&checkpoint_range.start, |(s, _c)| {
-            s.checkpoint_seq_range.start
-        }) {
-            Ok(index) => index,
-            Err(index) => index - 1,
-        };
-
-        let end_index = match files.binary_search_by_key(&checkpoint_range.end, |(s, _c)| {
-            s.checkpoint_seq_range.start
-        }) {
-            Ok(index) => index,
-            Err(index) => index,
-        };
+        let files = self.get_files_for_range(checkpoint_range.clone()).await?;
 
         let remote_object_store = self.remote_object_store.clone();
         futures::stream::iter(files.iter())
-            .enumerate()
-            .filter(|(index, (_s, _c))| future::ready(*index >= start_index && *index < end_index))
-            .map(|(_, (summary_metadata, content_metadata))| {
+            .map(|(summary_metadata, content_metadata)| {
                 let remote_object_store = remote_object_store.clone();
                 async move {
                     let summary_data =
@@ -515,6 +507,66 @@ impl ArchiveReader {
             .await
     }
 
+    /// Download summaries+contents of the checkpoints in the given range from archive, in order.
+    /// Neither summaries nor contents are verified. If latest available checkpoint in archive is
+    /// older than the end of the input range then only the checkpoints available are returned.
+    pub async fn get_checkpoints_for_range(
+        &self,
+        checkpoint_range: Range<CheckpointSequenceNumber>,
+    ) -> Result<Vec<(CertifiedCheckpointSummary, CheckpointContents)>> {
+        let files = self.get_files_for_range(checkpoint_range.clone()).await?;
+
+        let remote_object_store = self.remote_object_store.clone();
+        let checkpoints = futures::stream::iter(files.iter())
+            .map(|(summary_metadata, content_metadata)| {
+                let remote_object_store = remote_object_store.clone();
+                async move {
+                    let summary_data =
+                        get(&remote_object_store, &summary_metadata.file_path()).await?;
+                    let content_data =
+                        get(&remote_object_store, &content_metadata.file_path()).await?;
+                    Ok::<(Bytes, Bytes), anyhow::Error>((summary_data, content_data))
+                }
+            })
+            .boxed()
+            .buffered(self.concurrency)
+            .try_fold(vec![], |mut acc, (summary_data, content_data)| {
+                let result: Result<Vec<_>, anyhow::Error> =
+                    make_iterator::<CertifiedCheckpointSummary, Reader<Bytes>>(
+                        SUMMARY_FILE_MAGIC,
+                        summary_data.reader(),
+                    )
+                    .and_then(|s| {
+                        make_iterator::<CheckpointContents, Reader<Bytes>>(
+                            CHECKPOINT_FILE_MAGIC,
+                            content_data.reader(),
+                        )
+                        .map(|c| (s, c))
+                    })
+                    .map(|(summary_iter, content_iter)| {
+                        acc.extend(
+                            summary_iter
+                                .zip(content_iter)
+                                .filter(|(s, _c)| checkpoint_range.contains(&s.sequence_number)),
+                        );
+                        acc
+                    });
+                futures::future::ready(result)
+            })
+            .await?;
+
+        let num_txns: usize = checkpoints.iter().map(|(_s, c)| c.size()).sum();
+        self.archive_reader_metrics
+            .archive_txns_read
+            .with_label_values(&[&self.bucket])
+            .inc_by(num_txns as u64);
+        self.archive_reader_metrics
+            .archive_checkpoints_read
+            .with_label_values(&[&self.bucket])
+            .inc_by(checkpoints.len() as u64);
+        Ok(checkpoints)
+    }
+
     /// Return latest available checkpoint in archive
     pub async fn latest_available_checkpoint(&self) -> Result<CheckpointSequenceNumber> {
         let manifest = self.manifest.lock().await.clone();
@@ -608,6 +660,49 @@ impl ArchiveReader {
             .map_err(|e| anyhow!("Failed to get verified checkpoint: {:?}", e))
     }
 
+    /// Return the summary and content files holding the checkpoints in the given range
+    async fn get_files_for_range(
+        &self,
+        checkpoint_range: Range<CheckpointSequenceNumber>,
+    ) -> Result<Vec<(FileMetadata, FileMetadata)>> {
+        let manifest = self.manifest.lock().await.clone();
+
+        let latest_available_checkpoint = manifest
+            .next_checkpoint_seq_num()
+            .checked_sub(1)
+            .context("Checkpoint seq num underflow")?;
+
+        if checkpoint_range.start > latest_available_checkpoint {
+            return Err(anyhow!(
+                "Latest available checkpoint is: {}",
+                latest_available_checkpoint
+            ));
+        }
+
+        let files: Vec<(FileMetadata, FileMetadata)> = self.verify_manifest(manifest).await?;
+
+        let start_index = match files.binary_search_by_key(&checkpoint_range.start, |(s, _c)| {
+            s.checkpoint_seq_range.start
+        }) {
+            Ok(index) => index,
+            Err(index) => index - 1,
+        };
+
+        let end_index = match files.binary_search_by_key(&checkpoint_range.end, |(s, _c)| {
+            s.checkpoint_seq_range.start
+        }) {
+            Ok(index) => index,
+            Err(index) => index,
+        };
+
+        Ok(files
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| *index >= start_index && *index < end_index)
+            .map(|(_, files)| files)
+            .collect())
+    }
+
     async fn get_summary_files_for_range(
         &self,
         checkpoint_range: Range<CheckpointSequenceNumber>,
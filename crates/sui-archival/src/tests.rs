@@ -252,6 +252,55 @@ async fn test_archive_reader_e2e() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_checkpoints_for_range() -> Result<(), anyhow::Error> {
+    let test_store = SharedInMemoryStore::default();
+    let test_state = setup_test_state(temp_dir()).await?;
+    let kill = test_state.archive_writer.start(test_store.clone()).await?;
+    let mut prev_checkpoint = None;
+    let mut latest_archived_checkpoint_seq_num = 0;
+    while latest_archived_checkpoint_seq_num < 10 {
+        prev_checkpoint = insert_checkpoints_and_verify_manifest(
+            &test_state,
+            test_store.clone(),
+            prev_checkpoint,
+        )
+        .await?;
+        test_state.archive_reader.sync_manifest_once().await?;
+        latest_archived_checkpoint_seq_num = test_state
+            .archive_reader
+            .latest_available_checkpoint()
+            .await?;
+    }
+    let checkpoints = test_state
+        .archive_reader
+        .get_checkpoints_for_range(3..8)
+        .await?;
+    let sequence_numbers: Vec<_> = checkpoints
+        .iter()
+        .map(|(s, _c)| s.sequence_number)
+        .collect();
+    assert_eq!(sequence_numbers, (3..8).collect::<Vec<_>>());
+    for (summary, contents) in checkpoints {
+        let checkpoint = test_store
+            .get_checkpoint_by_sequence_number(summary.sequence_number)
+            .context("Missing checkpoint")?;
+        assert_eq!(summary.digest(), checkpoint.digest());
+        contents.verify_digests(summary.content_digest)?;
+    }
+
+    // Only the archived checkpoints are returned for a range past the end of the archive.
+    let checkpoints = test_state
+        .archive_reader
+        .get_checkpoints_for_range(
+            latest_archived_checkpoint_seq_num..(latest_archived_checkpoint_seq_num + 100),
+        )
+        .await?;
+    assert_eq!(checkpoints.len(), 1);
+    kill.send(())?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_verify_archive_with_oneshot_store() -> Result<(), anyhow::Error> {
     let test_store = SharedInMemoryStore::default();
@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use shared_crypto::intent::Intent;
 use shared_crypto::intent::IntentMessage;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -22,12 +23,15 @@ use sui_bridge::abi::{eth_sui_bridge, EthSuiBridge};
 use sui_bridge::crypto::BridgeAuthorityPublicKeyBytes;
 use sui_bridge::error::BridgeResult;
 use sui_bridge::sui_client::SuiBridgeClient;
-use sui_bridge::types::BridgeAction;
 use sui_bridge::types::{
     AddTokensOnEvmAction, AddTokensOnSuiAction, AssetPriceUpdateAction, BlocklistCommitteeAction,
     BlocklistType, EmergencyAction, EmergencyActionType, EvmContractUpgradeAction,
     LimitUpdateAction,
 };
+use sui_bridge::types::{
+    BridgeAction, BridgeAuthority, BridgeCommittee, BridgeCommitteeValiditySignInfo,
+    CertifiedBridgeAction, SignedBridgeAction, VerifiedCertifiedBridgeAction,
+};
 use sui_bridge::utils::{get_eth_signer_client, EthSigner};
 use sui_config::Config;
 use sui_json_rpc_types::SuiObjectDataOptions;
@@ -36,7 +40,8 @@ use sui_sdk::SuiClientBuilder;
 use sui_types::base_types::SuiAddress;
 use sui_types::base_types::{ObjectID, ObjectRef};
 use sui_types::bridge::{BridgeChainId, BRIDGE_MODULE_NAME};
-use sui_types::crypto::{Signature, SuiKeyPair};
+use sui_types::committee::StakeUnit;
+use sui_types::crypto::{Signature, SuiKeyPair, ToFromBytes};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::transaction::{ObjectArg, Transaction, TransactionData};
 use sui_types::{TypeTag, BRIDGE_PACKAGE_ID};
@@ -95,6 +100,49 @@ pub enum BridgeCommand {
         #[clap(long = "dry-run")]
         dry_run: bool,
     },
+    /// Write an unsigned governance action to a file, so that committee members can sign it
+    /// offline with `sign-governance-action`
+    #[clap(name = "export-governance-action")]
+    ExportGovernanceAction {
+        #[clap(long = "chain-id")]
+        chain_id: u8,
+        /// Path of the file to write the action to
+        #[clap(long = "output-path")]
+        output_path: PathBuf,
+        #[clap(subcommand)]
+        cmd: GovernanceClientCommands,
+    },
+    /// Sign a governance action exported by `export-governance-action` with a bridge validator key.
+    /// Does not require network access.
+    #[clap(name = "sign-governance-action")]
+    SignGovernanceAction {
+        /// Path of the exported action
+        #[clap(long = "action-path")]
+        action_path: PathBuf,
+        /// Path of the bridge validator key (Secp256k1)
+        #[clap(long = "bridge-key-path")]
+        bridge_key_path: PathBuf,
+        /// Path of the file to write the signature to
+        #[clap(long = "output-path")]
+        output_path: PathBuf,
+    },
+    /// Combine offline signatures of a governance action, check them against the current
+    /// committee and execute the action on chain
+    #[clap(name = "submit-governance-action")]
+    SubmitGovernanceAction {
+        /// Path of BridgeCliConfig
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+        /// Path of the exported action
+        #[clap(long = "action-path")]
+        action_path: PathBuf,
+        /// Paths of the signatures written by `sign-governance-action`
+        #[clap(long = "signature-paths", use_value_delimiter = true)]
+        signature_paths: Vec<PathBuf>,
+        /// If true, only check the signatures but not execute on chain
+        #[clap(long = "dry-run")]
+        dry_run: bool,
+    },
     /// View current status of Eth bridge
     #[clap(name = "view-eth-bridge")]
     ViewEthBridge {
@@ -121,6 +169,17 @@ pub enum BridgeCommand {
         #[clap(long, default_value = "false")]
         ping: bool,
     },
+    /// View pending actions, event cursors and signature collection progress of a bridge node
+    #[clap(name = "view-bridge-node-status")]
+    ViewBridgeNodeStatus {
+        /// Base url of the status api of the bridge node, which only listens on localhost,
+        /// e.g. http://127.0.0.1:9192
+        #[clap(long = "url")]
+        url: String,
+        /// Path of the file where the status api token of the node is stored
+        #[clap(long = "token-path")]
+        token_path: PathBuf,
+    },
     /// Client to facilitate and execute Bridge actions
     #[clap(name = "client")]
     Client {
@@ -358,21 +417,176 @@ fn encode_call_data(function_selector: &str, params: &[String]) -> Vec<u8> {
 
 pub fn select_contract_address(
     config: &LoadedBridgeCliConfig,
-    cmd: &GovernanceClientCommands,
+    action: &BridgeAction,
 ) -> EthAddress {
-    match cmd {
-        GovernanceClientCommands::EmergencyButton { .. } => config.eth_bridge_proxy_address,
-        GovernanceClientCommands::UpdateCommitteeBlocklist { .. } => {
-            config.eth_bridge_committee_proxy_address
+    match action {
+        BridgeAction::EmergencyAction(_) => config.eth_bridge_proxy_address,
+        BridgeAction::BlocklistCommitteeAction(_) => config.eth_bridge_committee_proxy_address,
+        BridgeAction::LimitUpdateAction(_) => config.eth_bridge_limiter_proxy_address,
+        BridgeAction::AssetPriceUpdateAction(_) => config.eth_bridge_config_proxy_address,
+        BridgeAction::EvmContractUpgradeAction(action) => action.proxy_address,
+        BridgeAction::AddTokensOnEvmAction(_) => config.eth_bridge_config_proxy_address,
+        BridgeAction::SuiToEthBridgeAction(_)
+        | BridgeAction::EthToSuiBridgeAction(_)
+        | BridgeAction::AddTokensOnSuiAction(_) => unreachable!(),
+    }
+}
+
+/// Check that `action` is a governance action that can be executed on the chain it targets.
+/// Token transfers are certified by the bridge nodes themselves and must never be signed or
+/// submitted offline.
+pub fn validate_governance_action(action: &BridgeAction) -> anyhow::Result<()> {
+    if !action.is_governace_action() {
+        return Err(anyhow!(
+            "{} is not a governance action",
+            action.action_type()
+        ));
+    }
+    let is_sui_chain = action.chain_id().is_sui_chain();
+    match action {
+        BridgeAction::AddTokensOnSuiAction(_) if !is_sui_chain => Err(anyhow!(
+            "{} must target a Sui chain, got {}",
+            action.action_type(),
+            action.chain_id()
+        )),
+        BridgeAction::AddTokensOnEvmAction(_) | BridgeAction::EvmContractUpgradeAction(_)
+            if is_sui_chain =>
+        {
+            Err(anyhow!(
+                "{} must target an Eth chain, got {}",
+                action.action_type(),
+                action.chain_id()
+            ))
         }
-        GovernanceClientCommands::UpdateLimit { .. } => config.eth_bridge_limiter_proxy_address,
-        GovernanceClientCommands::UpdateAssetPrice { .. } => config.eth_bridge_config_proxy_address,
-        GovernanceClientCommands::UpgradeEVMContract { proxy_address, .. } => *proxy_address,
-        GovernanceClientCommands::AddTokensOnSui { .. } => unreachable!(),
-        GovernanceClientCommands::AddTokensOnEvm { .. } => config.eth_bridge_config_proxy_address,
+        _ => Ok(()),
     }
 }
 
+/// Human-readable description of a governance action, for signers to review before signing.
+pub fn governance_action_summary(action: &BridgeAction) -> String {
+    let mut lines = vec![format!(
+        "{} #{} on {}",
+        action.action_type(),
+        action.seq_number(),
+        action.chain_id()
+    )];
+    match action {
+        BridgeAction::EmergencyAction(a) => {
+            lines.push(format!("  {:?} the bridge", a.action_type));
+        }
+        BridgeAction::BlocklistCommitteeAction(a) => {
+            lines.push(format!("  {:?} committee members:", a.blocklist_type));
+            for member in &a.members_to_update {
+                lines.push(format!(
+                    "    {} (eth address {:?})",
+                    Hex::encode(member.as_bytes()),
+                    member.to_eth_address()
+                ));
+            }
+        }
+        BridgeAction::LimitUpdateAction(a) => {
+            lines.push(format!(
+                "  Set the USD limit of transfers from {} to {}",
+                a.sending_chain_id, a.new_usd_limit
+            ));
+        }
+        BridgeAction::AssetPriceUpdateAction(a) => {
+            lines.push(format!(
+                "  Set the USD price of token {} to {}",
+                a.token_id, a.new_usd_price
+            ));
+        }
+        BridgeAction::AddTokensOnSuiAction(a) => {
+            lines.push(format!("  Add tokens (native: {}):", a.native));
+            for ((id, type_name), price) in a
+                .token_ids
+                .iter()
+                .zip(&a.token_type_names)
+                .zip(&a.token_prices)
+            {
+                lines.push(format!(
+                    "    token {}: {} at USD price {}",
+                    id, type_name, price
+                ));
+            }
+        }
+        BridgeAction::AddTokensOnEvmAction(a) => {
+            lines.push(format!("  Add tokens (native: {}):", a.native));
+            for (((id, address), price), decimals) in a
+                .token_ids
+                .iter()
+                .zip(&a.token_addresses)
+                .zip(&a.token_prices)
+                .zip(&a.token_sui_decimals)
+            {
+                lines.push(format!(
+                    "    token {}: {:?} with {} Sui decimals at USD price {}",
+                    id, address, decimals, price
+                ));
+            }
+        }
+        BridgeAction::EvmContractUpgradeAction(a) => {
+            lines.push(format!(
+                "  Upgrade proxy {:?} to implementation {:?}",
+                a.proxy_address, a.new_impl_address
+            ));
+            if !a.call_data.is_empty() {
+                lines.push(format!("  Call data: 0x{}", Hex::encode(&a.call_data)));
+            }
+        }
+        BridgeAction::SuiToEthBridgeAction(_) | BridgeAction::EthToSuiBridgeAction(_) => {
+            lines.push(format!("  {:?}", action));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Combine signatures of `action` collected offline into a certificate. Every signature must be
+/// over `action` and from an active member of `committee`, and together they must reach the
+/// approval threshold of the action.
+pub fn certify_action_with_signatures(
+    action: BridgeAction,
+    signatures: Vec<SignedBridgeAction>,
+    committee: &BridgeCommittee,
+) -> anyhow::Result<VerifiedCertifiedBridgeAction> {
+    let mut sigs = BTreeMap::new();
+    for signed_action in signatures {
+        let sig = signed_action.auth_sig();
+        let signer = sig.authority_pub_key_bytes();
+        if signed_action.data() != &action {
+            return Err(anyhow!(
+                "Signature of {:?} is over a different action: {:?}",
+                signer,
+                signed_action.data()
+            ));
+        }
+        sig.verify(&action, committee)
+            .map_err(|e| anyhow!("Invalid signature of {:?}: {:?}", signer, e))?;
+        sigs.insert(signer, sig.signature.clone());
+    }
+
+    let stake: StakeUnit = sigs
+        .keys()
+        .map(|signer| committee.member(signer).unwrap().voting_power)
+        .sum();
+    let threshold = action.approval_threshold();
+    if stake < threshold {
+        return Err(anyhow!(
+            "Signatures from {} authorities have total stake {}, below the threshold {}",
+            sigs.len(),
+            stake,
+            threshold
+        ));
+    }
+    // Every signature was verified above
+    Ok(VerifiedCertifiedBridgeAction::new_from_verified(
+        CertifiedBridgeAction::new_from_data_and_sig(
+            action,
+            BridgeCommitteeValiditySignInfo { signatures: sigs },
+        ),
+    ))
+}
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -488,6 +702,32 @@ impl LoadedBridgeCliConfig {
         &self.eth_signer
     }
 
+    /// The bridge committee as recorded in the BridgeCommittee contract on Eth, which verifies
+    /// signatures against its own stake and blocklist rather than the ones on Sui. Members of
+    /// `sui_committee` unknown to Eth are given no stake.
+    pub async fn get_eth_bridge_committee(
+        self: &LoadedBridgeCliConfig,
+        sui_committee: &BridgeCommittee,
+    ) -> anyhow::Result<BridgeCommittee> {
+        let eth_committee = EthBridgeCommittee::new(
+            self.eth_bridge_committee_proxy_address,
+            Arc::new(self.eth_signer.clone()),
+        );
+        let mut members = vec![];
+        for member in sui_committee.members().values() {
+            let eth_address = member.pubkey_bytes().to_eth_address();
+            let stake = eth_committee.committee_stake(eth_address).call().await?;
+            let is_blocklisted = eth_committee.blocklist(eth_address).call().await?;
+            members.push(BridgeAuthority {
+                voting_power: stake as u64,
+                is_blocklisted,
+                ..member.clone()
+            });
+        }
+        BridgeCommittee::new(members)
+            .map_err(|e| anyhow!("Invalid bridge committee on Eth: {:?}", e))
+    }
+
     pub async fn get_sui_account_info(
         self: &LoadedBridgeCliConfig,
     ) -> anyhow::Result<(SuiKeyPair, SuiAddress, ObjectRef)> {
@@ -732,9 +972,119 @@ async fn claim_on_eth(
 #[cfg(test)]
 mod tests {
     use ethers::abi::FunctionExt;
+    use sui_bridge::crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo};
+    use sui_bridge::test_utils::get_test_authority_and_key;
 
     use super::*;
 
+    #[test]
+    fn test_certify_action_with_signatures() {
+        let (authority1, _, key1) = get_test_authority_and_key(5000, 0);
+        let (authority2, _, key2) = get_test_authority_and_key(2000, 0);
+        let (authority3, _, key3) = get_test_authority_and_key(3000, 0);
+        let (_, _, outsider_key) = get_test_authority_and_key(3000, 0);
+        let committee = BridgeCommittee::new(vec![authority1, authority2, authority3]).unwrap();
+        let action = BridgeAction::EmergencyAction(EmergencyAction {
+            nonce: 1,
+            chain_id: BridgeChainId::SuiCustom,
+            action_type: EmergencyActionType::Pause,
+        });
+        let sign = |action: &BridgeAction, key: &BridgeAuthorityKeyPair| {
+            SignedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                BridgeAuthoritySignInfo::new(action, key),
+            )
+        };
+
+        // Pausing needs 450 stake, the signature of any member is enough
+        let certificate =
+            certify_action_with_signatures(action.clone(), vec![sign(&action, &key2)], &committee)
+                .unwrap();
+        assert_eq!(certificate.data(), &action);
+        assert_eq!(certificate.auth_sig().signatures.len(), 1);
+
+        // A signature from outside the committee is rejected
+        certify_action_with_signatures(
+            action.clone(),
+            vec![sign(&action, &key1), sign(&action, &outsider_key)],
+            &committee,
+        )
+        .unwrap_err();
+
+        // A signature over a different action is rejected
+        let other_action = BridgeAction::EmergencyAction(EmergencyAction {
+            nonce: 2,
+            chain_id: BridgeChainId::SuiCustom,
+            action_type: EmergencyActionType::Pause,
+        });
+        certify_action_with_signatures(
+            action.clone(),
+            vec![sign(&action, &key1), sign(&other_action, &key2)],
+            &committee,
+        )
+        .unwrap_err();
+
+        // Unpausing needs 5001 stake, duplicated signatures are only counted once
+        let action = BridgeAction::EmergencyAction(EmergencyAction {
+            nonce: 1,
+            chain_id: BridgeChainId::SuiCustom,
+            action_type: EmergencyActionType::Unpause,
+        });
+        certify_action_with_signatures(
+            action.clone(),
+            vec![sign(&action, &key1), sign(&action, &key1)],
+            &committee,
+        )
+        .unwrap_err();
+        let certificate = certify_action_with_signatures(
+            action.clone(),
+            vec![sign(&action, &key1), sign(&action, &key3)],
+            &committee,
+        )
+        .unwrap();
+        assert_eq!(certificate.auth_sig().signatures.len(), 2);
+    }
+
+    #[test]
+    fn test_validate_governance_action() {
+        let pause = BridgeAction::EmergencyAction(EmergencyAction {
+            nonce: 3,
+            chain_id: BridgeChainId::EthSepolia,
+            action_type: EmergencyActionType::Pause,
+        });
+        validate_governance_action(&pause).unwrap();
+        assert_eq!(
+            governance_action_summary(&pause),
+            "EmergencyButton #3 on EthSepolia\n  Pause the bridge"
+        );
+
+        // Token transfers are never signed offline
+        let transfer = sui_bridge::test_utils::get_test_sui_to_eth_bridge_action(
+            None, None, None, None, None, None, None,
+        );
+        let err = validate_governance_action(&transfer).unwrap_err();
+        assert!(err.to_string().contains("not a governance action"));
+
+        // Actions are rejected on chains that cannot execute them
+        let add_tokens_on_sui = BridgeAction::AddTokensOnSuiAction(AddTokensOnSuiAction {
+            nonce: 0,
+            chain_id: BridgeChainId::EthSepolia,
+            native: false,
+            token_ids: vec![],
+            token_type_names: vec![],
+            token_prices: vec![],
+        });
+        validate_governance_action(&add_tokens_on_sui).unwrap_err();
+        let upgrade = BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
+            nonce: 0,
+            chain_id: BridgeChainId::SuiTestnet,
+            proxy_address: EthAddress::random(),
+            new_impl_address: EthAddress::random(),
+            call_data: vec![],
+        });
+        validate_governance_action(&upgrade).unwrap_err();
+    }
+
     #[tokio::test]
     async fn test_encode_call_data() {
         let abi_json =
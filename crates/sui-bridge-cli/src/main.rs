@@ -14,22 +14,30 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use sui_bridge::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
-use sui_bridge::crypto::{BridgeAuthorityPublicKey, BridgeAuthorityPublicKeyBytes};
+use sui_bridge::crypto::{
+    BridgeAuthorityPublicKey, BridgeAuthorityPublicKeyBytes, BridgeAuthoritySignInfo,
+};
 use sui_bridge::eth_transaction_builder::build_eth_transaction;
 use sui_bridge::metrics::BridgeMetrics;
+use sui_bridge::server::STATUS_PATH;
+use sui_bridge::status::BridgeNodeStatus;
 use sui_bridge::sui_client::SuiClient;
 use sui_bridge::sui_transaction_builder::build_sui_transaction;
-use sui_bridge::types::BridgeActionType;
+use sui_bridge::types::{
+    BridgeAction, BridgeActionType, SignedBridgeAction, VerifiedCertifiedBridgeAction,
+};
 use sui_bridge::utils::{
     examine_key, generate_bridge_authority_key_and_write_to_file,
     generate_bridge_client_key_and_write_to_file, generate_bridge_node_config_and_write_to_file,
 };
 use sui_bridge::utils::{get_eth_contracts, EthBridgeContracts};
 use sui_bridge_cli::{
-    make_action, select_contract_address, Args, BridgeCliConfig, BridgeCommand,
+    certify_action_with_signatures, governance_action_summary, make_action,
+    select_contract_address, validate_governance_action, Args, BridgeCliConfig, BridgeCommand,
     LoadedBridgeCliConfig, Network, SEPOLIA_BRIDGE_PROXY_ADDR,
 };
 use sui_config::Config;
+use sui_keys::keypair_file::read_key;
 use sui_sdk::SuiClient as SuiSdkClient;
 use sui_sdk::SuiClientBuilder;
 use sui_types::base_types::SuiAddress;
@@ -38,6 +46,7 @@ use sui_types::bridge::{MoveTypeCommitteeMember, MoveTypeCommitteeMemberRegistra
 use sui_types::committee::TOTAL_VOTING_POWER;
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::crypto::Signature;
+use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::ToFromBytes;
 use sui_types::transaction::Transaction;
 
@@ -86,10 +95,6 @@ async fn main() -> anyhow::Result<()> {
             let sui_bridge_client =
                 SuiClient::<SuiSdkClient>::new(&config.sui_rpc_url, metrics.clone()).await?;
 
-            let (sui_key, sui_address, gas_object_ref) = config
-                .get_sui_account_info()
-                .await
-                .expect("Failed to get sui account info");
             let bridge_summary = sui_bridge_client
                 .get_bridge_summary()
                 .await
@@ -125,49 +130,16 @@ async fn main() -> anyhow::Result<()> {
                     println!("Dryrun succeeded.");
                     return Ok(());
                 }
-                let bridge_arg = sui_bridge_client
-                    .get_mutable_bridge_object_arg_must_succeed()
+                execute_certified_action_on_sui(&config, &sui_bridge_client, certified_action)
                     .await;
-                let rgp = sui_bridge_client
-                    .get_reference_gas_price_until_success()
-                    .await;
-                let id_token_map = sui_bridge_client.get_token_id_map().await.unwrap();
-                let tx = build_sui_transaction(
-                    sui_address,
-                    &gas_object_ref,
-                    certified_action,
-                    bridge_arg,
-                    &id_token_map,
-                    rgp,
-                )
-                .expect("Failed to build sui transaction");
-                let sui_sig = Signature::new_secure(
-                    &IntentMessage::new(Intent::sui_transaction(), tx.clone()),
-                    &sui_key,
-                );
-                let tx = Transaction::from_data(tx, vec![sui_sig]);
-                let resp = sui_bridge_client
-                    .execute_transaction_block_with_effects(tx)
-                    .await
-                    .expect("Failed to execute transaction block with effects");
-                if resp.status_ok().unwrap() {
-                    println!("Sui Transaction succeeded: {:?}", resp.digest);
-                } else {
-                    println!(
-                        "Sui Transaction failed: {:?}. Effects: {:?}",
-                        resp.digest, resp.effects
-                    );
-                }
                 return Ok(());
             }
 
             // Handle eth side
             // TODO assert chain id returned from rpc matches chain_id
-            let eth_signer_client = config.eth_signer();
             // Create BridgeAction
             let eth_action = make_action(chain_id, &cmd);
             println!("Action to execute on Eth: {:?}", eth_action);
-            // Create Eth Signer Client
             // TODO if a validator is blocklisted on eth, ignore their signatures?
             let certified_action = agg
                 .request_committee_signatures(eth_action)
@@ -177,26 +149,107 @@ async fn main() -> anyhow::Result<()> {
                 println!("Dryrun succeeded.");
                 return Ok(());
             }
-            let contract_address = select_contract_address(&config, &cmd);
-            let tx = build_eth_transaction(
-                contract_address,
-                eth_signer_client.clone(),
-                certified_action,
-            )
-            .await
-            .expect("Failed to build eth transaction");
-            println!("sending Eth tx: {:?}", tx);
-            match tx.send().await {
-                Ok(tx_hash) => {
-                    println!("Transaction sent with hash: {:?}", tx_hash);
-                }
-                Err(err) => {
-                    let revert = err.as_revert();
-                    println!("Transaction reverted: {:?}", revert);
-                }
+            execute_certified_action_on_eth(&config, certified_action).await;
+            return Ok(());
+        }
+
+        BridgeCommand::ExportGovernanceAction {
+            chain_id,
+            output_path,
+            cmd,
+        } => {
+            let chain_id = BridgeChainId::try_from(chain_id).expect("Invalid chain id");
+            let action = make_action(chain_id, &cmd);
+            std::fs::write(&output_path, serde_json::to_string_pretty(&action)?)?;
+            println!("Action:\n{}", governance_action_summary(&action));
+            println!("Action exported to {}", output_path.display());
+        }
+
+        BridgeCommand::SignGovernanceAction {
+            action_path,
+            bridge_key_path,
+            output_path,
+        } => {
+            let action: BridgeAction =
+                serde_json::from_str(&std::fs::read_to_string(&action_path)?)?;
+            validate_governance_action(&action)?;
+            let SuiKeyPair::Secp256k1(key) = read_key(&bridge_key_path, true)? else {
+                unreachable!("we required secp256k1 key in `read_key`");
             };
+            println!("Action to sign:\n{}", governance_action_summary(&action));
+            let signed_action = SignedBridgeAction::new_from_data_and_sig(
+                action.clone(),
+                BridgeAuthoritySignInfo::new(&action, &key),
+            );
+            std::fs::write(&output_path, serde_json::to_string_pretty(&signed_action)?)?;
+            println!(
+                "Signature of {:?} written to {}",
+                BridgeAuthorityPublicKeyBytes::from(&key.public),
+                output_path.display()
+            );
+        }
 
-            return Ok(());
+        BridgeCommand::SubmitGovernanceAction {
+            config_path,
+            action_path,
+            signature_paths,
+            dry_run,
+        } => {
+            let config = BridgeCliConfig::load(config_path).expect("Couldn't load BridgeCliConfig");
+            let config = LoadedBridgeCliConfig::load(config).await?;
+            let metrics = Arc::new(BridgeMetrics::new_for_testing());
+            let sui_bridge_client =
+                SuiClient::<SuiSdkClient>::new(&config.sui_rpc_url, metrics).await?;
+            let bridge_committee = sui_bridge_client
+                .get_bridge_committee()
+                .await
+                .expect("Failed to get bridge committee");
+
+            let action: BridgeAction =
+                serde_json::from_str(&std::fs::read_to_string(&action_path)?)?;
+            validate_governance_action(&action)?;
+            // Eth verifies signatures against its own copy of the committee
+            let committee = if action.chain_id().is_sui_chain() {
+                bridge_committee
+            } else {
+                config.get_eth_bridge_committee(&bridge_committee).await?
+            };
+            let signatures = signature_paths
+                .iter()
+                .map(|path| {
+                    let signed_action: SignedBridgeAction =
+                        serde_json::from_str(&std::fs::read_to_string(path)?)?;
+                    Ok(signed_action)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let certified_action = certify_action_with_signatures(action, signatures, &committee)?;
+            println!(
+                "Certified action with signatures from {} authorities:\n{}",
+                certified_action.auth_sig().signatures.len(),
+                governance_action_summary(certified_action.data())
+            );
+            if dry_run {
+                println!("Dryrun succeeded.");
+                return Ok(());
+            }
+            if certified_action.data().chain_id().is_sui_chain() {
+                let bridge_summary = sui_bridge_client
+                    .get_bridge_summary()
+                    .await
+                    .expect("Failed to get bridge summary");
+                let sui_chain_id = BridgeChainId::try_from(bridge_summary.chain_id).unwrap();
+                assert_eq!(
+                    sui_chain_id,
+                    certified_action.data().chain_id(),
+                    "Chain ID mismatch, expected: {:?}, got from url: {:?}",
+                    certified_action.data().chain_id(),
+                    sui_chain_id
+                );
+                execute_certified_action_on_sui(&config, &sui_bridge_client, certified_action)
+                    .await;
+            } else {
+                execute_certified_action_on_eth(&config, certified_action).await;
+            }
         }
 
         BridgeCommand::ViewEthBridge {
@@ -512,6 +565,20 @@ async fn main() -> anyhow::Result<()> {
             output_wrapper.inner = output;
             println!("{}", serde_json::to_string_pretty(&output_wrapper).unwrap());
         }
+        BridgeCommand::ViewBridgeNodeStatus { url, token_path } => {
+            let token = std::fs::read_to_string(&token_path)?;
+            let status: BridgeNodeStatus = reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()?
+                .get(format!("{}{}", url.trim_end_matches('/'), STATUS_PATH))
+                .bearer_auth(token.trim())
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            print!("{}", status);
+        }
         BridgeCommand::Client { config_path, cmd } => {
             let config = BridgeCliConfig::load(config_path).expect("Couldn't load BridgeCliConfig");
             let config = LoadedBridgeCliConfig::load(config).await?;
@@ -595,3 +662,72 @@ struct OutputSuiBridgeRegistration {
     total_registered_stake: f32,
     committee: Vec<OutputMember>,
 }
+
+async fn execute_certified_action_on_sui(
+    config: &LoadedBridgeCliConfig,
+    sui_bridge_client: &SuiClient<SuiSdkClient>,
+    certified_action: VerifiedCertifiedBridgeAction,
+) {
+    let (sui_key, sui_address, gas_object_ref) = config
+        .get_sui_account_info()
+        .await
+        .expect("Failed to get sui account info");
+    let bridge_arg = sui_bridge_client
+        .get_mutable_bridge_object_arg_must_succeed()
+        .await;
+    let rgp = sui_bridge_client
+        .get_reference_gas_price_until_success()
+        .await;
+    let id_token_map = sui_bridge_client.get_token_id_map().await.unwrap();
+    let tx = build_sui_transaction(
+        sui_address,
+        &gas_object_ref,
+        certified_action,
+        bridge_arg,
+        &id_token_map,
+        rgp,
+    )
+    .expect("Failed to build sui transaction");
+    let sui_sig = Signature::new_secure(
+        &IntentMessage::new(Intent::sui_transaction(), tx.clone()),
+        &sui_key,
+    );
+    let tx = Transaction::from_data(tx, vec![sui_sig]);
+    let resp = sui_bridge_client
+        .execute_transaction_block_with_effects(tx)
+        .await
+        .expect("Failed to execute transaction block with effects");
+    if resp.status_ok().unwrap() {
+        println!("Sui Transaction succeeded: {:?}", resp.digest);
+    } else {
+        println!(
+            "Sui Transaction failed: {:?}. Effects: {:?}",
+            resp.digest, resp.effects
+        );
+    }
+}
+
+async fn execute_certified_action_on_eth(
+    config: &LoadedBridgeCliConfig,
+    certified_action: VerifiedCertifiedBridgeAction,
+) {
+    let eth_signer_client = config.eth_signer();
+    let contract_address = select_contract_address(config, certified_action.data());
+    let tx = build_eth_transaction(
+        contract_address,
+        eth_signer_client.clone(),
+        certified_action,
+    )
+    .await
+    .expect("Failed to build eth transaction");
+    println!("sending Eth tx: {:?}", tx);
+    match tx.send().await {
+        Ok(tx_hash) => {
+            println!("Transaction sent with hash: {:?}", tx_hash);
+        }
+        Err(err) => {
+            let revert = err.as_revert();
+            println!("Transaction reverted: {:?}", revert);
+        }
+    };
+}
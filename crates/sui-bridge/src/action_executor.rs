@@ -31,6 +31,7 @@ use crate::metrics::BridgeMetrics;
 use crate::{
     client::bridge_authority_aggregator::BridgeAuthorityAggregator,
     error::BridgeError,
+    status::ActionProgressTracker,
     storage::BridgeOrchestratorTables,
     sui_client::{SuiClient, SuiClientInner},
     sui_transaction_builder::build_sui_transaction,
@@ -80,6 +81,7 @@ pub struct BridgeActionExecutor<C> {
     bridge_object_arg: ObjectArg,
     sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
     bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+    action_progress: Arc<ActionProgressTracker>,
     metrics: Arc<BridgeMetrics>,
 }
 
@@ -111,6 +113,7 @@ where
         gas_object_id: ObjectID,
         sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
         bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+        action_progress: Arc<ActionProgressTracker>,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         let bridge_object_arg = sui_client
@@ -126,6 +129,7 @@ where
             bridge_object_arg,
             sui_token_type_tags,
             bridge_pause_rx,
+            action_progress,
             metrics,
         }
     }
@@ -168,6 +172,7 @@ where
                 sender_clone,
                 receiver,
                 execution_tx_clone,
+                self.action_progress.clone(),
                 metrics,
             )
         ));
@@ -186,6 +191,7 @@ where
                 self.bridge_object_arg,
                 self.sui_token_type_tags,
                 self.bridge_pause_rx,
+                self.action_progress,
                 metrics,
             )
         ));
@@ -203,6 +209,7 @@ where
         execution_queue_sender: mysten_metrics::metered_channel::Sender<
             CertifiedBridgeActionExecutionWrapper,
         >,
+        action_progress: Arc<ActionProgressTracker>,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting run_signature_aggregation_loop");
@@ -216,6 +223,7 @@ where
                 &sui_client,
                 &store,
                 action,
+                &action_progress,
                 &metrics,
             )
             .await;
@@ -245,6 +253,7 @@ where
         sui_client: &Arc<SuiClient<C>>,
         store: &Arc<BridgeOrchestratorTables>,
         action: BridgeActionExecutionWrapper,
+        action_progress: &Arc<ActionProgressTracker>,
         metrics: &Arc<BridgeMetrics>,
     ) {
         metrics.action_executor_signing_queue_received_actions.inc();
@@ -267,6 +276,7 @@ where
         let execution_queue_sender_clone = execution_queue_sender.clone();
         let sui_client_clone = sui_client.clone();
         let store_clone = store.clone();
+        let action_progress_clone = action_progress.clone();
         let metrics_clone = metrics.clone();
         let semaphore_clone = semaphore.clone();
         spawn_logged_monitored_task!(
@@ -278,6 +288,7 @@ where
                 store_clone,
                 signing_queue_sender_clone,
                 execution_queue_sender_clone,
+                action_progress_clone,
                 metrics_clone,
             )
             .instrument(tracing::debug_span!("request_signatures", action_key=?action_key)),
@@ -292,6 +303,7 @@ where
         sui_client: &Arc<SuiClient<C>>,
         action: &BridgeAction,
         store: &Arc<BridgeOrchestratorTables>,
+        action_progress: &Arc<ActionProgressTracker>,
         metrics: &Arc<BridgeMetrics>,
     ) -> bool {
         let status = sui_client
@@ -312,6 +324,7 @@ where
                     .unwrap_or_else(|e| {
                         panic!("Write to DB should not fail: {:?}", e);
                     });
+                action_progress.remove(&action.digest());
                 true
             }
             // Although theoretically a legit SuiToEthBridgeAction should not have
@@ -332,6 +345,7 @@ where
         execution_queue_sender: mysten_metrics::metered_channel::Sender<
             CertifiedBridgeActionExecutionWrapper,
         >,
+        action_progress: Arc<ActionProgressTracker>,
         metrics: Arc<BridgeMetrics>,
     ) {
         let _permit = semaphore
@@ -352,17 +366,23 @@ where
             &sui_client,
             &action,
             &store,
+            &action_progress,
             &metrics,
         )
         .await
         {
             return;
         }
-        match auth_agg
+        let (result, report) = auth_agg
             .load()
-            .request_committee_signatures(action.clone())
-            .await
-        {
+            .request_committee_signatures_with_report(action.clone())
+            .await;
+        action_progress.record_signing_attempt(
+            action.digest(),
+            report,
+            result.as_ref().err().map(|e| format!("{:?}", e)),
+        );
+        match result {
             Ok(certificate) => {
                 info!("Sending certificate to execution");
                 execution_queue_sender
@@ -410,6 +430,7 @@ where
         bridge_object_arg: ObjectArg,
         sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
         bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+        action_progress: Arc<ActionProgressTracker>,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting run_onchain_execution_loop");
@@ -434,6 +455,7 @@ where
                 &execution_queue_sender,
                 &bridge_object_arg,
                 &sui_token_type_tags,
+                &action_progress,
                 &metrics,
             )
             .await;
@@ -454,6 +476,7 @@ where
         >,
         bridge_object_arg: &ObjectArg,
         sui_token_type_tags: &ArcSwap<HashMap<u8, TypeTag>>,
+        action_progress: &Arc<ActionProgressTracker>,
         metrics: &Arc<BridgeMetrics>,
     ) {
         metrics
@@ -474,7 +497,11 @@ where
 
         // Check once: if the action is already processed, skip it.
         if Self::handle_already_processed_token_transfer_action_maybe(
-            sui_client, action, store, metrics,
+            sui_client,
+            action,
+            store,
+            action_progress,
+            metrics,
         )
         .await
         {
@@ -513,7 +540,11 @@ where
 
         // Check twice: If the action is already processed, skip it.
         if Self::handle_already_processed_token_transfer_action_maybe(
-            sui_client, action, store, metrics,
+            sui_client,
+            action,
+            store,
+            action_progress,
+            metrics,
         )
         .await
         {
@@ -527,11 +558,21 @@ where
             .await
         {
             Ok(resp) => {
-                Self::handle_execution_effects(tx_digest, resp, store, action, metrics).await
+                Self::handle_execution_effects(
+                    tx_digest,
+                    resp,
+                    store,
+                    action,
+                    action_progress,
+                    metrics,
+                )
+                .await
             }
 
             // If the transaction did not go through, retry up to a certain times.
             Err(err) => {
+                action_progress
+                    .record_execution_attempt(action.digest(), Some(format!("{:?}", err)));
                 error!(
                     ?action_key,
                     ?tx_digest,
@@ -572,6 +613,7 @@ where
         response: SuiTransactionBlockResponse,
         store: &Arc<BridgeOrchestratorTables>,
         action: &BridgeAction,
+        action_progress: &Arc<ActionProgressTracker>,
         metrics: &Arc<BridgeMetrics>,
     ) {
         let effects = response
@@ -599,7 +641,9 @@ where
                     .remove_pending_actions(&[action.digest()])
                     .unwrap_or_else(|e| {
                         panic!("Write to DB should not fail: {:?}", e);
-                    })
+                    });
+                // The action left the pending log, so it no longer shows up in the node status
+                action_progress.remove(&action.digest());
             }
             SuiExecutionStatus::Failure { error } => {
                 // In practice the transaction could fail because of running out of gas, but really
@@ -609,6 +653,10 @@ where
                 // After human examination, the node should be restarted and fetch them from WAL.
 
                 metrics.err_sui_transaction_execution.inc();
+                action_progress.record_execution_attempt(
+                    action.digest(),
+                    Some(format!("{:?} failed with error: {:?}", tx_digest, error)),
+                );
                 error!(?tx_digest, "Manual intervention is needed. Sui transaction executed and failed with error: {error:?}");
             }
         }
@@ -1529,6 +1577,7 @@ mod tests {
             gas_object_ref.0,
             sui_token_type_tags.clone(),
             bridge_pause_rx,
+            Arc::new(ActionProgressTracker::new()),
             metrics,
         )
         .await;
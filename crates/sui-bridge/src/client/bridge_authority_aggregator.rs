@@ -13,10 +13,11 @@ use crate::types::{
     BridgeAction, BridgeCommittee, CertifiedBridgeAction, VerifiedCertifiedBridgeAction,
     VerifiedSignedBridgeAction,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use sui_authority_aggregation::ReduceOutput;
 use sui_authority_aggregation::{quorum_map_then_reduce_with_timeout_and_prefs, SigRequestPrefs};
@@ -88,23 +89,65 @@ impl BridgeAuthorityAggregator {
         &self,
         action: BridgeAction,
     ) -> BridgeResult<VerifiedCertifiedBridgeAction> {
+        self.request_committee_signatures_with_report(action)
+            .await
+            .0
+    }
+
+    /// Same as `request_committee_signatures`, but also reports which authorities signed the
+    /// action and which failed to, whether or not enough signatures were collected.
+    pub async fn request_committee_signatures_with_report(
+        &self,
+        action: BridgeAction,
+    ) -> (
+        BridgeResult<VerifiedCertifiedBridgeAction>,
+        SignatureCollectionReport,
+    ) {
         let state = GetSigsState::new(
             action.approval_threshold(),
             self.committee.clone(),
             self.metrics.clone(),
             self.committee_keys_to_names.clone(),
         );
-        request_sign_bridge_action_into_certification(
+        let report = state.report.clone();
+        let result = request_sign_bridge_action_into_certification(
             action,
             self.committee.clone(),
             self.clients.clone(),
             state,
             Duration::from_millis(PREFETCH_TIMEOUT_MS),
         )
-        .await
+        .await;
+        let report = report.lock().unwrap().clone();
+        (result, report)
     }
 }
 
+/// The responses of committee members to one round of signature collection for an action.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignatureCollectionReport {
+    pub validity_threshold: StakeUnit,
+    /// Authorities whose valid signature was collected
+    pub signed: Vec<AuthorityResponse>,
+    /// Authorities that returned an error or an invalid signature
+    pub failed: Vec<AuthorityResponse>,
+}
+
+impl SignatureCollectionReport {
+    pub fn signed_stake(&self) -> StakeUnit {
+        self.signed.iter().map(|response| response.stake).sum()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuthorityResponse {
+    pub authority: BridgeAuthorityPublicKeyBytes,
+    /// Validator name of the authority, when known
+    pub name: Option<String>,
+    pub stake: StakeUnit,
+    pub error: Option<String>,
+}
+
 #[derive(Debug)]
 struct GetSigsState {
     total_bad_stake: StakeUnit,
@@ -114,6 +157,8 @@ struct GetSigsState {
     committee: Arc<BridgeCommittee>,
     metrics: Arc<BridgeMetrics>,
     committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    /// Shared with the caller, as the state itself is not handed back once a certificate is formed
+    report: Arc<Mutex<SignatureCollectionReport>>,
 }
 
 impl GetSigsState {
@@ -131,6 +176,30 @@ impl GetSigsState {
             validity_threshold,
             metrics,
             committee_keys_to_names,
+            report: Arc::new(Mutex::new(SignatureCollectionReport {
+                validity_threshold,
+                ..Default::default()
+            })),
+        }
+    }
+
+    fn record_response(
+        &self,
+        name: &BridgeAuthorityPublicKeyBytes,
+        stake: StakeUnit,
+        error: Option<String>,
+    ) {
+        let response = AuthorityResponse {
+            authority: name.clone(),
+            name: self.committee_keys_to_names.get(name).cloned(),
+            stake,
+            error,
+        };
+        let mut report = self.report.lock().unwrap();
+        if response.error.is_none() {
+            report.signed.push(response);
+        } else {
+            report.failed.push(response);
         }
     }
 
@@ -153,6 +222,7 @@ impl GetSigsState {
             Entry::Vacant(e) => {
                 e.insert(signed_action.auth_sig().clone());
                 self.add_ok_stake(stake, &name);
+                self.record_response(&name, stake, None);
             }
             Entry::Occupied(_e) => {
                 return Err(BridgeError::AuthoritySignatureDuplication(format!(
@@ -294,6 +364,7 @@ async fn request_sign_bridge_action_into_certification(
                                     e
                                 );
                                 state.add_bad_stake(stake, &name);
+                                state.record_response(&name, stake, Some(format!("{e:?}")));
                             }
                         }
                     }
@@ -304,6 +375,7 @@ async fn request_sign_bridge_action_into_certification(
                             e
                         );
                         state.add_bad_stake(stake, &name);
+                        state.record_response(&name, stake, Some(format!("{e:?}")));
                     }
                 };
 
@@ -419,7 +491,7 @@ mod tests {
             vec![mock0.clone(), mock1.clone(), mock2.clone(), mock3.clone()],
         );
 
-        let committee = BridgeCommittee::new(authorities).unwrap();
+        let committee = BridgeCommittee::new(authorities.clone()).unwrap();
 
         let agg = BridgeAuthorityAggregator::new_for_testing(Arc::new(committee));
 
@@ -495,14 +567,31 @@ mod tests {
             Err(BridgeError::RestAPIError("".into())),
             None,
         );
-        let err = agg
-            .request_committee_signatures(action.clone())
-            .await
-            .unwrap_err();
+        let (result, report) = agg
+            .request_committee_signatures_with_report(action.clone())
+            .await;
         assert!(matches!(
-            err,
+            result.unwrap_err(),
             BridgeError::AuthoritySignatureAggregationTooManyError(_)
         ));
+        // The failing authorities are reported, aggregation stops as soon as the third one fails
+        assert_eq!(
+            report
+                .failed
+                .iter()
+                .map(|response| response.authority.clone())
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from_iter(vec![
+                authorities[1].pubkey_bytes(),
+                authorities[2].pubkey_bytes(),
+                authorities[3].pubkey_bytes()
+            ])
+        );
+        assert!(report
+            .failed
+            .iter()
+            .all(|response| response.error.is_some()));
+        assert!(report.signed_stake() <= 2500);
     }
 
     #[tokio::test]
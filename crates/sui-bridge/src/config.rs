@@ -123,6 +123,9 @@ pub struct BridgeNodeConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub watchdog_config: Option<WatchdogConfig>,
+    /// Operator status API. Disabled when not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_api: Option<StatusApiConfig>,
 }
 
 pub fn default_ed25519_key_pair() -> NetworkKeyPair {
@@ -144,6 +147,16 @@ pub struct WatchdogConfig {
     pub total_supplies: BTreeMap<String, String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StatusApiConfig {
+    /// The port that the status API listens on. It is only bound on localhost, so that pending
+    /// actions are not exposed on the public signing server.
+    pub listen_port: u16,
+    /// Path of the file where the bearer token for the status API is stored.
+    pub token_path: PathBuf,
+}
+
 impl Config for BridgeNodeConfig {}
 
 impl BridgeNodeConfig {
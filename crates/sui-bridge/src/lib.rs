@@ -18,6 +18,7 @@ pub mod monitor;
 pub mod node;
 pub mod orchestrator;
 pub mod server;
+pub mod status;
 pub mod storage;
 pub mod sui_bridge_watchdog;
 pub mod sui_client;
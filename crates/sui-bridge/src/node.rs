@@ -24,7 +24,11 @@ use crate::{
     metrics::BridgeMetrics,
     monitor::BridgeMonitor,
     orchestrator::BridgeOrchestrator,
-    server::{handler::BridgeRequestHandler, run_server, BridgeNodePublicMetadata},
+    server::{
+        handler::BridgeRequestHandler, run_server, run_status_server, BridgeNodePublicMetadata,
+        BridgeNodeStatusApi,
+    },
+    status::{ActionProgressTracker, BridgeNodeStatusProvider},
     storage::BridgeOrchestratorTables,
     sui_syncer::SuiSyncer,
 };
@@ -58,6 +62,21 @@ pub async fn run_bridge_node(
     init_all_struct_tags();
     let metrics = Arc::new(BridgeMetrics::new(&prometheus_registry));
     let watchdog_config = config.watchdog_config.clone();
+    let status_api_config = config
+        .status_api
+        .as_ref()
+        .map(|status_api| {
+            std::fs::read_to_string(&status_api.token_path)
+                .map(|token| (status_api.listen_port, token.trim().to_string()))
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to read status api token from {:?}: {:?}",
+                        status_api.token_path,
+                        e
+                    )
+                })
+        })
+        .transpose()?;
     let (server_config, client_config) = config.validate(metrics.clone()).await?;
     let sui_chain_identifier = server_config
         .sui_client
@@ -110,17 +129,21 @@ pub async fn run_bridge_node(
         .await?;
 
     // Start Client
+    let action_progress = Arc::new(ActionProgressTracker::new());
+    let mut client_store = None;
     if let Some(client_config) = client_config {
         let committee_keys_to_names =
             Arc::new(get_validator_names_by_pub_keys(&committee, &sui_system).await);
-        let client_components = start_client_components(
+        let (client_components, store) = start_client_components(
             client_config,
             committee.clone(),
             committee_keys_to_names,
+            action_progress.clone(),
             metrics.clone(),
         )
         .await?;
         handles.extend(client_components);
+        client_store = Some(store);
     }
 
     let committee_name_mapping = get_committee_voting_power_by_name(&committee, &sui_system).await;
@@ -131,6 +154,18 @@ pub async fn run_bridge_node(
             .set(voting_power as i64);
     }
 
+    // Start the status API on localhost only, separately from the public server
+    if let Some((listen_port, token)) = status_api_config {
+        let status_api = BridgeNodeStatusApi::new(
+            BridgeNodeStatusProvider::new(metadata.version, client_store, action_progress),
+            token,
+        );
+        run_status_server(
+            &SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listen_port),
+            Arc::new(status_api),
+        );
+    }
+
     // Start Server
     let socket_address = SocketAddr::new(
         IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
@@ -224,8 +259,9 @@ async fn start_client_components(
     client_config: BridgeClientConfig,
     committee: Arc<BridgeCommittee>,
     committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    action_progress: Arc<ActionProgressTracker>,
     metrics: Arc<BridgeMetrics>,
-) -> anyhow::Result<Vec<JoinHandle<()>>> {
+) -> anyhow::Result<(Vec<JoinHandle<()>>, Arc<BridgeOrchestratorTables>)> {
     let store: std::sync::Arc<BridgeOrchestratorTables> =
         BridgeOrchestratorTables::new(&client_config.db_path.join("client"));
     let sui_modules_to_watch = get_sui_modules_to_watch(
@@ -295,6 +331,7 @@ async fn start_client_components(
         client_config.gas_object_ref.0,
         sui_token_type_tags.clone(),
         bridge_pause_rx,
+        action_progress,
         metrics.clone(),
     )
     .await;
@@ -321,7 +358,7 @@ async fn start_client_components(
     );
 
     all_handles.extend(orchestrator.run(bridge_action_executor).await);
-    Ok(all_handles)
+    Ok((all_handles, store))
 }
 
 fn get_sui_modules_to_watch(
@@ -598,6 +635,7 @@ mod tests {
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
             watchdog_config: None,
+            status_api: None,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
@@ -665,6 +703,7 @@ mod tests {
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
             watchdog_config: None,
+            status_api: None,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
@@ -743,6 +782,7 @@ mod tests {
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
             watchdog_config: None,
+            status_api: None,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
@@ -8,6 +8,7 @@ use crate::{
     error::BridgeError,
     metrics::BridgeMetrics,
     server::handler::{BridgeRequestHandler, BridgeRequestHandlerTrait},
+    status::{BridgeNodeStatus, BridgeNodeStatusProvider},
     types::{
         AddTokensOnEvmAction, AddTokensOnSuiAction, AssetPriceUpdateAction,
         BlocklistCommitteeAction, BlocklistType, BridgeAction, EmergencyAction,
@@ -18,7 +19,11 @@ use axum::{
     extract::{Path, State},
     Json,
 };
-use axum::{http::StatusCode, routing::get, Router};
+use axum::{
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    routing::get,
+    Router,
+};
 use ethers::types::Address as EthAddress;
 use fastcrypto::ed25519::Ed25519PublicKey;
 use fastcrypto::{
@@ -40,6 +45,7 @@ pub const APPLICATION_JSON: &str = "application/json";
 
 pub const PING_PATH: &str = "/ping";
 pub const METRICS_KEY_PATH: &str = "/metrics_pub_key";
+pub const STATUS_PATH: &str = "/status";
 
 // Important: for BridgeActions, the paths need to match the ones in bridge_client.rs
 pub const ETH_TO_SUI_TX_PATH: &str = "/sign/bridge_tx/eth/sui/:tx_hash/:event_index";
@@ -84,6 +90,36 @@ impl BridgeNodePublicMetadata {
     }
 }
 
+/// Operator status of the node, served on `/status` of a separate localhost listener to
+/// requests bearing `token`.
+pub struct BridgeNodeStatusApi {
+    provider: BridgeNodeStatusProvider,
+    token: String,
+}
+
+impl BridgeNodeStatusApi {
+    pub fn new(provider: BridgeNodeStatusProvider, token: String) -> Self {
+        Self { provider, token }
+    }
+
+    fn is_authorized(&self, headers: &HeaderMap) -> bool {
+        let Some(token) = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+        else {
+            return false;
+        };
+        // Compare in constant time to not leak the token through response timings.
+        token.len() == self.token.len()
+            && token
+                .bytes()
+                .zip(self.token.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+}
+
 pub fn run_server(
     socket_address: &SocketAddr,
     handler: BridgeRequestHandler,
@@ -102,6 +138,25 @@ pub fn run_server(
     })
 }
 
+pub fn run_status_server(
+    socket_address: &SocketAddr,
+    status_api: Arc<BridgeNodeStatusApi>,
+) -> tokio::task::JoinHandle<()> {
+    let socket_address = *socket_address;
+    tokio::spawn(async move {
+        let listener = tokio::net::TcpListener::bind(socket_address).await.unwrap();
+        axum::serve(listener, make_status_router(status_api).into_make_service())
+            .await
+            .unwrap();
+    })
+}
+
+pub(crate) fn make_status_router(status_api: Arc<BridgeNodeStatusApi>) -> Router {
+    Router::new()
+        .route(STATUS_PATH, get(handle_status))
+        .with_state(status_api)
+}
+
 pub(crate) fn make_router(
     handler: Arc<impl BridgeRequestHandlerTrait + Sync + Send + 'static>,
     metrics: Arc<BridgeMetrics>,
@@ -167,6 +222,16 @@ async fn ping(
     Ok(Json(metadata))
 }
 
+async fn handle_status(
+    State(status_api): State<Arc<BridgeNodeStatusApi>>,
+    headers: HeaderMap,
+) -> Result<Json<BridgeNodeStatus>, StatusCode> {
+    if !status_api.is_authorized(&headers) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(Json(status_api.provider.status()))
+}
+
 async fn metrics_key_fetch(
     State((_handler, _metrics, metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
@@ -633,6 +698,7 @@ mod tests {
     use super::*;
     use crate::client::bridge_client::BridgeClient;
     use crate::server::mock_handler::BridgeRequestMockHandler;
+    use crate::status::ActionProgressTracker;
     use crate::test_utils::get_test_authorities_and_run_mock_bridge_server;
     use crate::types::BridgeCommittee;
 
@@ -754,6 +820,63 @@ mod tests {
         client.request_sign_bridge_action(action).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_bridge_server_status_requires_token() {
+        let status_api = Arc::new(BridgeNodeStatusApi::new(
+            BridgeNodeStatusProvider::new("testing", None, Arc::new(ActionProgressTracker::new())),
+            "secret".to_string(),
+        ));
+        let router = make_status_router(status_api);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}{}", listener.local_addr().unwrap(), STATUS_PATH);
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let resp = client.get(&url).send().await.unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+        let resp = client
+            .get(&url)
+            .bearer_auth("not-the-secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let status: BridgeNodeStatus = client
+            .get(&url)
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(status.version, "testing");
+        assert!(!status.client_running);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_server_does_not_serve_status() {
+        let router = make_router(
+            Arc::new(BridgeRequestMockHandler::new()),
+            Arc::new(BridgeMetrics::new_for_testing()),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}{}", listener.local_addr().unwrap(), STATUS_PATH);
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let resp = reqwest::Client::new()
+            .get(&url)
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+
     fn setup() -> BridgeClient {
         let mock = BridgeRequestMockHandler::new();
         let (_handles, authorities, mut secrets) =
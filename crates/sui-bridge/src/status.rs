@@ -0,0 +1,324 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Operator-facing view of a bridge node's client: the actions waiting in the pending
+//! log, how far the syncers got, and how signing and execution of every action went.
+
+use crate::client::bridge_authority_aggregator::SignatureCollectionReport;
+use crate::storage::BridgeOrchestratorTables;
+use crate::types::{BridgeAction, BridgeActionDigest};
+use ethers::types::Address as EthAddress;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use sui_types::base_types::ConciseableName;
+use sui_types::event::EventID;
+
+/// Progress of an action through the action executor since the node started.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActionProgress {
+    pub signing_attempts: u64,
+    pub execution_attempts: u64,
+    /// Committee responses of the latest signature collection round
+    pub last_signatures: Option<SignatureCollectionReport>,
+    pub last_error: Option<String>,
+    /// Whether a certificate was formed for the action
+    pub certified: bool,
+}
+
+/// Records the signing and execution attempts of the action executor, keyed by action digest.
+#[derive(Debug, Default)]
+pub struct ActionProgressTracker {
+    progress: Mutex<HashMap<BridgeActionDigest, ActionProgress>>,
+}
+
+impl ActionProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_signing_attempt(
+        &self,
+        digest: BridgeActionDigest,
+        report: SignatureCollectionReport,
+        error: Option<String>,
+    ) {
+        let mut progress = self.progress.lock().unwrap();
+        let entry = progress.entry(digest).or_default();
+        entry.signing_attempts += 1;
+        entry.certified = error.is_none();
+        entry.last_signatures = Some(report);
+        entry.last_error = error;
+    }
+
+    pub fn record_execution_attempt(&self, digest: BridgeActionDigest, error: Option<String>) {
+        let mut progress = self.progress.lock().unwrap();
+        let entry = progress.entry(digest).or_default();
+        entry.execution_attempts += 1;
+        entry.last_error = error;
+    }
+
+    pub fn get(&self, digest: &BridgeActionDigest) -> Option<ActionProgress> {
+        self.progress.lock().unwrap().get(digest).cloned()
+    }
+
+    /// Drop the progress of an action once it is removed from the pending log.
+    pub fn remove(&self, digest: &BridgeActionDigest) {
+        self.progress.lock().unwrap().remove(digest);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingActionStatus {
+    pub digest: BridgeActionDigest,
+    pub action: BridgeAction,
+    /// None if the executor has not picked up the action since the node started
+    pub progress: Option<ActionProgress>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BridgeNodeStatus {
+    pub version: String,
+    pub client_running: bool,
+    pub pending_actions: Vec<PendingActionStatus>,
+    /// Last processed event of every watched Sui module
+    pub sui_event_cursors: BTreeMap<String, EventID>,
+    /// Last processed block of every watched Eth contract
+    pub eth_event_cursors: BTreeMap<EthAddress, u64>,
+}
+
+impl Display for BridgeNodeStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Version: {}", self.version)?;
+        if !self.client_running {
+            return writeln!(f, "Client: not running");
+        }
+        writeln!(f, "Client: running")?;
+
+        writeln!(f, "Sui event cursors:")?;
+        for (module, cursor) in &self.sui_event_cursors {
+            writeln!(
+                f,
+                "  {}: tx {} event {}",
+                module, cursor.tx_digest, cursor.event_seq
+            )?;
+        }
+        writeln!(f, "Eth event cursors:")?;
+        for (contract, block) in &self.eth_event_cursors {
+            writeln!(f, "  {:?}: block {}", contract, block)?;
+        }
+
+        writeln!(f, "Pending actions: {}", self.pending_actions.len())?;
+        for pending in &self.pending_actions {
+            let action = &pending.action;
+            writeln!(
+                f,
+                "  {} {} from {} seq {}",
+                pending.digest,
+                action.action_type(),
+                action.chain_id(),
+                action.seq_number()
+            )?;
+            let Some(progress) = &pending.progress else {
+                writeln!(f, "    not picked up by the executor yet")?;
+                continue;
+            };
+            writeln!(
+                f,
+                "    signing attempts: {}, execution attempts: {}, certified: {}",
+                progress.signing_attempts, progress.execution_attempts, progress.certified
+            )?;
+            if let Some(report) = &progress.last_signatures {
+                writeln!(
+                    f,
+                    "    signed stake: {} of {} required, from {} authorities",
+                    report.signed_stake(),
+                    report.validity_threshold,
+                    report.signed.len()
+                )?;
+                for failed in &report.failed {
+                    writeln!(
+                        f,
+                        "    failed: {} ({}) with stake {}: {}",
+                        failed.name.as_deref().unwrap_or("unknown"),
+                        failed.authority.concise(),
+                        failed.stake,
+                        failed.error.as_deref().unwrap_or("invalid signature")
+                    )?;
+                }
+            }
+            if let Some(error) = &progress.last_error {
+                writeln!(f, "    last error: {}", error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct BridgeNodeStatusProvider {
+    version: &'static str,
+    /// None when the node does not run the client
+    store: Option<Arc<BridgeOrchestratorTables>>,
+    tracker: Arc<ActionProgressTracker>,
+}
+
+impl BridgeNodeStatusProvider {
+    pub fn new(
+        version: &'static str,
+        store: Option<Arc<BridgeOrchestratorTables>>,
+        tracker: Arc<ActionProgressTracker>,
+    ) -> Self {
+        Self {
+            version,
+            store,
+            tracker,
+        }
+    }
+
+    pub fn status(&self) -> BridgeNodeStatus {
+        let Some(store) = &self.store else {
+            return BridgeNodeStatus {
+                version: self.version.to_string(),
+                client_running: false,
+                pending_actions: vec![],
+                sui_event_cursors: BTreeMap::new(),
+                eth_event_cursors: BTreeMap::new(),
+            };
+        };
+        let mut pending_actions: Vec<_> = store
+            .get_all_pending_actions()
+            .into_iter()
+            .map(|(digest, action)| PendingActionStatus {
+                digest,
+                action,
+                progress: self.tracker.get(&digest),
+            })
+            .collect();
+        pending_actions.sort_by_key(|status| status.digest);
+        BridgeNodeStatus {
+            version: self.version.to_string(),
+            client_running: true,
+            pending_actions,
+            sui_event_cursors: store
+                .get_all_sui_event_cursors()
+                .into_iter()
+                .map(|(module, cursor)| (module.to_string(), cursor))
+                .collect(),
+            eth_event_cursors: store.get_all_eth_event_cursors().into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use sui_types::digests::TransactionDigest;
+    use sui_types::Identifier;
+
+    #[tokio::test]
+    async fn test_bridge_node_status() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let tracker = Arc::new(ActionProgressTracker::new());
+        let provider =
+            BridgeNodeStatusProvider::new("testing", Some(store.clone()), tracker.clone());
+
+        let action1 = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(0),
+            Some(99),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+        let action2 = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(1),
+            Some(100),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+        store
+            .insert_pending_actions(&[action1.clone(), action2.clone()])
+            .unwrap();
+        let module = Identifier::new("bridge").unwrap();
+        let cursor = EventID {
+            tx_digest: TransactionDigest::random(),
+            event_seq: 1,
+        };
+        store.update_sui_event_cursor(module, cursor).unwrap();
+        store
+            .update_eth_event_cursor(EthAddress::random(), 42)
+            .unwrap();
+
+        tracker.record_signing_attempt(
+            action1.digest(),
+            SignatureCollectionReport::default(),
+            Some("too many errors".to_string()),
+        );
+        tracker.record_signing_attempt(
+            action1.digest(),
+            SignatureCollectionReport::default(),
+            None,
+        );
+        tracker.record_execution_attempt(action1.digest(), None);
+
+        let status = provider.status();
+        assert!(status.client_running);
+        assert_eq!(status.pending_actions.len(), 2);
+        let progress = status
+            .pending_actions
+            .iter()
+            .find(|status| status.digest == action1.digest())
+            .unwrap()
+            .progress
+            .clone()
+            .unwrap();
+        assert_eq!(progress.signing_attempts, 2);
+        assert_eq!(progress.execution_attempts, 1);
+        assert!(progress.certified);
+        assert!(progress.last_error.is_none());
+        assert!(status
+            .pending_actions
+            .iter()
+            .find(|status| status.digest == action2.digest())
+            .unwrap()
+            .progress
+            .is_none());
+        assert_eq!(status.sui_event_cursors.get("bridge"), Some(&cursor));
+        assert_eq!(
+            status.eth_event_cursors.values().collect::<Vec<_>>(),
+            vec![&42]
+        );
+
+        // Reading the status does not modify the tracked progress
+        store.remove_pending_actions(&[action1.digest()]).unwrap();
+        let status = provider.status();
+        assert_eq!(status.pending_actions.len(), 1);
+        assert_eq!(status.pending_actions[0].digest, action2.digest());
+        assert_eq!(tracker.get(&action1.digest()), Some(progress));
+
+        // Progress is dropped by the executor once the action leaves the pending log
+        tracker.remove(&action1.digest());
+        assert!(tracker.get(&action1.digest()).is_none());
+
+        let output = status.to_string();
+        assert!(output.contains("Client: running"));
+        assert!(output.contains("Pending actions: 1"));
+        assert!(output.contains(&format!("{} TokenTransfer", action2.digest())));
+        assert!(output.contains("not picked up by the executor yet"));
+
+        let status = BridgeNodeStatusProvider::new("testing", None, tracker).status();
+        assert!(!status.client_running);
+        assert!(status.pending_actions.is_empty());
+        assert_eq!(
+            status.to_string(),
+            "Version: testing\nClient: not running\n"
+        );
+    }
+}
@@ -129,6 +129,14 @@ impl BridgeOrchestratorTables {
                 BridgeError::StorageError(format!("Couldn't get sui_syncer_cursors: {:?}", e))
             })
     }
+
+    pub fn get_all_sui_event_cursors(&self) -> HashMap<Identifier, EventID> {
+        self.sui_syncer_cursors.unbounded_iter().collect()
+    }
+
+    pub fn get_all_eth_event_cursors(&self) -> HashMap<ethers::types::Address, u64> {
+        self.eth_syncer_cursors.unbounded_iter().collect()
+    }
 }
 
 #[cfg(test)]
@@ -9,11 +9,12 @@ use crate::verifier_signing_config::VerifierSigningConfig;
 use crate::Config;
 use anyhow::Result;
 use consensus_config::Parameters as ConsensusParameters;
+use move_core_types::language_storage::StructTag;
 use mysten_common::fatal;
 use once_cell::sync::OnceCell;
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{serde_as, DisplayFromStr};
 use std::collections::{BTreeMap, BTreeSet};
 use std::net::SocketAddr;
 use std::num::NonZeroUsize;
@@ -868,6 +869,9 @@ pub struct AuthorityStorePruningConfig {
     pub killswitch_tombstone_pruning: bool,
     #[serde(default = "default_smoothing", skip_serializing_if = "is_true")]
     pub smooth: bool,
+    /// history that is never pruned, regardless of the retention periods above
+    #[serde(default, skip_serializing_if = "HistoryRetentionConfig::is_empty")]
+    pub retain_history: HistoryRetentionConfig,
 }
 
 fn default_num_latest_epoch_dbs_to_retain() -> usize {
@@ -907,6 +911,7 @@ impl Default for AuthorityStorePruningConfig {
             num_epochs_to_retain_for_checkpoints: if cfg!(msim) { Some(2) } else { None },
             killswitch_tombstone_pruning: false,
             smooth: true,
+            retain_history: HistoryRetentionConfig::default(),
         }
     }
 }
@@ -938,6 +943,32 @@ impl AuthorityStorePruningConfig {
     }
 }
 
+/// Rules selecting history that the pruner keeps indefinitely: every version of the matching
+/// objects, and the transactions, effects and events touching them.
+#[serde_as]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoryRetentionConfig {
+    /// packages whose versions, calls and events are retained, along with all objects of the
+    /// types they define
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<ObjectID>,
+    /// Move types whose objects and events are retained. A type without type parameters, like
+    /// `0x2::coin::Coin`, matches all of its instantiations.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub move_types: Vec<StructTag>,
+    /// addresses whose transactions and owned objects are retained
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addresses: Vec<SuiAddress>,
+}
+
+impl HistoryRetentionConfig {
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.move_types.is_empty() && self.addresses.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct MetricsConfig {
@@ -984,6 +1015,9 @@ pub struct StateSnapshotConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub object_store_config: Option<ObjectStoreConfig>,
     pub concurrency: usize,
+    /// Also upload a delta snapshot from the previous epoch alongside every full snapshot.
+    #[serde(default)]
+    pub write_delta_snapshots: bool,
 }
 
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
@@ -187,6 +187,28 @@ pub struct StateSyncConfig {
     /// If unspecified, this will set to default value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wait_interval_when_no_peer_to_sync_content_ms: Option<u64>,
+
+    /// Number of checkpoints a node must be behind the latest archived checkpoint on startup for
+    /// state sync to bulk import checkpoints from archives before syncing from peers. The import
+    /// stops once the node is within this many checkpoints of the highest known checkpoint.
+    ///
+    /// If unspecified, checkpoints are only imported from archives as a fallback, when no peer
+    /// has the checkpoints this node is missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_import_threshold: Option<u64>,
+
+    /// Number of checkpoints downloaded from an archive at a time during bulk import.
+    ///
+    /// If unspecified, this will default to `1,000`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_import_batch_size: Option<u64>,
+
+    /// Set the upper bound on the number of batches of checkpoints to be downloaded concurrently
+    /// from archives during bulk import.
+    ///
+    /// If unspecified, this will default to `8`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_import_concurrency: Option<usize>,
 }
 
 impl StateSyncConfig {
@@ -252,6 +274,22 @@ impl StateSyncConfig {
             .unwrap_or(self.default_wait_interval_when_no_peer_to_sync_content())
     }
 
+    pub fn archive_import_batch_size(&self) -> u64 {
+        const ARCHIVE_IMPORT_BATCH_SIZE: u64 = 1_000;
+
+        self.archive_import_batch_size
+            .unwrap_or(ARCHIVE_IMPORT_BATCH_SIZE)
+            .max(1)
+    }
+
+    pub fn archive_import_concurrency(&self) -> usize {
+        const ARCHIVE_IMPORT_CONCURRENCY: usize = 8;
+
+        self.archive_import_concurrency
+            .unwrap_or(ARCHIVE_IMPORT_CONCURRENCY)
+            .max(1)
+    }
+
     fn default_wait_interval_when_no_peer_to_sync_content(&self) -> Duration {
         if cfg!(msim) {
             Duration::from_secs(5)
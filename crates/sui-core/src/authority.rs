@@ -348,6 +348,10 @@ const GAS_LATENCY_RATIO_BUCKETS: &[f64] = &[
 
 pub const DEV_INSPECT_GAS_COIN_VALUE: u64 = 1_000_000_000_000;
 
+/// The most pruned transactions or events a single index query skips over before returning a
+/// partial page, when the pruner retains only some history.
+pub const MAX_PRUNED_INDEX_ENTRIES_SKIPPED: usize = 10_000;
+
 impl AuthorityMetrics {
     pub fn new(registry: &prometheus::Registry) -> AuthorityMetrics {
         let execute_certificate_latency = register_histogram_vec_with_registry!(
@@ -3861,8 +3865,16 @@ impl AuthorityState {
         ));
         self.get_transactions(&kv_store, filter, cursor, limit, reverse)
             .await
+            .map(|(digests, _)| digests)
     }
 
+    /// Returns the digests of the transactions matching `filter`, and a cursor to continue from
+    /// when the page was cut short.
+    ///
+    /// The indexes are not pruned, so when the pruner retains only some history, the pruned
+    /// transactions are skipped. Each query skips at most
+    /// [MAX_PRUNED_INDEX_ENTRIES_SKIPPED] of them, and if it reaches that bound before the page
+    /// is full, it returns what it found along with the last index entry it scanned.
     #[instrument(level = "trace", skip_all)]
     pub async fn get_transactions(
         &self,
@@ -3872,7 +3884,7 @@ impl AuthorityState {
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         reverse: bool,
-    ) -> SuiResult<Vec<TransactionDigest>> {
+    ) -> SuiResult<(Vec<TransactionDigest>, Option<TransactionDigest>)> {
         if let Some(TransactionFilter::Checkpoint(sequence_number)) = filter {
             let checkpoint_contents = kv_store.get_checkpoint_contents(sequence_number).await?;
             let iter = checkpoint_contents.iter().map(|c| c.transaction);
@@ -3881,16 +3893,59 @@ impl AuthorityState {
                     .rev()
                     .skip_while(|d| cursor.is_some() && Some(*d) != cursor)
                     .skip(usize::from(cursor.is_some()));
-                return Ok(iter.take(limit.unwrap_or(usize::MAX)).collect());
+                return Ok((iter.take(limit.unwrap_or(usize::MAX)).collect(), None));
             } else {
                 let iter = iter
                     .skip_while(|d| cursor.is_some() && Some(*d) != cursor)
                     .skip(usize::from(cursor.is_some()));
-                return Ok(iter.take(limit.unwrap_or(usize::MAX)).collect());
+                return Ok((iter.take(limit.unwrap_or(usize::MAX)).collect(), None));
             }
         }
-        self.get_indexes()?
-            .get_transactions(filter, cursor, limit, reverse)
+        let indexes = self.get_indexes()?;
+        if self
+            .config
+            .authority_store_pruning_config
+            .retain_history
+            .is_empty()
+        {
+            return Ok((
+                indexes.get_transactions(filter, cursor, limit, reverse)?,
+                None,
+            ));
+        }
+
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut cursor = cursor;
+        let mut digests = vec![];
+        let mut skipped = 0;
+        while digests.len() < limit {
+            if skipped >= MAX_PRUNED_INDEX_ENTRIES_SKIPPED {
+                return Ok((digests, cursor));
+            }
+            let page_size = (limit - digests.len()).min(MAX_PRUNED_INDEX_ENTRIES_SKIPPED);
+            let page =
+                indexes.get_transactions(filter.clone(), cursor, Some(page_size), reverse)?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            cursor = Some(*last);
+            let exhausted = page.len() < page_size;
+            let scanned = page.len();
+            let effects = self
+                .get_transaction_cache_reader()
+                .multi_get_executed_effects_digests(&page);
+            let retained = digests.len();
+            digests.extend(
+                page.into_iter()
+                    .zip(effects)
+                    .filter_map(|(digest, effects)| effects.map(|_| digest)),
+            );
+            skipped += scanned - (digests.len() - retained);
+            if exhausted {
+                break;
+            }
+        }
+        Ok((digests, None))
     }
 
     pub fn get_checkpoint_store(&self) -> &Arc<CheckpointStore> {
@@ -4033,6 +4088,12 @@ impl AuthorityState {
         }
     }
 
+    /// Returns the events matching `query`, and a cursor to continue from when the page was cut
+    /// short.
+    ///
+    /// Like [Self::get_transactions], when the pruner retains only some history, the events of
+    /// pruned transactions are skipped, and a query that skips
+    /// [MAX_PRUNED_INDEX_ENTRIES_SKIPPED] of them returns a partial page.
     #[instrument(level = "trace", skip_all)]
     pub async fn query_events(
         &self,
@@ -4042,11 +4103,80 @@ impl AuthorityState {
         cursor: Option<EventID>,
         limit: usize,
         descending: bool,
-    ) -> SuiResult<Vec<SuiEvent>> {
+    ) -> SuiResult<(Vec<SuiEvent>, Option<EventID>)> {
         let index_store = self.get_indexes()?;
+        let retain_history = !self
+            .config
+            .authority_store_pruning_config
+            .retain_history
+            .is_empty();
+
+        let mut cursor = cursor;
+        let mut stored_events = vec![];
+        let mut skipped = 0;
+        while stored_events.len() < limit {
+            if skipped >= MAX_PRUNED_INDEX_ENTRIES_SKIPPED {
+                return Ok((self.to_sui_events(stored_events)?, cursor));
+            }
+            let page_size = limit - stored_events.len();
+            let event_keys = Self::query_event_keys(
+                &index_store,
+                &query,
+                cursor.as_ref(),
+                page_size,
+                descending,
+            )?;
+            let Some((_, tx_digest, event_seq, _)) = event_keys.last() else {
+                break;
+            };
+            cursor = Some(EventID {
+                tx_digest: *tx_digest,
+                event_seq: *event_seq as u64,
+            });
+            let exhausted = event_keys.len() < page_size;
+
+            // get the unique set of digests from the event_keys
+            let event_digests = event_keys
+                .iter()
+                .map(|(digest, _, _, _)| *digest)
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+
+            let events = kv_store.multi_get_events(&event_digests).await?;
+
+            let events_map: HashMap<_, _> = event_digests.iter().zip(events.into_iter()).collect();
+
+            for (digest, tx_digest, event_seq, timestamp) in event_keys {
+                let event = events_map
+                    .get(&digest)
+                    .expect("fetched digest is missing")
+                    .as_ref()
+                    .and_then(|e| e.data.get(event_seq).cloned());
+                match event {
+                    Some(event) => stored_events.push((event, tx_digest, event_seq, timestamp)),
+                    // The events of transactions that were not retained are pruned.
+                    None if retain_history => skipped += 1,
+                    None => return Err(SuiError::TransactionEventsNotFound { digest }),
+                }
+            }
+            if exhausted {
+                break;
+            }
+        }
+        Ok((self.to_sui_events(stored_events)?, None))
+    }
 
+    fn query_event_keys(
+        index_store: &IndexStore,
+        query: &EventFilter,
+        // If `Some`, the query will start from the next item after the specified cursor
+        cursor: Option<&EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
         //Get the tx_num from tx_digest
-        let (tx_num, event_num) = if let Some(cursor) = cursor.as_ref() {
+        let (tx_num, event_num) = if let Some(cursor) = cursor {
             let tx_seq = index_store.get_transaction_seq(&cursor.tx_digest)?.ok_or(
                 SuiError::TransactionNotFound {
                     digest: cursor.tx_digest,
@@ -4063,31 +4193,37 @@ impl AuthorityState {
         let mut event_keys = match query {
             EventFilter::All([]) => index_store.all_events(tx_num, event_num, limit, descending)?,
             EventFilter::Transaction(digest) => {
-                index_store.events_by_transaction(&digest, tx_num, event_num, limit, descending)?
+                index_store.events_by_transaction(digest, tx_num, event_num, limit, descending)?
             }
             EventFilter::MoveModule { package, module } => {
-                let module_id = ModuleId::new(package.into(), module);
+                let module_id = ModuleId::new((*package).into(), module.clone());
                 index_store.events_by_module_id(&module_id, tx_num, event_num, limit, descending)?
             }
             EventFilter::MoveEventType(struct_name) => index_store
                 .events_by_move_event_struct_name(
-                    &struct_name,
+                    struct_name,
                     tx_num,
                     event_num,
                     limit,
                     descending,
                 )?,
             EventFilter::Sender(sender) => {
-                index_store.events_by_sender(&sender, tx_num, event_num, limit, descending)?
+                index_store.events_by_sender(sender, tx_num, event_num, limit, descending)?
             }
             EventFilter::TimeRange {
                 start_time,
                 end_time,
-            } => index_store
-                .event_iterator(start_time, end_time, tx_num, event_num, limit, descending)?,
+            } => index_store.event_iterator(
+                *start_time,
+                *end_time,
+                tx_num,
+                event_num,
+                limit,
+                descending,
+            )?,
             EventFilter::MoveEventModule { package, module } => index_store
                 .events_by_move_event_module(
-                    &ModuleId::new(package.into(), module),
+                    &ModuleId::new((*package).into(), module.clone()),
                     tx_num,
                     event_num,
                     limit,
@@ -4112,38 +4248,13 @@ impl AuthorityState {
         } else {
             event_keys.truncate(limit - 1);
         }
+        Ok(event_keys)
+    }
 
-        // get the unique set of digests from the event_keys
-        let event_digests = event_keys
-            .iter()
-            .map(|(digest, _, _, _)| *digest)
-            .collect::<HashSet<_>>()
-            .into_iter()
-            .collect::<Vec<_>>();
-
-        let events = kv_store.multi_get_events(&event_digests).await?;
-
-        let events_map: HashMap<_, _> = event_digests.iter().zip(events.into_iter()).collect();
-
-        let stored_events = event_keys
-            .into_iter()
-            .map(|k| {
-                (
-                    k,
-                    events_map
-                        .get(&k.0)
-                        .expect("fetched digest is missing")
-                        .clone()
-                        .and_then(|e| e.data.get(k.2).cloned()),
-                )
-            })
-            .map(|((digest, tx_digest, event_seq, timestamp), event)| {
-                event
-                    .map(|e| (e, tx_digest, event_seq, timestamp))
-                    .ok_or(SuiError::TransactionEventsNotFound { digest })
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
+    fn to_sui_events(
+        &self,
+        stored_events: Vec<(Event, TransactionDigest, usize, u64)>,
+    ) -> SuiResult<Vec<SuiEvent>> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
         let backing_store = self.get_backing_package_store().as_ref();
         let mut layout_resolver = epoch_store
@@ -10,7 +10,8 @@ use crate::authority::authority_store_pruner::{
     AuthorityStorePruner, AuthorityStorePruningMetrics, EPOCH_DURATION_MS_FOR_TESTING,
 };
 use crate::authority::authority_store_types::{
-    get_store_object_pair, ObjectContentDigest, StoreObject, StoreObjectPair, StoreObjectWrapper,
+    get_store_object_pair, ObjectContentDigest, StoreData, StoreObject, StoreObjectPair,
+    StoreObjectWrapper,
 };
 use crate::authority::epoch_start_configuration::{EpochFlag, EpochStartConfiguration};
 use crate::rpc_index::RpcIndexStore;
@@ -777,6 +778,46 @@ impl AuthorityStore {
         Ok(())
     }
 
+    /// Removes the given object versions from the live object set. This is only meant for
+    /// applying delta state snapshots on top of a restored db, which holds exactly one version of
+    /// every live object. References held by removed objects on indirect objects are released, the
+    /// same way the pruner does.
+    pub fn bulk_remove_live_objects(
+        perpetual_db: &AuthorityPerpetualTables,
+        object_refs: &[ObjectRef],
+    ) -> SuiResult<()> {
+        let mut indirect_objects: HashMap<ObjectContentDigest, i64> = HashMap::new();
+        for object in perpetual_db
+            .objects
+            .multi_get(object_refs.iter().map(ObjectKey::from))?
+            .into_iter()
+            .flatten()
+        {
+            if let StoreObject::Value(obj) = object.into_inner() {
+                if let StoreData::IndirectObject(indirect_object) = obj.data {
+                    *indirect_objects.entry(indirect_object.digest).or_default() -= 1;
+                }
+            }
+        }
+
+        let mut batch = perpetual_db.objects.batch();
+        if !indirect_objects.is_empty() {
+            batch.partial_merge_batch(
+                &perpetual_db.indirect_move_objects,
+                indirect_objects
+                    .iter()
+                    .map(|(digest, delta)| (digest, delta.to_le_bytes())),
+            )?;
+        }
+        batch.delete_batch(
+            &perpetual_db.objects,
+            object_refs.iter().map(ObjectKey::from),
+        )?;
+        batch.delete_batch(&perpetual_db.live_owned_object_markers, object_refs.iter())?;
+        batch.write()?;
+        Ok(())
+    }
+
     pub fn set_epoch_start_configuration(
         &self,
         epoch_start_configuration: &EpochStartConfiguration,
@@ -1,10 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::authority::authority_store_types::{ObjectContentDigest, StoreData, StoreObject};
+use crate::authority::authority_store_types::{
+    ObjectContentDigest, StoreData, StoreObject, StoreObjectValue,
+};
 use crate::checkpoints::{CheckpointStore, CheckpointWatermark};
 use crate::rpc_index::RpcIndexStore;
 use anyhow::anyhow;
+use move_core_types::language_storage::StructTag;
 use mysten_metrics::{monitored_scope, spawn_monitored_task};
 use once_cell::sync::Lazy;
 use prometheus::{
@@ -12,21 +15,24 @@ use prometheus::{
     Registry,
 };
 use std::cmp::{max, min};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{sync::Arc, time::Duration};
 use sui_archival::reader::ArchiveReaderBalancer;
-use sui_config::node::AuthorityStorePruningConfig;
+use sui_config::node::{AuthorityStorePruningConfig, HistoryRetentionConfig};
 use sui_storage::mutex_table::RwLockTable;
-use sui_types::base_types::SequenceNumber;
+use sui_types::base_types::{SequenceNumber, SuiAddress};
 use sui_types::committee::EpochId;
 use sui_types::effects::TransactionEffects;
 use sui_types::effects::TransactionEffectsAPI;
+use sui_types::gas_coin::GasCoin;
 use sui_types::message_envelope::Message;
 use sui_types::messages_checkpoint::{
     CheckpointContents, CheckpointDigest, CheckpointSequenceNumber,
 };
+use sui_types::object::Owner;
+use sui_types::transaction::TransactionDataAPI;
 use sui_types::{
     base_types::{ObjectID, VersionNumber},
     storage::ObjectKey,
@@ -63,6 +69,8 @@ pub struct AuthorityStorePruningMetrics {
     pub last_pruned_checkpoint: IntGauge,
     pub num_pruned_objects: IntCounter,
     pub num_pruned_tombstones: IntCounter,
+    pub num_retained_objects: IntCounter,
+    pub num_retained_transactions: IntCounter,
     pub last_pruned_effects_checkpoint: IntGauge,
     pub num_epochs_to_retain_for_objects: IntGauge,
     pub num_epochs_to_retain_for_checkpoints: IntGauge,
@@ -89,6 +97,18 @@ impl AuthorityStorePruningMetrics {
                 registry
             )
             .unwrap(),
+            num_retained_objects: register_int_counter_with_registry!(
+                "num_retained_objects",
+                "Number of object versions kept by history retention rules",
+                registry
+            )
+            .unwrap(),
+            num_retained_transactions: register_int_counter_with_registry!(
+                "num_retained_transactions",
+                "Number of transactions kept by history retention rules",
+                registry
+            )
+            .unwrap(),
             last_pruned_effects_checkpoint: register_int_gauge_with_registry!(
                 "last_pruned_effects_checkpoint",
                 "Last pruned effects checkpoint",
@@ -122,6 +142,155 @@ pub enum PruningMode {
     Checkpoints,
 }
 
+/// Decides which object versions and transactions are kept by the pruner regardless of their
+/// age, following the rules of a [HistoryRetentionConfig].
+#[derive(Debug, Default)]
+pub struct HistoryRetention {
+    packages: HashSet<ObjectID>,
+    move_types: Vec<StructTag>,
+    addresses: HashSet<SuiAddress>,
+}
+
+impl HistoryRetention {
+    pub fn new(config: &HistoryRetentionConfig) -> Self {
+        Self {
+            packages: config.packages.iter().copied().collect(),
+            move_types: config.move_types.clone(),
+            addresses: config.addresses.iter().copied().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.move_types.is_empty() && self.addresses.is_empty()
+    }
+
+    fn retains_type(&self, type_: &StructTag) -> bool {
+        self.packages.contains(&ObjectID::from(type_.address))
+            || self.move_types.iter().any(|rule| {
+                rule.address == type_.address
+                    && rule.module == type_.module
+                    && rule.name == type_.name
+                    && (rule.type_params.is_empty() || rule.type_params == type_.type_params)
+            })
+    }
+
+    fn retains_owner(&self, owner: &Owner) -> bool {
+        match owner {
+            Owner::AddressOwner(address) => self.addresses.contains(address),
+            _ => false,
+        }
+    }
+
+    fn retains_object(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        object: &StoreObjectValue,
+    ) -> anyhow::Result<bool> {
+        if self.retains_owner(&object.owner) {
+            return Ok(true);
+        }
+        if self.packages.is_empty() && self.move_types.is_empty() {
+            return Ok(false);
+        }
+        let type_: StructTag = match &object.data {
+            StoreData::Package(package) => return Ok(self.packages.contains(&package.id())),
+            StoreData::Move(object) => object.type_().clone().into(),
+            StoreData::Coin(_) => GasCoin::type_(),
+            StoreData::IndirectObject(metadata) => {
+                match perpetual_db.indirect_move_objects.get(&metadata.digest)? {
+                    Some(object) => object.into_inner().type_.into(),
+                    None => return Ok(false),
+                }
+            }
+        };
+        Ok(self.retains_type(&type_))
+    }
+
+    /// Whether any of the given object versions that are still stored is retained.
+    fn retains_any_object(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        object_keys: &[ObjectKey],
+    ) -> anyhow::Result<bool> {
+        for object in perpetual_db
+            .objects
+            .multi_get(object_keys)?
+            .into_iter()
+            .flatten()
+        {
+            if let StoreObject::Value(object) = object.into_inner() {
+                if self.retains_object(perpetual_db, &object)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// A transaction is retained if it was sent or paid for by a retained address, calls into
+    /// a retained package, emits a retained event, or reads or writes a retained object.
+    fn retains_transaction(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        effects: &TransactionEffects,
+    ) -> anyhow::Result<bool> {
+        if let Some(transaction) = perpetual_db
+            .transactions
+            .get(effects.transaction_digest())?
+        {
+            let data = transaction.inner().data().transaction_data();
+            if self.addresses.contains(&data.sender())
+                || self.addresses.contains(&data.gas_owner())
+                || data
+                    .move_calls()
+                    .into_iter()
+                    .any(|(package, _, _)| self.packages.contains(package))
+            {
+                return Ok(true);
+            }
+        }
+
+        if let Some(events_digest) = effects.events_digest() {
+            for result in perpetual_db
+                .events
+                .safe_range_iter((*events_digest, 0)..=(*events_digest, usize::MAX))
+            {
+                let (_, event) = result?;
+                if self.addresses.contains(&event.sender)
+                    || self.packages.contains(&event.package_id)
+                    || self.retains_type(&event.type_)
+                {
+                    return Ok(true);
+                }
+            }
+        }
+
+        let changed_objects = effects.all_changed_objects();
+        let old_objects = effects.old_object_metadata();
+        if changed_objects
+            .iter()
+            .map(|(_, owner, _)| owner)
+            .chain(old_objects.iter().map(|(_, owner)| owner))
+            .any(|owner| self.retains_owner(owner))
+        {
+            return Ok(true);
+        }
+
+        // Versions that are not retained may already be pruned, but retained ones never are.
+        let object_keys: Vec<_> = changed_objects
+            .iter()
+            .map(|(object_ref, _, _)| ObjectKey(object_ref.0, object_ref.1))
+            .chain(
+                effects
+                    .modified_at_versions()
+                    .into_iter()
+                    .map(|(object_id, version)| ObjectKey(object_id, version)),
+            )
+            .collect();
+        self.retains_any_object(perpetual_db, &object_keys)
+    }
+}
+
 impl AuthorityStorePruner {
     /// prunes old versions of objects based on transaction effects
     async fn prune_objects(
@@ -132,6 +301,7 @@ impl AuthorityStorePruner {
         metrics: Arc<AuthorityStorePruningMetrics>,
         indirect_objects_threshold: usize,
         enable_pruning_tombstones: bool,
+        retention: &HistoryRetention,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("ObjectsLivePruner");
         let mut wb = perpetual_db.objects.batch();
@@ -152,6 +322,28 @@ impl AuthorityStorePruner {
             }
         }
 
+        // Objects with retained versions are pruned with point deletes, so that the range deletes
+        // of their other versions cannot cover the retained ones.
+        let mut retained_object_ids = HashSet::new();
+        if !retention.is_empty() {
+            let objects = perpetual_db
+                .objects
+                .multi_get(live_object_keys_to_prune.iter())?;
+            let mut object_keys = vec![];
+            for (object_key, object) in live_object_keys_to_prune.into_iter().zip(objects) {
+                match object.map(|object| object.into_inner()) {
+                    Some(StoreObject::Value(object))
+                        if retention.retains_object(perpetual_db, &object)? =>
+                    {
+                        retained_object_ids.insert(object_key.0);
+                        metrics.num_retained_objects.inc();
+                    }
+                    _ => object_keys.push(object_key),
+                }
+            }
+            live_object_keys_to_prune = object_keys;
+        }
+
         metrics
             .num_pruned_objects
             .inc_by(live_object_keys_to_prune.len() as u64);
@@ -176,7 +368,12 @@ impl AuthorityStorePruner {
         }
 
         let mut updates: HashMap<ObjectID, (VersionNumber, VersionNumber)> = HashMap::new();
+        let mut object_keys_to_delete = vec![];
         for ObjectKey(object_id, seq_number) in live_object_keys_to_prune {
+            if retained_object_ids.contains(&object_id) {
+                object_keys_to_delete.push(ObjectKey(object_id, seq_number));
+                continue;
+            }
             updates
                 .entry(object_id)
                 .and_modify(|range| *range = (min(range.0, seq_number), max(range.1, seq_number)))
@@ -192,23 +389,34 @@ impl AuthorityStorePruner {
             let end_range = ObjectKey(object_id, (max_version.value() + 1).into());
             wb.schedule_delete_range(&perpetual_db.objects, &start_range, &end_range)?;
         }
+        wb.delete_batch(&perpetual_db.objects, object_keys_to_delete)?;
 
         // When enable_pruning_tombstones is enabled, instead of using range deletes, we need to do a scan of all the keys
         // for the deleted objects and then do point deletes to delete all the existing keys. This is because to improve read
         // performance, we set `ignore_range_deletions` on all read options, and using range delete to delete tombstones
         // may leak object (imagine a tombstone is compacted away, but earlier version is still not). Using point deletes
         // guarantees that all earlier versions are deleted in the database.
+        // Deleted objects with retained versions keep their tombstone and all of their versions.
         if !object_tombstones_to_prune.is_empty() {
             let mut object_keys_to_delete = vec![];
-            for ObjectKey(object_id, seq_number) in object_tombstones_to_prune {
+            'tombstones: for ObjectKey(object_id, seq_number) in object_tombstones_to_prune {
+                let mut object_keys = vec![];
                 for result in perpetual_db.objects.safe_iter_with_bounds(
                     Some(ObjectKey(object_id, VersionNumber::MIN)),
                     Some(ObjectKey(object_id, seq_number.next())),
                 ) {
-                    let (object_key, _) = result?;
+                    let (object_key, object) = result?;
                     assert_eq!(object_key.0, object_id);
-                    object_keys_to_delete.push(object_key);
+                    if !retention.is_empty() {
+                        if let StoreObject::Value(object) = object.into_inner() {
+                            if retention.retains_object(perpetual_db, &object)? {
+                                continue 'tombstones;
+                            }
+                        }
+                    }
+                    object_keys.push(object_key);
                 }
+                object_keys_to_delete.extend(object_keys);
             }
 
             wb.delete_batch(&perpetual_db.objects, object_keys_to_delete)?;
@@ -239,13 +447,29 @@ impl AuthorityStorePruner {
         checkpoint_content_to_prune: Vec<CheckpointContents>,
         effects_to_prune: &Vec<TransactionEffects>,
         metrics: Arc<AuthorityStorePruningMetrics>,
+        retention: &HistoryRetention,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("EffectsLivePruner");
 
+        // Retained transactions keep their data, effects, events and checkpoint mapping, while
+        // the contents of the checkpoints that included them are pruned as usual.
+        let mut retained_transactions = HashSet::new();
+        if !retention.is_empty() {
+            for effects in effects_to_prune {
+                if retention.retains_transaction(perpetual_db, effects)? {
+                    retained_transactions.insert(*effects.transaction_digest());
+                }
+            }
+            metrics
+                .num_retained_transactions
+                .inc_by(retained_transactions.len() as u64);
+        }
+
         let mut perpetual_batch = perpetual_db.objects.batch();
         let transactions: Vec<_> = checkpoint_content_to_prune
             .iter()
             .flat_map(|content| content.iter().map(|tx| tx.transaction))
+            .filter(|digest| !retained_transactions.contains(digest))
             .collect();
 
         perpetual_batch.delete_batch(&perpetual_db.transactions, transactions.iter())?;
@@ -257,6 +481,9 @@ impl AuthorityStorePruner {
 
         let mut effect_digests = vec![];
         for effects in effects_to_prune {
+            if retained_transactions.contains(effects.transaction_digest()) {
+                continue;
+            }
             let effects_digest = effects.digest();
             debug!("Pruning effects {:?}", effects_digest);
             effect_digests.push(effects_digest);
@@ -426,6 +653,7 @@ impl AuthorityStorePruner {
         indirect_objects_threshold: usize,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("PruneForEligibleEpochs");
+        let retention = HistoryRetention::new(&config.retain_history);
 
         let mut checkpoint_number = starting_checkpoint_number;
         let current_epoch = checkpoint_store
@@ -486,6 +714,7 @@ impl AuthorityStorePruner {
                             metrics.clone(),
                             indirect_objects_threshold,
                             !config.killswitch_tombstone_pruning,
+                            &retention,
                         )
                         .await?
                     }
@@ -498,6 +727,7 @@ impl AuthorityStorePruner {
                         checkpoint_content_to_prune,
                         &effects_to_prune,
                         metrics.clone(),
+                        &retention,
                     )?,
                 };
                 checkpoints_to_prune = vec![];
@@ -519,6 +749,7 @@ impl AuthorityStorePruner {
                         metrics.clone(),
                         indirect_objects_threshold,
                         !config.killswitch_tombstone_pruning,
+                        &retention,
                     )
                     .await?
                 }
@@ -531,6 +762,7 @@ impl AuthorityStorePruner {
                     checkpoint_content_to_prune,
                     &effects_to_prune,
                     metrics.clone(),
+                    &retention,
                 )?,
             };
         }
@@ -760,11 +992,28 @@ mod tests {
         get_store_object_pair, ObjectContentDigest, StoreData, StoreObject, StoreObjectPair,
         StoreObjectWrapper,
     };
+    use crate::authority::AuthorityStore;
+    use crate::checkpoints::CheckpointStore;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::ident_str;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{StructTag, TypeTag};
     use prometheus::Registry;
+    use std::collections::{BTreeMap, BTreeSet};
+    use sui_config::node::HistoryRetentionConfig;
     use sui_storage::mutex_table::RwLockTable;
-    use sui_types::base_types::ObjectDigest;
+    use sui_test_transaction_builder::TestTransactionBuilder;
+    use sui_types::base_types::{random_object_ref, ObjectDigest, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
     use sui_types::effects::TransactionEffects;
     use sui_types::effects::TransactionEffectsAPI;
+    use sui_types::effects::TransactionEvents;
+    use sui_types::event::Event;
+    use sui_types::execution_status::ExecutionStatus;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::message_envelope::Message;
+    use sui_types::messages_checkpoint::CheckpointContents;
+    use sui_types::transaction::VerifiedTransaction;
     use sui_types::{
         base_types::{ObjectID, SequenceNumber},
         object::Object,
@@ -774,7 +1023,7 @@ mod tests {
     use typed_store::rocks::{DBMap, MetricConf, ReadWriteOptions};
     use typed_store::Map;
 
-    use super::AuthorityStorePruner;
+    use super::{AuthorityStorePruner, HistoryRetention};
 
     fn get_keys_after_pruning(path: &Path) -> anyhow::Result<HashSet<ObjectKey>> {
         let perpetual_db_path = path.join(Path::new("perpetual"));
@@ -925,6 +1174,7 @@ mod tests {
                 metrics,
                 indirect_object_threshold,
                 true,
+                &HistoryRetention::default(),
             )
             .await
             .unwrap();
@@ -989,6 +1239,228 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_bulk_remove_live_objects_releases_indirect_objects() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        let (live, _, _) = generate_test_data(db.clone(), 1, 1, 1000, 1).unwrap();
+        assert_eq!(db.indirect_move_objects.keys().count(), 1000);
+
+        let object_refs: Vec<_> = live
+            .into_iter()
+            .map(|ObjectKey(id, version)| (id, version, ObjectDigest::MIN))
+            .collect();
+        AuthorityStore::bulk_remove_live_objects(&db, &object_refs).unwrap();
+
+        db.indirect_move_objects.flush().unwrap();
+        db.indirect_move_objects
+            .compact_range(&ObjectDigest::MIN, &ObjectDigest::MAX)
+            .unwrap();
+        db.indirect_move_objects
+            .compact_range(&ObjectDigest::MIN, &ObjectDigest::MAX)
+            .unwrap();
+        assert_eq!(db.indirect_move_objects.keys().count(), 0);
+        assert_eq!(db.objects.keys().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pruning_keeps_retained_objects() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let retained_owner = SuiAddress::random_for_testing_only();
+        let other_owner = SuiAddress::random_for_testing_only();
+        let ids = ObjectID::in_range(ObjectID::ZERO, 4).unwrap();
+
+        // Objects 0 and 1 are live, objects 2 and 3 were deleted at version 3.
+        let mut effects = TransactionEffects::default();
+        let mut expected = HashSet::new();
+        let db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        for (i, id) in ids.into_iter().enumerate() {
+            let owner = if i % 2 == 0 {
+                retained_owner
+            } else {
+                other_owner
+            };
+            for seq in 0..3 {
+                let key = ObjectKey(id, SequenceNumber::from_u64(seq));
+                let object = Object::with_id_owner_for_testing(id, owner);
+                db.objects
+                    .insert(&key, &get_store_object_pair(object, 0).0)
+                    .unwrap();
+                if i < 2 && seq < 2 {
+                    effects.unsafe_add_deleted_live_object_for_testing((
+                        id,
+                        key.1,
+                        ObjectDigest::MIN,
+                    ));
+                }
+                if owner == retained_owner || (i < 2 && seq == 2) {
+                    expected.insert(key);
+                }
+            }
+            if i >= 2 {
+                let tombstone = ObjectKey(id, SequenceNumber::from_u64(3));
+                db.objects
+                    .insert(&tombstone, &StoreObjectWrapper::V1(StoreObject::Deleted))
+                    .unwrap();
+                effects.unsafe_add_object_tombstone_for_testing((
+                    id,
+                    tombstone.1,
+                    ObjectDigest::MIN,
+                ));
+                if owner == retained_owner {
+                    expected.insert(tombstone);
+                }
+            }
+        }
+
+        let retention = HistoryRetention::new(&HistoryRetentionConfig {
+            addresses: vec![retained_owner],
+            ..Default::default()
+        });
+        AuthorityStorePruner::prune_objects(
+            vec![effects],
+            &db,
+            &lock_table(),
+            0,
+            AuthorityStorePruningMetrics::new_for_test(),
+            0,
+            true,
+            &retention,
+        )
+        .await
+        .unwrap();
+        drop(db);
+
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        assert_eq!(get_keys_after_pruning(&path).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_pruning_keeps_retained_transactions() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        let checkpoint_store = CheckpointStore::new(&path.join("checkpoints"));
+        let (sender, keypair) = get_key_pair::<AccountKeyPair>();
+        let retained_package = ObjectID::random();
+        let other_package = ObjectID::random();
+        let event_type = |name: &str, type_params| StructTag {
+            address: AccountAddress::from_hex_literal("0xaa").unwrap(),
+            module: Identifier::new("m").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params,
+        };
+
+        // Each transaction calls a package and emits events of the given package and type.
+        let transactions = [
+            // calls a retained package
+            (retained_package, None, true),
+            // emits an instantiation of a retained type
+            (
+                other_package,
+                Some((other_package, event_type("Retained", vec![TypeTag::U64]))),
+                true,
+            ),
+            // emits an event from a retained package
+            (
+                other_package,
+                Some((retained_package, event_type("Other", vec![]))),
+                true,
+            ),
+            (
+                other_package,
+                Some((other_package, event_type("Other", vec![]))),
+                false,
+            ),
+            (other_package, None, false),
+        ];
+        let mut all_effects = vec![];
+        let mut expected = vec![];
+        for (package, event, retained) in transactions {
+            let transaction = VerifiedTransaction::new_unchecked(
+                TestTransactionBuilder::new(sender, random_object_ref(), 1)
+                    .move_call(package, "m", "f", vec![])
+                    .build_and_sign(&keypair),
+            );
+            let events = TransactionEvents {
+                data: event
+                    .into_iter()
+                    .map(|(event_package, type_)| {
+                        Event::new(
+                            &event_package.into(),
+                            ident_str!("m"),
+                            sender,
+                            type_,
+                            vec![],
+                        )
+                    })
+                    .collect(),
+            };
+            let events_digest = (!events.data.is_empty()).then(|| events.digest());
+            let effects = TransactionEffects::new_from_execution_v2(
+                ExecutionStatus::Success,
+                0,
+                GasCostSummary::default(),
+                vec![],
+                BTreeSet::new(),
+                *transaction.digest(),
+                SequenceNumber::from_u64(1),
+                BTreeMap::new(),
+                None,
+                events_digest,
+                vec![],
+            );
+
+            db.transactions
+                .insert(transaction.digest(), transaction.serializable_ref())
+                .unwrap();
+            db.effects.insert(&effects.digest(), &effects).unwrap();
+            db.executed_effects
+                .insert(transaction.digest(), &effects.digest())
+                .unwrap();
+            if let Some(events_digest) = events_digest {
+                for (i, event) in events.data.iter().enumerate() {
+                    db.events.insert(&(events_digest, i), event).unwrap();
+                }
+            }
+            expected.push((*transaction.digest(), effects.digest(), retained));
+            all_effects.push(effects);
+        }
+
+        let retention = HistoryRetention::new(&HistoryRetentionConfig {
+            packages: vec![retained_package],
+            move_types: vec![event_type("Retained", vec![])],
+            ..Default::default()
+        });
+        let contents = CheckpointContents::new_with_digests_only_for_tests(
+            all_effects
+                .iter()
+                .map(|effects| effects.execution_digests()),
+        );
+        AuthorityStorePruner::prune_checkpoints(
+            &db,
+            &checkpoint_store,
+            None,
+            0,
+            vec![],
+            vec![contents],
+            &all_effects,
+            AuthorityStorePruningMetrics::new_for_test(),
+            &retention,
+        )
+        .unwrap();
+
+        for ((digest, effects_digest, retained), effects) in expected.into_iter().zip(all_effects) {
+            assert_eq!(db.transactions.contains_key(&digest).unwrap(), retained);
+            assert_eq!(db.executed_effects.contains_key(&digest).unwrap(), retained);
+            assert_eq!(db.effects.contains_key(&effects_digest).unwrap(), retained);
+            // Events are pruned with range deletes, which reads ignore, so only check that the
+            // retained ones are kept.
+            if let (Some(events_digest), true) = (effects.events_digest(), retained) {
+                assert!(db.events.contains_key(&(*events_digest, 0)).unwrap());
+            }
+        }
+    }
+
     #[cfg(not(target_env = "msvc"))]
     #[tokio::test]
     async fn test_db_size_after_compaction() -> Result<(), anyhow::Error> {
@@ -1051,6 +1523,7 @@ mod tests {
             metrics,
             0,
             true,
+            &HistoryRetention::default(),
         )
         .await;
         info!("Total pruned keys = {:?}", total_pruned);
@@ -1095,7 +1568,7 @@ mod pprof_tests {
     use typed_store::rocks::DBMap;
     use typed_store::Map;
 
-    use super::AuthorityStorePruner;
+    use super::{AuthorityStorePruner, HistoryRetention};
 
     fn insert_keys(
         objects: &DBMap<ObjectKey, StoreObjectWrapper>,
@@ -1173,6 +1646,7 @@ mod pprof_tests {
             metrics,
             1,
             true,
+            &HistoryRetention::default(),
         )
         .await?;
         let guard = pprof::ProfilerGuardBuilder::default()
@@ -1211,6 +1685,7 @@ mod pprof_tests {
             metrics,
             1,
             true,
+            &HistoryRetention::default(),
         )
         .await?;
         if let Ok(()) = perpetual_db.objects.flush() {
@@ -1331,6 +1331,7 @@ impl ValidatorService {
     fn handle_traffic_resp<T>(
         &self,
         client: Option<IpAddr>,
+        method: &str,
         wrapped_response: WrappedServiceResponse<T>,
     ) -> Result<tonic::Response<T>, tonic::Status> {
         let (error, spam_weight, unwrapped_response) = match wrapped_response {
@@ -1352,6 +1353,7 @@ impl ValidatorService {
                     (error_weight, error_type)
                 }),
                 spam_weight,
+                method: Some(method.to_string()),
                 timestamp: SystemTime::now(),
             })
         }
@@ -1402,9 +1404,11 @@ macro_rules! handle_with_decoration {
         // check if either IP is blocked, in which case return early
         $self.handle_traffic_req(client.clone()).await?;
 
-        // handle traffic tallying
+        // handle traffic tallying. Methods are weighed by the name of their
+        // handler, without the `_impl` suffix (e.g. `handle_certificate_v2`).
         let wrapped_response = $self.$func_name($request).await;
-        $self.handle_traffic_resp(client, wrapped_response)
+        let method = stringify!($func_name).trim_end_matches("_impl");
+        $self.handle_traffic_resp(client, method, wrapped_response)
     }};
 }
 
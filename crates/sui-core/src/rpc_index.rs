@@ -11,6 +11,7 @@ use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -30,16 +31,19 @@ use sui_types::object::Object;
 use sui_types::object::Owner;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::BackingPackageStore;
+use sui_types::storage::BalanceInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
+use sui_types::TypeTag;
 use tracing::{debug, info};
-use typed_store::rocks::{DBMap, MetricConf};
+use typed_store::rocks::{default_db_options, DBMap, DBMapTableConfigMap, DBOptions, MetricConf};
+use typed_store::rocksdb::{CompactionDecision, MergeOperands};
 use typed_store::traits::Map;
 use typed_store::traits::{TableSummary, TypedStoreDebug};
 use typed_store::DBMapUtils;
 use typed_store::TypedStoreError;
 
-const CURRENT_DB_VERSION: u64 = 0;
+const CURRENT_DB_VERSION: u64 = 1;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct MetadataInfo {
@@ -91,6 +95,54 @@ pub struct CoinIndexInfo {
     pub treasury_object_id: Option<ObjectID>,
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BalanceKey {
+    pub owner: SuiAddress,
+    pub coin_type: StructTag,
+}
+
+/// A change to the balance of a coin type owned by an account. Entries in the `balance` table are
+/// merged by summing them, so the stored value is the account's total balance and coin count.
+#[derive(Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct BalanceIndexInfo {
+    pub balance_delta: i128,
+    pub coin_object_count_delta: i64,
+}
+
+impl BalanceIndexInfo {
+    fn new(balance: u64) -> Self {
+        Self {
+            balance_delta: balance as i128,
+            coin_object_count_delta: 1,
+        }
+    }
+
+    fn add(&mut self, other: Self) {
+        self.balance_delta += other.balance_delta;
+        self.coin_object_count_delta += other.coin_object_count_delta;
+    }
+
+    fn sub(&mut self, other: Self) {
+        self.balance_delta -= other.balance_delta;
+        self.coin_object_count_delta -= other.coin_object_count_delta;
+    }
+
+    fn is_zero(&self) -> bool {
+        self.balance_delta == 0 && self.coin_object_count_delta == 0
+    }
+}
+
+impl From<BalanceIndexInfo> for BalanceInfo {
+    fn from(info: BalanceIndexInfo) -> Self {
+        // Checkpoints are not necessarily indexed in order, so a balance can be briefly negative
+        // if a coin is spent in a checkpoint that is indexed before the one that created it.
+        BalanceInfo {
+            balance: info.balance_delta.clamp(0, u64::MAX as i128) as u64,
+            coin_object_count: info.coin_object_count_delta.max(0) as u64,
+        }
+    }
+}
+
 impl CoinIndexInfo {
     fn merge(self, other: Self) -> Self {
         Self {
@@ -143,6 +195,12 @@ struct IndexStoreTables {
     /// Allows looking up information related to published Coins, like the ObjectID of its
     /// coorisponding CoinMetadata.
     coin: DBMap<CoinIndexKey, CoinIndexInfo>,
+
+    /// An index of the balance of each coin type owned by an account.
+    ///
+    /// Updated by merging in changes to balances (see `balance_table_options`), so that
+    /// checkpoints can be indexed without reading the existing balances.
+    balance: DBMap<BalanceKey, BalanceIndexInfo>,
     // NOTE: Authors and Reviewers before adding any new tables ensure that they are either:
     // - bounded in size by the live object set
     // - are prune-able and have corresponding logic in the `prune` function
@@ -154,7 +212,10 @@ impl IndexStoreTables {
             path.into(),
             MetricConf::new("rpc-index"),
             None,
-            None,
+            Some(DBMapTableConfigMap::new(BTreeMap::from([(
+                "balance".to_string(),
+                balance_table_options(),
+            )]))),
         )
     }
 
@@ -298,10 +359,15 @@ impl IndexStoreTables {
         // object indexes
         {
             let mut coin_index = HashMap::new();
+            let mut balance_changes: HashMap<BalanceKey, BalanceIndexInfo> = HashMap::new();
 
             for tx in &checkpoint.transactions {
                 // determine changes from removed objects
                 for removed_object in tx.removed_objects_pre_version() {
+                    if let Some((key, info)) = try_create_balance_info(removed_object) {
+                        balance_changes.entry(key).or_default().sub(info);
+                    }
+
                     match removed_object.owner() {
                         Owner::AddressOwner(address) => {
                             let owner_key = OwnerIndexKey::new(*address, removed_object.id());
@@ -321,6 +387,16 @@ impl IndexStoreTables {
 
                 // determine changes from changed objects
                 for (object, old_object) in tx.changed_objects() {
+                    // A coin's balance is replaced by its new balance, even if its owner is the
+                    // same.
+                    if let Some((key, info)) = old_object.and_then(try_create_balance_info) {
+                        balance_changes.entry(key).or_default().sub(info);
+                    }
+
+                    if let Some((key, info)) = try_create_balance_info(object) {
+                        balance_changes.entry(key).or_default().add(info);
+                    }
+
                     if let Some(old_object) = old_object {
                         if old_object.owner() != object.owner() {
                             match old_object.owner() {
@@ -389,6 +465,12 @@ impl IndexStoreTables {
             }
 
             batch.insert_batch(&self.coin, coin_index)?;
+            batch.merge_batch(
+                &self.balance,
+                balance_changes
+                    .into_iter()
+                    .filter(|(_, info)| !info.is_zero()),
+            )?;
         }
 
         batch.write()?;
@@ -453,6 +535,37 @@ impl IndexStoreTables {
         };
         self.coin.get(&key)
     }
+
+    fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<Option<BalanceIndexInfo>, TypedStoreError> {
+        let key = BalanceKey {
+            owner: owner.to_owned(),
+            coin_type: coin_type.to_owned(),
+        };
+        self.balance.get(&key)
+    }
+
+    fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<StructTag>,
+    ) -> Result<impl Iterator<Item = (BalanceKey, BalanceIndexInfo)> + '_, TypedStoreError> {
+        // Keys are ordered by their serialized bytes, so all of an owner's balances are
+        // contiguous, and start no earlier than its balance of the smallest possible coin type.
+        let start = BalanceKey {
+            owner,
+            coin_type: cursor.unwrap_or_else(smallest_struct_tag),
+        };
+
+        Ok(self
+            .balance
+            .unbounded_iter()
+            .skip_to(&start)?
+            .take_while(move |(key, _)| key.owner == owner))
+    }
 }
 
 pub struct RpcIndexStore {
@@ -558,6 +671,22 @@ impl RpcIndexStore {
     ) -> Result<Option<CoinIndexInfo>, TypedStoreError> {
         self.tables.get_coin_info(coin_type)
     }
+
+    pub fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<Option<BalanceIndexInfo>, TypedStoreError> {
+        self.tables.get_balance(owner, coin_type)
+    }
+
+    pub fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<StructTag>,
+    ) -> Result<impl Iterator<Item = (BalanceKey, BalanceIndexInfo)> + '_, TypedStoreError> {
+        self.tables.balance_iter(owner, cursor)
+    }
 }
 
 fn try_create_dynamic_field_info(
@@ -602,6 +731,77 @@ fn try_create_dynamic_field_info(
     }))
 }
 
+/// The key and balance to index for `object`, if it is a coin owned by an account.
+fn try_create_balance_info(object: &Object) -> Option<(BalanceKey, BalanceIndexInfo)> {
+    let Owner::AddressOwner(owner) = object.owner() else {
+        return None;
+    };
+
+    let TypeTag::Struct(coin_type) = object.coin_type_maybe()? else {
+        return None;
+    };
+
+    let coin = object.as_coin_maybe()?;
+    Some((
+        BalanceKey {
+            owner: *owner,
+            coin_type: *coin_type,
+        },
+        BalanceIndexInfo::new(coin.value()),
+    ))
+}
+
+/// The struct tag whose serialized form is the smallest: the address and every other field is
+/// as small as possible, and `A` is the shortest identifier with the smallest first byte.
+fn smallest_struct_tag() -> StructTag {
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+
+    StructTag {
+        address: AccountAddress::ZERO,
+        module: Identifier::new("A").unwrap(),
+        name: Identifier::new("A").unwrap(),
+        type_params: vec![],
+    }
+}
+
+/// Changes to balances are merged by summing them, which lets checkpoints be indexed in any order.
+fn balance_table_options() -> DBOptions {
+    let mut options = default_db_options();
+    options
+        .options
+        .set_merge_operator_associative("balance_merge", balance_merge_operator);
+    options
+        .options
+        .set_compaction_filter("balance_zero_filter", balance_compaction_filter);
+    options
+}
+
+fn balance_merge_operator(
+    _key: &[u8],
+    existing: Option<&[u8]>,
+    operands: &MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut total = match existing {
+        Some(bytes) => bcs::from_bytes::<BalanceIndexInfo>(bytes).ok()?,
+        None => BalanceIndexInfo::default(),
+    };
+
+    for operand in operands {
+        total.add(bcs::from_bytes(operand).ok()?);
+    }
+
+    bcs::to_bytes(&total).ok()
+}
+
+/// Drop balances once the account no longer owns any coins of their type.
+fn balance_compaction_filter(_level: u32, _key: &[u8], value: &[u8]) -> CompactionDecision {
+    match bcs::from_bytes::<BalanceIndexInfo>(value) {
+        Ok(info) if info.is_zero() => CompactionDecision::Remove,
+        _ => CompactionDecision::Keep,
+    }
+}
+
 fn try_create_coin_index_info(object: &Object) -> Option<(CoinIndexKey, CoinIndexInfo)> {
     use sui_types::coin::CoinMetadata;
     use sui_types::coin::TreasuryCap;
@@ -648,6 +848,8 @@ struct RpcLiveObjectIndexer<'a> {
     tables: &'a IndexStoreTables,
     batch: typed_store::rocks::DBBatch,
     coin_index: &'a Mutex<HashMap<CoinIndexKey, CoinIndexInfo>>,
+    /// Balances of the coins seen since the batch was last written.
+    balances: HashMap<BalanceKey, BalanceIndexInfo>,
     resolver: Box<dyn LayoutResolver + 'a>,
 }
 
@@ -659,6 +861,7 @@ impl<'a> ParMakeLiveObjectIndexer for RpcParLiveObjectSetIndexer<'a> {
             tables: self.tables,
             batch: self.tables.owner.batch(),
             coin_index: self.coin_index,
+            balances: HashMap::new(),
             resolver: self
                 .epoch_store
                 .executor()
@@ -711,16 +914,24 @@ impl<'a> LiveObjectIndexer for RpcLiveObjectIndexer<'a> {
             }
         }
 
+        if let Some((key, info)) = try_create_balance_info(&object) {
+            self.balances.entry(key).or_default().add(info);
+        }
+
         // If the batch size grows to greater that 128MB then write out to the DB so that the
         // data we need to hold in memory doesn't grown unbounded.
         if self.batch.size_in_bytes() >= 1 << 27 {
+            self.batch
+                .merge_batch(&self.tables.balance, std::mem::take(&mut self.balances))?;
             std::mem::replace(&mut self.batch, self.tables.owner.batch()).write()?;
         }
 
         Ok(())
     }
 
-    fn finish(self) -> Result<(), StorageError> {
+    fn finish(mut self) -> Result<(), StorageError> {
+        self.batch
+            .merge_batch(&self.tables.balance, std::mem::take(&mut self.balances))?;
         self.batch.write()?;
         Ok(())
     }
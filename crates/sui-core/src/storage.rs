@@ -22,6 +22,7 @@ use sui_types::object::Object;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::error::Result;
 use sui_types::storage::AccountOwnedObjectInfo;
+use sui_types::storage::BalanceInfo;
 use sui_types::storage::CoinInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
@@ -37,6 +38,7 @@ use crate::authority::AuthorityState;
 use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::ExecutionCacheTraitPointers;
+use crate::rpc_index::BalanceKey;
 use crate::rpc_index::CoinIndexInfo;
 use crate::rpc_index::OwnerIndexInfo;
 use crate::rpc_index::OwnerIndexKey;
@@ -532,4 +534,29 @@ impl RpcIndexes for RpcIndexStore {
             )
             .pipe(Ok)
     }
+    fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &StructTag,
+    ) -> sui_types::storage::error::Result<Option<BalanceInfo>> {
+        self.get_balance(owner, coin_type)?
+            .map(BalanceInfo::from)
+            .pipe(Ok)
+    }
+
+    fn balance_iter(
+        &self,
+        owner: &SuiAddress,
+        cursor: Option<StructTag>,
+    ) -> sui_types::storage::error::Result<Box<dyn Iterator<Item = (StructTag, BalanceInfo)> + '_>>
+    {
+        let iter = self
+            .balance_iter(*owner, cursor)?
+            .map(|(BalanceKey { coin_type, .. }, info)| (coin_type, BalanceInfo::from(info)))
+            // Balances only reach zero once the owner has spent all of its coins of this type,
+            // and are removed during compaction.
+            .filter(|(_, info)| info.coin_object_count > 0);
+
+        Ok(Box::new(iter) as _)
+    }
 }
@@ -3,7 +3,8 @@
 
 use prometheus::{
     register_int_counter_vec_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry, IntCounter, IntCounterVec, IntGauge, Registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, IntCounter,
+    IntCounterVec, IntGauge, IntGaugeVec, Registry,
 };
 
 #[derive(Clone)]
@@ -24,6 +25,8 @@ pub struct TrafficControllerMetrics {
     pub highest_proxied_spam_rate: IntGauge,
     pub highest_direct_error_rate: IntGauge,
     pub highest_proxied_error_rate: IntGauge,
+    pub token_bucket_tracked_clients: IntGaugeVec,
+    pub token_bucket_depleted_clients: IntGaugeVec,
 }
 
 impl TrafficControllerMetrics {
@@ -129,6 +132,21 @@ impl TrafficControllerMetrics {
                 registry
             )
             .unwrap(),
+            token_bucket_tracked_clients: register_int_gauge_vec_with_registry!(
+                "traffic_control_token_bucket_tracked_clients",
+                "Number of client buckets tracked by the token bucket policy",
+                &["policy"],
+                registry
+            )
+            .unwrap(),
+            token_bucket_depleted_clients: register_int_gauge_vec_with_registry!(
+                "traffic_control_token_bucket_depleted_clients",
+                "Number of clients whose token bucket cannot currently cover \
+                    a request of default weight",
+                &["policy"],
+                registry
+            )
+            .unwrap(),
         }
     }
 
@@ -8,6 +8,8 @@ pub mod policies;
 
 use dashmap::DashMap;
 use fs::File;
+use ipnetwork::IpNetwork;
+use parking_lot::RwLock;
 use prometheus::IntGauge;
 use std::fs;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -17,7 +19,7 @@ use std::sync::Arc;
 use self::metrics::TrafficControllerMetrics;
 use crate::traffic_controller::nodefw_client::{BlockAddress, BlockAddresses, NodeFWClient};
 use crate::traffic_controller::policies::{
-    Policy, PolicyResponse, TrafficControlPolicy, TrafficTally,
+    ClientBucketState, Policy, PolicyResponse, TrafficControlPolicy, TrafficTally,
 };
 use mysten_metrics::spawn_monitored_task;
 use rand::Rng;
@@ -30,6 +32,9 @@ use tracing::{debug, error, info, trace, warn};
 
 pub const METRICS_INTERVAL_SECS: u64 = 2;
 pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 300;
+/// Maximum number of client buckets per policy exposed through
+/// `TrafficControllerState`.
+pub const MAX_REPORTED_CLIENT_BUCKETS: usize = 1_000;
 
 type Blocklist = Arc<DashMap<IpAddr, SystemTime>>;
 
@@ -49,11 +54,87 @@ enum Acl {
     Allowlist(Vec<IpAddr>),
 }
 
+/// CIDR based rules which are evaluated before, and take precedence
+/// over, the blocklists or allowlist.
+#[derive(Clone, Default)]
+struct CidrRules {
+    allow: Vec<IpNetwork>,
+    block: Vec<IpNetwork>,
+}
+
+impl CidrRules {
+    fn from_config(policy_config: &PolicyConfig) -> Self {
+        let parse = |cidrs: &[String]| -> Vec<IpNetwork> {
+            cidrs
+                .iter()
+                .map(|cidr| {
+                    cidr.parse::<IpNetwork>()
+                        .unwrap_or_else(|_| panic!("Failed to parse CIDR range: {:?}", cidr))
+                })
+                .collect()
+        };
+        Self {
+            allow: parse(&policy_config.cidr_allow_list),
+            block: parse(&policy_config.cidr_block_list),
+        }
+    }
+
+    /// Returns Some(true) if the client is explicitly allowed, Some(false)
+    /// if it is explicitly blocked, and None if no rule applies. Both the
+    /// connection IP and, behind a proxy, the IP of the proxied client are
+    /// checked. If either of them is blocked, the request is blocked, as the
+    /// allow list of one should not let through a blocked other.
+    fn check(&self, client: &Option<IpAddr>, proxied_client: &Option<IpAddr>) -> Option<bool> {
+        let client = self.check_ip(client);
+        let proxied_client = self.check_ip(proxied_client);
+        match (client, proxied_client) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (None, None) => None,
+        }
+    }
+
+    fn check_ip(&self, ip: &Option<IpAddr>) -> Option<bool> {
+        let ip = (*ip)?;
+        if self.allow.iter().any(|range| range.contains(ip)) {
+            Some(true)
+        } else if self.block.iter().any(|range| range.contains(ip)) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Read-only view into the per-client state of the traffic control
+/// policies, refreshed by the tally loop every `METRICS_INTERVAL_SECS`.
+/// Only policies which track per-client state (currently `TokenBucket`)
+/// populate it.
+#[derive(Clone, Default)]
+pub struct TrafficControllerState {
+    spam_buckets: Arc<RwLock<Vec<ClientBucketState>>>,
+    error_buckets: Arc<RwLock<Vec<ClientBucketState>>>,
+}
+
+impl TrafficControllerState {
+    /// Most throttled clients of the spam policy, in ascending order of tokens.
+    pub fn spam_client_buckets(&self) -> Vec<ClientBucketState> {
+        self.spam_buckets.read().clone()
+    }
+
+    /// Most throttled clients of the error policy, in ascending order of tokens.
+    pub fn error_client_buckets(&self) -> Vec<ClientBucketState> {
+        self.error_buckets.read().clone()
+    }
+}
+
 #[derive(Clone)]
 pub struct TrafficController {
     tally_channel: Option<mpsc::Sender<TrafficTally>>,
     acl: Acl,
+    cidr_rules: Arc<CidrRules>,
     metrics: Arc<TrafficControllerMetrics>,
+    state: TrafficControllerState,
     dry_run_mode: bool,
 }
 
@@ -83,6 +164,23 @@ impl TrafficController {
         metrics: TrafficControllerMetrics,
         fw_config: Option<RemoteFirewallConfig>,
     ) -> Self {
+        Self::init_with_state(
+            policy_config,
+            metrics,
+            fw_config,
+            TrafficControllerState::default(),
+        )
+    }
+
+    /// Same as `init`, but publishes policy state into the provided handle,
+    /// so that it can be inspected by the caller (e.g. the admin server).
+    pub fn init_with_state(
+        policy_config: PolicyConfig,
+        metrics: TrafficControllerMetrics,
+        fw_config: Option<RemoteFirewallConfig>,
+        state: TrafficControllerState,
+    ) -> Self {
+        let cidr_rules = Arc::new(CidrRules::from_config(&policy_config));
         match policy_config.allow_list {
             Some(allow_list) => {
                 let allowlist = allow_list
@@ -96,11 +194,13 @@ impl TrafficController {
                 Self {
                     tally_channel: None,
                     acl: Acl::Allowlist(allowlist),
+                    cidr_rules,
                     metrics: Arc::new(metrics),
+                    state,
                     dry_run_mode: policy_config.dry_run,
                 }
             }
-            None => Self::spawn(policy_config, metrics, fw_config),
+            None => Self::spawn(policy_config, metrics, fw_config, cidr_rules, state),
         }
     }
 
@@ -108,6 +208,8 @@ impl TrafficController {
         policy_config: PolicyConfig,
         metrics: TrafficControllerMetrics,
         fw_config: Option<RemoteFirewallConfig>,
+        cidr_rules: Arc<CidrRules>,
+        state: TrafficControllerState,
     ) -> Self {
         let metrics = Arc::new(metrics);
         let (tx, rx) = mpsc::channel(policy_config.channel_capacity);
@@ -137,6 +239,7 @@ impl TrafficController {
             fw_config,
             tally_loop_blocklists,
             tally_loop_metrics,
+            state.clone(),
             mem_drainfile_present,
         ));
         spawn_monitored_task!(run_clear_blocklists_loop(
@@ -146,7 +249,9 @@ impl TrafficController {
         Self {
             tally_channel: Some(tx),
             acl: Acl::Blocklists(blocklists),
+            cidr_rules,
             metrics: metrics.clone(),
+            state,
             dry_run_mode,
         }
     }
@@ -199,6 +304,10 @@ impl TrafficController {
             }
         };
 
+        if let Some(allowed) = self.cidr_rules.check(client, proxied_client) {
+            return check_with_dry_run_maybe(allowed);
+        }
+
         match &self.acl {
             Acl::Allowlist(allowlist) => {
                 let allowed = client.is_none() || allowlist.contains(&client.unwrap());
@@ -239,6 +348,10 @@ impl TrafficController {
         self.dry_run_mode
     }
 
+    pub fn state(&self) -> TrafficControllerState {
+        self.state.clone()
+    }
+
     async fn check_and_clear_blocklist(
         &self,
         client: &Option<IpAddr>,
@@ -296,6 +409,7 @@ async fn run_tally_loop(
     fw_config: Option<RemoteFirewallConfig>,
     blocklists: Blocklists,
     metrics: Arc<TrafficControllerMetrics>,
+    state: TrafficControllerState,
     mut mem_drainfile_present: bool,
 ) {
     let mut spam_policy = TrafficControlPolicy::from_spam_config(policy_config.clone()).await;
@@ -410,6 +524,30 @@ async fn run_tally_loop(
                     );
                 }
             }
+            if let TrafficControlPolicy::TokenBucket(spam_policy) = &spam_policy {
+                metrics
+                    .token_bucket_tracked_clients
+                    .with_label_values(&["spam"])
+                    .set(spam_policy.num_tracked_clients() as i64);
+                metrics
+                    .token_bucket_depleted_clients
+                    .with_label_values(&["spam"])
+                    .set(spam_policy.num_depleted_clients() as i64);
+                *state.spam_buckets.write() =
+                    spam_policy.client_bucket_states(MAX_REPORTED_CLIENT_BUCKETS);
+            }
+            if let TrafficControlPolicy::TokenBucket(error_policy) = &error_policy {
+                metrics
+                    .token_bucket_tracked_clients
+                    .with_label_values(&["error"])
+                    .set(error_policy.num_tracked_clients() as i64);
+                metrics
+                    .token_bucket_depleted_clients
+                    .with_label_values(&["error"])
+                    .set(error_policy.num_depleted_clients() as i64);
+                *state.error_buckets.write() =
+                    error_policy.client_bucket_states(MAX_REPORTED_CLIENT_BUCKETS);
+            }
             metric_timer = Instant::now();
         }
     }
@@ -846,3 +984,80 @@ pub fn parse_ip(ip: &str) -> Option<IpAddr> {
             })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_macros::sim_test;
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    fn cidr_config(allow: &[&str], block: &[&str]) -> PolicyConfig {
+        PolicyConfig {
+            cidr_allow_list: allow.iter().map(|s| s.to_string()).collect(),
+            cidr_block_list: block.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cidr_rules() {
+        let rules = CidrRules::from_config(&cidr_config(
+            &["10.0.0.0/8", "2001:db8::/32"],
+            &["10.1.0.0/16", "192.168.0.0/16"],
+        ));
+
+        // no rule applies
+        assert_eq!(rules.check(&None, &None), None);
+        assert_eq!(rules.check(&ip("8.8.8.8"), &None), None);
+        // allowed, including when also in a blocked range
+        assert_eq!(rules.check(&ip("10.2.3.4"), &None), Some(true));
+        assert_eq!(rules.check(&ip("10.1.2.3"), &None), Some(true));
+        assert_eq!(rules.check(&ip("2001:db8::1"), &None), Some(true));
+        // blocked
+        assert_eq!(rules.check(&ip("192.168.1.1"), &None), Some(false));
+        assert_eq!(rules.check(&ip("2001:db9::1"), &None), None);
+    }
+
+    #[test]
+    fn test_cidr_rules_proxied_client() {
+        let rules = CidrRules::from_config(&cidr_config(&["10.0.0.0/8"], &["192.168.0.0/16"]));
+
+        // the proxied client is matched when the proxy itself is not
+        assert_eq!(rules.check(&ip("8.8.8.8"), &ip("192.168.1.1")), Some(false));
+        assert_eq!(rules.check(&ip("8.8.8.8"), &ip("10.0.0.1")), Some(true));
+        assert_eq!(rules.check(&ip("8.8.8.8"), &ip("1.1.1.1")), None);
+        // an allowed proxy does not let a blocked client through
+        assert_eq!(
+            rules.check(&ip("10.0.0.1"), &ip("192.168.1.1")),
+            Some(false)
+        );
+        assert_eq!(rules.check(&ip("10.0.0.1"), &ip("1.1.1.1")), Some(true));
+    }
+
+    #[sim_test]
+    async fn test_cidr_rules_take_precedence_over_allowlist() {
+        let controller = TrafficController::init(
+            PolicyConfig {
+                allow_list: Some(vec!["1.1.1.1".to_string(), "192.168.1.1".to_string()]),
+                dry_run: false,
+                ..cidr_config(&["10.0.0.0/8"], &["192.168.0.0/16"])
+            },
+            TrafficControllerMetrics::new_for_tests(),
+            None,
+        );
+
+        // in the allowlist
+        assert!(controller.check(&ip("1.1.1.1"), &None).await);
+        // neither in the allowlist nor in a CIDR range
+        assert!(!controller.check(&ip("2.2.2.2"), &None).await);
+        // not in the allowlist, but in an allowed CIDR range
+        assert!(controller.check(&ip("10.1.1.1"), &None).await);
+        // in the allowlist, but in a blocked CIDR range
+        assert!(!controller.check(&ip("192.168.1.1"), &None).await);
+        // proxied client in a blocked CIDR range
+        assert!(!controller.check(&ip("1.1.1.1"), &ip("192.168.2.2")).await);
+    }
+}
@@ -13,13 +13,15 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use std::time::Duration;
 use std::time::{Instant, SystemTime};
-use sui_types::traffic_control::{FreqThresholdConfig, PolicyConfig, PolicyType, Weight};
+use sui_types::traffic_control::{
+    FreqThresholdConfig, PolicyConfig, PolicyType, TokenBucketConfig, Weight,
+};
 use tracing::{info, trace};
 
 const HIGHEST_RATES_CAPACITY: usize = 20;
 
 /// The type of request client.
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
 enum ClientType {
     Direct,
     ThroughFullnode,
@@ -228,6 +230,9 @@ pub struct TrafficTally {
     pub through_fullnode: Option<IpAddr>,
     pub error_info: Option<(Weight, String)>,
     pub spam_weight: Weight,
+    /// Name of the method or endpoint being called, if known. Used by
+    /// policies that weigh requests differently per method.
+    pub method: Option<String>,
     pub timestamp: SystemTime,
 }
 
@@ -243,6 +248,7 @@ impl TrafficTally {
             through_fullnode,
             error_info,
             spam_weight,
+            method: None,
             timestamp: SystemTime::now(),
         }
     }
@@ -265,6 +271,7 @@ pub trait Policy {
 // not object safe, so we can't use a trait object instead
 pub enum TrafficControlPolicy {
     FreqThreshold(FreqThresholdPolicy),
+    TokenBucket(TokenBucketPolicy),
     NoOp(NoOpPolicy),
     // Test policies below this point
     TestNConnIP(TestNConnIPPolicy),
@@ -276,6 +283,7 @@ impl Policy for TrafficControlPolicy {
         match self {
             TrafficControlPolicy::NoOp(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::FreqThreshold(policy) => policy.handle_tally(tally),
+            TrafficControlPolicy::TokenBucket(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::TestNConnIP(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::TestPanicOnInvocation(policy) => policy.handle_tally(tally),
         }
@@ -285,6 +293,7 @@ impl Policy for TrafficControlPolicy {
         match self {
             TrafficControlPolicy::NoOp(policy) => policy.policy_config(),
             TrafficControlPolicy::FreqThreshold(policy) => policy.policy_config(),
+            TrafficControlPolicy::TokenBucket(policy) => policy.policy_config(),
             TrafficControlPolicy::TestNConnIP(policy) => policy.policy_config(),
             TrafficControlPolicy::TestPanicOnInvocation(policy) => policy.policy_config(),
         }
@@ -304,6 +313,9 @@ impl TrafficControlPolicy {
             PolicyType::FreqThreshold(freq_threshold_config) => Self::FreqThreshold(
                 FreqThresholdPolicy::new(policy_config, freq_threshold_config),
             ),
+            PolicyType::TokenBucket(token_bucket_config) => {
+                Self::TokenBucket(TokenBucketPolicy::new(policy_config, token_bucket_config))
+            }
             PolicyType::TestNConnIP(n) => {
                 Self::TestNConnIP(TestNConnIPPolicy::new(policy_config, n).await)
             }
@@ -416,6 +428,229 @@ impl FreqThresholdPolicy {
     }
 }
 
+/// Snapshot of the bucket of a single client, as tracked by `TokenBucketPolicy`.
+#[derive(Clone, Debug)]
+pub struct ClientBucketState {
+    pub client: IpAddr,
+    pub proxied: bool,
+    pub tokens: f64,
+    pub capacity: f64,
+    pub last_method: Option<String>,
+    pub last_seen: SystemTime,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: SystemTime,
+    last_method: Option<String>,
+}
+
+impl TokenBucket {
+    fn new(capacity: f64, now: SystemTime) -> Self {
+        Self {
+            tokens: capacity,
+            last_refill: now,
+            last_method: None,
+        }
+    }
+
+    /// Returns the number of tokens the bucket would hold at `now`, without
+    /// updating it.
+    fn tokens_at(&self, capacity: f64, refill_rate: f64, now: SystemTime) -> f64 {
+        // Tallies may arrive slightly out of order, in which case we simply
+        // do not refill rather than moving `last_refill` backwards.
+        let elapsed = now
+            .duration_since(self.last_refill)
+            .unwrap_or_default()
+            .as_secs_f64();
+        (self.tokens + elapsed * refill_rate).min(capacity)
+    }
+
+    fn refill(&mut self, capacity: f64, refill_rate: f64, now: SystemTime) {
+        self.tokens = self.tokens_at(capacity, refill_rate, now);
+        if now > self.last_refill {
+            self.last_refill = now;
+        }
+    }
+
+    /// Takes `cost` tokens out of the bucket if it holds enough of them.
+    /// Returns false, leaving the bucket untouched, otherwise.
+    fn try_consume(&mut self, cost: f64) -> bool {
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub struct TokenBucketPolicy {
+    config: PolicyConfig,
+    bucket_config: TokenBucketConfig,
+    buckets: HashMap<(IpAddr, ClientType), TokenBucket>,
+}
+
+impl TokenBucketPolicy {
+    pub fn new(config: PolicyConfig, bucket_config: TokenBucketConfig) -> Self {
+        Self {
+            config,
+            bucket_config,
+            buckets: HashMap::new(),
+        }
+    }
+
+    pub fn handle_tally(&mut self, tally: TrafficTally) -> PolicyResponse {
+        let cost = self.bucket_config.method_weight(tally.method.as_deref());
+        let block_client = tally.direct.filter(|client| {
+            !self.consume(
+                *client,
+                ClientType::Direct,
+                cost,
+                tally.method.as_ref(),
+                tally.timestamp,
+            )
+        });
+        let block_proxied_client = tally.through_fullnode.filter(|client| {
+            !self.consume(
+                *client,
+                ClientType::ThroughFullnode,
+                cost,
+                tally.method.as_ref(),
+                tally.timestamp,
+            )
+        });
+        self.maybe_evict(tally.timestamp);
+        PolicyResponse {
+            block_client,
+            block_proxied_client,
+        }
+    }
+
+    /// Charges `cost` tokens to the bucket of `client`, returning false if
+    /// the bucket did not hold enough tokens to cover it.
+    fn consume(
+        &mut self,
+        client: IpAddr,
+        client_type: ClientType,
+        cost: f64,
+        method: Option<&String>,
+        now: SystemTime,
+    ) -> bool {
+        let (capacity, refill_rate) = self.bucket_params(client_type);
+        let bucket = self
+            .buckets
+            .entry((client, client_type))
+            .or_insert_with(|| TokenBucket::new(capacity, now));
+        bucket.refill(capacity, refill_rate, now);
+        bucket.last_method = method.cloned();
+        let allowed = bucket.try_consume(cost);
+        trace!(
+            "TokenBucketPolicy handling tally -- client: {:?}, client_type: {:?}, cost: {}, remaining_tokens: {}, allowed: {}",
+            client,
+            client_type,
+            cost,
+            bucket.tokens,
+            allowed,
+        );
+        allowed
+    }
+
+    fn bucket_params(&self, client_type: ClientType) -> (f64, f64) {
+        bucket_params(&self.bucket_config, client_type)
+    }
+
+    /// Keeps the number of tracked buckets under `max_tracked_clients`. A full
+    /// bucket is indistinguishable from a newly created one, so those are
+    /// dropped first. If that is not enough, the least recently refilled
+    /// buckets are dropped until we are back at 90% capacity, so that we do
+    /// not need to evict again on every subsequent tally.
+    fn maybe_evict(&mut self, now: SystemTime) {
+        let max_tracked_clients = self.bucket_config.max_tracked_clients;
+        if self.buckets.len() <= max_tracked_clients {
+            return;
+        }
+        let config = &self.bucket_config;
+        self.buckets.retain(|(_, client_type), bucket| {
+            let (capacity, refill_rate) = bucket_params(config, *client_type);
+            bucket.tokens_at(capacity, refill_rate, now) < capacity
+        });
+        let target = max_tracked_clients - max_tracked_clients / 10;
+        if self.buckets.len() > target {
+            let mut by_age: Vec<_> = self
+                .buckets
+                .iter()
+                .map(|(key, bucket)| (bucket.last_refill, *key))
+                .collect();
+            by_age.sort_unstable_by_key(|(last_refill, _)| *last_refill);
+            let num_to_evict = self.buckets.len() - target;
+            for (_, key) in by_age.into_iter().take(num_to_evict) {
+                self.buckets.remove(&key);
+            }
+        }
+    }
+
+    pub fn num_tracked_clients(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Returns the number of clients whose bucket currently cannot cover
+    /// a request charged at `default_method_weight`.
+    pub fn num_depleted_clients(&self) -> usize {
+        let now = SystemTime::now();
+        let cost = self.bucket_config.default_method_weight;
+        self.buckets
+            .iter()
+            .filter(|((_, client_type), bucket)| {
+                let (capacity, refill_rate) = self.bucket_params(*client_type);
+                bucket.tokens_at(capacity, refill_rate, now) < cost
+            })
+            .count()
+    }
+
+    /// Returns the state of the `limit` buckets holding the fewest tokens,
+    /// i.e. the most throttled clients, in ascending order of tokens.
+    pub fn client_bucket_states(&self, limit: usize) -> Vec<ClientBucketState> {
+        let now = SystemTime::now();
+        let mut states: Vec<_> = self
+            .buckets
+            .iter()
+            .map(|((client, client_type), bucket)| {
+                let (capacity, refill_rate) = self.bucket_params(*client_type);
+                ClientBucketState {
+                    client: *client,
+                    proxied: *client_type == ClientType::ThroughFullnode,
+                    tokens: bucket.tokens_at(capacity, refill_rate, now),
+                    capacity,
+                    last_method: bucket.last_method.clone(),
+                    last_seen: bucket.last_refill,
+                }
+            })
+            .collect();
+        states.sort_unstable_by(|a, b| a.tokens.total_cmp(&b.tokens));
+        states.truncate(limit);
+        states
+    }
+
+    fn policy_config(&self) -> &PolicyConfig {
+        &self.config
+    }
+}
+
+/// Returns the (capacity, refill rate) of buckets for the given client type.
+fn bucket_params(config: &TokenBucketConfig, client_type: ClientType) -> (f64, f64) {
+    match client_type {
+        ClientType::Direct => (
+            config.client_capacity as f64,
+            config.client_refill_rate as f64,
+        ),
+        ClientType::ThroughFullnode => (
+            config.proxied_client_capacity as f64,
+            config.proxied_client_refill_rate as f64,
+        ),
+    }
+}
+
 ////////////// *** Test policies below this point *** //////////////
 
 #[derive(Clone)]
@@ -543,6 +778,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4))),
             error_info: None,
             spam_weight: Weight::one(),
+            method: None,
             timestamp: SystemTime::now(),
         };
         let bob = TrafficTally {
@@ -550,6 +786,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(4, 3, 2, 1))),
             error_info: None,
             spam_weight: Weight::one(),
+            method: None,
             timestamp: SystemTime::now(),
         };
         let charlie = TrafficTally {
@@ -557,6 +794,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(5, 6, 7, 8))),
             error_info: None,
             spam_weight: Weight::one(),
+            method: None,
             timestamp: SystemTime::now(),
         };
 
@@ -674,6 +912,137 @@ mod tests {
         assert_eq!(proxied_rate, 1);
     }
 
+    fn tally_at(
+        direct: Option<IpAddr>,
+        through_fullnode: Option<IpAddr>,
+        method: &str,
+        timestamp: SystemTime,
+    ) -> TrafficTally {
+        TrafficTally {
+            direct,
+            through_fullnode,
+            error_info: None,
+            spam_weight: Weight::one(),
+            method: Some(method.to_string()),
+            timestamp,
+        }
+    }
+
+    #[sim_test]
+    async fn test_token_bucket_policy() {
+        // Buckets of 10 tokens refilled at 2 tokens per second, where dry runs
+        // cost 5 tokens and every other method costs 1 token.
+        let mut policy = TokenBucketPolicy::new(
+            PolicyConfig::default(),
+            TokenBucketConfig {
+                client_capacity: 10,
+                client_refill_rate: 2,
+                proxied_client_capacity: 4,
+                proxied_client_refill_rate: 1,
+                method_weights: [("sui_dryRunTransactionBlock".to_string(), 5.0)]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+        );
+        let alice = Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5)));
+        let bob = Some(IpAddr::V4(Ipv4Addr::new(4, 3, 2, 1)));
+        let start = SystemTime::now();
+
+        // alice can afford two dry runs, but not a third one
+        for _ in 0..2 {
+            let response =
+                policy.handle_tally(tally_at(alice, None, "sui_dryRunTransactionBlock", start));
+            assert_eq!(response.block_client, None);
+        }
+        let response =
+            policy.handle_tally(tally_at(alice, None, "sui_dryRunTransactionBlock", start));
+        assert_eq!(response.block_client, alice);
+
+        // bob has a separate bucket and is unaffected
+        let response = policy.handle_tally(tally_at(bob, None, "sui_getObject", start));
+        assert_eq!(response.block_client, None);
+
+        // after 2 seconds alice has 4 tokens, which is enough for cheap
+        // requests but not for a dry run
+        let later = start + Duration::from_secs(2);
+        let response =
+            policy.handle_tally(tally_at(alice, None, "sui_dryRunTransactionBlock", later));
+        assert_eq!(response.block_client, alice);
+        for _ in 0..4 {
+            let response = policy.handle_tally(tally_at(alice, None, "sui_getObject", later));
+            assert_eq!(response.block_client, None);
+        }
+        let response = policy.handle_tally(tally_at(alice, None, "sui_getObject", later));
+        assert_eq!(response.block_client, alice);
+
+        // buckets never refill beyond capacity
+        let much_later = start + Duration::from_secs(60);
+        for _ in 0..10 {
+            let response = policy.handle_tally(tally_at(alice, None, "sui_getObject", much_later));
+            assert_eq!(response.block_client, None);
+        }
+        let response = policy.handle_tally(tally_at(alice, None, "sui_getObject", much_later));
+        assert_eq!(response.block_client, alice);
+
+        // proxied clients are accounted separately, with their own capacity
+        for _ in 0..4 {
+            let response = policy.handle_tally(tally_at(bob, alice, "sui_getObject", much_later));
+            assert_eq!(response.block_proxied_client, None);
+        }
+        let response = policy.handle_tally(tally_at(bob, alice, "sui_getObject", much_later));
+        assert_eq!(response.block_proxied_client, alice);
+        assert_eq!(response.block_client, None);
+
+        assert_eq!(policy.num_tracked_clients(), 3);
+        let states = policy.client_bucket_states(1);
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].client, alice.unwrap());
+        assert_eq!(states[0].last_method.as_deref(), Some("sui_getObject"));
+    }
+
+    #[sim_test]
+    async fn test_token_bucket_policy_eviction() {
+        let mut policy = TokenBucketPolicy::new(
+            PolicyConfig::default(),
+            TokenBucketConfig {
+                client_capacity: 10,
+                client_refill_rate: 1,
+                max_tracked_clients: 10,
+                ..Default::default()
+            },
+        );
+        let start = SystemTime::now();
+        for i in 0..10 {
+            let client = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, i)));
+            policy.handle_tally(tally_at(client, None, "sui_getObject", start));
+        }
+        assert_eq!(policy.num_tracked_clients(), 10);
+
+        // Once all previous buckets have refilled, they are evicted to make
+        // room for the new client.
+        let later = start + Duration::from_secs(5);
+        let client = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 1, 0)));
+        policy.handle_tally(tally_at(client, None, "sui_getObject", later));
+        assert_eq!(policy.num_tracked_clients(), 1);
+
+        // If no bucket is full, the least recently used ones are evicted.
+        for i in 1..11 {
+            let client = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 1, i)));
+            policy.handle_tally(tally_at(
+                client,
+                None,
+                "sui_getObject",
+                later + Duration::from_millis(i as u64),
+            ));
+        }
+        assert_eq!(policy.num_tracked_clients(), 9);
+        assert!(policy
+            .client_bucket_states(usize::MAX)
+            .iter()
+            .all(|state| state.client != IpAddr::V4(Ipv4Addr::new(10, 0, 1, 0))));
+    }
+
     #[sim_test]
     async fn test_traffic_sketch_mem_estimate() {
         // Test for getting a rough estimate of memory usage for the traffic sketch
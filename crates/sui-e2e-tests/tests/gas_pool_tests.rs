@@ -0,0 +1,268 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+use sui_keys::keystore::AccountKeystore;
+use sui_macros::sim_test;
+use sui_sdk::gas_pool::{GasPool, GasPoolConfig, GasPoolStats};
+use sui_sdk::rpc_types::SuiTransactionBlockEffectsAPI;
+use sui_sdk::transaction_submitter::{SubmitterConfig, SubmitterMetrics, TransactionSubmitter};
+use sui_test_transaction_builder::TestTransactionBuilder;
+use sui_types::base_types::random_object_ref;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use test_cluster::{TestCluster, TestClusterBuilder};
+use tokio::time::{sleep, timeout};
+
+async fn make_pool(cluster: &TestCluster, config: GasPoolConfig) -> GasPool {
+    let owner = cluster.get_address_0();
+    let signer = cluster
+        .wallet
+        .config
+        .keystore
+        .get_key(&owner)
+        .unwrap()
+        .copy();
+    GasPool::new(
+        cluster.sui_client().clone(),
+        owner,
+        Arc::new(signer),
+        config,
+    )
+    .await
+    .unwrap()
+}
+
+/// Waits for the pool's background task to bring it to a state satisfying `condition`.
+async fn wait_for_pool(pool: &GasPool, condition: impl Fn(&GasPoolStats) -> bool) {
+    timeout(Duration::from_secs(60), async {
+        while !condition(&pool.stats()) {
+            sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[sim_test]
+async fn test_parallel_submissions() {
+    let cluster = TestClusterBuilder::new().build().await;
+    let pool = make_pool(
+        &cluster,
+        GasPoolConfig {
+            coin_count: 8,
+            coin_balance: 1_000_000_000,
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(pool.stats().available, 8);
+
+    let submitter = TransactionSubmitter::new(
+        pool.clone(),
+        SubmitterConfig::default(),
+        Arc::new(SubmitterMetrics::new_for_tests()),
+    );
+    let recipient = cluster.get_address_1();
+    let responses = join_all((0..64).map(|_| {
+        let submitter = submitter.clone();
+        async move {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.pay_sui(vec![recipient], vec![1]).unwrap();
+            submitter.submit(builder.finish()).await
+        }
+    }))
+    .await;
+
+    for response in responses {
+        assert!(response.unwrap().effects.unwrap().status().is_ok());
+    }
+    let metrics = submitter.metrics();
+    assert_eq!(metrics.submitted.get(), 64);
+    assert_eq!(metrics.succeeded.get(), 64);
+    assert_eq!(metrics.errors.get(), 0);
+    assert_eq!(metrics.latency.get_sample_count(), 64);
+
+    let stats = pool.stats();
+    assert_eq!(
+        (stats.available, stats.leased, stats.quarantined),
+        (8, 0, 0)
+    );
+}
+
+#[sim_test]
+async fn test_rejected_transaction_releases_coin() {
+    let cluster = TestClusterBuilder::new().build().await;
+    let pool = make_pool(
+        &cluster,
+        GasPoolConfig {
+            coin_count: 1,
+            coin_balance: 1_000_000_000,
+            ..Default::default()
+        },
+    )
+    .await;
+    let submitter = TransactionSubmitter::new(
+        pool.clone(),
+        SubmitterConfig::default(),
+        Arc::new(SubmitterMetrics::new_for_tests()),
+    );
+
+    // Validators reject a transaction with an input object that does not exist, before locking
+    // its gas coin.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder
+        .transfer_object(cluster.get_address_1(), random_object_ref())
+        .unwrap();
+    assert!(submitter.submit(builder.finish()).await.is_err());
+    assert_eq!(submitter.metrics().errors.get(), 1);
+    assert_eq!(submitter.metrics().retries.get(), 0);
+
+    let stats = pool.stats();
+    assert_eq!(
+        (stats.available, stats.leased, stats.quarantined),
+        (1, 0, 0)
+    );
+
+    // The coin can pay for the next transaction right away.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder
+        .pay_sui(vec![cluster.get_address_1()], vec![1])
+        .unwrap();
+    let response = submitter.submit(builder.finish()).await.unwrap();
+    assert!(response.effects.unwrap().status().is_ok());
+}
+
+#[sim_test]
+async fn test_coins_are_topped_up() {
+    let cluster = TestClusterBuilder::new().build().await;
+    let config = GasPoolConfig {
+        coin_count: 4,
+        coin_balance: 100_000_000,
+        // Any coin that paid for a transaction needs topping up.
+        min_coin_balance: 99_999_999,
+        maintenance_interval: Duration::from_secs(1),
+        ..Default::default()
+    };
+    let pool = make_pool(&cluster, config.clone()).await;
+    let submitter = TransactionSubmitter::new(
+        pool.clone(),
+        SubmitterConfig::default(),
+        Arc::new(SubmitterMetrics::new_for_tests()),
+    );
+
+    let recipient = cluster.get_address_1();
+    for _ in 0..4 {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_sui(vec![recipient], vec![1]).unwrap();
+        submitter.submit(builder.finish()).await.unwrap();
+    }
+
+    timeout(Duration::from_secs(60), async {
+        loop {
+            let leases: Vec<_> = std::iter::from_fn(|| pool.try_lease()).collect();
+            // Coins being topped up are leased by the pool itself.
+            let topped_up = leases.len() == 4
+                && leases
+                    .iter()
+                    .all(|l| l.balance() >= config.min_coin_balance);
+            leases.into_iter().for_each(|l| l.release_unused());
+            if topped_up {
+                break;
+            }
+            sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[sim_test]
+async fn test_quarantined_coins_are_released_after_epoch_change() {
+    let cluster = TestClusterBuilder::new().build().await;
+    let pool = make_pool(
+        &cluster,
+        GasPoolConfig {
+            coin_count: 2,
+            coin_balance: 1_000_000_000,
+            maintenance_interval: Duration::from_secs(1),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // A lease dropped without effects may have locked its coin, so it is kept out of the pool
+    // while its version is unchanged...
+    drop(pool.lease().await.unwrap());
+    assert_eq!(pool.stats().quarantined, 1);
+    sleep(Duration::from_secs(5)).await;
+    assert_eq!(pool.stats().quarantined, 1);
+    assert_eq!(pool.stats().available, 1);
+
+    // ...until the end of the epoch releases all locks.
+    cluster.trigger_reconfiguration().await;
+    wait_for_pool(&pool, |stats| {
+        stats.quarantined == 0 && stats.available == 2
+    })
+    .await;
+}
+
+#[sim_test]
+async fn test_lease_times_out() {
+    let cluster = TestClusterBuilder::new().build().await;
+    let pool = make_pool(
+        &cluster,
+        GasPoolConfig {
+            coin_count: 1,
+            coin_balance: 1_000_000_000,
+            lease_timeout: Duration::from_secs(1),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // The only coin is in use...
+    let lease = pool.lease().await.unwrap();
+    assert!(pool.lease().await.is_err());
+
+    // ...or quarantined.
+    drop(lease);
+    assert_eq!(pool.stats().quarantined, 1);
+    assert!(pool.lease().await.is_err());
+}
+
+#[sim_test]
+async fn test_lease_fails_once_coins_are_removed() {
+    let cluster = TestClusterBuilder::new().build().await;
+    let pool = make_pool(
+        &cluster,
+        GasPoolConfig {
+            coin_count: 1,
+            coin_balance: 1_000_000_000,
+            maintenance_interval: Duration::from_secs(1),
+            lease_timeout: Duration::from_secs(600),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // Quarantine the only coin of the pool, and send it away behind the pool's back.
+    let lease = pool.lease().await.unwrap();
+    let coin = lease.object_ref();
+    drop(lease);
+    let data = TestTransactionBuilder::new(pool.owner(), coin, pool.gas_price())
+        .transfer_sui(None, cluster.get_address_1())
+        .build();
+    cluster.sign_and_execute_transaction(&data).await;
+
+    // A pending lease fails as soon as the pool notices, rather than at its timeout.
+    let error = timeout(Duration::from_secs(60), pool.lease())
+        .await
+        .unwrap()
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("no coins left"), "{error}");
+    assert_eq!(pool.stats(), GasPoolStats::default());
+}
@@ -0,0 +1,189 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, Instant};
+
+use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+use sui_macros::sim_test;
+use sui_sdk::{ReadConsistency, SuiClientBuilder};
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use test_cluster::TestClusterBuilder;
+use tokio::net::TcpListener;
+
+/// Nothing listens on this port, so requests to it fail at the transport level.
+const UNREACHABLE_URL: &str = "http://127.0.0.1:1";
+
+/// Starts a server that accepts connections but never answers requests, returning its URL.
+async fn unresponsive_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut connections = vec![];
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+    url
+}
+
+#[sim_test]
+async fn test_reads_fail_over_to_healthy_endpoint() {
+    let cluster = TestClusterBuilder::new().build().await;
+    let client = SuiClientBuilder::default()
+        .fallback_urls([cluster.rpc_url()])
+        .build(UNREACHABLE_URL)
+        .await
+        .unwrap();
+    let tx_data = cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(1), cluster.get_address_1())
+        .build();
+
+    let gas_price = client.read_api().get_reference_gas_price().await.unwrap();
+    assert_eq!(gas_price, cluster.get_reference_gas_price().await);
+
+    let status = client.endpoint_status();
+    assert_eq!(status.len(), 2);
+    assert!(!status[0].healthy);
+    assert!(status[0].consecutive_failures > 0);
+    assert!(status[1].healthy);
+
+    // Transactions go to the healthy endpoint too.
+    let response = client
+        .quorum_driver_api()
+        .execute_transaction_block(
+            cluster.sign_transaction(&tx_data),
+            SuiTransactionBlockResponseOptions::new().with_effects(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.confirmed_local_execution, Some(true));
+}
+
+#[sim_test]
+async fn test_build_without_reachable_endpoint_fails() {
+    let client = SuiClientBuilder::default()
+        .fallback_urls([UNREACHABLE_URL])
+        .build(UNREACHABLE_URL)
+        .await;
+    assert!(client.is_err());
+}
+
+#[sim_test]
+async fn test_read_after_write_pins_reads_to_checkpoint() {
+    let mut cluster = TestClusterBuilder::new().build().await;
+    let fullnode = cluster.spawn_new_fullnode().await;
+    let client = SuiClientBuilder::default()
+        .fallback_urls([fullnode.rpc_url.as_str()])
+        .read_consistency(ReadConsistency::ReadAfterWrite)
+        .build(cluster.rpc_url())
+        .await
+        .unwrap();
+    assert_eq!(client.pinned_read_checkpoint(), 0);
+
+    let tx_data = cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(1), cluster.get_address_1())
+        .build();
+    let response = client
+        .quorum_driver_api()
+        .execute_transaction_block(
+            cluster.sign_transaction(&tx_data),
+            SuiTransactionBlockResponseOptions::new().with_effects(),
+            Some(ExecuteTransactionRequestType::WaitForEffectsCert),
+        )
+        .await
+        .unwrap();
+    let pinned = client.pinned_read_checkpoint();
+    assert!(pinned > 0);
+
+    // Whichever endpoint serves it, the read observes the transaction.
+    let read = client
+        .read_api()
+        .get_transaction_with_options(response.digest, SuiTransactionBlockResponseOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(read.checkpoint, Some(pinned));
+
+    // Pinning never lowers the checkpoint reads are pinned to.
+    client.pin_reads_to_checkpoint(0);
+    assert_eq!(client.pinned_read_checkpoint(), pinned);
+}
+
+#[sim_test]
+async fn test_slow_reads_are_hedged() {
+    let cluster = TestClusterBuilder::new().build().await;
+    let request_timeout = Duration::from_secs(10);
+    let client = SuiClientBuilder::default()
+        .fallback_urls([cluster.rpc_url()])
+        .request_timeout(request_timeout)
+        .hedge_reads_after(Duration::from_millis(100))
+        .build(unresponsive_server().await)
+        .await
+        .unwrap();
+
+    // The unresponsive endpoint comes first, but the read is answered by the next one long
+    // before the first request times out.
+    let start = Instant::now();
+    let gas_price = client.read_api().get_reference_gas_price().await.unwrap();
+    assert_eq!(gas_price, cluster.get_reference_gas_price().await);
+    assert!(start.elapsed() < request_timeout);
+
+    // The hedged request was abandoned rather than failed, so it doesn't count against the
+    // endpoint.
+    let status = client.endpoint_status();
+    assert!(status[0].healthy);
+    assert_eq!(status[0].consecutive_failures, 0);
+}
+
+#[sim_test]
+async fn test_reads_fail_over_when_endpoint_stops() {
+    let mut cluster = TestClusterBuilder::new().build().await;
+    let fullnode = cluster.spawn_new_fullnode().await;
+    let client = SuiClientBuilder::default()
+        .fallback_urls([cluster.rpc_url()])
+        .build(&fullnode.rpc_url)
+        .await
+        .unwrap();
+    let address = cluster.get_address_0();
+
+    let first_page = client
+        .coin_read_api()
+        .get_coins(address, None, None, Some(1))
+        .await
+        .unwrap();
+    assert!(first_page.has_next_page);
+    assert!(client.endpoint_status().iter().all(|s| s.healthy));
+
+    cluster.stop_node(&fullnode.sui_node.with(|node| node.state().name));
+
+    // The next page is only read from the stopped fullnode that returned its cursor, as another
+    // fullnode may not agree on where the first page ended...
+    assert!(client
+        .coin_read_api()
+        .get_coins(address, None, first_page.next_cursor, Some(1))
+        .await
+        .is_err());
+
+    // ...while other reads fail over to the remaining fullnode.
+    let page = client
+        .coin_read_api()
+        .get_coins(address, None, None, Some(1))
+        .await
+        .unwrap();
+    assert_eq!(page.data, first_page.data);
+    let status = client.endpoint_status();
+    assert!(!status[0].healthy);
+    assert!(status[1].healthy);
+
+    // Pages whose cursor was returned by the remaining fullnode are read from it.
+    let next_page = client
+        .coin_read_api()
+        .get_coins(address, None, page.next_cursor, Some(1))
+        .await
+        .unwrap();
+    assert_ne!(next_page.data, page.data);
+}
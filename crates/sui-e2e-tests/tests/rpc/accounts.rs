@@ -0,0 +1,141 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use sui_macros::sim_test;
+use sui_rpc_api::client::Client as CoreClient;
+use sui_rpc_api::rest::accounts::Balance;
+use sui_test_transaction_builder::TestTransactionBuilder;
+use sui_types::base_types::SuiAddress;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::gas_coin::GAS;
+use test_cluster::{TestCluster, TestClusterBuilder};
+
+use crate::transfer_coin;
+
+#[sim_test]
+async fn get_balance() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let core_client = CoreClient::new(test_cluster.rpc_url()).unwrap();
+
+    let addresses = test_cluster.get_addresses();
+    for address in &addresses {
+        wait_for_indexed_balance(&test_cluster, &core_client, *address).await;
+    }
+
+    // Moving a coin between accounts changes the balance and coin count of both sides.
+    transfer_coin(&test_cluster.wallet).await;
+    for address in &addresses[..2] {
+        wait_for_indexed_balance(&test_cluster, &core_client, *address).await;
+    }
+
+    // An account that has never held the coin type has a zero balance.
+    let balance = core_client
+        .get_balance(SuiAddress::random_for_testing_only(), &GAS::type_())
+        .await
+        .unwrap();
+    assert_eq!(balance.balance, 0);
+    assert_eq!(balance.coin_object_count, 0);
+}
+
+#[sim_test]
+async fn list_balances() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let core_client = CoreClient::new(test_cluster.rpc_url()).unwrap();
+
+    let address = test_cluster.get_address_0();
+    transfer_coin(&test_cluster.wallet).await;
+    let expected = wait_for_indexed_balance(&test_cluster, &core_client, address).await;
+
+    let page = core_client
+        .list_balances(address, None, None)
+        .await
+        .unwrap();
+    assert_eq!(page.items, vec![expected.clone()]);
+    assert!(page.next_page_token.is_none());
+
+    let page = core_client
+        .list_balances(address, Some(1), None)
+        .await
+        .unwrap();
+    assert_eq!(page.items, vec![expected]);
+    assert!(page.next_page_token.is_none());
+
+    let page = core_client
+        .list_balances(SuiAddress::random_for_testing_only(), None, None)
+        .await
+        .unwrap();
+    assert!(page.items.is_empty());
+    assert!(page.next_page_token.is_none());
+}
+
+#[sim_test]
+async fn simulate_transaction_reports_gas_used() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let core_client = CoreClient::new(test_cluster.rpc_url()).unwrap();
+
+    let context = &test_cluster.wallet;
+    let gas_price = context.get_reference_gas_price().await.unwrap();
+    let accounts_and_objs = context.get_all_accounts_and_gas_objects().await.unwrap();
+    let sender = accounts_and_objs[0].0;
+    let receiver = accounts_and_objs[1].0;
+    let transaction = TestTransactionBuilder::new(sender, accounts_and_objs[0].1[0], gas_price)
+        .transfer(accounts_and_objs[0].1[1], receiver)
+        .build();
+
+    let response = core_client
+        .simulate_transaction(&transaction, false)
+        .await
+        .unwrap();
+
+    let gas_cost_summary = response.effects.gas_cost_summary();
+    assert_eq!(
+        response.gas_used.computation_cost,
+        gas_cost_summary.computation_cost
+    );
+    assert_eq!(
+        response.gas_used.storage_cost,
+        gas_cost_summary.storage_cost
+    );
+    assert_eq!(
+        response.gas_used.storage_rebate,
+        gas_cost_summary.storage_rebate
+    );
+    assert_eq!(
+        response.gas_used.non_refundable_storage_fee,
+        gas_cost_summary.non_refundable_storage_fee
+    );
+    assert!(response.gas_used.computation_cost > 0);
+}
+
+/// The balance index is updated as checkpoints are executed, so wait for it to agree with the
+/// JSON-RPC view of the account's SUI balance.
+async fn wait_for_indexed_balance(
+    test_cluster: &TestCluster,
+    core_client: &CoreClient,
+    address: SuiAddress,
+) -> Balance {
+    let coin_read_api = test_cluster.sui_client().coin_read_api();
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let expected = coin_read_api.get_balance(address, None).await.unwrap();
+            let balance = core_client
+                .get_balance(address, &GAS::type_())
+                .await
+                .unwrap();
+
+            if u128::from(balance.balance) == expected.total_balance
+                && balance.coin_object_count as usize == expected.coin_object_count
+            {
+                assert_eq!(balance.coin_type, GAS::type_());
+                return balance;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("balance index did not catch up with the JSON-RPC balance")
+}
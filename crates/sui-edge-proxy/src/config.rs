@@ -6,6 +6,7 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use serde_with::DurationSeconds;
+use serde_with::OneOrMany;
 use std::{net::SocketAddr, time::Duration};
 use tracing::error;
 use url::Url;
@@ -16,8 +17,23 @@ use url::Url;
 pub struct ProxyConfig {
     pub listen_address: SocketAddr,
     pub metrics_address: SocketAddr,
-    pub execution_peer: PeerConfig,
-    pub read_peer: PeerConfig,
+    /// Peers serving `sui_executeTransactionBlock`. A single peer may also be given.
+    #[serde_as(as = "OneOrMany<_>")]
+    #[serde(alias = "execution-peer")]
+    pub execution_peers: Vec<PeerConfig>,
+    /// Peers serving every other request. A single peer may also be given.
+    #[serde_as(as = "OneOrMany<_>")]
+    #[serde(alias = "read-peer")]
+    pub read_peers: Vec<PeerConfig>,
+    /// How requests are spread across the healthy peers of a role.
+    #[serde(default)]
+    pub load_balancing: LoadBalancingStrategy,
+    #[serde(default)]
+    pub health_check: HealthCheckConfig,
+    /// Number of times a failed read request is retried against another peer. Execution
+    /// requests are never retried, as they are not idempotent.
+    #[serde(default = "default_max_read_retries")]
+    pub max_read_retries: usize,
     /// Maximum number of idle connections to keep in the connection pool.
     /// When set, this limits the number of connections that remain open but unused,
     /// helping to conserve system resources.
@@ -39,10 +55,70 @@ fn default_idle_timeout() -> Duration {
     Duration::from_secs(60)
 }
 
+fn default_max_read_retries() -> usize {
+    2
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct PeerConfig {
     pub address: Url,
+    /// Relative share of requests sent to this peer.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoadBalancingStrategy {
+    /// Smooth weighted round-robin over the healthy peers.
+    #[default]
+    WeightedRoundRobin,
+    /// Pick the healthy peer with the fewest in-flight requests relative to its weight.
+    LeastInflight,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HealthCheckConfig {
+    /// How often every peer is checked.
+    #[serde_as(as = "DurationSeconds")]
+    #[serde(default = "default_health_check_interval")]
+    pub interval_seconds: Duration,
+    #[serde_as(as = "DurationSeconds")]
+    #[serde(default = "default_health_check_timeout")]
+    pub timeout_seconds: Duration,
+    /// A peer whose latest checkpoint is more than this many checkpoints behind the most
+    /// up-to-date peer is considered unhealthy.
+    #[serde(default = "default_max_checkpoint_lag")]
+    pub max_checkpoint_lag: u64,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: default_health_check_interval(),
+            timeout_seconds: default_health_check_timeout(),
+            max_checkpoint_lag: default_max_checkpoint_lag(),
+        }
+    }
+}
+
+fn default_health_check_interval() -> Duration {
+    Duration::from_secs(5)
+}
+
+fn default_health_check_timeout() -> Duration {
+    Duration::from_secs(2)
+}
+
+fn default_max_checkpoint_lag() -> u64 {
+    20
 }
 
 /// Load and validate configuration
@@ -61,8 +137,15 @@ pub async fn load<P: AsRef<std::path::Path>>(path: P) -> Result<(ProxyConfig, Cl
         .build()
         .expect("Failed to build HTTP/2 client");
 
-    validate_peer_url(&client, &config.read_peer).await?;
-    validate_peer_url(&client, &config.execution_peer).await?;
+    if config.read_peers.is_empty() || config.execution_peers.is_empty() {
+        anyhow::bail!("at least one read peer and one execution peer must be configured");
+    }
+    for peer in config.read_peers.iter().chain(&config.execution_peers) {
+        if peer.weight == 0 {
+            anyhow::bail!("peer {} must have a positive weight", peer.address);
+        }
+        validate_peer_url(&client, peer).await?;
+    }
 
     Ok((config, client))
 }
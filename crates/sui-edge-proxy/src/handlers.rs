@@ -1,8 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::config::PeerConfig;
 use crate::metrics::AppMetrics;
+use crate::peers::{PeerPool, PeerRole};
 use axum::{
     body::Body,
     extract::{Request, State},
@@ -11,43 +11,32 @@ use axum::{
     response::Response,
 };
 use bytes::Bytes;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, info, warn};
 
-#[derive(Debug)]
-enum PeerRole {
-    Read,
-    Execution,
-}
-
-impl PeerRole {
-    fn as_str(&self) -> &'static str {
-        match self {
-            PeerRole::Read => "read",
-            PeerRole::Execution => "execution",
-        }
-    }
-}
-
 #[derive(Clone)]
 pub struct AppState {
     client: reqwest::Client,
-    read_peer: PeerConfig,
-    execution_peer: PeerConfig,
+    read_peers: Arc<PeerPool>,
+    execution_peers: Arc<PeerPool>,
+    max_read_retries: usize,
     metrics: AppMetrics,
 }
 
 impl AppState {
     pub fn new(
         client: reqwest::Client,
-        read_peer: PeerConfig,
-        execution_peer: PeerConfig,
+        read_peers: Arc<PeerPool>,
+        execution_peers: Arc<PeerPool>,
+        max_read_retries: usize,
         metrics: AppMetrics,
     ) -> Self {
         Self {
             client,
-            read_peer,
-            execution_peer,
+            read_peers,
+            execution_peers,
+            max_read_retries,
             metrics,
         }
     }
@@ -123,59 +112,114 @@ async fn proxy_request(
         .with_label_values(&[peer_type_str])
         .observe(body_bytes.len() as f64);
 
-    let peer_config = match peer_type {
-        PeerRole::Read => &state.read_peer,
-        PeerRole::Execution => &state.execution_peer,
+    let (pool, max_attempts) = match peer_type {
+        PeerRole::Read => (&state.read_peers, state.max_read_retries + 1),
+        // Transactions are not retried by the proxy, as it cannot tell whether a failed attempt
+        // reached the network.
+        PeerRole::Execution => (&state.execution_peers, 1),
     };
 
-    let mut target_url = peer_config.address.clone();
-    target_url.set_path(parts.uri.path());
-    if let Some(query) = parts.uri.query() {
-        target_url.set_query(Some(query));
-    }
-
-    // remove host header to avoid interfering with reqwest auto-host header
-    let mut headers = parts.headers.clone();
-    headers.remove("host");
-    let request_builder = state
-        .client
-        .request(parts.method.clone(), target_url)
-        .headers(headers)
-        .body(body_bytes);
-    debug!("Request builder: {:?}", request_builder);
-
-    let upstream_start = Instant::now();
-    let response = match request_builder.send().await {
-        Ok(response) => {
-            let status = response.status().as_u16().to_string();
-            metrics
-                .upstream_response_latency
-                .with_label_values(&[peer_type_str, &status])
-                .observe(upstream_start.elapsed().as_secs_f64());
-            metrics
-                .requests_total
-                .with_label_values(&[peer_type_str, &status])
-                .inc();
-            debug!("Response: {:?}", response);
-            response
-        }
-        Err(e) => {
-            warn!("Failed to send request: {}", e);
-            metrics
-                .upstream_response_latency
-                .with_label_values(&[peer_type_str, "error"])
-                .observe(upstream_start.elapsed().as_secs_f64());
+    let mut tried = vec![];
+    let (response, _guard) = loop {
+        let Some(guard) = pool.select(&tried) else {
             metrics
                 .requests_total
                 .with_label_values(&[peer_type_str, "error"])
                 .inc();
-            if e.is_timeout() {
+            return Err((
+                StatusCode::BAD_GATEWAY,
+                format!("No {} peer available", peer_type_str),
+            ));
+        };
+        let peer = guard.peer().clone();
+
+        let mut target_url = peer.config.address.clone();
+        target_url.set_path(parts.uri.path());
+        if let Some(query) = parts.uri.query() {
+            target_url.set_query(Some(query));
+        }
+
+        // remove host header to avoid interfering with reqwest auto-host header
+        let mut headers = parts.headers.clone();
+        headers.remove("host");
+        let request_builder = state
+            .client
+            .request(parts.method.clone(), target_url)
+            .headers(headers)
+            .body(body_bytes.clone());
+        debug!("Request builder: {:?}", request_builder);
+
+        tried.push(peer.clone());
+        let can_retry = tried.len() < max_attempts && tried.len() < pool.peers().len();
+
+        let upstream_start = Instant::now();
+        match request_builder.send().await {
+            Ok(response) => {
+                let status = response.status().as_u16().to_string();
+                metrics
+                    .upstream_response_latency
+                    .with_label_values(&[peer_type_str, &status])
+                    .observe(upstream_start.elapsed().as_secs_f64());
+                metrics
+                    .upstream_attempts_total
+                    .with_label_values(&[peer_type_str, &status])
+                    .inc();
+                metrics
+                    .peer_requests_total
+                    .with_label_values(&[peer_type_str, peer.address(), &status])
+                    .inc();
+                debug!("Response: {:?}", response);
+                if response.status().is_server_error() && can_retry {
+                    warn!(
+                        "Peer {} responded with {}, retrying",
+                        peer.address(),
+                        response.status()
+                    );
+                    metrics
+                        .retries_total
+                        .with_label_values(&[peer_type_str])
+                        .inc();
+                    continue;
+                }
+                metrics
+                    .requests_total
+                    .with_label_values(&[peer_type_str, &status])
+                    .inc();
+                break (response, guard);
+            }
+            Err(e) => {
+                warn!("Failed to send request to {}: {}", peer.address(), e);
+                metrics
+                    .upstream_response_latency
+                    .with_label_values(&[peer_type_str, "error"])
+                    .observe(upstream_start.elapsed().as_secs_f64());
+                metrics
+                    .upstream_attempts_total
+                    .with_label_values(&[peer_type_str, "error"])
+                    .inc();
+                metrics
+                    .peer_requests_total
+                    .with_label_values(&[peer_type_str, peer.address(), "error"])
+                    .inc();
+                if e.is_timeout() {
+                    metrics
+                        .timeouts_total
+                        .with_label_values(&[peer_type_str])
+                        .inc();
+                }
+                if can_retry {
+                    metrics
+                        .retries_total
+                        .with_label_values(&[peer_type_str])
+                        .inc();
+                    continue;
+                }
                 metrics
-                    .timeouts_total
-                    .with_label_values(&[peer_type_str])
+                    .requests_total
+                    .with_label_values(&[peer_type_str, "error"])
                     .inc();
+                return Err((StatusCode::BAD_GATEWAY, format!("Request failed: {}", e)));
             }
-            return Err((StatusCode::BAD_GATEWAY, format!("Request failed: {}", e)));
         }
     };
 
@@ -5,9 +5,11 @@ use axum::{routing::any, Router};
 use clap::Parser;
 use mysten_metrics::start_prometheus_server;
 use reqwest::Client;
+use std::sync::Arc;
 use sui_edge_proxy::config::{load, ProxyConfig};
 use sui_edge_proxy::handlers::{proxy_handler, AppState};
 use sui_edge_proxy::metrics::AppMetrics;
+use sui_edge_proxy::peers::{run_health_checks, PeerPool, PeerRole};
 use tracing::info;
 
 #[derive(Parser, Debug)]
@@ -42,10 +44,31 @@ async fn main() {
 
     let app_metrics = AppMetrics::new(&prometheus_registry);
 
+    let read_peers = Arc::new(PeerPool::new(
+        PeerRole::Read,
+        config.read_peers.clone(),
+        config.load_balancing,
+        app_metrics.clone(),
+    ));
+    let execution_peers = Arc::new(PeerPool::new(
+        PeerRole::Execution,
+        config.execution_peers.clone(),
+        config.load_balancing,
+        app_metrics.clone(),
+    ));
+
+    let health_checks = tokio::spawn(run_health_checks(
+        client.clone(),
+        vec![read_peers.clone(), execution_peers.clone()],
+        config.health_check.clone(),
+        app_metrics.clone(),
+    ));
+
     let app_state = AppState::new(
         client,
-        config.read_peer.clone(),
-        config.execution_peer.clone(),
+        read_peers,
+        execution_peers,
+        config.max_read_retries,
         app_metrics,
     );
 
@@ -54,8 +77,11 @@ async fn main() {
         .with_state(app_state);
 
     info!("Starting server on {}", config.listen_address);
-    axum_server::Server::bind(config.listen_address)
+    let result = axum_server::Server::bind(config.listen_address)
         .serve(app.into_make_service())
-        .await
-        .unwrap();
+        .await;
+
+    // Stop probing peers once there are no requests left to route to them.
+    health_checks.abort();
+    result.unwrap();
 }
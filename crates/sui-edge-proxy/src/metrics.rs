@@ -3,19 +3,25 @@
 
 use prometheus::{
     register_gauge_vec_with_registry, register_histogram_vec_with_registry,
-    register_int_counter_vec_with_registry, GaugeVec, HistogramVec, IntCounterVec, Registry,
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, GaugeVec,
+    HistogramVec, IntCounterVec, IntGaugeVec, Registry,
 };
 
 #[derive(Clone)]
 pub struct AppMetrics {
     pub backend_up: GaugeVec,
     pub requests_total: IntCounterVec,
+    pub upstream_attempts_total: IntCounterVec,
     pub request_latency: HistogramVec,
     pub upstream_response_latency: HistogramVec,
     pub response_size_bytes: HistogramVec,
     pub request_size_bytes: HistogramVec,
     pub timeouts_total: IntCounterVec,
     pub error_counts: IntCounterVec,
+    pub peer_requests_total: IntCounterVec,
+    pub peer_in_flight: IntGaugeVec,
+    pub peer_latest_checkpoint: IntGaugeVec,
+    pub retries_total: IntCounterVec,
 }
 
 impl AppMetrics {
@@ -24,7 +30,7 @@ impl AppMetrics {
             backend_up: register_gauge_vec_with_registry!(
                 "edge_proxy_backend_up",
                 "Indicates if the backend is up (1) or down (0)",
-                &["peer_type"],
+                &["peer_type", "peer"],
                 registry
             )
             .unwrap(),
@@ -35,6 +41,13 @@ impl AppMetrics {
                 registry
             )
             .unwrap(),
+            upstream_attempts_total: register_int_counter_vec_with_registry!(
+                "edge_proxy_upstream_attempts_total",
+                "Total number of attempts to send a request upstream, including retries",
+                &["peer_type", "status"],
+                registry
+            )
+            .unwrap(),
             request_latency: register_histogram_vec_with_registry!(
                 "edge_proxy_request_latency",
                 "Request latency in seconds",
@@ -77,6 +90,34 @@ impl AppMetrics {
                 registry
             )
             .unwrap(),
+            peer_requests_total: register_int_counter_vec_with_registry!(
+                "edge_proxy_peer_requests_total",
+                "Total number of requests sent to each peer",
+                &["peer_type", "peer", "status"],
+                registry
+            )
+            .unwrap(),
+            peer_in_flight: register_int_gauge_vec_with_registry!(
+                "edge_proxy_peer_in_flight",
+                "Number of requests currently in flight to each peer",
+                &["peer_type", "peer"],
+                registry
+            )
+            .unwrap(),
+            peer_latest_checkpoint: register_int_gauge_vec_with_registry!(
+                "edge_proxy_peer_latest_checkpoint",
+                "Latest checkpoint reported by each peer during health checks",
+                &["peer_type", "peer"],
+                registry
+            )
+            .unwrap(),
+            retries_total: register_int_counter_vec_with_registry!(
+                "edge_proxy_retries_total",
+                "Total number of requests retried against another peer",
+                &["peer_type"],
+                registry
+            )
+            .unwrap(),
         }
     }
 }
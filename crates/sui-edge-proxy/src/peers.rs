@@ -0,0 +1,446 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{HealthCheckConfig, LoadBalancingStrategy, PeerConfig};
+use crate::metrics::AppMetrics;
+use anyhow::{anyhow, Context, Result};
+use reqwest::Client;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeerRole {
+    Read,
+    Execution,
+}
+
+impl PeerRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PeerRole::Read => "read",
+            PeerRole::Execution => "execution",
+        }
+    }
+}
+
+/// An upstream fullnode along with its health and load, as seen by the proxy.
+#[derive(Debug)]
+pub struct Peer {
+    pub config: PeerConfig,
+    pub role: PeerRole,
+    /// Peers start out healthy, so that requests are served before the first health check.
+    healthy: AtomicBool,
+    in_flight: AtomicUsize,
+    latest_checkpoint: AtomicU64,
+}
+
+impl Peer {
+    fn new(config: PeerConfig, role: PeerRole) -> Self {
+        Self {
+            config,
+            role,
+            healthy: AtomicBool::new(true),
+            in_flight: AtomicUsize::new(0),
+            latest_checkpoint: AtomicU64::new(0),
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        self.config.address.as_str()
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    fn set_healthy(&self, healthy: bool, metrics: &AppMetrics) {
+        if self.healthy.swap(healthy, Ordering::Relaxed) != healthy {
+            warn!(
+                "{} peer {} is now {}",
+                self.role.as_str(),
+                self.address(),
+                if healthy { "healthy" } else { "unhealthy" }
+            );
+        }
+        metrics
+            .backend_up
+            .with_label_values(&[self.role.as_str(), self.address()])
+            .set(if healthy { 1.0 } else { 0.0 });
+    }
+}
+
+/// Counts a request as in flight on a peer for as long as it is alive.
+pub struct InFlightGuard {
+    peer: Arc<Peer>,
+    metrics: AppMetrics,
+}
+
+impl InFlightGuard {
+    fn new(peer: Arc<Peer>, metrics: AppMetrics) -> Self {
+        peer.in_flight.fetch_add(1, Ordering::Relaxed);
+        metrics
+            .peer_in_flight
+            .with_label_values(&[peer.role.as_str(), peer.address()])
+            .inc();
+        Self { peer, metrics }
+    }
+
+    pub fn peer(&self) -> &Arc<Peer> {
+        &self.peer
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.peer.in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.metrics
+            .peer_in_flight
+            .with_label_values(&[self.peer.role.as_str(), self.peer.address()])
+            .dec();
+    }
+}
+
+/// The peers serving one role, and the state needed to balance requests across them.
+pub struct PeerPool {
+    role: PeerRole,
+    peers: Vec<Arc<Peer>>,
+    strategy: LoadBalancingStrategy,
+    /// Current weight of every peer for smooth weighted round-robin.
+    current_weights: Mutex<Vec<i64>>,
+    metrics: AppMetrics,
+}
+
+impl PeerPool {
+    pub fn new(
+        role: PeerRole,
+        peers: Vec<PeerConfig>,
+        strategy: LoadBalancingStrategy,
+        metrics: AppMetrics,
+    ) -> Self {
+        let peers: Vec<_> = peers
+            .into_iter()
+            .map(|config| Arc::new(Peer::new(config, role)))
+            .collect();
+        for peer in &peers {
+            peer.set_healthy(true, &metrics);
+        }
+        Self {
+            role,
+            current_weights: Mutex::new(vec![0; peers.len()]),
+            peers,
+            strategy,
+            metrics,
+        }
+    }
+
+    pub fn role(&self) -> PeerRole {
+        self.role
+    }
+
+    pub fn peers(&self) -> &[Arc<Peer>] {
+        &self.peers
+    }
+
+    /// Pick the peer to send the next request to, skipping the peers in `tried`. Unhealthy peers
+    /// are only picked if no healthy peer is left, so that a pool whose peers are all failing
+    /// health checks still attempts to serve requests.
+    pub fn select(&self, tried: &[Arc<Peer>]) -> Option<InFlightGuard> {
+        let untried = |peer: &Arc<Peer>| !tried.iter().any(|t| Arc::ptr_eq(t, peer));
+        let healthy: Vec<usize> = (0..self.peers.len())
+            .filter(|i| untried(&self.peers[*i]) && self.peers[*i].is_healthy())
+            .collect();
+        let candidates = if healthy.is_empty() {
+            (0..self.peers.len())
+                .filter(|i| untried(&self.peers[*i]))
+                .collect()
+        } else {
+            healthy
+        };
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let index = match self.strategy {
+            LoadBalancingStrategy::WeightedRoundRobin => self.next_round_robin(&candidates),
+            LoadBalancingStrategy::LeastInflight => self.least_in_flight(&candidates),
+        };
+        Some(InFlightGuard::new(
+            self.peers[index].clone(),
+            self.metrics.clone(),
+        ))
+    }
+
+    /// Smooth weighted round-robin: every candidate gains its weight, the one with the highest
+    /// current weight is picked and loses the total weight of the candidates.
+    fn next_round_robin(&self, candidates: &[usize]) -> usize {
+        let mut current_weights = self.current_weights.lock().unwrap();
+        let mut total = 0;
+        let mut best = candidates[0];
+        for &i in candidates {
+            let weight = self.peers[i].config.weight as i64;
+            current_weights[i] += weight;
+            total += weight;
+            if current_weights[i] > current_weights[best] {
+                best = i;
+            }
+        }
+        current_weights[best] -= total;
+        best
+    }
+
+    fn least_in_flight(&self, candidates: &[usize]) -> usize {
+        // Compare in_flight / weight without dividing, by cross-multiplying.
+        let load = |i: usize| {
+            let peer = &self.peers[i];
+            (
+                peer.in_flight.load(Ordering::Relaxed) as u64,
+                peer.config.weight as u64,
+            )
+        };
+        *candidates
+            .iter()
+            .min_by(|a, b| {
+                let (a_in_flight, a_weight) = load(**a);
+                let (b_in_flight, b_weight) = load(**b);
+                (a_in_flight * b_weight).cmp(&(b_in_flight * a_weight))
+            })
+            .unwrap()
+    }
+}
+
+/// Periodically check every peer of `pools`, marking a peer unhealthy if it does not answer
+/// `sui_getLatestCheckpointSequenceNumber`, or if its latest checkpoint lags too far behind the
+/// most up-to-date peer of its pool.
+pub async fn run_health_checks(
+    client: Client,
+    pools: Vec<Arc<PeerPool>>,
+    config: HealthCheckConfig,
+    metrics: AppMetrics,
+) {
+    let (client, config, metrics) = (&client, &config, &metrics);
+    let mut interval = tokio::time::interval(config.interval_seconds);
+    loop {
+        interval.tick().await;
+
+        futures::future::join_all(pools.iter().map(|pool| async move {
+            let results = futures::future::join_all(
+                pool.peers()
+                    .iter()
+                    .map(|peer| latest_checkpoint(client, peer, config)),
+            )
+            .await;
+            update_health(pool, results, config, metrics);
+        }))
+        .await;
+    }
+}
+
+/// Record the health check `results` of the peers of `pool`, in the order of `pool.peers()`.
+/// Lag is measured against the highest checkpoint within the pool, as read and execution peers
+/// may legitimately be at different heights.
+fn update_health(
+    pool: &PeerPool,
+    results: Vec<Result<u64>>,
+    config: &HealthCheckConfig,
+    metrics: &AppMetrics,
+) {
+    let highest = results
+        .iter()
+        .filter_map(|result| result.as_ref().ok())
+        .max()
+        .copied()
+        .unwrap_or_default();
+
+    for (peer, result) in pool.peers().iter().zip(results) {
+        match result {
+            Ok(checkpoint) => {
+                peer.latest_checkpoint.store(checkpoint, Ordering::Relaxed);
+                metrics
+                    .peer_latest_checkpoint
+                    .with_label_values(&[peer.role.as_str(), peer.address()])
+                    .set(checkpoint as i64);
+                let lag = highest.saturating_sub(checkpoint);
+                if lag > config.max_checkpoint_lag {
+                    debug!(
+                        "Peer {} is {} checkpoints behind the latest checkpoint {}",
+                        peer.address(),
+                        lag,
+                        highest
+                    );
+                }
+                peer.set_healthy(lag <= config.max_checkpoint_lag, metrics);
+            }
+            Err(e) => {
+                debug!("Health check failed for peer {}: {:#}", peer.address(), e);
+                metrics
+                    .error_counts
+                    .with_label_values(&[peer.role.as_str(), "health_check"])
+                    .inc();
+                peer.set_healthy(false, metrics);
+            }
+        }
+    }
+}
+
+async fn latest_checkpoint(
+    client: &Client,
+    peer: &Peer,
+    config: &HealthCheckConfig,
+) -> Result<u64> {
+    let response: serde_json::Value = client
+        .post(peer.config.address.clone())
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sui_getLatestCheckpointSequenceNumber",
+            "params": [],
+        }))
+        .timeout(config.timeout_seconds)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    // Checkpoint sequence numbers are returned as strings, to avoid losing precision in
+    // javascript clients.
+    let result = response
+        .get("result")
+        .ok_or_else(|| anyhow!("unexpected response {response}"))?;
+    match result {
+        serde_json::Value::String(s) => s.parse().context("invalid checkpoint sequence number"),
+        serde_json::Value::Number(n) => n
+            .as_u64()
+            .ok_or_else(|| anyhow!("invalid checkpoint sequence number {n}")),
+        _ => Err(anyhow!("unexpected response {response}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::Registry;
+
+    fn pool(strategy: LoadBalancingStrategy, weights: &[u32]) -> PeerPool {
+        let peers = weights
+            .iter()
+            .enumerate()
+            .map(|(i, weight)| PeerConfig {
+                address: format!("http://peer-{i}:9000").parse().unwrap(),
+                weight: *weight,
+            })
+            .collect();
+        PeerPool::new(
+            PeerRole::Read,
+            peers,
+            strategy,
+            AppMetrics::new(&Registry::new()),
+        )
+    }
+
+    fn index_of(pool: &PeerPool, guard: &InFlightGuard) -> usize {
+        pool.peers()
+            .iter()
+            .position(|peer| Arc::ptr_eq(peer, guard.peer()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_smooth_weighted_round_robin() {
+        let pool = pool(LoadBalancingStrategy::WeightedRoundRobin, &[5, 1, 1]);
+        let picks: Vec<usize> = (0..7)
+            .map(|_| index_of(&pool, &pool.select(&[]).unwrap()))
+            .collect();
+        // The heavy peer gets its share without being picked in one burst.
+        assert_eq!(picks, vec![0, 0, 1, 0, 2, 0, 0]);
+
+        // The sequence repeats once every peer has been picked according to its weight.
+        let picks: Vec<usize> = (0..7)
+            .map(|_| index_of(&pool, &pool.select(&[]).unwrap()))
+            .collect();
+        assert_eq!(picks, vec![0, 0, 1, 0, 2, 0, 0]);
+    }
+
+    #[test]
+    fn test_least_inflight() {
+        let pool = pool(LoadBalancingStrategy::LeastInflight, &[2, 1]);
+
+        // Held guards count as in flight: peer 0 takes two requests for every one of peer 1.
+        let first = pool.select(&[]).unwrap();
+        assert_eq!(index_of(&pool, &first), 0);
+        let second = pool.select(&[]).unwrap();
+        assert_eq!(index_of(&pool, &second), 1);
+        let third = pool.select(&[]).unwrap();
+        assert_eq!(index_of(&pool, &third), 0);
+        assert_eq!(pool.peers()[0].in_flight.load(Ordering::Relaxed), 2);
+
+        // Completing requests frees up capacity on the peer.
+        drop(first);
+        drop(third);
+        assert_eq!(pool.peers()[0].in_flight.load(Ordering::Relaxed), 0);
+        let fourth = pool.select(&[]).unwrap();
+        assert_eq!(index_of(&pool, &fourth), 0);
+        drop(second);
+        drop(fourth);
+    }
+
+    #[test]
+    fn test_fallback_selection() {
+        let pool = pool(LoadBalancingStrategy::WeightedRoundRobin, &[1, 1, 1]);
+        let metrics = AppMetrics::new(&Registry::new());
+
+        // Unhealthy peers are skipped while a healthy one is left.
+        pool.peers()[0].set_healthy(false, &metrics);
+        pool.peers()[1].set_healthy(false, &metrics);
+        for _ in 0..3 {
+            assert_eq!(index_of(&pool, &pool.select(&[]).unwrap()), 2);
+        }
+
+        // Once the healthy peers have been tried, unhealthy ones are used as a last resort.
+        let tried = vec![pool.peers()[2].clone()];
+        let guard = pool.select(&tried).unwrap();
+        let index = index_of(&pool, &guard);
+        assert!(index == 0 || index == 1);
+
+        // No peer is picked twice for the same request.
+        let tried = pool.peers().to_vec();
+        assert!(pool.select(&tried).is_none());
+    }
+
+    #[test]
+    fn test_health_lag_eviction() {
+        let config = HealthCheckConfig {
+            max_checkpoint_lag: 10,
+            ..Default::default()
+        };
+        let metrics = AppMetrics::new(&Registry::new());
+        let read_pool = pool(LoadBalancingStrategy::WeightedRoundRobin, &[1, 1, 1]);
+
+        update_health(&read_pool, vec![Ok(100), Ok(90), Ok(89)], &config, &metrics);
+        let healthy: Vec<bool> = read_pool.peers().iter().map(|p| p.is_healthy()).collect();
+        assert_eq!(healthy, vec![true, true, false]);
+
+        // Failed checks evict a peer, and do not count towards the highest checkpoint.
+        update_health(
+            &read_pool,
+            vec![Err(anyhow!("timeout")), Ok(95), Ok(89)],
+            &config,
+            &metrics,
+        );
+        let healthy: Vec<bool> = read_pool.peers().iter().map(|p| p.is_healthy()).collect();
+        assert_eq!(healthy, vec![false, true, true]);
+
+        // The lag is measured within a pool, so a pool that is behind another one stays healthy.
+        let execution_pool = pool(LoadBalancingStrategy::WeightedRoundRobin, &[1, 1]);
+        update_health(
+            &read_pool,
+            vec![Ok(1000), Ok(1000), Ok(1000)],
+            &config,
+            &metrics,
+        );
+        update_health(&execution_pool, vec![Ok(50), Ok(45)], &config, &metrics);
+        assert!(execution_pool.peers().iter().all(|p| p.is_healthy()));
+    }
+}
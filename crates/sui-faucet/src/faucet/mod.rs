@@ -4,13 +4,18 @@ use crate::FaucetError;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::TypeTag;
 use uuid::Uuid;
 
 mod simple_faucet;
 mod write_ahead_log;
 pub use self::simple_faucet::SimpleFaucet;
 use clap::Parser;
-use std::{net::Ipv4Addr, path::PathBuf, sync::Arc};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FaucetReceipt {
@@ -75,6 +80,32 @@ pub trait Faucet {
 
     /// Get the status of a batch_send request
     async fn get_batch_send_status(&self, task_id: Uuid) -> Result<BatchSendStatus, FaucetError>;
+
+    /// Send the configured amount of a non-SUI coin of type `Coin<coin_type>` to the recipient
+    async fn send_coin(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        coin_type: &TypeTag,
+    ) -> Result<FaucetReceipt, FaucetError>;
+
+    /// Whether the faucet is configured to send coins of type `Coin<coin_type>`
+    fn supports_coin_type(&self, coin_type: &TypeTag) -> bool;
+
+    /// Record a request from `recipient` (and `ip`, if known) against their quotas, failing if
+    /// either quota is exhausted.
+    async fn check_quota(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+    ) -> Result<(), FaucetError>;
+
+    /// Give back a request recorded by `check_quota` that could not be served.
+    async fn release_quota(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+    ) -> Result<(), FaucetError>;
 }
 
 pub const DEFAULT_AMOUNT: u64 = 1_000_000_000;
@@ -125,6 +156,27 @@ pub struct FaucetConfig {
 
     #[clap(long, action = clap::ArgAction::Set, default_value_t = false)]
     pub batch_enabled: bool,
+
+    /// Path to a YAML file listing the non-SUI coin types the faucet dispenses
+    #[clap(long)]
+    pub coin_config: Option<PathBuf>,
+
+    /// Maximum number of requests served for a single recipient address per quota window
+    #[clap(long)]
+    pub max_requests_per_address: Option<u64>,
+
+    /// Maximum number of requests served for a single client IP per quota window
+    #[clap(long)]
+    pub max_requests_per_ip: Option<u64>,
+
+    #[clap(long, default_value_t = 86400)]
+    pub quota_window_secs: u64,
+
+    /// Number of trusted proxies in front of the faucet. If set, the client IP that per-IP quotas
+    /// are counted against is read from the X-Forwarded-For header, this many entries from the
+    /// right, instead of being the address of the connecting peer.
+    #[clap(long)]
+    pub x_forwarded_for_hops: Option<usize>,
 }
 
 impl Default for FaucetConfig {
@@ -143,6 +195,40 @@ impl Default for FaucetConfig {
             batch_request_size: 500,
             ttl_expiration: 300,
             batch_enabled: false,
+            coin_config: None,
+            max_requests_per_address: None,
+            max_requests_per_ip: None,
+            quota_window_secs: 86400,
+            x_forwarded_for_hops: None,
         }
     }
 }
+
+/// How the faucet obtains the coins of a configured coin type
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CoinSource {
+    /// Mint new coins with `coin::mint_and_transfer`, using a `TreasuryCap` owned by the faucet
+    TreasuryCap { treasury_cap: ObjectID },
+    /// Split from the coins of this type owned by the faucet
+    Pool,
+}
+
+/// A non-SUI coin type dispensed by the faucet
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CoinConfig {
+    /// Type argument `T` of `Coin<T>`, e.g. `0x123::my_coin::MY_COIN`
+    pub coin_type: String,
+    /// Amount sent per request
+    pub amount: u64,
+    pub source: CoinSource,
+}
+
+impl CoinConfig {
+    /// Read the list of coin configs from a YAML file
+    pub fn load(path: &Path) -> Result<Vec<Self>, anyhow::Error> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_yaml::from_reader(file)?)
+    }
+}
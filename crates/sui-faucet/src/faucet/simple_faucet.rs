@@ -12,9 +12,15 @@ use std::collections::HashMap;
 #[cfg(test)]
 use std::collections::HashSet;
 use std::fmt;
+use std::net::IpAddr;
 use std::path::Path;
 use std::sync::{Arc, Weak};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_types::coin::COIN_MODULE_NAME;
+use sui_types::gas_coin::GAS;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::ObjectArg;
+use sui_types::{parse_sui_type_tag, Identifier, TypeTag, SUI_FRAMEWORK_PACKAGE_ID};
 use tap::tap::TapFallible;
 use tokio::sync::oneshot;
 use ttl_cache::TtlCache;
@@ -43,8 +49,8 @@ use uuid::Uuid;
 
 use super::write_ahead_log::WriteAheadLog;
 use crate::{
-    BatchFaucetReceipt, BatchSendStatus, BatchSendStatusType, CoinInfo, Faucet, FaucetConfig,
-    FaucetError, FaucetReceipt,
+    BatchFaucetReceipt, BatchSendStatus, BatchSendStatusType, CoinConfig, CoinInfo, CoinSource,
+    Faucet, FaucetConfig, FaucetError, FaucetReceipt,
 };
 
 pub struct SimpleFaucet {
@@ -61,6 +67,11 @@ pub struct SimpleFaucet {
     task_id_cache: Mutex<TtlCache<Uuid, BatchSendStatus>>,
     ttl_expiration: u64,
     coin_amount: u64,
+    /// Non-SUI coins dispensed by the faucet, keyed by the type argument of `Coin<T>`
+    coins: HashMap<TypeTag, FaucetCoin>,
+    max_requests_per_address: Option<u64>,
+    max_requests_per_ip: Option<u64>,
+    quota_window: Duration,
     /// Shuts down the batch transfer task. Used only in testing.
     #[allow(unused)]
     batch_transfer_shutdown: parking_lot::Mutex<Option<oneshot::Sender<()>>>,
@@ -77,10 +88,20 @@ impl fmt::Debug for SimpleFaucet {
             .field("batch_request_size", &self.batch_request_size)
             .field("ttl_expiration", &self.ttl_expiration)
             .field("coin_amount", &self.coin_amount)
+            .field("coins", &self.coins)
             .finish()
     }
 }
 
+#[derive(Debug)]
+struct FaucetCoin {
+    amount: u64,
+    source: CoinSource,
+    /// Held while a transfer of this coin is being built and executed. The treasury cap or pool
+    /// coins are mutated by every transfer, so only one can be in flight at a time.
+    lock: Mutex<()>,
+}
+
 enum GasCoinResponse {
     GasCoinWithInsufficientBalance(ObjectID),
     InvalidGasCoin(ObjectID),
@@ -121,6 +142,27 @@ impl SimpleFaucet {
         let balance = coins.iter().map(|coin| coin.0.balance.value()).sum::<u64>();
         metrics.balance.set(balance as i64);
 
+        let mut faucet_coins = HashMap::new();
+        if let Some(path) = &config.coin_config {
+            for CoinConfig {
+                coin_type,
+                amount,
+                source,
+            } in CoinConfig::load(path).map_err(FaucetError::internal)?
+            {
+                let coin_type = parse_sui_type_tag(&coin_type).map_err(FaucetError::internal)?;
+                info!(%coin_type, ?source, "Dispensing {amount} per request");
+                faucet_coins.insert(
+                    coin_type,
+                    FaucetCoin {
+                        amount,
+                        source,
+                        lock: Mutex::new(()),
+                    },
+                );
+            }
+        }
+
         let wal = WriteAheadLog::open(wal_path);
         let mut pending = vec![];
 
@@ -191,6 +233,10 @@ impl SimpleFaucet {
             task_id_cache: TtlCache::new(config.max_request_per_second as usize * 60 * 10).into(),
             ttl_expiration: config.ttl_expiration,
             coin_amount: config.amount,
+            coins: faucet_coins,
+            max_requests_per_address: config.max_requests_per_address,
+            max_requests_per_ip: config.max_requests_per_ip,
+            quota_window: Duration::from_secs(config.quota_window_secs),
             batch_transfer_shutdown: parking_lot::Mutex::new(Some(batch_transfer_shutdown)),
         };
 
@@ -490,10 +536,11 @@ impl SimpleFaucet {
                     let sui_used = balances
                         .iter()
                         .find(|balance| {
-                            balance
-                                .owner
-                                .get_address_owner_address()
-                                .is_ok_and(|address| address == self.active_address)
+                            balance.coin_type == GAS::type_tag()
+                                && balance
+                                    .owner
+                                    .get_address_owner_address()
+                                    .is_ok_and(|address| address == self.active_address)
                         })
                         .map(|b| b.amount)
                         .unwrap_or_else(|| 0);
@@ -567,6 +614,151 @@ impl SimpleFaucet {
         }
     }
 
+    /// Like `transfer_gases`, but sends a configured non-SUI coin, paying for gas with a coin from
+    /// the gas pool.
+    async fn transfer_coin(
+        &self,
+        coin_type: &TypeTag,
+        coin: &FaucetCoin,
+        recipient: SuiAddress,
+        uuid: Uuid,
+    ) -> Result<(TransactionDigest, Vec<ObjectID>), FaucetError> {
+        let gas_cost = self.get_gas_cost().await?;
+
+        loop {
+            match self.prepare_gas_coin(gas_cost, uuid, false).await {
+                GasCoinResponse::ValidGasCoin(gas_coin_id) => {
+                    let _guard = coin.lock.lock().await;
+                    let tx_data = match self
+                        .build_coin_txn(gas_coin_id, coin_type, coin, recipient, gas_cost)
+                        .await
+                    {
+                        Ok(tx_data) => tx_data,
+                        Err(e) => {
+                            // Nothing was sent with the gas coin, so it can be used again.
+                            self.recycle_gas_coin(gas_coin_id, uuid).await;
+                            return Err(e);
+                        }
+                    };
+
+                    {
+                        // Register the intention to send this transaction before we send it, so
+                        // that if faucet fails or we give up before we get a definite response,
+                        // we have a chance to retry later.
+                        let mut wal = self.wal.lock().await;
+                        wal.reserve(uuid, gas_coin_id, recipient, tx_data.clone())
+                            .map_err(FaucetError::internal)?;
+                    }
+                    let response = self
+                        .sign_and_execute_txn(uuid, recipient, gas_coin_id, tx_data, false)
+                        .await?;
+                    self.metrics.total_coin_requests_succeeded.inc();
+                    return self
+                        .check_and_map_transfer_gas_result(response, 1, recipient)
+                        .await;
+                }
+
+                GasCoinResponse::UnknownGasCoin(coin_id) => {
+                    self.recycle_gas_coin(coin_id, uuid).await;
+                    return Err(FaucetError::FullnodeReadingError(format!(
+                        "unknown gas coin {coin_id:?}"
+                    )));
+                }
+
+                GasCoinResponse::GasCoinWithInsufficientBalance(coin_id)
+                | GasCoinResponse::InvalidGasCoin(coin_id) => {
+                    warn!(?uuid, ?coin_id, "Unusable gas coin, removing from pool");
+                    self.metrics.total_discarded_coins.inc();
+                }
+
+                GasCoinResponse::NoGasCoinAvailable => return Err(FaucetError::NoGasCoinAvailable),
+            }
+        }
+    }
+
+    /// Build a transaction sending `coin.amount` of `Coin<coin_type>` to `recipient`, either by
+    /// minting it with the faucet's treasury cap or by splitting it from the faucet's own coins.
+    async fn build_coin_txn(
+        &self,
+        gas_coin_id: ObjectID,
+        coin_type: &TypeTag,
+        coin: &FaucetCoin,
+        recipient: SuiAddress,
+        budget: u64,
+    ) -> Result<TransactionData, FaucetError> {
+        let gas_payment = self
+            .wallet
+            .get_object_ref(gas_coin_id)
+            .await
+            .map_err(FaucetError::internal)?;
+        let gas_price = self.get_gas_price().await?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        match &coin.source {
+            CoinSource::TreasuryCap { treasury_cap } => {
+                let treasury_cap = self
+                    .wallet
+                    .get_object_ref(*treasury_cap)
+                    .await
+                    .map_err(FaucetError::internal)?;
+                let arguments = vec![
+                    builder
+                        .obj(ObjectArg::ImmOrOwnedObject(treasury_cap))
+                        .map_err(FaucetError::internal)?,
+                    builder.pure(coin.amount).map_err(FaucetError::internal)?,
+                    builder.pure(recipient).map_err(FaucetError::internal)?,
+                ];
+                builder.programmable_move_call(
+                    SUI_FRAMEWORK_PACKAGE_ID,
+                    COIN_MODULE_NAME.to_owned(),
+                    Identifier::new("mint_and_transfer").map_err(FaucetError::internal)?,
+                    vec![coin_type.clone()],
+                    arguments,
+                );
+            }
+
+            CoinSource::Pool => {
+                let client = self
+                    .wallet
+                    .get_client()
+                    .await
+                    .map_err(|e| FaucetError::Wallet(format!("Unable to get client: {e:?}")))?;
+                let coins = client
+                    .coin_read_api()
+                    .select_coins(
+                        self.active_address,
+                        Some(coin_type.to_canonical_string(/* with_prefix */ true)),
+                        coin.amount as u128,
+                        vec![],
+                    )
+                    .await
+                    .map_err(|e| match e {
+                        sui_sdk::error::Error::InsufficientFund { .. } => {
+                            FaucetError::InsuffientBalance
+                        }
+                        e => FaucetError::FullnodeReadingError(format!(
+                            "Error selecting {coin_type} coins: {e:?}"
+                        )),
+                    })?;
+                builder
+                    .pay(
+                        coins.iter().map(|coin| coin.object_ref()).collect(),
+                        vec![recipient],
+                        vec![coin.amount],
+                    )
+                    .map_err(FaucetError::internal)?;
+            }
+        }
+
+        Ok(TransactionData::new_programmable(
+            self.active_address,
+            vec![gas_payment],
+            builder.finish(),
+            budget,
+            gas_price,
+        ))
+    }
+
     async fn recycle_gas_coin(&self, coin_id: ObjectID, uuid: Uuid) {
         // Once transactions are done, in despite of success or failure,
         // we put back the coins. The producer should never wait indefinitely,
@@ -970,6 +1162,104 @@ impl Faucet for SimpleFaucet {
             None => Err(FaucetError::Internal("task id not found".to_string())),
         }
     }
+
+    async fn send_coin(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        coin_type: &TypeTag,
+    ) -> Result<FaucetReceipt, FaucetError> {
+        info!(?recipient, uuid = ?id, %coin_type, "Getting faucet coin request");
+        let coin = self
+            .coins
+            .get(coin_type)
+            .ok_or_else(|| FaucetError::UnsupportedCoinType(coin_type.to_string()))?;
+
+        let (digest, coin_ids) = self.transfer_coin(coin_type, coin, recipient, id).await?;
+
+        info!(uuid = ?id, ?recipient, ?digest, %coin_type, "Coin transfer succeeded");
+        let sent = coin_ids
+            .into_iter()
+            .map(|coin_id| CoinInfo {
+                amount: coin.amount,
+                id: coin_id,
+                transfer_tx_digest: digest,
+            })
+            .collect();
+
+        let faucet_receipt = FaucetReceipt { sent };
+        let mut task_map = self.task_id_cache.lock().await;
+        task_map.insert(
+            id,
+            BatchSendStatus {
+                status: BatchSendStatusType::SUCCEEDED,
+                transferred_gas_objects: Some(faucet_receipt.clone()),
+            },
+            Duration::from_secs(self.ttl_expiration),
+        );
+
+        Ok(faucet_receipt)
+    }
+
+    fn supports_coin_type(&self, coin_type: &TypeTag) -> bool {
+        self.coins.contains_key(coin_type)
+    }
+
+    async fn check_quota(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+    ) -> Result<(), FaucetError> {
+        if self.max_requests_per_address.is_none() && self.max_requests_per_ip.is_none() {
+            return Ok(());
+        }
+
+        let now_ms = now_ms()?;
+        let consumed = self
+            .wal
+            .lock()
+            .await
+            .try_consume_quota(
+                (recipient, self.max_requests_per_address),
+                ip.map(|ip| (ip, self.max_requests_per_ip)),
+                self.quota_window.as_millis() as u64,
+                now_ms,
+            )
+            .map_err(FaucetError::internal)?;
+
+        if consumed {
+            Ok(())
+        } else {
+            info!(?recipient, ?ip, "Request quota exhausted");
+            Err(FaucetError::TooManyRequests(format!(
+                "request quota for {recipient} exhausted, try again in a while"
+            )))
+        }
+    }
+
+    async fn release_quota(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+    ) -> Result<(), FaucetError> {
+        if self.max_requests_per_address.is_none() && self.max_requests_per_ip.is_none() {
+            return Ok(());
+        }
+
+        let now_ms = now_ms()?;
+        self.wal
+            .lock()
+            .await
+            .release_quota(recipient, ip, self.quota_window.as_millis() as u64, now_ms)
+            .map_err(FaucetError::internal)
+    }
+}
+
+fn now_ms() -> Result<u64, FaucetError> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(FaucetError::internal)?
+        .as_millis() as u64)
 }
 
 pub async fn batch_gather(
@@ -1115,11 +1405,14 @@ mod tests {
     use super::*;
     use anyhow::*;
     use shared_crypto::intent::Intent;
+    use sui_json_rpc_types::ObjectChange;
     use sui_json_rpc_types::SuiExecutionStatus;
     use sui_json_rpc_types::SuiTransactionBlockEffects;
     use sui_sdk::wallet_context::WalletContext;
+    use sui_test_transaction_builder::TestTransactionBuilder;
     use sui_types::transaction::SenderSignedData;
     use sui_types::transaction::TransactionDataAPI;
+    use sui_types::transaction::TEST_ONLY_GAS_UNIT_FOR_HEAVY_COMPUTATION_STORAGE;
     use test_cluster::TestClusterBuilder;
 
     async fn execute_tx(
@@ -1978,6 +2271,207 @@ mod tests {
         }
     }
 
+    /// Publish the example coin package, whose treasury cap is sent to the publisher. Returns the
+    /// id of the treasury cap and the type of the coin.
+    async fn publish_example_coin(context: &WalletContext) -> (ObjectID, TypeTag) {
+        let (sender, gas_object) = context.get_one_gas_object().await.unwrap().unwrap();
+        let gas_price = context.get_reference_gas_price().await.unwrap();
+        let txn = context.sign_transaction(
+            &TestTransactionBuilder::new(sender, gas_object, gas_price)
+                .publish_examples("coin")
+                .build(),
+        );
+        let resp = context.execute_transaction_must_succeed(txn).await;
+        resp.object_changes
+            .unwrap()
+            .into_iter()
+            .find_map(|change| match change {
+                ObjectChange::Created {
+                    object_id,
+                    object_type,
+                    ..
+                } if object_type.name.as_str() == "TreasuryCap"
+                    && object_type.type_params[0].to_string().ends_with("MY_COIN") =>
+                {
+                    Some((object_id, object_type.type_params[0].clone()))
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mint_coin_with_treasury_cap_and_quota() {
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let context = test_cluster.wallet;
+
+        let (treasury_cap, coin_type) = publish_example_coin(&context).await;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let coin_config = tmp.path().join("coins.yaml");
+        std::fs::write(
+            &coin_config,
+            serde_yaml::to_string(&vec![CoinConfig {
+                coin_type: coin_type.to_canonical_string(true),
+                amount: 100,
+                source: CoinSource::TreasuryCap { treasury_cap },
+            }])
+            .unwrap(),
+        )
+        .unwrap();
+
+        let prom_registry = Registry::new();
+        let config = FaucetConfig {
+            coin_config: Some(coin_config),
+            max_requests_per_address: Some(1),
+            ..Default::default()
+        };
+        let faucet = SimpleFaucet::new(
+            context,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            config,
+        )
+        .await
+        .unwrap();
+
+        let recipient = SuiAddress::random_for_testing_only();
+        faucet.check_quota(recipient, None).await.unwrap();
+        let receipt = faucet
+            .send_coin(Uuid::new_v4(), recipient, &coin_type)
+            .await
+            .unwrap();
+        assert_eq!(receipt.sent.len(), 1);
+        assert_eq!(receipt.sent[0].amount, 100);
+
+        // The recipient has used up its quota, but other addresses are still served.
+        assert!(matches!(
+            faucet.check_quota(recipient, None).await,
+            Err(FaucetError::TooManyRequests(_))
+        ));
+        let other = SuiAddress::random_for_testing_only();
+        faucet.check_quota(other, None).await.unwrap();
+        faucet
+            .send_coin(Uuid::new_v4(), other, &coin_type)
+            .await
+            .unwrap();
+
+        // Coins that are not configured are rejected.
+        assert!(matches!(
+            faucet
+                .send_coin(Uuid::new_v4(), other, &GAS::type_tag())
+                .await,
+            Err(FaucetError::UnsupportedCoinType(_))
+        ));
+
+        // Treasury cap transactions release their gas coins, and leave nothing in the WAL.
+        let wal = faucet.wal.lock().await;
+        assert!(wal.log.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_send_coin_from_pool() {
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let mut context = test_cluster.wallet;
+        let faucet_address = context.active_address().unwrap();
+
+        // Mint a single coin of the example type to the faucet's own address, which the faucet
+        // then splits the coins it sends from.
+        let (treasury_cap, coin_type) = publish_example_coin(&context).await;
+        let gas_object = context
+            .get_one_gas_object_owned_by_address(faucet_address)
+            .await
+            .unwrap()
+            .unwrap();
+        let gas_price = context.get_reference_gas_price().await.unwrap();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let arguments = vec![
+            builder
+                .obj(ObjectArg::ImmOrOwnedObject(
+                    context.get_object_ref(treasury_cap).await.unwrap(),
+                ))
+                .unwrap(),
+            builder.pure(1000u64).unwrap(),
+            builder.pure(faucet_address).unwrap(),
+        ];
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            COIN_MODULE_NAME.to_owned(),
+            Identifier::new("mint_and_transfer").unwrap(),
+            vec![coin_type.clone()],
+            arguments,
+        );
+        let txn = context.sign_transaction(&TransactionData::new_programmable(
+            faucet_address,
+            vec![gas_object],
+            builder.finish(),
+            TEST_ONLY_GAS_UNIT_FOR_HEAVY_COMPUTATION_STORAGE * gas_price,
+            gas_price,
+        ));
+        context.execute_transaction_must_succeed(txn).await;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let coin_config = tmp.path().join("coins.yaml");
+        std::fs::write(
+            &coin_config,
+            serde_yaml::to_string(&vec![CoinConfig {
+                coin_type: coin_type.to_canonical_string(true),
+                amount: 300,
+                source: CoinSource::Pool,
+            }])
+            .unwrap(),
+        )
+        .unwrap();
+
+        let prom_registry = Registry::new();
+        let config = FaucetConfig {
+            coin_config: Some(coin_config),
+            ..Default::default()
+        };
+        let faucet = SimpleFaucet::new(
+            context,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            config,
+        )
+        .await
+        .unwrap();
+        assert!(faucet.supports_coin_type(&coin_type));
+        assert!(!faucet.supports_coin_type(&GAS::type_tag()));
+
+        let recipient = SuiAddress::random_for_testing_only();
+        for _ in 0..3 {
+            let receipt = faucet
+                .send_coin(Uuid::new_v4(), recipient, &coin_type)
+                .await
+                .unwrap();
+            assert_eq!(receipt.sent.len(), 1);
+            assert_eq!(receipt.sent[0].amount, 300);
+        }
+        let balance = faucet
+            .wallet
+            .get_client()
+            .await
+            .unwrap()
+            .coin_read_api()
+            .get_balance(recipient, Some(coin_type.to_canonical_string(true)))
+            .await
+            .unwrap();
+        assert_eq!(balance.total_balance, 900);
+
+        // Only 100 is left in the pool, which is not enough for another request.
+        assert!(matches!(
+            faucet
+                .send_coin(Uuid::new_v4(), recipient, &coin_type)
+                .await,
+            Err(FaucetError::InsuffientBalance)
+        ));
+
+        // The failed request released its gas coin, and left nothing in the WAL.
+        let wal = faucet.wal.lock().await;
+        assert!(wal.log.is_empty());
+    }
+
     async fn test_send_interface_has_success_status(faucet: &impl Faucet) {
         let recipient = SuiAddress::random_for_testing_only();
         let amounts = vec![1, 2, 3];
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::net::IpAddr;
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
@@ -21,9 +22,14 @@ use uuid::Uuid;
 ///
 /// This allows the faucet to go down and back up, and not forget which requests were in-flight that
 /// it needs to confirm succeeded or failed.
+///
+/// The same database also holds the per-address and per-IP request quotas, so that they survive
+/// restarts as well.
 #[derive(DBMapUtils, Clone)]
 pub struct WriteAheadLog {
     pub log: DBMap<ObjectID, Entry>,
+    pub address_quotas: DBMap<SuiAddress, QuotaEntry>,
+    pub ip_quotas: DBMap<IpAddr, QuotaEntry>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -36,6 +42,42 @@ pub struct Entry {
     pub in_flight: bool,
 }
 
+/// Number of requests served for a key since the start of its current quota window.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct QuotaEntry {
+    pub window_start_ms: u64,
+    pub count: u64,
+}
+
+impl QuotaEntry {
+    /// The entry after counting one more request at `now_ms`, or `None` if that would exceed
+    /// `limit` requests within the window.
+    fn next(entry: Option<Self>, limit: u64, window_ms: u64, now_ms: u64) -> Option<Self> {
+        let entry = match entry {
+            Some(entry) if now_ms.saturating_sub(entry.window_start_ms) < window_ms => entry,
+            _ => QuotaEntry {
+                window_start_ms: now_ms,
+                count: 0,
+            },
+        };
+        (entry.count < limit).then_some(QuotaEntry {
+            count: entry.count + 1,
+            ..entry
+        })
+    }
+
+    /// The entry with one request taken back at `now_ms`, or `None` if there is nothing to take
+    /// back within the current window.
+    fn prev(self, window_ms: u64, now_ms: u64) -> Option<Self> {
+        (self.count > 0 && now_ms.saturating_sub(self.window_start_ms) < window_ms).then_some(
+            QuotaEntry {
+                count: self.count - 1,
+                ..self
+            },
+        )
+    }
+}
+
 impl WriteAheadLog {
     pub(crate) fn open(path: &Path) -> Self {
         Self::open_tables_read_write(
@@ -108,6 +150,65 @@ impl WriteAheadLog {
         Ok(())
     }
 
+    /// Count a request from `address` (and `ip`, if provided) made at `now_ms` against the
+    /// quotas of both. `None` limits are not enforced. Returns `Ok(false)` without counting
+    /// anything if either quota has been exhausted within the current window.
+    pub(crate) fn try_consume_quota(
+        &mut self,
+        address: (SuiAddress, Option<u64>),
+        ip: Option<(IpAddr, Option<u64>)>,
+        window_ms: u64,
+        now_ms: u64,
+    ) -> Result<bool, TypedStoreError> {
+        let mut batch = self.log.batch();
+
+        let (address, address_limit) = address;
+        if let Some(limit) = address_limit {
+            let entry = self.address_quotas.get(&address)?;
+            let Some(entry) = QuotaEntry::next(entry, limit, window_ms, now_ms) else {
+                return Ok(false);
+            };
+            batch.insert_batch(&self.address_quotas, [(address, entry)])?;
+        }
+
+        if let Some((ip, Some(limit))) = ip {
+            let entry = self.ip_quotas.get(&ip)?;
+            let Some(entry) = QuotaEntry::next(entry, limit, window_ms, now_ms) else {
+                return Ok(false);
+            };
+            batch.insert_batch(&self.ip_quotas, [(ip, entry)])?;
+        }
+
+        batch.write()?;
+        Ok(true)
+    }
+
+    /// Give back a request from `address` (and `ip`, if provided) that was counted by
+    /// `try_consume_quota` at `now_ms` but could not be served. Quotas whose window has since
+    /// been reset are left alone.
+    pub(crate) fn release_quota(
+        &mut self,
+        address: SuiAddress,
+        ip: Option<IpAddr>,
+        window_ms: u64,
+        now_ms: u64,
+    ) -> Result<(), TypedStoreError> {
+        let mut batch = self.log.batch();
+        if let Some(entry) = self.address_quotas.get(&address)? {
+            if let Some(entry) = entry.prev(window_ms, now_ms) {
+                batch.insert_batch(&self.address_quotas, [(address, entry)])?;
+            }
+        }
+        if let Some(ip) = ip {
+            if let Some(entry) = self.ip_quotas.get(&ip)? {
+                if let Some(entry) = entry.prev(window_ms, now_ms) {
+                    batch.insert_batch(&self.ip_quotas, [(ip, entry)])?;
+                }
+            }
+        }
+        batch.write()
+    }
+
     pub(crate) fn set_in_flight(
         &mut self,
         coin: ObjectID,
@@ -252,6 +353,85 @@ mod tests {
         wal.reserve(uuid, coin.0, recv1, tx1).unwrap();
     }
 
+    #[tokio::test]
+    async fn quota_exhausted_then_reset() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut wal = WriteAheadLog::open(&tmp.path().join("wal"));
+
+        let address = SuiAddress::random_for_testing_only();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let window_ms = 1000;
+
+        for _ in 0..2 {
+            assert!(wal
+                .try_consume_quota((address, Some(2)), Some((ip, Some(5))), window_ms, 0)
+                .unwrap());
+        }
+
+        // The address quota is exhausted, and the IP quota must not be charged for the rejection.
+        assert!(!wal
+            .try_consume_quota((address, Some(2)), Some((ip, Some(5))), window_ms, 10)
+            .unwrap());
+        assert_eq!(wal.ip_quotas.get(&ip).unwrap().unwrap().count, 2);
+
+        // Other addresses from the same IP are still served until the IP quota runs out.
+        for _ in 0..3 {
+            let other = SuiAddress::random_for_testing_only();
+            assert!(wal
+                .try_consume_quota((other, Some(2)), Some((ip, Some(5))), window_ms, 20)
+                .unwrap());
+        }
+        let other = SuiAddress::random_for_testing_only();
+        assert!(!wal
+            .try_consume_quota((other, Some(2)), Some((ip, Some(5))), window_ms, 30)
+            .unwrap());
+
+        // Quotas are reset once the window has elapsed, including after reopening the database.
+        drop(wal);
+        let mut wal = WriteAheadLog::open(&tmp.path().join("wal"));
+        assert!(!wal
+            .try_consume_quota((address, Some(2)), None, window_ms, 999)
+            .unwrap());
+        assert!(wal
+            .try_consume_quota((address, Some(2)), Some((ip, Some(5))), window_ms, 1000)
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn quota_released() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut wal = WriteAheadLog::open(&tmp.path().join("wal"));
+
+        let address = SuiAddress::random_for_testing_only();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let window_ms = 1000;
+
+        assert!(wal
+            .try_consume_quota((address, Some(1)), Some((ip, Some(1))), window_ms, 0)
+            .unwrap());
+        assert!(!wal
+            .try_consume_quota((address, Some(1)), Some((ip, Some(1))), window_ms, 10)
+            .unwrap());
+
+        // A request that could not be served is given back to both quotas.
+        wal.release_quota(address, Some(ip), window_ms, 20).unwrap();
+        assert_eq!(wal.address_quotas.get(&address).unwrap().unwrap().count, 0);
+        assert_eq!(wal.ip_quotas.get(&ip).unwrap().unwrap().count, 0);
+        assert!(wal
+            .try_consume_quota((address, Some(1)), Some((ip, Some(1))), window_ms, 30)
+            .unwrap());
+
+        // Releasing never goes below zero, and leaves quotas from a past window alone.
+        wal.release_quota(address, Some(ip), window_ms, 40).unwrap();
+        wal.release_quota(address, Some(ip), window_ms, 50).unwrap();
+        assert_eq!(wal.address_quotas.get(&address).unwrap().unwrap().count, 0);
+        assert!(wal
+            .try_consume_quota((address, Some(1)), None, window_ms, 60)
+            .unwrap());
+        wal.release_quota(address, None, window_ms, 1000).unwrap();
+        assert_eq!(wal.address_quotas.get(&address).unwrap().unwrap().count, 1);
+    }
+
     fn random_request(coin: ObjectRef) -> (SuiAddress, TransactionData) {
         let gas_price = 1;
         let send = SuiAddress::random_for_testing_only();
@@ -13,6 +13,9 @@ pub enum FaucetRequest {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FixedAmountRequest {
     pub recipient: SuiAddress,
+    /// Type argument `T` of the requested `Coin<T>`. Defaults to SUI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coin_type: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -24,6 +27,17 @@ impl FaucetRequest {
     pub fn new_fixed_amount_request(recipient: impl Into<SuiAddress>) -> Self {
         Self::FixedAmountRequest(FixedAmountRequest {
             recipient: recipient.into(),
+            coin_type: None,
+        })
+    }
+
+    pub fn new_fixed_amount_request_for_coin(
+        recipient: impl Into<SuiAddress>,
+        coin_type: impl Into<String>,
+    ) -> Self {
+        Self::FixedAmountRequest(FixedAmountRequest {
+            recipient: recipient.into(),
+            coin_type: Some(coin_type.into()),
         })
     }
 
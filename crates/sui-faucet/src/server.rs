@@ -3,13 +3,13 @@
 
 use crate::{
     AppState, BatchFaucetResponse, BatchStatusFaucetResponse, FaucetConfig, FaucetError,
-    FaucetRequest, FaucetResponse, RequestMetricsLayer,
+    FaucetRequest, FaucetResponse, FixedAmountRequest, RequestMetricsLayer,
 };
 
 use axum::{
     error_handling::HandleErrorLayer,
-    extract::Path,
-    http::StatusCode,
+    extract::{ConnectInfo, Path},
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     routing::{get, post},
     BoxError, Extension, Json, Router,
@@ -26,6 +26,7 @@ use std::{
 };
 use sui_config::SUI_CLIENT_CONFIG;
 use sui_sdk::wallet_context::WalletContext;
+use sui_types::{base_types::SuiAddress, gas_coin::GAS, parse_sui_type_tag, TypeTag};
 use tower::ServiceBuilder;
 use tower_governor::{
     governor::GovernorConfigBuilder, key_extractor::GlobalKeyExtractor, GovernorLayer,
@@ -96,7 +97,11 @@ pub async fn start_faucet(
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("listening on {}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
 
@@ -105,9 +110,82 @@ async fn health() -> &'static str {
     "OK"
 }
 
+/// Parse the coin type requested, returning `None` for SUI, which is served from the gas coin pool
+/// rather than the configured coins.
+fn requested_coin_type(request: &FixedAmountRequest) -> Result<Option<TypeTag>, FaucetError> {
+    let Some(coin_type) = &request.coin_type else {
+        return Ok(None);
+    };
+    let coin_type = parse_sui_type_tag(coin_type)
+        .map_err(|_| FaucetError::UnsupportedCoinType(coin_type.clone()))?;
+    Ok((coin_type != GAS::type_tag()).then_some(coin_type))
+}
+
+/// The IP that per-IP quotas are counted against. Behind `x_forwarded_for_hops` trusted proxies,
+/// this is the entry that many hops from the right of the X-Forwarded-For header, otherwise it is
+/// the address of the connecting peer.
+fn client_ip(x_forwarded_for_hops: Option<usize>, headers: &HeaderMap, peer: SocketAddr) -> IpAddr {
+    let Some(num_hops) = x_forwarded_for_hops else {
+        return peer.ip();
+    };
+    let forwarded = headers
+        .get("x-forwarded-for")
+        .and_then(|header| header.to_str().ok())
+        .map(|header| header.split(',').map(str::trim).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let client = forwarded
+        .len()
+        .checked_sub(num_hops)
+        .and_then(|i| forwarded.get(i))
+        .and_then(|client| client.parse().ok());
+    client.unwrap_or_else(|| {
+        warn!(
+            ?forwarded,
+            num_hops, "Could not read client IP from x-forwarded-for, using the peer address"
+        );
+        peer.ip()
+    })
+}
+
+/// Validate the coin type of `request` and count it against the quotas of its recipient and
+/// client IP. Requests that are admitted but then fail are given back with `release_quota`.
+async fn admit_request(
+    state: &AppState,
+    request: &FixedAmountRequest,
+    ip: IpAddr,
+) -> Result<Option<TypeTag>, (StatusCode, FaucetError)> {
+    let coin_type = requested_coin_type(request).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    if let Some(coin_type) = &coin_type {
+        if !state.faucet.supports_coin_type(coin_type) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                FaucetError::UnsupportedCoinType(coin_type.to_string()),
+            ));
+        }
+    }
+    state
+        .faucet
+        .check_quota(request.recipient, Some(ip))
+        .await
+        .map_err(|e| match e {
+            FaucetError::TooManyRequests(_) => (StatusCode::TOO_MANY_REQUESTS, e),
+            e => (StatusCode::INTERNAL_SERVER_ERROR, e),
+        })?;
+    Ok(coin_type)
+}
+
+/// Give back the quota charged by `admit_request` for a request that could not be served.
+async fn release_quota(state: &AppState, recipient: SuiAddress, ip: IpAddr) {
+    if let Err(e) = state.faucet.release_quota(recipient, Some(ip)).await {
+        warn!(?recipient, ?ip, "Failed to release request quota: {:?}", e);
+    }
+}
+
 /// handler for batch_request_gas requests
 async fn batch_request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     let id = Uuid::new_v4();
@@ -123,16 +201,58 @@ async fn batch_request_gas(
         );
     };
 
-    if state.config.batch_enabled {
+    let ip = client_ip(state.config.x_forwarded_for_hops, &headers, peer);
+    let coin_type = match admit_request(&state, &request, ip).await {
+        Ok(coin_type) => coin_type,
+        Err((status, e)) => {
+            warn!(uuid =?id, "Rejected gas request: {:?}", e);
+            return (status, Json(BatchFaucetResponse::from(e)));
+        }
+    };
+
+    if let Some(coin_type) = coin_type {
+        // Only SUI is batched, other coins are sent straight away and their status recorded
+        // against the request id.
         let result = spawn_monitored_task!(async move {
-            state
+            let result = state
+                .faucet
+                .send_coin(id, request.recipient, &coin_type)
+                .await;
+            if result.is_err() {
+                release_quota(&state, request.recipient, ip).await;
+            }
+            result
+        })
+        .await
+        .unwrap();
+
+        match result {
+            Ok(_) => {
+                info!(uuid =?id, "Request is successfully served");
+                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(id)))
+            }
+            Err(v) => {
+                warn!(uuid =?id, "Failed to request coin: {:?}", v);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(BatchFaucetResponse::from(v)),
+                )
+            }
+        }
+    } else if state.config.batch_enabled {
+        let result = spawn_monitored_task!(async move {
+            let result = state
                 .faucet
                 .batch_send(
                     id,
                     request.recipient,
                     &vec![state.config.amount; state.config.num_coins],
                 )
-                .await
+                .await;
+            if result.is_err() {
+                release_quota(&state, request.recipient, ip).await;
+            }
+            result
         })
         .await
         .unwrap();
@@ -154,14 +274,18 @@ async fn batch_request_gas(
         // TODO (jian): remove this feature gate when batch has proven to be baked long enough
         info!(uuid = ?id, "Falling back to v1 implementation");
         let result = spawn_monitored_task!(async move {
-            state
+            let result = state
                 .faucet
                 .send(
                     id,
                     request.recipient,
                     &vec![state.config.amount; state.config.num_coins],
                 )
-                .await
+                .await;
+            if result.is_err() {
+                release_quota(&state, request.recipient, ip).await;
+            }
+            result
         })
         .await
         .unwrap();
@@ -213,6 +337,8 @@ async fn request_status(
 /// handler for all the request_gas requests
 async fn request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     // ID for traceability
@@ -220,17 +346,40 @@ async fn request_gas(
     info!(uuid = ?id, "Got new gas request.");
     let result = match payload {
         FaucetRequest::FixedAmountRequest(requests) => {
+            let ip = client_ip(state.config.x_forwarded_for_hops, &headers, peer);
+            let coin_type = match admit_request(&state, &requests, ip).await {
+                Ok(coin_type) => coin_type,
+                Err((status, e)) => {
+                    warn!(uuid =?id, "Rejected gas request: {:?}", e);
+                    return (status, Json(FaucetResponse::from(e)));
+                }
+            };
+
             // We spawn a tokio task for this such that connection drop will not interrupt
             // it and impact the recycling of coins
             spawn_monitored_task!(async move {
-                state
-                    .faucet
-                    .send(
-                        id,
-                        requests.recipient,
-                        &vec![state.config.amount; state.config.num_coins],
-                    )
-                    .await
+                let result = match coin_type {
+                    Some(coin_type) => {
+                        state
+                            .faucet
+                            .send_coin(id, requests.recipient, &coin_type)
+                            .await
+                    }
+                    None => {
+                        state
+                            .faucet
+                            .send(
+                                id,
+                                requests.recipient,
+                                &vec![state.config.amount; state.config.num_coins],
+                            )
+                            .await
+                    }
+                };
+                if result.is_err() {
+                    release_quota(&state, requests.recipient, ip).await;
+                }
+                result
             })
             .await
             .unwrap()
@@ -285,3 +434,33 @@ async fn handle_error(error: BoxError) -> impl IntoResponse {
         Cow::from(format!("Unhandled internal error: {}", error)),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_ip() {
+        let peer: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            "1.1.1.1, 2.2.2.2, 3.3.3.3".parse().unwrap(),
+        );
+
+        // The header is only trusted when the faucet is configured to be behind proxies.
+        assert_eq!(client_ip(None, &headers, peer), peer.ip());
+        assert_eq!(
+            client_ip(Some(1), &headers, peer),
+            "3.3.3.3".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(
+            client_ip(Some(2), &headers, peer),
+            "2.2.2.2".parse::<IpAddr>().unwrap()
+        );
+
+        // Missing or short headers fall back to the peer address.
+        assert_eq!(client_ip(Some(4), &headers, peer), peer.ip());
+        assert_eq!(client_ip(Some(1), &HeaderMap::new(), peer), peer.ip());
+    }
+}
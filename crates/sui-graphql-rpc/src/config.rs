@@ -8,7 +8,7 @@ use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use move_core_types::ident_str;
 use move_core_types::identifier::IdentStr;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeSet, fmt::Display, time::Duration};
+use std::{collections::BTreeSet, fmt::Display, path::PathBuf, time::Duration};
 use sui_default_config::DefaultConfig;
 use sui_json_rpc::name_service::NameServiceConfig;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -24,6 +24,10 @@ const MOVE_REGISTRY_PACKAGE: &str =
 const MOVE_REGISTRY_TABLE_ID: &str =
     "0xe8417c530cde59eddf6dfb760e8a0e3e2c6f17c69ddaab5a73dd6a6e65fc463b";
 const DEFAULT_PAGE_LIMIT: u16 = 50;
+const DEFAULT_MAX_AUTOMATIC_PERSISTED_QUERIES: usize = 1_000;
+const DEFAULT_RESPONSE_CACHE_CAPACITY: usize = 10_000;
+const DEFAULT_API_KEYS_RELOAD_INTERVAL_MS: u64 = 30_000;
+const DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION: usize = 10;
 
 /// The combination of all configurations for the GraphQL service.
 #[DefaultConfig]
@@ -79,6 +83,10 @@ pub struct ServiceConfig {
     pub background_tasks: BackgroundTasksConfig,
     pub zklogin: ZkLoginConfig,
     pub move_registry: MoveRegistryConfig,
+    pub persisted_queries: PersistedQueriesConfig,
+    pub response_cache: ResponseCacheConfig,
+    pub api_keys: ApiKeysConfig,
+    pub subscriptions: SubscriptionsConfig,
 }
 
 #[DefaultConfig]
@@ -134,6 +142,75 @@ pub struct BackgroundTasksConfig {
     pub watermark_update_ms: u64,
 }
 
+/// Configuration for persisted queries: operations that are registered with the service ahead of
+/// time (from a file or a database table), or by clients at runtime (Automatic Persisted Queries),
+/// so that they can be referred to by the SHA-256 hash of their query text.
+#[DefaultConfig]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct PersistedQueriesConfig {
+    pub mode: PersistedQueriesMode,
+    /// Path to a TOML file of operations to register, under `[[operations]]`. Each operation has a
+    /// `name`, a `query`, and optionally some `limits` that override the service's limits when
+    /// running that operation.
+    pub path: Option<PathBuf>,
+    /// Name of a table in the database to load operations to register from. The table must have
+    /// `name` and `query` text columns, and a nullable `limits` text column containing overrides
+    /// as a JSON object.
+    pub table: Option<String>,
+    /// Maximum number of queries registered by clients to remember at once.
+    pub max_automatic_queries: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PersistedQueriesMode {
+    /// Persisted queries are not supported, every request must contain its query.
+    #[default]
+    Disabled,
+    /// Clients may refer to registered operations by hash, and may also register new queries by
+    /// sending them along with their hash. Requests can still contain arbitrary queries.
+    Automatic,
+    /// Only registered operations may be run. Requests can refer to them by hash or by including
+    /// their query text.
+    Strict,
+}
+
+/// Configuration for caching the results of top-level query fields. Results are cached for as long
+/// as the checkpoint they were computed at remains in the service's available range.
+#[DefaultConfig]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ResponseCacheConfig {
+    pub enabled: bool,
+    /// Maximum number of results to keep in the in-process cache at once.
+    pub capacity: usize,
+}
+
+/// Configuration for authenticating requests by API key, sent in the `x-sui-rpc-api-key` header.
+/// Each key can be subject to its own quotas.
+#[DefaultConfig]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ApiKeysConfig {
+    /// Path to a TOML file of API keys, under `[[keys]]`. Each key has a `name`, the `key` itself,
+    /// whether it can access admin endpoints (`admin`), and optionally `requests-per-minute`,
+    /// `query-cost-per-minute` and `max-page-size` quotas. API keys are not checked if this is not
+    /// set.
+    pub path: Option<PathBuf>,
+    /// Whether requests without an API key are rejected. If not, they are only subject to the
+    /// service's limits.
+    pub required: bool,
+    /// How often to check the API keys file for changes.
+    pub reload_interval_ms: u64,
+}
+
+/// Configuration for subscriptions, served over WebSockets at `/subscriptions`.
+#[DefaultConfig]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SubscriptionsConfig {
+    /// Maximum number of subscriptions that can be active at once on a single WebSocket
+    /// connection.
+    pub max_per_connection: usize,
+}
+
 #[DefaultConfig]
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct MoveRegistryConfig {
@@ -544,6 +621,44 @@ impl Default for BackgroundTasksConfig {
     }
 }
 
+impl Default for PersistedQueriesConfig {
+    fn default() -> Self {
+        Self {
+            mode: PersistedQueriesMode::Disabled,
+            path: None,
+            table: None,
+            max_automatic_queries: DEFAULT_MAX_AUTOMATIC_PERSISTED_QUERIES,
+        }
+    }
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capacity: DEFAULT_RESPONSE_CACHE_CAPACITY,
+        }
+    }
+}
+
+impl Default for SubscriptionsConfig {
+    fn default() -> Self {
+        Self {
+            max_per_connection: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+        }
+    }
+}
+
+impl Default for ApiKeysConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            required: false,
+            reload_interval_ms: DEFAULT_API_KEYS_RELOAD_INTERVAL_MS,
+        }
+    }
+}
+
 impl Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.full)
@@ -717,6 +832,92 @@ mod tests {
         assert_eq!(actual, expect);
     }
 
+    #[test]
+    fn test_read_persisted_queries_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [persisted-queries]
+                mode = "strict"
+                path = "persisted-queries.toml"
+                table = "graphql_persisted_queries"
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            persisted_queries: PersistedQueriesConfig {
+                mode: PersistedQueriesMode::Strict,
+                path: Some(PathBuf::from("persisted-queries.toml")),
+                table: Some("graphql_persisted_queries".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_read_response_cache_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [response-cache]
+                enabled = true
+                capacity = 500
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            response_cache: ResponseCacheConfig {
+                enabled: true,
+                capacity: 500,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_read_api_keys_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [api-keys]
+                path = "api-keys.toml"
+                required = true
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            api_keys: ApiKeysConfig {
+                path: Some(PathBuf::from("api-keys.toml")),
+                required: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_read_subscriptions_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [subscriptions]
+                max-per-connection = 3
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            subscriptions: SubscriptionsConfig {
+                max_per_connection: 3,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
     #[test]
     fn test_read_partial_in_service_config() {
         let actual = ServiceConfig::read(
@@ -47,6 +47,13 @@ pub(crate) trait Checkpointed: CursorType {
     fn checkpoint_viewed_at(&self) -> u64;
 }
 
+/// Trait for cursors that can be re-issued at a later checkpoint, to resume iterating over results
+/// that were added to the data-set after the cursor was originally handed out (e.g. to serve
+/// subscriptions as the watermark advances).
+pub(crate) trait Resumable: Checkpointed {
+    fn at_checkpoint(&self, checkpoint_viewed_at: u64) -> Self;
+}
+
 impl Checkpointed for JsonCursor<ConsistentIndexCursor> {
     fn checkpoint_viewed_at(&self) -> u64 {
         self.c
@@ -13,7 +13,11 @@ use crate::types::move_registry::error::MoveRegistryError;
 pub(crate) mod code {
     pub const BAD_USER_INPUT: &str = "BAD_USER_INPUT";
     pub const INTERNAL_SERVER_ERROR: &str = "INTERNAL_SERVER_ERROR";
+    pub const PERSISTED_QUERY_NOT_FOUND: &str = "PERSISTED_QUERY_NOT_FOUND";
+    pub const PERSISTED_QUERY_NOT_ALLOWED: &str = "PERSISTED_QUERY_NOT_ALLOWED";
+    pub const QUOTA_EXCEEDED: &str = "QUOTA_EXCEEDED";
     pub const REQUEST_TIMEOUT: &str = "REQUEST_TIMEOUT";
+    pub const UNAUTHENTICATED: &str = "UNAUTHENTICATED";
     pub const UNKNOWN: &str = "UNKNOWN";
 }
 
@@ -0,0 +1,565 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest, NextRequest},
+    from_value, Request, Response, ServerError, ServerResult,
+};
+use diesel::{
+    backend::Backend,
+    deserialize::{self, FromSql, QueryableByName},
+    row::NamedRow,
+    sql_types::{Nullable, Text},
+};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use fastcrypto::hash::{HashFunction, Sha256};
+use lru::LruCache;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::config::{Limits, PersistedQueriesConfig, PersistedQueriesMode, ServiceConfig};
+use crate::data::{Db, DbConnection, QueryExecutor};
+use crate::error::{code, graphql_error, Error};
+use crate::metrics::Metrics;
+use crate::query;
+
+/// Extension factory for serving persisted queries, backed by a shared store of registered
+/// operations.
+pub(crate) struct PersistedQueries(Arc<PersistedQueryStore>);
+
+struct PersistedQueriesExt {
+    store: Arc<PersistedQueryStore>,
+    /// The name of the registered operation this request is for, if any.
+    operation: Mutex<Option<String>>,
+}
+
+/// Operations registered with the service, and the queries registered by clients.
+pub(crate) struct PersistedQueryStore {
+    mode: PersistedQueriesMode,
+    /// Operations registered ahead of time, keyed by the SHA-256 hash of their query.
+    registered: HashMap<String, PersistedOperation>,
+    /// Queries registered by clients (in automatic mode), keyed by the SHA-256 hash of the query.
+    automatic: Mutex<LruCache<String, String>>,
+}
+
+/// An operation that has been registered ahead of time. Requests for registered operations have
+/// it added to their data, so that the limits they are subject to can be overridden.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PersistedOperation {
+    pub name: String,
+    pub query: String,
+    pub limits: Limits,
+}
+
+/// Limits that can be overridden per operation. Fields that are not set inherit the service's
+/// limits.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct LimitOverrides {
+    max_query_depth: Option<u32>,
+    max_query_nodes: Option<u32>,
+    max_output_nodes: Option<u32>,
+    max_query_payload_size: Option<u32>,
+    request_timeout_ms: Option<u32>,
+}
+
+/// An operation to register, as it appears in the persisted queries file.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct OperationEntry {
+    name: String,
+    query: String,
+    #[serde(default)]
+    limits: LimitOverrides,
+}
+
+#[derive(Deserialize)]
+struct OperationsFile {
+    #[serde(default)]
+    operations: Vec<OperationEntry>,
+}
+
+/// A row from the persisted queries table, where `limits` is a JSON representation of
+/// `LimitOverrides`.
+struct StoredOperation {
+    name: String,
+    query: String,
+    limits: Option<String>,
+}
+
+/// The contents of the `persistedQuery` request extension.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQueryExtension {
+    version: u32,
+    sha256_hash: String,
+}
+
+/// The outcome of checking a request against the store.
+#[derive(Debug, PartialEq, Eq)]
+enum Resolution {
+    /// The request is for a registered operation.
+    Registered(PersistedOperation),
+    /// The request is for a query that was registered by a client, or contains an arbitrary query
+    /// (only possible in automatic mode).
+    Query(String),
+}
+
+/// Reasons for rejecting a request, used to label metrics.
+#[derive(Debug, PartialEq, Eq)]
+enum Rejection {
+    NotFound,
+    NotAllowed,
+    HashMismatch,
+}
+
+impl PersistedQueries {
+    pub(crate) fn new(store: PersistedQueryStore) -> Self {
+        Self(Arc::new(store))
+    }
+}
+
+impl ExtensionFactory for PersistedQueries {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PersistedQueriesExt {
+            store: self.0.clone(),
+            operation: Mutex::new(None),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for PersistedQueriesExt {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let instant = Instant::now();
+        let resp = next.run(ctx).await;
+
+        if let Some(name) = self.operation.lock().unwrap().take() {
+            let metrics: &Metrics = ctx.data_unchecked();
+            metrics.observe_persisted_operation(&name, instant.elapsed(), resp.errors.is_empty());
+        }
+
+        resp
+    }
+
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        mut request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let hash = match request.extensions.remove("persistedQuery") {
+            None => None,
+            Some(value) => {
+                let ext: PersistedQueryExtension = from_value(value).map_err(|_| {
+                    graphql_error(
+                        code::BAD_USER_INPUT,
+                        "Invalid \"persistedQuery\" extension in request",
+                    )
+                })?;
+
+                if ext.version != 1 {
+                    return Err(graphql_error(
+                        code::BAD_USER_INPUT,
+                        format!(
+                            "Unsupported \"persistedQuery\" extension version: {}",
+                            ext.version
+                        ),
+                    ));
+                }
+
+                Some(ext.sha256_hash)
+            }
+        };
+
+        match self.store.resolve(&request.query, hash) {
+            Ok(Resolution::Registered(operation)) => {
+                *self.operation.lock().unwrap() = Some(operation.name.clone());
+                request.query = operation.query.clone();
+                request.data.insert(operation);
+            }
+
+            Ok(Resolution::Query(query)) => {
+                request.query = query;
+            }
+
+            Err(rejection) => {
+                let metrics: &Metrics = ctx.data_unchecked();
+                metrics.inc_persisted_query_rejections(rejection.label());
+                return Err(rejection.error());
+            }
+        }
+
+        next.run(ctx, request).await
+    }
+}
+
+impl PersistedQueryStore {
+    /// Create a store that registers `operations`, with limits overriding `limits`.
+    fn new(
+        config: &PersistedQueriesConfig,
+        limits: &Limits,
+        operations: impl IntoIterator<Item = OperationEntry>,
+    ) -> Self {
+        let registered = operations
+            .into_iter()
+            .map(|entry| {
+                let operation = PersistedOperation {
+                    limits: entry.limits.apply(limits),
+                    name: entry.name,
+                    query: entry.query,
+                };
+
+                (query_hash(&operation.query), operation)
+            })
+            .collect();
+
+        let capacity = NonZeroUsize::new(config.max_automatic_queries).unwrap_or(NonZeroUsize::MIN);
+
+        Self {
+            mode: config.mode,
+            registered,
+            automatic: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Load registered operations from the file and database table named in `config`.
+    pub(crate) async fn load(
+        config: &PersistedQueriesConfig,
+        limits: &Limits,
+        db: &Db,
+    ) -> Result<Self, Error> {
+        let mut operations = vec![];
+
+        if let Some(path) = &config.path {
+            operations.extend(read_operations_file(path)?);
+        }
+
+        if let Some(table) = &config.table {
+            operations.extend(read_operations_table(db, table).await?);
+        }
+
+        let store = Self::new(config, limits, operations);
+        info!(
+            "Registered {} persisted operations ({:?} mode)",
+            store.registered.len(),
+            store.mode,
+        );
+
+        Ok(store)
+    }
+
+    /// Check a request's `query` (which may be empty) and the `hash` from its `persistedQuery`
+    /// extension (if any), and decide which query the request should run.
+    fn resolve(&self, query: &str, hash: Option<String>) -> Result<Resolution, Rejection> {
+        let strict = self.mode == PersistedQueriesMode::Strict;
+
+        if query.is_empty() {
+            // An empty query without a hash is left for the parser to reject, unless only
+            // registered operations are allowed.
+            let Some(hash) = hash else {
+                return if strict {
+                    Err(Rejection::NotAllowed)
+                } else {
+                    Ok(Resolution::Query(String::new()))
+                };
+            };
+
+            if let Some(operation) = self.registered.get(&hash) {
+                return Ok(Resolution::Registered(operation.clone()));
+            }
+
+            if strict {
+                return Err(Rejection::NotAllowed);
+            }
+
+            return match self.automatic.lock().unwrap().get(&hash) {
+                Some(query) => Ok(Resolution::Query(query.clone())),
+                None => Err(Rejection::NotFound),
+            };
+        }
+
+        let actual = query_hash(query);
+        if hash.as_ref().is_some_and(|hash| *hash != actual) {
+            return Err(Rejection::HashMismatch);
+        }
+
+        if let Some(operation) = self.registered.get(&actual) {
+            return Ok(Resolution::Registered(operation.clone()));
+        }
+
+        if strict {
+            return Err(Rejection::NotAllowed);
+        }
+
+        // Clients register queries by sending them along with their hash.
+        if hash.is_some() {
+            self.automatic
+                .lock()
+                .unwrap()
+                .put(actual, query.to_string());
+        }
+
+        Ok(Resolution::Query(query.to_string()))
+    }
+}
+
+impl LimitOverrides {
+    /// The limits in `limits`, with any overrides applied.
+    fn apply(&self, limits: &Limits) -> Limits {
+        Limits {
+            max_query_depth: self.max_query_depth.unwrap_or(limits.max_query_depth),
+            max_query_nodes: self.max_query_nodes.unwrap_or(limits.max_query_nodes),
+            max_output_nodes: self.max_output_nodes.unwrap_or(limits.max_output_nodes),
+            max_query_payload_size: self
+                .max_query_payload_size
+                .unwrap_or(limits.max_query_payload_size),
+            request_timeout_ms: self.request_timeout_ms.unwrap_or(limits.request_timeout_ms),
+            ..limits.clone()
+        }
+    }
+}
+
+impl Rejection {
+    fn label(&self) -> &'static str {
+        match self {
+            Rejection::NotFound => "not_found",
+            Rejection::NotAllowed => "not_allowed",
+            Rejection::HashMismatch => "hash_mismatch",
+        }
+    }
+
+    fn error(&self) -> ServerError {
+        match self {
+            // Clients that support automatic persisted queries recognise this message, and retry
+            // with the full query.
+            Rejection::NotFound => {
+                graphql_error(code::PERSISTED_QUERY_NOT_FOUND, "PersistedQueryNotFound")
+            }
+            Rejection::NotAllowed => graphql_error(
+                code::PERSISTED_QUERY_NOT_ALLOWED,
+                "Only registered operations can be run on this service",
+            ),
+            Rejection::HashMismatch => graphql_error(
+                code::BAD_USER_INPUT,
+                "Provided sha256Hash does not match query",
+            ),
+        }
+    }
+}
+
+/// The limits that apply to the request in `ctx`: the limits of the persisted operation it is for,
+/// if any, otherwise the limits of the service.
+pub(crate) fn request_limits<'a>(ctx: &ExtensionContext<'a>) -> &'a Limits {
+    match ctx.data_opt::<PersistedOperation>() {
+        Some(operation) => &operation.limits,
+        None => {
+            let cfg: &ServiceConfig = ctx.data_unchecked();
+            &cfg.limits
+        }
+    }
+}
+
+/// Hex-encoded SHA-256 hash of a query, which is how persisted queries are referred to.
+fn query_hash(query: &str) -> String {
+    hex::encode(Sha256::digest(query.as_bytes()).digest)
+}
+
+fn read_operations_file(path: &Path) -> Result<Vec<OperationEntry>, Error> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to read persisted queries from {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    let file: OperationsFile = toml::de::from_str(&contents).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to parse persisted queries from {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    Ok(file.operations)
+}
+
+async fn read_operations_table(db: &Db, table: &str) -> Result<Vec<OperationEntry>, Error> {
+    let select = format!("SELECT name, query, limits FROM {table}");
+    let stored: Vec<StoredOperation> = db
+        .execute(move |conn| {
+            async move {
+                conn.results(move || query!(select.clone()).into_boxed())
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to read persisted queries table: {e}")))?;
+
+    stored
+        .into_iter()
+        .map(
+            |StoredOperation {
+                 name,
+                 query,
+                 limits,
+             }| {
+                let limits = match limits {
+                    Some(limits) => serde_json::from_str(&limits).map_err(|e| {
+                        Error::Internal(format!("Invalid limits for persisted query {name}: {e}"))
+                    })?,
+                    None => LimitOverrides::default(),
+                };
+
+                Ok(OperationEntry {
+                    name,
+                    query,
+                    limits,
+                })
+            },
+        )
+        .collect()
+}
+
+/// `sql_query` raw queries require `QueryableByName`. Implementing it directly avoids the default
+/// implementation's requirement that the struct mirrors a table known to diesel, as the table's
+/// name is only known at runtime.
+impl<DB> QueryableByName<DB> for StoredOperation
+where
+    DB: Backend,
+    String: FromSql<Text, DB>,
+    Option<String>: FromSql<Nullable<Text>, DB>,
+{
+    fn build<'a>(row: &impl NamedRow<'a, DB>) -> deserialize::Result<Self> {
+        let name = NamedRow::get::<Text, _>(row, "name")?;
+        let query = NamedRow::get::<Text, _>(row, "query")?;
+        let limits = NamedRow::get::<Nullable<Text>, _>(row, "limits")?;
+
+        Ok(Self {
+            name,
+            query,
+            limits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUERY: &str = "query { chainIdentifier }";
+
+    fn store(mode: PersistedQueriesMode) -> PersistedQueryStore {
+        let config = PersistedQueriesConfig {
+            mode,
+            ..Default::default()
+        };
+
+        let limits = Limits::default();
+        let file: OperationsFile = toml::de::from_str(&format!(
+            r#"
+                [[operations]]
+                name = "chainIdentifier"
+                query = "{QUERY}"
+
+                [operations.limits]
+                max-query-nodes = 1000
+                request-timeout-ms = 60000
+            "#
+        ))
+        .unwrap();
+
+        PersistedQueryStore::new(&config, &limits, file.operations)
+    }
+
+    fn registered() -> PersistedOperation {
+        PersistedOperation {
+            name: "chainIdentifier".to_string(),
+            query: QUERY.to_string(),
+            limits: Limits {
+                max_query_nodes: 1000,
+                request_timeout_ms: 60_000,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_registered_operation() {
+        let store = store(PersistedQueriesMode::Strict);
+        let hash = query_hash(QUERY);
+
+        // By hash alone, by query alone, and by both.
+        assert_eq!(
+            store.resolve("", Some(hash.clone())),
+            Ok(Resolution::Registered(registered())),
+        );
+        assert_eq!(
+            store.resolve(QUERY, None),
+            Ok(Resolution::Registered(registered())),
+        );
+        assert_eq!(
+            store.resolve(QUERY, Some(hash)),
+            Ok(Resolution::Registered(registered())),
+        );
+    }
+
+    #[test]
+    fn test_strict_mode() {
+        let store = store(PersistedQueriesMode::Strict);
+        let other = "query { checkpoint { digest } }";
+
+        assert_eq!(store.resolve(other, None), Err(Rejection::NotAllowed));
+        assert_eq!(
+            store.resolve(other, Some(query_hash(other))),
+            Err(Rejection::NotAllowed),
+        );
+
+        // Nothing was registered by the previous request.
+        assert_eq!(
+            store.resolve("", Some(query_hash(other))),
+            Err(Rejection::NotAllowed),
+        );
+    }
+
+    #[test]
+    fn test_automatic_mode() {
+        let store = store(PersistedQueriesMode::Automatic);
+        let other = "query { checkpoint { digest } }";
+        let hash = query_hash(other);
+
+        // Arbitrary queries are still allowed.
+        assert_eq!(
+            store.resolve(other, None),
+            Ok(Resolution::Query(other.to_string())),
+        );
+
+        assert_eq!(
+            store.resolve("", Some(hash.clone())),
+            Err(Rejection::NotFound)
+        );
+        assert_eq!(
+            store.resolve(other, Some(hash.clone())),
+            Ok(Resolution::Query(other.to_string())),
+        );
+        assert_eq!(
+            store.resolve("", Some(hash)),
+            Ok(Resolution::Query(other.to_string())),
+        );
+    }
+
+    #[test]
+    fn test_hash_mismatch() {
+        let store = store(PersistedQueriesMode::Automatic);
+        assert_eq!(
+            store.resolve(QUERY, Some(query_hash("query { epoch { epochId } }"))),
+            Err(Rejection::HashMismatch),
+        );
+    }
+}
@@ -1,9 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::config::{Limits, ServiceConfig};
+use crate::config::Limits;
 use crate::error::{code, graphql_error, graphql_error_at_pos};
+use crate::extensions::persisted_queries::request_limits;
 use crate::metrics::Metrics;
+use crate::server::api_keys::ApiKey;
 use async_graphql::extensions::NextParseQuery;
 use async_graphql::extensions::NextRequest;
 use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory};
@@ -31,6 +33,8 @@ const DRY_RUN_TX_BLOCK: &str = "dryRunTransactionBlock";
 const EXECUTE_TX_BLOCK: &str = "executeTransactionBlock";
 
 /// The size of the query payload in bytes, as it comes from the request header: `Content-Length`.
+/// Requests that arrive without one (subscriptions, which are sent as WebSocket messages) are
+/// measured by the size of their query and variables.
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct PayloadSize(pub u64);
 
@@ -87,6 +91,15 @@ struct Usage {
     query_payload: u32,
 }
 
+impl PayloadSize {
+    /// The size of a request made up of `query` and `variables`, as they would be serialized in
+    /// the JSON body of a request.
+    fn of_request(query: &str, variables: &Variables) -> Self {
+        let variables = serde_json::to_vec(variables).map_or(0, |v| v.len());
+        PayloadSize((query.len() + variables) as u64)
+    }
+}
+
 impl ShowUsage {
     pub(crate) fn name() -> &'static HeaderName {
         &LIMITS_HEADER
@@ -574,9 +587,8 @@ impl<'a> LimitsTraversal<'a> {
 
 impl<'a> Reporter<'a> {
     fn new(ctx: &'a ExtensionContext<'a>) -> Self {
-        let cfg: &ServiceConfig = ctx.data_unchecked();
         Self {
-            limits: &cfg.limits,
+            limits: request_limits(ctx),
             query_id: ctx.data_unchecked(),
             session_id: ctx.data_unchecked(),
         }
@@ -700,7 +712,10 @@ impl Extension for QueryLimitsCheckerExt {
         next: NextParseQuery<'_>,
     ) -> ServerResult<ExecutableDocument> {
         let metrics: &Metrics = ctx.data_unchecked();
-        let payload_size: &PayloadSize = ctx.data_unchecked();
+        let payload_size = ctx
+            .data_opt::<PayloadSize>()
+            .copied()
+            .unwrap_or_else(|| PayloadSize::of_request(query, variables));
         let reporter = Reporter::new(ctx);
 
         let instant = Instant::now();
@@ -731,13 +746,19 @@ impl Extension for QueryLimitsCheckerExt {
         }
 
         let mut traversal =
-            LimitsTraversal::new(*payload_size, &reporter, &doc.fragments, variables);
+            LimitsTraversal::new(payload_size, &reporter, &doc.fragments, variables);
 
         let res = traversal.check_document(&doc);
         let usage = traversal.finish(query.len() as u32);
         metrics.query_validation_latency(instant.elapsed());
         usage.report(metrics);
 
+        // Requests authenticated with an API key are charged for their estimated output, against
+        // that key's query cost quota.
+        if let (Ok(()), Some(key)) = (&res, ctx.data_opt::<ApiKey>()) {
+            key.charge_query_cost(usage.output_nodes)?;
+        }
+
         res.map(|()| {
             if ctx.data_opt::<ShowUsage>().is_some() {
                 *self.usage.lock().unwrap() = Some(usage);
@@ -0,0 +1,546 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use async_graphql::{
+    connection::CursorType,
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextPrepareRequest,
+        NextResolve, ResolveInfo,
+    },
+    parser::types::Field,
+    PathSegment, Request, Response, ServerResult, Value, Variables,
+};
+use fastcrypto::hash::{HashFunction, Sha256};
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::{ResponseCacheConfig, ServiceConfig};
+use crate::metrics::Metrics;
+use crate::server::watermark_task::Watermark;
+use crate::types::cursor::JsonCursor;
+
+/// A cache shared between instances of the service (for example, backed by a key-value store).
+/// It is consulted when a result is not found in the service's in-process cache, and is written
+/// to alongside it.
+///
+/// Keys are hex-encoded hashes, and values are opaque byte strings, so implementations do not need
+/// to interpret either. Implementations are free to evict entries at any time.
+#[async_trait::async_trait]
+pub trait ExternalCache: Send + Sync {
+    async fn get(&self, key: &str) -> Option<Vec<u8>>;
+
+    async fn set(&self, key: &str, value: Vec<u8>);
+}
+
+/// Extension factory for caching the results of top-level query fields, backed by a shared store.
+pub(crate) struct ResponseCache(Arc<ResponseCacheStore>);
+
+struct ResponseCacheExt {
+    store: Arc<ResponseCacheStore>,
+    /// Identifies the query, variables and operation of this request, once it has been prepared.
+    request: Mutex<Option<RequestKey>>,
+    /// Results computed while executing this request. They are only added to the cache once the
+    /// request has finished, and only if they did not produce any errors.
+    pending: Mutex<Vec<PendingEntry>>,
+}
+
+/// Results for top-level fields, shared between requests.
+pub(crate) struct ResponseCacheStore {
+    local: Mutex<LruCache<String, Entry>>,
+    external: Option<Arc<dyn ExternalCache>>,
+    /// The highest watermark (`hi_cp`, `lo_cp`) that the in-process cache has been swept at.
+    swept_at: Mutex<(u64, u64)>,
+}
+
+struct RequestKey {
+    /// Hash of the request's query, variables and operation name.
+    hash: String,
+    variables: Variables,
+}
+
+struct PendingEntry {
+    /// The name (or alias) that the field's result appears under in the response.
+    response_key: String,
+    key: String,
+    entry: Entry,
+}
+
+/// How long the result of a top-level field can be cached for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Policy {
+    /// The field's result never changes.
+    Immutable,
+    /// The field's result is fixed once the checkpoint it is viewed at is fixed: by a cursor in its
+    /// arguments, or otherwise by the latest checkpoint the service has indexed.
+    Checkpoint,
+}
+
+/// The range of watermarks that a cached result is valid for.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+enum Scope {
+    Immutable,
+    /// Computed as of the latest checkpoint, `hi_cp`. Requests will only look it up while `hi_cp`
+    /// is still the latest checkpoint.
+    Latest(u64),
+    /// Computed as of a checkpoint that was fixed by a cursor. It remains valid for as long as the
+    /// checkpoint is in the service's available range.
+    Pinned(u64),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct Entry {
+    scope: Scope,
+    value: Value,
+}
+
+/// Mapping from top-level query field to how its result can be cached. Fields that are not in this
+/// list are never cached, typically because their results depend on something other than the
+/// checkpoint being viewed (e.g. dry runs, or the available range itself).
+fn cache_policies() -> &'static BTreeMap<&'static str, Policy> {
+    use Policy as P;
+    static POLICIES: Lazy<BTreeMap<&str, Policy>> = Lazy::new(|| {
+        BTreeMap::from_iter([
+            ("address", P::Checkpoint),
+            ("chainIdentifier", P::Immutable),
+            ("checkpoint", P::Checkpoint),
+            ("checkpoints", P::Checkpoint),
+            ("coinMetadata", P::Checkpoint),
+            ("coins", P::Checkpoint),
+            ("epoch", P::Checkpoint),
+            ("epochs", P::Checkpoint),
+            ("events", P::Checkpoint),
+            ("latestPackage", P::Checkpoint),
+            ("object", P::Checkpoint),
+            ("objects", P::Checkpoint),
+            ("owner", P::Checkpoint),
+            ("package", P::Checkpoint),
+            ("packageVersions", P::Checkpoint),
+            ("packages", P::Checkpoint),
+            ("protocolConfig", P::Checkpoint),
+            ("resolveSuinsAddress", P::Checkpoint),
+            ("serviceConfig", P::Immutable),
+            ("transactionBlock", P::Checkpoint),
+            ("transactionBlocks", P::Checkpoint),
+            ("type", P::Checkpoint),
+        ])
+    });
+
+    Lazy::force(&POLICIES)
+}
+
+impl ResponseCache {
+    pub(crate) fn new(
+        config: &ResponseCacheConfig,
+        external: Option<Arc<dyn ExternalCache>>,
+    ) -> Self {
+        Self(Arc::new(ResponseCacheStore::new(config, external)))
+    }
+}
+
+impl ExtensionFactory for ResponseCache {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ResponseCacheExt {
+            store: self.0.clone(),
+            request: Mutex::new(None),
+            pending: Mutex::new(vec![]),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for ResponseCacheExt {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let request = next.run(ctx, request).await?;
+
+        // Variables are included in the hash verbatim, so the same variables supplied in a
+        // different order will not share cache entries, but will still be correct.
+        let variables = serde_json::to_string(&request.variables).unwrap_or_default();
+        let key = format!(
+            "{}\0{variables}\0{}",
+            request.query,
+            request.operation_name.as_deref().unwrap_or_default(),
+        );
+
+        *self.request.lock().unwrap() = Some(RequestKey {
+            hash: hex::encode(Sha256::digest(key.as_bytes()).digest),
+            variables: request.variables.clone(),
+        });
+
+        Ok(request)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let resp = next.run(ctx, operation_name).await;
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+
+        for PendingEntry {
+            response_key,
+            key,
+            entry,
+        } in pending
+        {
+            // Errors without a path (e.g. timeouts) could have affected any field.
+            let failed = resp.errors.iter().any(|e| match e.path.first() {
+                None => true,
+                Some(PathSegment::Field(field)) => *field == response_key,
+                Some(PathSegment::Index(_)) => false,
+            });
+
+            if !failed {
+                self.store.set(key, entry).await;
+            }
+        }
+
+        resp
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if info.parent_type != "Query" || info.path_node.parent.is_some() {
+            return next.run(ctx, info).await;
+        }
+
+        let Some(policy) = cache_policies().get(info.name).copied() else {
+            return next.run(ctx, info).await;
+        };
+
+        let (Some(watermark), Some(metrics)) =
+            (ctx.data_opt::<Watermark>(), ctx.data_opt::<Metrics>())
+        else {
+            return next.run(ctx, info).await;
+        };
+
+        // The hash identifying the request, and the checkpoint its cursor is pinned to, if any.
+        let request = self
+            .request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|r| (r.hash.clone(), pinned_checkpoint(info.field, &r.variables)));
+
+        let Some((hash, pinned)) = request else {
+            return next.run(ctx, info).await;
+        };
+
+        let scope = match (policy, pinned) {
+            (Policy::Immutable, _) => Scope::Immutable,
+            (Policy::Checkpoint, Some(cp)) => Scope::Pinned(cp),
+            (Policy::Checkpoint, None) => Scope::Latest(watermark.hi_cp),
+        };
+
+        self.store.sweep(watermark, metrics);
+
+        // Results depend on the limits they were computed under (e.g. the maximum page size),
+        // which can be overridden per API key, so requests only share results if they are subject
+        // to the same limits.
+        let limits = ctx
+            .data_opt::<ServiceConfig>()
+            .and_then(|config| serde_json::to_string(&config.limits).ok())
+            .unwrap_or_default();
+
+        let response_key = info.alias.unwrap_or(info.name).to_string();
+        let key = cache_key(&hash, &limits, &response_key, scope);
+
+        if let Some(entry) = self.store.get_local(&key, watermark) {
+            metrics.inc_response_cache_lookups(info.name, "hit");
+            return Ok(Some(entry.value));
+        }
+
+        if let Some(entry) = self.store.get_external(&key, watermark).await {
+            metrics.inc_response_cache_lookups(info.name, "external_hit");
+            return Ok(Some(entry.value));
+        }
+
+        metrics.inc_response_cache_lookups(info.name, "miss");
+        let value = next.run(ctx, info).await?;
+
+        if let Some(value) = &value {
+            self.pending.lock().unwrap().push(PendingEntry {
+                response_key,
+                key,
+                entry: Entry {
+                    scope,
+                    value: value.clone(),
+                },
+            });
+        }
+
+        Ok(value)
+    }
+}
+
+impl ResponseCacheStore {
+    fn new(config: &ResponseCacheConfig, external: Option<Arc<dyn ExternalCache>>) -> Self {
+        let capacity = NonZeroUsize::new(config.capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            local: Mutex::new(LruCache::new(capacity)),
+            external,
+            swept_at: Mutex::new((0, 0)),
+        }
+    }
+
+    /// Look up `key` in the in-process cache, as long as its entry is valid at `watermark`.
+    fn get_local(&self, key: &str, watermark: &Watermark) -> Option<Entry> {
+        let mut local = self.local.lock().unwrap();
+        let entry = local.get(key)?;
+        if entry.scope.is_valid(watermark) {
+            Some(entry.clone())
+        } else {
+            local.pop(key);
+            None
+        }
+    }
+
+    /// Look up `key` in the external cache (if there is one), as long as its entry is valid at
+    /// `watermark`. Entries found here are also added to the in-process cache.
+    async fn get_external(&self, key: &str, watermark: &Watermark) -> Option<Entry> {
+        let bytes = self.external.as_ref()?.get(key).await?;
+        let entry: Entry = match serde_json::from_slice(&bytes) {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!("Failed to deserialize response cache entry {key}: {e}");
+                return None;
+            }
+        };
+
+        if !entry.scope.is_valid(watermark) {
+            return None;
+        }
+
+        self.local
+            .lock()
+            .unwrap()
+            .put(key.to_string(), entry.clone());
+        Some(entry)
+    }
+
+    async fn set(&self, key: String, entry: Entry) {
+        if let Some(external) = &self.external {
+            match serde_json::to_vec(&entry) {
+                Ok(bytes) => external.set(&key, bytes).await,
+                Err(e) => warn!("Failed to serialize response cache entry {key}: {e}"),
+            }
+        }
+
+        self.local.lock().unwrap().put(key, entry);
+    }
+
+    /// Drop entries from the in-process cache that are no longer valid, the first time the cache is
+    /// accessed at a new watermark. Entries in the external cache are validated when they are read.
+    fn sweep(&self, watermark: &Watermark, metrics: &Metrics) {
+        {
+            let mut swept_at = self.swept_at.lock().unwrap();
+            let (hi_cp, lo_cp) = *swept_at;
+            if watermark.hi_cp <= hi_cp && watermark.lo_cp <= lo_cp {
+                return;
+            }
+
+            *swept_at = (hi_cp.max(watermark.hi_cp), lo_cp.max(watermark.lo_cp));
+        }
+
+        let mut local = self.local.lock().unwrap();
+        let stale: Vec<_> = local
+            .iter()
+            .filter(|(_, entry)| !entry.scope.is_valid(watermark))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in &stale {
+            local.pop(key);
+        }
+
+        if !stale.is_empty() {
+            metrics.inc_response_cache_invalidations(stale.len() as u64);
+        }
+    }
+}
+
+impl Scope {
+    fn is_valid(&self, watermark: &Watermark) -> bool {
+        match self {
+            Scope::Immutable => true,
+            Scope::Latest(cp) => *cp >= watermark.hi_cp,
+            Scope::Pinned(cp) => *cp >= watermark.lo_cp,
+        }
+    }
+}
+
+/// The key for the result of the top-level field at `response_key` in the request identified by
+/// `hash`, computed under `limits` (serialized), valid for `scope`.
+fn cache_key(hash: &str, limits: &str, response_key: &str, scope: Scope) -> String {
+    let scope = match scope {
+        Scope::Immutable => "i".to_string(),
+        Scope::Latest(cp) | Scope::Pinned(cp) => format!("c{cp}"),
+    };
+
+    let key = format!("{hash}:{limits}:{response_key}:{scope}");
+    hex::encode(Sha256::digest(key.as_bytes()).digest)
+}
+
+/// The checkpoint that `field` is pinned to by the cursor in its `after` or `before` argument, if
+/// it has one. Only JSON cursors are inspected, so fields paginated by other kinds of cursors are
+/// cached as of the latest checkpoint.
+fn pinned_checkpoint(field: &Field, variables: &Variables) -> Option<u64> {
+    #[derive(Serialize, Deserialize)]
+    struct Pinned {
+        c: u64,
+    }
+
+    ["after", "before"].into_iter().find_map(|name| {
+        let value = field.get_argument(name)?.node.clone();
+        let value = value
+            .into_const_with(|var| variables.get(&var).cloned().ok_or(()))
+            .ok()?;
+
+        let Value::String(cursor) = value else {
+            return None;
+        };
+
+        let cursor = JsonCursor::<Pinned>::decode_cursor(&cursor).ok()?;
+        Some(cursor.c)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{parser::parse_query, Name};
+
+    use super::*;
+
+    fn watermark(hi_cp: u64, lo_cp: u64) -> Watermark {
+        Watermark {
+            hi_cp,
+            hi_cp_timestamp_ms: 0,
+            epoch: 0,
+            lo_cp,
+            lo_tx: 0,
+        }
+    }
+
+    fn entry(scope: Scope) -> Entry {
+        Entry {
+            scope,
+            value: Value::String("value".to_string()),
+        }
+    }
+
+    fn root_field(query: &str) -> Field {
+        let doc = parse_query(query).unwrap();
+        let (_, operation) = doc.operations.iter().next().unwrap();
+        let selection = &operation.node.selection_set.node.items[0].node;
+        let async_graphql::parser::types::Selection::Field(field) = selection else {
+            panic!("Expected a field");
+        };
+
+        field.node.clone()
+    }
+
+    #[test]
+    fn test_scope_validity() {
+        let wm = watermark(100, 50);
+
+        assert!(Scope::Immutable.is_valid(&wm));
+        assert!(Scope::Latest(100).is_valid(&wm));
+        assert!(!Scope::Latest(99).is_valid(&wm));
+
+        // Pinned results stay valid as the latest checkpoint moves on, until they are pruned.
+        assert!(Scope::Pinned(50).is_valid(&wm));
+        assert!(Scope::Pinned(75).is_valid(&wm));
+        assert!(!Scope::Pinned(49).is_valid(&wm));
+    }
+
+    #[test]
+    fn test_sweep_on_new_watermark() {
+        let store = ResponseCacheStore::new(&ResponseCacheConfig::default(), None);
+        let metrics = Metrics::new(&prometheus::Registry::new());
+
+        let mut local = store.local.lock().unwrap();
+        local.put("immutable".to_string(), entry(Scope::Immutable));
+        local.put("latest".to_string(), entry(Scope::Latest(100)));
+        local.put("pinned".to_string(), entry(Scope::Pinned(60)));
+        drop(local);
+
+        store.sweep(&watermark(100, 50), &metrics);
+        assert_eq!(store.local.lock().unwrap().len(), 3);
+
+        store.sweep(&watermark(101, 50), &metrics);
+        assert!(store.get_local("latest", &watermark(101, 50)).is_none());
+        assert!(store.get_local("pinned", &watermark(101, 50)).is_some());
+
+        store.sweep(&watermark(101, 61), &metrics);
+        assert!(store.get_local("pinned", &watermark(101, 61)).is_none());
+        assert!(store.get_local("immutable", &watermark(101, 61)).is_some());
+
+        assert_eq!(
+            metrics.request_metrics.response_cache_invalidations.get(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let latest = cache_key("hash", "limits", "checkpoint", Scope::Latest(10));
+        assert_eq!(
+            latest,
+            cache_key("hash", "limits", "checkpoint", Scope::Pinned(10))
+        );
+        assert_ne!(
+            latest,
+            cache_key("hash", "limits", "checkpoint", Scope::Latest(11))
+        );
+        assert_ne!(latest, cache_key("hash", "limits", "cp", Scope::Latest(10)));
+        assert_ne!(
+            latest,
+            cache_key("other", "limits", "checkpoint", Scope::Latest(10))
+        );
+        assert_ne!(
+            latest,
+            cache_key("hash", "other", "checkpoint", Scope::Latest(10))
+        );
+    }
+
+    #[test]
+    fn test_pinned_checkpoint() {
+        #[derive(Serialize, Deserialize)]
+        struct Cursor {
+            c: u64,
+            s: u64,
+        }
+
+        let cursor = JsonCursor::new(Cursor { c: 42, s: 7 }).encode_cursor();
+
+        let field = root_field(&format!(
+            "{{ checkpoints(after: \"{cursor}\") {{ pageInfo {{ hasNextPage }} }} }}"
+        ));
+        assert_eq!(pinned_checkpoint(&field, &Variables::default()), Some(42));
+
+        let field =
+            root_field("query ($c: String) { events(before: $c) { pageInfo { hasNextPage } } }");
+        let mut variables = Variables::default();
+        variables.insert(Name::new("c"), Value::String(cursor));
+        assert_eq!(pinned_checkpoint(&field, &variables), Some(42));
+
+        let field = root_field("{ checkpoints { pageInfo { hasNextPage } } }");
+        assert_eq!(pinned_checkpoint(&field, &Variables::default()), None);
+    }
+}
@@ -17,7 +17,7 @@ use tokio::time::timeout;
 use tracing::error;
 use uuid::Uuid;
 
-use crate::{config::ServiceConfig, error::code};
+use crate::{error::code, extensions::persisted_queries::request_limits};
 
 /// Extension factory for creating new `Timeout` instances, per query.
 pub(crate) struct Timeout;
@@ -64,16 +64,15 @@ impl Extension for TimeoutExt {
         operation_name: Option<&str>,
         next: NextExecute<'_>,
     ) -> Response {
-        let cfg: &ServiceConfig = ctx
-            .data()
-            .expect("No service config provided in schema data");
+        // Persisted operations may override the service's limits.
+        let limits = request_limits(ctx);
 
         // increase the timeout if the request is a mutation
         let is_mutation = self.is_mutation.load(Ordering::Relaxed);
         let request_timeout = if is_mutation {
-            Duration::from_millis(cfg.limits.mutation_timeout_ms.into())
+            Duration::from_millis(limits.mutation_timeout_ms.into())
         } else {
-            Duration::from_millis(cfg.limits.request_timeout_ms.into())
+            Duration::from_millis(limits.request_timeout_ms.into())
         };
 
         timeout(request_timeout, next.run(ctx, operation_name))
@@ -79,6 +79,27 @@ pub(crate) struct RequestMetrics {
     pub num_queries_top_level: IntCounterVec,
     /// Total inflight requests
     pub inflight_requests: Gauge,
+    /// Number of requests for each persisted operation
+    pub persisted_operations: IntCounterVec,
+    /// The time it takes to serve each persisted operation
+    pub persisted_operation_latency: HistogramVec,
+    /// Number of requests for each persisted operation that resulted in errors
+    pub persisted_operation_errors: IntCounterVec,
+    /// Number of requests rejected by persisted query checks, by reason
+    pub persisted_query_rejections: IntCounterVec,
+    /// Number of lookups in the response cache for each top-level field, by result (`hit`,
+    /// `external_hit` or `miss`)
+    pub response_cache_lookups: IntCounterVec,
+    /// Number of cached results dropped because the checkpoint they were computed at is no longer
+    /// being served
+    pub response_cache_invalidations: IntCounter,
+    /// Number of requests made with each API key
+    pub api_key_requests: IntCounterVec,
+    /// Total estimated cost (output nodes) of queries made with each API key
+    pub api_key_query_cost: IntCounterVec,
+    /// Number of requests made with each API key that were rejected for exceeding a quota, by
+    /// quota
+    pub api_key_rejections: IntCounterVec,
 }
 
 impl Metrics {
@@ -121,6 +142,72 @@ impl Metrics {
         self.request_metrics.num_queries.inc();
     }
 
+    /// Record a request for a persisted operation, and whether it succeeded.
+    pub(crate) fn observe_persisted_operation(&self, name: &str, time: Duration, succeeded: bool) {
+        let request_metrics = &self.request_metrics;
+        request_metrics
+            .persisted_operations
+            .with_label_values(&[name])
+            .inc();
+        request_metrics
+            .persisted_operation_latency
+            .with_label_values(&[name])
+            .observe(time.as_secs_f64());
+        if !succeeded {
+            request_metrics
+                .persisted_operation_errors
+                .with_label_values(&[name])
+                .inc();
+        }
+    }
+
+    /// Increment the number of requests rejected by persisted query checks for `reason`.
+    pub(crate) fn inc_persisted_query_rejections(&self, reason: &str) {
+        self.request_metrics
+            .persisted_query_rejections
+            .with_label_values(&[reason])
+            .inc();
+    }
+
+    /// Record a lookup in the response cache for top-level field `field`, with result `result`.
+    pub(crate) fn inc_response_cache_lookups(&self, field: &str, result: &str) {
+        self.request_metrics
+            .response_cache_lookups
+            .with_label_values(&[field, result])
+            .inc();
+    }
+
+    /// Record that `count` results were dropped from the response cache.
+    pub(crate) fn inc_response_cache_invalidations(&self, count: u64) {
+        self.request_metrics
+            .response_cache_invalidations
+            .inc_by(count);
+    }
+
+    /// Record a request made with the API key named `key`.
+    pub(crate) fn inc_api_key_requests(&self, key: &str) {
+        self.request_metrics
+            .api_key_requests
+            .with_label_values(&[key])
+            .inc();
+    }
+
+    /// Record the estimated cost of a query made with the API key named `key`.
+    pub(crate) fn inc_api_key_query_cost(&self, key: &str, cost: u64) {
+        self.request_metrics
+            .api_key_query_cost
+            .with_label_values(&[key])
+            .inc_by(cost);
+    }
+
+    /// Record a request made with the API key named `key` that was rejected for exceeding `quota`.
+    pub(crate) fn inc_api_key_rejections(&self, key: &str, quota: &str) {
+        self.request_metrics
+            .api_key_rejections
+            .with_label_values(&[key, quota])
+            .inc();
+    }
+
     /// Use this function to increment the number of errors per path and per error type.
     /// The error type is detected automatically from the passed errors.
     pub(crate) fn inc_errors(&self, errors: &[ServerError]) {
@@ -249,6 +336,69 @@ impl RequestMetrics {
                 registry
             )
             .unwrap(),
+            persisted_operations: register_int_counter_vec_with_registry!(
+                "persisted_operations",
+                "Number of requests for each persisted operation",
+                &["operation"],
+                registry,
+            )
+            .unwrap(),
+            persisted_operation_latency: register_histogram_vec_with_registry!(
+                "persisted_operation_latency",
+                "The time needed to serve each persisted operation",
+                &["operation"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            persisted_operation_errors: register_int_counter_vec_with_registry!(
+                "persisted_operation_errors",
+                "Number of requests for each persisted operation that resulted in errors",
+                &["operation"],
+                registry,
+            )
+            .unwrap(),
+            persisted_query_rejections: register_int_counter_vec_with_registry!(
+                "persisted_query_rejections",
+                "Number of requests rejected by persisted query checks, by reason",
+                &["reason"],
+                registry,
+            )
+            .unwrap(),
+            response_cache_lookups: register_int_counter_vec_with_registry!(
+                "response_cache_lookups",
+                "Number of lookups in the response cache for each top-level field, by result",
+                &["field", "result"],
+                registry,
+            )
+            .unwrap(),
+            response_cache_invalidations: register_int_counter_with_registry!(
+                "response_cache_invalidations",
+                "Number of cached results dropped because their checkpoint is no longer served",
+                registry,
+            )
+            .unwrap(),
+            api_key_requests: register_int_counter_vec_with_registry!(
+                "api_key_requests",
+                "Number of requests made with each API key",
+                &["key"],
+                registry,
+            )
+            .unwrap(),
+            api_key_query_cost: register_int_counter_vec_with_registry!(
+                "api_key_query_cost",
+                "Total estimated cost (output nodes) of queries made with each API key",
+                &["key"],
+                registry,
+            )
+            .unwrap(),
+            api_key_rejections: register_int_counter_vec_with_registry!(
+                "api_key_rejections",
+                "Number of requests made with each API key that exceeded a quota, by quota",
+                &["key", "quota"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -0,0 +1,749 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use async_graphql::{Data, ServerError};
+use axum::http::{HeaderMap, HeaderName};
+use serde::{Deserialize, Serialize};
+use sui_graphql_rpc_headers::API_KEY_HEADER;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::config::{ApiKeysConfig, Limits, ServiceConfig};
+use crate::error::{code, graphql_error, Error};
+use crate::metrics::Metrics;
+
+/// API keys that requests can be authenticated with, and the state of each key's quotas. Keys are
+/// loaded from a file, which is reloaded by `ApiKeysTask` when it changes.
+pub(crate) struct ApiKeyStore {
+    path: PathBuf,
+    required: bool,
+    /// The service's configuration, which keys' quotas are applied to.
+    service: ServiceConfig,
+    metrics: Metrics,
+    /// Keys by their secret.
+    keys: RwLock<HashMap<String, Arc<ApiKeyState>>>,
+    /// When the keys file was last modified, as of the last time it was loaded.
+    modified: Mutex<Option<SystemTime>>,
+}
+
+/// Background task that reloads API keys whenever their file changes.
+pub(crate) struct ApiKeysTask {
+    store: Arc<ApiKeyStore>,
+    interval: Duration,
+    cancel: CancellationToken,
+}
+
+/// Added to the data of requests that were authenticated with an API key.
+#[derive(Clone)]
+pub(crate) struct ApiKey(Arc<ApiKeyState>);
+
+pub(crate) struct ApiKeyState {
+    name: String,
+    admin: bool,
+    quotas: Quotas,
+    /// The service's configuration with this key's quotas applied, if they differ from the
+    /// service's limits.
+    config: Option<ServiceConfig>,
+    /// Shared by successive versions of the key with the same name and rate, so that keys cannot
+    /// escape their quotas when keys are reloaded.
+    requests: Option<Arc<Mutex<Bucket>>>,
+    query_cost: Option<Arc<Mutex<Bucket>>>,
+    /// Shared by successive versions of the key with the same name, so that usage is preserved
+    /// when keys are reloaded.
+    usage: Arc<UsageCounters>,
+    metrics: Metrics,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+struct Quotas {
+    requests_per_minute: Option<u64>,
+    /// Measured in estimated output nodes, as calculated by `QueryLimitsChecker`.
+    query_cost_per_minute: Option<u64>,
+    max_page_size: Option<u32>,
+}
+
+/// An API key, as it appears in the API keys file.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct KeyEntry {
+    name: String,
+    key: String,
+    #[serde(default)]
+    admin: bool,
+    #[serde(flatten)]
+    quotas: Quotas,
+}
+
+#[derive(Deserialize)]
+struct KeysFile {
+    #[serde(default)]
+    keys: Vec<KeyEntry>,
+}
+
+#[derive(Default)]
+struct UsageCounters {
+    requests: AtomicU64,
+    query_cost: AtomicU64,
+    rejections: AtomicU64,
+}
+
+/// Usage of an API key since the service started, as reported by the admin endpoint.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct KeyUsage {
+    name: String,
+    requests: u64,
+    query_cost: u64,
+    rejections: u64,
+    requests_per_minute: Option<u64>,
+    query_cost_per_minute: Option<u64>,
+    max_page_size: Option<u32>,
+}
+
+/// A quota of units that refills continuously, at a rate of its capacity per minute.
+struct Bucket {
+    capacity: u64,
+    available: f64,
+    last_refill: Instant,
+}
+
+/// Reasons for rejecting a request, used to label metrics.
+#[derive(Debug, PartialEq, Eq)]
+enum Rejection {
+    Requests,
+    QueryCost,
+}
+
+impl ApiKeyStore {
+    /// Load API keys from the file in `config`. Fails if the file cannot be read, or contains
+    /// invalid keys.
+    pub(crate) fn load(
+        config: &ApiKeysConfig,
+        service: &ServiceConfig,
+        metrics: &Metrics,
+    ) -> Result<Option<Self>, Error> {
+        let Some(path) = &config.path else {
+            return Ok(None);
+        };
+
+        let store = Self {
+            path: path.clone(),
+            required: config.required,
+            service: service.clone(),
+            metrics: metrics.clone(),
+            keys: RwLock::new(HashMap::new()),
+            modified: Mutex::new(None),
+        };
+
+        store.reload()?;
+        Ok(Some(store))
+    }
+
+    /// Authenticate a request with the API key in its `headers`, if there is one, adding the key
+    /// (and any configuration overrides from its quotas) to the request's `data`.
+    ///
+    /// Fails if the key is not recognised, if a key is required but not supplied, or if the key
+    /// has exceeded its request rate.
+    pub(crate) fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        data: &mut Data,
+    ) -> Result<(), ServerError> {
+        let Some(secret) = headers.get(ApiKey::name()) else {
+            return if self.required {
+                Err(graphql_error(
+                    code::UNAUTHENTICATED,
+                    format!(
+                        "Requests must include an API key in the {} header",
+                        ApiKey::name()
+                    ),
+                ))
+            } else {
+                Ok(())
+            };
+        };
+
+        let key = secret
+            .to_str()
+            .ok()
+            .and_then(|secret| self.keys.read().unwrap().get(secret).cloned())
+            .ok_or_else(|| graphql_error(code::UNAUTHENTICATED, "Invalid API key"))?;
+
+        key.check_request()?;
+
+        if let Some(config) = &key.config {
+            data.insert(config.clone());
+        }
+
+        data.insert(ApiKey(key));
+        Ok(())
+    }
+
+    /// Whether the API key in `headers` can access admin endpoints.
+    pub(crate) fn is_admin(&self, headers: &HeaderMap) -> bool {
+        let Some(Ok(secret)) = headers.get(ApiKey::name()).map(|s| s.to_str()) else {
+            return false;
+        };
+
+        self.keys
+            .read()
+            .unwrap()
+            .get(secret)
+            .is_some_and(|key| key.admin)
+    }
+
+    /// Usage of every key, ordered by name.
+    pub(crate) fn usage(&self) -> Vec<KeyUsage> {
+        let mut usage: Vec<_> = self
+            .keys
+            .read()
+            .unwrap()
+            .values()
+            .map(|key| key.usage())
+            .collect();
+
+        usage.sort_by(|a, b| a.name.cmp(&b.name));
+        usage
+    }
+
+    /// Reload keys from the file if it has been modified since it was last loaded.
+    fn reload(&self) -> Result<(), Error> {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to read API keys from {}: {e}",
+                    self.path.display()
+                ))
+            })?;
+
+        if *self.modified.lock().unwrap() == Some(modified) {
+            return Ok(());
+        }
+
+        let entries = read_keys_file(&self.path)?;
+        self.replace(entries)?;
+        *self.modified.lock().unwrap() = Some(modified);
+        Ok(())
+    }
+
+    /// Replace the store's keys with `entries`. Keys that share a name with an existing key keep
+    /// its usage counters, and the state of any of its quotas that have not changed.
+    fn replace(&self, entries: Vec<KeyEntry>) -> Result<(), Error> {
+        let mut keys = self.keys.write().unwrap();
+        let previous: HashMap<_, _> = keys
+            .values()
+            .map(|key| (key.name.clone(), key.clone()))
+            .collect();
+
+        let mut names = HashSet::new();
+        let mut replacement = HashMap::new();
+        for entry in entries {
+            if !names.insert(entry.name.clone()) {
+                return Err(Error::Internal(format!(
+                    "Duplicate API key name: {}",
+                    entry.name
+                )));
+            }
+
+            let state = ApiKeyState::new(
+                &entry,
+                &self.service,
+                previous.get(&entry.name).map(|key| key.as_ref()),
+                self.metrics.clone(),
+            );
+
+            if replacement.insert(entry.key, Arc::new(state)).is_some() {
+                return Err(Error::Internal(format!(
+                    "API key for {} is not unique",
+                    entry.name
+                )));
+            }
+        }
+
+        info!("Loaded {} API keys", replacement.len());
+        *keys = replacement;
+        Ok(())
+    }
+}
+
+impl ApiKeysTask {
+    pub(crate) fn new(
+        store: Arc<ApiKeyStore>,
+        config: &ApiKeysConfig,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            store,
+            interval: Duration::from_millis(config.reload_interval_ms),
+            cancel,
+        }
+    }
+
+    pub(crate) async fn run(&self) {
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => {
+                    info!("Shutdown signal received, terminating API keys reload task");
+                    return;
+                },
+                _ = interval.tick() => {
+                    // Keep serving the existing keys if the new ones can't be loaded.
+                    if let Err(e) = self.store.reload() {
+                        error!("Failed to reload API keys: {e}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ApiKey {
+    pub(crate) fn name() -> &'static HeaderName {
+        &API_KEY_HEADER
+    }
+
+    /// Charge the estimated `cost` of a query to this key. Fails if the key does not have enough
+    /// of its query cost quota left.
+    pub(crate) fn charge_query_cost(&self, cost: u32) -> Result<(), ServerError> {
+        let key = &self.0;
+        if let Some(bucket) = &key.query_cost {
+            if !bucket.lock().unwrap().try_take(cost as u64) {
+                return Err(key.reject(Rejection::QueryCost));
+            }
+        }
+
+        key.usage
+            .query_cost
+            .fetch_add(cost as u64, Ordering::Relaxed);
+        key.metrics.inc_api_key_query_cost(&key.name, cost as u64);
+        Ok(())
+    }
+}
+
+impl ApiKeyState {
+    /// The state for the key in `entry`. If it replaces a `previous` version of the key, it
+    /// inherits its usage counters, and the buckets of any quotas whose rates are unchanged.
+    fn new(
+        entry: &KeyEntry,
+        service: &ServiceConfig,
+        previous: Option<&ApiKeyState>,
+        metrics: Metrics,
+    ) -> Self {
+        let quotas = entry.quotas.clone();
+        let config = quotas.apply(&service.limits).map(|limits| ServiceConfig {
+            limits,
+            ..service.clone()
+        });
+
+        let requests = match previous {
+            Some(prev) if prev.quotas.requests_per_minute == quotas.requests_per_minute => {
+                prev.requests.clone()
+            }
+            _ => quotas.requests_per_minute.map(Bucket::shared),
+        };
+
+        let query_cost = match previous {
+            Some(prev) if prev.quotas.query_cost_per_minute == quotas.query_cost_per_minute => {
+                prev.query_cost.clone()
+            }
+            _ => quotas.query_cost_per_minute.map(Bucket::shared),
+        };
+
+        Self {
+            name: entry.name.clone(),
+            admin: entry.admin,
+            config,
+            requests,
+            query_cost,
+            quotas,
+            usage: previous.map(|prev| prev.usage.clone()).unwrap_or_default(),
+            metrics,
+        }
+    }
+
+    /// Count a request against this key. Fails if the key has exceeded its request rate.
+    fn check_request(&self) -> Result<(), ServerError> {
+        if let Some(bucket) = &self.requests {
+            if !bucket.lock().unwrap().try_take(1) {
+                return Err(self.reject(Rejection::Requests));
+            }
+        }
+
+        self.usage.requests.fetch_add(1, Ordering::Relaxed);
+        self.metrics.inc_api_key_requests(&self.name);
+        Ok(())
+    }
+
+    fn reject(&self, rejection: Rejection) -> ServerError {
+        self.usage.rejections.fetch_add(1, Ordering::Relaxed);
+        self.metrics
+            .inc_api_key_rejections(&self.name, rejection.label());
+
+        let message = match rejection {
+            Rejection::Requests => format!(
+                "API key {} has exceeded its quota of {} requests per minute",
+                self.name,
+                self.quotas.requests_per_minute.unwrap_or_default(),
+            ),
+            Rejection::QueryCost => format!(
+                "API key {} has exceeded its quota of {} output nodes per minute",
+                self.name,
+                self.quotas.query_cost_per_minute.unwrap_or_default(),
+            ),
+        };
+
+        graphql_error(code::QUOTA_EXCEEDED, message)
+    }
+
+    fn usage(&self) -> KeyUsage {
+        KeyUsage {
+            name: self.name.clone(),
+            requests: self.usage.requests.load(Ordering::Relaxed),
+            query_cost: self.usage.query_cost.load(Ordering::Relaxed),
+            rejections: self.usage.rejections.load(Ordering::Relaxed),
+            requests_per_minute: self.quotas.requests_per_minute,
+            query_cost_per_minute: self.quotas.query_cost_per_minute,
+            max_page_size: self.quotas.max_page_size,
+        }
+    }
+}
+
+impl Quotas {
+    /// The service's `limits` with this key's page size quota applied, if that changes them. Keys
+    /// cannot raise the service's max page size.
+    fn apply(&self, limits: &Limits) -> Option<Limits> {
+        let max_page_size = self.max_page_size?.min(limits.max_page_size);
+        if max_page_size == limits.max_page_size {
+            return None;
+        }
+
+        Some(Limits {
+            max_page_size,
+            default_page_size: limits.default_page_size.min(max_page_size),
+            ..limits.clone()
+        })
+    }
+}
+
+impl Bucket {
+    fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            available: capacity as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn shared(capacity: u64) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::new(capacity)))
+    }
+
+    /// Take `amount` units from the bucket, if they are available.
+    fn try_take(&mut self, amount: u64) -> bool {
+        self.try_take_at(amount, Instant::now())
+    }
+
+    fn try_take_at(&mut self, amount: u64, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refill = self.capacity as f64 * elapsed.as_secs_f64() / 60.0;
+        self.available = (self.available + refill).min(self.capacity as f64);
+        self.last_refill = now;
+
+        if self.available < amount as f64 {
+            return false;
+        }
+
+        self.available -= amount as f64;
+        true
+    }
+}
+
+impl Rejection {
+    fn label(&self) -> &'static str {
+        match self {
+            Rejection::Requests => "requests",
+            Rejection::QueryCost => "query_cost",
+        }
+    }
+}
+
+fn read_keys_file(path: &Path) -> Result<Vec<KeyEntry>, Error> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to read API keys from {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    parse_keys(&contents).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to parse API keys from {}: {e}",
+            path.display()
+        ))
+    })
+}
+
+fn parse_keys(contents: &str) -> Result<Vec<KeyEntry>, toml::de::Error> {
+    let file: KeysFile = toml::de::from_str(contents)?;
+    Ok(file.keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    const KEYS: &str = r#"
+        [[keys]]
+        name = "explorer"
+        key = "explorer-secret"
+        requests-per-minute = 2
+        max-page-size = 10
+
+        [[keys]]
+        name = "indexer"
+        key = "indexer-secret"
+        query-cost-per-minute = 100
+
+        [[keys]]
+        name = "ops"
+        key = "ops-secret"
+        admin = true
+    "#;
+
+    fn store(required: bool) -> ApiKeyStore {
+        let store = ApiKeyStore {
+            path: PathBuf::new(),
+            required,
+            service: ServiceConfig::default(),
+            metrics: Metrics::new(&prometheus::Registry::new()),
+            keys: RwLock::new(HashMap::new()),
+            modified: Mutex::new(None),
+        };
+
+        store.replace(parse_keys(KEYS).unwrap()).unwrap();
+        store
+    }
+
+    fn headers(secret: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ApiKey::name(), HeaderValue::from_str(secret).unwrap());
+        headers
+    }
+
+    fn authenticated_key(store: &ApiKeyStore, secret: &str) -> ApiKey {
+        let mut data = Data::default();
+        store.authenticate(&headers(secret), &mut data).unwrap();
+        let key = store.keys.read().unwrap().get(secret).cloned().unwrap();
+        ApiKey(key)
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let store = store(false);
+        let mut data = Data::default();
+
+        // No key is fine, unless one is required.
+        store.authenticate(&HeaderMap::new(), &mut data).unwrap();
+        let err = store(true)
+            .authenticate(&HeaderMap::new(), &mut data)
+            .unwrap_err();
+        assert!(err.message.contains("must include an API key"));
+
+        let err = store
+            .authenticate(&headers("unknown"), &mut data)
+            .unwrap_err();
+        assert_eq!(err.message, "Invalid API key");
+
+        store
+            .authenticate(&headers("indexer-secret"), &mut data)
+            .unwrap();
+        assert!(!data.is_empty());
+    }
+
+    #[test]
+    fn test_request_quota() {
+        let store = store(false);
+        let mut data = Data::default();
+
+        store
+            .authenticate(&headers("explorer-secret"), &mut data)
+            .unwrap();
+        store
+            .authenticate(&headers("explorer-secret"), &mut data)
+            .unwrap();
+        let err = store
+            .authenticate(&headers("explorer-secret"), &mut data)
+            .unwrap_err();
+        assert!(err.message.contains("2 requests per minute"));
+
+        let usage = &store.usage()[0];
+        assert_eq!(usage.name, "explorer");
+        assert_eq!(usage.requests, 2);
+        assert_eq!(usage.rejections, 1);
+    }
+
+    #[test]
+    fn test_query_cost_quota() {
+        let store = store(false);
+        let key = authenticated_key(&store, "indexer-secret");
+
+        key.charge_query_cost(60).unwrap();
+        key.charge_query_cost(40).unwrap();
+        key.charge_query_cost(1).unwrap_err();
+
+        let usage = &store.usage()[1];
+        assert_eq!(usage.name, "indexer");
+        assert_eq!(usage.query_cost, 100);
+        assert_eq!(usage.rejections, 1);
+    }
+
+    #[test]
+    fn test_page_size_quota() {
+        let store = store(false);
+        let keys = store.keys.read().unwrap();
+
+        let config = keys["explorer-secret"].config.as_ref().unwrap();
+        assert_eq!(config.limits.max_page_size, 10);
+        assert_eq!(config.limits.default_page_size, 10);
+
+        // Keys without a page size quota use the service's config.
+        assert!(keys["indexer-secret"].config.is_none());
+    }
+
+    #[test]
+    fn test_admin() {
+        let store = store(false);
+        assert!(store.is_admin(&headers("ops-secret")));
+        assert!(!store.is_admin(&headers("explorer-secret")));
+        assert!(!store.is_admin(&headers("unknown")));
+        assert!(!store.is_admin(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn test_reload_preserves_usage() {
+        let store = store(false);
+        authenticated_key(&store, "explorer-secret");
+
+        // The explorer key is rotated, and the indexer key is removed.
+        store
+            .replace(
+                parse_keys(
+                    r#"
+                    [[keys]]
+                    name = "explorer"
+                    key = "rotated-secret"
+                "#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let mut data = Data::default();
+        store
+            .authenticate(&headers("explorer-secret"), &mut data)
+            .unwrap_err();
+        store
+            .authenticate(&headers("indexer-secret"), &mut data)
+            .unwrap_err();
+
+        authenticated_key(&store, "rotated-secret");
+        let usage = store.usage();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].requests, 2);
+        assert_eq!(usage[0].requests_per_minute, None);
+    }
+
+    #[test]
+    fn test_reload_preserves_quotas() {
+        let store = store(false);
+        authenticated_key(&store, "explorer-secret");
+        authenticated_key(&store, "explorer-secret");
+        let key = authenticated_key(&store, "indexer-secret");
+        key.charge_query_cost(100).unwrap();
+
+        // The explorer key is rotated, keeping its request rate, and the indexer key's query cost
+        // quota is raised.
+        store
+            .replace(
+                parse_keys(
+                    r#"
+                    [[keys]]
+                    name = "explorer"
+                    key = "rotated-secret"
+                    requests-per-minute = 2
+
+                    [[keys]]
+                    name = "indexer"
+                    key = "indexer-secret"
+                    query-cost-per-minute = 200
+                "#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        // The explorer key's requests so far still count against its quota...
+        let mut data = Data::default();
+        let err = store
+            .authenticate(&headers("rotated-secret"), &mut data)
+            .unwrap_err();
+        assert!(err.message.contains("2 requests per minute"));
+
+        // ...but the indexer key starts with a fresh bucket at its new rate.
+        let key = authenticated_key(&store, "indexer-secret");
+        key.charge_query_cost(200).unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let store = store(false);
+        let entries = parse_keys(
+            r#"
+            [[keys]]
+            name = "a"
+            key = "secret"
+
+            [[keys]]
+            name = "b"
+            key = "secret"
+        "#,
+        )
+        .unwrap();
+
+        store.replace(entries).unwrap_err();
+
+        // The existing keys are kept.
+        assert_eq!(store.usage().len(), 3);
+    }
+
+    #[test]
+    fn test_bucket_refill() {
+        let start = Instant::now();
+        let mut bucket = Bucket {
+            capacity: 60,
+            available: 60.0,
+            last_refill: start,
+        };
+
+        assert!(bucket.try_take_at(60, start));
+        assert!(!bucket.try_take_at(1, start));
+
+        // Refills at a rate of one unit per second.
+        assert!(bucket.try_take_at(10, start + Duration::from_secs(10)));
+        assert!(!bucket.try_take_at(1, start + Duration::from_secs(10)));
+
+        // But never beyond its capacity.
+        assert!(!bucket.try_take_at(61, start + Duration::from_secs(600)));
+    }
+}
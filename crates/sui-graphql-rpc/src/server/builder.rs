@@ -1,11 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use super::api_keys::{ApiKeyStore, ApiKeysTask, KeyUsage};
 use super::exchange_rates_task::TriggerExchangeRatesTask;
 use super::system_package_task::SystemPackageTask;
 use super::watermark_task::{ChainIdentifierLock, Watermark, WatermarkLock, WatermarkTask};
 use crate::config::{
-    ConnectionConfig, ServiceConfig, Version, MAX_CONCURRENT_REQUESTS,
+    ConnectionConfig, PersistedQueriesMode, ServiceConfig, Version, MAX_CONCURRENT_REQUESTS,
     RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
 };
 use crate::data::move_registry_data_loader::MoveRegistryDataLoader;
@@ -14,6 +15,7 @@ use crate::data::{DataLoader, Db};
 use crate::extensions::directive_checker::DirectiveChecker;
 use crate::metrics::Metrics;
 use crate::mutation::Mutation;
+use crate::subscription::{Subscription, SubscriptionSlots};
 use crate::types::datatype::IMoveDatatype;
 use crate::types::move_object::IMoveObject;
 use crate::types::object::IObject;
@@ -25,7 +27,9 @@ use crate::{
     extensions::{
         feature_gate::FeatureGate,
         logger::Logger,
+        persisted_queries::{PersistedQueries, PersistedQueryStore},
         query_limits_checker::{PayloadSize, QueryLimitsChecker, ShowUsage},
+        response_cache::{ExternalCache, ResponseCache},
         timeout::Timeout,
     },
     server::version::set_version_middleware,
@@ -33,10 +37,11 @@ use crate::{
 };
 use async_graphql::extensions::ApolloTracing;
 use async_graphql::extensions::Tracing;
-use async_graphql::EmptySubscription;
-use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
+use async_graphql::{extensions::ExtensionFactory, Data, Schema, SchemaBuilder};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
 use axum::body::Body;
+use axum::extract::ws::WebSocketUpgrade;
 use axum::extract::FromRef;
 use axum::extract::{ConnectInfo, Query as AxumQuery, State};
 use axum::http::{HeaderMap, StatusCode};
@@ -56,7 +61,7 @@ use std::net::TcpStream;
 use std::sync::Arc;
 use std::time::Duration;
 use std::{any::Any, net::SocketAddr, time::Instant};
-use sui_graphql_rpc_headers::LIMITS_HEADER;
+use sui_graphql_rpc_headers::{API_KEY_HEADER, LIMITS_HEADER};
 use sui_indexer::db::check_db_migration_consistency;
 use sui_package_resolver::{PackageStoreWithLruCache, Resolver};
 use sui_sdk::SuiClientBuilder;
@@ -78,6 +83,7 @@ pub(crate) struct Server {
     watermark_task: WatermarkTask,
     system_package_task: SystemPackageTask,
     trigger_exchange_rates_task: TriggerExchangeRatesTask,
+    api_keys_task: Option<ApiKeysTask>,
     state: AppState,
 }
 
@@ -111,6 +117,15 @@ impl Server {
             })
         };
 
+        // A handle that spawns a background task to reload API keys when they change, if they are
+        // configured.
+        let api_keys_task = spawn_monitored_task!(async move {
+            if let Some(task) = self.api_keys_task {
+                info!("Starting API keys reload task");
+                task.run().await;
+            }
+        });
+
         let server_task = {
             info!("Starting graphql service");
             let cancellation_token = self.state.cancellation_token.clone();
@@ -136,6 +151,7 @@ impl Server {
             watermark_task,
             system_package_task,
             trigger_exchange_rates_task,
+            api_keys_task,
             server_task
         );
 
@@ -145,10 +161,11 @@ impl Server {
 
 pub(crate) struct ServerBuilder {
     state: AppState,
-    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, Subscription>,
     router: Option<Router>,
     db_reader: Option<Db>,
     resolver: Option<PackageResolver>,
+    api_keys: Option<Arc<ApiKeyStore>>,
 }
 
 #[derive(Clone)]
@@ -184,12 +201,24 @@ impl FromRef<AppState> for ConnectionConfig {
     }
 }
 
+impl FromRef<AppState> for ServiceConfig {
+    fn from_ref(app_state: &AppState) -> ServiceConfig {
+        app_state.service.clone()
+    }
+}
+
 impl FromRef<AppState> for Metrics {
     fn from_ref(app_state: &AppState) -> Metrics {
         app_state.metrics.clone()
     }
 }
 
+impl FromRef<AppState> for CancellationToken {
+    fn from_ref(app_state: &AppState) -> CancellationToken {
+        app_state.cancellation_token.clone()
+    }
+}
+
 impl ServerBuilder {
     pub fn new(state: AppState) -> Self {
         Self {
@@ -198,6 +227,7 @@ impl ServerBuilder {
             router: None,
             db_reader: None,
             resolver: None,
+            api_keys: None,
         }
     }
 
@@ -219,7 +249,7 @@ impl ServerBuilder {
     }
 
     #[cfg(test)]
-    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
+    fn build_schema(self) -> Schema<Query, Mutation, Subscription> {
         self.schema.finish()
     }
 
@@ -229,7 +259,7 @@ impl ServerBuilder {
         self,
     ) -> (
         String,
-        Schema<Query, Mutation, EmptySubscription>,
+        Schema<Query, Mutation, Subscription>,
         Db,
         PackageResolver,
         Router,
@@ -241,6 +271,7 @@ impl ServerBuilder {
             db_reader,
             resolver,
             router,
+            api_keys: _,
         } = self;
         (
             address,
@@ -256,8 +287,11 @@ impl ServerBuilder {
             let router: Router = Router::new()
                 .route("/", post(graphql_handler))
                 .route("/graphql", post(graphql_handler))
+                .route("/subscriptions", get(subscription_handler))
+                .route("/graphql/subscriptions", get(subscription_handler))
                 .route("/health", get(health_check))
                 .route("/graphql/health", get(health_check))
+                .route("/admin/api-keys", get(api_keys_handler))
                 .with_state(self.state.clone())
                 .route_layer(CallbackLayer::new(MetricsMakeCallbackHandler {
                     metrics: self.state.metrics.clone(),
@@ -308,13 +342,18 @@ impl ServerBuilder {
             .allow_methods([Method::POST])
             // Allow requests from any origin
             .allow_origin(acl)
-            .allow_headers([hyper::header::CONTENT_TYPE, LIMITS_HEADER.clone()]);
+            .allow_headers([
+                hyper::header::CONTENT_TYPE,
+                LIMITS_HEADER.clone(),
+                API_KEY_HEADER.clone(),
+            ]);
         Ok(cors)
     }
 
     /// Consumes the `ServerBuilder` to create a `Server` that can be run.
     pub fn build(self) -> Result<Server, Error> {
         let state = self.state.clone();
+        let api_keys = self.api_keys.clone();
         let (address, schema, db_reader, resolver, router) = self.build_components();
 
         // Initialize the watermark background task struct.
@@ -337,6 +376,14 @@ impl ServerBuilder {
             state.cancellation_token.clone(),
         );
 
+        let api_keys_task = api_keys.clone().map(|store| {
+            ApiKeysTask::new(
+                store,
+                &state.service.api_keys,
+                state.cancellation_token.clone(),
+            )
+        });
+
         let router = router
             .route_layer(middleware::from_fn_with_state(
                 state.version,
@@ -345,6 +392,7 @@ impl ServerBuilder {
             .layer(axum::extract::Extension(schema))
             .layer(axum::extract::Extension(watermark_task.lock()))
             .layer(axum::extract::Extension(watermark_task.chain_id_lock()))
+            .layer(axum::extract::Extension(api_keys))
             .layer(Self::cors()?);
 
         Ok(Server {
@@ -353,16 +401,21 @@ impl ServerBuilder {
             watermark_task,
             system_package_task,
             trigger_exchange_rates_task,
+            api_keys_task,
             state,
         })
     }
 
     /// Instantiate a `ServerBuilder` from a `ServerConfig`, typically called when building the
     /// graphql service for production usage.
+    ///
+    /// If an `external_cache` is supplied, it backs the in-process response cache (when the
+    /// response cache is enabled).
     pub async fn from_config(
         config: &ServerConfig,
         version: &Version,
         cancellation_token: CancellationToken,
+        external_cache: Option<Arc<dyn ExternalCache>>,
     ) -> Result<Self, Error> {
         // PROMETHEUS
         let prom_addr: SocketAddr = format!(
@@ -399,6 +452,10 @@ impl ServerBuilder {
         );
         let mut builder = ServerBuilder::new(state);
 
+        // API keys are reloaded in the background, whenever their file changes.
+        builder.api_keys =
+            ApiKeyStore::load(&config.service.api_keys, &config.service, &metrics)?.map(Arc::new);
+
         let name_service_config = config.service.name_service.clone();
         let move_registry_config = config.service.move_registry.clone();
         let zklogin_config = config.service.zklogin.clone();
@@ -438,6 +495,21 @@ impl ServerBuilder {
             config.service.limits.package_resolver_limits(),
         ));
 
+        // Persisted queries are loaded once, on start-up.
+        let persisted_queries =
+            if config.service.persisted_queries.mode != PersistedQueriesMode::Disabled {
+                Some(
+                    PersistedQueryStore::load(
+                        &config.service.persisted_queries,
+                        &config.service.limits,
+                        &db,
+                    )
+                    .await?,
+                )
+            } else {
+                None
+            };
+
         builder.db_reader = Some(db.clone());
         builder.resolver = Some(resolver.clone());
 
@@ -471,6 +543,10 @@ impl ServerBuilder {
             .context_data(move_registry_config.clone())
             .context_data(MoveRegistryDataLoader::new(move_registry_config));
 
+        if let Some(store) = persisted_queries {
+            builder = builder.extension(PersistedQueries::new(store));
+        }
+
         if config.internal_features.feature_gate {
             builder = builder.extension(FeatureGate);
         }
@@ -487,6 +563,13 @@ impl ServerBuilder {
             builder = builder.extension(DirectiveChecker);
         }
 
+        if config.service.response_cache.enabled {
+            builder = builder.extension(ResponseCache::new(
+                &config.service.response_cache,
+                external_cache,
+            ));
+        }
+
         if config.internal_features.query_timeout {
             builder = builder.extension(Timeout);
         }
@@ -506,8 +589,8 @@ impl ServerBuilder {
     }
 }
 
-fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    async_graphql::Schema::build(Query, Mutation, EmptySubscription)
+fn schema_builder() -> SchemaBuilder<Query, Mutation, Subscription> {
+    async_graphql::Schema::build(Query, Mutation, Subscription)
         .register_output_type::<IMoveObject>()
         .register_output_type::<IObject>()
         .register_output_type::<IOwner>()
@@ -520,13 +603,15 @@ pub fn export_schema() -> String {
 }
 
 /// Entry point for graphql requests. Each request is stamped with a unique ID, a `ShowUsage` flag
-/// if set in the request headers, and the watermark as set by the background task.
+/// if set in the request headers, and the watermark as set by the background task. If API keys are
+/// configured, the request is also authenticated by its API key, which is added to its data.
 async fn graphql_handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     TypedHeader(ContentLength(content_length)): TypedHeader<ContentLength>,
     schema: Extension<SuiGraphQLSchema>,
     Extension(watermark_lock): Extension<WatermarkLock>,
     Extension(chain_identifier_lock): Extension<ChainIdentifierLock>,
+    Extension(api_keys): Extension<Option<Arc<ApiKeyStore>>>,
     headers: HeaderMap,
     req: GraphQLRequest,
 ) -> (axum::http::Extensions, GraphQLResponse) {
@@ -544,7 +629,15 @@ async fn graphql_handler(
     req.data.insert(Watermark::new(watermark_lock).await);
     req.data.insert(chain_identifier_lock.read().await);
 
-    let result = schema.execute(req).await;
+    let authenticated = match &api_keys {
+        Some(api_keys) => api_keys.authenticate(&headers, &mut req.data),
+        None => Ok(()),
+    };
+
+    let result = match authenticated {
+        Ok(()) => schema.execute(req).await,
+        Err(e) => async_graphql::Response::from_errors(vec![e]),
+    };
 
     // If there are errors, insert them as an extension so that the Metrics callback handler can
     // pull it out later.
@@ -555,6 +648,74 @@ async fn graphql_handler(
     (extensions, result.into())
 }
 
+/// Entry point for subscriptions, which are served over a WebSocket. Like `graphql_handler`, each
+/// connection is stamped with a unique ID and the watermark, and subscriptions additionally get
+/// access to the watermark lock, so they can follow the watermark as it advances, and the
+/// cancellation token, so they can end when the service shuts down.
+///
+/// Connections are authenticated by API key (if API keys are configured) before they are upgraded,
+/// and are refused if authentication fails. Each connection can run a limited number of
+/// subscriptions at once, and each subscription request is checked against the usual limits based
+/// on its own size, as it arrives in a WebSocket message.
+#[allow(clippy::too_many_arguments)]
+async fn subscription_handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(service): State<ServiceConfig>,
+    State(cancellation_token): State<CancellationToken>,
+    Extension(schema): Extension<SuiGraphQLSchema>,
+    Extension(watermark_lock): Extension<WatermarkLock>,
+    Extension(chain_identifier_lock): Extension<ChainIdentifierLock>,
+    Extension(api_keys): Extension<Option<Arc<ApiKeyStore>>>,
+    headers: HeaderMap,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> axum::response::Response {
+    let max_payload_size = service.limits.max_query_payload_size;
+
+    let mut data = Data::default();
+    data.insert(Uuid::new_v4());
+    data.insert(addr);
+    data.insert(Watermark::new(watermark_lock.clone()).await);
+    data.insert(watermark_lock);
+    data.insert(chain_identifier_lock.read().await);
+    data.insert(cancellation_token);
+    data.insert(SubscriptionSlots::new(
+        service.subscriptions.max_per_connection,
+    ));
+
+    if let Some(api_keys) = &api_keys {
+        if let Err(e) = api_keys.authenticate(&headers, &mut data) {
+            return (StatusCode::FORBIDDEN, e.message).into_response();
+        }
+    }
+
+    upgrade
+        .max_message_size(max_payload_size as usize)
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .serve()
+        })
+}
+
+/// Endpoint for querying the usage of each API key since the service started. Only available if
+/// API keys are configured, and to requests made with an admin key.
+async fn api_keys_handler(
+    Extension(api_keys): Extension<Option<Arc<ApiKeyStore>>>,
+    headers: HeaderMap,
+) -> Result<axum::Json<Vec<KeyUsage>>, StatusCode> {
+    let Some(api_keys) = api_keys else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    if !api_keys.is_admin(&headers) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(axum::Json(api_keys.usage()))
+}
+
 #[derive(Clone)]
 struct MetricsMakeCallbackHandler {
     metrics: Metrics,
@@ -678,10 +839,13 @@ async fn get_or_init_server_start_time() -> &'static Instant {
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::server::api_keys::ApiKey;
     use crate::test_infra::cluster::{prep_executor_cluster, start_cluster};
     use crate::types::chain_identifier::ChainIdentifier;
     use crate::{
-        config::{ConnectionConfig, Limits, ServiceConfig, Version},
+        config::{
+            ApiKeysConfig, ConnectionConfig, Limits, ResponseCacheConfig, ServiceConfig, Version,
+        },
         context_data::db_data_provider::PgManager,
         extensions::{query_limits_checker::QueryLimitsChecker, timeout::Timeout},
     };
@@ -1099,6 +1263,70 @@ pub mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_api_key_page_size_with_response_cache() {
+        telemetry_subscribers::init_for_testing();
+        let cluster = prep_executor_cluster().await;
+        let db_url = cluster.graphql_connection_config.db_url.clone();
+        let cache_config = ResponseCacheConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let schema = prep_schema(db_url, None)
+            .await
+            .extension(ResponseCache::new(&cache_config, None))
+            .build_schema();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api-keys.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[keys]]
+            name = "explorer"
+            key = "explorer-secret"
+            max-page-size = 2
+            "#,
+        )
+        .unwrap();
+
+        let api_keys_config = ApiKeysConfig {
+            path: Some(path),
+            ..Default::default()
+        };
+        let api_keys = ApiKeyStore::load(&api_keys_config, &ServiceConfig::default(), &metrics())
+            .unwrap()
+            .unwrap();
+
+        let query = "{ checkpoints(first: 3) { nodes { sequenceNumber } } }";
+
+        // Populate the cache with a request that is not subject to the key's page size.
+        schema
+            .execute(query)
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+
+        // The same request, made with the API key, should not be served the cached result.
+        let mut headers = HeaderMap::new();
+        headers.insert(ApiKey::name(), "explorer-secret".parse().unwrap());
+        let mut request = Request::new(query);
+        api_keys.authenticate(&headers, &mut request.data).unwrap();
+
+        let err: Vec<_> = schema
+            .execute(request)
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            err,
+            vec!["Connection's page size of 3 exceeds max of 2".to_string()]
+        );
+    }
+
     #[tokio::test]
     async fn test_query_complexity_metrics() {
         telemetry_subscribers::init_for_testing();
@@ -1,12 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use axum::extract::Path;
 use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 use crate::config::{ServerConfig, Version};
 use crate::error::Error;
+use crate::extensions::response_cache::ExternalCache;
 use crate::server::builder::ServerBuilder;
 
 async fn graphiql(
@@ -34,7 +37,31 @@ pub async fn start_graphiql_server(
     info!("Starting server with config: {:#?}", server_config);
     info!("Server version: {}", version);
     start_graphiql_server_impl(
-        ServerBuilder::from_config(server_config, version, cancellation_token).await?,
+        ServerBuilder::from_config(server_config, version, cancellation_token, None).await?,
+        server_config.ide.ide_title.clone(),
+    )
+    .await
+}
+
+/// Start the server with `external_cache` shared between instances of the service, backing each
+/// instance's in-process response cache. The response cache must also be enabled in the service's
+/// config for the external cache to be used.
+pub async fn start_graphiql_server_with_external_cache(
+    server_config: &ServerConfig,
+    version: &Version,
+    cancellation_token: CancellationToken,
+    external_cache: Arc<dyn ExternalCache>,
+) -> Result<(), Error> {
+    info!("Starting server with config: {:#?}", server_config);
+    info!("Server version: {}", version);
+    start_graphiql_server_impl(
+        ServerBuilder::from_config(
+            server_config,
+            version,
+            cancellation_token,
+            Some(external_cache),
+        )
+        .await?,
         server_config.ide.ide_title.clone(),
     )
     .await
@@ -0,0 +1,334 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_graphql::connection::{Connection, CursorType, Edge, EmptyFields};
+use async_graphql::*;
+use futures::Stream;
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::ServiceConfig;
+use crate::connection::ScanConnection;
+use crate::consistency::Resumable;
+use crate::error::Error;
+use crate::functional_group::FunctionalGroup;
+use crate::server::watermark_task::{Watermark, WatermarkLock};
+use crate::types::checkpoint::{self, Checkpoint, CheckpointCursor, CheckpointId};
+use crate::types::cursor::Page;
+use crate::types::event::{self, Event, EventFilter, EventKey};
+use crate::types::transaction_block::{
+    self, TransactionBlock, TransactionBlockCursor, TransactionBlockFilter,
+};
+
+pub(crate) struct Subscription;
+
+/// The subscriptions that can still be started on a WebSocket connection. Each active
+/// subscription holds a permit for as long as its stream is alive.
+#[derive(Clone)]
+pub(crate) struct SubscriptionSlots(Arc<Semaphore>);
+
+/// A page of results fetched by a subscription, and the cursor to resume from to fetch the next
+/// page.
+struct Batch<N: OutputType> {
+    edges: Vec<Edge<String, N, EmptyFields>>,
+    end_cursor: Option<String>,
+    has_next_page: bool,
+}
+
+/// Subscriptions are used to stream data from the Sui network, as it is indexed by the service.
+/// They are served over a WebSocket connection at the `/subscriptions` endpoint.
+///
+/// Each item in a subscription is delivered as an edge, so that its cursor can be used to resume
+/// the subscription (by passing it as `after`) if the connection is interrupted.
+#[Subscription]
+impl Subscription {
+    /// Events emitted on the network, optionally filtered by `filter`.
+    ///
+    /// If `after` is not provided, the subscription starts from the latest checkpoint indexed by
+    /// the service. Filtering by both emitting module and event type is not supported.
+    async fn events<'ctx>(
+        &self,
+        ctx: &'ctx Context<'ctx>,
+        filter: Option<EventFilter>,
+        after: Option<event::Cursor>,
+    ) -> Result<impl Stream<Item = Result<Edge<String, Event, EmptyFields>>> + 'ctx> {
+        check_enabled(ctx)?;
+
+        let cursor = match after {
+            Some(cursor) => cursor,
+            None => {
+                let Watermark { hi_cp, .. } = latest_watermark(ctx).await?;
+                event::Cursor::new(EventKey {
+                    tx: last_tx_sequence_number(ctx, hi_cp).await?,
+                    // Larger than any event sequence number, so that the subscription starts with
+                    // the first event of the next transaction.
+                    e: i64::MAX as u64,
+                    checkpoint_viewed_at: hi_cp,
+                })
+            }
+        };
+
+        let filter = filter.unwrap_or_default();
+        poll(ctx, cursor, move |page, hi_cp| {
+            let filter = filter.clone();
+            async move {
+                let conn = Event::paginate(ctx.data_unchecked(), page, filter, hi_cp).await?;
+                Ok(Batch::from(conn))
+            }
+        })
+        .await
+    }
+
+    /// Transaction blocks as they are finalized on the network, optionally filtered by `filter`.
+    ///
+    /// If `after` is not provided, the subscription starts from the latest checkpoint indexed by
+    /// the service. `scanLimit` bounds the number of candidate transactions scanned per page of
+    /// results, and follows the same rules as for `Query.transactionBlocks`.
+    async fn transactions<'ctx>(
+        &self,
+        ctx: &'ctx Context<'ctx>,
+        filter: Option<TransactionBlockFilter>,
+        after: Option<transaction_block::Cursor>,
+        scan_limit: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<Edge<String, TransactionBlock, EmptyFields>>> + 'ctx>
+    {
+        check_enabled(ctx)?;
+
+        let cursor = match after {
+            Some(cursor) => cursor,
+            None => {
+                let Watermark { hi_cp, .. } = latest_watermark(ctx).await?;
+                transaction_block::Cursor::new(TransactionBlockCursor {
+                    checkpoint_viewed_at: hi_cp,
+                    tx_sequence_number: last_tx_sequence_number(ctx, hi_cp).await?,
+                    is_scan_limited: false,
+                })
+            }
+        };
+
+        let filter = filter.unwrap_or_default();
+        poll(ctx, cursor, move |page, hi_cp| {
+            let filter = filter.clone();
+            async move {
+                let conn = TransactionBlock::paginate(ctx, page, filter, hi_cp, scan_limit).await?;
+                Ok(Batch::from(conn))
+            }
+        })
+        .await
+    }
+
+    /// Checkpoints as they are indexed by the service.
+    ///
+    /// If `after` is not provided, the subscription starts with the checkpoint after the latest
+    /// one indexed by the service.
+    async fn checkpoints<'ctx>(
+        &self,
+        ctx: &'ctx Context<'ctx>,
+        after: Option<checkpoint::Cursor>,
+    ) -> Result<impl Stream<Item = Result<Edge<String, Checkpoint, EmptyFields>>> + 'ctx> {
+        check_enabled(ctx)?;
+
+        let cursor = match after {
+            Some(cursor) => cursor,
+            None => {
+                let Watermark { hi_cp, .. } = latest_watermark(ctx).await?;
+                checkpoint::Cursor::new(CheckpointCursor {
+                    checkpoint_viewed_at: hi_cp,
+                    sequence_number: hi_cp,
+                })
+            }
+        };
+
+        poll(ctx, cursor, move |page, hi_cp| async move {
+            let conn = Checkpoint::paginate(ctx.data_unchecked(), page, None, hi_cp).await?;
+            Ok(Batch::from(conn))
+        })
+        .await
+    }
+}
+
+impl SubscriptionSlots {
+    pub(crate) fn new(max_per_connection: usize) -> Self {
+        Self(Arc::new(Semaphore::new(max_per_connection)))
+    }
+}
+
+impl<N: OutputType> From<Connection<String, N>> for Batch<N> {
+    fn from(conn: Connection<String, N>) -> Self {
+        Batch {
+            end_cursor: conn.edges.last().map(|edge| edge.cursor.clone()),
+            has_next_page: conn.has_next_page,
+            edges: conn.edges,
+        }
+    }
+}
+
+impl<N: OutputType> From<ScanConnection<String, N>> for Batch<N> {
+    fn from(conn: ScanConnection<String, N>) -> Self {
+        Batch {
+            // When the scan limit is hit, the end cursor may not correspond to the last edge.
+            end_cursor: conn
+                .end_cursor
+                .or_else(|| conn.edges.last().map(|edge| edge.cursor.clone())),
+            has_next_page: conn.has_next_page,
+            edges: conn.edges,
+        }
+    }
+}
+
+/// Subscriptions can be disabled as a whole. This is also enforced per-item by the `FeatureGate`
+/// extension, but checking up-front avoids polling the database for a stream that would only
+/// produce errors.
+fn check_enabled(ctx: &Context<'_>) -> Result<()> {
+    let ServiceConfig {
+        disabled_features, ..
+    } = ctx.data_unchecked();
+
+    if disabled_features.contains(&FunctionalGroup::Subscriptions) {
+        return Err(Error::Client(format!(
+            "Feature {} is disabled.",
+            FunctionalGroup::Subscriptions.name(),
+        ))
+        .extend());
+    }
+
+    Ok(())
+}
+
+/// The latest watermark, as opposed to the one captured when the request started. Subscriptions
+/// can only be served when the watermark lock has been added to the request's data (by the
+/// WebSocket handler).
+async fn latest_watermark(ctx: &Context<'_>) -> Result<Watermark> {
+    let lock: &WatermarkLock = ctx
+        .data()
+        .map_err(|_| Error::Client("Subscriptions must be made over a WebSocket".to_string()))
+        .extend()?;
+
+    Ok(Watermark::new(lock.clone()).await)
+}
+
+/// Sequence number of the last transaction in checkpoint `hi_cp`.
+async fn last_tx_sequence_number(ctx: &Context<'_>, hi_cp: u64) -> Result<u64> {
+    let Some(checkpoint) = Checkpoint::query(ctx, CheckpointId::by_seq_num(hi_cp), hi_cp)
+        .await
+        .extend()?
+    else {
+        return Err(Error::Internal(format!("Checkpoint {hi_cp} has not been indexed")).extend());
+    };
+
+    Ok(checkpoint
+        .network_total_transactions_impl()
+        .saturating_sub(1))
+}
+
+/// Stream the edges of successive pages of results, starting after `cursor`. Each page is fetched
+/// by `fetch`, at the latest watermark. Once the subscription has caught up with the watermark, it
+/// waits for the watermark to be updated before fetching the next page.
+///
+/// The stream ends after it yields an error, or when the service is shutting down.
+async fn poll<'ctx, C, N, F, Fut>(
+    ctx: &'ctx Context<'ctx>,
+    cursor: C,
+    fetch: F,
+) -> Result<impl Stream<Item = Result<Edge<String, N, EmptyFields>>> + 'ctx>
+where
+    C: Resumable + Send + Sync + 'ctx,
+    N: OutputType + 'ctx,
+    F: Fn(Page<C>, u64) -> Fut + Send + Sync + 'ctx,
+    Fut: Future<Output = Result<Batch<N>, Error>> + Send + 'ctx,
+{
+    let config: &ServiceConfig = ctx.data_unchecked();
+    let page_size = config.limits.max_page_size as u64;
+    let interval = Duration::from_millis(config.background_tasks.watermark_update_ms);
+
+    // Make sure subscriptions are being served over a WebSocket before starting the stream.
+    latest_watermark(ctx).await?;
+
+    // Claim one of the connection's subscription slots, to be released when the stream is dropped.
+    let permit = match ctx.data_opt::<SubscriptionSlots>() {
+        None => None,
+        Some(SubscriptionSlots(slots)) => Some(
+            slots
+                .clone()
+                .try_acquire_owned()
+                .map_err(|_| {
+                    Error::Client(format!(
+                        "Too many subscriptions on this connection, at most {} can be active at \
+                         once",
+                        config.subscriptions.max_per_connection,
+                    ))
+                })
+                .extend()?,
+        ),
+    };
+
+    let cancel = ctx
+        .data_opt::<CancellationToken>()
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(async_stream::stream! {
+        let _permit = permit;
+        let mut cursor = cursor;
+        while !cancel.is_cancelled() {
+            let Watermark { hi_cp, .. } = match latest_watermark(ctx).await {
+                Ok(watermark) => watermark,
+                Err(e) => {
+                    yield Err(e);
+                    break;
+                }
+            };
+
+            let page = match Page::from_params(
+                config,
+                Some(page_size),
+                Some(cursor.at_checkpoint(hi_cp)),
+                None,
+                None,
+            ) {
+                Ok(page) => page,
+                Err(e) => {
+                    yield Err(e);
+                    break;
+                }
+            };
+
+            let Batch {
+                edges,
+                end_cursor,
+                has_next_page,
+            } = match fetch(page, hi_cp).await {
+                Ok(batch) => batch,
+                Err(e) => {
+                    yield Err(e.extend());
+                    break;
+                }
+            };
+
+            if let Some(end_cursor) = end_cursor {
+                cursor = match C::decode_cursor(&end_cursor) {
+                    Ok(cursor) => cursor,
+                    Err(e) => {
+                        yield Err(Error::Internal(format!("Failed to decode cursor: {e}")).extend());
+                        break;
+                    }
+                };
+            }
+
+            for edge in edges {
+                yield Ok(edge);
+            }
+
+            if !has_next_page {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+            }
+        }
+    })
+}
@@ -13,7 +13,10 @@ use super::{
     transaction_block::{self, TransactionBlock, TransactionBlockFilter},
     uint53::UInt53,
 };
-use crate::{connection::ScanConnection, consistency::Checkpointed};
+use crate::{
+    connection::ScanConnection,
+    consistency::{Checkpointed, Resumable},
+};
 use crate::{
     data::{self, Conn, DataLoader, Db, DbConnection, QueryExecutor},
     error::Error,
@@ -429,6 +432,15 @@ impl Checkpointed for Cursor {
     }
 }
 
+impl Resumable for Cursor {
+    fn at_checkpoint(&self, checkpoint_viewed_at: u64) -> Self {
+        Cursor::new(CheckpointCursor {
+            checkpoint_viewed_at,
+            sequence_number: self.sequence_number,
+        })
+    }
+}
+
 impl ScanLimited for Cursor {}
 
 #[async_trait::async_trait]
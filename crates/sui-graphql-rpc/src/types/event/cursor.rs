@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    consistency::Checkpointed,
+    consistency::{Checkpointed, Resumable},
     filter,
     raw_query::RawQuery,
     types::cursor::{self, Paginated, RawPaginated, ScanLimited, Target},
@@ -122,6 +122,16 @@ impl Checkpointed for Cursor {
     }
 }
 
+impl Resumable for Cursor {
+    fn at_checkpoint(&self, checkpoint_viewed_at: u64) -> Self {
+        Cursor::new(EventKey {
+            tx: self.tx,
+            e: self.e,
+            checkpoint_viewed_at,
+        })
+    }
+}
+
 impl ScanLimited for Cursor {}
 
 impl Target<Cursor> for EvLookup {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    consistency::Checkpointed,
+    consistency::{Checkpointed, Resumable},
     filter,
     raw_query::RawQuery,
     types::cursor::{self, Paginated, RawPaginated, ScanLimited, Target},
@@ -51,6 +51,16 @@ impl Checkpointed for Cursor {
     }
 }
 
+impl Resumable for Cursor {
+    fn at_checkpoint(&self, checkpoint_viewed_at: u64) -> Self {
+        Cursor::new(TransactionBlockCursor {
+            checkpoint_viewed_at,
+            tx_sequence_number: self.tx_sequence_number,
+            is_scan_limited: self.is_scan_limited,
+        })
+    }
+}
+
 impl ScanLimited for Cursor {
     fn is_scan_limited(&self) -> bool {
         self.is_scan_limited
@@ -46,7 +46,7 @@ mod cursor;
 mod filter;
 mod tx_lookups;
 
-pub(crate) use cursor::Cursor;
+pub(crate) use cursor::{Cursor, TransactionBlockCursor};
 pub(crate) use filter::TransactionBlockFilter;
 pub(crate) use tx_lookups::{subqueries, TxBounds};
 
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use fastcrypto::encoding::{Base64, Encoding};
+use futures::{SinkExt, StreamExt};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use serde_json::json;
@@ -10,10 +11,14 @@ use std::sync::Arc;
 use std::time::Duration;
 use sui_graphql_rpc::client::simple_client::GraphqlQueryVariable;
 use sui_graphql_rpc::client::ClientError;
+use sui_graphql_rpc::config::ConnectionConfig;
 use sui_graphql_rpc::config::Limits;
 use sui_graphql_rpc::config::ServiceConfig;
+use sui_graphql_rpc::config::SubscriptionsConfig;
 use sui_graphql_rpc::test_infra::cluster::prep_executor_cluster;
 use sui_graphql_rpc::test_infra::cluster::start_cluster;
+use sui_graphql_rpc::test_infra::cluster::Cluster;
+use sui_test_transaction_builder::TestTransactionBuilder;
 use sui_types::digests::ChainIdentifier;
 use sui_types::gas_coin::GAS;
 use sui_types::transaction::CallArg;
@@ -23,7 +28,14 @@ use sui_types::DEEPBOOK_ADDRESS;
 use sui_types::SUI_FRAMEWORK_ADDRESS;
 use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
 use tempfile::tempdir;
+use tokio::net::TcpStream;
 use tokio::time::sleep;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
 #[tokio::test]
 async fn test_simple_client_validator_cluster() {
@@ -852,3 +864,169 @@ async fn test_payload_using_vars_mutation_passes() {
 
     assert!(res.errors().is_empty(), "{:#?}", res.errors());
 }
+
+#[tokio::test]
+async fn test_subscription_receives_events() {
+    let cluster = start_cluster(ServiceConfig::test_defaults()).await;
+    cluster
+        .wait_for_checkpoint_catchup(1, Duration::from_secs(30))
+        .await;
+
+    let test_cluster = &cluster.network.validator_fullnode_handle;
+    let (sender, mut coins) = test_cluster.wallet.get_one_account().await.unwrap();
+    let validator = test_cluster
+        .swarm
+        .active_validators()
+        .next()
+        .unwrap()
+        .config()
+        .sui_address();
+
+    let mut socket = connect_subscriptions(&cluster).await;
+    subscribe(
+        &mut socket,
+        "1",
+        r#"subscription($sender: SuiAddress!) {
+            events(filter: { sender: $sender }) {
+                node {
+                    sender { address }
+                    contents { type { repr } }
+                }
+            }
+        }"#,
+        json!({ "sender": sender }),
+    )
+    .await;
+
+    // Give the subscription a chance to start before the event is emitted.
+    sleep(Duration::from_secs(1)).await;
+
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let tx = TestTransactionBuilder::new(sender, coins.pop().unwrap(), rgp)
+        .call_staking(coins.pop().unwrap(), validator)
+        .build();
+    test_cluster.sign_and_execute_transaction(&tx).await;
+
+    let message = next_message_for(&mut socket, "1").await;
+    assert_eq!(message["type"], "next", "{message:#}");
+
+    let node = &message["payload"]["data"]["events"]["node"];
+    assert_eq!(node["sender"]["address"], json!(sender), "{message:#}");
+    let event_type = node["contents"]["type"]["repr"].as_str().unwrap();
+    assert!(
+        event_type.ends_with("::validator::StakingRequestEvent"),
+        "Unexpected event type: {event_type}",
+    );
+}
+
+#[tokio::test]
+async fn test_subscriptions_per_connection_limit() {
+    let cluster = start_cluster(ServiceConfig {
+        subscriptions: SubscriptionsConfig {
+            max_per_connection: 1,
+        },
+        ..ServiceConfig::test_defaults()
+    })
+    .await;
+    cluster
+        .wait_for_checkpoint_catchup(1, Duration::from_secs(30))
+        .await;
+
+    let checkpoints = "subscription { checkpoints { node { sequenceNumber } } }";
+    let mut socket = connect_subscriptions(&cluster).await;
+    subscribe(&mut socket, "1", checkpoints, json!({})).await;
+    subscribe(&mut socket, "2", checkpoints, json!({})).await;
+
+    // The second subscription is refused, because the first one is still active.
+    let message = next_message_for(&mut socket, "2").await;
+    let errors = if message["type"] == "error" {
+        &message["payload"]
+    } else {
+        &message["payload"]["errors"]
+    };
+    let error = errors[0]["message"].as_str().unwrap();
+    assert!(
+        error.contains("Too many subscriptions on this connection"),
+        "Unexpected error: {error}",
+    );
+
+    // Once the first subscription is stopped, its slot can be reused.
+    let complete = json!({ "id": "1", "type": "complete" });
+    socket
+        .send(Message::Text(complete.to_string()))
+        .await
+        .unwrap();
+    subscribe(&mut socket, "3", checkpoints, json!({})).await;
+
+    let message = next_message_for(&mut socket, "3").await;
+    assert_eq!(message["type"], "next", "{message:#}");
+    assert!(
+        message["payload"]["data"]["checkpoints"]["node"]["sequenceNumber"].is_u64(),
+        "{message:#}",
+    );
+}
+
+/// Open a WebSocket to the service's subscriptions endpoint, and initialize the connection using
+/// the `graphql-transport-ws` protocol.
+async fn connect_subscriptions(cluster: &Cluster) -> WebSocket {
+    let ConnectionConfig { host, port, .. } = &cluster.network.graphql_connection_config;
+    let mut request = format!("ws://{host}:{port}/subscriptions")
+        .into_client_request()
+        .unwrap();
+    request.headers_mut().insert(
+        "Sec-WebSocket-Protocol",
+        HeaderValue::from_static("graphql-transport-ws"),
+    );
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+    let init = json!({ "type": "connection_init" });
+    socket.send(Message::Text(init.to_string())).await.unwrap();
+
+    let ack = next_message(&mut socket).await;
+    assert_eq!(ack["type"], "connection_ack", "{ack:#}");
+    socket
+}
+
+async fn subscribe(socket: &mut WebSocket, id: &str, query: &str, variables: serde_json::Value) {
+    let message = json!({
+        "id": id,
+        "type": "subscribe",
+        "payload": { "query": query, "variables": variables },
+    });
+
+    socket
+        .send(Message::Text(message.to_string()))
+        .await
+        .unwrap();
+}
+
+/// The next message for the subscription with the given `id`, skipping messages for other
+/// subscriptions.
+async fn next_message_for(socket: &mut WebSocket, id: &str) -> serde_json::Value {
+    loop {
+        let message = next_message(socket).await;
+        if message["id"] == id {
+            return message;
+        }
+    }
+}
+
+/// The next protocol message received over `socket`, skipping keep-alive messages.
+async fn next_message(socket: &mut WebSocket) -> serde_json::Value {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(60), socket.next())
+            .await
+            .expect("Timed out waiting for a message")
+            .expect("WebSocket closed")
+            .unwrap();
+
+        let Message::Text(text) = message else {
+            continue;
+        };
+
+        let message: serde_json::Value = serde_json::from_str(&text).unwrap();
+        if message["type"] != "ping" && message["type"] != "pong" {
+            return message;
+        }
+    }
+}
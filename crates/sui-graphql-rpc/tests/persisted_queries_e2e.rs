@@ -0,0 +1,150 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::sql_query;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use fastcrypto::hash::{HashFunction, Sha256};
+use serde_json::{json, Value};
+use sui_graphql_rpc::config::{
+    ConnectionConfig, PersistedQueriesConfig, PersistedQueriesMode, ServiceConfig,
+};
+use sui_graphql_rpc::test_infra::cluster::{
+    prep_executor_cluster, start_graphql_server, wait_for_graphql_server, ExecutorCluster,
+};
+use sui_graphql_rpc_client::simple_client::SimpleClient;
+use sui_pg_db::temp::get_available_port;
+
+/// Registered through the persisted queries file.
+const FILE_QUERY: &str = "query { serviceConfig { maxQueryDepth } }";
+
+/// Registered through the persisted queries table.
+const TABLE_QUERY: &str = "query { serviceConfig { maxQueryNodes } }";
+
+const TABLE: &str = "graphql_persisted_queries";
+
+#[tokio::test]
+async fn test_persisted_queries() {
+    let cluster = prep_executor_cluster().await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("persisted_queries.toml");
+    std::fs::write(
+        &path,
+        format!(
+            r#"
+                [[operations]]
+                name = "maxQueryDepth"
+                query = "{FILE_QUERY}"
+            "#
+        ),
+    )
+    .unwrap();
+
+    let mut conn = AsyncPgConnection::establish(&cluster.graphql_connection_config.db_url)
+        .await
+        .unwrap();
+    sql_query(format!(
+        "CREATE TABLE {TABLE} (name TEXT NOT NULL, query TEXT NOT NULL, limits TEXT)"
+    ))
+    .execute(&mut conn)
+    .await
+    .unwrap();
+    sql_query(format!(
+        "INSERT INTO {TABLE} VALUES ('maxQueryNodes', '{TABLE_QUERY}', '{{\"max-query-nodes\": 100}}')"
+    ))
+    .execute(&mut conn)
+    .await
+    .unwrap();
+
+    let client = start_persisted_queries_server(
+        &cluster,
+        PersistedQueriesConfig {
+            mode: PersistedQueriesMode::Automatic,
+            path: Some(path),
+            table: Some(TABLE.to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // Operations from either source are served from their hash alone.
+    let resp = execute_by_hash(&client, &query_hash(FILE_QUERY)).await;
+    assert!(resp.get("errors").is_none(), "{resp}");
+    assert!(
+        resp["data"]["serviceConfig"]["maxQueryDepth"].is_u64(),
+        "{resp}"
+    );
+
+    let resp = execute_by_hash(&client, &query_hash(TABLE_QUERY)).await;
+    assert!(resp.get("errors").is_none(), "{resp}");
+    assert!(
+        resp["data"]["serviceConfig"]["maxQueryNodes"].is_u64(),
+        "{resp}"
+    );
+
+    // A hash that was never registered is rejected, so that clients can retry with the query.
+    let unknown = query_hash("query { chainIdentifier }");
+    let resp = execute_by_hash(&client, &unknown).await;
+    assert!(resp.get("data").is_none(), "{resp}");
+    assert_eq!(resp["errors"][0]["message"], "PersistedQueryNotFound");
+    assert_eq!(
+        resp["errors"][0]["extensions"]["code"],
+        "PERSISTED_QUERY_NOT_FOUND"
+    );
+}
+
+/// Starts a GraphQL service with `config`, alongside the one already serving the cluster's
+/// database.
+async fn start_persisted_queries_server(
+    cluster: &ExecutorCluster,
+    config: PersistedQueriesConfig,
+) -> SimpleClient {
+    let connection_config = ConnectionConfig {
+        port: get_available_port(),
+        prom_port: get_available_port(),
+        ..cluster.graphql_connection_config.clone()
+    };
+
+    let _gql_handle = start_graphql_server(
+        connection_config.clone(),
+        cluster.cancellation_token.clone(),
+        ServiceConfig {
+            persisted_queries: config,
+            ..ServiceConfig::test_defaults()
+        },
+    )
+    .await;
+
+    let server_url = format!(
+        "http://{}:{}/",
+        connection_config.host, connection_config.port
+    );
+
+    let client = SimpleClient::new(server_url);
+    wait_for_graphql_server(&client).await;
+    client
+}
+
+/// Sends a request that refers to its query by `hash`, without including it.
+async fn execute_by_hash(client: &SimpleClient, hash: &str) -> Value {
+    reqwest::Client::new()
+        .post(client.url())
+        .json(&json!({
+            "extensions": {
+                "persistedQuery": {
+                    "version": 1,
+                    "sha256Hash": hash,
+                },
+            },
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+fn query_hash(query: &str) -> String {
+    hex::encode(Sha256::digest(query.as_bytes()).digest)
+}
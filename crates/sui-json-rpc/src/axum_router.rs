@@ -23,6 +23,7 @@ use jsonrpsee::{core::server::rpc_module::Methods, server::logger::Logger};
 use serde_json::value::RawValue;
 use sui_core::traffic_controller::{
     metrics::TrafficControllerMetrics, parse_ip, policies::TrafficTally, TrafficController,
+    TrafficControllerState,
 };
 use sui_json_rpc_api::TRANSACTION_EXECUTION_CLIENT_ERROR_CODE;
 use sui_types::traffic_control::ClientIdSource;
@@ -56,6 +57,7 @@ impl<L> JsonRpcService<L> {
         remote_fw_config: Option<RemoteFirewallConfig>,
         policy_config: Option<PolicyConfig>,
         traffic_controller_metrics: TrafficControllerMetrics,
+        traffic_controller_state: TrafficControllerState,
     ) -> Self {
         Self {
             methods,
@@ -63,10 +65,11 @@ impl<L> JsonRpcService<L> {
             logger,
             id_provider: Arc::new(RandomIntegerIdProvider),
             traffic_controller: policy_config.clone().map(|policy| {
-                Arc::new(TrafficController::init(
+                Arc::new(TrafficController::init_with_state(
                     policy,
                     traffic_controller_metrics,
                     remote_fw_config,
+                    traffic_controller_state,
                 ))
             }),
             client_id_source: policy_config.map(|policy| policy.client_id_source),
@@ -212,9 +215,10 @@ async fn process_raw_request<L: Logger>(
         }
 
         // handle response tallying
+        let method = request.method.to_string();
         let response = process_request(request, api_version, service.call_data()).await;
         if let Some(traffic_controller) = &service.traffic_controller {
-            handle_traffic_resp(traffic_controller.clone(), client, &response);
+            handle_traffic_resp(traffic_controller.clone(), client, method, &response);
         }
 
         response
@@ -246,6 +250,7 @@ async fn handle_traffic_req(
 fn handle_traffic_resp(
     traffic_controller: Arc<TrafficController>,
     client: Option<IpAddr>,
+    method: String,
     response: &MethodResponse,
 ) {
     let error = response.error_code.map(ErrorCode::from);
@@ -264,7 +269,10 @@ fn handle_traffic_resp(
         // traffic and incentivize high volume clients to choose a
         // suitable rpc provider (or run their own). Later we may want
         // to provide a weight distribution based on the method being called.
+        // Policies which weigh methods differently (e.g. `TokenBucket`) do so
+        // based on `method` instead.
         spam_weight: Weight::one(),
+        method: Some(method),
         timestamp: SystemTime::now(),
     });
 }
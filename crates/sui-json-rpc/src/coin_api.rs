@@ -421,66 +421,28 @@ impl CoinReadInternal for CoinReadInternalImpl {
 mod tests {
     use super::*;
     use crate::authority_state::{MockStateRead, StateReadError};
+    use crate::test_utils::MockKeyValueStore;
     use expect_test::expect;
     use jsonrpsee::types::ErrorObjectOwned;
-    use mockall::mock;
     use mockall::predicate;
     use move_core_types::account_address::AccountAddress;
     use move_core_types::language_storage::StructTag;
     use sui_json_rpc_types::Coin;
-    use sui_storage::key_value_store::{
-        KVStoreCheckpointData, KVStoreTransactionData, TransactionKeyValueStoreTrait,
-    };
     use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
     use sui_types::balance::Supply;
     use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
     use sui_types::coin::TreasuryCap;
-    use sui_types::digests::{ObjectDigest, TransactionDigest, TransactionEventsDigest};
-    use sui_types::effects::{TransactionEffects, TransactionEvents};
-    use sui_types::error::{SuiError, SuiResult};
+    use sui_types::digests::{ObjectDigest, TransactionDigest};
+    use sui_types::effects::TransactionEffects;
+    use sui_types::error::SuiError;
     use sui_types::gas_coin::GAS;
     use sui_types::id::UID;
-    use sui_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber};
     use sui_types::object::MoveObject;
     use sui_types::object::Object;
     use sui_types::object::Owner;
     use sui_types::utils::create_fake_transaction;
     use sui_types::{parse_sui_struct_tag, TypeTag};
 
-    mock! {
-        pub KeyValueStore {}
-        #[async_trait]
-        impl TransactionKeyValueStoreTrait for KeyValueStore {
-            async fn multi_get(
-                &self,
-                transactions: &[TransactionDigest],
-                effects: &[TransactionDigest],
-                events: &[TransactionEventsDigest],
-            ) -> SuiResult<KVStoreTransactionData>;
-
-            async fn multi_get_checkpoints(
-                &self,
-                checkpoint_summaries: &[CheckpointSequenceNumber],
-                checkpoint_contents: &[CheckpointSequenceNumber],
-                checkpoint_summaries_by_digest: &[CheckpointDigest],
-            ) -> SuiResult<KVStoreCheckpointData>;
-
-            async fn deprecated_get_transaction_checkpoint(
-                &self,
-                digest: TransactionDigest,
-            ) -> SuiResult<Option<CheckpointSequenceNumber>>;
-
-            async fn get_object(&self, object_id: ObjectID, version: SequenceNumber) -> SuiResult<Option<Object>>;
-
-            async fn multi_get_transaction_checkpoint(
-                &self,
-                digests: &[TransactionDigest],
-            ) -> SuiResult<Vec<Option<CheckpointSequenceNumber>>>;
-
-            async fn multi_get_events_by_tx_digests(&self,digests: &[TransactionDigest]) -> SuiResult<Vec<Option<TransactionEvents>>>;
-        }
-    }
-
     impl CoinReadInternalImpl {
         pub fn new_for_tests(
             state: Arc<MockStateRead>,
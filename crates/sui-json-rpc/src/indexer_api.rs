@@ -207,7 +207,7 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             let opts = query.options.unwrap_or_default();
 
             // Retrieve 1 extra item for next cursor
-            let mut digests = self
+            let (mut digests, scan_cursor) = self
                 .state
                 .get_transactions(
                     &self.transaction_kv_store,
@@ -223,10 +223,11 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             let mut seen = HashSet::new();
             digests.retain(|digest| seen.insert(*digest));
 
-            // extract next cursor
-            let has_next_page = digests.len() > limit;
+            // extract next cursor, continuing a page that was cut short from where its scan
+            // stopped
+            let has_next_page = digests.len() > limit || scan_cursor.is_some();
             digests.truncate(limit);
-            let next_cursor = digests.last().cloned().map_or(cursor, Some);
+            let next_cursor = scan_cursor.or_else(|| digests.last().cloned().map_or(cursor, Some));
 
             let data: Vec<SuiTransactionBlockResponse> = if opts.only_digest() {
                 digests
@@ -266,7 +267,7 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             let limit = cap_page_limit(limit);
             self.metrics.query_events_limit.observe(limit as f64);
             // Retrieve 1 extra item for next cursor
-            let mut data = self
+            let (mut data, scan_cursor) = self
                 .state
                 .query_events(
                     &self.transaction_kv_store,
@@ -277,9 +278,9 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
                 )
                 .await
                 .map_err(Error::from)?;
-            let has_next_page = data.len() > limit;
+            let has_next_page = data.len() > limit || scan_cursor.is_some();
             data.truncate(limit);
-            let next_cursor = data.last().map_or(cursor, |e| Some(e.id));
+            let next_cursor = scan_cursor.or_else(|| data.last().map_or(cursor, |e| Some(e.id)));
             self.metrics
                 .query_events_result_size
                 .observe(data.len() as f64);
@@ -509,3 +510,87 @@ impl<R: ReadApiServer> SuiRpcModule for IndexerApi<R> {
         IndexerApiOpenRpc::module_doc()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority_state::MockStateRead;
+    use crate::read_api::ReadApi;
+    use crate::test_utils::MockKeyValueStore;
+    use sui_json_rpc_types::SuiEvent;
+    use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
+
+    fn indexer_api_for_tests(state: MockStateRead) -> IndexerApi<ReadApi> {
+        let state: Arc<dyn StateRead> = Arc::new(state);
+        let transaction_kv_store = Arc::new(TransactionKeyValueStore::new(
+            "rocksdb",
+            KeyValueStoreMetrics::new_for_tests(),
+            Arc::new(MockKeyValueStore::new()),
+        ));
+        let metrics = Arc::new(JsonRpcMetrics::new_for_tests());
+        IndexerApi {
+            state: state.clone(),
+            read_api: ReadApi {
+                state,
+                transaction_kv_store: transaction_kv_store.clone(),
+                metrics: metrics.clone(),
+            },
+            transaction_kv_store,
+            name_service_config: NameServiceConfig::default(),
+            metrics,
+            subscription_semaphore: Arc::new(Semaphore::new(DEFAULT_MAX_SUBSCRIPTIONS)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_transaction_blocks_continues_cut_short_page() {
+        let digests: Vec<_> = (0..3).map(|_| TransactionDigest::random()).collect();
+        let (retained, scanned) = (digests[0], digests[2]);
+
+        let mut state = MockStateRead::new();
+        state
+            .expect_get_transactions()
+            .withf(|_, _, cursor, limit, _| cursor.is_none() && *limit == Some(3))
+            .return_once(move |_, _, _, _, _| Ok((vec![retained], Some(scanned))));
+        let api = indexer_api_for_tests(state);
+
+        let page = api
+            .query_transaction_blocks(
+                SuiTransactionBlockResponseQuery::default(),
+                None,
+                Some(2),
+                None,
+            )
+            .await
+            .unwrap();
+        let data: Vec<_> = page.data.iter().map(|response| response.digest).collect();
+        assert_eq!(data, vec![retained]);
+        assert!(page.has_next_page);
+        assert_eq!(page.next_cursor, Some(scanned));
+    }
+
+    #[tokio::test]
+    async fn test_query_events_continues_cut_short_page() {
+        let event = SuiEvent::random_for_testing();
+        let scanned = EventID {
+            tx_digest: TransactionDigest::random(),
+            event_seq: 0,
+        };
+
+        let mut state = MockStateRead::new();
+        let events = vec![event.clone()];
+        state
+            .expect_query_events()
+            .withf(|_, _, cursor, limit, _| cursor.is_none() && *limit == 3)
+            .return_once(move |_, _, _, _, _| Ok((events, Some(scanned))));
+        let api = indexer_api_for_tests(state);
+
+        let page = api
+            .query_events(EventFilter::All([]), None, Some(2), None)
+            .await
+            .unwrap();
+        assert_eq!(page.data, vec![event]);
+        assert!(page.has_next_page);
+        assert_eq!(page.next_cursor, Some(scanned));
+    }
+}
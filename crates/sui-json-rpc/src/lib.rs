@@ -13,6 +13,7 @@ use hyper::Request;
 use jsonrpsee::RpcModule;
 use prometheus::Registry;
 use sui_core::traffic_controller::metrics::TrafficControllerMetrics;
+use sui_core::traffic_controller::TrafficControllerState;
 use sui_types::traffic_control::PolicyConfig;
 use sui_types::traffic_control::RemoteFirewallConfig;
 use tokio::runtime::Handle;
@@ -49,6 +50,8 @@ pub mod name_service;
 mod object_changes;
 pub mod read_api;
 mod routing_layer;
+#[cfg(test)]
+mod test_utils;
 pub mod transaction_builder_api;
 pub mod transaction_execution_api;
 
@@ -62,6 +65,7 @@ pub struct JsonRpcServerBuilder {
     registry: Registry,
     policy_config: Option<PolicyConfig>,
     firewall_config: Option<RemoteFirewallConfig>,
+    traffic_controller_state: TrafficControllerState,
 }
 
 pub fn sui_rpc_doc(version: &str) -> Project {
@@ -90,9 +94,16 @@ impl JsonRpcServerBuilder {
             registry: prometheus_registry.clone(),
             policy_config,
             firewall_config,
+            traffic_controller_state: TrafficControllerState::default(),
         }
     }
 
+    /// Publish the state of the traffic controller policies into `state`,
+    /// instead of a handle private to this server.
+    pub fn with_traffic_controller_state(&mut self, state: TrafficControllerState) {
+        self.traffic_controller_state = state;
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
@@ -199,6 +210,7 @@ impl JsonRpcServerBuilder {
             self.firewall_config.clone(),
             self.policy_config.clone(),
             traffic_controller_metrics,
+            self.traffic_controller_state.clone(),
         );
 
         let mut router = axum::Router::new();
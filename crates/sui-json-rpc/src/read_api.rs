@@ -1125,7 +1125,7 @@ async fn get_display_object_by_type(
     object_type: &StructTag,
     // TODO: add query version support
 ) -> Result<Option<DisplayVersionUpdatedEvent>, ObjectDisplayError> {
-    let mut events = fullnode_api
+    let (mut events, _) = fullnode_api
         .state
         .query_events(
             kv_store,
@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use mockall::mock;
+use sui_storage::key_value_store::{
+    KVStoreCheckpointData, KVStoreTransactionData, TransactionKeyValueStoreTrait,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::digests::{TransactionDigest, TransactionEventsDigest};
+use sui_types::effects::TransactionEvents;
+use sui_types::error::SuiResult;
+use sui_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber};
+use sui_types::object::Object;
+
+mock! {
+    pub KeyValueStore {}
+    #[async_trait]
+    impl TransactionKeyValueStoreTrait for KeyValueStore {
+        async fn multi_get(
+            &self,
+            transactions: &[TransactionDigest],
+            effects: &[TransactionDigest],
+            events: &[TransactionEventsDigest],
+        ) -> SuiResult<KVStoreTransactionData>;
+
+        async fn multi_get_checkpoints(
+            &self,
+            checkpoint_summaries: &[CheckpointSequenceNumber],
+            checkpoint_contents: &[CheckpointSequenceNumber],
+            checkpoint_summaries_by_digest: &[CheckpointDigest],
+        ) -> SuiResult<KVStoreCheckpointData>;
+
+        async fn deprecated_get_transaction_checkpoint(
+            &self,
+            digest: TransactionDigest,
+        ) -> SuiResult<Option<CheckpointSequenceNumber>>;
+
+        async fn get_object(&self, object_id: ObjectID, version: SequenceNumber) -> SuiResult<Option<Object>>;
+
+        async fn multi_get_transaction_checkpoint(
+            &self,
+            digests: &[TransactionDigest],
+        ) -> SuiResult<Vec<Option<CheckpointSequenceNumber>>>;
+
+        async fn multi_get_events_by_tx_digests(&self,digests: &[TransactionDigest]) -> SuiResult<Vec<Option<TransactionEvents>>>;
+    }
+}
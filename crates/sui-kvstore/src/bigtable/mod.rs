@@ -3,4 +3,3 @@
 
 pub(crate) mod client;
 mod proto;
-pub(crate) mod worker;
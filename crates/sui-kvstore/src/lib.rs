@@ -1,21 +1,36 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 mod bigtable;
+mod migration;
+mod progress_store;
+mod rocksdb;
+mod server;
+#[cfg(test)]
+mod test_utils;
+mod worker;
 use anyhow::Result;
 use async_trait::async_trait;
 pub use bigtable::client::BigTableClient;
-pub use bigtable::worker::KvWorker;
+pub use migration::copy_checkpoints;
+pub use progress_store::KvProgressStore;
+pub use rocksdb::client::RocksDbClient;
+use serde::{Deserialize, Serialize};
+pub use server::{make_router, run_server};
 use sui_types::base_types::ObjectID;
 use sui_types::crypto::AuthorityStrongQuorumSignInfo;
 use sui_types::digests::{CheckpointDigest, TransactionDigest};
 use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::{
-    CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
 };
 use sui_types::object::Object;
 use sui_types::storage::ObjectKey;
 use sui_types::transaction::Transaction;
+pub use worker::KvWorker;
+
+/// Name of the watermark tracking the latest checkpoint ingested into the store
+pub const AGGREGATED_WATERMARK_NAME: &str = "bigtable";
 
 #[async_trait]
 pub trait KeyValueStoreReader {
@@ -49,14 +64,22 @@ pub trait KeyValueStoreWriter {
     ) -> Result<()>;
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Checkpoint {
     pub summary: CheckpointSummary,
     pub contents: CheckpointContents,
     pub signatures: AuthorityStrongQuorumSignInfo,
 }
 
-#[derive(Clone, Debug)]
+impl Checkpoint {
+    pub fn into_parts(self) -> (CertifiedCheckpointSummary, CheckpointContents) {
+        let summary =
+            CertifiedCheckpointSummary::new_from_data_and_sig(self.summary, self.signatures);
+        (summary, self.contents)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TransactionData {
     pub transaction: Transaction,
     pub effects: TransactionEffects,
@@ -1,35 +1,184 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use anyhow::Result;
-use sui_data_ingestion_core::setup_single_workflow;
-use sui_kvstore::BigTableClient;
-use sui_kvstore::KvWorker;
+use clap::{Parser, Subcommand};
+use prometheus::Registry;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use sui_data_ingestion_core::{
+    setup_single_workflow, DataIngestionMetrics, IndexerExecutor, ReaderOptions, WorkerPool,
+};
+use sui_kvstore::{
+    copy_checkpoints, run_server, BigTableClient, KeyValueStoreReader, KeyValueStoreWriter,
+    KvProgressStore, KvWorker, RocksDbClient, AGGREGATED_WATERMARK_NAME,
+};
 use telemetry_subscribers::TelemetryConfig;
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let _guard = TelemetryConfig::new().with_env().init();
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() < 3 {
-        eprintln!("Please provide BigTable instance id and network name");
-        std::process::exit(1);
+#[derive(Parser)]
+#[command(name = "sui-kvstore")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Ingest checkpoints of a network into Bigtable
+    Bigtable {
+        instance_id: String,
+        network: String,
+    },
+    /// Ingest checkpoints of a network into a local RocksDB store, optionally serving it over HTTP
+    Rocksdb {
+        path: PathBuf,
+        network: String,
+        #[arg(long, default_value_t = 1)]
+        concurrency: usize,
+        /// Address to serve the store on, in the format read by the full node's http kv store
+        #[arg(long)]
+        http_address: Option<SocketAddr>,
+    },
+    /// Serve a local RocksDB store over HTTP, without ingesting
+    Serve {
+        path: PathBuf,
+        #[arg(long)]
+        http_address: SocketAddr,
+    },
+    /// Copy a range of checkpoints between stores
+    Copy {
+        /// Store to copy from, `bigtable:<instance id>` or `rocksdb:<path>`
+        #[arg(long)]
+        from: Backend,
+        /// Store to copy to, `bigtable:<instance id>` or `rocksdb:<path>`
+        #[arg(long)]
+        to: Backend,
+        #[arg(long)]
+        start: u64,
+        #[arg(long)]
+        end: u64,
+    },
+}
+
+#[derive(Clone, Debug)]
+enum Backend {
+    Bigtable(String),
+    Rocksdb(PathBuf),
+}
+
+impl std::str::FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            Some(("bigtable", instance_id)) => Ok(Self::Bigtable(instance_id.to_string())),
+            Some(("rocksdb", path)) => Ok(Self::Rocksdb(path.into())),
+            _ => Err(anyhow::anyhow!(
+                "expected `bigtable:<instance id>` or `rocksdb:<path>`, got {}",
+                s
+            )),
+        }
+    }
+}
+
+enum Client {
+    Bigtable(BigTableClient),
+    Rocksdb(RocksDbClient),
+}
+
+impl Client {
+    async fn new(backend: Backend, is_read_only: bool) -> Result<Self> {
+        Ok(match backend {
+            Backend::Bigtable(instance_id) => {
+                Self::Bigtable(BigTableClient::new_remote(instance_id, is_read_only, None).await?)
+            }
+            Backend::Rocksdb(path) => Self::Rocksdb(RocksDbClient::new(&path)),
+        })
+    }
+
+    fn reader(&mut self) -> &mut (dyn KeyValueStoreReader + Send) {
+        match self {
+            Self::Bigtable(client) => client,
+            Self::Rocksdb(client) => client,
+        }
     }
-    let instance_id = args[1].to_string();
-    let network = args[2].to_string();
+
+    fn writer(&mut self) -> &mut (dyn KeyValueStoreWriter + Send) {
+        match self {
+            Self::Bigtable(client) => client,
+            Self::Rocksdb(client) => client,
+        }
+    }
+}
+
+fn checkpoints_url(network: &str) -> String {
     assert!(
         network == "mainnet" || network == "testnet",
         "Invalid network name"
     );
+    format!("https://checkpoints.{}.sui.io", network)
+}
 
-    let client = BigTableClient::new_remote(instance_id, false, None).await?;
-    let (executor, _term_sender) = setup_single_workflow(
-        KvWorker { client },
-        format!("https://checkpoints.{}.sui.io", network),
-        0,
-        1,
-        None,
-    )
-    .await?;
-    executor.await?;
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _guard = TelemetryConfig::new().with_env().init();
+    match Args::parse().command {
+        Command::Bigtable {
+            instance_id,
+            network,
+        } => {
+            let client = BigTableClient::new_remote(instance_id, false, None).await?;
+            let (executor, _term_sender) =
+                setup_single_workflow(KvWorker { client }, checkpoints_url(&network), 0, 1, None)
+                    .await?;
+            executor.await?;
+        }
+        Command::Rocksdb {
+            path,
+            network,
+            concurrency,
+            http_address,
+        } => {
+            let client = RocksDbClient::new(&path);
+            if let Some(http_address) = http_address {
+                tokio::spawn(run_server(http_address, client.clone()));
+            }
+            let metrics = DataIngestionMetrics::new(&Registry::new());
+            let mut executor =
+                IndexerExecutor::new(KvProgressStore::new(client.clone()), 1, metrics);
+            executor
+                .register(WorkerPool::new(
+                    KvWorker { client },
+                    AGGREGATED_WATERMARK_NAME.to_string(),
+                    concurrency,
+                ))
+                .await?;
+            // Checkpoints are fetched remotely, the reader only polls this directory for local files
+            let local_checkpoints_path = std::env::temp_dir().join("sui-kvstore-checkpoints");
+            std::fs::create_dir_all(&local_checkpoints_path)?;
+            let (_exit_sender, exit_receiver) = tokio::sync::oneshot::channel();
+            executor
+                .run(
+                    local_checkpoints_path,
+                    Some(checkpoints_url(&network)),
+                    vec![],
+                    ReaderOptions::default(),
+                    exit_receiver,
+                )
+                .await?;
+        }
+        Command::Serve { path, http_address } => {
+            run_server(http_address, RocksDbClient::new(&path)).await?;
+        }
+        Command::Copy {
+            from,
+            to,
+            start,
+            end,
+        } => {
+            let mut source = Client::new(from, true).await?;
+            let mut target = Client::new(to, false).await?;
+            copy_checkpoints(source.reader(), target.writer(), start..=end).await?;
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,142 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{KeyValueStoreReader, KeyValueStoreWriter, AGGREGATED_WATERMARK_NAME};
+use anyhow::{anyhow, Result};
+use std::ops::RangeInclusive;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::storage::ObjectKey;
+use tracing::info;
+
+/// Copy the checkpoints in `range`, with their transactions and output objects, from one key value
+/// store to another, e.g. to export a local RocksDB store to Bigtable or the other way around.
+/// Ranges are expected to be copied in increasing order: the latest checkpoint watermark of the
+/// target is moved to the end of the range.
+pub async fn copy_checkpoints(
+    source: &mut (dyn KeyValueStoreReader + Send),
+    target: &mut (dyn KeyValueStoreWriter + Send),
+    range: RangeInclusive<CheckpointSequenceNumber>,
+) -> Result<()> {
+    for sequence_number in range.clone() {
+        let checkpoint = source
+            .get_checkpoints(&[sequence_number])
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("checkpoint {} is missing", sequence_number))?;
+        let digests: Vec<_> = checkpoint
+            .contents
+            .iter()
+            .map(|digests| digests.transaction)
+            .collect();
+        let transactions = source.get_transactions(&digests).await?;
+        if transactions.len() != digests.len() {
+            return Err(anyhow!(
+                "transactions of checkpoint {} are missing",
+                sequence_number
+            ));
+        }
+        let object_keys: Vec<_> = transactions
+            .iter()
+            .flat_map(|transaction| transaction.effects.all_changed_objects())
+            .map(|((id, version, _), _, _)| ObjectKey(id, version))
+            .collect();
+        let objects = source.get_objects(&object_keys).await?;
+        if objects.len() != object_keys.len() {
+            return Err(anyhow!(
+                "objects of checkpoint {} are missing",
+                sequence_number
+            ));
+        }
+
+        target
+            .save_objects(&objects.iter().collect::<Vec<_>>())
+            .await?;
+        target.save_transactions(&transactions).await?;
+        let (checkpoint_summary, checkpoint_contents) = checkpoint.into_parts();
+        // Transactions were saved above, only the summary and contents are left to save
+        target
+            .save_checkpoint(&CheckpointData {
+                checkpoint_summary,
+                checkpoint_contents,
+                transactions: vec![],
+            })
+            .await?;
+        if sequence_number % 1000 == 0 {
+            info!("Copied checkpoint {}", sequence_number);
+        }
+    }
+    target
+        .save_watermark(AGGREGATED_WATERMARK_NAME, *range.end() + 1)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_checkpoint;
+    use crate::{KvWorker, RocksDbClient};
+    use sui_data_ingestion_core::Worker;
+
+    #[tokio::test]
+    async fn test_copy_checkpoints() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let target_dir = tempfile::tempdir().unwrap();
+        let mut source = RocksDbClient::new(source_dir.path());
+        let mut target = RocksDbClient::new(target_dir.path());
+
+        let worker = KvWorker {
+            client: source.clone(),
+        };
+        let checkpoints: Vec<_> = (0..3).map(test_checkpoint).collect();
+        for checkpoint in &checkpoints {
+            worker.process_checkpoint(checkpoint).await.unwrap();
+        }
+
+        copy_checkpoints(&mut source, &mut target, 1..=2)
+            .await
+            .unwrap();
+        assert_eq!(target.get_latest_checkpoint().await.unwrap(), 3);
+        assert!(target.get_checkpoints(&[0]).await.unwrap().is_empty());
+
+        for (sequence_number, checkpoint) in checkpoints.iter().enumerate().skip(1) {
+            let sequence_number = sequence_number as CheckpointSequenceNumber;
+            let (summary, contents) = target
+                .get_checkpoints(&[sequence_number])
+                .await
+                .unwrap()
+                .pop()
+                .unwrap()
+                .into_parts();
+            assert_eq!(summary.digest(), checkpoint.checkpoint_summary.digest());
+            assert_eq!(contents, checkpoint.checkpoint_contents);
+
+            let expected = &checkpoint.transactions[0];
+            let transaction = target
+                .get_transactions(&[*expected.transaction.digest()])
+                .await
+                .unwrap()
+                .pop()
+                .unwrap();
+            assert_eq!(transaction.effects, expected.effects);
+            assert_eq!(transaction.checkpoint_number, sequence_number);
+
+            let object_keys: Vec<_> = expected
+                .output_objects
+                .iter()
+                .map(|object| ObjectKey(object.id(), object.version()))
+                .collect();
+            assert_eq!(
+                target.get_objects(&object_keys).await.unwrap(),
+                expected.output_objects
+            );
+        }
+
+        // Checkpoints missing from the source fail the copy instead of leaving gaps in the target
+        let error = copy_checkpoints(&mut source, &mut target, 3..=3)
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "checkpoint 3 is missing");
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{KeyValueStoreReader, KeyValueStoreWriter};
+use anyhow::Result;
+use async_trait::async_trait;
+use sui_data_ingestion_core::ProgressStore;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+/// Keeps the ingestion progress in the watermark table of the key value store itself, so that a
+/// standalone store does not need an external progress store.
+pub struct KvProgressStore<C> {
+    client: C,
+}
+
+impl<C> KvProgressStore<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C> ProgressStore for KvProgressStore<C>
+where
+    C: KeyValueStoreReader + KeyValueStoreWriter + Send + Sync,
+{
+    async fn load(&mut self, task_name: String) -> Result<CheckpointSequenceNumber> {
+        self.client.get_watermark(&task_name).await
+    }
+
+    async fn save(
+        &mut self,
+        task_name: String,
+        checkpoint_number: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        self.client
+            .save_watermark(&task_name, checkpoint_number)
+            .await
+    }
+}
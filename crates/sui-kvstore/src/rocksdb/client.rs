@@ -0,0 +1,219 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    Checkpoint, KeyValueStoreReader, KeyValueStoreWriter, TransactionData,
+    AGGREGATED_WATERMARK_NAME,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Arc;
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::digests::CheckpointDigest;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use sui_types::storage::ObjectKey;
+use typed_store::rocks::{DBMap, MetricConf};
+use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::{DBMapUtils, Map};
+
+/// Tables mirroring the Bigtable layout: one column family per Bigtable table, keyed by the same
+/// row keys.
+#[derive(DBMapUtils)]
+pub struct KvTables {
+    objects: DBMap<ObjectKey, Object>,
+    transactions: DBMap<TransactionDigest, TransactionData>,
+    checkpoints: DBMap<CheckpointSequenceNumber, Checkpoint>,
+    checkpoints_by_digest: DBMap<CheckpointDigest, CheckpointSequenceNumber>,
+    watermark: DBMap<String, CheckpointSequenceNumber>,
+}
+
+/// Key value store backed by a local RocksDB instance, for deployments without Bigtable.
+#[derive(Clone)]
+pub struct RocksDbClient {
+    tables: Arc<KvTables>,
+}
+
+impl RocksDbClient {
+    pub fn new(path: &Path) -> Self {
+        let tables = KvTables::open_tables_read_write(
+            path.to_path_buf(),
+            MetricConf::new("kvstore"),
+            None,
+            None,
+        );
+        Self {
+            tables: Arc::new(tables),
+        }
+    }
+}
+
+#[async_trait]
+impl KeyValueStoreWriter for RocksDbClient {
+    async fn save_objects(&mut self, objects: &[&Object]) -> Result<()> {
+        let mut batch = self.tables.objects.batch();
+        batch.insert_batch(
+            &self.tables.objects,
+            objects
+                .iter()
+                .map(|object| (ObjectKey(object.id(), object.version()), *object)),
+        )?;
+        Ok(batch.write()?)
+    }
+
+    async fn save_transactions(&mut self, transactions: &[TransactionData]) -> Result<()> {
+        let mut batch = self.tables.transactions.batch();
+        batch.insert_batch(
+            &self.tables.transactions,
+            transactions
+                .iter()
+                .map(|transaction| (*transaction.transaction.digest(), transaction)),
+        )?;
+        Ok(batch.write()?)
+    }
+
+    async fn save_checkpoint(&mut self, checkpoint: &CheckpointData) -> Result<()> {
+        let summary = checkpoint.checkpoint_summary.data();
+        let sequence_number = summary.sequence_number;
+        let row = Checkpoint {
+            summary: summary.clone(),
+            contents: checkpoint.checkpoint_contents.clone(),
+            signatures: checkpoint.checkpoint_summary.auth_sig().clone(),
+        };
+        let mut batch = self.tables.checkpoints.batch();
+        batch.insert_batch(&self.tables.checkpoints, [(sequence_number, row)])?;
+        batch.insert_batch(
+            &self.tables.checkpoints_by_digest,
+            [(*checkpoint.checkpoint_summary.digest(), sequence_number)],
+        )?;
+        Ok(batch.write()?)
+    }
+
+    async fn save_watermark(
+        &mut self,
+        name: &str,
+        watermark: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        Ok(self
+            .tables
+            .watermark
+            .insert(&name.to_string(), &watermark)?)
+    }
+}
+
+#[async_trait]
+impl KeyValueStoreReader for RocksDbClient {
+    async fn get_objects(&mut self, objects: &[ObjectKey]) -> Result<Vec<Object>> {
+        Ok(self
+            .tables
+            .objects
+            .multi_get(objects)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    async fn get_transactions(
+        &mut self,
+        transactions: &[TransactionDigest],
+    ) -> Result<Vec<TransactionData>> {
+        Ok(self
+            .tables
+            .transactions
+            .multi_get(transactions)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    async fn get_checkpoints(
+        &mut self,
+        sequence_numbers: &[CheckpointSequenceNumber],
+    ) -> Result<Vec<Checkpoint>> {
+        Ok(self
+            .tables
+            .checkpoints
+            .multi_get(sequence_numbers)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    async fn get_checkpoint_by_digest(
+        &mut self,
+        digest: CheckpointDigest,
+    ) -> Result<Option<Checkpoint>> {
+        match self.tables.checkpoints_by_digest.get(&digest)? {
+            Some(sequence_number) => Ok(self.tables.checkpoints.get(&sequence_number)?),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_latest_checkpoint(&mut self) -> Result<CheckpointSequenceNumber> {
+        self.get_watermark(AGGREGATED_WATERMARK_NAME).await
+    }
+
+    async fn get_latest_object(&mut self, object_id: &ObjectID) -> Result<Option<Object>> {
+        let mut iterator = self
+            .tables
+            .objects
+            .unbounded_iter()
+            .skip_prior_to(&ObjectKey::max_for_id(object_id))?;
+        if let Some((object_key, object)) = iterator.next() {
+            if object_key.0 == *object_id {
+                return Ok(Some(object));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn get_watermark(&mut self, watermark_name: &str) -> Result<CheckpointSequenceNumber> {
+        Ok(self
+            .tables
+            .watermark
+            .get(&watermark_name.to_string())?
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{SequenceNumber, SuiAddress};
+
+    #[tokio::test]
+    async fn test_objects_and_watermarks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut client = RocksDbClient::new(temp_dir.path());
+
+        let id = ObjectID::random();
+        let owner = SuiAddress::random_for_testing_only();
+        let v1 = Object::with_id_owner_version_for_testing(id, SequenceNumber::from_u64(1), owner);
+        let v2 = Object::with_id_owner_version_for_testing(id, SequenceNumber::from_u64(2), owner);
+        let other = Object::immutable_with_id_for_testing(ObjectID::random());
+        client.save_objects(&[&v1, &v2, &other]).await.unwrap();
+
+        let objects = client
+            .get_objects(&[
+                ObjectKey(id, v1.version()),
+                ObjectKey(id, SequenceNumber::from_u64(42)),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(objects, vec![v1]);
+        assert_eq!(client.get_latest_object(&id).await.unwrap(), Some(v2));
+        assert_eq!(
+            client.get_latest_object(&ObjectID::random()).await.unwrap(),
+            None
+        );
+
+        assert_eq!(client.get_latest_checkpoint().await.unwrap(), 0);
+        client
+            .save_watermark(AGGREGATED_WATERMARK_NAME, 10)
+            .await
+            .unwrap();
+        assert_eq!(client.get_latest_checkpoint().await.unwrap(), 10);
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serves the key value store over the URL scheme `HttpKVStore` reads from
+//! (`/<base64 key>/<item type>`, bcs encoded values), so that full nodes can fall back to a store
+//! populated by the kv worker.
+
+use crate::KeyValueStoreReader;
+use anyhow::Result;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+use std::net::SocketAddr;
+use sui_storage::http_key_value_store::{path_elements_to_key, Key};
+use sui_types::storage::ObjectKey;
+use tracing::{error, info};
+
+pub fn make_router<C>(client: C) -> Router
+where
+    C: KeyValueStoreReader + Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/:key/:item_type", get(handle_get::<C>))
+        .with_state(client)
+}
+
+pub async fn run_server<C>(address: SocketAddr, client: C) -> Result<()>
+where
+    C: KeyValueStoreReader + Clone + Send + Sync + 'static,
+{
+    info!("Serving key value store on {}", address);
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    axum::serve(listener, make_router(client)).await?;
+    Ok(())
+}
+
+async fn handle_get<C>(
+    State(client): State<C>,
+    Path((key, item_type)): Path<(String, String)>,
+) -> Response
+where
+    C: KeyValueStoreReader + Clone + Send + Sync + 'static,
+{
+    let key = match path_elements_to_key(&key, &item_type) {
+        Ok(key) => key,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    match get_item(client, key).await {
+        Ok(Some(bytes)) => bytes.into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            error!("Failed to read {:?} from the key value store: {:?}", key, e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn get_item<C: KeyValueStoreReader>(mut client: C, key: Key) -> Result<Option<Vec<u8>>> {
+    fn encode<T: Serialize>(value: Option<T>) -> Result<Option<Vec<u8>>> {
+        Ok(value.map(|value| bcs::to_bytes(&value)).transpose()?)
+    }
+
+    match key {
+        Key::Tx(digest) => {
+            let transaction = client.get_transactions(&[digest]).await?.pop();
+            encode(transaction.map(|transaction| transaction.transaction))
+        }
+        Key::Fx(digest) => {
+            let transaction = client.get_transactions(&[digest]).await?.pop();
+            encode(transaction.map(|transaction| transaction.effects))
+        }
+        Key::TxToCheckpoint(digest) => {
+            let transaction = client.get_transactions(&[digest]).await?.pop();
+            encode(transaction.map(|transaction| transaction.checkpoint_number))
+        }
+        // Served as an option, like the other stores `HttpKVStore` reads from
+        Key::EventsByTxDigest(digest) => {
+            let transaction = client.get_transactions(&[digest]).await?.pop();
+            encode(transaction.and_then(|transaction| transaction.events.map(Some)))
+        }
+        Key::CheckpointSummary(sequence_number) => {
+            let checkpoint = client.get_checkpoints(&[sequence_number]).await?.pop();
+            encode(checkpoint.map(|checkpoint| checkpoint.into_parts().0))
+        }
+        Key::CheckpointContents(sequence_number) => {
+            let checkpoint = client.get_checkpoints(&[sequence_number]).await?.pop();
+            encode(checkpoint.map(|checkpoint| checkpoint.contents))
+        }
+        Key::CheckpointSummaryByDigest(digest) => {
+            let checkpoint = client.get_checkpoint_by_digest(digest).await?;
+            encode(checkpoint.map(|checkpoint| checkpoint.into_parts().0))
+        }
+        Key::ObjectKey(object_id, version) => {
+            let object = client
+                .get_objects(&[ObjectKey(object_id, version)])
+                .await?
+                .pop();
+            encode(object)
+        }
+        // The store is not indexed by events or contents digest
+        Key::Events(_) | Key::CheckpointContentsByDigest(_) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_checkpoint;
+    use crate::{KvWorker, RocksDbClient};
+    use sui_data_ingestion_core::Worker;
+    use sui_storage::http_key_value_store::HttpKVStore;
+    use sui_storage::key_value_store::TransactionKeyValueStoreTrait;
+    use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
+    use sui_types::digests::TransactionDigest;
+
+    #[tokio::test]
+    async fn test_http_read_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let client = RocksDbClient::new(temp_dir.path());
+        let checkpoint = test_checkpoint(5);
+        KvWorker {
+            client: client.clone(),
+        }
+        .process_checkpoint(&checkpoint)
+        .await
+        .unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, make_router(client)).await });
+        let store = HttpKVStore::new(
+            &format!("http://{}", address),
+            100,
+            KeyValueStoreMetrics::new_for_tests(),
+        )
+        .unwrap();
+
+        let expected = &checkpoint.transactions[0];
+        let digest = *expected.transaction.digest();
+        let missing = TransactionDigest::random();
+        let (transactions, effects, _) = store
+            .multi_get(&[digest, missing], &[digest], &[])
+            .await
+            .unwrap();
+        assert_eq!(transactions, vec![Some(expected.transaction.clone()), None]);
+        assert_eq!(effects, vec![Some(expected.effects.clone())]);
+        assert_eq!(
+            store
+                .multi_get_transaction_checkpoint(&[digest, missing])
+                .await
+                .unwrap(),
+            vec![Some(5), None]
+        );
+        assert_eq!(
+            store
+                .multi_get_events_by_tx_digests(&[digest, missing])
+                .await
+                .unwrap(),
+            vec![expected.events.clone(), None]
+        );
+
+        let summary_digest = *checkpoint.checkpoint_summary.digest();
+        let (summaries, contents, summaries_by_digest) = store
+            .multi_get_checkpoints(&[5, 6], &[5], &[summary_digest])
+            .await
+            .unwrap();
+        assert_eq!(
+            summaries,
+            vec![Some(checkpoint.checkpoint_summary.clone()), None]
+        );
+        assert_eq!(contents, vec![Some(checkpoint.checkpoint_contents.clone())]);
+        assert_eq!(
+            summaries_by_digest,
+            vec![Some(checkpoint.checkpoint_summary.clone())]
+        );
+
+        let object = &expected.output_objects[0];
+        assert_eq!(
+            store
+                .get_object(object.id(), object.version())
+                .await
+                .unwrap(),
+            Some(object.clone())
+        );
+    }
+}
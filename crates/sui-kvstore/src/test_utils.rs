@@ -0,0 +1,78 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_protocol_config::ProtocolConfig;
+use sui_types::base_types::{random_object_ref, ExecutionDigests};
+use sui_types::committee::Committee;
+use sui_types::crypto::{get_key_pair, AccountKeyPair, KeypairTraits};
+use sui_types::effects::{TestEffectsBuilder, TransactionEvents};
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::gas::GasCostSummary;
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
+    SignedCheckpointSummary,
+};
+use sui_types::object::Object;
+use sui_types::transaction::{Transaction, TransactionData};
+
+/// A certified checkpoint holding a single transfer, with the objects it outputs.
+pub(crate) fn test_checkpoint(sequence_number: CheckpointSequenceNumber) -> CheckpointData {
+    let (sender, key): (_, AccountKeyPair) = get_key_pair();
+    let transaction = Transaction::from_data_and_signer(
+        TransactionData::new_transfer(
+            sender,
+            random_object_ref(),
+            sender,
+            random_object_ref(),
+            100_000_000_000,
+            100,
+        ),
+        vec![&key],
+    );
+    let effects = TestEffectsBuilder::new(transaction.data()).build();
+    let output_objects = effects
+        .all_changed_objects()
+        .into_iter()
+        .map(|((id, version, _), _, _)| {
+            Object::with_id_owner_version_for_testing(id, version, sender)
+        })
+        .collect();
+
+    let contents = CheckpointContents::new_with_digests_only_for_tests([ExecutionDigests::new(
+        *transaction.digest(),
+        effects.digest(),
+    )]);
+    let summary = CheckpointSummary::new(
+        &ProtocolConfig::get_for_max_version_UNSAFE(),
+        0,
+        sequence_number,
+        sequence_number + 1,
+        &contents,
+        None,
+        GasCostSummary::default(),
+        None,
+        sequence_number * 1000,
+        vec![],
+    );
+    let (committee, keys) = Committee::new_simple_test_committee();
+    let signatures = keys
+        .iter()
+        .map(|key| {
+            SignedCheckpointSummary::sign(committee.epoch, &summary, key, key.public().into())
+        })
+        .collect();
+
+    CheckpointData {
+        checkpoint_summary: CertifiedCheckpointSummary::new(summary, signatures, &committee)
+            .unwrap(),
+        checkpoint_contents: contents,
+        transactions: vec![CheckpointTransaction {
+            transaction,
+            effects,
+            events: Some(TransactionEvents::default()),
+            input_objects: vec![],
+            output_objects,
+        }],
+    }
+}
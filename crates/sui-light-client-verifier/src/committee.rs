@@ -0,0 +1,114 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::{Error, Result};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use fastcrypto::bls12381::min_sig::{BLS12381AggregateSignature, BLS12381PublicKey};
+use fastcrypto::traits::{AggregateAuthenticator, ToFromBytes};
+use sui_sdk_types::types::{
+    CheckpointContents, CheckpointSummary, SignedCheckpointSummary, ValidatorCommittee,
+    ValidatorCommitteeMember,
+};
+
+/// Stake needed to certify a checkpoint, out of a total stake of 10,000 (2f + 1).
+pub const QUORUM_THRESHOLD: u64 = 6_667;
+
+/// Intent (scope, version, app id) that validators sign checkpoint summaries with.
+const CHECKPOINT_SUMMARY_INTENT: [u8; 3] = [2, 0, 0];
+
+/// The message validators sign to certify a checkpoint summary: the intent message of the summary
+/// followed by the epoch.
+pub fn checkpoint_signing_message(summary: &CheckpointSummary) -> Vec<u8> {
+    let mut message = CHECKPOINT_SUMMARY_INTENT.to_vec();
+    message.extend(bcs::to_bytes(summary).expect("Message serialization should not fail"));
+    message.extend(bcs::to_bytes(&summary.epoch).expect("Message serialization should not fail"));
+    message
+}
+
+/// Committee members in the order signers are indexed by in the signature bitmap.
+fn sorted_members(committee: &ValidatorCommittee) -> Vec<&ValidatorCommitteeMember> {
+    let mut members: Vec<_> = committee.members.iter().collect();
+    members.sort_by(|a, b| a.public_key.as_bytes().cmp(b.public_key.as_bytes()));
+    members
+}
+
+/// Verify that a checkpoint summary is certified by a quorum of `committee`.
+pub fn verify_checkpoint_summary(
+    committee: &ValidatorCommittee,
+    checkpoint: &SignedCheckpointSummary,
+) -> Result<()> {
+    let summary = &checkpoint.checkpoint;
+    let signature = &checkpoint.signature;
+    for epoch in [summary.epoch, signature.epoch] {
+        if epoch != committee.epoch {
+            return Err(Error::WrongEpoch {
+                expected: committee.epoch,
+                actual: epoch,
+            });
+        }
+    }
+
+    let members = sorted_members(committee);
+    let mut stake = 0;
+    let mut public_keys = Vec::new();
+    for index in signature.bitmap.iter() {
+        let member = members
+            .get(index as usize)
+            .ok_or(Error::UnknownSigner(index))?;
+        stake += member.stake;
+        public_keys.push(
+            BLS12381PublicKey::from_bytes(member.public_key.as_bytes())
+                .map_err(|e| Error::InvalidSignature(e.to_string()))?,
+        );
+    }
+    if stake < QUORUM_THRESHOLD {
+        return Err(Error::InsufficientStake {
+            stake,
+            threshold: QUORUM_THRESHOLD,
+        });
+    }
+
+    BLS12381AggregateSignature::from_bytes(signature.signature.as_bytes())
+        .and_then(|aggregate| aggregate.verify(&public_keys, &checkpoint_signing_message(summary)))
+        .map_err(|e| Error::InvalidSignature(e.to_string()))
+}
+
+/// Verify that `contents` are the contents of the checkpoint `summary`.
+pub fn verify_checkpoint_contents(
+    summary: &CheckpointSummary,
+    contents: &CheckpointContents,
+) -> Result<()> {
+    if contents.digest() != summary.content_digest {
+        return Err(Error::ContentsDigestMismatch);
+    }
+    Ok(())
+}
+
+/// The committee of the next epoch, if `summary` is the last checkpoint of its epoch. Members are
+/// sorted by public key, so that committees can be compared for equality.
+pub fn next_epoch_committee(summary: &CheckpointSummary) -> Option<ValidatorCommittee> {
+    let end_of_epoch_data = summary.end_of_epoch_data.as_ref()?;
+    Some(normalize_committee(ValidatorCommittee {
+        epoch: summary.epoch.checked_add(1)?,
+        members: end_of_epoch_data.next_epoch_committee.clone(),
+    }))
+}
+
+/// Sort the members of `committee` by public key.
+pub fn normalize_committee(mut committee: ValidatorCommittee) -> ValidatorCommittee {
+    committee
+        .members
+        .sort_by(|a, b| a.public_key.as_bytes().cmp(b.public_key.as_bytes()));
+    committee
+}
+
+/// Verify the last checkpoint of the epoch of `committee`, and return the committee of the next
+/// epoch it certifies.
+pub fn verify_committee_transition(
+    committee: &ValidatorCommittee,
+    end_of_epoch_checkpoint: &SignedCheckpointSummary,
+) -> Result<ValidatorCommittee> {
+    verify_checkpoint_summary(committee, end_of_epoch_checkpoint)?;
+    next_epoch_committee(&end_of_epoch_checkpoint.checkpoint).ok_or(Error::NotEndOfEpoch)
+}
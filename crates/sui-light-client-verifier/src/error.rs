@@ -0,0 +1,70 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use sui_sdk_types::types::EpochId;
+
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+// `Display` and `Error` are implemented by hand rather than derived with `thiserror`, which
+// requires `std`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    WrongEpoch { expected: EpochId, actual: EpochId },
+
+    UnknownSigner(u32),
+
+    InsufficientStake { stake: u64, threshold: u64 },
+
+    InvalidSignature(String),
+
+    ContentsDigestMismatch,
+
+    NotEndOfEpoch,
+
+    InvalidProof(String),
+
+    MissingCommittee(EpochId),
+
+    EmptyCommitteeChain,
+
+    Store(String),
+}
+
+impl Error {
+    pub(crate) fn invalid_proof(reason: &str) -> Self {
+        Self::InvalidProof(reason.to_string())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::WrongEpoch { expected, actual } => {
+                write!(f, "Expected epoch {expected}, got {actual}")
+            }
+            Error::UnknownSigner(index) => {
+                write!(f, "Signer index {index} is not part of the committee")
+            }
+            Error::InsufficientStake { stake, threshold } => write!(
+                f,
+                "Signers hold {stake} stake, below the quorum threshold of {threshold}"
+            ),
+            Error::InvalidSignature(e) => write!(f, "Invalid checkpoint signature: {e}"),
+            Error::ContentsDigestMismatch => write!(
+                f,
+                "Checkpoint contents digest does not match the checkpoint summary"
+            ),
+            Error::NotEndOfEpoch => {
+                write!(f, "No end of epoch committee in the checkpoint summary")
+            }
+            Error::InvalidProof(reason) => write!(f, "Invalid proof: {reason}"),
+            Error::MissingCommittee(epoch) => write!(f, "No trusted committee for epoch {epoch}"),
+            Error::EmptyCommitteeChain => write!(f, "The committee chain has no trusted committee"),
+            Error::Store(e) => write!(f, "Committee store error: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
@@ -0,0 +1,35 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of Sui light client proofs, free of I/O and async runtimes so that it can be
+//! embedded in browser wallets (it builds for `wasm32-unknown-unknown`) or in other chains'
+//! contracts.
+//!
+//! A light client trusts a committee out of band (typically the genesis committee), and then
+//! follows the chain of committees by verifying the end of epoch checkpoint of every epoch, see
+//! [`TrustedCommitteeChain`]. Proofs about objects, events or the next committee are then
+//! verified against the committee of the epoch they were certified in, see [`verify_proof`].
+//!
+//! The crate itself is `no_std` and only needs `alloc`. Its signature checks rely on `fastcrypto`
+//! and `bcs`, which still link `std`, so targets must provide it; `wasm32-unknown-unknown` does.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod committee;
+pub mod error;
+pub mod proof;
+pub mod store;
+
+#[doc(inline)]
+pub use committee::*;
+
+#[doc(inline)]
+pub use error::{Error, Result};
+
+#[doc(inline)]
+pub use proof::*;
+
+#[doc(inline)]
+pub use store::*;
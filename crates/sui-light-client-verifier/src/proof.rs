@@ -0,0 +1,252 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::committee::{
+    next_epoch_committee, normalize_committee, verify_checkpoint_contents,
+    verify_checkpoint_summary,
+};
+use crate::error::{Error, Result};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use sui_sdk_types::types::{
+    CheckpointContents, Event, Object, ObjectOut, ObjectReference, SignedCheckpointSummary,
+    SignedTransaction, TransactionDigest, TransactionEffects, TransactionEvents,
+    TransactionEventsDigest, ValidatorCommittee,
+};
+
+/// Identifies an event by the transaction that emitted it and its index in the transaction's
+/// events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventId {
+    pub tx_digest: TransactionDigest,
+    pub event_seq: u64,
+}
+
+/// Define aspect of Sui state that need to be certified in a proof
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct ProofTarget {
+    /// Objects that need to be certified.
+    pub objects: Vec<(ObjectReference, Object)>,
+
+    /// Events that need to be certified.
+    pub events: Vec<(EventId, Event)>,
+
+    /// The next committee being certified.
+    pub committee: Option<ValidatorCommittee>,
+}
+
+impl ProofTarget {
+    /// Create a new empty proof target. An empty proof target still ensures that the
+    /// checkpoint summary is correct.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an object to be certified by object reference and content. A verified proof will
+    /// ensure that both the reference and content are correct.
+    pub fn add_object(mut self, object_ref: ObjectReference, object: Object) -> Self {
+        self.objects.push((object_ref, object));
+        self
+    }
+
+    /// Add an event to be certified by event ID and content. A verified proof will ensure that
+    /// both the ID and content are correct.
+    pub fn add_event(mut self, event_id: EventId, event: Event) -> Self {
+        self.events.push((event_id, event));
+        self
+    }
+
+    /// Add the next committee to be certified. A verified proof will ensure that the next
+    /// committee is correct.
+    pub fn set_committee(mut self, committee: ValidatorCommittee) -> Self {
+        self.committee = Some(committee);
+        self
+    }
+}
+
+/// Part of a proof that provides evidence relating to a specific transaction to
+/// certify objects and events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionProof {
+    /// Checkpoint contents including this transaction.
+    pub checkpoint_contents: CheckpointContents,
+
+    /// The transaction being certified.
+    pub transaction: SignedTransaction,
+
+    /// The effects of the transaction being certified.
+    pub effects: TransactionEffects,
+
+    /// The events of the transaction being certified.
+    pub events: Option<TransactionEvents>,
+}
+
+/// A proof for specific targets. It certifies a checkpoint summary and optionally includes
+/// transaction evidence to certify objects and events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proof {
+    /// Targets of the proof are a committee, objects, or events that need to be certified.
+    pub targets: ProofTarget,
+
+    /// A summary of the checkpoint being certified.
+    pub checkpoint_summary: SignedCheckpointSummary,
+
+    /// Optional transaction proof to certify objects and events.
+    pub contents_proof: Option<TransactionProof>,
+}
+
+/// Verify a proof against a committee. A proof is valid if it certifies the checkpoint summary
+/// and optionally includes transaction evidence to certify objects and events.
+///
+/// Once a proof is verified it can be trusted, and information in `targets` as well as
+/// `checkpoint_summary` or `contents_proof` can be trusted as being authentic.
+///
+/// The authoritative committee is required to verify the proof, see
+/// [`crate::TrustedCommitteeChain`] to follow the sequence of committees.
+pub fn verify_proof(committee: &ValidatorCommittee, proof: &Proof) -> Result<()> {
+    let summary = &proof.checkpoint_summary.checkpoint;
+
+    // Verify the checkpoint summary using the committee, and the contents against the summary
+    verify_checkpoint_summary(committee, &proof.checkpoint_summary)?;
+    if let Some(contents_proof) = &proof.contents_proof {
+        verify_checkpoint_contents(summary, &contents_proof.checkpoint_contents)?;
+    }
+
+    // MILESTONE 1 : summary and contents is correct
+
+    // If the proof target is the next committee check it
+    if let Some(committee) = &proof.targets.committee {
+        let next_committee = next_epoch_committee(summary).ok_or(Error::NotEndOfEpoch)?;
+        if next_committee != normalize_committee(committee.clone()) {
+            return Err(Error::invalid_proof(
+                "Given committee does not match the end of epoch committee",
+            ));
+        }
+    }
+
+    // MILESTONE 2: committee if requested is correct
+
+    if (!proof.targets.objects.is_empty() || !proof.targets.events.is_empty())
+        && proof.contents_proof.is_none()
+    {
+        return Err(Error::invalid_proof("Contents proof is missing"));
+    }
+
+    // MILESTONE 3: contents proof is present if required
+
+    let Some(contents_proof) = &proof.contents_proof else {
+        return Ok(());
+    };
+
+    let transaction_digest = effects_transaction_digest(&contents_proof.effects);
+    if contents_proof.transaction.transaction.digest() != transaction_digest {
+        return Err(Error::invalid_proof(
+            "Transaction digest does not match the execution digest",
+        ));
+    }
+
+    let effects_digest = contents_proof.effects.digest();
+    if !contents_proof
+        .checkpoint_contents
+        .clone()
+        .into_v1()
+        .iter()
+        .any(|info| info.transaction == transaction_digest && info.effects == effects_digest)
+    {
+        return Err(Error::invalid_proof(
+            "Transaction digest not found in the checkpoint contents",
+        ));
+    }
+
+    // MILESTONE 4: Transaction & Effect correct and in contents
+
+    if effects_events_digest(&contents_proof.effects)
+        != contents_proof.events.as_ref().map(|events| events.digest())
+    {
+        return Err(Error::invalid_proof(
+            "Events digest does not match the execution digest",
+        ));
+    }
+
+    // MILESTONE 5: Events digest & Events are correct and present if required
+
+    for (event_id, event) in &proof.targets.events {
+        let events = contents_proof
+            .events
+            .as_ref()
+            .ok_or(Error::invalid_proof("Events digest is missing"))?;
+        if event_id.tx_digest != transaction_digest {
+            return Err(Error::invalid_proof(
+                "Event does not belong to the transaction",
+            ));
+        }
+        let proven_event = events
+            .0
+            .get(event_id.event_seq as usize)
+            .ok_or(Error::invalid_proof("Event sequence number out of bounds"))?;
+        if proven_event != event {
+            return Err(Error::invalid_proof("Event contents do not match"));
+        }
+    }
+
+    // MILESTONE 6: Event contents are correct
+
+    let changed_objects = effects_changed_objects(&contents_proof.effects);
+    for (object_ref, object) in &proof.targets.objects {
+        let expected = ObjectReference::new(object.object_id(), object.version(), object.digest());
+        if object_ref != &expected {
+            return Err(Error::invalid_proof(
+                "Object reference does not match the object",
+            ));
+        }
+        if !changed_objects.contains(object_ref) {
+            return Err(Error::invalid_proof("Object not found"));
+        }
+    }
+
+    // MILESTONE 7: Object references are correct and in the effects
+
+    Ok(())
+}
+
+fn effects_transaction_digest(effects: &TransactionEffects) -> TransactionDigest {
+    match effects {
+        TransactionEffects::V1(effects) => effects.transaction_digest,
+        TransactionEffects::V2(effects) => effects.transaction_digest,
+    }
+}
+
+fn effects_events_digest(effects: &TransactionEffects) -> Option<TransactionEventsDigest> {
+    match effects {
+        TransactionEffects::V1(effects) => effects.events_digest,
+        TransactionEffects::V2(effects) => effects.events_digest,
+    }
+}
+
+/// References of the objects created, mutated or unwrapped by a transaction.
+fn effects_changed_objects(effects: &TransactionEffects) -> Vec<ObjectReference> {
+    match effects {
+        TransactionEffects::V1(effects) => effects
+            .created
+            .iter()
+            .chain(&effects.mutated)
+            .chain(&effects.unwrapped)
+            .map(|object| object.reference.clone())
+            .collect(),
+        TransactionEffects::V2(effects) => effects
+            .changed_objects
+            .iter()
+            .filter_map(|changed| match &changed.change.output_state {
+                ObjectOut::NotExist => None,
+                ObjectOut::ObjectWrite { digest, .. } => Some(ObjectReference::new(
+                    changed.object_id,
+                    effects.lamport_version,
+                    *digest,
+                )),
+                ObjectOut::PackageWrite { version, digest } => {
+                    Some(ObjectReference::new(changed.object_id, *version, *digest))
+                }
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::committee::{normalize_committee, verify_committee_transition};
+use crate::error::{Error, Result};
+use crate::proof::{verify_proof, Proof};
+use alloc::collections::BTreeMap;
+use alloc::string::ToString;
+use core::convert::Infallible;
+use core::fmt::Display;
+use sui_sdk_types::types::{EpochId, SignedCheckpointSummary, ValidatorCommittee};
+
+/// Persistence of verified committees. Implement this over whatever storage the embedding
+/// environment offers (browser local storage, a contract's state, a file) so that a light client
+/// does not need to re-verify the committee chain from genesis on every start.
+pub trait CommitteeStore {
+    type Error: Display;
+
+    fn get_committee(&self, epoch: EpochId) -> Result<Option<ValidatorCommittee>, Self::Error>;
+
+    fn latest_committee(&self) -> Result<Option<ValidatorCommittee>, Self::Error>;
+
+    fn insert_committee(&mut self, committee: ValidatorCommittee) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct InMemoryCommitteeStore {
+    committees: BTreeMap<EpochId, ValidatorCommittee>,
+}
+
+impl InMemoryCommitteeStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CommitteeStore for InMemoryCommitteeStore {
+    type Error = Infallible;
+
+    fn get_committee(&self, epoch: EpochId) -> Result<Option<ValidatorCommittee>, Self::Error> {
+        Ok(self.committees.get(&epoch).cloned())
+    }
+
+    fn latest_committee(&self) -> Result<Option<ValidatorCommittee>, Self::Error> {
+        Ok(self.committees.values().next_back().cloned())
+    }
+
+    fn insert_committee(&mut self, committee: ValidatorCommittee) -> Result<(), Self::Error> {
+        self.committees.insert(committee.epoch, committee);
+        Ok(())
+    }
+}
+
+/// A chain of committees, each certified by the end of epoch checkpoint signed by the previous
+/// one, starting from a committee trusted out of band.
+pub struct TrustedCommitteeChain<S> {
+    store: S,
+}
+
+impl<S: CommitteeStore> TrustedCommitteeChain<S> {
+    /// Resume a chain from the committees already in `store`.
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    /// Start a chain from `trusted`, typically the genesis committee. Committees already in the
+    /// store are kept.
+    pub fn initialize(mut store: S, trusted: ValidatorCommittee) -> Result<Self> {
+        store
+            .insert_committee(normalize_committee(trusted))
+            .map_err(store_error)?;
+        Ok(Self { store })
+    }
+
+    pub fn latest_committee(&self) -> Result<ValidatorCommittee> {
+        self.store
+            .latest_committee()
+            .map_err(store_error)?
+            .ok_or(Error::EmptyCommitteeChain)
+    }
+
+    pub fn committee(&self, epoch: EpochId) -> Result<ValidatorCommittee> {
+        self.store
+            .get_committee(epoch)
+            .map_err(store_error)?
+            .ok_or(Error::MissingCommittee(epoch))
+    }
+
+    /// Extend the chain with the committee certified by the end of epoch checkpoint of the
+    /// latest trusted epoch, and return it.
+    pub fn advance(
+        &mut self,
+        end_of_epoch_checkpoint: &SignedCheckpointSummary,
+    ) -> Result<ValidatorCommittee> {
+        let latest = self.latest_committee()?;
+        let next = verify_committee_transition(&latest, end_of_epoch_checkpoint)?;
+        self.store
+            .insert_committee(next.clone())
+            .map_err(store_error)?;
+        Ok(next)
+    }
+
+    /// Verify `proof` against the trusted committee of the epoch its checkpoint belongs to.
+    pub fn verify_proof(&self, proof: &Proof) -> Result<()> {
+        let committee = self.committee(proof.checkpoint_summary.checkpoint.epoch)?;
+        verify_proof(&committee, proof)
+    }
+
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+fn store_error(e: impl Display) -> Error {
+    Error::Store(e.to_string())
+}
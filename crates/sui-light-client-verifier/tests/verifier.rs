@@ -0,0 +1,144 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_light_client_verifier::{
+    next_epoch_committee, verify_committee_transition, verify_proof, Error, EventId,
+    InMemoryCommitteeStore, Proof, ProofTarget, TransactionProof, TrustedCommitteeChain,
+};
+use sui_sdk_types::types::{CheckpointData, ValidatorCommittee};
+
+// The checkpoints are embedded so that the tests also run in a wasm runtime, which has no file
+// system.
+const COMMITTEE_CHECKPOINT: &[u8] =
+    include_bytes!("../../sui-light-client/example_config/15918264.chk");
+const CHECKPOINT: &[u8] = include_bytes!("../../sui-light-client/example_config/16005062.chk");
+
+fn read_checkpoint(bytes: &[u8]) -> CheckpointData {
+    let (_, data): (u8, CheckpointData) = bcs::from_bytes(bytes).unwrap();
+    data
+}
+
+/// The committee signing `CHECKPOINT`, and `CHECKPOINT` itself.
+fn read_data() -> (ValidatorCommittee, CheckpointData) {
+    let committee_checkpoint = read_checkpoint(COMMITTEE_CHECKPOINT);
+    let committee = next_epoch_committee(&committee_checkpoint.checkpoint_summary.checkpoint)
+        .expect("Expected checkpoint to be end-of-epoch");
+    (committee, read_checkpoint(CHECKPOINT))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_committee_transition() {
+    let (committee, checkpoint) = read_data();
+    let next_committee =
+        verify_committee_transition(&committee, &checkpoint.checkpoint_summary).unwrap();
+    assert_eq!(next_committee.epoch, committee.epoch + 1);
+
+    let proof = Proof {
+        checkpoint_summary: checkpoint.checkpoint_summary.clone(),
+        contents_proof: None,
+        targets: ProofTarget::new().set_committee(next_committee.clone()),
+    };
+    verify_proof(&committee, &proof).unwrap();
+
+    // The checkpoint is not signed by the next committee
+    assert!(matches!(
+        verify_committee_transition(&next_committee, &checkpoint.checkpoint_summary),
+        Err(Error::WrongEpoch { .. })
+    ));
+
+    // Fail if the new committee does not match the target of the proof
+    let proof = Proof {
+        targets: ProofTarget::new().set_committee(committee.clone()),
+        ..proof
+    };
+    assert!(matches!(
+        verify_proof(&committee, &proof),
+        Err(Error::InvalidProof(_))
+    ));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_committee_chain() {
+    let (committee, checkpoint) = read_data();
+    let mut chain =
+        TrustedCommitteeChain::initialize(InMemoryCommitteeStore::new(), committee.clone())
+            .unwrap();
+    let next_committee = chain.advance(&checkpoint.checkpoint_summary).unwrap();
+    assert_eq!(chain.latest_committee().unwrap(), next_committee);
+
+    // The end of epoch checkpoint can only be used once
+    assert!(chain.advance(&checkpoint.checkpoint_summary).is_err());
+
+    // Proofs are checked against the committee of their epoch, and the store can be reused
+    let proof = Proof {
+        checkpoint_summary: checkpoint.checkpoint_summary.clone(),
+        contents_proof: None,
+        targets: ProofTarget::new(),
+    };
+    let chain = TrustedCommitteeChain::new(chain.into_store());
+    chain.verify_proof(&proof).unwrap();
+    assert!(matches!(
+        chain.committee(committee.epoch + 2),
+        Err(Error::MissingCommittee(_))
+    ));
+
+    let empty = TrustedCommitteeChain::new(InMemoryCommitteeStore::new());
+    assert_eq!(empty.latest_committee(), Err(Error::EmptyCommitteeChain));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_event_and_object_targets() {
+    let (committee, checkpoint) = read_data();
+    let transaction = checkpoint.transactions[1].clone();
+    let events = transaction.events.clone().unwrap();
+    let event_id = EventId {
+        tx_digest: transaction.transaction.transaction.digest(),
+        event_seq: 0,
+    };
+    let object = transaction.output_objects[0].clone();
+    let object_ref = sui_sdk_types::types::ObjectReference::new(
+        object.object_id(),
+        object.version(),
+        object.digest(),
+    );
+    let proof = Proof {
+        checkpoint_summary: checkpoint.checkpoint_summary.clone(),
+        contents_proof: Some(TransactionProof {
+            checkpoint_contents: checkpoint.checkpoint_contents.clone(),
+            transaction: transaction.transaction.clone(),
+            effects: transaction.effects.clone(),
+            events: Some(events.clone()),
+        }),
+        targets: ProofTarget::new()
+            .add_event(event_id, events.0[0].clone())
+            .add_object(object_ref, object),
+    };
+    verify_proof(&committee, &proof).unwrap();
+
+    // Contents proof is required for event targets
+    let bad_proof = Proof {
+        contents_proof: None,
+        ..proof.clone()
+    };
+    assert!(verify_proof(&committee, &bad_proof).is_err());
+
+    // Event out of bounds
+    let mut bad_proof = proof.clone();
+    bad_proof.targets.events[0].0.event_seq = events.0.len() as u64;
+    assert!(verify_proof(&committee, &bad_proof).is_err());
+
+    // Contents that do not match the summary
+    let mut bad_proof = proof;
+    bad_proof
+        .contents_proof
+        .as_mut()
+        .unwrap()
+        .checkpoint_contents = read_checkpoint(COMMITTEE_CHECKPOINT).checkpoint_contents;
+    assert_eq!(
+        verify_proof(&committee, &bad_proof),
+        Err(Error::ContentsDigestMismatch)
+    );
+}
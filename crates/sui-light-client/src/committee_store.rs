@@ -0,0 +1,91 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::PathBuf;
+use sui_light_client_verifier::CommitteeStore;
+use sui_sdk_types::types::{EpochId, ValidatorCommittee};
+
+/// Keeps every verified committee in its own file, `<dir>/<epoch>.bcs`, so that `sync` only
+/// needs to verify the epochs that ended since it last ran.
+pub struct FileCommitteeStore {
+    dir: PathBuf,
+}
+
+impl FileCommitteeStore {
+    pub fn new(dir: PathBuf) -> anyhow::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, epoch: EpochId) -> PathBuf {
+        self.dir.join(format!("{}.bcs", epoch))
+    }
+}
+
+impl CommitteeStore for FileCommitteeStore {
+    type Error = anyhow::Error;
+
+    fn get_committee(&self, epoch: EpochId) -> anyhow::Result<Option<ValidatorCommittee>> {
+        let path = self.path(epoch);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(bcs::from_bytes(&fs::read(path)?)?))
+    }
+
+    fn latest_committee(&self) -> anyhow::Result<Option<ValidatorCommittee>> {
+        let mut latest = None;
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "bcs") {
+                if let Some(epoch) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.parse::<EpochId>().ok())
+                {
+                    latest = latest.max(Some(epoch));
+                }
+            }
+        }
+        match latest {
+            Some(epoch) => self.get_committee(epoch),
+            None => Ok(None),
+        }
+    }
+
+    fn insert_committee(&mut self, committee: ValidatorCommittee) -> anyhow::Result<()> {
+        // Write to a temporary file first, so that an interrupted sync never leaves a truncated
+        // committee behind.
+        let path = self.path(committee.epoch);
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, bcs::to_bytes(&committee)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_committee_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let committee = |epoch| ValidatorCommittee {
+            epoch,
+            members: vec![],
+        };
+
+        let mut store = FileCommitteeStore::new(dir.path().join("committees")).unwrap();
+        assert_eq!(store.latest_committee().unwrap(), None);
+        store.insert_committee(committee(9)).unwrap();
+        store.insert_committee(committee(10)).unwrap();
+
+        // Committees are read back from disk by a new store
+        let store = FileCommitteeStore::new(dir.path().join("committees")).unwrap();
+        assert_eq!(store.latest_committee().unwrap(), Some(committee(10)));
+        assert_eq!(store.get_committee(9).unwrap(), Some(committee(9)));
+        assert_eq!(store.get_committee(11).unwrap(), None);
+    }
+}
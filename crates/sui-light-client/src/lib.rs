@@ -1,11 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod committee_store;
 pub mod construct;
 pub mod proof;
 
 #[doc(inline)]
 pub use proof::*;
 
+#[doc(inline)]
+pub use committee_store::*;
+
 #[doc(inline)]
 pub use construct::*;
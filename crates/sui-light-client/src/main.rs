@@ -14,11 +14,14 @@ use sui_types::{
     digests::TransactionDigest,
     effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
     message_envelope::Envelope,
-    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSummary, EndOfEpochData},
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSummary},
     object::{bounded_visitor::BoundedVisitor, Data, Object},
 };
 
 use sui_config::genesis::Genesis;
+use sui_light_client::FileCommitteeStore;
+use sui_light_client_verifier::TrustedCommitteeChain;
+use sui_sdk_types::types::SignedCheckpointSummary;
 
 use sui_package_resolver::Result as ResolverResult;
 use sui_package_resolver::{Package, PackageStore, Resolver};
@@ -298,6 +301,21 @@ async fn sync_checkpoint_list_to_latest(config: &Config) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// The chain of committees verified so far, starting from the genesis committee.
+fn committee_chain(config: &Config) -> anyhow::Result<TrustedCommitteeChain<FileCommitteeStore>> {
+    let mut genesis_path = config.checkpoint_summary_dir.clone();
+    genesis_path.push(&config.genesis_filename);
+    let genesis_committee = Genesis::load(&genesis_path)?
+        .committee()
+        .map_err(|e| anyhow!(format!("Cannot load Genesis: {e}")))?;
+
+    let store = FileCommitteeStore::new(config.checkpoint_summary_dir.join("committees"))?;
+    Ok(TrustedCommitteeChain::initialize(
+        store,
+        genesis_committee.into(),
+    )?)
+}
+
 async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
     sync_checkpoint_list_to_latest(config)
         .await
@@ -307,17 +325,10 @@ async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
     let checkpoints_list: CheckpointsList = read_checkpoint_list(config)
         .map_err(|e| anyhow!(format!("Cannot read checkpoint list: {e}")))?;
 
-    // Load the genesis committee
-    let mut genesis_path = config.checkpoint_summary_dir.clone();
-    genesis_path.push(&config.genesis_filename);
-    let genesis_committee = Genesis::load(&genesis_path)?
-        .committee()
-        .map_err(|e| anyhow!(format!("Cannot load Genesis: {e}")))?;
+    let mut chain = committee_chain(config)?;
 
-    // Check the signatures of all checkpoints
-    // And download any missing ones
-
-    let mut prev_committee = genesis_committee;
+    // Extend the committee chain with every end of epoch checkpoint it does not cover yet,
+    // downloading any missing ones
     for ckp_id in &checkpoints_list.checkpoints {
         // check if there is a file with this name ckp_id.yaml in the checkpoint_summary_dir
         let mut checkpoint_path = config.checkpoint_summary_dir.clone();
@@ -332,7 +343,6 @@ async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
             let summary = download_checkpoint_summary(config, *ckp_id)
                 .await
                 .map_err(|e| anyhow!(format!("Cannot download summary: {e}")))?;
-            summary.clone().try_into_verified(&prev_committee)?;
             // Write the checkpoint summary to a file
             write_checkpoint(config, &summary)?;
             summary
@@ -345,20 +355,14 @@ async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
             summary.digest()
         );
 
-        // Extract the new committee information
-        if let Some(EndOfEpochData {
-            next_epoch_committee,
-            ..
-        }) = &summary.end_of_epoch_data
-        {
-            let next_committee = next_epoch_committee.iter().cloned().collect();
-            prev_committee =
-                Committee::new(summary.epoch().checked_add(1).unwrap(), next_committee);
-        } else {
-            return Err(anyhow!(
-                "Expected all checkpoints to be end-of-epoch checkpoints"
-            ));
+        // Committees verified by a previous sync are already in the store
+        if chain.latest_committee()?.epoch > summary.epoch() {
+            continue;
         }
+        let summary: SignedCheckpointSummary = summary.try_into()?;
+        chain
+            .advance(&summary)
+            .map_err(|e| anyhow!(format!("Cannot verify checkpoint {ckp_id}: {e}")))?;
     }
 
     Ok(())
@@ -441,48 +445,12 @@ async fn get_verified_effects_and_events(
         .await
         .map_err(|e| anyhow!(format!("Cannot get full checkpoint: {e}")))?;
 
-    // Load the list of stored checkpoints
-    let checkpoints_list: CheckpointsList = read_checkpoint_list(config)?;
-
-    // find the stored checkpoint before the seq checkpoint
-    let prev_ckp_id = checkpoints_list
-        .checkpoints
-        .iter()
-        .filter(|ckp_id| **ckp_id < seq)
-        .last();
-
-    let committee = if let Some(prev_ckp_id) = prev_ckp_id {
-        // Read it from the store
-        let prev_ckp = read_checkpoint(config, *prev_ckp_id)?;
-
-        // Check we have the right checkpoint
-        anyhow::ensure!(
-            prev_ckp.epoch().checked_add(1).unwrap() == full_check_point.checkpoint_summary.epoch(),
-            "Checkpoint sequence number does not match. Need to Sync."
-        );
-
-        // Get the committee from the previous checkpoint
-        let current_committee = prev_ckp
-            .end_of_epoch_data
-            .as_ref()
-            .ok_or(anyhow!(
-                "Expected all checkpoints to be end-of-epoch checkpoints"
-            ))?
-            .next_epoch_committee
-            .iter()
-            .cloned()
-            .collect();
-
-        // Make a committee object using this
-        Committee::new(prev_ckp.epoch().checked_add(1).unwrap(), current_committee)
-    } else {
-        // Since we did not find a small committee checkpoint we use the genesis
-        let mut genesis_path = config.checkpoint_summary_dir.clone();
-        genesis_path.push(&config.genesis_filename);
-        Genesis::load(&genesis_path)?
-            .committee()
-            .map_err(|e| anyhow!(format!("Cannot load Genesis: {e}")))?
-    };
+    // Get the committee of the epoch of the checkpoint, as verified by sync
+    let epoch = full_check_point.checkpoint_summary.epoch();
+    let committee: Committee = committee_chain(config)?
+        .committee(epoch)
+        .map_err(|e| anyhow!(format!("{e}. Need to Sync.")))?
+        .into();
 
     info!("Extracting effects and events for TID: {}", tid);
     extract_verified_effects_and_events(&full_check_point, &committee, tid)
@@ -1,15 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::anyhow;
-
 use serde::{Deserialize, Serialize};
+use sui_sdk_types::types::ObjectReference;
 use sui_types::{
     base_types::ObjectRef,
     committee::Committee,
-    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    effects::{TransactionEffects, TransactionEvents},
     event::{Event, EventID},
-    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents, EndOfEpochData},
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents},
     object::Object,
     transaction::Transaction,
 };
@@ -99,135 +98,65 @@ pub struct Proof {
 /// The authoritative committee is required to verify the proof. The sequence of committees can be
 /// verified through a Committee proof target on the last checkpoint of each epoch,
 /// sequentially since the first epoch.
+///
+/// The checks themselves live in `sui-light-client-verifier`, which can also be used where this
+/// crate cannot, e.g. in wasm.
 pub fn verify_proof(committee: &Committee, proof: &Proof) -> anyhow::Result<()> {
-    // Get checkpoint summary and optional contents
-    let summary = &proof.checkpoint_summary;
-    let contents_ref = proof
+    let committee = committee.clone().into();
+    let proof = to_verifier_proof(proof)?;
+    sui_light_client_verifier::verify_proof(&committee, &proof)?;
+    Ok(())
+}
+
+/// Convert a proof to its `sui-sdk-types` based equivalent, as used by the verifier.
+pub fn to_verifier_proof(proof: &Proof) -> anyhow::Result<sui_light_client_verifier::Proof> {
+    let objects = proof
+        .targets
+        .objects
+        .iter()
+        .map(|((id, version, digest), object)| {
+            let object_ref = ObjectReference::new((*id).into(), version.value(), (*digest).into());
+            Ok::<_, anyhow::Error>((object_ref, object.clone().try_into()?))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    let events = proof
+        .targets
+        .events
+        .iter()
+        .map(|(event_id, event)| {
+            let event_id = sui_light_client_verifier::EventId {
+                tx_digest: event_id.tx_digest.into(),
+                event_seq: event_id.event_seq,
+            };
+            Ok::<_, anyhow::Error>((event_id, event.clone().try_into()?))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    let targets = sui_light_client_verifier::ProofTarget {
+        objects,
+        events,
+        committee: proof.targets.committee.clone().map(Into::into),
+    };
+
+    let contents_proof = proof
         .contents_proof
         .as_ref()
-        .map(|x| &x.checkpoint_contents);
-
-    // Verify the checkpoint summary using the committee
-    summary.verify_with_contents(committee, contents_ref)?;
-
-    // MILESTONE 1 : summary and contents is correct
-    // Note: this is unconditional on the proof targets, and always checked.
-
-    // If the proof target is the next committee check it
-    if let Some(committee) = &proof.targets.committee {
-        match &summary.end_of_epoch_data {
-            Some(EndOfEpochData {
-                next_epoch_committee,
-                ..
-            }) => {
-                // Extract the end of epoch committee
-                let next_committee_data = next_epoch_committee.iter().cloned().collect();
-                let new_committee =
-                    Committee::new(summary.epoch().checked_add(1).unwrap(), next_committee_data);
-
-                if new_committee != *committee {
-                    return Err(anyhow!(
-                        "Given committee does not match the end of epoch committee"
-                    ));
-                }
-            }
-            None => {
-                return Err(anyhow!(
-                    "No end of epoch committee in the checkpoint summary"
-                ));
-            }
-        }
-    }
-
-    // MILESTONE 2: committee if requested is correct
-
-    // Non empty object or event targets require the optional contents proof
-    // If it is not present return an error
-
-    if (!proof.targets.objects.is_empty() || !proof.targets.events.is_empty())
-        && proof.contents_proof.is_none()
-    {
-        return Err(anyhow!("Contents proof is missing"));
-    }
-
-    // MILESTONE 3: contents proof is present if required
-
-    if let Some(contents_proof) = &proof.contents_proof {
-        // Extract Transaction Digests and check they are in contents
-        let digests = contents_proof.effects.execution_digests();
-        if contents_proof.transaction.digest() != &digests.transaction {
-            return Err(anyhow!(
-                "Transaction digest does not match the execution digest"
-            ));
-        }
-
-        // Ensure the digests are in the checkpoint contents
-        if !contents_proof
-            .checkpoint_contents
-            .enumerate_transactions(summary)
-            .any(|x| x.1 == &digests)
-        {
-            // Could not find the digest in the checkpoint contents
-            return Err(anyhow!(
-                "Transaction digest not found in the checkpoint contents"
-            ));
-        }
-
-        // MILESTONE 4: Transaction & Effect correct and in contents
-
-        if contents_proof.effects.events_digest()
-            != contents_proof.events.as_ref().map(|e| e.digest()).as_ref()
-        {
-            return Err(anyhow!("Events digest does not match the execution digest"));
-        }
-
-        // If the target includes any events ensure the events digest is not None
-        if !proof.targets.events.is_empty() && contents_proof.events.is_none() {
-            return Err(anyhow!("Events digest is missing"));
-        }
-
-        // MILESTONE 5: Events digest & Events are correct and present if required
-
-        // Now we verify the content of any target events
-
-        for (event_id, event) in &proof.targets.events {
-            // Check the event corresponds to the transaction
-            if event_id.tx_digest != digests.transaction {
-                return Err(anyhow!("Event does not belong to the transaction"));
-            }
-
-            // The sequence number must be a valid index
-            // Note: safe to unwrap as we have checked that its not None above
-            if event_id.event_seq as usize >= contents_proof.events.as_ref().unwrap().data.len() {
-                return Err(anyhow!("Event sequence number out of bounds"));
-            }
-
-            // Now check that the contents of the event are the same
-            if &contents_proof.events.as_ref().unwrap().data[event_id.event_seq as usize] != event {
-                return Err(anyhow!("Event contents do not match"));
-            }
-        }
-
-        // MILESTONE 6: Event contents are correct
-
-        // Now check all object references are correct and in the effects
-        let changed_objects = contents_proof.effects.all_changed_objects();
-
-        for (object_ref, object) in &proof.targets.objects {
-            // Is the given reference correct?
-            if object_ref != &object.compute_object_reference() {
-                return Err(anyhow!("Object reference does not match the object"));
-            }
-
-            // Has this object been created in these effects?
-            changed_objects
-                .iter()
-                .find(|effects_object_ref| &effects_object_ref.0 == object_ref)
-                .ok_or(anyhow!("Object not found"))?;
-        }
-
-        // MILESTONE 7: Object references are correct and in the effects
-    }
-
-    Ok(())
+        .map(|contents_proof| {
+            anyhow::Ok(sui_light_client_verifier::TransactionProof {
+                checkpoint_contents: contents_proof.checkpoint_contents.clone().try_into()?,
+                transaction: contents_proof.transaction.clone().try_into()?,
+                effects: contents_proof.effects.clone().try_into()?,
+                events: contents_proof
+                    .events
+                    .clone()
+                    .map(TryInto::try_into)
+                    .transpose()?,
+            })
+        })
+        .transpose()?;
+
+    Ok(sui_light_client_verifier::Proof {
+        targets,
+        checkpoint_summary: proof.checkpoint_summary.clone().try_into()?,
+        contents_proof,
+    })
 }
@@ -0,0 +1,11 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+fn main() {
+    let package = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../sui-move-bindgen/tests/data/bindgen_example");
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("bindgen_example.rs");
+    sui_move_bindgen::write_bindings(&package, &out, &Default::default()).unwrap();
+}
@@ -0,0 +1,9 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bindings generated by `sui-move-bindgen` from a build script, so that the generated code is
+//! checked by the compiler and can be exercised by tests.
+
+pub mod bindgen_example {
+    include!(concat!(env!("OUT_DIR"), "/bindgen_example.rs"));
+}
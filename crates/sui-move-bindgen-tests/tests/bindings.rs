@@ -0,0 +1,101 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::marker::PhantomData;
+
+use move_core_types::account_address::AccountAddress;
+use sui_move_bindgen_tests::bindgen_example::counter::{self, Counter, Status};
+use sui_move_bindgen_tests::bindgen_example::dependencies::dep_0x2::vec_map::{Entry, VecMap};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::id::UID;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::{Argument, CallArg, Command};
+
+#[test]
+fn test_decode_object() {
+    let id = ObjectID::from_single_byte(0x42);
+    let owner = SuiAddress::from(ObjectID::from_single_byte(0x7));
+
+    // The BCS layout of a `Counter` object, as it is stored on chain.
+    let bytes = bcs::to_bytes(&(
+        UID::new(id),
+        owner,
+        3u64,
+        vec![("a".to_string(), 1u64), ("b".to_string(), 2u64)],
+        Some(10u64),
+    ))
+    .unwrap();
+
+    let counter = Counter::from_bcs(&bytes).unwrap();
+    assert_eq!(
+        counter,
+        Counter {
+            id: UID::new(id),
+            owner,
+            value: 3,
+            labels: VecMap {
+                contents: vec![
+                    Entry {
+                        key: "a".to_string(),
+                        value: 1,
+                        __phantom: PhantomData,
+                    },
+                    Entry {
+                        key: "b".to_string(),
+                        value: 2,
+                        __phantom: PhantomData,
+                    },
+                ],
+                __phantom: PhantomData,
+            },
+            limit: Some(10),
+        }
+    );
+    assert_eq!(bcs::to_bytes(&counter).unwrap(), bytes);
+}
+
+#[test]
+fn test_decode_enum() {
+    assert_eq!(Status::from_bcs(&[0]).unwrap(), Status::Active);
+    assert_eq!(
+        Status::from_bcs(&[1, 3, b'a', b'b', b'c']).unwrap(),
+        Status::Paused {
+            reason: "abc".to_string()
+        }
+    );
+    assert!(Status::from_bcs(&[2]).is_err());
+}
+
+#[test]
+fn test_struct_tag() {
+    let tag = Counter::struct_tag();
+    assert_eq!(tag.address, AccountAddress::from(counter::TYPE_ORIGIN));
+    assert_eq!(tag.module.as_str(), "counter");
+    assert_eq!(tag.name.as_str(), "Counter");
+    assert!(tag.type_params.is_empty());
+}
+
+#[test]
+fn test_move_call() {
+    let owner = SuiAddress::from(ObjectID::from_single_byte(0x7));
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let result = counter::create(&mut builder, owner, Some(10)).unwrap();
+    assert_eq!(result, Argument::Result(0));
+
+    let pt = builder.finish();
+    assert_eq!(
+        pt.inputs,
+        vec![
+            CallArg::Pure(bcs::to_bytes(&owner).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&Some(10u64)).unwrap()),
+        ]
+    );
+    let [Command::MoveCall(call)] = pt.commands.as_slice() else {
+        panic!("Expected a single Move call, got {:?}", pt.commands);
+    };
+    assert_eq!(call.package, counter::PACKAGE_ID);
+    assert_eq!(call.module, "counter");
+    assert_eq!(call.function, "create");
+    assert!(call.type_arguments.is_empty());
+    assert_eq!(call.arguments, vec![Argument::Input(0), Argument::Input(1)]);
+}
@@ -0,0 +1,699 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, bail};
+use move_binary_format::file_format::Visibility;
+use move_binary_format::normalized::{Enum, Field, Function, Module, Struct, Type};
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use sui_types::balance::{BALANCE_MODULE_NAME, BALANCE_STRUCT_NAME};
+use sui_types::base_types::{
+    ObjectID, RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR, TX_CONTEXT_MODULE_NAME,
+    TX_CONTEXT_STRUCT_NAME,
+};
+use sui_types::coin::{COIN_MODULE_NAME, COIN_STRUCT_NAME};
+use sui_types::id::{OBJECT_MODULE_NAME, RESOLVED_SUI_ID, UID_STRUCT_NAME};
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+use crate::BindgenConfig;
+
+/// Module that the types generated for dependencies are nested under.
+const DEPENDENCIES_MODULE: &str = "dependencies";
+
+const DERIVES: &str =
+    "#[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]";
+const MODULE_LINTS: &str = "#[allow(dead_code, non_camel_case_types, non_snake_case, clippy::all)]";
+
+const ARGUMENT: &str = "::sui_sdk::types::transaction::Argument";
+const BUILDER: &str =
+    "::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder";
+const IDENTIFIER: &str = "::sui_sdk::types::Identifier";
+const OBJECT_ID: &str = "::sui_sdk::types::base_types::ObjectID";
+const STRUCT_TAG: &str = "::move_core_types::language_storage::StructTag";
+const SUI_ADDRESS: &str = "::sui_sdk::types::base_types::SuiAddress";
+const SUI_EVENT: &str = "::sui_sdk::rpc_types::SuiEvent";
+const TYPE_TAG: &str = "::sui_sdk::types::TypeTag";
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Move types that are bound to a type from `std` or `sui-types` instead of a generated one.
+enum KnownType {
+    String,
+    Option,
+    Id,
+    Uid,
+    Balance,
+    Coin,
+}
+
+impl KnownType {
+    fn resolve(address: &AccountAddress, module: &Identifier, name: &Identifier) -> Option<Self> {
+        let resolved = (address, module.as_ident_str(), name.as_ident_str());
+        let uid = (&SUI_FRAMEWORK_ADDRESS, OBJECT_MODULE_NAME, UID_STRUCT_NAME);
+        let balance = (
+            &SUI_FRAMEWORK_ADDRESS,
+            BALANCE_MODULE_NAME,
+            BALANCE_STRUCT_NAME,
+        );
+        let coin = (&SUI_FRAMEWORK_ADDRESS, COIN_MODULE_NAME, COIN_STRUCT_NAME);
+
+        if resolved == RESOLVED_UTF8_STR || resolved == RESOLVED_ASCII_STR {
+            Some(KnownType::String)
+        } else if resolved == RESOLVED_STD_OPTION {
+            Some(KnownType::Option)
+        } else if resolved == RESOLVED_SUI_ID {
+            Some(KnownType::Id)
+        } else if resolved == uid {
+            Some(KnownType::Uid)
+        } else if resolved == balance {
+            Some(KnownType::Balance)
+        } else if resolved == coin {
+            Some(KnownType::Coin)
+        } else {
+            None
+        }
+    }
+}
+
+/// Where the code being generated lives: the Move module it binds and how deeply nested it is
+/// in the generated file, so that other modules can be referred to with relative paths (the
+/// bindings are usually `include!`d somewhere down the including crate's module tree).
+struct Scope<'a> {
+    module: &'a ModuleId,
+    depth: usize,
+}
+
+/// Source code buffer that keeps track of indentation.
+#[derive(Default)]
+struct Writer {
+    out: String,
+    indent: usize,
+}
+
+impl Writer {
+    fn line(&mut self, line: impl AsRef<str>) {
+        let line = line.as_ref();
+        if !line.is_empty() {
+            self.out.push_str(&"    ".repeat(self.indent));
+            self.out.push_str(line);
+        }
+        self.out.push('\n');
+    }
+
+    fn open(&mut self, line: impl AsRef<str>) {
+        self.line(line);
+        self.indent += 1;
+    }
+
+    fn close(&mut self, line: impl AsRef<str>) {
+        self.indent -= 1;
+        self.line(line);
+    }
+}
+
+pub(crate) struct Generator {
+    /// Package that the generated function calls are made on.
+    package_id: Option<ObjectID>,
+    root: BTreeMap<ModuleId, Module>,
+    dependencies: BTreeMap<ModuleId, Module>,
+}
+
+impl Generator {
+    pub(crate) fn new(
+        root_modules: &[CompiledModule],
+        dependencies: &[CompiledModule],
+        config: &BindgenConfig,
+    ) -> anyhow::Result<Self> {
+        let normalize = |modules: &[CompiledModule]| -> BTreeMap<ModuleId, Module> {
+            modules
+                .iter()
+                .map(|m| (m.self_id(), Module::new(m)))
+                .collect()
+        };
+        let root = normalize(root_modules);
+        if root.is_empty() {
+            bail!("Package has no modules to generate bindings for");
+        }
+        if let Some(id) = root
+            .keys()
+            .find(|id| id.name().as_str() == DEPENDENCIES_MODULE)
+        {
+            bail!("Module {id} clashes with the module generated for dependencies");
+        }
+
+        Ok(Self {
+            package_id: config.package_id,
+            root,
+            dependencies: normalize(dependencies),
+        })
+    }
+
+    pub(crate) fn generate(&self) -> anyhow::Result<String> {
+        let mut w = Writer::default();
+        w.line("// @generated by sui-move-bindgen, do not edit by hand.");
+
+        for (id, module) in &self.root {
+            w.line("");
+            self.module(&mut w, id, module, 1, None)?;
+        }
+
+        let mut by_package: BTreeMap<AccountAddress, Vec<(&ModuleId, BTreeSet<Identifier>)>> =
+            BTreeMap::new();
+        for (id, datatypes) in self.dependency_datatypes()? {
+            by_package
+                .entry(*id.address())
+                .or_default()
+                .push((id, datatypes));
+        }
+        if !by_package.is_empty() {
+            w.line("");
+            w.line("/// Types from the package's dependencies that its types refer to.");
+            w.line(MODULE_LINTS);
+            w.open(format!("pub mod {DEPENDENCIES_MODULE} {{"));
+            for (address, modules) in by_package {
+                w.open(format!(
+                    "pub mod {} {{",
+                    dependency_package_module(&address)
+                ));
+                for (id, datatypes) in modules {
+                    self.module(&mut w, id, &self.dependencies[id], 3, Some(&datatypes))?;
+                }
+                w.close("}");
+            }
+            w.close("}");
+        }
+
+        Ok(w.out)
+    }
+
+    /// Finds the datatypes declared by dependencies that root modules' datatypes refer to,
+    /// directly or transitively, and that aren't bound to an existing Rust type.
+    fn dependency_datatypes(&self) -> anyhow::Result<BTreeMap<&ModuleId, BTreeSet<Identifier>>> {
+        let mut pending: Vec<&Type> = vec![];
+        for module in self.root.values() {
+            for s in module.structs.values() {
+                pending.extend(s.fields.iter().map(|f| &f.type_));
+            }
+            for e in module.enums.values() {
+                pending.extend(e.variants.iter().flat_map(|v| &v.fields).map(|f| &f.type_));
+            }
+        }
+
+        let mut found: BTreeMap<&ModuleId, BTreeSet<Identifier>> = BTreeMap::new();
+        while let Some(type_) = pending.pop() {
+            let (address, module, name, type_arguments) = match type_ {
+                Type::Vector(t) | Type::Reference(t) | Type::MutableReference(t) => {
+                    pending.push(t);
+                    continue;
+                }
+                Type::Struct {
+                    address,
+                    module,
+                    name,
+                    type_arguments,
+                } => (address, module, name, type_arguments),
+                _ => continue,
+            };
+
+            pending.extend(type_arguments);
+            let id = ModuleId::new(*address, module.clone());
+            if self.root.contains_key(&id) || KnownType::resolve(address, module, name).is_some() {
+                continue;
+            }
+
+            let (id, declaring) = self.dependencies.get_key_value(&id).ok_or_else(|| {
+                anyhow!("Module {id} is not part of the package or its dependencies")
+            })?;
+            if !found.entry(id).or_default().insert(name.clone()) {
+                continue;
+            }
+            if let Some(s) = declaring.structs.get(name) {
+                pending.extend(s.fields.iter().map(|f| &f.type_));
+            } else if let Some(e) = declaring.enums.get(name) {
+                pending.extend(e.variants.iter().flat_map(|v| &v.fields).map(|f| &f.type_));
+            } else {
+                bail!("Type {name} is not declared in module {id}");
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Generates the Rust module for Move module `id`. Only `datatypes` are generated if set,
+    /// otherwise all datatypes and the functions that can be called from transactions are.
+    fn module(
+        &self,
+        w: &mut Writer,
+        id: &ModuleId,
+        module: &Module,
+        depth: usize,
+        datatypes: Option<&BTreeSet<Identifier>>,
+    ) -> anyhow::Result<()> {
+        let scope = Scope { module: id, depth };
+        let type_origin = match self.package_id {
+            Some(package_id) if *id.address() == AccountAddress::ZERO => package_id,
+            _ => ObjectID::from(*id.address()),
+        };
+        let included = |name: &Identifier| datatypes.map_or(true, |d| d.contains(name));
+
+        w.line(format!(
+            "/// Bindings for Move module `{}`.",
+            display_module(id)
+        ));
+        w.line(MODULE_LINTS);
+        w.open(format!("pub mod {} {{", rust_ident(id.name().as_str())));
+        if datatypes.is_none() {
+            let package_id = self
+                .package_id
+                .unwrap_or_else(|| ObjectID::from(*id.address()));
+            w.line("/// The package that functions of this module are called on.");
+            w.line(format!(
+                "pub const PACKAGE_ID: {OBJECT_ID} = {};",
+                object_id_literal(package_id)
+            ));
+        }
+        w.line("/// The package that first defined the types of this module.");
+        w.line(format!(
+            "pub const TYPE_ORIGIN: {OBJECT_ID} = {};",
+            object_id_literal(type_origin)
+        ));
+        w.line(format!("pub const MODULE_NAME: &str = \"{}\";", id.name()));
+
+        for (name, s) in module.structs.iter().filter(|(n, _)| included(n)) {
+            w.line("");
+            self.struct_(w, &scope, name, s)?;
+        }
+        for (name, e) in module.enums.iter().filter(|(n, _)| included(n)) {
+            w.line("");
+            self.enum_(w, &scope, name, e)?;
+        }
+        if datatypes.is_none() {
+            for (name, f) in &module.functions {
+                if f.visibility == Visibility::Public || f.is_entry {
+                    w.line("");
+                    self.function(w, &scope, name, f)?;
+                }
+            }
+        }
+
+        w.close("}");
+        Ok(())
+    }
+
+    fn struct_(
+        &self,
+        w: &mut Writer,
+        scope: &Scope,
+        name: &Identifier,
+        s: &Struct,
+    ) -> anyhow::Result<()> {
+        let arity = s.type_parameters.len();
+        w.line(format!(
+            "/// Move struct `{}::{name}`.",
+            display_module(scope.module)
+        ));
+        w.line(DERIVES);
+        w.open(format!(
+            "pub struct {}{} {{",
+            rust_ident(name.as_str()),
+            type_parameters(arity, "")
+        ));
+        for field in &s.fields {
+            self.field(w, scope, field, "pub ")?;
+        }
+        if arity > 0 {
+            w.line("#[serde(skip)]");
+            w.line(format!(
+                "pub __phantom: ::std::marker::PhantomData<{}>,",
+                phantom_tuple(arity)
+            ));
+        }
+        w.close("}");
+
+        let is_event = s.abilities.has_copy() && s.abilities.has_drop();
+        self.datatype_impl(w, name, arity, is_event);
+        Ok(())
+    }
+
+    fn enum_(
+        &self,
+        w: &mut Writer,
+        scope: &Scope,
+        name: &Identifier,
+        e: &Enum,
+    ) -> anyhow::Result<()> {
+        let arity = e.type_parameters.len();
+        w.line(format!(
+            "/// Move enum `{}::{name}`.",
+            display_module(scope.module)
+        ));
+        w.line(DERIVES);
+        w.open(format!(
+            "pub enum {}{} {{",
+            rust_ident(name.as_str()),
+            type_parameters(arity, "")
+        ));
+        for variant in &e.variants {
+            let (ident, renamed) = escape(variant.name.as_str());
+            if renamed {
+                w.line(format!("#[serde(rename = \"{}\")]", variant.name));
+            }
+            if variant.fields.is_empty() {
+                w.line(format!("{ident},"));
+                continue;
+            }
+            w.open(format!("{ident} {{"));
+            for field in &variant.fields {
+                self.field(w, scope, field, "")?;
+            }
+            w.close("},");
+        }
+        if arity > 0 {
+            w.line("#[doc(hidden)]");
+            w.line("#[serde(skip)]");
+            w.line(format!(
+                "__Phantom(::std::marker::PhantomData<{}>),",
+                phantom_tuple(arity)
+            ));
+        }
+        w.close("}");
+
+        let is_event = e.abilities.has_copy() && e.abilities.has_drop();
+        self.datatype_impl(w, name, arity, is_event);
+        Ok(())
+    }
+
+    fn field(
+        &self,
+        w: &mut Writer,
+        scope: &Scope,
+        field: &Field,
+        visibility: &str,
+    ) -> anyhow::Result<()> {
+        let (ident, renamed) = escape(field.name.as_str());
+        if renamed {
+            w.line(format!("#[serde(rename = \"{}\")]", field.name));
+        }
+        w.line(format!(
+            "{visibility}{ident}: {},",
+            self.rust_type(scope, &field.type_)?
+        ));
+        Ok(())
+    }
+
+    /// Generates the type tag constructor, the BCS decoder and, for possible events, the event
+    /// decoders of a datatype.
+    fn datatype_impl(&self, w: &mut Writer, name: &Identifier, arity: usize, is_event: bool) {
+        let type_ = format!(
+            "{}{}",
+            rust_ident(name.as_str()),
+            type_parameters(arity, "")
+        );
+
+        w.line("");
+        w.open(format!("impl{} {type_} {{", type_parameters(arity, "")));
+        w.line(format!("pub const TYPE_NAME: &'static str = \"{name}\";"));
+        w.line("");
+        if arity == 0 {
+            w.line("/// The Move type of this datatype.");
+            w.open(format!("pub fn struct_tag() -> {STRUCT_TAG} {{"));
+        } else {
+            w.line("/// The Move type of this datatype, instantiated with `type_params`.");
+            w.open(format!(
+                "pub fn struct_tag(type_params: Vec<{TYPE_TAG}>) -> {STRUCT_TAG} {{"
+            ));
+        }
+        w.open(format!("{STRUCT_TAG} {{"));
+        w.line("address: TYPE_ORIGIN.into(),");
+        w.line(format!("module: {IDENTIFIER}::new(MODULE_NAME).unwrap(),"));
+        w.line(format!(
+            "name: {IDENTIFIER}::new(Self::TYPE_NAME).unwrap(),"
+        ));
+        w.line(if arity == 0 {
+            "type_params: vec![],"
+        } else {
+            "type_params,"
+        });
+        w.close("}");
+        w.close("}");
+        w.close("}");
+
+        w.line("");
+        w.open(format!(
+            "impl{} {type_} {{",
+            type_parameters(arity, ": ::serde::de::DeserializeOwned")
+        ));
+        w.line("/// Decodes a value of this type from its BCS representation.");
+        w.open("pub fn from_bcs(bytes: &[u8]) -> Result<Self, ::bcs::Error> {");
+        w.line("::bcs::from_bytes(bytes)");
+        w.close("}");
+        if is_event {
+            w.line("");
+            w.line("/// Whether `event` was emitted with this type, for any type parameters.");
+            w.open(format!("pub fn is_event(event: &{SUI_EVENT}) -> bool {{"));
+            w.line(format!(
+                "{OBJECT_ID}::from(event.type_.address) == TYPE_ORIGIN"
+            ));
+            w.line("    && event.type_.module.as_str() == MODULE_NAME");
+            w.line("    && event.type_.name.as_str() == Self::TYPE_NAME");
+            w.close("}");
+            w.line("");
+            w.line("/// Decodes `event`, or returns `None` if it was emitted with another type.");
+            w.open(format!(
+                "pub fn from_event(event: &{SUI_EVENT}) -> Option<Result<Self, ::bcs::Error>> {{"
+            ));
+            w.line("Self::is_event(event).then(|| Self::from_bcs(event.bcs.bytes()))");
+            w.close("}");
+        }
+        w.close("}");
+    }
+
+    /// Generates a helper adding a call to function `name` to a programmable transaction.
+    /// Arguments that can be passed as pure inputs take Rust values, the others (objects and
+    /// results of previous commands) take `Argument`s. A trailing `TxContext` is provided by the
+    /// runtime and is left out.
+    fn function(
+        &self,
+        w: &mut Writer,
+        scope: &Scope,
+        name: &Identifier,
+        f: &Function,
+    ) -> anyhow::Result<()> {
+        let mut parameters = f.parameters.as_slice();
+        if let Some((last, rest)) = parameters.split_last() {
+            if is_tx_context(last) {
+                parameters = rest;
+            }
+        }
+        let parameters: Vec<_> = parameters
+            .iter()
+            .map(|p| self.pure_type(scope, p))
+            .collect::<anyhow::Result<_>>()?;
+
+        w.line(format!(
+            "/// Adds a call to `{}::{name}` to `builder`, returning its result.",
+            display_module(scope.module)
+        ));
+        w.open(format!("pub fn {}(", rust_ident(name.as_str())));
+        w.line(format!("builder: &mut {BUILDER},"));
+        for i in 0..f.type_parameters.len() {
+            w.line(format!("t{i}: {TYPE_TAG},"));
+        }
+        for (i, pure) in parameters.iter().enumerate() {
+            w.line(format!("arg{i}: {},", pure.as_deref().unwrap_or(ARGUMENT)));
+        }
+        w.close(format!(") -> Result<{ARGUMENT}, ::bcs::Error> {{"));
+        w.indent += 1;
+
+        w.open("let arguments = vec![");
+        for (i, pure) in parameters.iter().enumerate() {
+            w.line(if pure.is_some() {
+                format!("builder.pure_bytes(::bcs::to_bytes(&arg{i})?, false),")
+            } else {
+                format!("arg{i},")
+            });
+        }
+        w.close("];");
+        w.open("Ok(builder.programmable_move_call(");
+        w.line("PACKAGE_ID,");
+        w.line(format!("{IDENTIFIER}::new(MODULE_NAME).unwrap(),"));
+        w.line(format!("{IDENTIFIER}::new(\"{name}\").unwrap(),"));
+        let type_arguments: Vec<_> = (0..f.type_parameters.len())
+            .map(|i| format!("t{i}"))
+            .collect();
+        w.line(format!("vec![{}],", type_arguments.join(", ")));
+        w.line("arguments,");
+        w.close("))");
+        w.close("}");
+        Ok(())
+    }
+
+    /// The Rust type of a datatype field of type `type_`.
+    fn rust_type(&self, scope: &Scope, type_: &Type) -> anyhow::Result<String> {
+        Ok(match type_ {
+            Type::Bool => "bool".to_string(),
+            Type::U8 => "u8".to_string(),
+            Type::U16 => "u16".to_string(),
+            Type::U32 => "u32".to_string(),
+            Type::U64 => "u64".to_string(),
+            Type::U128 => "u128".to_string(),
+            // BCS encodes u256s as 32 little-endian bytes.
+            Type::U256 => "[u8; 32]".to_string(),
+            Type::Address => SUI_ADDRESS.to_string(),
+            Type::Vector(t) => format!("::std::vec::Vec<{}>", self.rust_type(scope, t)?),
+            Type::TypeParameter(i) => format!("T{i}"),
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => match KnownType::resolve(address, module, name) {
+                Some(KnownType::String) => "::std::string::String".to_string(),
+                Some(KnownType::Option) => format!(
+                    "::std::option::Option<{}>",
+                    self.rust_type(scope, &type_arguments[0])?
+                ),
+                Some(KnownType::Id) => OBJECT_ID.to_string(),
+                Some(KnownType::Uid) => "::sui_sdk::types::id::UID".to_string(),
+                Some(KnownType::Balance) => "::sui_sdk::types::balance::Balance".to_string(),
+                Some(KnownType::Coin) => "::sui_sdk::types::coin::Coin".to_string(),
+                None => {
+                    let arguments = type_arguments
+                        .iter()
+                        .map(|t| self.rust_type(scope, t))
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    let mut path = self.datatype_path(scope, address, module, name);
+                    if !arguments.is_empty() {
+                        path = format!("{path}<{}>", arguments.join(", "));
+                    }
+                    path
+                }
+            },
+            Type::Signer | Type::Reference(_) | Type::MutableReference(_) => {
+                bail!("Type {type_} cannot be stored in a datatype")
+            }
+        })
+    }
+
+    /// The Rust type taken for a function parameter of type `type_`, if it can be passed as a
+    /// pure input.
+    fn pure_type(&self, scope: &Scope, type_: &Type) -> anyhow::Result<Option<String>> {
+        Ok(match type_ {
+            Type::Reference(t) | Type::MutableReference(t) => return self.pure_type(scope, t),
+            Type::Bool
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::U128
+            | Type::U256
+            | Type::Address => Some(self.rust_type(scope, type_)?),
+            Type::Vector(t) => self
+                .pure_type(scope, t)?
+                .map(|t| format!("::std::vec::Vec<{t}>")),
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => match KnownType::resolve(address, module, name) {
+                Some(KnownType::String | KnownType::Id) => Some(self.rust_type(scope, type_)?),
+                Some(KnownType::Option) => self
+                    .pure_type(scope, &type_arguments[0])?
+                    .map(|t| format!("::std::option::Option<{t}>")),
+                _ => None,
+            },
+            Type::Signer | Type::TypeParameter(_) => None,
+        })
+    }
+
+    /// Path to the generated type for datatype `address::module::name`, relative to `scope`.
+    fn datatype_path(
+        &self,
+        scope: &Scope,
+        address: &AccountAddress,
+        module: &Identifier,
+        name: &Identifier,
+    ) -> String {
+        let name = rust_ident(name.as_str());
+        let id = ModuleId::new(*address, module.clone());
+        if &id == scope.module {
+            return name;
+        }
+
+        let mut path = "super::".repeat(scope.depth);
+        if !self.root.contains_key(&id) {
+            path.push_str(DEPENDENCIES_MODULE);
+            path.push_str("::");
+            path.push_str(&dependency_package_module(address));
+            path.push_str("::");
+        }
+        format!("{path}{}::{name}", rust_ident(module.as_str()))
+    }
+}
+
+fn is_tx_context(type_: &Type) -> bool {
+    let (Type::Reference(t) | Type::MutableReference(t)) = type_ else {
+        return false;
+    };
+    matches!(
+        &**t,
+        Type::Struct { address, module, name, .. }
+            if address == &SUI_FRAMEWORK_ADDRESS
+                && module.as_ident_str() == TX_CONTEXT_MODULE_NAME
+                && name.as_ident_str() == TX_CONTEXT_STRUCT_NAME
+    )
+}
+
+fn display_module(id: &ModuleId) -> String {
+    format!("0x{}::{}", id.address().short_str_lossless(), id.name())
+}
+
+/// Name of the module that types generated for the dependency at `address` are nested under.
+fn dependency_package_module(address: &AccountAddress) -> String {
+    format!("dep_0x{}", address.short_str_lossless())
+}
+
+fn object_id_literal(id: ObjectID) -> String {
+    format!("{OBJECT_ID}::new({:?})", id.into_bytes())
+}
+
+/// `<T0, T1, ...>` for `arity` type parameters, each followed by `bound`.
+fn type_parameters(arity: usize, bound: &str) -> String {
+    if arity == 0 {
+        return String::new();
+    }
+    let parameters: Vec<_> = (0..arity).map(|i| format!("T{i}{bound}")).collect();
+    format!("<{}>", parameters.join(", "))
+}
+
+fn phantom_tuple(arity: usize) -> String {
+    let parameters: Vec<_> = (0..arity).map(|i| format!("T{i},")).collect();
+    format!("({})", parameters.join(" "))
+}
+
+/// Rust identifier for a Move identifier. Move identifiers are valid Rust identifiers, except for
+/// Rust keywords.
+fn rust_ident(name: &str) -> String {
+    escape(name).0
+}
+
+/// Escapes Rust keywords in `name`, returning whether the resulting identifier differs from the
+/// Move one (and so needs to be renamed for serde).
+fn escape(name: &str) -> (String, bool) {
+    match name {
+        "self" | "Self" | "super" | "crate" => (format!("{name}_"), true),
+        n if RUST_KEYWORDS.contains(&n) => (format!("r#{n}"), false),
+        n => (n.to_string(), false),
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates typed Rust bindings for a Sui Move package from its compiled, normalized modules.
+//!
+//! For every module of the package the bindings contain:
+//!
+//! - A Rust struct or enum for each Move struct and enum, deriving `serde` so that values can be
+//!   decoded from (and encoded to) BCS. Types from dependencies that don't have a counterpart in
+//!   `sui-types` are generated as well, under a `dependencies` module.
+//! - Typed event decoders (`is_event`, `from_event`) for structs with `copy` and `drop`, which
+//!   are the only structs that can be emitted as events.
+//! - A helper for each `public` or `entry` function that adds a call to it to a
+//!   `ProgrammableTransactionBuilder`.
+//!
+//! The generated code refers to `sui_sdk`, `move_core_types`, `serde` and `bcs`, which must be
+//! dependencies of the crate that includes it. From a build script:
+//!
+//! ```ignore
+//! fn main() {
+//!     let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("my_package.rs");
+//!     sui_move_bindgen::write_bindings("move/my_package".as_ref(), &out, &Default::default())
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! and then `include!(concat!(env!("OUT_DIR"), "/my_package.rs"));` in a module of the crate.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use move_binary_format::CompiledModule;
+use move_package::BuildConfig as MoveBuildConfig;
+use sui_move_build::{BuildConfig, CompiledPackage, SuiPackageHooks};
+use sui_types::base_types::ObjectID;
+
+mod codegen;
+
+#[derive(Clone, Debug, Default)]
+pub struct BindgenConfig {
+    /// The package that generated function calls are made on. Defaults to the `published-at`
+    /// address of the package, or the address its modules were compiled with.
+    pub package_id: Option<ObjectID>,
+}
+
+/// Generates bindings for `root_modules`, resolving the types they refer to in `dependencies`.
+pub fn generate_bindings(
+    root_modules: &[CompiledModule],
+    dependencies: &[CompiledModule],
+    config: &BindgenConfig,
+) -> anyhow::Result<String> {
+    codegen::Generator::new(root_modules, dependencies, config)?.generate()
+}
+
+/// Generates bindings for the root modules of an already built package.
+pub fn generate_for_compiled_package(
+    package: &CompiledPackage,
+    config: &BindgenConfig,
+) -> anyhow::Result<String> {
+    let config = BindgenConfig {
+        package_id: config
+            .package_id
+            .or_else(|| package.published_at.as_ref().ok().copied()),
+    };
+    let root_modules: Vec<_> = package.get_modules().cloned().collect();
+    let dependencies: Vec<_> = package.get_dependent_modules().cloned().collect();
+    generate_bindings(&root_modules, &dependencies, &config)
+}
+
+/// Builds the package at `package_path` and generates bindings for it.
+pub fn generate_for_package(package_path: &Path, config: &BindgenConfig) -> anyhow::Result<String> {
+    let package = build_package(package_path, None)?;
+    generate_for_compiled_package(&package, config)
+}
+
+/// Builds the package at `package_path` and writes its bindings to `out_file`. Meant to be called
+/// from build scripts: the package is built next to `out_file` rather than in its own directory,
+/// and cargo is asked to re-run the script when the package changes.
+pub fn write_bindings(
+    package_path: &Path,
+    out_file: &Path,
+    config: &BindgenConfig,
+) -> anyhow::Result<()> {
+    println!(
+        "cargo:rerun-if-changed={}",
+        package_path.join("Move.toml").display()
+    );
+    println!(
+        "cargo:rerun-if-changed={}",
+        package_path.join("sources").display()
+    );
+    let install_dir = out_file
+        .parent()
+        .map_or_else(PathBuf::new, Path::to_path_buf)
+        .join("move");
+    let package = build_package(package_path, Some(install_dir))?;
+    fs::write(out_file, generate_for_compiled_package(&package, config)?)?;
+    Ok(())
+}
+
+fn build_package(
+    package_path: &Path,
+    install_dir: Option<PathBuf>,
+) -> anyhow::Result<CompiledPackage> {
+    move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
+    let lock_file = install_dir.as_ref().map(|dir| dir.join("Move.lock"));
+    let package = BuildConfig {
+        config: MoveBuildConfig {
+            silence_warnings: true,
+            install_dir,
+            lock_file,
+            ..Default::default()
+        },
+        run_bytecode_verifier: true,
+        print_diags_to_stderr: false,
+        chain_id: None,
+    }
+    .build(package_path)?;
+    Ok(package)
+}
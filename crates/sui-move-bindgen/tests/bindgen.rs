@@ -0,0 +1,96 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use sui_move_bindgen::{generate_for_compiled_package, BindgenConfig};
+use sui_move_build::BuildConfig;
+use sui_types::base_types::ObjectID;
+
+fn generate(config: &BindgenConfig) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/bindgen_example");
+    let package = BuildConfig::new_for_testing().build(&path).unwrap();
+    generate_for_compiled_package(&package, config).unwrap()
+}
+
+#[test]
+fn test_datatypes() {
+    let bindings = generate(&BindgenConfig::default());
+
+    assert!(bindings.contains("pub mod counter {"));
+    assert!(bindings.contains("pub struct Counter {"));
+    assert!(bindings.contains("pub id: ::sui_sdk::types::id::UID,"));
+    assert!(bindings.contains("pub owner: ::sui_sdk::types::base_types::SuiAddress,"));
+    assert!(bindings.contains("pub limit: ::std::option::Option<u64>,"));
+    assert!(bindings.contains("pub struct Wrapper<T0, T1> {"));
+    assert!(bindings.contains("pub __phantom: ::std::marker::PhantomData<(T0, T1,)>,"));
+    assert!(bindings.contains("pub enum Status {"));
+    assert!(bindings.contains("Paused {"));
+
+    // `VecMap` has no counterpart in sui-types, so it is generated along with the types it
+    // refers to.
+    assert!(bindings.contains(
+        "pub labels: super::dependencies::dep_0x2::vec_map::VecMap<::std::string::String, u64>,"
+    ));
+    assert!(bindings.contains("pub mod dep_0x2 {"));
+    assert!(bindings.contains("pub struct VecMap<T0, T1> {"));
+    assert!(bindings.contains("pub contents: ::std::vec::Vec<Entry<T0, T1>>,"));
+    assert!(bindings.contains("pub struct Entry<T0, T1> {"));
+    // Only the types that are referred to are generated.
+    assert!(!bindings.contains("pub struct VecSet<"));
+}
+
+/// The generated code for datatype `name` of the root module, up to the next datatype.
+fn datatype<'a>(bindings: &'a str, name: &str) -> &'a str {
+    let start = bindings
+        .find(&format!("/// Move struct `0x0::counter::{name}`."))
+        .or_else(|| bindings.find(&format!("/// Move enum `0x0::counter::{name}`.")))
+        .unwrap();
+    let end = bindings[start + 1..]
+        .find("/// Move ")
+        .map_or(bindings.len(), |end| start + 1 + end);
+    &bindings[start..end]
+}
+
+#[test]
+fn test_events() {
+    let bindings = generate(&BindgenConfig::default());
+
+    // Only datatypes with copy and drop can be emitted as events.
+    assert!(datatype(&bindings, "Incremented").contains("pub fn from_event("));
+    assert!(datatype(&bindings, "Status").contains("pub fn from_event("));
+    assert!(!datatype(&bindings, "Counter").contains("pub fn from_event("));
+    assert!(!datatype(&bindings, "Wrapper").contains("pub fn from_event("));
+    assert!(datatype(&bindings, "Counter").contains("pub fn from_bcs("));
+}
+
+#[test]
+fn test_functions() {
+    let package_id = ObjectID::from_single_byte(0x42);
+    let bindings = generate(&BindgenConfig {
+        package_id: Some(package_id),
+    });
+
+    assert!(bindings.contains(&format!(
+        "pub const PACKAGE_ID: ::sui_sdk::types::base_types::ObjectID = \
+         ::sui_sdk::types::base_types::ObjectID::new({:?});",
+        package_id.into_bytes()
+    )));
+
+    // Pure arguments take Rust values, and the trailing `TxContext` is left out.
+    let create = bindings.find("pub fn create(").unwrap();
+    let signature = &bindings[create..bindings[create..].find('{').unwrap() + create];
+    assert!(signature.contains("arg0: ::sui_sdk::types::base_types::SuiAddress,"));
+    assert!(signature.contains("arg1: ::std::option::Option<u64>,"));
+    assert!(!signature.contains("arg2"));
+
+    // Objects are passed as arguments.
+    assert!(bindings.contains("arg0: ::sui_sdk::types::transaction::Argument,"));
+    assert!(bindings.contains("pub fn increment("));
+    assert!(bindings.contains("pub fn wrap("));
+    assert!(bindings.contains("t1: ::sui_sdk::types::TypeTag,"));
+    assert!(bindings.contains("vec![t0, t1],"));
+
+    // Private functions cannot be called from transactions.
+    assert!(!bindings.contains("pub fn value("));
+}
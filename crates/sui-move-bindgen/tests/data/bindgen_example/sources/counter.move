@@ -0,0 +1,59 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module bindgen_example::counter {
+    use std::string::String;
+    use sui::event;
+    use sui::vec_map::{Self, VecMap};
+
+    public struct Counter has key {
+        id: UID,
+        owner: address,
+        value: u64,
+        labels: VecMap<String, u64>,
+        limit: Option<u64>,
+    }
+
+    public struct Incremented has copy, drop {
+        counter: ID,
+        by: u64,
+    }
+
+    public struct Wrapper<phantom T, V: store> has store {
+        inner: V,
+    }
+
+    public enum Status has copy, drop, store {
+        Active,
+        Paused { reason: String },
+    }
+
+    public fun create(owner: address, limit: Option<u64>, ctx: &mut TxContext): Counter {
+        Counter { id: object::new(ctx), owner, value: 0, labels: vec_map::empty(), limit }
+    }
+
+    public fun share(counter: Counter) {
+        transfer::share_object(counter)
+    }
+
+    entry fun increment(counter: &mut Counter, by: u64) {
+        counter.value = counter.value + by;
+        event::emit(Incremented { counter: object::id(counter), by });
+    }
+
+    public fun wrap<T, V: store>(inner: V): Wrapper<T, V> {
+        Wrapper { inner }
+    }
+
+    public fun status(counter: &Counter): Status {
+        if (counter.value < *counter.limit.borrow_with_default(&counter.value)) {
+            Status::Active
+        } else {
+            Status::Paused { reason: b"limit reached".to_string() }
+        }
+    }
+
+    fun value(counter: &Counter): u64 {
+        counter.value
+    }
+}
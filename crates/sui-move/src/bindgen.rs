@@ -0,0 +1,57 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::manage_package::resolve_lock_file_path;
+use clap::Parser;
+use move_cli::base;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::{fs, path::Path, path::PathBuf};
+use sui_move_bindgen::{generate_for_compiled_package, BindgenConfig};
+use sui_move_build::BuildConfig;
+use sui_types::base_types::ObjectID;
+
+/// Generate typed Rust bindings for the package
+///
+/// The bindings contain types for the package's structs, enums and events, and helpers adding
+/// calls to its functions to programmable transactions.
+#[derive(Parser)]
+#[group(id = "sui-move-bindgen")]
+pub struct Bindgen {
+    /// File to write the bindings to. They are printed to stdout if not set.
+    #[clap(long, short)]
+    pub output: Option<PathBuf>,
+    /// The package that generated function calls are made on. Defaults to the package's
+    /// `published-at` address, or the address its modules are compiled with.
+    #[clap(long, value_parser = ObjectID::from_hex_literal)]
+    pub package_id: Option<ObjectID>,
+}
+
+impl Bindgen {
+    pub fn execute(
+        &self,
+        path: Option<&Path>,
+        build_config: MoveBuildConfig,
+    ) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path)?;
+        let build_config = resolve_lock_file_path(build_config, Some(&rerooted_path))?;
+        let pkg = BuildConfig {
+            config: build_config,
+            run_bytecode_verifier: true,
+            print_diags_to_stderr: true,
+            chain_id: None,
+        }
+        .build(&rerooted_path)?;
+
+        let bindings = generate_for_compiled_package(
+            &pkg,
+            &BindgenConfig {
+                package_id: self.package_id,
+            },
+        )?;
+        match &self.output {
+            Some(output) => fs::write(output, bindings)?,
+            None => print!("{bindings}"),
+        }
+        Ok(())
+    }
+}
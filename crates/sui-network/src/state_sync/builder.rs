@@ -218,6 +218,7 @@ where
                 metrics,
                 archive_readers,
                 sync_checkpoint_from_archive_task: None,
+                archive_import_task: None,
             },
             handle,
         )
@@ -3,10 +3,11 @@
 
 use mysten_metrics::histogram::Histogram as MystenHistogram;
 use prometheus::{
-    register_histogram_with_registry, register_int_gauge_with_registry, Histogram, IntGauge,
-    Registry,
+    register_histogram_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
 };
 use std::sync::Arc;
+use std::time::Duration;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tap::Pipe;
 
@@ -48,6 +49,36 @@ impl Metrics {
         }
     }
 
+    pub fn set_archive_import_in_progress(&self, in_progress: bool) {
+        if let Some(inner) = &self.0 {
+            inner.archive_import_in_progress.set(in_progress as i64);
+        }
+    }
+
+    pub fn inc_archive_imported_checkpoints(&self, num_transactions: usize) {
+        if let Some(inner) = &self.0 {
+            inner.archive_imported_checkpoints.inc();
+            inner
+                .archive_imported_transactions
+                .inc_by(num_transactions as u64);
+        }
+    }
+
+    pub fn observe_archive_import_batch_latency(&self, latency: Duration) {
+        if let Some(inner) = &self.0 {
+            inner
+                .archive_import_batch_latency
+                .observe(latency.as_secs_f64());
+        }
+    }
+
+    pub fn inc_peer_synced_checkpoints(&self, num_transactions: u64) {
+        if let Some(inner) = &self.0 {
+            inner.peer_synced_checkpoints.inc();
+            inner.peer_synced_transactions.inc_by(num_transactions);
+        }
+    }
+
     pub fn checkpoint_summary_age_metrics(&self) -> Option<(&Histogram, &MystenHistogram)> {
         if let Some(inner) = &self.0 {
             return Some((
@@ -66,6 +97,12 @@ struct Inner {
     checkpoint_summary_age: Histogram,
     // TODO: delete once users are migrated to non-Mysten histogram.
     checkpoint_summary_age_ms: MystenHistogram,
+    archive_import_in_progress: IntGauge,
+    archive_imported_checkpoints: IntCounter,
+    archive_imported_transactions: IntCounter,
+    archive_import_batch_latency: Histogram,
+    peer_synced_checkpoints: IntCounter,
+    peer_synced_transactions: IntCounter,
 }
 
 impl Inner {
@@ -104,6 +141,49 @@ impl Inner {
                 "Age of checkpoints summaries when they arrive and are verified.",
                 registry,
             ),
+
+            archive_import_in_progress: register_int_gauge_with_registry!(
+                "archive_import_in_progress",
+                "Whether checkpoints are being bulk imported from archives",
+                registry
+            )
+            .unwrap(),
+
+            archive_imported_checkpoints: register_int_counter_with_registry!(
+                "archive_imported_checkpoints",
+                "Number of checkpoints bulk imported from archives",
+                registry
+            )
+            .unwrap(),
+
+            archive_imported_transactions: register_int_counter_with_registry!(
+                "archive_imported_transactions",
+                "Number of transactions in checkpoints bulk imported from archives",
+                registry
+            )
+            .unwrap(),
+
+            archive_import_batch_latency: register_histogram_with_registry!(
+                "archive_import_batch_latency",
+                "Time taken to download a batch of checkpoints from an archive.",
+                mysten_metrics::COARSE_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+
+            peer_synced_checkpoints: register_int_counter_with_registry!(
+                "peer_synced_checkpoints",
+                "Number of checkpoints whose contents were synced from peers",
+                registry
+            )
+            .unwrap(),
+
+            peer_synced_transactions: register_int_counter_with_registry!(
+                "peer_synced_transactions",
+                "Number of transactions in checkpoints whose contents were synced from peers",
+                registry
+            )
+            .unwrap(),
         }
         .pipe(Arc::new)
     }
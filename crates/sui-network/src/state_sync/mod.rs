@@ -379,6 +379,7 @@ struct StateSyncEventLoop<S> {
 
     archive_readers: ArchiveReaderBalancer,
     sync_checkpoint_from_archive_task: Option<AbortHandle>,
+    archive_import_task: Option<AbortHandle>,
 }
 
 impl<S> StateSyncEventLoop<S>
@@ -415,36 +416,30 @@ where
             self.metrics.clone(),
         ));
 
-        // Start checkpoint contents sync loop.
-        let task = sync_checkpoint_contents(
-            self.network.clone(),
-            self.store.clone(),
-            self.peer_heights.clone(),
-            self.weak_sender.clone(),
-            self.checkpoint_event_sender.clone(),
-            self.config.checkpoint_content_download_concurrency(),
-            self.config.checkpoint_content_download_tx_concurrency(),
-            self.config.checkpoint_content_timeout(),
-            target_checkpoint_contents_sequence_receiver,
-        );
-        let task_handle = self.tasks.spawn(task);
-        self.sync_checkpoint_contents_task = Some(task_handle);
-
-        // Start archive based checkpoint content sync loop.
-        // TODO: Consider switching to sync from archive only on startup.
-        // Right now because the peer set is fixed at startup, a node may eventually
-        // end up with peers who have all purged their local state. In such a scenario it will be
-        // stuck until restart when it ends up with a different set of peers. Once the discovery
-        // mechanism can dynamically identify and connect to other peers on the network, we will rely
-        // on sync from archive as a fall back.
-        let task = sync_checkpoint_contents_from_archive(
-            self.network.clone(),
-            self.archive_readers.clone(),
-            self.store.clone(),
-            self.peer_heights.clone(),
-        );
-        let task_handle = self.tasks.spawn(task);
-        self.sync_checkpoint_from_archive_task = Some(task_handle);
+        // If configured to, bulk import checkpoints from archives while we are far behind, and only
+        // start syncing checkpoints from peers once that is done, so that the two never race to
+        // sync the same checkpoints.
+        let mut target_checkpoint_contents_sequence_receiver =
+            Some(target_checkpoint_contents_sequence_receiver);
+        if let Some(threshold) = self.config.archive_import_threshold {
+            let task = import_checkpoints_from_archive(
+                self.archive_readers.clone(),
+                self.store.clone(),
+                self.peer_heights.clone(),
+                self.checkpoint_event_sender.clone(),
+                self.metrics.clone(),
+                self.config.pinned_checkpoints.clone(),
+                threshold,
+                self.config.archive_import_batch_size(),
+                self.config.archive_import_concurrency(),
+            );
+            let task_handle = self.tasks.spawn(task);
+            self.archive_import_task = Some(task_handle);
+        } else {
+            self.start_checkpoint_contents_sync_tasks(
+                target_checkpoint_contents_sequence_receiver.take().unwrap(),
+            );
+        }
 
         // Start main loop.
         loop {
@@ -490,6 +485,13 @@ where
                     if matches!(&self.sync_checkpoint_from_archive_task, Some(t) if t.is_finished()) {
                         panic!("sync_checkpoint_from_archive task unexpectedly terminated")
                     }
+
+                    if matches!(&self.archive_import_task, Some(t) if t.is_finished()) {
+                        self.archive_import_task = None;
+                        if let Some(receiver) = target_checkpoint_contents_sequence_receiver.take() {
+                            self.start_checkpoint_contents_sync_tasks(receiver);
+                        }
+                    }
                 },
             }
 
@@ -502,6 +504,43 @@ where
         info!("State-Synchronizer ended");
     }
 
+    fn start_checkpoint_contents_sync_tasks(
+        &mut self,
+        target_sequence_channel: watch::Receiver<CheckpointSequenceNumber>,
+    ) {
+        // Start checkpoint contents sync loop.
+        let task = sync_checkpoint_contents(
+            self.network.clone(),
+            self.store.clone(),
+            self.peer_heights.clone(),
+            self.weak_sender.clone(),
+            self.checkpoint_event_sender.clone(),
+            self.metrics.clone(),
+            self.config.checkpoint_content_download_concurrency(),
+            self.config.checkpoint_content_download_tx_concurrency(),
+            self.config.checkpoint_content_timeout(),
+            target_sequence_channel,
+        );
+        let task_handle = self.tasks.spawn(task);
+        self.sync_checkpoint_contents_task = Some(task_handle);
+
+        // Start archive based checkpoint content sync loop.
+        // TODO: Consider switching to sync from archive only on startup.
+        // Right now because the peer set is fixed at startup, a node may eventually
+        // end up with peers who have all purged their local state. In such a scenario it will be
+        // stuck until restart when it ends up with a different set of peers. Once the discovery
+        // mechanism can dynamically identify and connect to other peers on the network, we will rely
+        // on sync from archive as a fall back.
+        let task = sync_checkpoint_contents_from_archive(
+            self.network.clone(),
+            self.archive_readers.clone(),
+            self.store.clone(),
+            self.peer_heights.clone(),
+        );
+        let task_handle = self.tasks.spawn(task);
+        self.sync_checkpoint_from_archive_task = Some(task_handle);
+    }
+
     fn handle_message(&mut self, message: StateSyncMessage) {
         debug!("Received message: {:?}", message);
         match message {
@@ -658,8 +697,8 @@ where
     }
 
     fn maybe_start_checkpoint_summary_sync_task(&mut self) {
-        // Only run one sync task at a time
-        if self.sync_checkpoint_summaries_task.is_some() {
+        // Only run one sync task at a time, and don't sync from peers while importing from archives
+        if self.sync_checkpoint_summaries_task.is_some() || self.archive_import_task.is_some() {
             return;
         }
 
@@ -1178,12 +1217,187 @@ async fn sync_checkpoint_contents_from_archive<S>(
     }
 }
 
+/// Bulk imports checkpoints from archives while we are more than `threshold` checkpoints behind
+/// the highest checkpoint known from archives and peers. Batches of checkpoints are downloaded
+/// from archives concurrently, then verified against the committee chain and handed to the
+/// checkpoint executor in order, like checkpoints synced from peers.
+async fn import_checkpoints_from_archive<S>(
+    archive_readers: ArchiveReaderBalancer,
+    store: S,
+    peer_heights: Arc<RwLock<PeerHeights>>,
+    checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
+    metrics: Metrics,
+    pinned_checkpoints: Vec<(CheckpointSequenceNumber, CheckpointDigest)>,
+    threshold: u64,
+    batch_size: u64,
+    concurrency: usize,
+) where
+    S: WriteStore + Clone + Send + Sync + 'static,
+{
+    archive_readers.sync_manifests().await;
+    metrics.set_archive_import_in_progress(true);
+    loop {
+        let highest_synced = *store
+            .get_highest_synced_checkpoint()
+            .expect("store operation should not fail")
+            .sequence_number();
+        let Some(latest_archived) = archive_readers.latest_available_checkpoint().await else {
+            break;
+        };
+        let highest_known = peer_heights
+            .read()
+            .unwrap()
+            .highest_known_checkpoint_sequence_number()
+            .unwrap_or_default()
+            .max(latest_archived);
+        if latest_archived <= highest_synced
+            || highest_synced.saturating_add(threshold) >= highest_known
+        {
+            break;
+        }
+
+        let checkpoint_range = highest_synced.checked_add(1).unwrap()..latest_archived + 1;
+        info!("Importing checkpoints {checkpoint_range:?} from archives");
+        if let Err(err) = import_checkpoint_range(
+            &archive_readers,
+            &store,
+            &checkpoint_event_sender,
+            &metrics,
+            &pinned_checkpoints,
+            checkpoint_range,
+            batch_size,
+            concurrency,
+        )
+        .await
+        {
+            warn!("Importing checkpoints from archives failed with error: {err:?}");
+            break;
+        }
+
+        // Stop if the archives did not let us make progress, e.g. because the only archives
+        // holding the remaining checkpoints are unreachable.
+        let new_highest_synced = *store
+            .get_highest_synced_checkpoint()
+            .expect("store operation should not fail")
+            .sequence_number();
+        if new_highest_synced == highest_synced {
+            break;
+        }
+    }
+    metrics.set_archive_import_in_progress(false);
+    info!("Done importing checkpoints from archives, syncing checkpoints from peers");
+}
+
+async fn import_checkpoint_range<S>(
+    archive_readers: &ArchiveReaderBalancer,
+    store: &S,
+    checkpoint_event_sender: &broadcast::Sender<VerifiedCheckpoint>,
+    metrics: &Metrics,
+    pinned_checkpoints: &[(CheckpointSequenceNumber, CheckpointDigest)],
+    checkpoint_range: std::ops::Range<CheckpointSequenceNumber>,
+    batch_size: u64,
+    concurrency: usize,
+) -> Result<()>
+where
+    S: WriteStore,
+{
+    let mut current = store
+        .get_highest_synced_checkpoint()
+        .expect("store operation should not fail");
+    let mut batches = (checkpoint_range.start..checkpoint_range.end)
+        .step_by(batch_size as usize)
+        .map(|start| {
+            let batch =
+                start..std::cmp::min(start.saturating_add(batch_size), checkpoint_range.end);
+            async move {
+                let now = tokio::time::Instant::now();
+                let archive_reader = archive_readers
+                    .pick_one_random(batch.clone())
+                    .await
+                    .ok_or_else(|| anyhow::anyhow!("no archive has checkpoints {batch:?}"))?;
+                let checkpoints = archive_reader.get_checkpoints_for_range(batch).await?;
+                metrics.observe_archive_import_batch_latency(now.elapsed());
+                Ok::<_, anyhow::Error>(checkpoints)
+            }
+        })
+        .pipe(futures::stream::iter)
+        .buffered(concurrency);
+
+    while let Some(checkpoints) = batches.next().await {
+        let checkpoints = checkpoints?;
+        let batch_end = current.sequence_number().saturating_add(batch_size);
+        for (checkpoint, contents) in checkpoints {
+            let next = current.sequence_number().checked_add(1).unwrap();
+            if *checkpoint.sequence_number() != next {
+                return Err(anyhow::anyhow!(
+                    "archive returned checkpoint {} while importing checkpoint {next}",
+                    checkpoint.sequence_number()
+                ));
+            }
+
+            // Verify the checkpoint, unless we already have it or it was manually pinned.
+            let checkpoint = if let Some(existing) = store.get_checkpoint_by_sequence_number(next) {
+                if existing.digest() != checkpoint.digest() {
+                    return Err(anyhow::anyhow!(
+                        "archived checkpoint {next} does not match the one in our store"
+                    ));
+                }
+                existing
+            } else if let Ok(index) =
+                pinned_checkpoints.binary_search_by_key(&next, |(seq_num, _digest)| *seq_num)
+            {
+                if pinned_checkpoints[index].1 != *checkpoint.digest() {
+                    return Err(anyhow::anyhow!(
+                        "archived checkpoint {next} does not match pinned digest"
+                    ));
+                }
+                VerifiedCheckpoint::new_unchecked(checkpoint)
+            } else {
+                verify_checkpoint(&current, store, checkpoint).map_err(|checkpoint| {
+                    anyhow::anyhow!("unable to verify archived checkpoint {checkpoint:?}")
+                })?
+            };
+            contents.verify_digests(checkpoint.content_digest)?;
+
+            let num_transactions = contents.size();
+            store
+                .insert_checkpoint(&checkpoint)
+                .expect("store operation should not fail");
+            store
+                .update_highest_verified_checkpoint(&checkpoint)
+                .expect("store operation should not fail");
+            store
+                .insert_checkpoint_contents(
+                    &checkpoint,
+                    VerifiedCheckpointContents::new_unchecked(contents),
+                )
+                .expect("store operation should not fail");
+            store
+                .update_highest_synced_checkpoint(&checkpoint)
+                .expect("store operation should not fail");
+            metrics.inc_archive_imported_checkpoints(num_transactions);
+            // We don't care if no one is listening as this is a broadcast channel
+            let _ = checkpoint_event_sender.send(checkpoint.clone());
+            current = checkpoint;
+        }
+
+        // The archive we picked did not have the whole batch, try again from where it stopped.
+        if *current.sequence_number() < batch_end
+            && *current.sequence_number() + 1 < checkpoint_range.end
+        {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
 async fn sync_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
     sender: mpsc::WeakSender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
+    metrics: Metrics,
     checkpoint_content_download_concurrency: usize,
     checkpoint_content_download_tx_concurrency: u64,
     timeout: Duration,
@@ -1225,7 +1439,9 @@ async fn sync_checkpoint_contents<S>(
                             .expect("store operation should not fail");
                         // We don't care if no one is listening as this is a broadcast channel
                         let _ = checkpoint_event_sender.send(checkpoint.clone());
-                        tx_concurrency_remaining += checkpoint.network_total_transactions - highest_synced.network_total_transactions;
+                        let tx_count = checkpoint.network_total_transactions - highest_synced.network_total_transactions;
+                        metrics.inc_peer_synced_checkpoints(tx_count);
+                        tx_concurrency_remaining += tx_count;
                         highest_synced = checkpoint;
 
                     }
@@ -10,6 +10,7 @@ use crate::{
 };
 use anemo::{PeerId, Request};
 use anyhow::anyhow;
+use prometheus::proto::MetricType;
 use prometheus::Registry;
 use std::num::NonZeroUsize;
 use std::{collections::HashMap, time::Duration};
@@ -17,6 +18,7 @@ use sui_archival::reader::ArchiveReaderBalancer;
 use sui_archival::writer::ArchiveWriter;
 use sui_config::node::ArchiveReaderConfig;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+use sui_config::p2p::StateSyncConfig;
 use sui_storage::{FileCompression, StorageFormat};
 use sui_swarm_config::test_utils::{empty_contents, CommitteeFixture};
 use sui_types::{
@@ -446,6 +448,179 @@ async fn test_state_sync_using_archive() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_state_sync_imports_from_archive_when_far_behind() -> anyhow::Result<()> {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    // build mock data
+    let (ordered_checkpoints, _, sequence_number_to_digest, checkpoints) =
+        committee.make_empty_checkpoints(100, None);
+    let last_checkpoint = *ordered_checkpoints.last().unwrap().sequence_number();
+    // Initialize archive store with the first half of the checkpoints
+    let temp_dir = tempdir()?.into_path();
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir.join("local_dir")),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir.join("remote_dir")),
+        ..Default::default()
+    };
+    let archive_writer = ArchiveWriter::new(
+        local_store_config,
+        remote_store_config.clone(),
+        FileCompression::Zstd,
+        StorageFormat::Blob,
+        Duration::from_secs(10),
+        20,
+        &Registry::default(),
+    )
+    .await?;
+    let test_store = SharedInMemoryStore::default();
+    test_store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+    for checkpoint in &ordered_checkpoints[0..50] {
+        test_store.inner_mut().insert_checkpoint(checkpoint);
+    }
+    let kill = archive_writer.start(test_store).await?;
+    let archive_reader_config = ArchiveReaderConfig {
+        remote_store_config,
+        download_concurrency: NonZeroUsize::new(1).unwrap(),
+        use_for_pruning_watermark: false,
+    };
+    let archive_import_threshold = 10;
+    let archive_readers =
+        ArchiveReaderBalancer::new(vec![archive_reader_config], &Registry::default())?;
+    let archive_reader = archive_readers.pick_one_random(0..u64::MAX).await.unwrap();
+    loop {
+        archive_reader.sync_manifest_once().await?;
+        if let Ok(latest_available_checkpoint_in_archive) =
+            archive_reader.latest_available_checkpoint().await
+        {
+            // Node 1 must be more than the threshold behind the archive for the import to start
+            if latest_available_checkpoint_in_archive > 2 * archive_import_threshold {
+                break;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    // Stop the writer so that the tip of the archive no longer moves
+    kill.send(())?;
+    archive_reader.sync_manifest_once().await?;
+    let archive_tip = archive_reader.latest_available_checkpoint().await?;
+
+    // Build and connect two nodes where Node 1 is configured to bulk import from the archive, and
+    // Node 2 has all checkpoints, so Node 1 syncs the ones past the archive's tip from it
+    let registry = Registry::new();
+    let (builder, server) = Builder::new()
+        .store(SharedInMemoryStore::default())
+        .config(StateSyncConfig {
+            archive_import_threshold: Some(archive_import_threshold),
+            ..Default::default()
+        })
+        .archive_readers(archive_readers)
+        .with_metrics(&registry)
+        .build();
+    let network_1 = build_network(|router| router.add_rpc_service(server));
+    let (event_loop_1, _handle_1) = builder.build(network_1.clone());
+    let (builder, server) = Builder::new().store(SharedInMemoryStore::default()).build();
+    let network_2 = build_network(|router| router.add_rpc_service(server));
+    let (event_loop_2, _handle_2) = builder.build(network_2.clone());
+    network_1.connect(network_2.local_addr()).await.unwrap();
+
+    // Init the root committee in both nodes
+    event_loop_1.store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+    event_loop_2.store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+    {
+        let mut store = event_loop_2.store.inner_mut();
+        for checkpoint in ordered_checkpoints.clone() {
+            store.insert_checkpoint(&checkpoint);
+            store.insert_checkpoint_contents(&checkpoint, empty_contents());
+            store.update_highest_synced_checkpoint(&checkpoint);
+        }
+    }
+
+    // Node 1 knows how far behind Node 2 it is from the start
+    event_loop_1.peer_heights.write().unwrap().peers.insert(
+        network_2.peer_id(),
+        PeerStateSyncInfo {
+            genesis_checkpoint_digest: *ordered_checkpoints[0].digest(),
+            on_same_chain_as_us: true,
+            height: last_checkpoint,
+            lowest: 0,
+        },
+    );
+
+    let store_1 = event_loop_1.store.clone();
+    tokio::spawn(event_loop_1.start());
+    tokio::spawn(event_loop_2.start());
+
+    timeout(Duration::from_secs(120), async {
+        loop {
+            let highest_synced = store_1.inner().get_highest_synced_checkpoint().cloned();
+            if highest_synced.is_some_and(|c| c.sequence_number == last_checkpoint) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    })
+    .await?;
+
+    let store = store_1.inner();
+    let expected = checkpoints
+        .iter()
+        .map(|(key, value)| (key, value.data()))
+        .collect::<HashMap<_, _>>();
+    let actual = store
+        .checkpoints()
+        .iter()
+        .map(|(key, value)| (key, value.data()))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(actual, expected);
+    assert_eq!(
+        store.checkpoint_sequence_number_to_digest(),
+        &sequence_number_to_digest
+    );
+
+    // Everything up to the archive's tip was imported from it, and the rest synced from Node 2
+    assert_eq!(metric_value(&registry, "archive_import_in_progress"), 0.0);
+    assert_eq!(
+        metric_value(&registry, "archive_imported_checkpoints"),
+        archive_tip as f64
+    );
+    assert_eq!(
+        metric_value(&registry, "peer_synced_checkpoints"),
+        (last_checkpoint - archive_tip) as f64
+    );
+    Ok(())
+}
+
+fn metric_value(registry: &Registry, name: &str) -> f64 {
+    let family = registry
+        .gather()
+        .into_iter()
+        .find(|family| family.get_name() == name)
+        .unwrap_or_else(|| panic!("metric {name} is not registered"));
+    let metric = &family.get_metric()[0];
+    match family.get_field_type() {
+        MetricType::COUNTER => metric.get_counter().get_value(),
+        MetricType::GAUGE => metric.get_gauge().get_value(),
+        field_type => panic!("unexpected type {field_type:?} of metric {name}"),
+    }
+}
+
 #[tokio::test]
 async fn sync_with_checkpoints_being_inserted() {
     telemetry_subscribers::init_for_testing();
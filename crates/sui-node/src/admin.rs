@@ -68,6 +68,10 @@ use tracing::info;
 // Inject a full signature from another node, bypassing validity checks.
 //
 //  $ curl 'http://127.0.0.1:1337/randomness-inject-full-sig?round=123&sigs=base64encodedsig'
+//
+// View the token buckets of the most throttled JSON-RPC clients, optionally filtered by IP.
+//
+//  $ curl 'http://127.0.0.1:1337/traffic-control-buckets?ip=1.2.3.4'
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -80,6 +84,7 @@ const NODE_CONFIG: &str = "/node-config";
 const RANDOMNESS_PARTIAL_SIGS_ROUTE: &str = "/randomness-partial-sigs";
 const RANDOMNESS_INJECT_PARTIAL_SIGS_ROUTE: &str = "/randomness-inject-partial-sigs";
 const RANDOMNESS_INJECT_FULL_SIG_ROUTE: &str = "/randomness-inject-full-sig";
+const TRAFFIC_CONTROL_BUCKETS_ROUTE: &str = "/traffic-control-buckets";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -119,6 +124,7 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
             RANDOMNESS_INJECT_FULL_SIG_ROUTE,
             post(randomness_inject_full_sig),
         )
+        .route(TRAFFIC_CONTROL_BUCKETS_ROUTE, get(traffic_control_buckets))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -443,3 +449,43 @@ async fn randomness_inject_full_sig(
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
     }
 }
+
+#[derive(Deserialize)]
+struct TrafficControlBuckets {
+    ip: Option<String>,
+}
+
+async fn traffic_control_buckets(
+    State(state): State<Arc<AppState>>,
+    query: Query<TrafficControlBuckets>,
+) -> (StatusCode, String) {
+    let Query(TrafficControlBuckets { ip }) = query;
+    let ip = match ip.map(|ip| IpAddr::from_str(&ip)).transpose() {
+        Ok(ip) => ip,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+
+    let traffic_controller_state = state.node.traffic_controller_state();
+    let mut output = String::new();
+    for (policy, buckets) in [
+        ("spam", traffic_controller_state.spam_client_buckets()),
+        ("error", traffic_controller_state.error_client_buckets()),
+    ] {
+        for bucket in buckets
+            .iter()
+            .filter(|bucket| ip.map_or(true, |ip| bucket.client == ip))
+        {
+            output.push_str(&format!(
+                "policy={} client={} proxied={} tokens={:.2}/{} last_method={:?}\n",
+                policy,
+                bucket.client,
+                bucket.proxied,
+                bucket.tokens,
+                bucket.capacity,
+                bucket.last_method,
+            ));
+        }
+    }
+
+    (StatusCode::OK, output)
+}
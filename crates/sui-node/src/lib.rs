@@ -36,6 +36,7 @@ use sui_core::execution_cache::build_execution_cache;
 use sui_core::state_accumulator::StateAccumulatorMetrics;
 use sui_core::storage::RestReadStore;
 use sui_core::traffic_controller::metrics::TrafficControllerMetrics;
+use sui_core::traffic_controller::TrafficControllerState;
 use sui_json_rpc::bridge_api::BridgeReadApi;
 use sui_json_rpc_api::JsonRpcMetrics;
 use sui_network::randomness;
@@ -230,6 +231,8 @@ pub struct SuiNode {
     validator_components: Mutex<Option<ValidatorComponents>>,
     /// The http server responsible for serving JSON-RPC as well as the experimental rest service
     _http_server: Option<tokio::task::JoinHandle<()>>,
+    /// State of the JSON-RPC traffic controller policies, exposed via the admin server
+    traffic_controller_state: TrafficControllerState,
     state: Arc<AuthorityState>,
     transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     registry_service: RegistryService,
@@ -769,6 +772,7 @@ impl SuiNode {
             None
         };
 
+        let traffic_controller_state = TrafficControllerState::default();
         let http_server = build_http_server(
             state.clone(),
             state_sync_store,
@@ -777,6 +781,7 @@ impl SuiNode {
             &prometheus_registry,
             custom_rpc_runtime,
             software_version,
+            traffic_controller_state.clone(),
         )
         .await?;
 
@@ -842,6 +847,7 @@ impl SuiNode {
             config,
             validator_components: Mutex::new(validator_components),
             _http_server: http_server,
+            traffic_controller_state,
             state,
             transaction_orchestrator,
             registry_service,
@@ -973,6 +979,7 @@ impl SuiNode {
                 60,
                 prometheus_registry,
                 checkpoint_store,
+                config.state_snapshot_write_config.write_delta_snapshots,
             )?;
             Ok(Some(snapshot_uploader.start()))
         } else {
@@ -1512,6 +1519,10 @@ impl SuiNode {
         self.state.clone()
     }
 
+    pub fn traffic_controller_state(&self) -> TrafficControllerState {
+        self.traffic_controller_state.clone()
+    }
+
     // Only used for testing because of how epoch store is loaded.
     pub fn reference_gas_price_for_testing(&self) -> Result<u64, anyhow::Error> {
         self.state.reference_gas_price_for_testing()
@@ -2023,6 +2034,7 @@ pub async fn build_http_server(
     prometheus_registry: &Registry,
     _custom_runtime: Option<Handle>,
     software_version: &'static str,
+    traffic_controller_state: TrafficControllerState,
 ) -> Result<Option<tokio::task::JoinHandle<()>>> {
     // Validators do not expose these APIs
     if config.consensus_config().is_some() {
@@ -2038,6 +2050,7 @@ pub async fn build_http_server(
             config.policy_config.clone(),
             config.firewall_config.clone(),
         );
+        server.with_traffic_controller_state(traffic_controller_state);
 
         let kv_store = build_kv_store(&state, config, prometheus_registry)?;
 
@@ -13,17 +13,21 @@ use tonic::metadata::MetadataMap;
 
 use crate::proto::node::node_client::NodeClient;
 use crate::proto::node::{
-    ExecuteTransactionResponse, GetCheckpointResponse, GetFullCheckpointResponse, GetObjectResponse,
+    ExecuteTransactionResponse, GetCheckpointResponse, GetFullCheckpointResponse,
+    GetObjectResponse, SimulateTransactionResponse,
 };
 use crate::proto::types::Bcs;
 use crate::proto::TryFromProtoError;
+use crate::rest::accounts::{AccountOwnedObjectInfo, Balance};
+use crate::rest::objects::DynamicFieldInfo;
 use crate::types::ExecuteTransactionOptions;
-use sui_types::base_types::{ObjectID, SequenceNumber};
+use move_core_types::language_storage::StructTag;
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
 use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
 use sui_types::object::Object;
-use sui_types::transaction::Transaction;
+use sui_types::transaction::{Transaction, TransactionData};
 
 pub type Result<T, E = tonic::Status> = std::result::Result<T, E>;
 
@@ -217,6 +221,119 @@ impl Client {
         execute_transaction_response_try_from_proto(response)
             .map_err(|e| status_from_error_with_metadata(e, metadata))
     }
+
+    pub async fn simulate_transaction(
+        &self,
+        transaction: &TransactionData,
+        balance_changes: bool,
+    ) -> Result<TransactionSimulationResponse> {
+        let request = crate::proto::node::SimulateTransactionRequest {
+            transaction: None,
+            transaction_bcs: Some(
+                crate::proto::types::Bcs::serialize(transaction)
+                    .map_err(|e| Status::from_error(e.into()))?,
+            ),
+            options: Some(crate::proto::node::SimulateTransactionOptions {
+                effects: Some(false),
+                effects_bcs: Some(true),
+                events: Some(false),
+                events_bcs: Some(true),
+                balance_changes: Some(balance_changes),
+            }),
+        };
+
+        let (metadata, response, _extentions) = self
+            .raw_client()
+            .simulate_transaction(request)
+            .await?
+            .into_parts();
+
+        simulate_transaction_response_try_from_proto(response)
+            .map_err(|e| status_from_error_with_metadata(e, metadata))
+    }
+
+    pub async fn list_owned_objects(
+        &self,
+        owner: SuiAddress,
+        page_size: Option<u32>,
+        page_token: Option<bytes::Bytes>,
+    ) -> Result<Page<AccountOwnedObjectInfo>> {
+        let request = crate::proto::node::ListOwnedObjectsRequest {
+            owner: Some(sui_sdk_types::types::Address::from(owner).into()),
+            page_size,
+            page_token,
+        };
+
+        let (metadata, response, _extentions) = self
+            .raw_client()
+            .list_owned_objects(request)
+            .await?
+            .into_parts();
+
+        page_try_from_proto(&response.objects, response.next_page_token)
+            .map_err(|e| status_from_error_with_metadata(e, metadata))
+    }
+
+    pub async fn get_balance(&self, owner: SuiAddress, coin_type: &StructTag) -> Result<Balance> {
+        let coin_type =
+            sui_types::sui_sdk_types_conversions::struct_tag_core_to_sdk(coin_type.clone())
+                .map_err(|e| Status::from_error(e.into()))?;
+        let request = crate::proto::node::GetBalanceRequest {
+            owner: Some(sui_sdk_types::types::Address::from(owner).into()),
+            coin_type: Some(coin_type.into()),
+        };
+
+        let (metadata, response, _extentions) =
+            self.raw_client().get_balance(request).await?.into_parts();
+
+        response
+            .balance
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("balance"))
+            .and_then(TryInto::try_into)
+            .map_err(|e| status_from_error_with_metadata(e, metadata))
+    }
+
+    pub async fn list_balances(
+        &self,
+        owner: SuiAddress,
+        page_size: Option<u32>,
+        page_token: Option<bytes::Bytes>,
+    ) -> Result<Page<Balance>> {
+        let request = crate::proto::node::ListBalancesRequest {
+            owner: Some(sui_sdk_types::types::Address::from(owner).into()),
+            page_size,
+            page_token,
+        };
+
+        let (metadata, response, _extentions) =
+            self.raw_client().list_balances(request).await?.into_parts();
+
+        page_try_from_proto(&response.balances, response.next_page_token)
+            .map_err(|e| status_from_error_with_metadata(e, metadata))
+    }
+
+    pub async fn list_dynamic_fields(
+        &self,
+        parent: ObjectID,
+        page_size: Option<u32>,
+        page_token: Option<bytes::Bytes>,
+    ) -> Result<Page<DynamicFieldInfo>> {
+        let request = crate::proto::node::ListDynamicFieldsRequest {
+            parent: Some(sui_sdk_types::types::ObjectId::from(parent).into()),
+            page_size,
+            page_token,
+        };
+
+        let (metadata, response, _extentions) = self
+            .raw_client()
+            .list_dynamic_fields(request)
+            .await?
+            .into_parts();
+
+        page_try_from_proto(&response.dynamic_fields, response.next_page_token)
+            .map_err(|e| status_from_error_with_metadata(e, metadata))
+    }
 }
 
 #[derive(Debug)]
@@ -228,6 +345,23 @@ pub struct TransactionExecutionResponse {
     pub balance_changes: Option<Vec<sui_sdk_types::types::BalanceChange>>,
 }
 
+#[derive(Debug)]
+pub struct TransactionSimulationResponse {
+    pub effects: TransactionEffects,
+    pub events: Option<TransactionEvents>,
+    pub balance_changes: Option<Vec<sui_sdk_types::types::BalanceChange>>,
+    /// The gas that would be charged if the transaction were executed.
+    pub gas_used: sui_sdk_types::types::GasCostSummary,
+}
+
+/// A single page of results from one of the `List*` apis.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Token to pass as `page_token` to fetch the next page, if there is one.
+    pub next_page_token: Option<bytes::Bytes>,
+}
+
 /// Attempts to parse `CertifiedCheckpointSummary` from the bcs fields in `GetCheckpointResponse`
 fn certified_checkpoint_summary_try_from_proto(
     summary_bcs: Option<Bcs>,
@@ -388,6 +522,68 @@ fn execute_transaction_response_try_from_proto(
     .pipe(Ok)
 }
 
+/// Attempts to parse `TransactionSimulationResponse` from the fields in `SimulateTransactionResponse`
+fn simulate_transaction_response_try_from_proto(
+    SimulateTransactionResponse {
+        effects_bcs,
+        events_bcs,
+        balance_changes,
+        gas_used,
+        ..
+    }: SimulateTransactionResponse,
+) -> Result<TransactionSimulationResponse, TryFromProtoError> {
+    let effects = effects_bcs
+        .ok_or_else(|| TryFromProtoError::missing("effects_bcs"))?
+        .deserialize()
+        .map_err(TryFromProtoError::from_error)?;
+    let events = events_bcs
+        .map(|bcs| bcs.deserialize())
+        .transpose()
+        .map_err(TryFromProtoError::from_error)?;
+
+    let balance_changes = balance_changes
+        .map(|balance_changes| {
+            balance_changes
+                .balance_changes
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+
+    let gas_used = gas_used
+        .as_ref()
+        .ok_or_else(|| TryFromProtoError::missing("gas_used"))?
+        .try_into()?;
+
+    TransactionSimulationResponse {
+        effects,
+        events,
+        balance_changes,
+        gas_used,
+    }
+    .pipe(Ok)
+}
+
+/// Attempts to parse a `Page` from the entries and page token of a `List*` response
+fn page_try_from_proto<'a, P, T>(
+    items: &'a [P],
+    next_page_token: Option<bytes::Bytes>,
+) -> Result<Page<T>, TryFromProtoError>
+where
+    T: TryFrom<&'a P, Error = TryFromProtoError>,
+{
+    let items = items
+        .iter()
+        .map(TryInto::try_into)
+        .collect::<Result<_, _>>()?;
+
+    Ok(Page {
+        items,
+        next_page_token,
+    })
+}
+
 fn status_from_error_with_metadata<T: Into<BoxError>>(err: T, metadata: MetadataMap) -> Status {
     let mut status = Status::from_error(err.into());
     *status.metadata_mut() = metadata;
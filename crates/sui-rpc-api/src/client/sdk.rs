@@ -21,7 +21,9 @@ use sui_sdk_types::types::Version;
 use tap::Pipe;
 
 use crate::rest::accounts::AccountOwnedObjectInfo;
+use crate::rest::accounts::Balance;
 use crate::rest::accounts::ListAccountOwnedObjectsQueryParameters;
+use crate::rest::accounts::ListBalancesQueryParameters;
 use crate::rest::checkpoints::ListCheckpointsPaginationParameters;
 use crate::rest::coins::CoinInfo;
 use crate::rest::health::Threshold;
@@ -123,6 +125,32 @@ impl Client {
         self.json(request).await
     }
 
+    pub async fn get_balance(
+        &self,
+        account: Address,
+        coin_type: &StructTag,
+    ) -> Result<Response<Balance>> {
+        let url = self
+            .url()
+            .join(&format!("accounts/{account}/balances/{coin_type}"))?;
+
+        let request = self.inner.get(url);
+
+        self.json(request).await
+    }
+
+    pub async fn list_balances(
+        &self,
+        account: Address,
+        parameters: &ListBalancesQueryParameters,
+    ) -> Result<Response<Vec<Balance>>> {
+        let url = self.url().join(&format!("accounts/{account}/balances"))?;
+
+        let request = self.inner.get(url).query(parameters);
+
+        self.json(request).await
+    }
+
     pub async fn get_object(&self, object_id: ObjectId) -> Result<Response<Object>> {
         let url = self.url().join(&format!("objects/{object_id}"))?;
 
@@ -250,4 +250,229 @@ impl crate::proto::node::node_server::Node for crate::RpcService {
             .map(tonic::Response::new)
             .map_err(Into::into)
     }
+
+    async fn simulate_transaction(
+        &self,
+        request: tonic::Request<crate::proto::node::SimulateTransactionRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::SimulateTransactionResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let transaction = match (request.transaction, request.transaction_bcs) {
+            (Some(_), Some(_)) => {
+                return Err(tonic::Status::new(
+                    tonic::Code::InvalidArgument,
+                    "only one of `transaction` or `transaction_bcs` can be provided",
+                ))
+            }
+            (Some(transaction), None) => (&transaction).try_into().map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::InvalidArgument,
+                    format!("invalid transaction: {e}"),
+                )
+            })?,
+
+            (None, Some(bcs)) => bcs::from_bytes(bcs.bcs()).map_err(|_| {
+                tonic::Status::new(tonic::Code::InvalidArgument, "invalid transaction bcs")
+            })?,
+
+            (None, None) => {
+                return Err(tonic::Status::new(
+                    tonic::Code::InvalidArgument,
+                    "one of `transaction` or `transaction_bcs` must be provided",
+                ))
+            }
+        };
+
+        let options = request.options.unwrap_or_default();
+        let parameters = crate::rest::transactions::SimulateTransactionQueryParameters {
+            balance_changes: options.balance_changes.unwrap_or(false),
+            input_objects: false,
+            output_objects: false,
+        };
+
+        let crate::rest::transactions::TransactionSimulationResponse {
+            effects,
+            events,
+            balance_changes,
+            ..
+        } = self
+            .clone()
+            .simulate_transaction(&parameters, transaction)?;
+
+        let gas_used = match &effects {
+            sui_sdk_types::types::TransactionEffects::V1(effects) => effects.gas_used.clone(),
+            sui_sdk_types::types::TransactionEffects::V2(effects) => effects.gas_used.clone(),
+        };
+        let effects_bcs = options
+            .effects_bcs
+            .unwrap_or(false)
+            .then(|| crate::proto::types::Bcs::serialize(&effects))
+            .transpose()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let events_bcs = options
+            .events_bcs
+            .unwrap_or(false)
+            .then(|| events.as_ref().map(crate::proto::types::Bcs::serialize))
+            .flatten()
+            .transpose()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        crate::proto::node::SimulateTransactionResponse {
+            effects: options.effects.unwrap_or(true).then(|| effects.into()),
+            effects_bcs,
+            events: options
+                .events
+                .unwrap_or(true)
+                .then(|| events.map(Into::into))
+                .flatten(),
+            events_bcs,
+            balance_changes: balance_changes.map(|balance_changes| {
+                crate::proto::node::BalanceChanges {
+                    balance_changes: balance_changes.into_iter().map(Into::into).collect(),
+                }
+            }),
+            gas_used: Some(gas_used.into()),
+        }
+        .pipe(tonic::Response::new)
+        .pipe(Ok)
+    }
+
+    async fn list_owned_objects(
+        &self,
+        request: tonic::Request<crate::proto::node::ListOwnedObjectsRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::ListOwnedObjectsResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let owner = request
+            .owner
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing owner"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid owner"))?;
+        let start = decode_page_token(request.page_token.as_ref())?;
+
+        let (objects, cursor) =
+            self.list_owned_objects(owner, page_size(request.page_size), start)?;
+
+        crate::proto::node::ListOwnedObjectsResponse {
+            objects: objects.into_iter().map(Into::into).collect(),
+            next_page_token: encode_page_token(cursor)?,
+        }
+        .pipe(tonic::Response::new)
+        .pipe(Ok)
+    }
+
+    async fn get_balance(
+        &self,
+        request: tonic::Request<crate::proto::node::GetBalanceRequest>,
+    ) -> std::result::Result<tonic::Response<crate::proto::node::GetBalanceResponse>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let owner = request
+            .owner
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing owner"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid owner"))?;
+        let coin_type = request
+            .coin_type
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing coin_type"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid coin_type"))?;
+
+        let balance = self.get_balance(owner, coin_type)?;
+
+        crate::proto::node::GetBalanceResponse {
+            balance: Some(balance.into()),
+        }
+        .pipe(tonic::Response::new)
+        .pipe(Ok)
+    }
+
+    async fn list_balances(
+        &self,
+        request: tonic::Request<crate::proto::node::ListBalancesRequest>,
+    ) -> std::result::Result<tonic::Response<crate::proto::node::ListBalancesResponse>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let owner = request
+            .owner
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing owner"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid owner"))?;
+        let start = decode_page_token(request.page_token.as_ref())?;
+
+        let (balances, cursor) = self.list_balances(owner, page_size(request.page_size), start)?;
+
+        crate::proto::node::ListBalancesResponse {
+            balances: balances.into_iter().map(Into::into).collect(),
+            next_page_token: encode_page_token(cursor)?,
+        }
+        .pipe(tonic::Response::new)
+        .pipe(Ok)
+    }
+
+    async fn list_dynamic_fields(
+        &self,
+        request: tonic::Request<crate::proto::node::ListDynamicFieldsRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::ListDynamicFieldsResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let parent = request
+            .parent
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing parent"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid parent"))?;
+        let start = decode_page_token(request.page_token.as_ref())?;
+
+        let (dynamic_fields, cursor) =
+            self.list_dynamic_fields(parent, page_size(request.page_size), start)?;
+
+        crate::proto::node::ListDynamicFieldsResponse {
+            dynamic_fields: dynamic_fields.into_iter().map(Into::into).collect(),
+            next_page_token: encode_page_token(cursor)?,
+        }
+        .pipe(tonic::Response::new)
+        .pipe(Ok)
+    }
+}
+
+/// Clamp a requested page size to the bounds used by the REST API.
+fn page_size(page_size: Option<u32>) -> usize {
+    page_size
+        .map(|size| (size as usize).clamp(1, crate::rest::MAX_PAGE_SIZE))
+        .unwrap_or(crate::rest::DEFAULT_PAGE_SIZE)
+}
+
+/// Page tokens are the BCS encoding of the first entry of the next page.
+fn decode_page_token<T: serde::de::DeserializeOwned>(
+    page_token: Option<&bytes::Bytes>,
+) -> Result<Option<T>, tonic::Status> {
+    page_token
+        .map(|token| {
+            bcs::from_bytes(token)
+                .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid page_token"))
+        })
+        .transpose()
+}
+
+fn encode_page_token<T: serde::Serialize>(
+    cursor: Option<T>,
+) -> Result<Option<bytes::Bytes>, tonic::Status> {
+    cursor
+        .map(|cursor| {
+            bcs::to_bytes(&cursor)
+                .map(Into::into)
+                .map_err(|e| tonic::Status::internal(e.to_string()))
+        })
+        .transpose()
 }
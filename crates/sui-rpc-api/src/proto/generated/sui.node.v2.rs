@@ -433,6 +433,204 @@ pub struct ExecuteTransactionOptions {
     #[prost(bool, optional, tag = "8")]
     pub balance_changes: ::core::option::Option<bool>,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulateTransactionRequest {
+    #[prost(message, optional, tag = "1")]
+    pub transaction: ::core::option::Option<super::super::types::Transaction>,
+    #[prost(message, optional, tag = "2")]
+    pub transaction_bcs: ::core::option::Option<super::super::types::Bcs>,
+    #[prost(message, optional, tag = "3")]
+    pub options: ::core::option::Option<SimulateTransactionOptions>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SimulateTransactionOptions {
+    /// Include the sui.types.TransactionEffects message in the response.
+    ///
+    /// Defaults to true if not included
+    #[prost(bool, optional, tag = "1")]
+    pub effects: ::core::option::Option<bool>,
+    /// Include the TransactionEffects formatted as BCS in the response.
+    ///
+    /// Defaults to false if not included
+    #[prost(bool, optional, tag = "2")]
+    pub effects_bcs: ::core::option::Option<bool>,
+    /// Include the sui.types.TransactionEvents message in the response.
+    ///
+    /// Defaults to true if not included
+    #[prost(bool, optional, tag = "3")]
+    pub events: ::core::option::Option<bool>,
+    /// Include the TransactionEvents formatted as BCS in the response.
+    ///
+    /// Defaults to false if not included
+    #[prost(bool, optional, tag = "4")]
+    pub events_bcs: ::core::option::Option<bool>,
+    /// Include the BalanceChanges in the response.
+    ///
+    /// Defaults to false if not included
+    #[prost(bool, optional, tag = "5")]
+    pub balance_changes: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulateTransactionResponse {
+    #[prost(message, optional, tag = "1")]
+    pub effects: ::core::option::Option<super::super::types::TransactionEffects>,
+    #[prost(message, optional, tag = "2")]
+    pub effects_bcs: ::core::option::Option<super::super::types::Bcs>,
+    #[prost(message, optional, tag = "3")]
+    pub events: ::core::option::Option<super::super::types::TransactionEvents>,
+    #[prost(message, optional, tag = "4")]
+    pub events_bcs: ::core::option::Option<super::super::types::Bcs>,
+    #[prost(message, optional, tag = "5")]
+    pub balance_changes: ::core::option::Option<BalanceChanges>,
+    /// The gas that would be charged if this transaction were executed
+    #[prost(message, optional, tag = "6")]
+    pub gas_used: ::core::option::Option<super::super::types::GasCostSummary>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOwnedObjectsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// The maximum number of entries to return.
+    ///
+    /// Defaults to 50 if not included, and is capped at 100
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListOwnedObjects` call.
+    #[prost(bytes = "bytes", optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOwnedObjectsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub objects: ::prost::alloc::vec::Vec<OwnedObject>,
+    /// A token which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OwnedObject {
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    #[prost(message, optional, tag = "2")]
+    pub object_id: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(uint64, optional, tag = "3")]
+    pub version: ::core::option::Option<u64>,
+    #[prost(message, optional, tag = "4")]
+    pub object_type: ::core::option::Option<super::super::types::StructTag>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    #[prost(message, optional, tag = "2")]
+    pub coin_type: ::core::option::Option<super::super::types::StructTag>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub balance: ::core::option::Option<Balance>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBalancesRequest {
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// The maximum number of entries to return.
+    ///
+    /// Defaults to 50 if not included, and is capped at 100
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListBalances` call.
+    #[prost(bytes = "bytes", optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBalancesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub balances: ::prost::alloc::vec::Vec<Balance>,
+    /// A token which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Balance {
+    #[prost(message, optional, tag = "1")]
+    pub coin_type: ::core::option::Option<super::super::types::StructTag>,
+    /// The sum of the balances of all coins of `coin_type` owned by the address
+    #[prost(uint64, optional, tag = "2")]
+    pub balance: ::core::option::Option<u64>,
+    /// The number of coins of `coin_type` owned by the address
+    #[prost(uint64, optional, tag = "3")]
+    pub coin_object_count: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListDynamicFieldsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub parent: ::core::option::Option<super::super::types::ObjectId>,
+    /// The maximum number of entries to return.
+    ///
+    /// Defaults to 50 if not included, and is capped at 100
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListDynamicFields` call.
+    #[prost(bytes = "bytes", optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListDynamicFieldsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub dynamic_fields: ::prost::alloc::vec::Vec<DynamicField>,
+    /// A token which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DynamicField {
+    #[prost(message, optional, tag = "1")]
+    pub parent: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(message, optional, tag = "2")]
+    pub field_id: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(enumeration = "DynamicFieldType", optional, tag = "3")]
+    pub dynamic_field_type: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "4")]
+    pub name_type: ::core::option::Option<super::super::types::TypeTag>,
+    #[prost(bytes = "bytes", optional, tag = "5")]
+    pub name_value: ::core::option::Option<::prost::bytes::Bytes>,
+    /// ObjectId of the child object when `dynamic_field_type == DYNAMIC_FIELD_TYPE_OBJECT`
+    #[prost(message, optional, tag = "6")]
+    pub dynamic_object_id: ::core::option::Option<super::super::types::ObjectId>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DynamicFieldType {
+    Unknown = 0,
+    Field = 1,
+    Object = 2,
+}
+impl DynamicFieldType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "DYNAMIC_FIELD_TYPE_UNKNOWN",
+            Self::Field => "DYNAMIC_FIELD_TYPE_FIELD",
+            Self::Object => "DYNAMIC_FIELD_TYPE_OBJECT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DYNAMIC_FIELD_TYPE_UNKNOWN" => Some(Self::Unknown),
+            "DYNAMIC_FIELD_TYPE_FIELD" => Some(Self::Field),
+            "DYNAMIC_FIELD_TYPE_OBJECT" => Some(Self::Object),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod node_client {
     #![allow(
@@ -692,6 +890,126 @@ pub mod node_client {
                 .insert(GrpcMethod::new("sui.node.v2.Node", "ExecuteTransaction"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn simulate_transaction(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SimulateTransactionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SimulateTransactionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.Node/SimulateTransaction",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.Node", "SimulateTransaction"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_owned_objects(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListOwnedObjectsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOwnedObjectsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.Node/ListOwnedObjects",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.Node", "ListOwnedObjects"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_balance(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBalanceResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.Node/GetBalance",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.Node", "GetBalance"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_balances(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBalancesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.Node/ListBalances",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.Node", "ListBalances"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_dynamic_fields(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListDynamicFieldsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListDynamicFieldsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.Node/ListDynamicFields",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.Node", "ListDynamicFields"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -756,6 +1074,41 @@ pub mod node_server {
             tonic::Response<super::ExecuteTransactionResponse>,
             tonic::Status,
         >;
+        async fn simulate_transaction(
+            &self,
+            request: tonic::Request<super::SimulateTransactionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SimulateTransactionResponse>,
+            tonic::Status,
+        >;
+        async fn list_owned_objects(
+            &self,
+            request: tonic::Request<super::ListOwnedObjectsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOwnedObjectsResponse>,
+            tonic::Status,
+        >;
+        async fn get_balance(
+            &self,
+            request: tonic::Request<super::GetBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBalanceResponse>,
+            tonic::Status,
+        >;
+        async fn list_balances(
+            &self,
+            request: tonic::Request<super::ListBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBalancesResponse>,
+            tonic::Status,
+        >;
+        async fn list_dynamic_fields(
+            &self,
+            request: tonic::Request<super::ListDynamicFieldsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListDynamicFieldsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct NodeServer<T> {
@@ -1138,6 +1491,227 @@ pub mod node_server {
                     };
                     Box::pin(fut)
                 }
+                "/sui.node.v2.Node/SimulateTransaction" => {
+                    #[allow(non_camel_case_types)]
+                    struct SimulateTransactionSvc<T: Node>(pub Arc<T>);
+                    impl<
+                        T: Node,
+                    > tonic::server::UnaryService<super::SimulateTransactionRequest>
+                    for SimulateTransactionSvc<T> {
+                        type Response = super::SimulateTransactionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SimulateTransactionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Node>::simulate_transaction(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SimulateTransactionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.Node/ListOwnedObjects" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListOwnedObjectsSvc<T: Node>(pub Arc<T>);
+                    impl<
+                        T: Node,
+                    > tonic::server::UnaryService<super::ListOwnedObjectsRequest>
+                    for ListOwnedObjectsSvc<T> {
+                        type Response = super::ListOwnedObjectsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListOwnedObjectsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Node>::list_owned_objects(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListOwnedObjectsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.Node/GetBalance" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBalanceSvc<T: Node>(pub Arc<T>);
+                    impl<T: Node> tonic::server::UnaryService<super::GetBalanceRequest>
+                    for GetBalanceSvc<T> {
+                        type Response = super::GetBalanceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBalanceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Node>::get_balance(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetBalanceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.Node/ListBalances" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListBalancesSvc<T: Node>(pub Arc<T>);
+                    impl<T: Node> tonic::server::UnaryService<super::ListBalancesRequest>
+                    for ListBalancesSvc<T> {
+                        type Response = super::ListBalancesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBalancesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Node>::list_balances(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListBalancesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.Node/ListDynamicFields" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListDynamicFieldsSvc<T: Node>(pub Arc<T>);
+                    impl<
+                        T: Node,
+                    > tonic::server::UnaryService<super::ListDynamicFieldsRequest>
+                    for ListDynamicFieldsSvc<T> {
+                        type Response = super::ListDynamicFieldsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListDynamicFieldsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Node>::list_dynamic_fields(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListDynamicFieldsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(empty_body());
@@ -964,3 +964,195 @@ impl TryFrom<&crate::proto::node::EffectsFinality> for crate::types::EffectsFina
         .pipe(Ok)
     }
 }
+
+//
+// OwnedObject
+//
+
+impl From<crate::rest::accounts::AccountOwnedObjectInfo> for OwnedObject {
+    fn from(
+        crate::rest::accounts::AccountOwnedObjectInfo {
+            owner,
+            object_id,
+            version,
+            type_,
+        }: crate::rest::accounts::AccountOwnedObjectInfo,
+    ) -> Self {
+        Self {
+            owner: Some(owner.into()),
+            object_id: Some(object_id.into()),
+            version: Some(version),
+            object_type: Some(type_.into()),
+        }
+    }
+}
+
+impl TryFrom<&OwnedObject> for crate::rest::accounts::AccountOwnedObjectInfo {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        OwnedObject {
+            owner,
+            object_id,
+            version,
+            object_type,
+        }: &OwnedObject,
+    ) -> Result<Self, Self::Error> {
+        let owner = owner
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("owner"))?
+            .pipe(TryInto::try_into)?;
+        let object_id = object_id
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("object_id"))?
+            .pipe(TryInto::try_into)?;
+        let version = version.ok_or_else(|| TryFromProtoError::missing("version"))?;
+        let type_ = object_type
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("object_type"))?
+            .pipe(TryInto::try_into)?;
+
+        Self {
+            owner,
+            object_id,
+            version,
+            type_,
+        }
+        .pipe(Ok)
+    }
+}
+
+//
+// Balance
+//
+
+impl From<crate::rest::accounts::Balance> for Balance {
+    fn from(
+        crate::rest::accounts::Balance {
+            coin_type,
+            balance,
+            coin_object_count,
+        }: crate::rest::accounts::Balance,
+    ) -> Self {
+        Self {
+            coin_type: Some(coin_type.into()),
+            balance: Some(balance),
+            coin_object_count: Some(coin_object_count),
+        }
+    }
+}
+
+impl TryFrom<&Balance> for crate::rest::accounts::Balance {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        Balance {
+            coin_type,
+            balance,
+            coin_object_count,
+        }: &Balance,
+    ) -> Result<Self, Self::Error> {
+        let coin_type = coin_type
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("coin_type"))?
+            .pipe(TryInto::try_into)?;
+        let balance = balance.ok_or_else(|| TryFromProtoError::missing("balance"))?;
+        let coin_object_count =
+            coin_object_count.ok_or_else(|| TryFromProtoError::missing("coin_object_count"))?;
+
+        Self {
+            coin_type,
+            balance,
+            coin_object_count,
+        }
+        .pipe(Ok)
+    }
+}
+
+//
+// DynamicField
+//
+
+impl From<crate::rest::objects::DynamicFieldInfo> for DynamicField {
+    fn from(
+        crate::rest::objects::DynamicFieldInfo {
+            parent,
+            field_id,
+            dynamic_field_type,
+            name_type,
+            name_value,
+            dynamic_object_id,
+        }: crate::rest::objects::DynamicFieldInfo,
+    ) -> Self {
+        let dynamic_field_type = match dynamic_field_type {
+            crate::rest::objects::DynamicFieldType::Field => DynamicFieldType::Field,
+            crate::rest::objects::DynamicFieldType::Object => DynamicFieldType::Object,
+        };
+
+        Self {
+            parent: Some(parent.into()),
+            field_id: Some(field_id.into()),
+            dynamic_field_type: Some(dynamic_field_type.into()),
+            name_type: Some(name_type.into()),
+            name_value: Some(name_value.into()),
+            dynamic_object_id: dynamic_object_id.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<&DynamicField> for crate::rest::objects::DynamicFieldInfo {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        DynamicField {
+            parent,
+            field_id,
+            dynamic_field_type,
+            name_type,
+            name_value,
+            dynamic_object_id,
+        }: &DynamicField,
+    ) -> Result<Self, Self::Error> {
+        let parent = parent
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("parent"))?
+            .pipe(TryInto::try_into)?;
+        let field_id = field_id
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("field_id"))?
+            .pipe(TryInto::try_into)?;
+        let dynamic_field_type = match dynamic_field_type
+            .ok_or_else(|| TryFromProtoError::missing("dynamic_field_type"))?
+            .pipe(DynamicFieldType::try_from)
+            .map_err(TryFromProtoError::from_error)?
+        {
+            DynamicFieldType::Field => crate::rest::objects::DynamicFieldType::Field,
+            DynamicFieldType::Object => crate::rest::objects::DynamicFieldType::Object,
+            DynamicFieldType::Unknown => {
+                return Err(TryFromProtoError::from_error("unknown dynamic_field_type"))
+            }
+        };
+        let name_type = name_type
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("name_type"))?
+            .pipe(TryInto::try_into)?;
+        let name_value = name_value
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("name_value"))?
+            .to_vec();
+        let dynamic_object_id = dynamic_object_id
+            .as_ref()
+            .map(TryInto::try_into)
+            .transpose()?;
+
+        Self {
+            parent,
+            field_id,
+            dynamic_field_type,
+            name_type,
+            name_value,
+            dynamic_object_id,
+        }
+        .pipe(Ok)
+    }
+}
@@ -1,17 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::reader::StateReader;
 use crate::rest::openapi::{ApiEndpoint, OperationBuilder, ResponseBuilder, RouteHandler};
 use crate::Result;
-use crate::{rest::PageCursor, RpcService, RpcServiceError};
+use crate::{rest::PageCursor, RpcService};
 use axum::extract::Query;
 use axum::extract::{Path, State};
 use axum::Json;
 use openapiv3::v3_1::Operation;
 use sui_sdk_types::types::{Address, ObjectId, StructTag, Version};
-use sui_types::sui_sdk_types_conversions::struct_tag_core_to_sdk;
-use tap::Pipe;
 
 pub struct ListAccountObjects;
 
@@ -48,36 +45,10 @@ impl ApiEndpoint<RpcService> for ListAccountObjects {
 async fn list_account_objects(
     Path(address): Path<Address>,
     Query(parameters): Query<ListAccountOwnedObjectsQueryParameters>,
-    State(state): State<StateReader>,
+    State(state): State<RpcService>,
 ) -> Result<(PageCursor<ObjectId>, Json<Vec<AccountOwnedObjectInfo>>)> {
-    let indexes = state
-        .inner()
-        .indexes()
-        .ok_or_else(RpcServiceError::not_found)?;
-    let limit = parameters.limit();
-    let start = parameters.start();
-
-    let mut object_info = indexes
-        .account_owned_objects_info_iter(address.into(), start)?
-        .take(limit + 1)
-        .map(|info| {
-            AccountOwnedObjectInfo {
-                owner: info.owner.into(),
-                object_id: info.object_id.into(),
-                version: info.version.into(),
-                type_: struct_tag_core_to_sdk(info.type_.into())?,
-            }
-            .pipe(Ok)
-        })
-        .collect::<Result<Vec<_>>>()?;
-
-    let cursor = if object_info.len() > limit {
-        // SAFETY: We've already verified that object_info is greater than limit, which is
-        // gaurenteed to be >= 1.
-        object_info.pop().unwrap().object_id.pipe(Some)
-    } else {
-        None
-    };
+    let (object_info, cursor) =
+        state.list_owned_objects(address, parameters.limit(), parameters.start)?;
 
     Ok((PageCursor(cursor), Json(object_info)))
 }
@@ -111,3 +82,113 @@ pub struct AccountOwnedObjectInfo {
     #[serde(rename = "type")]
     pub type_: StructTag,
 }
+
+pub struct GetBalance;
+
+impl ApiEndpoint<RpcService> for GetBalance {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/accounts/{account}/balances/{coin_type}"
+    }
+
+    fn operation(&self, generator: &mut schemars::gen::SchemaGenerator) -> Operation {
+        OperationBuilder::new()
+            .tag("Account")
+            .operation_id("GetBalance")
+            .path_parameter::<Address>("account", generator)
+            .path_parameter::<StructTag>("coin_type", generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<Balance>(generator)
+                    .build(),
+            )
+            .build()
+    }
+
+    fn handler(&self) -> crate::rest::openapi::RouteHandler<RpcService> {
+        RouteHandler::new(self.method(), get_balance)
+    }
+}
+
+async fn get_balance(
+    Path((address, coin_type)): Path<(Address, StructTag)>,
+    State(state): State<RpcService>,
+) -> Result<Json<Balance>> {
+    state.get_balance(address, coin_type).map(Json)
+}
+
+pub struct ListBalances;
+
+impl ApiEndpoint<RpcService> for ListBalances {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/accounts/{account}/balances"
+    }
+
+    fn operation(&self, generator: &mut schemars::gen::SchemaGenerator) -> Operation {
+        OperationBuilder::new()
+            .tag("Account")
+            .operation_id("ListBalances")
+            .path_parameter::<Address>("account", generator)
+            .query_parameters::<ListBalancesQueryParameters>(generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<Vec<Balance>>(generator)
+                    .header::<String>(crate::types::X_SUI_CURSOR, generator)
+                    .build(),
+            )
+            .build()
+    }
+
+    fn handler(&self) -> crate::rest::openapi::RouteHandler<RpcService> {
+        RouteHandler::new(self.method(), list_balances)
+    }
+}
+
+async fn list_balances(
+    Path(address): Path<Address>,
+    Query(parameters): Query<ListBalancesQueryParameters>,
+    State(state): State<RpcService>,
+) -> Result<(PageCursor<StructTag>, Json<Vec<Balance>>)> {
+    let limit = parameters.limit();
+    let (balances, cursor) = state.list_balances(address, limit, parameters.start)?;
+
+    Ok((PageCursor(cursor), Json(balances)))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct ListBalancesQueryParameters {
+    pub limit: Option<u32>,
+    pub start: Option<StructTag>,
+}
+
+impl ListBalancesQueryParameters {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|l| (l as usize).clamp(1, crate::rest::MAX_PAGE_SIZE))
+            .unwrap_or(crate::rest::DEFAULT_PAGE_SIZE)
+    }
+}
+
+/// The total balance of a single coin type owned by an account.
+#[serde_with::serde_as]
+#[derive(
+    Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+pub struct Balance {
+    pub coin_type: StructTag,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    #[schemars(with = "crate::rest::_schemars::U64")]
+    pub balance: u64,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    #[schemars(with = "crate::rest::_schemars::U64")]
+    pub coin_object_count: u64,
+}
@@ -36,6 +36,8 @@ pub const ENDPOINTS: &[&dyn ApiEndpoint<RpcService>] = &[
     &checkpoints::GetCheckpoint,
     // unstable APIs
     &accounts::ListAccountObjects,
+    &accounts::GetBalance,
+    &accounts::ListBalances,
     &objects::GetObject,
     &objects::GetObjectWithVersion,
     &objects::ListDynamicFields,
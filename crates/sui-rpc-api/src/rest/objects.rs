@@ -3,10 +3,9 @@
 
 use crate::types::{GetObjectOptions, ObjectResponse};
 use crate::{
-    reader::StateReader,
     rest::openapi::{ApiEndpoint, OperationBuilder, ResponseBuilder, RouteHandler},
     rest::PageCursor,
-    Result, RpcService, RpcServiceError,
+    Result, RpcService,
 };
 use axum::extract::Query;
 use axum::extract::{Path, State};
@@ -149,34 +148,10 @@ impl ApiEndpoint<RpcService> for ListDynamicFields {
 async fn list_dynamic_fields(
     Path(parent): Path<ObjectId>,
     Query(parameters): Query<ListDynamicFieldsQueryParameters>,
-    State(state): State<StateReader>,
+    State(state): State<RpcService>,
 ) -> Result<(PageCursor<ObjectId>, Json<Vec<DynamicFieldInfo>>)> {
-    let indexes = state
-        .inner()
-        .indexes()
-        .ok_or_else(RpcServiceError::not_found)?;
-
-    let limit = parameters.limit();
-    let start = parameters.start();
-
-    let mut dynamic_fields = indexes
-        .dynamic_field_iter(parent.into(), start)?
-        .take(limit + 1)
-        .map(DynamicFieldInfo::try_from)
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let cursor = if dynamic_fields.len() > limit {
-        // SAFETY: We've already verified that object_keys is greater than limit, which is
-        // gaurenteed to be >= 1.
-        dynamic_fields
-            .pop()
-            .unwrap()
-            .field_id
-            .pipe(ObjectId::from)
-            .pipe(Some)
-    } else {
-        None
-    };
+    let (dynamic_fields, cursor) =
+        state.list_dynamic_fields(parent, parameters.limit(), parameters.start)?;
 
     Ok((PageCursor(cursor), Json(dynamic_fields)))
 }
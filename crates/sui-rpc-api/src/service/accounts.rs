@@ -0,0 +1,114 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::rest::accounts::AccountOwnedObjectInfo;
+use crate::rest::accounts::Balance;
+use crate::Result;
+use crate::RpcService;
+use crate::RpcServiceError;
+use sui_sdk_types::types::Address;
+use sui_sdk_types::types::ObjectId;
+use sui_sdk_types::types::StructTag;
+use sui_types::storage::BalanceInfo;
+use sui_types::sui_sdk_types_conversions::struct_tag_core_to_sdk;
+use sui_types::sui_sdk_types_conversions::struct_tag_sdk_to_core;
+use tap::Pipe;
+
+impl RpcService {
+    pub fn list_owned_objects(
+        &self,
+        owner: Address,
+        limit: usize,
+        start: Option<ObjectId>,
+    ) -> Result<(Vec<AccountOwnedObjectInfo>, Option<ObjectId>)> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+
+        let mut object_info = indexes
+            .account_owned_objects_info_iter(owner.into(), start.map(Into::into))?
+            .take(limit + 1)
+            .map(|info| {
+                AccountOwnedObjectInfo {
+                    owner: info.owner.into(),
+                    object_id: info.object_id.into(),
+                    version: info.version.into(),
+                    type_: struct_tag_core_to_sdk(info.type_.into())?,
+                }
+                .pipe(Ok)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let cursor = if object_info.len() > limit {
+            // SAFETY: We've already verified that object_info is greater than limit, which is
+            // gaurenteed to be >= 1.
+            object_info.pop().unwrap().object_id.pipe(Some)
+        } else {
+            None
+        };
+
+        Ok((object_info, cursor))
+    }
+
+    pub fn get_balance(&self, owner: Address, coin_type: StructTag) -> Result<Balance> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+
+        let core_coin_type = struct_tag_sdk_to_core(coin_type.clone())?;
+        let BalanceInfo {
+            balance,
+            coin_object_count,
+        } = indexes
+            .get_balance(&owner.into(), &core_coin_type)?
+            .unwrap_or_default();
+
+        Ok(Balance {
+            coin_type,
+            balance,
+            coin_object_count,
+        })
+    }
+
+    pub fn list_balances(
+        &self,
+        owner: Address,
+        limit: usize,
+        start: Option<StructTag>,
+    ) -> Result<(Vec<Balance>, Option<StructTag>)> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+
+        let start = start.map(struct_tag_sdk_to_core).transpose()?;
+
+        let mut balances = indexes
+            .balance_iter(&owner.into(), start)?
+            .take(limit + 1)
+            .map(|(coin_type, info)| {
+                Balance {
+                    coin_type: struct_tag_core_to_sdk(coin_type)?,
+                    balance: info.balance,
+                    coin_object_count: info.coin_object_count,
+                }
+                .pipe(Ok)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let cursor = if balances.len() > limit {
+            // SAFETY: We've already verified that balances is greater than limit, which is
+            // gaurenteed to be >= 1.
+            balances.pop().unwrap().coin_type.pipe(Some)
+        } else {
+            None
+        };
+
+        Ok((balances, cursor))
+    }
+}
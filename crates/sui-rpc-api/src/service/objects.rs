@@ -1,10 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::rest::objects::DynamicFieldInfo;
 use crate::types::GetObjectOptions;
 use crate::types::ObjectResponse;
 use crate::Result;
 use crate::RpcService;
+use crate::RpcServiceError;
 use sui_sdk_types::types::ObjectId;
 use sui_sdk_types::types::Version;
 use tap::Pipe;
@@ -40,6 +42,35 @@ impl RpcService {
         }
         .pipe(Ok)
     }
+
+    pub fn list_dynamic_fields(
+        &self,
+        parent: ObjectId,
+        limit: usize,
+        start: Option<ObjectId>,
+    ) -> Result<(Vec<DynamicFieldInfo>, Option<ObjectId>)> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+
+        let mut dynamic_fields = indexes
+            .dynamic_field_iter(parent.into(), start.map(Into::into))?
+            .take(limit + 1)
+            .map(DynamicFieldInfo::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let cursor = if dynamic_fields.len() > limit {
+            // SAFETY: We've already verified that dynamic_fields is greater than limit, which is
+            // gaurenteed to be >= 1.
+            dynamic_fields.pop().unwrap().field_id.pipe(Some)
+        } else {
+            None
+        };
+
+        Ok((dynamic_fields, cursor))
+    }
 }
 
 #[derive(Debug)]
@@ -14,6 +14,7 @@ use std::time::Instant;
 use sui_json_rpc_types::DevInspectArgs;
 use sui_json_rpc_types::SuiData;
 
+use crate::endpoints::ReadConsistency;
 use crate::error::{Error, SuiRpcResult};
 use crate::RpcClient;
 use sui_json_rpc_api::{
@@ -84,11 +85,12 @@ impl ReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<ObjectsPage> {
-        Ok(self
-            .api
-            .http
-            .get_owned_objects(address, query, cursor, limit)
-            .await?)
+        self.api
+            .endpoints
+            .read_page(cursor.as_ref(), |http| {
+                http.get_owned_objects(address, query.clone(), cursor, limit)
+            })
+            .await
     }
 
     /// Return a paginated response with the dynamic fields owned by the given [ObjectID], or an error upon failure.
@@ -137,11 +139,12 @@ impl ReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<DynamicFieldPage> {
-        Ok(self
-            .api
-            .http
-            .get_dynamic_fields(object_id, cursor, limit)
-            .await?)
+        self.api
+            .endpoints
+            .read_page(cursor.as_ref(), |http| {
+                http.get_dynamic_fields(object_id, cursor, limit)
+            })
+            .await
     }
 
     /// Return the dynamic field object information for a specified object.
@@ -150,11 +153,10 @@ impl ReadApi {
         parent_object_id: ObjectID,
         name: DynamicFieldName,
     ) -> SuiRpcResult<SuiObjectResponse> {
-        Ok(self
-            .api
-            .http
-            .get_dynamic_field_object(parent_object_id, name)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_dynamic_field_object(parent_object_id, name.clone()))
+            .await
     }
 
     /// Return a parsed past object for the provided [ObjectID] and version, or an error upon failure.
@@ -215,11 +217,10 @@ impl ReadApi {
         version: SequenceNumber,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<SuiPastObjectResponse> {
-        Ok(self
-            .api
-            .http
-            .try_get_past_object(object_id, version, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.try_get_past_object(object_id, version, Some(options.clone())))
+            .await
     }
 
     /// Return a list of [SuiPastObjectResponse] objects, or an error upon failure.
@@ -296,11 +297,12 @@ impl ReadApi {
         past_objects: Vec<SuiGetPastObjectRequest>,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<Vec<SuiPastObjectResponse>> {
-        Ok(self
-            .api
-            .http
-            .try_multi_get_past_objects(past_objects, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| {
+                http.try_multi_get_past_objects(past_objects.clone(), Some(options.clone()))
+            })
+            .await
     }
 
     /// Return a [SuiObjectResponse] based on the provided [ObjectID] and [SuiObjectDataOptions], or an error upon failure.
@@ -354,7 +356,10 @@ impl ReadApi {
         object_id: ObjectID,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<SuiObjectResponse> {
-        Ok(self.api.http.get_object(object_id, Some(options)).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_object(object_id, Some(options.clone())))
+            .await
     }
 
     /// Return a list of [SuiObjectResponse] from the given vector of [ObjectID]s and [SuiObjectDataOptions], or an error upon failure.
@@ -406,11 +411,10 @@ impl ReadApi {
         object_ids: Vec<ObjectID>,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<Vec<SuiObjectResponse>> {
-        Ok(self
-            .api
-            .http
-            .multi_get_objects(object_ids, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.multi_get_objects(object_ids.clone(), Some(options.clone())))
+            .await
     }
 
     /// Return An object's bcs content [`Vec<u8>`] based on the provided [ObjectID], or an error upon failure.
@@ -449,7 +453,11 @@ impl ReadApi {
     /// }
     /// ```
     pub async fn get_total_transaction_blocks(&self) -> SuiRpcResult<u64> {
-        Ok(*self.api.http.get_total_transaction_blocks().await?)
+        Ok(*self
+            .api
+            .endpoints
+            .read(|http| http.get_total_transaction_blocks())
+            .await?)
     }
 
     /// Return a transaction and its effects in a [SuiTransactionBlockResponse] based on its
@@ -459,11 +467,10 @@ impl ReadApi {
         digest: TransactionDigest,
         options: SuiTransactionBlockResponseOptions,
     ) -> SuiRpcResult<SuiTransactionBlockResponse> {
-        Ok(self
-            .api
-            .http
-            .get_transaction_block(digest, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_transaction_block(digest, Some(options.clone())))
+            .await
     }
     /// Return a list of [SuiTransactionBlockResponse] based on the given vector of [TransactionDigest], or an error upon failure.
     ///
@@ -474,11 +481,10 @@ impl ReadApi {
         digests: Vec<TransactionDigest>,
         options: SuiTransactionBlockResponseOptions,
     ) -> SuiRpcResult<Vec<SuiTransactionBlockResponse>> {
-        Ok(self
-            .api
-            .http
-            .multi_get_transaction_blocks(digests, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.multi_get_transaction_blocks(digests.clone(), Some(options.clone())))
+            .await
     }
 
     /// Return the [SuiCommittee] information for the provided `epoch`, or an error upon failure.
@@ -506,7 +512,10 @@ impl ReadApi {
         &self,
         epoch: Option<BigInt<u64>>,
     ) -> SuiRpcResult<SuiCommittee> {
-        Ok(self.api.http.get_committee_info(epoch).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_committee_info(epoch))
+            .await
     }
 
     /// Return a paginated response with all transaction blocks information, or an error upon failure.
@@ -517,16 +526,20 @@ impl ReadApi {
         limit: Option<usize>,
         descending_order: bool,
     ) -> SuiRpcResult<TransactionBlocksPage> {
-        Ok(self
-            .api
-            .http
-            .query_transaction_blocks(query, cursor, limit, Some(descending_order))
-            .await?)
+        self.api
+            .endpoints
+            .read_page(cursor.as_ref(), |http| {
+                http.query_transaction_blocks(query.clone(), cursor, limit, Some(descending_order))
+            })
+            .await
     }
 
     /// Return the first four bytes of the chain's genesis checkpoint digest, or an error upon failure.
     pub async fn get_chain_identifier(&self) -> SuiRpcResult<String> {
-        Ok(self.api.http.get_chain_identifier().await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_chain_identifier())
+            .await
     }
 
     /// Return a checkpoint, or an error upon failure.
@@ -534,7 +547,10 @@ impl ReadApi {
     /// A Sui checkpoint is a sequence of transaction sets that a quorum of validators
     /// agree upon as having been executed within the Sui system.
     pub async fn get_checkpoint(&self, id: CheckpointId) -> SuiRpcResult<Checkpoint> {
-        Ok(self.api.http.get_checkpoint(id).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_checkpoint(id))
+            .await
     }
 
     /// Return a paginated list of checkpoints, or an error upon failure.
@@ -544,11 +560,12 @@ impl ReadApi {
         limit: Option<usize>,
         descending_order: bool,
     ) -> SuiRpcResult<CheckpointPage> {
-        Ok(self
-            .api
-            .http
-            .get_checkpoints(cursor, limit, descending_order)
-            .await?)
+        self.api
+            .endpoints
+            .read_page(cursor.as_ref(), |http| {
+                http.get_checkpoints(cursor, limit, descending_order)
+            })
+            .await
     }
 
     /// Return the sequence number of the latest checkpoint that has been executed, or an error upon failure.
@@ -557,8 +574,8 @@ impl ReadApi {
     ) -> SuiRpcResult<CheckpointSequenceNumber> {
         Ok(*self
             .api
-            .http
-            .get_latest_checkpoint_sequence_number()
+            .endpoints
+            .read(|http| http.get_latest_checkpoint_sequence_number())
             .await?)
     }
 
@@ -617,17 +634,20 @@ impl ReadApi {
         &self,
         package: ObjectID,
     ) -> SuiRpcResult<BTreeMap<String, SuiMoveNormalizedModule>> {
-        Ok(self
-            .api
-            .http
-            .get_normalized_move_modules_by_package(package)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_normalized_move_modules_by_package(package))
+            .await
     }
 
     // TODO(devx): we can probably cache this given an epoch
     /// Return the reference gas price, or an error upon failure.
     pub async fn get_reference_gas_price(&self) -> SuiRpcResult<u64> {
-        Ok(*self.api.http.get_reference_gas_price().await?)
+        Ok(*self
+            .api
+            .endpoints
+            .read(|http| http.get_reference_gas_price())
+            .await?)
     }
 
     /// Dry run a transaction block given the provided transaction data. Returns an error upon failure.
@@ -639,11 +659,11 @@ impl ReadApi {
         &self,
         tx: TransactionData,
     ) -> SuiRpcResult<DryRunTransactionBlockResponse> {
-        Ok(self
-            .api
-            .http
-            .dry_run_transaction_block(Base64::from_bytes(&bcs::to_bytes(&tx)?))
-            .await?)
+        let tx_bytes = Base64::from_bytes(&bcs::to_bytes(&tx)?);
+        self.api
+            .endpoints
+            .read(|http| http.dry_run_transaction_block(tx_bytes.clone()))
+            .await
     }
 
     /// Return the inspection of the transaction block, or an error upon failure.
@@ -676,17 +696,19 @@ impl ReadApi {
         epoch: Option<BigInt<u64>>,
         additional_args: Option<DevInspectArgs>,
     ) -> SuiRpcResult<DevInspectResults> {
-        Ok(self
-            .api
-            .http
-            .dev_inspect_transaction_block(
-                sender_address,
-                Base64::from_bytes(&bcs::to_bytes(&tx)?),
-                gas_price,
-                epoch,
-                additional_args,
-            )
-            .await?)
+        let tx_bytes = Base64::from_bytes(&bcs::to_bytes(&tx)?);
+        self.api
+            .endpoints
+            .read(|http| {
+                http.dev_inspect_transaction_block(
+                    sender_address,
+                    tx_bytes.clone(),
+                    gas_price,
+                    epoch,
+                    additional_args.clone(),
+                )
+            })
+            .await
     }
 
     /// Return the protocol config, or an error upon failure.
@@ -694,7 +716,10 @@ impl ReadApi {
         &self,
         version: Option<BigInt<u64>>,
     ) -> SuiRpcResult<ProtocolConfigResponse> {
-        Ok(self.api.http.get_protocol_config(version).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_protocol_config(version))
+            .await
     }
 
     pub async fn try_get_object_before_version(
@@ -702,11 +727,10 @@ impl ReadApi {
         object_id: ObjectID,
         version: SequenceNumber,
     ) -> SuiRpcResult<SuiPastObjectResponse> {
-        Ok(self
-            .api
-            .http
-            .try_get_object_before_version(object_id, version)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.try_get_object_before_version(object_id, version))
+            .await
     }
 }
 
@@ -751,11 +775,12 @@ impl CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<CoinPage> {
-        Ok(self
-            .api
-            .http
-            .get_coins(owner, coin_type, cursor, limit)
-            .await?)
+        self.api
+            .endpoints
+            .read_page(cursor.as_ref(), |http| {
+                http.get_coins(owner, coin_type.clone(), cursor, limit)
+            })
+            .await
     }
     /// Return a paginated response with all the coins for the given address, or an error upon failure.
     ///
@@ -785,7 +810,12 @@ impl CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<CoinPage> {
-        Ok(self.api.http.get_all_coins(owner, cursor, limit).await?)
+        self.api
+            .endpoints
+            .read_page(cursor.as_ref(), |http| {
+                http.get_all_coins(owner, cursor, limit)
+            })
+            .await
     }
 
     /// Return the coins for the given address as a stream.
@@ -925,7 +955,10 @@ impl CoinReadApi {
         owner: SuiAddress,
         coin_type: Option<String>,
     ) -> SuiRpcResult<Balance> {
-        Ok(self.api.http.get_balance(owner, coin_type).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_balance(owner, coin_type.clone()))
+            .await
     }
 
     /// Return a list of balances for each coin type owned by the given address,
@@ -952,7 +985,10 @@ impl CoinReadApi {
     /// }
     /// ```
     pub async fn get_all_balances(&self, owner: SuiAddress) -> SuiRpcResult<Vec<Balance>> {
-        Ok(self.api.http.get_all_balances(owner).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_all_balances(owner))
+            .await
     }
 
     /// Return the coin metadata (name, symbol, description, decimals, etc.) for a given coin type,
@@ -976,7 +1012,10 @@ impl CoinReadApi {
         &self,
         coin_type: String,
     ) -> SuiRpcResult<Option<SuiCoinMetadata>> {
-        Ok(self.api.http.get_coin_metadata(coin_type).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_coin_metadata(coin_type.clone()))
+            .await
     }
 
     /// Return the total supply for a given coin type, or an error upon failure.
@@ -997,7 +1036,10 @@ impl CoinReadApi {
     /// }
     /// ```
     pub async fn get_total_supply(&self, coin_type: String) -> SuiRpcResult<Supply> {
-        Ok(self.api.http.get_total_supply(coin_type).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_total_supply(coin_type.clone()))
+            .await
     }
 }
 
@@ -1058,7 +1100,10 @@ impl EventApi {
 
     /// Return a list of events for the given transaction digest, or an error upon failure.
     pub async fn get_events(&self, digest: TransactionDigest) -> SuiRpcResult<Vec<SuiEvent>> {
-        Ok(self.api.http.get_events(digest).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_events(digest))
+            .await
     }
 
     /// Return a paginated response with events for the given event filter, or an error upon failure.
@@ -1072,11 +1117,12 @@ impl EventApi {
         limit: Option<usize>,
         descending_order: bool,
     ) -> SuiRpcResult<EventPage> {
-        Ok(self
-            .api
-            .http
-            .query_events(query, cursor, limit, Some(descending_order))
-            .await?)
+        self.api
+            .endpoints
+            .read_page(cursor.as_ref(), |http| {
+                http.query_events(query.clone(), cursor, limit, Some(descending_order))
+            })
+            .await
     }
 
     /// Return a stream of events for the given event filter.
@@ -1128,6 +1174,10 @@ impl QuorumDriverApi {
     /// but returned `confirmed_local_execution` is false, the client will
     /// keep retry for WAIT_FOR_LOCAL_EXECUTION_RETRY_COUNT times. If it
     /// still fails, it will return an error.
+    ///
+    /// The transaction is sent to the first healthy endpoint of the client, and is not retried
+    /// on other endpoints. With [ReadConsistency::ReadAfterWrite], this also waits for the
+    /// transaction to be checkpointed, and pins later reads to its checkpoint.
     pub async fn execute_transaction_block(
         &self,
         tx: Transaction,
@@ -1137,9 +1187,10 @@ impl QuorumDriverApi {
         let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
         let request_type = request_type.unwrap_or_else(|| options.default_execution_request_type());
 
+        let endpoints = &self.api.endpoints;
+        let endpoint = endpoints.write_endpoint();
         let start = Instant::now();
-        let response = self
-            .api
+        let result = endpoint
             .http
             .execute_transaction_block(
                 tx_bytes.clone(),
@@ -1149,9 +1200,18 @@ impl QuorumDriverApi {
                 // It will default to WaitForEffectsCert on the RPC nodes.
                 None,
             )
-            .await?;
+            .await;
+        endpoint.record(&result, endpoints.config().failure_backoff);
+        let response = result?;
 
-        if let ExecuteTransactionRequestType::WaitForEffectsCert = request_type {
+        // With read-after-write consistency, later reads are pinned to the checkpoint of the
+        // transaction, so wait until it is known, whatever the request type.
+        let pin_reads = endpoints.config().read_consistency == ReadConsistency::ReadAfterWrite;
+        let wait_for_effects_cert = matches!(
+            request_type,
+            ExecuteTransactionRequestType::WaitForEffectsCert
+        );
+        if wait_for_effects_cert && !pin_reads {
             return Ok(response);
         }
 
@@ -1164,13 +1224,14 @@ impl QuorumDriverApi {
             loop {
                 interval.tick().await;
 
-                if let Ok(poll_response) = self
-                    .api
+                if let Ok(poll_response) = endpoint
                     .http
                     .get_transaction_block(*tx.digest(), Some(options.clone()))
                     .await
                 {
-                    break poll_response;
+                    if !pin_reads || poll_response.checkpoint.is_some() {
+                        break poll_response;
+                    }
                 }
             }
         })
@@ -1179,6 +1240,14 @@ impl QuorumDriverApi {
             Error::FailToConfirmTransactionStatus(*tx.digest(), start.elapsed().as_secs())
         })?;
 
+        if let Some(checkpoint) = poll_response.checkpoint.filter(|_| pin_reads) {
+            endpoint.observe_checkpoint(checkpoint);
+            endpoints.raise_read_watermark(checkpoint);
+        }
+        if wait_for_effects_cert {
+            return Ok(response);
+        }
+
         poll_response.confirmed_local_execution = Some(true);
         Ok(poll_response)
     }
@@ -1197,7 +1266,7 @@ impl GovernanceApi {
 
     /// Return a list of [DelegatedStake] objects for the given address, or an error upon failure.
     pub async fn get_stakes(&self, owner: SuiAddress) -> SuiRpcResult<Vec<DelegatedStake>> {
-        Ok(self.api.http.get_stakes(owner).await?)
+        self.api.endpoints.read(|http| http.get_stakes(owner)).await
     }
 
     /// Return the [SuiCommittee] information for the given `epoch`, or an error upon failure.
@@ -1223,7 +1292,10 @@ impl GovernanceApi {
         &self,
         epoch: Option<BigInt<u64>>,
     ) -> SuiRpcResult<SuiCommittee> {
-        Ok(self.api.http.get_committee_info(epoch).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_committee_info(epoch))
+            .await
     }
 
     /// Return the latest SUI system state object on-chain, or an error upon failure.
@@ -1232,11 +1304,18 @@ impl GovernanceApi {
     /// the protocol version, the reference gas price, the total stake, active validators,
     /// and much more. See the [SuiSystemStateSummary] for all the available fields.
     pub async fn get_latest_sui_system_state(&self) -> SuiRpcResult<SuiSystemStateSummary> {
-        Ok(self.api.http.get_latest_sui_system_state().await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_latest_sui_system_state())
+            .await
     }
 
     /// Return the reference gas price for the network, or an error upon failure.
     pub async fn get_reference_gas_price(&self) -> SuiRpcResult<u64> {
-        Ok(*self.api.http.get_reference_gas_price().await?)
+        Ok(*self
+            .api
+            .endpoints
+            .read(|http| http.get_reference_gas_price())
+            .await?)
     }
 }
@@ -0,0 +1,398 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracking of the fullnodes a [crate::SuiClient] is connected to.
+//!
+//! A client built with fallback URLs sends idempotent reads to the healthiest endpoint, retries
+//! them on the next one when the request fails at the transport level, and optionally hedges
+//! them by sending the same request to another endpoint when the first one is slow to answer.
+//! Endpoints whose requests fail are backed off for a while, and a background task polls the
+//! latest checkpoint of every endpoint so that reads can be pinned to endpoints that have caught
+//! up with a given checkpoint (see [ReadConsistency]). The pages of a paginated read are all
+//! served by the endpoint that served the first one.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use futures::future::{self, BoxFuture, Either};
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::http_client::HttpClient;
+use serde::Serialize;
+use sui_json_rpc_api::ReadApiClient;
+use sui_json_rpc_types::Page;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::debug;
+
+use crate::error::{Error, SuiRpcResult};
+use crate::RpcClient;
+
+/// Longest time an endpoint is backed off for after consecutive failures.
+const MAX_FAILURE_BACKOFF: Duration = Duration::from_secs(60);
+/// How often endpoints are polled while waiting for one of them to reach the read watermark.
+const WATERMARK_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// Number of page cursors whose endpoint is remembered, oldest ones being forgotten first.
+const MAX_PINNED_CURSORS: usize = 1024;
+
+/// Which endpoints reads may be served by.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReadConsistency {
+    /// Reads are served by any endpoint that has reached the checkpoint reads are pinned to with
+    /// [crate::SuiClient::pin_reads_to_checkpoint], if any.
+    #[default]
+    Eventual,
+    /// Like [ReadConsistency::Eventual], but reads are also pinned to the checkpoint of every
+    /// transaction executed through the client, so that they observe its effects whichever
+    /// endpoint serves them. Executing a transaction waits until it is checkpointed.
+    ReadAfterWrite,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct EndpointConfig {
+    /// Number of other endpoints a failed read is retried on.
+    pub read_retries: usize,
+    /// Send a read to the next endpoint as well if the previous one did not answer in time.
+    pub hedge_after: Option<Duration>,
+    /// How often the latest checkpoint of every endpoint is polled.
+    pub health_check_interval: Option<Duration>,
+    /// How long an endpoint is backed off for after its first failure, doubling on each
+    /// consecutive failure.
+    pub failure_backoff: Duration,
+    /// How long reads wait for an endpoint to reach the checkpoint they are pinned to.
+    pub watermark_timeout: Duration,
+    pub read_consistency: ReadConsistency,
+}
+
+impl Default for EndpointConfig {
+    fn default() -> Self {
+        Self {
+            read_retries: 2,
+            hedge_after: None,
+            health_check_interval: Some(Duration::from_secs(10)),
+            failure_backoff: Duration::from_secs(1),
+            watermark_timeout: Duration::from_secs(30),
+            read_consistency: ReadConsistency::Eventual,
+        }
+    }
+}
+
+/// Health of an endpoint as last observed by the client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndpointStatus {
+    pub url: String,
+    /// False while the endpoint is backed off after failed requests.
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    /// Latest checkpoint the endpoint reported, if it was ever polled.
+    pub latest_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+#[derive(Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    backoff_until: Option<Instant>,
+    latest_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+pub(crate) struct Endpoint {
+    pub(crate) url: String,
+    pub(crate) http: HttpClient,
+    health: Mutex<EndpointHealth>,
+}
+
+impl Endpoint {
+    pub(crate) fn new(url: String, http: HttpClient) -> Self {
+        Self {
+            url,
+            http,
+            health: Mutex::new(EndpointHealth::default()),
+        }
+    }
+
+    fn is_backed_off(&self, now: Instant) -> bool {
+        let health = self.health.lock().unwrap();
+        health.backoff_until.is_some_and(|until| until > now)
+    }
+
+    fn latest_checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        self.health.lock().unwrap().latest_checkpoint
+    }
+
+    /// Records the outcome of a request sent to this endpoint. Only transport failures count
+    /// against its health: errors returned by the node itself are answers like any other.
+    pub(crate) fn record<T>(&self, result: &Result<T, jsonrpsee::core::Error>, backoff: Duration) {
+        let mut health = self.health.lock().unwrap();
+        match result {
+            Err(e) if is_retryable(e) => {
+                health.consecutive_failures += 1;
+                let backoff = backoff
+                    .saturating_mul(1u32 << (health.consecutive_failures - 1).min(16))
+                    .min(MAX_FAILURE_BACKOFF);
+                health.backoff_until = Some(Instant::now() + backoff);
+                debug!(
+                    "Endpoint {} failed {} time(s) in a row: {e}",
+                    self.url, health.consecutive_failures
+                );
+            }
+            _ => {
+                health.consecutive_failures = 0;
+                health.backoff_until = None;
+            }
+        }
+    }
+
+    pub(crate) fn observe_checkpoint(&self, checkpoint: CheckpointSequenceNumber) {
+        let mut health = self.health.lock().unwrap();
+        health.latest_checkpoint = health.latest_checkpoint.max(Some(checkpoint));
+    }
+
+    fn status(&self, now: Instant) -> EndpointStatus {
+        let health = self.health.lock().unwrap();
+        EndpointStatus {
+            url: self.url.clone(),
+            healthy: !health.backoff_until.is_some_and(|until| until > now),
+            consecutive_failures: health.consecutive_failures,
+            latest_checkpoint: health.latest_checkpoint,
+        }
+    }
+}
+
+/// The endpoints of a client, in the order they were given to the builder.
+pub(crate) struct EndpointSet {
+    endpoints: Vec<Endpoint>,
+    config: EndpointConfig,
+    /// Reads are only served by endpoints known to have reached this checkpoint.
+    read_watermark: AtomicU64,
+    /// Cursors of the next pages of paginated reads, and the endpoint that returned them.
+    pinned_cursors: Mutex<VecDeque<(String, usize)>>,
+}
+
+impl EndpointSet {
+    pub(crate) fn new(endpoints: Vec<Endpoint>, config: EndpointConfig) -> Self {
+        assert!(
+            !endpoints.is_empty(),
+            "a client needs at least one endpoint"
+        );
+        Self {
+            endpoints,
+            config,
+            read_watermark: AtomicU64::new(0),
+            pinned_cursors: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub(crate) fn config(&self) -> &EndpointConfig {
+        &self.config
+    }
+
+    /// The first endpoint given to the builder.
+    pub(crate) fn primary(&self) -> &Endpoint {
+        &self.endpoints[0]
+    }
+
+    pub(crate) fn statuses(&self) -> Vec<EndpointStatus> {
+        let now = Instant::now();
+        self.endpoints.iter().map(|e| e.status(now)).collect()
+    }
+
+    pub(crate) fn read_watermark(&self) -> CheckpointSequenceNumber {
+        self.read_watermark.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn raise_read_watermark(&self, checkpoint: CheckpointSequenceNumber) {
+        self.read_watermark.fetch_max(checkpoint, Ordering::Relaxed);
+    }
+
+    /// Indices of the endpoints to try, healthy ones first, in the order they were given.
+    fn ranked(&self, min_checkpoint: CheckpointSequenceNumber) -> Vec<usize> {
+        let now = Instant::now();
+        let mut ranked: Vec<usize> = (0..self.endpoints.len())
+            .filter(|i| {
+                min_checkpoint == 0
+                    || self.endpoints[*i]
+                        .latest_checkpoint()
+                        .is_some_and(|c| c >= min_checkpoint)
+            })
+            .collect();
+        ranked.sort_by_key(|i| self.endpoints[*i].is_backed_off(now));
+        ranked
+    }
+
+    /// The endpoint transactions are executed on.
+    pub(crate) fn write_endpoint(&self) -> &Endpoint {
+        &self.endpoints[self.ranked(0)[0]]
+    }
+
+    /// Polls the latest checkpoint of every endpoint, which also refreshes their health.
+    pub(crate) async fn refresh(&self) {
+        future::join_all(self.endpoints.iter().map(|endpoint| async move {
+            let result = endpoint.http.get_latest_checkpoint_sequence_number().await;
+            endpoint.record(&result, self.config.failure_backoff);
+            if let Ok(checkpoint) = result {
+                endpoint.observe_checkpoint(*checkpoint);
+            }
+        }))
+        .await;
+    }
+
+    /// Endpoints that reads can be sent to, waiting for one of them to reach the read watermark
+    /// if none is known to have reached it yet.
+    async fn read_candidates(&self) -> SuiRpcResult<Vec<usize>> {
+        let watermark = self.read_watermark();
+        let deadline = Instant::now() + self.config.watermark_timeout;
+        loop {
+            let ranked = self.ranked(watermark);
+            if !ranked.is_empty() {
+                return Ok(ranked);
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::DataError(format!(
+                    "No endpoint reached checkpoint {watermark} within {:?}",
+                    self.config.watermark_timeout
+                )));
+            }
+            self.refresh().await;
+            if self.ranked(watermark).is_empty() {
+                tokio::time::sleep(WATERMARK_POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    /// Sends an idempotent read to the endpoints, retrying it on the next endpoint when it fails
+    /// at the transport level, and hedging it if configured to.
+    pub(crate) async fn read<T, F>(&self, call: F) -> SuiRpcResult<T>
+    where
+        F: for<'a> Fn(&'a HttpClient) -> BoxFuture<'a, Result<T, jsonrpsee::core::Error>>,
+    {
+        let candidates = self.read_candidates().await?;
+        Ok(self.read_from(candidates, call).await?.1)
+    }
+
+    /// Sends the read of a page of a paginated query. A cursor is only meaningful to the endpoint
+    /// that returned it, which may also be at another checkpoint than the others, so the page a
+    /// cursor points to is read from that endpoint alone. Reads of a first page, or with a cursor
+    /// that wasn't returned by this client, are sent like any other read.
+    pub(crate) async fn read_page<T, C, F>(
+        &self,
+        cursor: Option<&C>,
+        call: F,
+    ) -> SuiRpcResult<Page<T, C>>
+    where
+        C: Serialize,
+        F: for<'a> Fn(&'a HttpClient) -> BoxFuture<'a, Result<Page<T, C>, jsonrpsee::core::Error>>,
+    {
+        let pinned = cursor.and_then(|c| self.pinned_endpoint(c));
+        let candidates = match pinned {
+            Some(i) => vec![i],
+            None => self.read_candidates().await?,
+        };
+        let (i, page) = self.read_from(candidates, call).await?;
+        if let Some(next) = &page.next_cursor {
+            self.pin_cursor(next, i);
+        }
+        Ok(page)
+    }
+
+    fn pinned_endpoint<C: Serialize>(&self, cursor: &C) -> Option<usize> {
+        let key = serde_json::to_string(cursor).ok()?;
+        let pinned = self.pinned_cursors.lock().unwrap();
+        pinned.iter().find(|(c, _)| *c == key).map(|(_, i)| *i)
+    }
+
+    fn pin_cursor<C: Serialize>(&self, cursor: &C, endpoint: usize) {
+        let Ok(key) = serde_json::to_string(cursor) else {
+            return;
+        };
+        let mut pinned = self.pinned_cursors.lock().unwrap();
+        pinned.retain(|(c, _)| *c != key);
+        if pinned.len() == MAX_PINNED_CURSORS {
+            pinned.pop_front();
+        }
+        pinned.push_back((key, endpoint));
+    }
+
+    /// Sends a read to `candidates`, in order, returning the index of the endpoint that served it
+    /// along with its result.
+    async fn read_from<T, F>(&self, candidates: Vec<usize>, call: F) -> SuiRpcResult<(usize, T)>
+    where
+        F: for<'a> Fn(&'a HttpClient) -> BoxFuture<'a, Result<T, jsonrpsee::core::Error>>,
+    {
+        let attempts = candidates.len().min(self.config.read_retries + 1);
+        let mut pending: VecDeque<usize> = candidates.into_iter().take(attempts).collect();
+
+        let launch = |i: usize| call(&self.endpoints[i].http).map(move |result| (i, result));
+        let mut in_flight = FuturesUnordered::new();
+        let mut last_error = None;
+        if let Some(i) = pending.pop_front() {
+            in_flight.push(launch(i));
+        }
+
+        loop {
+            let hedge = match self.config.hedge_after {
+                Some(delay) if !pending.is_empty() => tokio::time::sleep(delay).boxed(),
+                _ => future::pending().boxed(),
+            };
+            let completed = match future::select(in_flight.next(), hedge).await {
+                Either::Left((completed, _)) => Some(completed),
+                Either::Right(_) => None,
+            };
+            let (i, result) = match completed {
+                Some(Some(completed)) => completed,
+                Some(None) => break,
+                None => {
+                    if let Some(i) = pending.pop_front() {
+                        debug!("Hedging read on {}", self.endpoints[i].url);
+                        in_flight.push(launch(i));
+                    }
+                    continue;
+                }
+            };
+
+            self.endpoints[i].record(&result, self.config.failure_backoff);
+            match result {
+                Err(e) if is_retryable(&e) => {
+                    if let Some(next) = pending.pop_front() {
+                        debug!(
+                            "Read on {} failed, retrying on {}: {e}",
+                            self.endpoints[i].url, self.endpoints[next].url
+                        );
+                        in_flight.push(launch(next));
+                    }
+                    last_error = Some(e);
+                }
+                result => return Ok((i, result?)),
+            }
+        }
+
+        // unwrap: at least one read was sent, and all of them failed.
+        Err(last_error.unwrap().into())
+    }
+}
+
+/// Whether a request failed before reaching the node, or without an answer from it. Errors
+/// returned by the node are deterministic and would be returned by any other endpoint too.
+fn is_retryable(error: &jsonrpsee::core::Error) -> bool {
+    !matches!(error, jsonrpsee::core::Error::Call(_))
+}
+
+/// Keeps the health and latest checkpoint of the endpoints of a client up to date, until the
+/// client is dropped.
+pub(crate) fn spawn_health_check(api: Weak<RpcClient>, interval: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let Some(api) = api.upgrade() else {
+                return;
+            };
+            api.endpoints.refresh().await;
+        }
+    });
+}
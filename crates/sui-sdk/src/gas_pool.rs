@@ -0,0 +1,616 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A pool of gas coins for services submitting many transactions concurrently from one address.
+//!
+//! A transaction locks the gas coin it pays with until it is finalized, so concurrent
+//! transactions paying with the same coin conflict with each other, and can leave the coin
+//! locked until the end of the epoch. [GasPool] splits the balance of an address into a number of
+//! gas coins and leases each of them to one transaction at a time, tracking their latest
+//! versions from the effects of these transactions.
+//!
+//! A background task keeps the pool healthy: it tops up coins running low on balance from the
+//! richest coin, and hands out coins again whose last transaction had an unknown outcome once it
+//! is safe to do so. See [crate::transaction_submitter::TransactionSubmitter] for executing
+//! transactions with coins from the pool.
+
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use fastcrypto::traits::Signer;
+use futures::StreamExt;
+use sui_json_rpc_api::QUERY_MAX_RESULT_LIMIT;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
+    SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_types::base_types::{ObjectRef, SuiAddress};
+use sui_types::coin::Coin;
+use sui_types::committee::EpochId;
+use sui_types::crypto::Signature;
+use sui_types::gas::GasCostSummary;
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{Transaction, TransactionData};
+use tokio::sync::Notify;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, warn};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::{SuiClient, SUI_COIN_TYPE};
+
+/// Maximum number of coins that can be merged into the gas coin of a transaction.
+const MAX_GAS_PAYMENT_OBJECTS: usize = 256;
+
+/// Signs the transactions paid for by a [GasPool], on behalf of the address owning its coins.
+pub type GasPoolSigner = dyn Signer<Signature> + Send + Sync;
+
+#[derive(Clone, Debug)]
+pub struct GasPoolConfig {
+    /// Number of gas coins to split the balance of the address into.
+    pub coin_count: usize,
+    /// Balance of the coins split off, and that coins are topped up to.
+    pub coin_balance: u64,
+    /// Coins with a lower balance get topped up.
+    pub min_coin_balance: u64,
+    /// Gas budget of the transactions splitting and topping up coins.
+    pub gas_budget: u64,
+    /// How often the pool tops up coins and checks whether quarantined coins can be used again.
+    pub maintenance_interval: Duration,
+    /// How long [GasPool::lease] waits for a coin to become available before failing.
+    pub lease_timeout: Duration,
+}
+
+impl Default for GasPoolConfig {
+    fn default() -> Self {
+        Self {
+            coin_count: 16,
+            coin_balance: 5_000_000_000,
+            min_coin_balance: 500_000_000,
+            gas_budget: 50_000_000,
+            maintenance_interval: Duration::from_secs(10),
+            lease_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A gas coin of the pool, at its latest known version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasCoin {
+    pub object_ref: ObjectRef,
+    pub balance: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GasPoolStats {
+    /// Coins that can be leased.
+    pub available: usize,
+    /// Coins currently leased.
+    pub leased: usize,
+    /// Coins whose last transaction had an unknown outcome.
+    pub quarantined: usize,
+    /// Total balance of the available coins.
+    pub available_balance: u64,
+}
+
+/// A coin whose lease ended without the effects of its transaction: the transaction may still
+/// get executed, or may have locked the coin on some validators until the end of `epoch`.
+struct QuarantinedCoin {
+    coin: GasCoin,
+    epoch: EpochId,
+}
+
+#[derive(Default)]
+struct PoolState {
+    available: VecDeque<GasCoin>,
+    leased: usize,
+    quarantined: Vec<QuarantinedCoin>,
+    /// Quarantined coins taken out of `quarantined` while their latest version is read.
+    checking: usize,
+}
+
+impl PoolState {
+    /// Whether all coins of the pool were removed from it, so that none can be leased anymore.
+    fn is_empty(&self) -> bool {
+        self.available.is_empty()
+            && self.leased == 0
+            && self.quarantined.is_empty()
+            && self.checking == 0
+    }
+}
+
+struct Inner {
+    client: SuiClient,
+    owner: SuiAddress,
+    signer: Arc<GasPoolSigner>,
+    config: GasPoolConfig,
+    epoch: AtomicU64,
+    gas_price: AtomicU64,
+    state: Mutex<PoolState>,
+    coin_returned: Notify,
+}
+
+/// A pool of gas coins owned by one address. Cloning it is cheap, clones share the same coins.
+#[derive(Clone)]
+pub struct GasPool {
+    inner: Arc<Inner>,
+}
+
+impl GasPool {
+    /// Creates a pool of `config.coin_count` gas coins for `owner`, merging its SUI coins and
+    /// splitting them into coins of `config.coin_balance`, except for the one keeping the rest
+    /// of the balance. `signer` must sign for `owner`.
+    pub async fn new(
+        client: SuiClient,
+        owner: SuiAddress,
+        signer: Arc<GasPoolSigner>,
+        config: GasPoolConfig,
+    ) -> SuiRpcResult<Self> {
+        if config.coin_count == 0 {
+            return Err(Error::DataError(
+                "Gas pool must have at least one coin".to_string(),
+            ));
+        }
+
+        let system_state = client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?;
+        let inner = Arc::new(Inner {
+            client,
+            owner,
+            signer,
+            epoch: AtomicU64::new(system_state.epoch),
+            gas_price: AtomicU64::new(system_state.reference_gas_price),
+            state: Mutex::new(PoolState::default()),
+            coin_returned: Notify::new(),
+            config,
+        });
+
+        let mut coins: Vec<_> = inner
+            .client
+            .coin_read_api()
+            .get_coins_stream(owner, Some(SUI_COIN_TYPE.to_string()))
+            .map(|c| GasCoin {
+                object_ref: c.object_ref(),
+                balance: c.balance,
+            })
+            .collect()
+            .await;
+        coins.sort_by_key(|c| Reverse(c.balance));
+        coins.truncate(MAX_GAS_PAYMENT_OBJECTS);
+
+        let count = inner.config.coin_count - 1;
+        let required =
+            inner.config.coin_balance as u128 * count as u128 + inner.config.gas_budget as u128;
+        let total: u128 = coins.iter().map(|c| c.balance as u128).sum();
+        if total < required {
+            return Err(Error::InsufficientFund {
+                address: owner,
+                amount: required,
+            });
+        }
+
+        let coins = inner.split(coins, count).await?;
+        if coins.len() != inner.config.coin_count {
+            return Err(Error::DataError(format!(
+                "Failed to split the balance of {owner} into gas coins"
+            )));
+        }
+        inner.state.lock().unwrap().available.extend(coins);
+
+        tokio::spawn(maintain(Arc::downgrade(&inner)));
+        Ok(Self { inner })
+    }
+
+    pub fn client(&self) -> &SuiClient {
+        &self.inner.client
+    }
+
+    pub fn owner(&self) -> SuiAddress {
+        self.inner.owner
+    }
+
+    /// The reference gas price of the current epoch.
+    pub fn gas_price(&self) -> u64 {
+        self.inner.gas_price.load(Ordering::Relaxed)
+    }
+
+    pub fn stats(&self) -> GasPoolStats {
+        let state = self.inner.state.lock().unwrap();
+        GasPoolStats {
+            available: state.available.len(),
+            leased: state.leased,
+            quarantined: state.quarantined.len(),
+            available_balance: state.available.iter().map(|c| c.balance).sum(),
+        }
+    }
+
+    /// Leases a gas coin, waiting up to `config.lease_timeout` for one to be returned if they are
+    /// all in use or quarantined. Fails right away once the pool has no coins left, which happens
+    /// when its coins are no longer owned by its owner.
+    pub async fn lease(&self) -> SuiRpcResult<GasLease> {
+        let wait = async {
+            loop {
+                let returned = self.inner.coin_returned.notified();
+                if let Some(lease) = self.try_lease() {
+                    return Ok(lease);
+                }
+                if self.inner.state.lock().unwrap().is_empty() {
+                    return Err(Error::DataError(format!(
+                        "Gas pool of {} has no coins left",
+                        self.inner.owner
+                    )));
+                }
+                returned.await;
+            }
+        };
+
+        let timeout = self.inner.config.lease_timeout;
+        tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            Error::DataError(format!(
+                "No gas coin of {} became available within {timeout:?}",
+                self.inner.owner
+            ))
+        })?
+    }
+
+    /// Leases a gas coin if one is available.
+    pub fn try_lease(&self) -> Option<GasLease> {
+        let mut state = self.inner.state.lock().unwrap();
+        let coin = state.available.pop_front()?;
+        state.leased += 1;
+        Some(GasLease {
+            inner: self.inner.clone(),
+            coin,
+            epoch: self.inner.epoch.load(Ordering::Relaxed),
+            returned: false,
+        })
+    }
+
+    /// Signs `data` on behalf of the owner of the pool.
+    pub fn sign(&self, data: TransactionData) -> Transaction {
+        self.inner.sign(data)
+    }
+}
+
+/// Exclusive use of a gas coin of a [GasPool] for one transaction.
+///
+/// The coin goes back to the pool with [GasLease::release] once the effects of the transaction
+/// are known, or with [GasLease::release_unused] if no transaction was signed with it. Dropping
+/// the lease otherwise quarantines the coin: it is handed out again once its version changed (the
+/// transaction got executed) or the epoch ended (releasing any lock on it).
+pub struct GasLease {
+    inner: Arc<Inner>,
+    coin: GasCoin,
+    epoch: EpochId,
+    returned: bool,
+}
+
+impl GasLease {
+    pub fn coin(&self) -> GasCoin {
+        self.coin
+    }
+
+    pub fn object_ref(&self) -> ObjectRef {
+        self.coin.object_ref
+    }
+
+    pub fn balance(&self) -> u64 {
+        self.coin.balance
+    }
+
+    /// Returns the coin to the pool at the version written by the transaction it paid for.
+    pub fn release(mut self, effects: &SuiTransactionBlockEffects) {
+        let gas = effects.gas_object();
+        if gas.object_id() != self.coin.object_ref.0 {
+            warn!(
+                "Effects of {} were paid with {} instead of leased coin {}",
+                effects.transaction_digest(),
+                gas.object_id(),
+                self.coin.object_ref.0,
+            );
+            return;
+        }
+
+        self.returned = true;
+        self.inner.return_coins(
+            1,
+            [GasCoin {
+                object_ref: gas.reference.to_object_ref(),
+                balance: charge_gas(self.coin.balance, effects.gas_cost_summary()),
+            }],
+        );
+    }
+
+    /// Returns the coin to the pool as it was leased, no transaction having been signed with it.
+    pub fn release_unused(mut self) {
+        self.returned = true;
+        self.inner.return_coins(1, [self.coin]);
+    }
+}
+
+impl Drop for GasLease {
+    fn drop(&mut self) {
+        if !self.returned {
+            self.inner.quarantine(1, [self.coin], self.epoch);
+        }
+    }
+}
+
+impl Inner {
+    fn sign(&self, data: TransactionData) -> Transaction {
+        Transaction::from_data_and_signer(data, vec![self.signer.as_ref()])
+    }
+
+    /// Returns `coins` to the pool, ending `leases` leases.
+    fn return_coins(&self, leases: usize, coins: impl IntoIterator<Item = GasCoin>) {
+        let mut state = self.state.lock().unwrap();
+        state.leased -= leases;
+        for coin in coins {
+            state.available.push_back(coin);
+            self.coin_returned.notify_one();
+        }
+    }
+
+    fn quarantine(&self, leases: usize, coins: impl IntoIterator<Item = GasCoin>, epoch: EpochId) {
+        let mut state = self.state.lock().unwrap();
+        state.leased -= leases;
+        for coin in coins {
+            debug!("Quarantining gas coin {}", coin.object_ref.0);
+            state.quarantined.push(QuarantinedCoin { coin, epoch });
+        }
+    }
+
+    /// Merges `gas` into its first coin and splits `count` coins of `coin_balance` off it. Returns
+    /// the new coins followed by the merged one, or just the merged coin if the split failed.
+    async fn split(&self, gas: Vec<GasCoin>, count: usize) -> SuiRpcResult<Vec<GasCoin>> {
+        let total: u64 = gas.iter().map(|c| c.balance).sum();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .pay_sui(
+                vec![self.owner; count],
+                vec![self.config.coin_balance; count],
+            )
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        let data = TransactionData::new_programmable(
+            self.owner,
+            gas.iter().map(|c| c.object_ref).collect(),
+            builder.finish(),
+            self.config.gas_budget,
+            self.gas_price.load(Ordering::Relaxed),
+        );
+
+        let response = self
+            .client
+            .quorum_driver_api()
+            .execute_transaction_block(
+                self.sign(data),
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForEffectsCert),
+            )
+            .await?;
+        let effects = response.effects.ok_or_else(|| {
+            Error::DataError(format!("No effects for transaction {}", response.digest))
+        })?;
+        if let SuiExecutionStatus::Failure { error } = effects.status() {
+            warn!("Failed to split gas coins: {error}");
+        }
+
+        let mut coins: Vec<_> = effects
+            .created()
+            .iter()
+            .map(|c| GasCoin {
+                object_ref: c.reference.to_object_ref(),
+                balance: self.config.coin_balance,
+            })
+            .collect();
+        let split_off = self.config.coin_balance * coins.len() as u64;
+        coins.push(GasCoin {
+            object_ref: effects.gas_object().reference.to_object_ref(),
+            balance: charge_gas(total.saturating_sub(split_off), effects.gas_cost_summary()),
+        });
+        Ok(coins)
+    }
+
+    async fn update_epoch(&self) -> SuiRpcResult<()> {
+        let system_state = self
+            .client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?;
+        self.epoch.store(system_state.epoch, Ordering::Relaxed);
+        self.gas_price
+            .store(system_state.reference_gas_price, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Hands out quarantined coins again once their version changed or the epoch they were
+    /// quarantined in ended.
+    async fn release_quarantined(&self) -> SuiRpcResult<()> {
+        let quarantined = {
+            let mut state = self.state.lock().unwrap();
+            state.checking = state.quarantined.len();
+            std::mem::take(&mut state.quarantined)
+        };
+        let epoch = self.epoch.load(Ordering::Relaxed);
+        let mut still_quarantined = vec![];
+        let mut released = vec![];
+        let mut removed = 0;
+
+        for chunk in quarantined.chunks(*QUERY_MAX_RESULT_LIMIT) {
+            let ids = chunk.iter().map(|q| q.coin.object_ref.0).collect();
+            let responses = match self
+                .client
+                .read_api()
+                .multi_get_object_with_options(
+                    ids,
+                    SuiObjectDataOptions::new().with_bcs().with_owner(),
+                )
+                .await
+            {
+                Ok(responses) => responses,
+                Err(e) => {
+                    still_quarantined.extend(chunk.iter().map(|q| (q.coin, q.epoch)));
+                    warn!("Failed to read quarantined gas coins: {e}");
+                    continue;
+                }
+            };
+
+            for (q, response) in chunk.iter().zip(responses) {
+                match self.latest_coin(response) {
+                    Some(coin) if coin.object_ref.1 > q.coin.object_ref.1 || q.epoch < epoch => {
+                        released.push(coin)
+                    }
+                    Some(_) => still_quarantined.push((q.coin, q.epoch)),
+                    None => {
+                        removed += 1;
+                        warn!(
+                            "Gas coin {} is no longer owned by {}, removing it from the pool",
+                            q.coin.object_ref.0, self.owner
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.checking = 0;
+        state.quarantined.extend(
+            still_quarantined
+                .into_iter()
+                .map(|(coin, epoch)| QuarantinedCoin { coin, epoch }),
+        );
+        drop(state);
+        self.return_coins(0, released);
+        if removed > 0 {
+            // Wake up leases waiting on a pool that may have no coins left.
+            self.coin_returned.notify_waiters();
+        }
+        Ok(())
+    }
+
+    /// The latest version of a coin, if it is still a coin owned by the pool's owner.
+    fn latest_coin(&self, response: SuiObjectResponse) -> Option<GasCoin> {
+        let data = response.data?;
+        if data.owner != Some(Owner::AddressOwner(self.owner)) {
+            return None;
+        }
+        let Some(SuiRawData::MoveObject(object)) = &data.bcs else {
+            return None;
+        };
+        let coin = Coin::from_bcs_bytes(&object.bcs_bytes).ok()?;
+        Some(GasCoin {
+            object_ref: data.object_ref(),
+            balance: coin.value(),
+        })
+    }
+
+    /// Tops up the available coins whose balance fell under `min_coin_balance` from the richest
+    /// available coin, merging them into it and splitting new coins off.
+    async fn rebalance(&self) -> SuiRpcResult<()> {
+        let config = &self.config;
+        let gas = {
+            let mut state = self.state.lock().unwrap();
+            let Some((richest, _)) = state
+                .available
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.balance >= config.min_coin_balance)
+                .max_by_key(|(_, c)| c.balance)
+            else {
+                return Ok(());
+            };
+
+            let mut low: Vec<_> = state
+                .available
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.balance < config.min_coin_balance)
+                .map(|(i, _)| i)
+                .take(MAX_GAS_PAYMENT_OBJECTS - 1)
+                .collect();
+            if low.is_empty() {
+                return Ok(());
+            }
+
+            let total: u128 = low
+                .iter()
+                .chain([&richest])
+                .map(|i| state.available[*i].balance as u128)
+                .sum();
+            let required =
+                config.coin_balance as u128 * low.len() as u128 + config.gas_budget as u128;
+            if total < required {
+                warn!(
+                    "Gas pool of {} is running out of balance: {} coins need topping up",
+                    self.owner,
+                    low.len()
+                );
+                return Ok(());
+            }
+
+            // Take the coins out of the pool, from the back so that indices stay valid.
+            low.push(richest);
+            low.sort_by_key(|i| Reverse(*i));
+            let mut gas: Vec<_> = low
+                .iter()
+                .map(|i| state.available.remove(*i).unwrap())
+                .collect();
+            state.leased += gas.len();
+            // The richest coin pays for the transaction, and receives the others.
+            let richest = gas
+                .iter()
+                .position(|c| c.balance >= config.min_coin_balance);
+            gas.swap(0, richest.unwrap());
+            gas
+        };
+
+        let leases = gas.len();
+        debug!("Topping up {} gas coins", leases - 1);
+        match self.split(gas.clone(), leases - 1).await {
+            Ok(coins) => {
+                self.return_coins(leases, coins);
+                Ok(())
+            }
+            Err(e) => {
+                self.quarantine(leases, gas, self.epoch.load(Ordering::Relaxed));
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Maintains the pool until it is dropped.
+async fn maintain(pool: Weak<Inner>) {
+    let Some(period) = pool.upgrade().map(|p| p.config.maintenance_interval) else {
+        return;
+    };
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        let Some(pool) = pool.upgrade() else {
+            return;
+        };
+        if let Err(e) = pool.update_epoch().await {
+            warn!("Failed to read the current epoch: {e}");
+        }
+        if let Err(e) = pool.release_quarantined().await {
+            warn!("Failed to release quarantined gas coins: {e}");
+        }
+        if let Err(e) = pool.rebalance().await {
+            warn!("Failed to top up gas coins: {e}");
+        }
+    }
+}
+
+/// Balance of a gas coin after paying for a transaction.
+fn charge_gas(balance: u64, gas: &GasCostSummary) -> u64 {
+    (balance as i128 - gas.net_gas_usage() as i128).max(0) as u64
+}
@@ -96,14 +96,20 @@ use sui_json_rpc_types::{
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi};
+use crate::endpoints::{spawn_health_check, Endpoint, EndpointConfig, EndpointSet};
+pub use crate::endpoints::{EndpointStatus, ReadConsistency};
 use crate::error::{Error, SuiRpcResult};
 
 pub mod apis;
+pub mod endpoints;
 pub mod error;
+pub mod gas_pool;
 pub mod json_rpc_error;
 pub mod sui_client_config;
+pub mod transaction_submitter;
 pub mod wallet_context;
 
 pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
@@ -122,6 +128,11 @@ pub const SUI_TESTNET_URL: &str = "https://fullnode.testnet.sui.io:443";
 /// value of your choice to prevent the inactive WS subscription being
 /// disconnected due to proxy timeout.
 ///
+/// Additional fullnodes can be given with `fallback_urls`: reads are then retried on
+/// the next healthy fullnode when one fails, and can be hedged across them with
+/// `hedge_reads_after`. See [ReadConsistency] for pinning reads to fullnodes that
+/// have caught up with the transactions executed through the client.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -142,6 +153,8 @@ pub struct SuiClientBuilder {
     ws_url: Option<String>,
     ws_ping_interval: Option<Duration>,
     basic_auth: Option<(String, String)>,
+    fallback_urls: Vec<String>,
+    endpoint_config: EndpointConfig,
 }
 
 impl Default for SuiClientBuilder {
@@ -152,6 +165,8 @@ impl Default for SuiClientBuilder {
             ws_url: None,
             ws_ping_interval: None,
             basic_auth: None,
+            fallback_urls: vec![],
+            endpoint_config: EndpointConfig::default(),
         }
     }
 }
@@ -187,6 +202,41 @@ impl SuiClientBuilder {
         self
     }
 
+    /// Set the URLs of fullnodes to fall back to when the one the client is built with fails
+    pub fn fallback_urls(mut self, urls: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.fallback_urls = urls
+            .into_iter()
+            .map(|url| url.as_ref().to_string())
+            .collect();
+        self
+    }
+
+    /// Set the number of other fullnodes a failed read is retried on (2 by default)
+    pub fn read_retries(mut self, retries: usize) -> Self {
+        self.endpoint_config.read_retries = retries;
+        self
+    }
+
+    /// Send reads to the next fullnode as well when the previous one did not answer within
+    /// `delay`, using whichever answer comes first
+    pub fn hedge_reads_after(mut self, delay: Duration) -> Self {
+        self.endpoint_config.hedge_after = Some(delay);
+        self
+    }
+
+    /// Set how often the health and latest checkpoint of fullnodes is polled (10 seconds by
+    /// default), when the client has fallback URLs
+    pub fn health_check_interval(mut self, interval: Duration) -> Self {
+        self.endpoint_config.health_check_interval = Some(interval);
+        self
+    }
+
+    /// Set which fullnodes reads may be served by
+    pub fn read_consistency(mut self, consistency: ReadConsistency) -> Self {
+        self.endpoint_config.read_consistency = consistency;
+        self
+    }
+
     /// Returns a [SuiClient] object connected to the Sui network running at the URI provided.
     ///
     /// # Examples
@@ -244,17 +294,40 @@ impl SuiClientBuilder {
             None
         };
 
-        let http = HttpClientBuilder::default()
-            .max_request_body_size(2 << 30)
-            .max_concurrent_requests(self.max_concurrent_requests)
-            .set_headers(headers.clone())
-            .request_timeout(self.request_timeout)
-            .build(http)?;
-
-        let info = Self::get_server_info(&http, &ws).await?;
+        let mut endpoints = vec![];
+        for url in
+            std::iter::once(http.as_ref()).chain(self.fallback_urls.iter().map(String::as_str))
+        {
+            let http = HttpClientBuilder::default()
+                .max_request_body_size(2 << 30)
+                .max_concurrent_requests(self.max_concurrent_requests)
+                .set_headers(headers.clone())
+                .request_timeout(self.request_timeout)
+                .build(url)?;
+            endpoints.push(Endpoint::new(url.to_string(), http));
+        }
 
-        let rpc = RpcClient { http, ws, info };
+        // Use the first fullnode that answers to discover the server information.
+        let mut info = Self::get_server_info(&endpoints[0].http, &ws).await;
+        for endpoint in &endpoints[1..] {
+            if info.is_ok() {
+                break;
+            }
+            info = Self::get_server_info(&endpoint.http, &ws).await;
+        }
+        let info = info?;
+
+        let health_check_interval = self.endpoint_config.health_check_interval;
+        let endpoints = EndpointSet::new(endpoints, self.endpoint_config);
+        let rpc = RpcClient {
+            endpoints,
+            ws,
+            info,
+        };
         let api = Arc::new(rpc);
+        if let Some(interval) = health_check_interval.filter(|_| api.endpoints.len() > 1) {
+            spawn_health_check(Arc::downgrade(&api), interval);
+        }
         let read_api = Arc::new(ReadApi::new(api.clone()));
         let quorum_driver_api = QuorumDriverApi::new(api.clone());
         let event_api = EventApi::new(api.clone());
@@ -439,7 +512,7 @@ pub struct SuiClient {
 }
 
 pub(crate) struct RpcClient {
-    http: HttpClient,
+    endpoints: EndpointSet,
     ws: Option<WsClient>,
     info: ServerInfo,
 }
@@ -449,7 +522,8 @@ impl Debug for RpcClient {
         write!(
             f,
             "RPC client. Http: {:?}, Websocket: {:?}",
-            self.http, self.ws
+            self.endpoints.primary().http,
+            self.ws
         )
     }
 }
@@ -523,9 +597,28 @@ impl SuiClient {
         &self.transaction_builder
     }
 
-    /// Returns a reference to the underlying http client.
+    /// Returns a reference to the underlying http client of the first fullnode the client was
+    /// built with.
     pub fn http(&self) -> &HttpClient {
-        &self.api.http
+        &self.api.endpoints.primary().http
+    }
+
+    /// Returns the health of the fullnodes the client is connected to, in the order they were
+    /// given to the builder.
+    pub fn endpoint_status(&self) -> Vec<EndpointStatus> {
+        self.api.endpoints.statuses()
+    }
+
+    /// Only serve reads from fullnodes that have reached `checkpoint`, e.g. to observe the
+    /// effects of a transaction executed by another client. This applies to all the clones of
+    /// this client, and never lowers the checkpoint reads are already pinned to.
+    pub fn pin_reads_to_checkpoint(&self, checkpoint: CheckpointSequenceNumber) {
+        self.api.endpoints.raise_read_watermark(checkpoint);
+    }
+
+    /// Returns the checkpoint reads are pinned to, 0 if they are not.
+    pub fn pinned_read_checkpoint(&self) -> CheckpointSequenceNumber {
+        self.api.endpoints.read_watermark()
     }
 
     /// Returns a reference to the underlying WebSocket client, if any.
@@ -0,0 +1,232 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Concurrent transaction submission paid for by a [GasPool].
+//!
+//! Transactions are signed once and resubmitted as is when their outcome is unknown (the request
+//! timed out, the connection dropped, the node was overloaded), so a retry can never produce a
+//! second transaction conflicting with the first over the same gas coin.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_with_registry, Histogram, IntCounter,
+    Registry,
+};
+use sui_json_rpc_types::{
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{ProgrammableTransaction, Transaction, TransactionData};
+use tracing::debug;
+
+use crate::error::{Error, JsonRpcError, SuiRpcResult};
+use crate::gas_pool::GasPool;
+
+#[derive(Clone, Debug)]
+pub struct SubmitterConfig {
+    /// Gas budget of the submitted transactions.
+    pub gas_budget: u64,
+    /// How many times a transaction with an unknown outcome is resubmitted before giving up.
+    pub max_retries: usize,
+    /// Delay before the first resubmission, doubling with every further one.
+    pub retry_delay: Duration,
+    /// What to include in the responses, on top of the effects which are always requested.
+    pub options: SuiTransactionBlockResponseOptions,
+}
+
+impl Default for SubmitterConfig {
+    fn default() -> Self {
+        Self {
+            gas_budget: 50_000_000,
+            max_retries: 5,
+            retry_delay: Duration::from_millis(200),
+            options: SuiTransactionBlockResponseOptions::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SubmitterMetrics {
+    pub submitted: IntCounter,
+    pub succeeded: IntCounter,
+    pub failed: IntCounter,
+    pub errors: IntCounter,
+    pub retries: IntCounter,
+    pub latency: Histogram,
+}
+
+impl SubmitterMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            submitted: register_int_counter_with_registry!(
+                "transaction_submitter_submitted",
+                "Transactions submitted for the first time",
+                registry,
+            )
+            .unwrap(),
+            succeeded: register_int_counter_with_registry!(
+                "transaction_submitter_succeeded",
+                "Transactions executed successfully",
+                registry,
+            )
+            .unwrap(),
+            failed: register_int_counter_with_registry!(
+                "transaction_submitter_failed",
+                "Transactions executed with a failure, e.g. an aborted Move call",
+                registry,
+            )
+            .unwrap(),
+            errors: register_int_counter_with_registry!(
+                "transaction_submitter_errors",
+                "Transactions rejected, or with an unknown outcome after all retries",
+                registry,
+            )
+            .unwrap(),
+            retries: register_int_counter_with_registry!(
+                "transaction_submitter_retries",
+                "Resubmissions of transactions with an unknown outcome",
+                registry,
+            )
+            .unwrap(),
+            latency: register_histogram_with_registry!(
+                "transaction_submitter_latency",
+                "Time spent getting effects of executed transactions, in seconds",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+}
+
+/// Executes programmable transactions concurrently, paying for each with a gas coin leased from a
+/// [GasPool].
+#[derive(Clone)]
+pub struct TransactionSubmitter {
+    pool: GasPool,
+    config: SubmitterConfig,
+    metrics: Arc<SubmitterMetrics>,
+}
+
+impl TransactionSubmitter {
+    pub fn new(pool: GasPool, config: SubmitterConfig, metrics: Arc<SubmitterMetrics>) -> Self {
+        Self {
+            pool,
+            config,
+            metrics,
+        }
+    }
+
+    pub fn pool(&self) -> &GasPool {
+        &self.pool
+    }
+
+    pub fn metrics(&self) -> &Arc<SubmitterMetrics> {
+        &self.metrics
+    }
+
+    /// Executes `pt` on behalf of the owner of the gas pool, returning its response once effects
+    /// are certified. The response can hold a failed execution: only rejected transactions, or
+    /// ones whose outcome is still unknown after all retries, are errors.
+    pub async fn submit(
+        &self,
+        pt: ProgrammableTransaction,
+    ) -> SuiRpcResult<SuiTransactionBlockResponse> {
+        let lease = self.pool.lease().await?;
+        let data = TransactionData::new_programmable(
+            self.pool.owner(),
+            vec![lease.object_ref()],
+            pt,
+            self.config.gas_budget,
+            self.pool.gas_price(),
+        );
+        let tx = self.pool.sign(data);
+        self.metrics.submitted.inc();
+
+        let start = Instant::now();
+        let response = match self.execute(tx).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.metrics.errors.inc();
+                // A rejected transaction never locked its coin, which can be leased again right
+                // away. Otherwise the lease is dropped, which quarantines the coin: the
+                // transaction may have locked it.
+                if is_rejection(&e) {
+                    lease.release_unused();
+                }
+                return Err(e);
+            }
+        };
+        let Some(effects) = &response.effects else {
+            self.metrics.errors.inc();
+            return Err(Error::DataError(format!(
+                "No effects for transaction {}",
+                response.digest
+            )));
+        };
+
+        self.metrics.latency.observe(start.elapsed().as_secs_f64());
+        if effects.status().is_ok() {
+            self.metrics.succeeded.inc();
+        } else {
+            self.metrics.failed.inc();
+        }
+        lease.release(effects);
+        Ok(response)
+    }
+
+    /// Executes `tx`, resubmitting it as long as its outcome is unknown.
+    async fn execute(&self, tx: Transaction) -> SuiRpcResult<SuiTransactionBlockResponse> {
+        let options = self.config.options.clone().with_effects();
+        let mut delay = self.config.retry_delay;
+        let mut retries = 0;
+
+        loop {
+            let error = match self
+                .pool
+                .client()
+                .quorum_driver_api()
+                .execute_transaction_block(
+                    tx.clone(),
+                    options.clone(),
+                    Some(ExecuteTransactionRequestType::WaitForEffectsCert),
+                )
+                .await
+            {
+                Ok(response) => return Ok(response),
+                // Only errors returned by the node tell whether the transaction was rejected.
+                Err(Error::RpcError(e)) => {
+                    let error = Error::JsonRpcError(JsonRpcError::from(e));
+                    if is_rejection(&error) {
+                        return Err(error);
+                    }
+                    error
+                }
+                Err(e) => return Err(e),
+            };
+
+            if retries == self.config.max_retries {
+                return Err(error);
+            }
+            retries += 1;
+            self.metrics.retries.inc();
+            debug!(
+                "Resubmitting transaction {} after error: {error}",
+                tx.digest()
+            );
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+}
+
+/// Whether `error` is the node definitely rejecting a transaction, e.g. failing its validation,
+/// as opposed to a failure leaving its outcome unknown.
+fn is_rejection(error: &Error) -> bool {
+    matches!(error, Error::JsonRpcError(e) if e.is_call_error() && !e.is_transient_error())
+}
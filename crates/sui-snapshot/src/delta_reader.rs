@@ -0,0 +1,409 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::reader::{
+    download_bytes, read_manifest_file, DigestByBucketAndPartition, LiveObjectIter, ObjectRefIter,
+};
+use crate::{
+    accumulate_object_change, DeltaManifest, FileMetadata, FileType, ObjectChange,
+    CHANGE_FILE_MAGIC, DELTA_MANIFEST_FILE_MAGIC, OBJECT_CHANGE_BYTES,
+};
+use anyhow::{anyhow, Context, Result};
+use byteorder::{BigEndian, ReadBytesExt};
+use fastcrypto::hash::MultisetHash;
+use fastcrypto::hash::{HashFunction, Sha3_256};
+use futures::future::{AbortRegistration, Abortable};
+use futures::{StreamExt, TryStreamExt};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use object_store::path::Path;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{ErrorKind, Read};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_config::object_storage_config::ObjectStoreConfig;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::AuthorityStore;
+use sui_storage::compute_sha3_checksum;
+use sui_storage::object_store::util::{copy_file, copy_files, path_to_filesystem};
+use sui_storage::object_store::{ObjectStoreGetExt, ObjectStorePutExt};
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::{ObjectID, ObjectRef};
+use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
+use tokio::sync::Mutex;
+use tracing::info;
+
+/// StateSnapshotDeltaReaderV1 brings a db holding the live object set at the end of `base_epoch`
+/// (e.g. restored with `StateSnapshotReaderV1`) forward to the end of `epoch`, by applying the delta
+/// snapshots of every epoch in between in order.
+pub struct StateSnapshotDeltaReaderV1 {
+    base_epoch: u64,
+    epoch: u64,
+    local_staging_dir_root: PathBuf,
+    remote_object_store: Arc<dyn ObjectStoreGetExt>,
+    local_object_store: Arc<dyn ObjectStorePutExt>,
+    manifests: Vec<DeltaManifest>,
+    indirect_objects_threshold: usize,
+    m: MultiProgress,
+    concurrency: usize,
+}
+
+impl StateSnapshotDeltaReaderV1 {
+    pub async fn new(
+        base_epoch: u64,
+        epoch: u64,
+        remote_store_config: &ObjectStoreConfig,
+        local_store_config: &ObjectStoreConfig,
+        indirect_objects_threshold: usize,
+        download_concurrency: NonZeroUsize,
+        m: MultiProgress,
+    ) -> Result<Self> {
+        if epoch <= base_epoch {
+            return Err(anyhow!(
+                "Epoch: {} must be greater than base epoch: {}",
+                epoch,
+                base_epoch
+            ));
+        }
+        let remote_object_store = if remote_store_config.no_sign_request {
+            remote_store_config.make_http()?
+        } else {
+            remote_store_config.make().map(Arc::new)?
+        };
+        let local_object_store: Arc<dyn ObjectStorePutExt> =
+            local_store_config.make().map(Arc::new)?;
+        let local_staging_dir_root = local_store_config
+            .directory
+            .as_ref()
+            .context("No directory specified")?
+            .clone();
+        let mut manifests = vec![];
+        for delta_epoch in base_epoch + 1..=epoch {
+            let delta_dir = Self::delta_dir(delta_epoch);
+            let local_delta_dir_path =
+                path_to_filesystem(local_staging_dir_root.clone(), &delta_dir)?;
+            if local_delta_dir_path.exists() {
+                fs::remove_dir_all(&local_delta_dir_path)?;
+            }
+            fs::create_dir_all(&local_delta_dir_path)?;
+            // Download MANIFEST first
+            let manifest_file_path = delta_dir.child("MANIFEST");
+            copy_file(
+                &manifest_file_path,
+                &manifest_file_path,
+                &remote_object_store,
+                &local_object_store,
+            )
+            .await?;
+            let manifest: DeltaManifest = read_manifest_file(
+                path_to_filesystem(local_staging_dir_root.clone(), &manifest_file_path)?,
+                DELTA_MANIFEST_FILE_MAGIC,
+            )?;
+            let snapshot_version = manifest.snapshot_version();
+            if snapshot_version != 1u8 {
+                return Err(anyhow!(
+                    "Unexpected delta snapshot version: {}",
+                    snapshot_version
+                ));
+            }
+            if manifest.address_length() as usize > ObjectID::LENGTH {
+                return Err(anyhow!(
+                    "Max possible address length is: {}",
+                    ObjectID::LENGTH
+                ));
+            }
+            if manifest.epoch() != delta_epoch || manifest.base_epoch() != delta_epoch - 1 {
+                return Err(anyhow!(
+                    "Downloaded delta manifest is not for epoch: {}",
+                    delta_epoch
+                ));
+            }
+            // Change logs and references are needed up front to verify the chain of deltas
+            // against the root state hashes. Objects are only downloaded once that succeeds.
+            let files: Vec<&FileMetadata> = manifest
+                .file_metadata()
+                .iter()
+                .filter(|file_metadata| file_metadata.file_type != FileType::Object)
+                .collect();
+            let file_paths: Vec<Path> = files
+                .iter()
+                .map(|file_metadata| file_metadata.file_path(&delta_dir))
+                .collect();
+            copy_files(
+                &file_paths,
+                &file_paths,
+                &remote_object_store,
+                &local_object_store,
+                download_concurrency,
+                None,
+            )
+            .await?;
+            for file_metadata in files {
+                let sha3_digest = compute_sha3_checksum(
+                    &file_metadata.local_file_path(&local_staging_dir_root, &delta_dir)?,
+                )?;
+                if sha3_digest != file_metadata.sha3_digest {
+                    return Err(anyhow!(
+                        "Checksum mismatch for {} in delta snapshot for epoch: {}",
+                        file_metadata.file_path(&delta_dir),
+                        delta_epoch
+                    ));
+                }
+            }
+            manifests.push(manifest);
+        }
+        Ok(StateSnapshotDeltaReaderV1 {
+            base_epoch,
+            epoch,
+            local_staging_dir_root,
+            remote_object_store,
+            local_object_store,
+            manifests,
+            indirect_objects_threshold,
+            m,
+            concurrency: download_concurrency.get(),
+        })
+    }
+
+    /// Applies every delta to `perpetual_db`. `base_accumulator` is the state accumulator at the end
+    /// of the base epoch and `root_state_hashes` must hold the root state hash of every epoch in the
+    /// chain. The whole chain is verified before anything is written to the db. Returns the state
+    /// accumulator at the end of `epoch`.
+    pub async fn read(
+        &mut self,
+        perpetual_db: &AuthorityPerpetualTables,
+        base_accumulator: Accumulator,
+        root_state_hashes: &BTreeMap<u64, ECMHLiveObjectSetDigest>,
+        abort_registration: AbortRegistration,
+    ) -> Result<Accumulator> {
+        let acc = self.verify(base_accumulator, root_state_hashes)?;
+        Abortable::new(
+            async {
+                for manifest in &self.manifests {
+                    self.apply(perpetual_db, manifest).await?;
+                }
+                Ok::<(), anyhow::Error>(())
+            },
+            abort_registration,
+        )
+        .await??;
+        Ok(acc)
+    }
+
+    fn verify(
+        &self,
+        base_accumulator: Accumulator,
+        root_state_hashes: &BTreeMap<u64, ECMHLiveObjectSetDigest>,
+    ) -> Result<Accumulator> {
+        let mut acc = base_accumulator;
+        for manifest in &self.manifests {
+            let epoch = manifest.epoch();
+            let delta_dir = Self::delta_dir(epoch);
+            // Objects are matched against the .ref files when they are inserted, so the .ref files
+            // have to list exactly the objects which the change log says were created or mutated.
+            let mut changed_acc = Accumulator::default();
+            let mut ref_acc = Accumulator::default();
+            for file_metadata in manifest.file_metadata() {
+                match file_metadata.file_type {
+                    FileType::Change => {
+                        for change in ObjectChangeIter::new(
+                            file_metadata,
+                            self.local_staging_dir_root.clone(),
+                            delta_dir.clone(),
+                        )? {
+                            let change = change?;
+                            accumulate_object_change(&mut acc, &change);
+                            if let Some(current) = change.current_ref() {
+                                changed_acc.insert(current.2);
+                            }
+                        }
+                    }
+                    FileType::Reference => {
+                        for object_ref in ObjectRefIter::new(
+                            file_metadata,
+                            self.local_staging_dir_root.clone(),
+                            delta_dir.clone(),
+                        )? {
+                            ref_acc.insert(object_ref.2);
+                        }
+                    }
+                    FileType::Object => {}
+                }
+            }
+            if changed_acc.digest() != ref_acc.digest() {
+                return Err(anyhow!(
+                    "Object references don't match change log in delta snapshot for epoch: {}",
+                    epoch
+                ));
+            }
+            let root_state_hash = root_state_hashes
+                .get(&epoch)
+                .context(format!("No root state hash for epoch: {epoch}"))?;
+            let local_digest = ECMHLiveObjectSetDigest::from(acc.digest());
+            if local_digest != *root_state_hash {
+                return Err(anyhow!(
+                    "End of epoch {} root state digest {} does not match local root state hash {} \
+                    after applying delta snapshot",
+                    epoch,
+                    root_state_hash.digest,
+                    local_digest.digest,
+                ));
+            }
+            info!("Verified delta snapshot for epoch: {}", epoch);
+        }
+        Ok(acc)
+    }
+
+    async fn apply(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        manifest: &DeltaManifest,
+    ) -> Result<()> {
+        let delta_dir = Self::delta_dir(manifest.epoch());
+        let mut sha3_digests: DigestByBucketAndPartition = BTreeMap::new();
+        let mut object_files = vec![];
+        for file_metadata in manifest.file_metadata() {
+            match file_metadata.file_type {
+                FileType::Change => {
+                    // Previous versions of mutated objects are removed along with deleted objects
+                    let removed: Vec<ObjectRef> = ObjectChangeIter::new(
+                        file_metadata,
+                        self.local_staging_dir_root.clone(),
+                        delta_dir.clone(),
+                    )?
+                    .filter_map(|change| change.map(|c| c.previous_ref()).transpose())
+                    .collect::<Result<_>>()?;
+                    AuthorityStore::bulk_remove_live_objects(perpetual_db, &removed)?;
+                }
+                FileType::Reference => {
+                    let mut hasher = Sha3_256::default();
+                    for object_ref in ObjectRefIter::new(
+                        file_metadata,
+                        self.local_staging_dir_root.clone(),
+                        delta_dir.clone(),
+                    )? {
+                        hasher.update(object_ref.2.inner());
+                    }
+                    sha3_digests
+                        .entry(file_metadata.bucket_num)
+                        .or_default()
+                        .insert(file_metadata.part_num, hasher.finalize().digest);
+                }
+                FileType::Object => object_files.push(file_metadata.clone()),
+            }
+        }
+        let sha3_digests = Arc::new(Mutex::new(sha3_digests));
+        let obj_progress_bar = self.m.add(
+            ProgressBar::new(object_files.len() as u64).with_style(
+                ProgressStyle::with_template(&format!(
+                    "[{{elapsed_precise}}] {{wide_bar}} {{pos}} out of {{len}} .obj files of \
+                    epoch {} delta done ({{msg}})",
+                    manifest.epoch()
+                ))
+                .unwrap(),
+            ),
+        );
+        let threshold = self.indirect_objects_threshold;
+        futures::stream::iter(object_files.iter())
+            .map(|file_metadata| {
+                let delta_dir = delta_dir.clone();
+                let remote_object_store = self.remote_object_store.clone();
+                let sha3_digests = sha3_digests.clone();
+                async move {
+                    let (bytes, sha3_digest) = download_bytes(
+                        remote_object_store,
+                        file_metadata,
+                        delta_dir,
+                        sha3_digests,
+                        &&file_metadata.bucket_num,
+                        &file_metadata.part_num,
+                        None,
+                    )
+                    .await;
+                    Ok::<_, anyhow::Error>((bytes, file_metadata, sha3_digest))
+                }
+            })
+            .boxed()
+            .buffer_unordered(self.concurrency)
+            .try_for_each(|(bytes, file_metadata, sha3_digest)| {
+                let result = LiveObjectIter::new(file_metadata, bytes).and_then(|obj_iter| {
+                    AuthorityStore::bulk_insert_live_objects(
+                        perpetual_db,
+                        obj_iter,
+                        threshold,
+                        &sha3_digest,
+                    )
+                    .map_err(anyhow::Error::from)
+                });
+                obj_progress_bar.inc(1);
+                futures::future::ready(result)
+            })
+            .await?;
+        obj_progress_bar.finish_with_message("Objects download complete");
+        Ok(())
+    }
+
+    pub fn base_epoch(&self) -> u64 {
+        self.base_epoch
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    fn delta_dir(epoch: u64) -> Path {
+        Path::from(format!("delta/epoch_{}", epoch))
+    }
+}
+
+/// An iterator over all object changes in a *.chg file.
+pub struct ObjectChangeIter {
+    reader: Box<dyn Read>,
+}
+
+impl ObjectChangeIter {
+    pub fn new(file_metadata: &FileMetadata, root_path: PathBuf, dir_path: Path) -> Result<Self> {
+        let file_path = file_metadata.local_file_path(&root_path, &dir_path)?;
+        let mut reader = file_metadata.file_compression.decompress(&file_path)?;
+        let magic = reader.read_u32::<BigEndian>()?;
+        if magic != CHANGE_FILE_MAGIC {
+            Err(anyhow!(
+                "Unexpected magic string in CHANGE file: {:?}",
+                magic
+            ))
+        } else {
+            Ok(ObjectChangeIter { reader })
+        }
+    }
+
+    /// Reads the next fixed size record, returning `None` once the file ends on a record boundary.
+    /// A file that ends in the middle of a record is truncated, which is reported as an error.
+    fn next_change(&mut self) -> Result<Option<ObjectChange>> {
+        let mut buf = [0u8; OBJECT_CHANGE_BYTES];
+        let mut read = 0;
+        while read < OBJECT_CHANGE_BYTES {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        match read {
+            0 => Ok(None),
+            OBJECT_CHANGE_BYTES => ObjectChange::from_bytes(&buf).map(Some),
+            _ => Err(anyhow!(
+                "CHANGE file ends in the middle of a record after {} bytes",
+                read
+            )),
+        }
+    }
+}
+
+impl Iterator for ObjectChangeIter {
+    type Item = Result<ObjectChange>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_change().transpose()
+    }
+}
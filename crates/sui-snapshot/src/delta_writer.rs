@@ -0,0 +1,408 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::writer::{
+    start_upload, sync_file_to_remote, write_manifest_file, LiveObjectSetWriterV1,
+    StateSnapshotWriterV1,
+};
+use crate::{
+    create_file_metadata, DeltaManifest, DeltaManifestV1, FileCompression, FileMetadata, FileType,
+    ObjectChange, CHANGE_FILE_MAGIC, DELTA_MANIFEST_FILE_MAGIC, FILE_MAX_BYTES, MAGIC_BYTES,
+    OBJECT_CHANGE_BYTES,
+};
+use anyhow::{anyhow, Context, Result};
+use byteorder::{BigEndian, ByteOrder};
+use fastcrypto::hash::MultisetHash;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry::Vacant;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_config::object_storage_config::ObjectStoreConfig;
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_core::authority::CHAIN_IDENTIFIER;
+use sui_core::state_accumulator::StateAccumulator;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_storage::object_store::util::{delete_recursively, path_to_filesystem};
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::ObjectID;
+use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
+use sui_types::sui_system_state::get_sui_system_state;
+use sui_types::sui_system_state::SuiSystemStateTrait;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::Sender;
+
+/// ObjectChangeWriterV1 writes the change log of a delta snapshot. It creates multiple *.chg files
+struct ObjectChangeWriterV1 {
+    dir_path: PathBuf,
+    bucket_num: u32,
+    current_part_num: u32,
+    wbuf: BufWriter<File>,
+    n: usize,
+    files: Vec<FileMetadata>,
+    sender: Option<Sender<FileMetadata>>,
+    file_compression: FileCompression,
+}
+
+impl ObjectChangeWriterV1 {
+    fn new(
+        dir_path: PathBuf,
+        bucket_num: u32,
+        file_compression: FileCompression,
+        sender: Sender<FileMetadata>,
+    ) -> Result<Self> {
+        let part_num = 1;
+        let (n, f) = Self::change_file(dir_path.clone(), bucket_num, part_num)?;
+        Ok(ObjectChangeWriterV1 {
+            dir_path,
+            bucket_num,
+            current_part_num: part_num,
+            wbuf: BufWriter::new(f),
+            n,
+            files: vec![],
+            sender: Some(sender),
+            file_compression,
+        })
+    }
+    pub fn write(&mut self, change: &ObjectChange) -> Result<()> {
+        if self.n + OBJECT_CHANGE_BYTES > FILE_MAX_BYTES {
+            self.cut()?;
+        }
+        self.wbuf.write_all(&change.to_bytes())?;
+        self.n += OBJECT_CHANGE_BYTES;
+        Ok(())
+    }
+    pub fn done(mut self) -> Result<Vec<FileMetadata>> {
+        self.finalize()?;
+        self.sender = None;
+        Ok(self.files.clone())
+    }
+    fn change_file(dir_path: PathBuf, bucket_num: u32, part_num: u32) -> Result<(usize, File)> {
+        let change_path = dir_path.join(format!("{bucket_num}_{part_num}.chg"));
+        let change_tmp_path = dir_path.join(format!("{bucket_num}_{part_num}.chg.tmp"));
+        let mut f = File::create(change_tmp_path.clone())?;
+        f.rewind()?;
+        let mut metab = [0u8; MAGIC_BYTES];
+        BigEndian::write_u32(&mut metab, CHANGE_FILE_MAGIC);
+        let n = f.write(&metab)?;
+        drop(f);
+        fs::rename(change_tmp_path, change_path.clone())?;
+        let mut f = OpenOptions::new().append(true).open(change_path)?;
+        f.seek(SeekFrom::Start(n as u64))?;
+        Ok((n, f))
+    }
+    fn finalize(&mut self) -> Result<()> {
+        self.wbuf.flush()?;
+        self.wbuf.get_ref().sync_data()?;
+        let off = self.wbuf.get_ref().stream_position()?;
+        self.wbuf.get_ref().set_len(off)?;
+        let file_path = self
+            .dir_path
+            .join(format!("{}_{}.chg", self.bucket_num, self.current_part_num));
+        let file_metadata = create_file_metadata(
+            &file_path,
+            self.file_compression,
+            FileType::Change,
+            self.bucket_num,
+            self.current_part_num,
+        )?;
+        self.files.push(file_metadata.clone());
+        if let Some(sender) = &self.sender {
+            sender.blocking_send(file_metadata)?;
+        }
+        Ok(())
+    }
+    fn cut(&mut self) -> Result<()> {
+        self.finalize()?;
+        self.current_part_num += 1;
+        let (n, f) = Self::change_file(
+            self.dir_path.clone(),
+            self.bucket_num,
+            self.current_part_num,
+        )?;
+        self.n = n;
+        self.wbuf = BufWriter::new(f);
+        Ok(())
+    }
+}
+
+/// StateSnapshotDeltaWriterV1 writes the difference between the live object sets at the end of two
+/// consecutive epochs to a local staging dir and simultaneously uploads it to a remote object store.
+/// Only objects which were created or mutated in the epoch are written out, so the size of a delta
+/// is proportional to the number of objects touched in the epoch rather than the whole live set.
+pub struct StateSnapshotDeltaWriterV1 {
+    local_staging_dir: PathBuf,
+    file_compression: FileCompression,
+    remote_object_store: Arc<DynObjectStore>,
+    local_staging_store: Arc<DynObjectStore>,
+    concurrency: usize,
+}
+
+impl StateSnapshotDeltaWriterV1 {
+    pub async fn new_from_store(
+        local_staging_path: &std::path::Path,
+        local_staging_store: &Arc<DynObjectStore>,
+        remote_object_store: &Arc<DynObjectStore>,
+        file_compression: FileCompression,
+        concurrency: NonZeroUsize,
+    ) -> Result<Self> {
+        Ok(StateSnapshotDeltaWriterV1 {
+            file_compression,
+            local_staging_dir: local_staging_path.to_path_buf(),
+            remote_object_store: remote_object_store.clone(),
+            local_staging_store: local_staging_store.clone(),
+            concurrency: concurrency.get(),
+        })
+    }
+
+    pub async fn new(
+        local_store_config: &ObjectStoreConfig,
+        remote_store_config: &ObjectStoreConfig,
+        file_compression: FileCompression,
+        concurrency: NonZeroUsize,
+    ) -> Result<Self> {
+        let remote_object_store = remote_store_config.make()?;
+        let local_staging_store = local_store_config.make()?;
+        let local_staging_dir = local_store_config
+            .directory
+            .as_ref()
+            .context("No local directory specified")?
+            .clone();
+        Ok(StateSnapshotDeltaWriterV1 {
+            local_staging_dir,
+            file_compression,
+            remote_object_store,
+            local_staging_store,
+            concurrency: concurrency.get(),
+        })
+    }
+
+    /// Writes the delta from the live object set in `base_perpetual_db` (end of `epoch - 1`) to the
+    /// one in `perpetual_db` (end of `epoch`). `root_state_hash` is the state commitment of `epoch`.
+    pub async fn write(
+        self,
+        epoch: u64,
+        base_perpetual_db: Arc<AuthorityPerpetualTables>,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        let system_state_object = get_sui_system_state(&perpetual_db)?;
+
+        let protocol_version = system_state_object.protocol_version();
+        let chain_identifier = CHAIN_IDENTIFIER
+            .get()
+            .ok_or(anyhow!("No chain identifier found"))?;
+        let protocol_config = ProtocolConfig::get_for_version(
+            ProtocolVersion::new(protocol_version),
+            chain_identifier.chain(),
+        );
+        let include_wrapped_tombstone = !protocol_config.simplified_unwrap_then_delete();
+        self.write_internal(
+            epoch,
+            include_wrapped_tombstone,
+            base_perpetual_db,
+            perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
+    pub(crate) async fn write_internal(
+        mut self,
+        epoch: u64,
+        include_wrapped_tombstone: bool,
+        base_perpetual_db: Arc<AuthorityPerpetualTables>,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        if epoch == 0 {
+            return Err(anyhow!("There is no delta snapshot for the genesis epoch"));
+        }
+        self.setup_delta_dir(epoch).await?;
+
+        let manifest_file_path = self.delta_dir(epoch).child("MANIFEST");
+        let local_staging_dir = self.local_staging_dir.clone();
+        let local_object_store = self.local_staging_store.clone();
+        let remote_object_store = self.remote_object_store.clone();
+
+        let (sender, receiver) = mpsc::channel::<FileMetadata>(1000);
+        let upload_handle = start_upload(
+            self.delta_dir(epoch),
+            receiver,
+            self.local_staging_dir.clone(),
+            self.local_staging_store.clone(),
+            self.remote_object_store.clone(),
+            self.concurrency,
+        )?;
+        let write_handler = tokio::task::spawn_blocking(move || {
+            self.write_delta(
+                epoch,
+                base_perpetual_db,
+                perpetual_db,
+                sender,
+                StateSnapshotWriterV1::bucket_func,
+                include_wrapped_tombstone,
+                root_state_hash,
+            )
+        });
+        write_handler.await?.context(format!(
+            "Failed to write delta state snapshot for epoch: {}",
+            &epoch
+        ))?;
+
+        upload_handle.await?.context(format!(
+            "Failed to upload delta state snapshot for epoch: {}",
+            &epoch
+        ))?;
+
+        sync_file_to_remote(
+            local_staging_dir,
+            manifest_file_path,
+            local_object_store,
+            remote_object_store,
+        )
+        .await?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_delta<F>(
+        &mut self,
+        epoch: u64,
+        base_perpetual_db: Arc<AuthorityPerpetualTables>,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        sender: Sender<FileMetadata>,
+        bucket_func: F,
+        include_wrapped_tombstone: bool,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()>
+    where
+        F: Fn(&LiveObject) -> u32,
+    {
+        let local_staging_dir_path =
+            path_to_filesystem(self.local_staging_dir.clone(), &self.delta_dir(epoch))?;
+        let mut object_writers: HashMap<u32, LiveObjectSetWriterV1> = HashMap::new();
+        let mut change_writer = ObjectChangeWriterV1::new(
+            local_staging_dir_path.clone(),
+            1,
+            self.file_compression,
+            sender.clone(),
+        )?;
+        let mut write_object = |object: &LiveObject| -> Result<()> {
+            let bucket_num = bucket_func(object);
+            if let Vacant(entry) = object_writers.entry(bucket_num) {
+                entry.insert(LiveObjectSetWriterV1::new(
+                    local_staging_dir_path.clone(),
+                    bucket_num,
+                    self.file_compression,
+                    sender.clone(),
+                )?);
+            }
+            object_writers
+                .get_mut(&bucket_num)
+                .context("Unexpected missing bucket writer")?
+                .write(object)
+        };
+
+        // Both live object sets are iterated in object id order, so the delta can be computed by
+        // merging the two iterators in a single pass.
+        let mut acc = Accumulator::default();
+        let (mut num_created, mut num_mutated, mut num_deleted) = (0u64, 0u64, 0u64);
+        let mut base_iter = base_perpetual_db
+            .iter_live_object_set(include_wrapped_tombstone)
+            .peekable();
+        let mut iter = perpetual_db
+            .iter_live_object_set(include_wrapped_tombstone)
+            .peekable();
+        loop {
+            let ordering = match (base_iter.peek(), iter.peek()) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(base), Some(object)) => base.object_id().cmp(&object.object_id()),
+            };
+            match ordering {
+                Ordering::Less => {
+                    let base = base_iter.next().context("Base live object set ended")?;
+                    change_writer.write(&ObjectChange::deleted(base.object_reference()))?;
+                    num_deleted += 1;
+                }
+                Ordering::Greater => {
+                    let object = iter.next().context("Live object set ended")?;
+                    StateAccumulator::accumulate_live_object(&mut acc, &object);
+                    write_object(&object)?;
+                    change_writer.write(&ObjectChange::created(object.object_reference()))?;
+                    num_created += 1;
+                }
+                Ordering::Equal => {
+                    let base = base_iter.next().context("Base live object set ended")?;
+                    let object = iter.next().context("Live object set ended")?;
+                    StateAccumulator::accumulate_live_object(&mut acc, &object);
+                    if base.object_reference() != object.object_reference() {
+                        write_object(&object)?;
+                        change_writer.write(&ObjectChange::mutated(
+                            base.object_reference(),
+                            object.object_reference(),
+                        ))?;
+                        num_mutated += 1;
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            ECMHLiveObjectSetDigest::from(acc.digest()),
+            root_state_hash,
+            "Root state hash mismatch!"
+        );
+        let mut files = change_writer.done()?;
+        for (_, writer) in object_writers.into_iter() {
+            files.extend(writer.done()?);
+        }
+        let manifest = DeltaManifest::V1(DeltaManifestV1 {
+            snapshot_version: 1,
+            address_length: ObjectID::LENGTH as u64,
+            file_metadata: files,
+            base_epoch: epoch - 1,
+            epoch,
+            num_created,
+            num_mutated,
+            num_deleted,
+        });
+        write_manifest_file(
+            path_to_filesystem(
+                self.local_staging_dir.clone(),
+                &self.delta_dir(epoch).child("MANIFEST"),
+            )?,
+            DELTA_MANIFEST_FILE_MAGIC,
+            &manifest,
+        )
+    }
+
+    fn delta_dir(&self, epoch: u64) -> Path {
+        Path::from(format!("delta/epoch_{}", epoch))
+    }
+
+    async fn setup_delta_dir(&self, epoch: u64) -> Result<()> {
+        let delta_dir = self.delta_dir(epoch);
+        // Delete remote delta dir if it exists
+        delete_recursively(
+            &delta_dir,
+            &self.remote_object_store,
+            NonZeroUsize::new(self.concurrency).unwrap(),
+        )
+        .await?;
+        // Delete local staging delta dir if it exists
+        let local_delta_dir_path = path_to_filesystem(self.local_staging_dir.clone(), &delta_dir)?;
+        if local_delta_dir_path.exists() {
+            fs::remove_dir_all(&local_delta_dir_path)?;
+        }
+        fs::create_dir_all(&local_delta_dir_path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::reader::LiveObjectIter;
+use anyhow::{anyhow, Result};
+use arrow_array::{ArrayRef, BinaryArray, RecordBatch, StringArray, UInt64Array};
+use fastcrypto::hash::{HashFunction, Sha3_256};
+use move_core_types::language_storage::StructTag;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::fs;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use sui_core::authority::authority_store_tables::LiveObject;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::object::Object;
+use sui_types::TypeTag;
+use typed_store::rocks::{DBMap, MetricConf};
+use typed_store::traits::TableSummary;
+use typed_store::traits::TypedStoreDebug;
+use typed_store::DBMapUtils;
+use typed_store::Map;
+
+/// Number of objects buffered in memory before a new parquet file is written
+const PARQUET_MAX_ROWS: usize = 100_000;
+
+/// Selects the live objects kept by a filtered restore. An object is kept if it matches any of the
+/// configured types, packages or owners.
+#[derive(Clone, Debug, Default)]
+pub struct LiveObjectFilter {
+    /// Types to keep. A type without type parameters matches every instantiation of it, e.g.
+    /// `0x2::coin::Coin` matches `0x2::coin::Coin<0x2::sui::SUI>`.
+    pub types: Vec<StructTag>,
+    /// Packages to keep, along with all objects whose type is defined in them
+    pub packages: Vec<ObjectID>,
+    /// Objects whose immediate owner is one of these addresses. Ownership isn't followed through
+    /// objects: a child object, e.g. a dynamic field, is only kept if the id of its parent object
+    /// is listed here.
+    pub owners: Vec<SuiAddress>,
+}
+
+impl LiveObjectFilter {
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.packages.is_empty() && self.owners.is_empty()
+    }
+
+    pub fn matches(&self, object: &Object) -> bool {
+        if object.is_package() {
+            return self.packages.contains(&object.id());
+        }
+        if let Ok(owner) = object.owner.get_owner_address() {
+            if self.owners.contains(&owner) {
+                return true;
+            }
+        }
+        let Some(struct_tag) = object.struct_tag() else {
+            return false;
+        };
+        self.packages
+            .iter()
+            .any(|package| struct_tag_in_package(&struct_tag, package))
+            || self
+                .types
+                .iter()
+                .any(|type_| struct_tag_matches(type_, &struct_tag))
+    }
+}
+
+fn struct_tag_matches(filter: &StructTag, struct_tag: &StructTag) -> bool {
+    filter.address == struct_tag.address
+        && filter.module == struct_tag.module
+        && filter.name == struct_tag.name
+        && (filter.type_params.is_empty() || filter.type_params == struct_tag.type_params)
+}
+
+/// Whether `struct_tag` or any of its type parameters is defined in `package`
+fn struct_tag_in_package(struct_tag: &StructTag, package: &ObjectID) -> bool {
+    ObjectID::from(struct_tag.address) == *package
+        || struct_tag
+            .type_params
+            .iter()
+            .any(|type_param| match type_param {
+                TypeTag::Struct(struct_tag) => struct_tag_in_package(struct_tag, package),
+                TypeTag::Vector(inner) => match inner.as_ref() {
+                    TypeTag::Struct(struct_tag) => struct_tag_in_package(struct_tag, package),
+                    _ => false,
+                },
+                _ => false,
+            })
+}
+
+/// Destination of the objects kept by a filtered restore
+pub trait FilteredObjectWriter: Send + Sync {
+    fn write(&self, objects: Vec<Object>) -> Result<()>;
+    /// Called once after every object has been written
+    fn finish(&self) -> Result<()>;
+}
+
+/// Consumes every object of a *.obj file, checking them against the sha3 digest of the matching
+/// REFERENCE file partition like `AuthorityStore::bulk_insert_live_objects` does, and hands the
+/// ones accepted by `filter` to `writer`.
+pub(crate) fn write_filtered_live_objects(
+    live_objects: LiveObjectIter,
+    filter: &LiveObjectFilter,
+    writer: &dyn FilteredObjectWriter,
+    expected_sha3_digest: &[u8; 32],
+) -> Result<()> {
+    let mut hasher = Sha3_256::default();
+    let mut objects = vec![];
+    for live_object in live_objects {
+        hasher.update(live_object.object_reference().2.inner());
+        if let LiveObject::Normal(object) = live_object {
+            if filter.matches(&object) {
+                objects.push(object);
+            }
+        }
+    }
+    let sha3_digest = hasher.finalize().digest;
+    if *expected_sha3_digest != sha3_digest {
+        return Err(anyhow!(
+            "Sha does not match! expected: {:?}, actual: {:?}",
+            expected_sha3_digest,
+            sha3_digest
+        ));
+    }
+    writer.write(objects)
+}
+
+#[derive(DBMapUtils)]
+pub struct FilteredObjectTables {
+    pub objects: DBMap<ObjectID, Object>,
+}
+
+/// Writes filtered objects to a standalone RocksDB, keyed by object id
+pub struct RocksDbFilteredObjectWriter {
+    tables: FilteredObjectTables,
+}
+
+impl RocksDbFilteredObjectWriter {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            tables: FilteredObjectTables::open_tables_read_write(
+                path.to_path_buf(),
+                MetricConf::new("filtered_snapshot"),
+                None,
+                None,
+            ),
+        }
+    }
+
+    pub fn tables(&self) -> &FilteredObjectTables {
+        &self.tables
+    }
+}
+
+impl FilteredObjectWriter for RocksDbFilteredObjectWriter {
+    fn write(&self, objects: Vec<Object>) -> Result<()> {
+        let mut batch = self.tables.objects.batch();
+        batch.insert_batch(
+            &self.tables.objects,
+            objects.iter().map(|object| (object.id(), object)),
+        )?;
+        batch.write()?;
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        self.tables.objects.rocksdb.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes filtered objects to parquet files in a directory. Every file holds up to
+/// `PARQUET_MAX_ROWS` objects with their id, version, digest, type, owner and bcs bytes.
+pub struct ParquetFilteredObjectWriter {
+    dir_path: PathBuf,
+    state: Mutex<ParquetWriterState>,
+}
+
+#[derive(Default)]
+struct ParquetWriterState {
+    objects: Vec<Object>,
+    next_file_num: u64,
+}
+
+impl ParquetFilteredObjectWriter {
+    pub fn new(dir_path: &Path) -> Result<Self> {
+        fs::create_dir_all(dir_path)?;
+        Ok(Self {
+            dir_path: dir_path.to_path_buf(),
+            state: Mutex::new(ParquetWriterState::default()),
+        })
+    }
+
+    fn flush(&self, state: &mut ParquetWriterState) -> Result<()> {
+        if state.objects.is_empty() {
+            return Ok(());
+        }
+        let objects = std::mem::take(&mut state.objects);
+        let object_ids: Vec<String> = objects.iter().map(|o| o.id().to_string()).collect();
+        let versions: Vec<u64> = objects.iter().map(|o| o.version().value()).collect();
+        let digests: Vec<String> = objects.iter().map(|o| o.digest().to_string()).collect();
+        let types: Vec<Option<String>> = objects
+            .iter()
+            .map(|o| o.struct_tag().map(|t| t.to_canonical_string(true)))
+            .collect();
+        let owners: Vec<String> = objects.iter().map(|o| o.owner.to_string()).collect();
+        let bcs: Vec<Vec<u8>> = objects
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, _>>()?;
+        let batch = RecordBatch::try_from_iter([
+            (
+                "object_id",
+                Arc::new(StringArray::from(object_ids)) as ArrayRef,
+            ),
+            ("version", Arc::new(UInt64Array::from(versions)) as ArrayRef),
+            ("digest", Arc::new(StringArray::from(digests)) as ArrayRef),
+            ("type", Arc::new(StringArray::from(types)) as ArrayRef),
+            ("owner", Arc::new(StringArray::from(owners)) as ArrayRef),
+            (
+                "bcs",
+                Arc::new(BinaryArray::from_iter_values(bcs)) as ArrayRef,
+            ),
+        ])?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let file_path = self
+            .dir_path
+            .join(format!("objects_{}.parquet", state.next_file_num));
+        state.next_file_num += 1;
+        let mut writer =
+            ArrowWriter::try_new(File::create(file_path)?, batch.schema(), Some(properties))?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+}
+
+impl FilteredObjectWriter for ParquetFilteredObjectWriter {
+    fn write(&self, objects: Vec<Object>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.objects.extend(objects);
+        if state.objects.len() >= PARQUET_MAX_ROWS {
+            self.flush(&mut state)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        self.flush(&mut state)
+    }
+}
@@ -5,11 +5,15 @@
 #[cfg(test)]
 mod tests;
 
+pub mod delta_reader;
+pub mod delta_writer;
+pub mod filter;
 pub mod reader;
 pub mod uploader;
 mod writer;
 
 use anyhow::Result;
+use byteorder::{BigEndian, ByteOrder};
 use fastcrypto::hash::MultisetHash;
 use indicatif::MultiProgress;
 use indicatif::ProgressBar;
@@ -34,7 +38,7 @@ use sui_protocol_config::Chain;
 use sui_storage::object_store::util::path_to_filesystem;
 use sui_storage::{compute_sha3_checksum, FileCompression, SHA3_BYTES};
 use sui_types::accumulator::Accumulator;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
 use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use sui_types::sui_system_state::get_sui_system_state;
@@ -115,9 +119,48 @@ use tokio::time::Instant;
 ///├──────────────────────────────┤
 ///│      sha3 <32 bytes>         │
 ///└──────────────────────────────┘
+///
+/// Delta snapshots record how the live object set changed between the end of two consecutive
+/// epochs, so that a node restored from the formal snapshot of epoch N can be brought forward to
+/// epoch N + k without downloading the full live object set again. The objects which were created
+/// or mutated in the epoch are stored in *.obj and *.ref files in exactly the same format as above.
+/// Every change to the live object set, including deletions, is recorded in the change log (*.chg)
+/// along with the reference the object had at the end of the previous epoch. This is what allows
+/// the state accumulator of the base epoch to be rolled forward and checked against the root state
+/// hash of every epoch in the chain before anything is written to the db. The delta MANIFEST has
+/// the same layout as the snapshot MANIFEST but a different magic.
+/// Delta Snapshot Directory Layout
+///  - snapshot/
+///     - delta/
+///        - epoch_1/
+///           - 1_1.obj
+///           - 1_1.ref
+///           - 1_1.chg
+///           - MANIFEST
+///        - epoch_2/
+///           - ...
+///
+/// CHANGE File Disk Format
+///┌──────────────────────────────┐
+///│  magic(0x0DE17A5E) <4 byte>  │
+///├──────────────────────────────┤
+///│ ┌──────────────────────────┐ │
+///│ │       ObjectChange 1     │ │
+///│ ├──────────────────────────┤ │
+///│ │          ...             │ │
+///│ ├──────────────────────────┤ │
+///│ │       ObjectChange N     │ │
+///│ └──────────────────────────┘ │
+///└──────────────────────────────┘
+/// ObjectChange (previous and current version/digest are zeroed when absent)
+///┌───────────────┬──────────────────┬─────────────────────────┬─────────────────────────┐
+///│ kind <1 byte> │ ObjectID <bytes> │ previous (seq, digest)  │ current (seq, digest)   │
+///└───────────────┴──────────────────┴─────────────────────────┴─────────────────────────┘
 const OBJECT_FILE_MAGIC: u32 = 0x00B7EC75;
 const REFERENCE_FILE_MAGIC: u32 = 0xDEADBEEF;
 const MANIFEST_FILE_MAGIC: u32 = 0x00C0FFEE;
+const CHANGE_FILE_MAGIC: u32 = 0x0DE17A5E;
+const DELTA_MANIFEST_FILE_MAGIC: u32 = 0x0DE17AFE;
 const MAGIC_BYTES: usize = 4;
 const SNAPSHOT_VERSION_BYTES: usize = 1;
 const ADDRESS_LENGTH_BYTES: usize = 8;
@@ -129,6 +172,9 @@ const OBJECT_ID_BYTES: usize = ObjectID::LENGTH;
 const SEQUENCE_NUM_BYTES: usize = 8;
 const OBJECT_DIGEST_BYTES: usize = 32;
 const OBJECT_REF_BYTES: usize = OBJECT_ID_BYTES + SEQUENCE_NUM_BYTES + OBJECT_DIGEST_BYTES;
+const CHANGE_KIND_BYTES: usize = 1;
+const OBJECT_CHANGE_BYTES: usize =
+    CHANGE_KIND_BYTES + OBJECT_ID_BYTES + 2 * (SEQUENCE_NUM_BYTES + OBJECT_DIGEST_BYTES);
 const FILE_TYPE_BYTES: usize = 1;
 const BUCKET_BYTES: usize = 4;
 const BUCKET_PARTITION_BYTES: usize = 4;
@@ -143,6 +189,7 @@ const FILE_METADATA_BYTES: usize =
 pub enum FileType {
     Object = 0,
     Reference,
+    Change,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -163,6 +210,9 @@ impl FileMetadata {
             FileType::Reference => {
                 dir_path.child(&*format!("{}_{}.ref", self.bucket_num, self.part_num))
             }
+            FileType::Change => {
+                dir_path.child(&*format!("{}_{}.chg", self.bucket_num, self.part_num))
+            }
         }
     }
     pub fn local_file_path(&self, root_path: &std::path::Path, dir_path: &Path) -> Result<PathBuf> {
@@ -206,6 +256,171 @@ impl Manifest {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DeltaManifestV1 {
+    pub snapshot_version: u8,
+    pub address_length: u64,
+    pub file_metadata: Vec<FileMetadata>,
+    /// Epoch whose live object set this delta applies on top of
+    pub base_epoch: u64,
+    pub epoch: u64,
+    pub num_created: u64,
+    pub num_mutated: u64,
+    pub num_deleted: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum DeltaManifest {
+    V1(DeltaManifestV1),
+}
+
+impl DeltaManifest {
+    pub fn snapshot_version(&self) -> u8 {
+        match self {
+            Self::V1(manifest) => manifest.snapshot_version,
+        }
+    }
+    pub fn address_length(&self) -> u64 {
+        match self {
+            Self::V1(manifest) => manifest.address_length,
+        }
+    }
+    pub fn file_metadata(&self) -> &Vec<FileMetadata> {
+        match self {
+            Self::V1(manifest) => &manifest.file_metadata,
+        }
+    }
+    pub fn base_epoch(&self) -> u64 {
+        match self {
+            Self::V1(manifest) => manifest.base_epoch,
+        }
+    }
+    pub fn epoch(&self) -> u64 {
+        match self {
+            Self::V1(manifest) => manifest.epoch,
+        }
+    }
+}
+
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, TryFromPrimitive, IntoPrimitive,
+)]
+#[repr(u8)]
+pub enum ObjectChangeKind {
+    Created = 0,
+    Mutated,
+    Deleted,
+}
+
+/// A single change to the live object set between the base epoch and the epoch of a delta snapshot
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ObjectChange {
+    pub kind: ObjectChangeKind,
+    pub object_id: ObjectID,
+    /// Version and digest at the end of the base epoch, absent for created objects
+    pub previous: Option<(SequenceNumber, ObjectDigest)>,
+    /// Version and digest at the end of the epoch, absent for deleted objects
+    pub current: Option<(SequenceNumber, ObjectDigest)>,
+}
+
+impl ObjectChange {
+    pub fn created(current: ObjectRef) -> Self {
+        Self {
+            kind: ObjectChangeKind::Created,
+            object_id: current.0,
+            previous: None,
+            current: Some((current.1, current.2)),
+        }
+    }
+    pub fn mutated(previous: ObjectRef, current: ObjectRef) -> Self {
+        Self {
+            kind: ObjectChangeKind::Mutated,
+            object_id: current.0,
+            previous: Some((previous.1, previous.2)),
+            current: Some((current.1, current.2)),
+        }
+    }
+    pub fn deleted(previous: ObjectRef) -> Self {
+        Self {
+            kind: ObjectChangeKind::Deleted,
+            object_id: previous.0,
+            previous: Some((previous.1, previous.2)),
+            current: None,
+        }
+    }
+    pub fn previous_ref(&self) -> Option<ObjectRef> {
+        self.previous
+            .map(|(version, digest)| (self.object_id, version, digest))
+    }
+    pub fn current_ref(&self) -> Option<ObjectRef> {
+        self.current
+            .map(|(version, digest)| (self.object_id, version, digest))
+    }
+    pub fn to_bytes(&self) -> [u8; OBJECT_CHANGE_BYTES] {
+        let mut buf = [0u8; OBJECT_CHANGE_BYTES];
+        buf[0] = self.kind.into();
+        let mut off = CHANGE_KIND_BYTES;
+        buf[off..off + OBJECT_ID_BYTES].copy_from_slice(self.object_id.as_ref());
+        off += OBJECT_ID_BYTES;
+        for entry in [self.previous, self.current] {
+            if let Some((version, digest)) = entry {
+                BigEndian::write_u64(&mut buf[off..off + SEQUENCE_NUM_BYTES], version.value());
+                buf[off + SEQUENCE_NUM_BYTES..off + SEQUENCE_NUM_BYTES + OBJECT_DIGEST_BYTES]
+                    .copy_from_slice(digest.as_ref());
+            }
+            off += SEQUENCE_NUM_BYTES + OBJECT_DIGEST_BYTES;
+        }
+        buf
+    }
+    pub fn from_bytes(buf: &[u8; OBJECT_CHANGE_BYTES]) -> Result<Self> {
+        let kind = ObjectChangeKind::try_from(buf[0])?;
+        let mut off = CHANGE_KIND_BYTES;
+        let object_id = ObjectID::from_bytes(&buf[off..off + OBJECT_ID_BYTES])?;
+        off += OBJECT_ID_BYTES;
+        let mut entries = [None, None];
+        for entry in entries.iter_mut() {
+            let version = BigEndian::read_u64(&buf[off..off + SEQUENCE_NUM_BYTES]);
+            let digest = ObjectDigest::try_from(
+                &buf[off + SEQUENCE_NUM_BYTES..off + SEQUENCE_NUM_BYTES + OBJECT_DIGEST_BYTES],
+            )?;
+            *entry = Some((SequenceNumber::from_u64(version), digest));
+            off += SEQUENCE_NUM_BYTES + OBJECT_DIGEST_BYTES;
+        }
+        let [previous, current] = entries;
+        let (previous, current) = match kind {
+            ObjectChangeKind::Created => (None, current),
+            ObjectChangeKind::Mutated => (previous, current),
+            ObjectChangeKind::Deleted => (previous, None),
+        };
+        Ok(Self {
+            kind,
+            object_id,
+            previous,
+            current,
+        })
+    }
+}
+
+/// Rolls `acc` forward by one change, removing the entry for the object as of the base epoch and
+/// inserting the one for the current epoch. Entries mirror `StateAccumulator::accumulate_live_object`.
+pub fn accumulate_object_change(acc: &mut Accumulator, change: &ObjectChange) {
+    if let Some(previous) = change.previous_ref() {
+        acc.remove(accumulator_entry(&previous));
+    }
+    if let Some(current) = change.current_ref() {
+        acc.insert(accumulator_entry(&current));
+    }
+}
+
+fn accumulator_entry(object_ref: &ObjectRef) -> Vec<u8> {
+    if object_ref.2 == ObjectDigest::OBJECT_DIGEST_WRAPPED {
+        bcs::to_bytes(&WrappedObject::new(object_ref.0, object_ref.1))
+            .expect("Failed to serialize WrappedObject")
+    } else {
+        object_ref.2.inner().to_vec()
+    }
+}
+
 pub fn create_file_metadata(
     file_path: &std::path::Path,
     file_compression: FileCompression,
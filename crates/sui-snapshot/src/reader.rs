@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::filter::{write_filtered_live_objects, FilteredObjectWriter, LiveObjectFilter};
 use crate::{
     FileMetadata, FileType, Manifest, MAGIC_BYTES, MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC,
     OBJECT_ID_BYTES, OBJECT_REF_BYTES, REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES, SHA3_BYTES,
@@ -15,6 +16,7 @@ use futures::{StreamExt, TryStreamExt};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use integer_encoding::VarIntReader;
 use object_store::path::Path;
+use serde::de::DeserializeOwned;
 use std::collections::BTreeMap;
 use std::fs;
 use std::fs::File;
@@ -122,6 +124,12 @@ impl StateSnapshotReaderV1 {
                         .or_insert_with(BTreeMap::new);
                     entry.insert(file_metadata.part_num, file_metadata.clone());
                 }
+                FileType::Change => {
+                    return Err(anyhow!(
+                        "Unexpected change file in state snapshot for epoch: {}",
+                        epoch
+                    ));
+                }
             }
         }
         let epoch_dir_path = Path::from(epoch_dir);
@@ -181,14 +189,48 @@ impl StateSnapshotReaderV1 {
         let (sha3_digests, num_part_files) = self.compute_checksum().await?;
         let accum_handle =
             sender.map(|sender| self.spawn_accumulation_tasks(sender, num_part_files));
-        self.sync_live_objects(perpetual_db, abort_registration, sha3_digests)
-            .await?;
+        let threshold = self.indirect_objects_threshold;
+        self.sync_live_objects(abort_registration, sha3_digests, |obj_iter, sha3_digest| {
+            AuthorityStore::bulk_insert_live_objects(
+                perpetual_db,
+                obj_iter,
+                threshold,
+                sha3_digest,
+            )
+            .expect("Failed to insert live objects");
+            Ok(())
+        })
+        .await?;
         if let Some(handle) = accum_handle {
             handle.await?;
         }
         Ok(())
     }
 
+    /// Like `read`, but instead of materializing every live object into the authority store, only
+    /// the objects accepted by `filter` are handed to `writer`. Every *.obj file is still checked
+    /// against its REFERENCE file and accumulation is done over the full set of object references,
+    /// so the root state hash can be verified exactly as for a full restore.
+    pub async fn read_filtered(
+        &mut self,
+        filter: &LiveObjectFilter,
+        writer: &dyn FilteredObjectWriter,
+        abort_registration: AbortRegistration,
+        sender: Option<tokio::sync::mpsc::Sender<(Accumulator, u64)>>,
+    ) -> Result<()> {
+        let (sha3_digests, num_part_files) = self.compute_checksum().await?;
+        let accum_handle =
+            sender.map(|sender| self.spawn_accumulation_tasks(sender, num_part_files));
+        self.sync_live_objects(abort_registration, sha3_digests, |obj_iter, sha3_digest| {
+            write_filtered_live_objects(obj_iter, filter, writer, sha3_digest)
+        })
+        .await?;
+        if let Some(handle) = accum_handle {
+            handle.await?;
+        }
+        writer.finish()
+    }
+
     pub async fn compute_checksum(
         &mut self,
     ) -> Result<(Arc<Mutex<BTreeMap<u32, BTreeMap<u32, [u8; 32]>>>>, usize), anyhow::Error> {
@@ -332,15 +374,17 @@ impl StateSnapshotReaderV1 {
         })
     }
 
-    async fn sync_live_objects(
+    async fn sync_live_objects<F>(
         &self,
-        perpetual_db: &AuthorityPerpetualTables,
         abort_registration: AbortRegistration,
         sha3_digests: Arc<Mutex<DigestByBucketAndPartition>>,
-    ) -> Result<(), anyhow::Error> {
+        insert_live_objects: F,
+    ) -> Result<(), anyhow::Error>
+    where
+        F: Fn(LiveObjectIter, &[u8; 32]) -> Result<()>,
+    {
         let epoch_dir = self.epoch_dir();
         let concurrency = self.concurrency;
-        let threshold = self.indirect_objects_threshold;
         let remote_object_store = self.remote_object_store.clone();
         let input_files: Vec<_> = self
             .object_files
@@ -396,15 +440,8 @@ impl StateSnapshotReaderV1 {
                     .try_for_each(|(bytes, file_metadata, sha3_digest)| {
                         let bytes_len = bytes.len();
                         let result: Result<(), anyhow::Error> =
-                            LiveObjectIter::new(&file_metadata, bytes).map(|obj_iter| {
-                                AuthorityStore::bulk_insert_live_objects(
-                                    perpetual_db,
-                                    obj_iter,
-                                    threshold,
-                                    &sha3_digest,
-                                )
-                                .expect("Failed to insert live objects");
-                            });
+                            LiveObjectIter::new(&file_metadata, bytes)
+                                .and_then(|obj_iter| insert_live_objects(obj_iter, &sha3_digest));
                         downloaded_bytes.fetch_add(bytes_len, Ordering::Relaxed);
                         obj_progress_bar_clone.inc(1);
                         obj_progress_bar_clone.set_message(format!(
@@ -478,34 +515,7 @@ impl StateSnapshotReaderV1 {
     }
 
     fn read_manifest(path: PathBuf) -> anyhow::Result<Manifest> {
-        let manifest_file = File::open(path)?;
-        let manifest_file_size = manifest_file.metadata()?.len() as usize;
-        let mut manifest_reader = BufReader::new(manifest_file);
-        manifest_reader.rewind()?;
-        let magic = manifest_reader.read_u32::<BigEndian>()?;
-        if magic != MANIFEST_FILE_MAGIC {
-            return Err(anyhow!("Unexpected magic byte: {}", magic));
-        }
-        manifest_reader.seek(SeekFrom::End(-(SHA3_BYTES as i64)))?;
-        let mut sha3_digest = [0u8; SHA3_BYTES];
-        manifest_reader.read_exact(&mut sha3_digest)?;
-        manifest_reader.rewind()?;
-        let mut content_buf = vec![0u8; manifest_file_size - SHA3_BYTES];
-        manifest_reader.read_exact(&mut content_buf)?;
-        let mut hasher = Sha3_256::default();
-        hasher.update(&content_buf);
-        let computed_digest = hasher.finalize().digest;
-        if computed_digest != sha3_digest {
-            return Err(anyhow!(
-                "Checksum: {:?} don't match: {:?}",
-                computed_digest,
-                sha3_digest
-            ));
-        }
-        manifest_reader.rewind()?;
-        manifest_reader.seek(SeekFrom::Start(MAGIC_BYTES as u64))?;
-        let manifest = bcs::from_bytes(&content_buf[MAGIC_BYTES..])?;
-        Ok(manifest)
+        read_manifest_file(path, MANIFEST_FILE_MAGIC)
     }
 
     pub fn get_multi_progress(&self) -> MultiProgress {
@@ -513,6 +523,40 @@ impl StateSnapshotReaderV1 {
     }
 }
 
+pub(crate) fn read_manifest_file<T: DeserializeOwned>(
+    path: PathBuf,
+    expected_magic: u32,
+) -> anyhow::Result<T> {
+    let manifest_file = File::open(path)?;
+    let manifest_file_size = manifest_file.metadata()?.len() as usize;
+    let mut manifest_reader = BufReader::new(manifest_file);
+    manifest_reader.rewind()?;
+    let magic = manifest_reader.read_u32::<BigEndian>()?;
+    if magic != expected_magic {
+        return Err(anyhow!("Unexpected magic byte: {}", magic));
+    }
+    manifest_reader.seek(SeekFrom::End(-(SHA3_BYTES as i64)))?;
+    let mut sha3_digest = [0u8; SHA3_BYTES];
+    manifest_reader.read_exact(&mut sha3_digest)?;
+    manifest_reader.rewind()?;
+    let mut content_buf = vec![0u8; manifest_file_size - SHA3_BYTES];
+    manifest_reader.read_exact(&mut content_buf)?;
+    let mut hasher = Sha3_256::default();
+    hasher.update(&content_buf);
+    let computed_digest = hasher.finalize().digest;
+    if computed_digest != sha3_digest {
+        return Err(anyhow!(
+            "Checksum: {:?} don't match: {:?}",
+            computed_digest,
+            sha3_digest
+        ));
+    }
+    manifest_reader.rewind()?;
+    manifest_reader.seek(SeekFrom::Start(MAGIC_BYTES as u64))?;
+    let manifest = bcs::from_bytes(&content_buf[MAGIC_BYTES..])?;
+    Ok(manifest)
+}
+
 pub async fn download_bytes(
     remote_object_store: Arc<dyn ObjectStoreGetExt>,
     file_metadata: &FileMetadata,
@@ -1,13 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::delta_reader::{ObjectChangeIter, StateSnapshotDeltaReaderV1};
+use crate::delta_writer::StateSnapshotDeltaWriterV1;
+use crate::filter::{LiveObjectFilter, RocksDbFilteredObjectWriter};
 use crate::reader::StateSnapshotReaderV1;
 use crate::writer::StateSnapshotWriterV1;
-use crate::FileCompression;
+use crate::{
+    FileCompression, FileMetadata, FileType, ObjectChange, CHANGE_FILE_MAGIC, OBJECT_CHANGE_BYTES,
+};
 use fastcrypto::hash::MultisetHash;
 use futures::future::AbortHandle;
 use indicatif::MultiProgress;
-use std::collections::HashSet;
+use object_store::path::Path;
+use std::collections::{BTreeMap, HashSet};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
@@ -15,10 +21,11 @@ use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::state_accumulator::StateAccumulator;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::accumulator::Accumulator;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
 use sui_types::object::Object;
 use tempfile::tempdir;
+use typed_store::Map;
 
 fn temp_dir() -> std::path::PathBuf {
     tempdir()
@@ -181,3 +188,301 @@ async fn test_snapshot_empty_db() -> Result<(), anyhow::Error> {
     )?;
     Ok(())
 }
+
+/// Writes a full snapshot of `base_db` for epoch 0 and a delta from `base_db` to `db` for epoch 1,
+/// then restores the full snapshot and applies the delta to it with `root_state_hash` as the
+/// expected root state hash of epoch 1. Returns the restored db along with the result of the delta.
+async fn write_and_restore_delta(
+    base_db: Arc<AuthorityPerpetualTables>,
+    db: Arc<AuthorityPerpetualTables>,
+    root_state_hash: ECMHLiveObjectSetDigest,
+) -> Result<(AuthorityPerpetualTables, Result<Accumulator, anyhow::Error>), anyhow::Error> {
+    let restored_db_path = temp_dir();
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("local_dir")),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("remote_dir")),
+        ..Default::default()
+    };
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("local_dir_restore")),
+        ..Default::default()
+    };
+
+    let base_accumulator = accumulate_live_object_set(&base_db, true);
+    StateSnapshotWriterV1::new(
+        &local_store_config,
+        &remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?
+    .write_internal(
+        0,
+        true,
+        base_db.clone(),
+        ECMHLiveObjectSetDigest::from(base_accumulator.digest()),
+    )
+    .await?;
+    StateSnapshotDeltaWriterV1::new(
+        &local_store_config,
+        &remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?
+    .write_internal(
+        1,
+        true,
+        base_db,
+        db.clone(),
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&db, true).digest()),
+    )
+    .await?;
+
+    let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None);
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    StateSnapshotReaderV1::new(
+        0,
+        &remote_store_config,
+        &local_store_restore_config,
+        usize::MAX,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+    )
+    .await?
+    .read(&restored_perpetual_db, abort_registration, None)
+    .await?;
+
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    let mut delta_reader = StateSnapshotDeltaReaderV1::new(
+        0,
+        1,
+        &remote_store_config,
+        &local_store_restore_config,
+        usize::MAX,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+    )
+    .await?;
+    let result = delta_reader
+        .read(
+            &restored_perpetual_db,
+            base_accumulator,
+            &BTreeMap::from([(1, root_state_hash)]),
+            abort_registration,
+        )
+        .await;
+    Ok((restored_perpetual_db, result))
+}
+
+/// Populates `base_db` with 1000 objects, and `db` with the same set after 200 of them were
+/// mutated, 400 deleted and 200 new ones created
+fn insert_delta_keys(
+    base_db: &AuthorityPerpetualTables,
+    db: &AuthorityPerpetualTables,
+) -> Result<(), anyhow::Error> {
+    let ids = ObjectID::in_range(ObjectID::ZERO, 1200)?;
+    for id in &ids[..1000] {
+        base_db.insert_object_test_only(Object::immutable_with_id_for_testing(*id))?;
+    }
+    for id in &ids[..400] {
+        db.insert_object_test_only(Object::immutable_with_id_for_testing(*id))?;
+    }
+    for id in &ids[400..600] {
+        db.insert_object_test_only(Object::with_id_owner_version_for_testing(
+            *id,
+            SequenceNumber::from_u64(10),
+            SuiAddress::ZERO,
+        ))?;
+    }
+    for id in &ids[1000..] {
+        db.insert_object_test_only(Object::immutable_with_id_for_testing(*id))?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delta_snapshot_basic() -> Result<(), anyhow::Error> {
+    let base_perpetual_db = Arc::new(AuthorityPerpetualTables::open(&temp_dir(), None));
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&temp_dir(), None));
+    insert_delta_keys(&base_perpetual_db, &perpetual_db)?;
+    let root_state_hash =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&perpetual_db, true).digest());
+    let (restored_perpetual_db, result) =
+        write_and_restore_delta(base_perpetual_db, perpetual_db.clone(), root_state_hash).await?;
+    assert_eq!(
+        ECMHLiveObjectSetDigest::from(result?.digest()),
+        root_state_hash
+    );
+    compare_live_objects(&perpetual_db, &restored_perpetual_db, true)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delta_snapshot_root_state_hash_mismatch() -> Result<(), anyhow::Error> {
+    let base_perpetual_db = Arc::new(AuthorityPerpetualTables::open(&temp_dir(), None));
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&temp_dir(), None));
+    insert_delta_keys(&base_perpetual_db, &perpetual_db)?;
+    let wrong_root_state_hash = ECMHLiveObjectSetDigest::from(
+        accumulate_live_object_set(&base_perpetual_db, true).digest(),
+    );
+    let (restored_perpetual_db, result) = write_and_restore_delta(
+        base_perpetual_db.clone(),
+        perpetual_db,
+        wrong_root_state_hash,
+    )
+    .await?;
+    assert!(result.is_err());
+    // Nothing is written to the db unless the whole chain of deltas verifies
+    compare_live_objects(&base_perpetual_db, &restored_perpetual_db, true)?;
+    Ok(())
+}
+
+#[test]
+fn test_object_change_iter_reports_truncated_file() -> Result<(), anyhow::Error> {
+    let root_path = temp_dir();
+    let dir_path = Path::from("delta");
+    let file_metadata = FileMetadata {
+        file_type: FileType::Change,
+        bucket_num: 1,
+        part_num: 1,
+        file_compression: FileCompression::None,
+        sha3_digest: [0; 32],
+    };
+    let file_path = file_metadata.local_file_path(&root_path, &dir_path)?;
+    std::fs::create_dir_all(file_path.parent().unwrap())?;
+    let changes: Vec<_> = ObjectID::in_range(ObjectID::ZERO, 2)?
+        .into_iter()
+        .map(|id| ObjectChange::created((id, SequenceNumber::from_u64(1), ObjectDigest::MIN)))
+        .collect();
+    let mut bytes = CHANGE_FILE_MAGIC.to_be_bytes().to_vec();
+    for change in &changes {
+        bytes.extend_from_slice(&change.to_bytes());
+    }
+
+    // A file that ends on a record boundary yields every change
+    std::fs::write(&file_path, &bytes)?;
+    let read = ObjectChangeIter::new(&file_metadata, root_path.clone(), dir_path.clone())?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(read, changes);
+
+    // A trailing partial record is an error rather than the end of the file
+    bytes.extend_from_slice(&changes[0].to_bytes()[..OBJECT_CHANGE_BYTES / 2]);
+    std::fs::write(&file_path, &bytes)?;
+    let read: Vec<_> = ObjectChangeIter::new(&file_metadata, root_path, dir_path)?.collect();
+    assert_eq!(read.len(), 3);
+    assert!(read[..2].iter().all(|change| change.is_ok()));
+    assert!(read[2].is_err());
+    Ok(())
+}
+
+#[test]
+fn test_filter_matches_immediate_owner_only() -> Result<(), anyhow::Error> {
+    let owner = SuiAddress::random_for_testing_only();
+    let ids = ObjectID::in_range(ObjectID::ZERO, 2)?;
+    let parent = Object::with_id_owner_for_testing(ids[0], owner);
+    let child = Object::with_object_owner_for_testing(ids[1], ids[0]);
+
+    let filter = LiveObjectFilter {
+        owners: vec![owner],
+        ..Default::default()
+    };
+    assert!(filter.matches(&parent));
+    assert!(!filter.matches(&child));
+
+    let filter = LiveObjectFilter {
+        owners: vec![ids[0].into()],
+        ..Default::default()
+    };
+    assert!(filter.matches(&child));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_filtered() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let filtered_db_path = temp_dir();
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("local_dir")),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("remote_dir")),
+        ..Default::default()
+    };
+    let snapshot_writer = StateSnapshotWriterV1::new(
+        &local_store_config,
+        &remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None));
+    insert_keys(&perpetual_db, 1000)?;
+    let owner = SuiAddress::random_for_testing_only();
+    let owned_ids: Vec<_> = (0..10).map(|_| ObjectID::random()).collect();
+    for id in &owned_ids {
+        perpetual_db.insert_object_test_only(Object::with_id_owner_for_testing(*id, owner))?;
+    }
+    let root_accumulator = accumulate_live_object_set(&perpetual_db, true);
+    snapshot_writer
+        .write_internal(
+            0,
+            true,
+            perpetual_db.clone(),
+            ECMHLiveObjectSetDigest::from(root_accumulator.digest()),
+        )
+        .await?;
+
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("local_dir_restore")),
+        ..Default::default()
+    };
+    let mut snapshot_reader = StateSnapshotReaderV1::new(
+        0,
+        &remote_store_config,
+        &local_store_restore_config,
+        usize::MAX,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+    )
+    .await?;
+    let filter = LiveObjectFilter {
+        owners: vec![owner],
+        ..Default::default()
+    };
+    let writer = RocksDbFilteredObjectWriter::new(&filtered_db_path);
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(10);
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    snapshot_reader
+        .read_filtered(&filter, &writer, abort_registration, Some(sender))
+        .await?;
+
+    // The accumulator still covers every live object, not just the filtered ones
+    let mut acc = Accumulator::default();
+    let mut num_objects = 0;
+    while let Some((partial_acc, num)) = receiver.recv().await {
+        acc.union(&partial_acc);
+        num_objects += num;
+    }
+    assert_eq!(num_objects, 1010);
+    assert_eq!(acc.digest(), root_accumulator.digest());
+
+    let restored: HashSet<_> = writer
+        .tables()
+        .objects
+        .unbounded_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(restored, owned_ids.into_iter().collect::<HashSet<_>>());
+    Ok(())
+}
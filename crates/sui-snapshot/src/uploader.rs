@@ -1,14 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::delta_writer::StateSnapshotDeltaWriterV1;
 use crate::writer::StateSnapshotWriterV1;
 use anyhow::Result;
 use bytes::Bytes;
+use object_store::path::Path;
 use object_store::DynObjectStore;
 use prometheus::{
     register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
     Registry,
 };
+use std::collections::BTreeMap;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -22,8 +25,8 @@ use sui_storage::object_store::util::{
     run_manifest_update_loop,
 };
 use sui_storage::FileCompression;
-use sui_types::messages_checkpoint::CheckpointCommitment::ECMHLiveObjectSetDigest;
-use tracing::{debug, error, info};
+use sui_types::messages_checkpoint::{CheckpointCommitment, ECMHLiveObjectSetDigest};
+use tracing::{debug, error, info, warn};
 
 pub struct StateSnapshotUploaderMetrics {
     pub first_missing_state_snapshot_epoch: IntGauge,
@@ -65,6 +68,9 @@ pub struct StateSnapshotUploader {
     snapshot_store: Arc<DynObjectStore>,
     /// Time interval to check for presence of new db checkpoint
     interval: Duration,
+    /// Whether to also upload a delta snapshot from the previous epoch with every full snapshot.
+    /// The db checkpoint of an epoch is then kept until the delta of the next epoch is written.
+    write_delta_snapshots: bool,
     metrics: Arc<StateSnapshotUploaderMetrics>,
 }
 
@@ -76,6 +82,7 @@ impl StateSnapshotUploader {
         interval_s: u64,
         registry: &Registry,
         checkpoint_store: Arc<CheckpointStore>,
+        write_delta_snapshots: bool,
     ) -> Result<Arc<Self>> {
         let db_checkpoint_store_config = ObjectStoreConfig {
             object_store: Some(ObjectStoreType::File),
@@ -95,6 +102,7 @@ impl StateSnapshotUploader {
             staging_store: staging_store_config.make()?,
             snapshot_store: snapshot_store_config.make()?,
             interval: Duration::from_secs(interval_s),
+            write_delta_snapshots,
             metrics: StateSnapshotUploaderMetrics::new(registry),
         }))
     }
@@ -130,49 +138,106 @@ impl StateSnapshotUploader {
                     &path_to_filesystem(self.db_checkpoint_path.clone(), &db_path.child("store"))?,
                     None,
                 ));
-                let commitments = self
-                    .checkpoint_store
-                    .get_epoch_state_commitments(*epoch)
-                    .expect("Expected last checkpoint of epoch to have end of epoch data")
-                    .expect("Expected end of epoch data to be present");
-                let ECMHLiveObjectSetDigest(state_hash_commitment) = commitments
-                    .last()
-                    .expect("Expected at least one commitment")
-                    .clone();
+                let state_hash_commitment = self.state_hash_commitment(*epoch);
                 state_snapshot_writer
-                    .write(*epoch, db, state_hash_commitment)
+                    .write(*epoch, db.clone(), state_hash_commitment.clone())
                     .await?;
                 info!("State snapshot creation successful for epoch: {}", *epoch);
+                if self.write_delta_snapshots {
+                    self.upload_delta_snapshot(
+                        *epoch,
+                        db,
+                        state_hash_commitment,
+                        &local_checkpoints_by_epoch,
+                    )
+                    .await?;
+                }
                 // Drop marker in the output directory that upload completed successfully
                 let bytes = Bytes::from_static(b"success");
                 let success_marker = db_path.child(SUCCESS_MARKER);
                 put(&self.snapshot_store, &success_marker, bytes.clone()).await?;
-                let bytes = Bytes::from_static(b"success");
-                let state_snapshot_completed_marker =
-                    db_path.child(STATE_SNAPSHOT_COMPLETED_MARKER);
-                put(
-                    &self.db_checkpoint_store.clone(),
-                    &state_snapshot_completed_marker,
-                    bytes.clone(),
-                )
-                .await?;
+                // With delta snapshots, the db checkpoint is released once the delta of the next
+                // epoch has been written on top of it
+                if !self.write_delta_snapshots {
+                    self.mark_state_snapshot_completed(db_path).await?;
+                }
                 info!("State snapshot completed for epoch: {epoch}");
+            } else if self.write_delta_snapshots
+                && (missing_epochs.contains(&(*epoch + 1))
+                    || !local_checkpoints_by_epoch.contains_key(&(*epoch + 1)))
+            {
+                // The delta of the next epoch hasn't been written yet
+                info!("State snapshot skipped for epoch: {epoch}, kept for the next delta");
             } else {
-                let bytes = Bytes::from_static(b"success");
-                let state_snapshot_completed_marker =
-                    db_path.child(STATE_SNAPSHOT_COMPLETED_MARKER);
-                put(
-                    &self.db_checkpoint_store.clone(),
-                    &state_snapshot_completed_marker,
-                    bytes.clone(),
-                )
-                .await?;
+                self.mark_state_snapshot_completed(db_path).await?;
                 info!("State snapshot skipped for epoch: {epoch}");
             }
         }
         Ok(())
     }
 
+    /// Writes the delta snapshot from the end of `epoch - 1` to the end of `epoch`, if the db
+    /// checkpoint of the previous epoch is still around, and releases that db checkpoint.
+    async fn upload_delta_snapshot(
+        &self,
+        epoch: u64,
+        db: Arc<AuthorityPerpetualTables>,
+        state_hash_commitment: ECMHLiveObjectSetDigest,
+        local_checkpoints_by_epoch: &BTreeMap<u64, Path>,
+    ) -> Result<()> {
+        let Some(base_db_path) = epoch
+            .checked_sub(1)
+            .and_then(|base_epoch| local_checkpoints_by_epoch.get(&base_epoch))
+        else {
+            warn!("No db checkpoint of the previous epoch, skipping delta snapshot for epoch: {epoch}");
+            return Ok(());
+        };
+        let base_db = Arc::new(AuthorityPerpetualTables::open(
+            &path_to_filesystem(
+                self.db_checkpoint_path.clone(),
+                &base_db_path.child("store"),
+            )?,
+            None,
+        ));
+        StateSnapshotDeltaWriterV1::new_from_store(
+            &self.staging_path,
+            &self.staging_store,
+            &self.snapshot_store,
+            FileCompression::Zstd,
+            NonZeroUsize::new(20).unwrap(),
+        )
+        .await?
+        .write(epoch, base_db, db, state_hash_commitment)
+        .await?;
+        info!("Delta state snapshot creation successful for epoch: {epoch}");
+        self.mark_state_snapshot_completed(base_db_path).await
+    }
+
+    fn state_hash_commitment(&self, epoch: u64) -> ECMHLiveObjectSetDigest {
+        let commitments = self
+            .checkpoint_store
+            .get_epoch_state_commitments(epoch)
+            .expect("Expected last checkpoint of epoch to have end of epoch data")
+            .expect("Expected end of epoch data to be present");
+        let CheckpointCommitment::ECMHLiveObjectSetDigest(state_hash_commitment) = commitments
+            .last()
+            .expect("Expected at least one commitment")
+            .clone();
+        state_hash_commitment
+    }
+
+    /// Drops the marker that lets the db checkpoint handler garbage collect the db checkpoint.
+    async fn mark_state_snapshot_completed(&self, db_path: &Path) -> Result<()> {
+        let bytes = Bytes::from_static(b"success");
+        let state_snapshot_completed_marker = db_path.child(STATE_SNAPSHOT_COMPLETED_MARKER);
+        put(
+            &self.db_checkpoint_store.clone(),
+            &state_snapshot_completed_marker,
+            bytes,
+        )
+        .await
+    }
+
     async fn run_upload_loop(
         self: Arc<Self>,
         mut recv: tokio::sync::broadcast::Receiver<()>,
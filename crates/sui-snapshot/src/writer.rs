@@ -14,6 +14,7 @@ use futures::StreamExt;
 use integer_encoding::VarInt;
 use object_store::path::Path;
 use object_store::DynObjectStore;
+use serde::Serialize;
 use std::collections::hash_map::Entry::Vacant;
 use std::collections::HashMap;
 use std::fs;
@@ -41,7 +42,7 @@ use tokio_stream::wrappers::ReceiverStream;
 use tracing::debug;
 
 /// LiveObjectSetWriterV1 writes live object set. It creates multiple *.obj files and *.ref file
-struct LiveObjectSetWriterV1 {
+pub(crate) struct LiveObjectSetWriterV1 {
     dir_path: PathBuf,
     bucket_num: u32,
     current_part_num: u32,
@@ -54,7 +55,7 @@ struct LiveObjectSetWriterV1 {
 }
 
 impl LiveObjectSetWriterV1 {
-    fn new(
+    pub(crate) fn new(
         dir_path: PathBuf,
         bucket_num: u32,
         file_compression: FileCompression,
@@ -317,7 +318,7 @@ impl StateSnapshotWriterV1 {
             &epoch
         ))?;
 
-        Self::sync_file_to_remote(
+        sync_file_to_remote(
             local_staging_dir,
             manifest_file_path,
             local_object_store,
@@ -332,38 +333,14 @@ impl StateSnapshotWriterV1 {
         epoch: u64,
         receiver: Receiver<FileMetadata>,
     ) -> Result<JoinHandle<Result<Vec<()>, anyhow::Error>>> {
-        let remote_object_store = self.remote_object_store.clone();
-        let local_staging_store = self.local_staging_store.clone();
-        let local_dir_path = self.local_staging_dir.clone();
-        let epoch_dir = self.epoch_dir(epoch);
-        let upload_concurrency = self.concurrency;
-        let join_handle = tokio::spawn(async move {
-            let results: Vec<Result<(), anyhow::Error>> = ReceiverStream::new(receiver)
-                .map(|file_metadata| {
-                    let file_path = file_metadata.file_path(&epoch_dir);
-                    let remote_object_store = remote_object_store.clone();
-                    let local_object_store = local_staging_store.clone();
-                    let local_dir_path = local_dir_path.clone();
-                    async move {
-                        Self::sync_file_to_remote(
-                            local_dir_path.clone(),
-                            file_path.clone(),
-                            local_object_store.clone(),
-                            remote_object_store.clone(),
-                        )
-                        .await?;
-                        Ok(())
-                    }
-                })
-                .boxed()
-                .buffer_unordered(upload_concurrency)
-                .collect()
-                .await;
-            results
-                .into_iter()
-                .collect::<Result<Vec<()>, anyhow::Error>>()
-        });
-        Ok(join_handle)
+        start_upload(
+            self.epoch_dir(epoch),
+            receiver,
+            self.local_staging_dir.clone(),
+            self.local_staging_store.clone(),
+            self.remote_object_store.clone(),
+            self.concurrency,
+        )
     }
 
     fn write_live_object_set<F>(
@@ -412,51 +389,20 @@ impl StateSnapshotWriterV1 {
     }
 
     fn write_manifest(&mut self, epoch: u64, file_metadata: Vec<FileMetadata>) -> Result<()> {
-        let (f, manifest_file_path) = self.manifest_file(epoch)?;
-        let mut wbuf = BufWriter::new(f);
+        let manifest_file_path = path_to_filesystem(
+            self.local_staging_dir.clone(),
+            &self.epoch_dir(epoch).child("MANIFEST"),
+        )?;
         let manifest: Manifest = Manifest::V1(ManifestV1 {
             snapshot_version: 1,
             address_length: ObjectID::LENGTH as u64,
             file_metadata,
             epoch,
         });
-        let serialized_manifest = bcs::to_bytes(&manifest)?;
-        wbuf.write_all(&serialized_manifest)?;
-        wbuf.flush()?;
-        wbuf.get_ref().sync_data()?;
-        let sha3_digest = compute_sha3_checksum(&manifest_file_path)?;
-        wbuf.write_all(&sha3_digest)?;
-        wbuf.flush()?;
-        wbuf.get_ref().sync_data()?;
-        let off = wbuf.get_ref().stream_position()?;
-        wbuf.get_ref().set_len(off)?;
-        Ok(())
-    }
-
-    fn manifest_file(&mut self, epoch: u64) -> Result<(File, PathBuf)> {
-        let manifest_file_path = path_to_filesystem(
-            self.local_staging_dir.clone(),
-            &self.epoch_dir(epoch).child("MANIFEST"),
-        )?;
-        let manifest_file_tmp_path = path_to_filesystem(
-            self.local_staging_dir.clone(),
-            &self.epoch_dir(epoch).child("MANIFEST.tmp"),
-        )?;
-        let mut f = File::create(manifest_file_tmp_path.clone())?;
-        let mut metab = vec![0u8; MAGIC_BYTES];
-        BigEndian::write_u32(&mut metab, MANIFEST_FILE_MAGIC);
-        f.rewind()?;
-        f.write_all(&metab)?;
-        drop(f);
-        fs::rename(manifest_file_tmp_path, manifest_file_path.clone())?;
-        let mut f = OpenOptions::new()
-            .append(true)
-            .open(manifest_file_path.clone())?;
-        f.seek(SeekFrom::Start(MAGIC_BYTES as u64))?;
-        Ok((f, manifest_file_path))
+        write_manifest_file(manifest_file_path, MANIFEST_FILE_MAGIC, &manifest)
     }
 
-    fn bucket_func(_object: &LiveObject) -> u32 {
+    pub(crate) fn bucket_func(_object: &LiveObject) -> u32 {
         // TODO: Use the hash bucketing function used for accumulator tree if there is one
         1u32
     }
@@ -482,16 +428,86 @@ impl StateSnapshotWriterV1 {
         fs::create_dir_all(&local_epoch_dir_path)?;
         Ok(())
     }
+}
 
-    async fn sync_file_to_remote(
-        local_path: PathBuf,
-        path: Path,
-        from: Arc<DynObjectStore>,
-        to: Arc<DynObjectStore>,
-    ) -> Result<()> {
-        debug!("Syncing snapshot file to remote: {:?}", path);
-        copy_file(&path, &path, &from, &to).await?;
-        fs::remove_file(path_to_filesystem(local_path, &path)?)?;
-        Ok(())
-    }
+/// Uploads every file sent on `receiver` from the local staging store to the remote store as soon
+/// as it has been finalized
+pub(crate) fn start_upload(
+    dir_path: Path,
+    receiver: Receiver<FileMetadata>,
+    local_dir_path: PathBuf,
+    local_staging_store: Arc<DynObjectStore>,
+    remote_object_store: Arc<DynObjectStore>,
+    upload_concurrency: usize,
+) -> Result<JoinHandle<Result<Vec<()>, anyhow::Error>>> {
+    let join_handle = tokio::spawn(async move {
+        let results: Vec<Result<(), anyhow::Error>> = ReceiverStream::new(receiver)
+            .map(|file_metadata| {
+                let file_path = file_metadata.file_path(&dir_path);
+                let remote_object_store = remote_object_store.clone();
+                let local_object_store = local_staging_store.clone();
+                let local_dir_path = local_dir_path.clone();
+                async move {
+                    sync_file_to_remote(
+                        local_dir_path.clone(),
+                        file_path.clone(),
+                        local_object_store.clone(),
+                        remote_object_store.clone(),
+                    )
+                    .await?;
+                    Ok(())
+                }
+            })
+            .boxed()
+            .buffer_unordered(upload_concurrency)
+            .collect()
+            .await;
+        results
+            .into_iter()
+            .collect::<Result<Vec<()>, anyhow::Error>>()
+    });
+    Ok(join_handle)
+}
+
+pub(crate) async fn sync_file_to_remote(
+    local_path: PathBuf,
+    path: Path,
+    from: Arc<DynObjectStore>,
+    to: Arc<DynObjectStore>,
+) -> Result<()> {
+    debug!("Syncing snapshot file to remote: {:?}", path);
+    copy_file(&path, &path, &from, &to).await?;
+    fs::remove_file(path_to_filesystem(local_path, &path)?)?;
+    Ok(())
+}
+
+/// Writes a manifest file at `file_path`: `magic`, followed by the bcs serialized manifest and the
+/// sha3 checksum of everything before it
+pub(crate) fn write_manifest_file<T: Serialize>(
+    file_path: PathBuf,
+    magic: u32,
+    manifest: &T,
+) -> Result<()> {
+    let file_tmp_path = file_path.with_extension("tmp");
+    let mut f = File::create(file_tmp_path.clone())?;
+    let mut metab = vec![0u8; MAGIC_BYTES];
+    BigEndian::write_u32(&mut metab, magic);
+    f.rewind()?;
+    f.write_all(&metab)?;
+    drop(f);
+    fs::rename(file_tmp_path, file_path.clone())?;
+    let mut f = OpenOptions::new().append(true).open(file_path.clone())?;
+    f.seek(SeekFrom::Start(MAGIC_BYTES as u64))?;
+    let mut wbuf = BufWriter::new(f);
+    let serialized_manifest = bcs::to_bytes(manifest)?;
+    wbuf.write_all(&serialized_manifest)?;
+    wbuf.flush()?;
+    wbuf.get_ref().sync_data()?;
+    let sha3_digest = compute_sha3_checksum(&file_path)?;
+    wbuf.write_all(&sha3_digest)?;
+    wbuf.flush()?;
+    wbuf.get_ref().sync_data()?;
+    let off = wbuf.get_ref().stream_position()?;
+    wbuf.get_ref().set_len(off)?;
+    Ok(())
 }
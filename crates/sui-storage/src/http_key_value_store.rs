@@ -180,6 +180,11 @@ pub fn path_elements_to_key(digest: &str, type_: &str) -> anyhow::Result<Key> {
             let object_key: ObjectKey = bcs::from_bytes(&decoded_digest)?;
             Ok(Key::ObjectKey(object_key.0, object_key.1))
         }
+        // Requested by `multi_get_events_by_tx_digests`, which has no events digest to key on.
+        // Servers answer with a bcs `Option<TransactionEvents>`, hence the skipped tag byte there.
+        "evtx" => Ok(Key::EventsByTxDigest(TransactionDigest::try_from(
+            decoded_digest,
+        )?)),
         _ => Err(anyhow::anyhow!("Invalid type: {}", type_)),
     }
 }
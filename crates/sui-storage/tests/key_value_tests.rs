@@ -636,4 +636,11 @@ fn test_key_to_path_and_back() {
         path_elements_to_key(path_elts.0.as_str(), path_elts.1).unwrap(),
         key
     );
+
+    let key = Key::EventsByTxDigest(tx);
+    let path_elts = key.to_path_elements();
+    assert_eq!(
+        path_elements_to_key(path_elts.0.as_str(), path_elts.1).unwrap(),
+        key
+    );
 }
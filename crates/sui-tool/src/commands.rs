@@ -7,7 +7,7 @@ use crate::{
     download_db_snapshot, download_formal_snapshot, dump_checkpoints_from_archive,
     get_latest_available_epoch, get_object, get_transaction_block, make_clients,
     restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum, ConciseObjectOutput,
-    GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
+    FilteredSnapshotOutput, GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
 };
 use anyhow::Result;
 use futures::{future::join_all, StreamExt};
@@ -18,6 +18,7 @@ use sui_core::authority_client::AuthorityAPI;
 use sui_protocol_config::Chain;
 use sui_replay::{execute_replay_command, ReplayToolCommand};
 use sui_sdk::{rpc_types::SuiTransactionBlockResponseOptions, SuiClient, SuiClientBuilder};
+use sui_snapshot::filter::LiveObjectFilter;
 use telemetry_subscribers::TracingHandle;
 
 use sui_types::{
@@ -26,6 +27,7 @@ use sui_types::{
 
 use clap::*;
 use fastcrypto::encoding::Encoding;
+use move_core_types::language_storage::StructTag;
 use sui_archival::{read_manifest_as_json, write_manifest_from_json};
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_config::Config;
@@ -33,6 +35,7 @@ use sui_core::authority_aggregator::AuthorityAggregatorBuilder;
 use sui_types::messages_checkpoint::{
     CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
 };
+use sui_types::parse_sui_struct_tag;
 use sui_types::transaction::{SenderSignedData, Transaction};
 
 #[derive(Parser, Clone, ValueEnum)]
@@ -376,6 +379,31 @@ pub enum ToolCommand {
         /// downloaded, and (if --verify is provided) will be verified via committee signature.
         #[clap(long = "all-checkpoints")]
         all_checkpoints: bool,
+
+        /// Only restore objects of this type. Can be repeated. A type without type parameters
+        /// matches all of its instantiations. If any `--filter-*` flag is set, only objects
+        /// matching at least one of them are restored, to `<path>/filtered` in the format given by
+        /// `--filter-output`, instead of to a node db. The root state hash is still verified over
+        /// the full live object set.
+        #[clap(long = "filter-type", value_parser = parse_sui_struct_tag)]
+        filter_types: Vec<StructTag>,
+        /// Only restore this package and objects of types defined in it. Can be repeated.
+        #[clap(long = "filter-package")]
+        filter_packages: Vec<ObjectID>,
+        /// Only restore objects whose immediate owner is this address. Objects owned by another
+        /// object (e.g. dynamic fields) are only restored if that object's id is given here.
+        /// Can be repeated.
+        #[clap(long = "filter-owner")]
+        filter_owners: Vec<SuiAddress>,
+        /// Output format of a filtered restore.
+        #[clap(long = "filter-output", default_value = "rocksdb")]
+        filter_output: FilteredSnapshotOutput,
+
+        /// If provided, the delta snapshots of every epoch after `--epoch` up to and including
+        /// this one are applied on top of the restored snapshot, so the db ends up at the end of
+        /// this epoch. Each delta is verified against the root state hash of its epoch.
+        #[clap(long = "delta-epoch")]
+        delta_epoch: Option<u64>,
     },
 
     #[clap(name = "replay")]
@@ -669,6 +697,11 @@ impl ToolCommand {
                 latest,
                 verbose,
                 all_checkpoints,
+                filter_types,
+                filter_packages,
+                filter_owners,
+                filter_output,
+                delta_epoch,
             } => {
                 if !verbose {
                     tracing_handle
@@ -878,6 +911,13 @@ impl ToolCommand {
                     network,
                     verify,
                     all_checkpoints,
+                    LiveObjectFilter {
+                        types: filter_types,
+                        packages: filter_packages,
+                        owners: filter_owners,
+                    },
+                    filter_output,
+                    delta_epoch,
                 )
                 .await?;
             }
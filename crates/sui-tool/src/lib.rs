@@ -54,6 +54,11 @@ use sui_core::authority::AuthorityStore;
 use sui_core::checkpoints::CheckpointStore;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::storage::RocksDbStore;
+use sui_snapshot::delta_reader::StateSnapshotDeltaReaderV1;
+use sui_snapshot::filter::{
+    FilteredObjectWriter, LiveObjectFilter, ParquetFilteredObjectWriter,
+    RocksDbFilteredObjectWriter,
+};
 use sui_snapshot::reader::StateSnapshotReaderV1;
 use sui_snapshot::setup_db_state;
 use sui_storage::object_store::util::{copy_file, exists, get_path};
@@ -90,6 +95,18 @@ pub enum SnapshotVerifyMode {
     Strict,
 }
 
+#[derive(
+    Clone, Serialize, Deserialize, Debug, PartialEq, Copy, PartialOrd, Ord, Eq, ValueEnum, Default,
+)]
+pub enum FilteredSnapshotOutput {
+    /// A standalone RocksDB holding the selected objects keyed by object id.
+    #[default]
+    #[value(name = "rocksdb")]
+    RocksDb,
+    /// Parquet files with the id, version, digest, type, owner and bcs bytes of the selected objects.
+    Parquet,
+}
+
 // This functions requires at least one of genesis or fullnode_rpc to be `Some`.
 async fn make_clients(
     sui_client: &Arc<SuiClient>,
@@ -684,6 +701,15 @@ fn start_summary_sync(
         }
         sync_progress_bar.finish_with_message("Checkpoint summary sync is complete");
 
+        for (cp_epoch, epoch_last_cp_seq_num) in end_of_epoch_checkpoint_seq_nums.iter().enumerate()
+        {
+            let epoch_last_checkpoint = checkpoint_store
+                .get_checkpoint_by_sequence_number(*epoch_last_cp_seq_num)?
+                .ok_or(anyhow!("Failed to read checkpoint"))?;
+            checkpoint_store
+                .insert_epoch_last_checkpoint(cp_epoch as u64, &epoch_last_checkpoint)?;
+        }
+
         let checkpoint = checkpoint_store
             .get_checkpoint_by_sequence_number(*last_checkpoint)?
             .ok_or(anyhow!("Failed to read last checkpoint"))?;
@@ -827,12 +853,55 @@ pub async fn download_formal_snapshot(
     network: Chain,
     verify: SnapshotVerifyMode,
     all_checkpoints: bool,
+    filter: LiveObjectFilter,
+    filter_output: FilteredSnapshotOutput,
+    delta_epoch: Option<EpochId>,
 ) -> Result<(), anyhow::Error> {
+    // Delta snapshots are applied on top of the full snapshot of `epoch` to bring the db forward to
+    // the end of `delta_epoch`.
+    let target_epoch = match delta_epoch {
+        Some(delta_epoch) if delta_epoch <= epoch => {
+            return Err(anyhow!(
+                "Delta epoch {} must be greater than snapshot epoch {}",
+                delta_epoch,
+                epoch
+            ));
+        }
+        Some(_) if !filter.is_empty() => {
+            return Err(anyhow!(
+                "Delta snapshots can't be applied to a filtered restore"
+            ));
+        }
+        Some(delta_epoch) => delta_epoch,
+        None => epoch,
+    };
     let m = MultiProgress::new();
     m.println(format!(
         "Beginning formal snapshot restore to end of epoch {}, network: {:?}, verification mode: {:?}",
-        epoch, network, verify,
+        target_epoch, network, verify,
     ))?;
+    // A filtered restore only keeps the selected objects, so it is written to a standalone output
+    // instead of a db that a node can be started from.
+    let filtered_writer: Option<Arc<dyn FilteredObjectWriter>> = if filter.is_empty() {
+        None
+    } else {
+        let filtered_path = path.join("filtered");
+        if filtered_path.exists() {
+            fs::remove_dir_all(filtered_path.clone())?;
+        }
+        m.println(format!(
+            "Restoring only objects matching {:?} to {:?} as {:?}",
+            filter, filtered_path, filter_output,
+        ))?;
+        Some(match filter_output {
+            FilteredSnapshotOutput::RocksDb => {
+                Arc::new(RocksDbFilteredObjectWriter::new(&filtered_path))
+            }
+            FilteredSnapshotOutput::Parquet => {
+                Arc::new(ParquetFilteredObjectWriter::new(&filtered_path)?)
+            }
+        })
+    };
     let path = path.join("staging").to_path_buf();
     if path.exists() {
         fs::remove_dir_all(path.clone())?;
@@ -859,7 +928,7 @@ pub async fn download_formal_snapshot(
         m.clone(),
         genesis.clone(),
         archive_store_config.clone(),
-        epoch,
+        target_epoch,
         num_parallel_downloads,
         verify != SnapshotVerifyMode::None,
         all_checkpoints,
@@ -876,6 +945,8 @@ pub async fn download_formal_snapshot(
     // not pass in a channel to the reader
     let (sender, mut receiver) = mpsc::channel(num_parallel_downloads);
     let m_clone = m.clone();
+    let is_filtered = filtered_writer.is_some();
+    let delta_store_config = snapshot_store_config.clone();
 
     let snapshot_handle = tokio::spawn(async move {
         let local_store_config = ObjectStoreConfig {
@@ -893,10 +964,16 @@ pub async fn download_formal_snapshot(
         )
         .await
         .unwrap_or_else(|err| panic!("Failed to create reader: {}", err));
-        reader
-            .read(&perpetual_db_clone, abort_registration, Some(sender))
-            .await
-            .unwrap_or_else(|err| panic!("Failed during read: {}", err));
+        match filtered_writer {
+            Some(writer) => reader
+                .read_filtered(&filter, writer.as_ref(), abort_registration, Some(sender))
+                .await
+                .unwrap_or_else(|err| panic!("Failed during filtered read: {}", err)),
+            None => reader
+                .read(&perpetual_db_clone, abort_registration, Some(sender))
+                .await
+                .unwrap_or_else(|err| panic!("Failed during read: {}", err)),
+        }
         Ok::<(), anyhow::Error>(())
     });
     let mut root_accumulator = Accumulator::default();
@@ -918,13 +995,16 @@ pub async fn download_formal_snapshot(
     if verify != SnapshotVerifyMode::None {
         assert_eq!(
             last_checkpoint.epoch(),
-            epoch,
+            target_epoch,
             "Expected highest verified checkpoint ({}) to be for epoch {} but was for epoch {}",
             last_checkpoint.sequence_number,
-            epoch,
+            target_epoch,
             last_checkpoint.epoch()
         );
-        let commitment = last_checkpoint
+        let epoch_last_checkpoint = checkpoint_store
+            .get_epoch_last_checkpoint(epoch)?
+            .expect("Expected last checkpoint of snapshot epoch to be synced");
+        let commitment = epoch_last_checkpoint
             .end_of_epoch_data
             .as_ref()
             .expect("Expected highest verified checkpoint to have end of epoch data")
@@ -969,13 +1049,59 @@ pub async fn download_formal_snapshot(
         .expect("Task join failed")
         .expect("Snapshot restore task failed");
 
-    // TODO we should ensure this map is being updated for all end of epoch
-    // checkpoints during summary sync. This happens in `insert_{verified|certified}_checkpoint`
-    // in checkpoint store, but not in the corresponding functions in ObjectStore trait
-    checkpoint_store.insert_epoch_last_checkpoint(epoch, &last_checkpoint)?;
+    if let Some(delta_epoch) = delta_epoch {
+        // Every delta is checked against the root state hash its epoch committed to before any of
+        // them is applied.
+        let root_state_hashes = (epoch + 1..=delta_epoch)
+            .map(|cp_epoch| {
+                let commitments = checkpoint_store
+                    .get_epoch_state_commitments(cp_epoch)?
+                    .ok_or(anyhow!("No end of epoch checkpoint for epoch {cp_epoch}"))?;
+                match commitments.last() {
+                    Some(CheckpointCommitment::ECMHLiveObjectSetDigest(digest)) => {
+                        Ok((cp_epoch, digest.clone()))
+                    }
+                    None => Err(anyhow!("No state commitment for epoch {cp_epoch}")),
+                }
+            })
+            .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?;
+        let local_store_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(snapshot_dir.clone()),
+            ..Default::default()
+        };
+        let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+        root_accumulator = StateSnapshotDeltaReaderV1::new(
+            epoch,
+            delta_epoch,
+            &delta_store_config,
+            &local_store_config,
+            usize::MAX,
+            NonZeroUsize::new(num_parallel_downloads).unwrap(),
+            m.clone(),
+        )
+        .await?
+        .read(
+            &perpetual_db,
+            root_accumulator,
+            &root_state_hashes,
+            abort_registration,
+        )
+        .await?;
+    }
+
+    if is_filtered {
+        // There is no node db to set up, only the filtered objects are kept
+        fs::remove_dir_all(snapshot_dir.clone())?;
+        println!(
+            "Successfully restored filtered state from snapshot at end of epoch {}",
+            epoch
+        );
+        return Ok(());
+    }
 
     setup_db_state(
-        epoch,
+        target_epoch,
         root_accumulator.clone(),
         perpetual_db.clone(),
         checkpoint_store,
@@ -995,7 +1121,7 @@ pub async fn download_formal_snapshot(
     fs::remove_dir_all(snapshot_dir.clone())?;
     println!(
         "Successfully restored state from snapshot at end of epoch {}",
-        epoch
+        target_epoch
     );
 
     Ok(())
@@ -207,6 +207,7 @@ impl TransactionalAdapter for ValidatorWithFullnode {
                 false,
             )
             .await
+            .map(|(events, _)| events)
             .unwrap_or_default()
             .into_iter()
             .map(|sui_event| sui_event.into())
@@ -630,6 +630,17 @@ pub trait RpcIndexes: Send + Sync {
     ) -> Result<Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>>;
 
     fn get_coin_info(&self, coin_type: &StructTag) -> Result<Option<CoinInfo>>;
+
+    fn get_balance(&self, owner: &SuiAddress, coin_type: &StructTag)
+        -> Result<Option<BalanceInfo>>;
+
+    /// Iterate over the balances of `owner`, starting from the coin type `cursor`. Coin types are
+    /// visited in the order of the index, rather than the order of `StructTag`.
+    fn balance_iter(
+        &self,
+        owner: &SuiAddress,
+        cursor: Option<StructTag>,
+    ) -> Result<Box<dyn Iterator<Item = (StructTag, BalanceInfo)> + '_>>;
 }
 
 pub struct AccountOwnedObjectInfo {
@@ -675,3 +686,9 @@ pub struct CoinInfo {
     pub coin_metadata_object_id: Option<ObjectID>,
     pub treasury_object_id: Option<ObjectID>,
 }
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct BalanceInfo {
+    pub balance: u64,
+    pub coin_object_count: u64,
+}
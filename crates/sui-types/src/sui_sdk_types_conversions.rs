@@ -344,6 +344,52 @@ pub fn struct_tag_sdk_to_core(
     .pipe(Ok)
 }
 
+impl TryFrom<crate::event::Event> for Event {
+    type Error = SdkTypeConversionError;
+
+    fn try_from(value: crate::event::Event) -> Result<Self, Self::Error> {
+        let crate::event::Event {
+            package_id,
+            transaction_module,
+            sender,
+            type_,
+            contents,
+        } = value;
+
+        Self {
+            package_id: package_id.into(),
+            module: Identifier::new(transaction_module.as_str())?,
+            sender: sender.into(),
+            type_: struct_tag_core_to_sdk(type_)?,
+            contents,
+        }
+        .pipe(Ok)
+    }
+}
+
+impl TryFrom<Event> for crate::event::Event {
+    type Error = SdkTypeConversionError;
+
+    fn try_from(value: Event) -> Result<Self, Self::Error> {
+        let Event {
+            package_id,
+            module,
+            sender,
+            type_,
+            contents,
+        } = value;
+
+        Self {
+            package_id: package_id.into(),
+            transaction_module: move_core_types::identifier::Identifier::new(module.into_inner())?,
+            sender: sender.into(),
+            type_: struct_tag_sdk_to_core(type_)?,
+            contents,
+        }
+        .pipe(Ok)
+    }
+}
+
 impl From<crate::messages_checkpoint::CheckpointDigest> for CheckpointDigest {
     fn from(value: crate::messages_checkpoint::CheckpointDigest) -> Self {
         Self::new(value.into_inner())
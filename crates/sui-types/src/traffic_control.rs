@@ -3,6 +3,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 // These values set to loosely attempt to limit
@@ -202,6 +203,77 @@ fn default_sketch_tolerance() -> f64 {
     DEFAULT_SKETCH_TOLERANCE
 }
 
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TokenBucketConfig {
+    /// Maximum number of tokens a direct client bucket can hold, i.e. the
+    /// largest burst of (weighted) requests a client can make at once.
+    #[serde(default = "default_bucket_capacity")]
+    pub client_capacity: u64,
+    /// Number of tokens refilled into each direct client bucket per second.
+    #[serde(default = "default_bucket_refill_rate")]
+    pub client_refill_rate: u64,
+    #[serde(default = "default_bucket_capacity")]
+    pub proxied_client_capacity: u64,
+    #[serde(default = "default_bucket_refill_rate")]
+    pub proxied_client_refill_rate: u64,
+    /// Number of tokens consumed by a request, keyed by the method name
+    /// as it appears in the request (e.g. `sui_dryRunTransactionBlock`
+    /// or `sui_multiGetObjects` for JSON-RPC, and `transaction` or
+    /// `handle_certificate_v2` for the validator gRPC service). Methods
+    /// not listed here are charged `default_method_weight`.
+    #[serde(default)]
+    pub method_weights: HashMap<String, f64>,
+    #[serde(default = "default_method_weight")]
+    pub default_method_weight: f64,
+    /// Upper bound on the number of client buckets kept in memory. When
+    /// exceeded, full (idle) buckets are evicted first, followed by the
+    /// least recently used ones.
+    #[serde(default = "default_max_tracked_clients")]
+    pub max_tracked_clients: usize,
+}
+
+impl Default for TokenBucketConfig {
+    fn default() -> Self {
+        Self {
+            client_capacity: default_bucket_capacity(),
+            client_refill_rate: default_bucket_refill_rate(),
+            proxied_client_capacity: default_bucket_capacity(),
+            proxied_client_refill_rate: default_bucket_refill_rate(),
+            method_weights: HashMap::new(),
+            default_method_weight: default_method_weight(),
+            max_tracked_clients: default_max_tracked_clients(),
+        }
+    }
+}
+
+impl TokenBucketConfig {
+    /// Returns the number of tokens charged for a request to `method`.
+    pub fn method_weight(&self, method: Option<&str>) -> f64 {
+        method
+            .and_then(|method| self.method_weights.get(method))
+            .copied()
+            .unwrap_or(self.default_method_weight)
+    }
+}
+
+fn default_bucket_capacity() -> u64 {
+    100
+}
+
+fn default_bucket_refill_rate() -> u64 {
+    10
+}
+
+fn default_method_weight() -> f64 {
+    1.0
+}
+
+fn default_max_tracked_clients() -> usize {
+    100_000
+}
+
 // Serializable representation of policy types, used in config
 // in order to easily change in tests or to killswitch
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
@@ -215,6 +287,12 @@ pub enum PolicyType {
     /// with granularity of `update_interval_secs`
     FreqThreshold(FreqThresholdConfig),
 
+    /// Maintains a token bucket per client, refilled at a constant rate.
+    /// Each request consumes a number of tokens based on the method called,
+    /// and the client is blocked once its bucket cannot cover the cost of
+    /// a request.
+    TokenBucket(TokenBucketConfig),
+
     /* Below this point are test policies, and thus should not be used in production */
     ///
     /// Simple policy that adds connection_ip to blocklist when the same connection_ip
@@ -256,6 +334,18 @@ pub struct PolicyConfig {
     /// and any blocklist related configuration will be ignored.
     #[serde(default)]
     pub allow_list: Option<Vec<String>>,
+    /// List of CIDR ranges (e.g. "10.0.0.0/8") whose clients are never
+    /// blocked, regardless of the configured policies. Unlike `allow_list`,
+    /// clients outside of these ranges are still subject to the policies.
+    #[serde(default)]
+    pub cidr_allow_list: Vec<String>,
+    /// List of CIDR ranges whose clients are always blocked. If a client
+    /// matches both lists, `cidr_allow_list` takes precedence. Both lists
+    /// are matched against the connection IP and, for proxied requests,
+    /// the IP of the proxied client; if either IP is blocked, so is the
+    /// request.
+    #[serde(default)]
+    pub cidr_block_list: Vec<String>,
 }
 
 impl Default for PolicyConfig {
@@ -270,6 +360,8 @@ impl Default for PolicyConfig {
             spam_sample_rate: default_spam_sample_rate(),
             dry_run: default_dry_run(),
             allow_list: None,
+            cidr_allow_list: vec![],
+            cidr_block_list: vec![],
         }
     }
 }
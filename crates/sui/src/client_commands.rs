@@ -9,7 +9,7 @@ use crate::{
     verifier_meter::{AccumulatingMeter, Accumulator},
 };
 use std::{
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
     fs,
     path::{Path, PathBuf},
@@ -120,6 +120,26 @@ pub enum SuiClientCommands {
         #[clap(long, short = 's')]
         sort_by_alias: bool,
     },
+    /// Sign a serialized transaction with an address of the keystore, adding its signature to the
+    /// ones the transaction already has. This is how the sender and the gas sponsor of a sponsored
+    /// transaction each sign it. Outputs the signed transaction, which can be executed with
+    /// `sui client execute-combined-signed-tx` once it has the signatures of all its signers.
+    #[clap(name = "add-signature")]
+    AddSignature {
+        /// BCS serialized transaction data bytes without its type tag, as base64 encoded string.
+        /// This is the output of sui client command using --serialize-unsigned-transaction.
+        #[clap(long, required_unless_present = "signed_tx_bytes")]
+        tx_bytes: Option<String>,
+        /// BCS serialized sender signed data, as base64 encoded string. This is the output of sui
+        /// client command using --serialize-signed-transaction, or of this command.
+        #[clap(long, conflicts_with = "tx_bytes")]
+        signed_tx_bytes: Option<String>,
+        /// Address (or its alias) to sign with. If not provided, the transaction is signed by
+        /// every signer of the transaction whose key is in the keystore.
+        #[clap(long)]
+        #[arg(value_parser)]
+        address: Option<KeyIdentity>,
+    },
     /// List the coin balance of an address
     #[clap(name = "balance")]
     Balance {
@@ -170,6 +190,16 @@ pub enum SuiClientCommands {
     #[clap(name = "chain-identifier")]
     ChainIdentifier,
 
+    /// Combine the signatures of several signed copies of the same transaction, for instance one
+    /// signed by the sender and one signed by the gas sponsor, into a single signed transaction.
+    #[clap(name = "combine-signed-tx")]
+    CombineSignedTx {
+        /// BCS serialized sender signed data, as base64 encoded strings. These are outputs of sui
+        /// client commands using --serialize-signed-transaction, or of `sui client add-signature`.
+        #[clap(long, num_args(1..), required = true)]
+        signed_tx_bytes: Vec<String>,
+    },
+
     /// Query a dynamic field by its address.
     #[clap(name = "dynamic-field")]
     DynamicFieldQuery {
@@ -1569,16 +1599,68 @@ impl SuiClientCommands {
                 SuiClientCommandResult::TransactionBlock(response)
             }
             SuiClientCommands::ExecuteCombinedSignedTx { signed_tx_bytes } => {
-                let data: SenderSignedData = bcs::from_bytes(
-                    &Base64::try_from(signed_tx_bytes)
-                        .map_err(|_| anyhow!("Invalid Base64 encoding"))?
-                        .to_vec()
-                        .map_err(|_| anyhow!("Invalid Base64 encoding"))?
-                ).map_err(|_| anyhow!("Failed to parse SenderSignedData bytes, check if it matches the output of sui client commands with --serialize-signed-transaction"))?;
+                let data = decode_signed_tx(signed_tx_bytes)?;
                 let transaction = Envelope::<SenderSignedData, EmptySignInfo>::new(data);
                 let response = context.execute_transaction_may_fail(transaction).await?;
                 SuiClientCommandResult::TransactionBlock(response)
             }
+            SuiClientCommands::AddSignature {
+                tx_bytes,
+                signed_tx_bytes,
+                address,
+            } => {
+                let mut signed_tx = match (tx_bytes, signed_tx_bytes) {
+                    (Some(tx_bytes), None) => {
+                        let data = bcs::from_bytes(
+                            &Base64::try_from(tx_bytes)
+                            .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+                            .to_vec()
+                            .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+                        ).map_err(|_| anyhow!("Failed to parse tx bytes, check if it matches the output of sui client commands with --serialize-unsigned-transaction"))?;
+                        SenderSignedData::new(data, vec![])
+                    }
+                    (None, Some(signed_tx_bytes)) => decode_signed_tx(signed_tx_bytes)?,
+                    _ => bail!("Provide exactly one of --tx-bytes and --signed-tx-bytes"),
+                };
+                let signer = match address {
+                    Some(address) => Some(get_identity_address(Some(address), context)?),
+                    None => None,
+                };
+                let missing_signers = countersign(context, &mut signed_tx, signer)?;
+                SuiClientCommandResult::signed_transaction(signed_tx, missing_signers)
+            }
+            SuiClientCommands::CombineSignedTx { signed_tx_bytes } => {
+                let signed_txs = signed_tx_bytes
+                    .into_iter()
+                    .map(decode_signed_tx)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let Some(first) = signed_txs.first() else {
+                    bail!("No signed transaction to combine");
+                };
+                let tx_data = first.transaction_data().clone();
+                let signers = tx_data.signers();
+
+                let mut signatures = BTreeMap::new();
+                for signed_tx in &signed_txs {
+                    ensure!(
+                        signed_tx.transaction_data() == &tx_data,
+                        "Cannot combine signatures of different transactions"
+                    );
+                    for signature in signed_tx.tx_signatures() {
+                        let signer = SuiAddress::try_from(signature)?;
+                        ensure!(
+                            signers.contains(&signer),
+                            "Found a signature of {signer}, which is neither the sender nor the \
+                            gas owner of the transaction"
+                        );
+                        signatures.insert(signer, signature.clone());
+                    }
+                }
+
+                let signed_tx = SenderSignedData::new(tx_data, signatures.into_values().collect());
+                let missing_signers = missing_signers(&signed_tx)?;
+                SuiClientCommandResult::signed_transaction(signed_tx, missing_signers)
+            }
             SuiClientCommands::NewEnv {
                 alias,
                 rpc,
@@ -1651,10 +1733,7 @@ impl SuiClientCommands {
 
                 SuiClientCommandResult::VerifySource
             }
-            SuiClientCommands::PTB(ptb) => {
-                ptb.execute(context).await?;
-                SuiClientCommandResult::NoOutput
-            }
+            SuiClientCommands::PTB(ptb) => ptb.execute(context).await?,
         };
         Ok(ret.prerender_clever_errors(context).await)
     }
@@ -2105,6 +2184,27 @@ impl Display for SuiClientCommandResult {
                     fastcrypto::encoding::Base64::encode(bcs::to_bytes(sender_signed_tx).unwrap())
                 )?;
             }
+            SuiClientCommandResult::PartiallySignedTransaction {
+                signed_tx,
+                missing_signers,
+            } => {
+                let missing = missing_signers
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(
+                    writer,
+                    "The transaction still needs to be signed by: {missing}. Pass the bytes \
+                    below to `sui client add-signature --signed-tx-bytes` to sign it, then to \
+                    `sui client execute-combined-signed-tx` to execute it."
+                )?;
+                writeln!(
+                    writer,
+                    "{}",
+                    fastcrypto::encoding::Base64::encode(bcs::to_bytes(signed_tx).unwrap())
+                )?;
+            }
             SuiClientCommandResult::SyncClientState => {
                 writeln!(writer, "Client state sync complete.")?;
             }
@@ -2313,6 +2413,18 @@ impl SuiClientCommandResult {
         }
     }
 
+    /// A signed transaction, serialized if it has all its signatures.
+    fn signed_transaction(signed_tx: SenderSignedData, missing_signers: Vec<SuiAddress>) -> Self {
+        if missing_signers.is_empty() {
+            SuiClientCommandResult::SerializedSignedTransaction(signed_tx)
+        } else {
+            SuiClientCommandResult::PartiallySignedTransaction {
+                signed_tx,
+                missing_signers,
+            }
+        }
+    }
+
     pub fn tx_block_response(&self) -> Option<&SuiTransactionBlockResponse> {
         use SuiClientCommandResult::*;
         match self {
@@ -2350,6 +2462,7 @@ impl SuiClientCommandResult {
             | SuiClientCommandResult::NoOutput
             | SuiClientCommandResult::Object(_)
             | SuiClientCommandResult::Objects(_)
+            | SuiClientCommandResult::PartiallySignedTransaction { .. }
             | SuiClientCommandResult::RawObject(_)
             | SuiClientCommandResult::SerializedSignedTransaction(_)
             | SuiClientCommandResult::SerializedUnsignedTransaction(_)
@@ -2500,6 +2613,10 @@ pub enum SuiClientCommandResult {
     NoOutput,
     Object(SuiObjectResponse),
     Objects(Vec<SuiObjectResponse>),
+    PartiallySignedTransaction {
+        signed_tx: SenderSignedData,
+        missing_signers: Vec<SuiAddress>,
+    },
     RawObject(SuiObjectResponse),
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
@@ -2815,6 +2932,34 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
     gas_price: Option<u64>,
     gas: Option<ObjectID>,
     opts: Opts,
+) -> Result<SuiClientCommandResult, anyhow::Error> {
+    dry_run_or_execute_or_serialize_with_sponsor(
+        signer,
+        tx_kind,
+        context,
+        gas_payment,
+        gas_price,
+        gas,
+        None,
+        opts,
+    )
+    .await
+}
+
+/// Like [dry_run_or_execute_or_serialize], but the gas is paid by `gas_sponsor` if one is given.
+///
+/// A sponsored transaction needs the signatures of both the sender and the sponsor. If the
+/// keystore does not hold the keys of both, the transaction is not executed: it is returned with
+/// the signatures that could be added, so that the other party can add theirs.
+pub(crate) async fn dry_run_or_execute_or_serialize_with_sponsor(
+    signer: SuiAddress,
+    tx_kind: TransactionKind,
+    context: &mut WalletContext,
+    gas_payment: Option<Vec<ObjectID>>,
+    gas_price: Option<u64>,
+    gas: Option<ObjectID>,
+    gas_sponsor: Option<SuiAddress>,
+    opts: Opts,
 ) -> Result<SuiClientCommandResult, anyhow::Error> {
     let (
         dry_run,
@@ -2849,7 +2994,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             gas_budget,
             gas_price,
             gas_payment,
-            None,
+            gas_sponsor,
             None,
         )
         .await;
@@ -2868,7 +3013,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             gas_budget,
             gas_price,
             gas.clone(),
-            None,
+            gas_sponsor,
         )
         .await;
     }
@@ -2883,7 +3028,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
                 tx_kind.clone(),
                 gas_price,
                 gas.clone(),
-                None,
+                gas_sponsor,
             )
             .await?;
             debug!("Finished estimating gas budget");
@@ -2900,7 +3045,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             gas_budget,
             gas_price,
             gas.unwrap_or_default(),
-            None,
+            gas_sponsor,
         )
         .await?;
     debug!("Finished preparing transaction data");
@@ -2915,8 +3060,15 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             &tx_data,
             Intent::sui_transaction(),
         )?;
-        let sender_signed_data = SenderSignedData::new_from_sender_signature(tx_data, signature);
-        if serialize_signed_transaction {
+        let mut sender_signed_data =
+            SenderSignedData::new_from_sender_signature(tx_data, signature);
+        let missing_signers = countersign(context, &mut sender_signed_data, None)?;
+        if !missing_signers.is_empty() {
+            Ok(SuiClientCommandResult::PartiallySignedTransaction {
+                signed_tx: sender_signed_data,
+                missing_signers,
+            })
+        } else if serialize_signed_transaction {
             Ok(SuiClientCommandResult::SerializedSignedTransaction(
                 sender_signed_data,
             ))
@@ -2944,6 +3096,74 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
     }
 }
 
+/// Decodes the base64 encoded BCS bytes of a signed transaction.
+fn decode_signed_tx(signed_tx_bytes: String) -> Result<SenderSignedData, anyhow::Error> {
+    bcs::from_bytes(
+        &Base64::try_from(signed_tx_bytes)
+            .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+            .to_vec()
+            .map_err(|_| anyhow!("Invalid Base64 encoding"))?,
+    )
+    .map_err(|_| anyhow!("Failed to parse SenderSignedData bytes, check if it matches the output of sui client commands with --serialize-signed-transaction"))
+}
+
+/// The signers of a transaction (its sender and gas owner) that have not signed it yet.
+fn missing_signers(signed_tx: &SenderSignedData) -> Result<Vec<SuiAddress>, anyhow::Error> {
+    let signed = signed_tx
+        .tx_signatures()
+        .iter()
+        .map(SuiAddress::try_from)
+        .collect::<Result<BTreeSet<_>, _>>()?;
+    Ok(signed_tx
+        .transaction_data()
+        .signers()
+        .into_iter()
+        .filter(|signer| !signed.contains(signer))
+        .collect())
+}
+
+/// Adds the signature of `signer` to a transaction, or if no signer is given, the signatures of
+/// all the signers that have not signed it yet and whose keys are in the keystore. Returns the
+/// signers still missing afterwards.
+fn countersign(
+    context: &WalletContext,
+    signed_tx: &mut SenderSignedData,
+    signer: Option<SuiAddress>,
+) -> Result<Vec<SuiAddress>, anyhow::Error> {
+    let missing = missing_signers(signed_tx)?;
+    let keystore = &context.config.keystore;
+    let to_sign: Vec<_> = match signer {
+        Some(signer) => {
+            ensure!(
+                signed_tx.transaction_data().signers().contains(&signer),
+                "{signer} is neither the sender nor the gas owner of the transaction"
+            );
+            ensure!(
+                missing.contains(&signer),
+                "The transaction is already signed by {signer}"
+            );
+            vec![signer]
+        }
+        None => {
+            let addresses = keystore.addresses();
+            missing
+                .into_iter()
+                .filter(|signer| addresses.contains(signer))
+                .collect()
+        }
+    };
+
+    for signer in to_sign {
+        let signature = keystore.sign_secure(
+            &signer,
+            signed_tx.transaction_data(),
+            Intent::sui_transaction(),
+        )?;
+        signed_tx.add_signature(signature);
+    }
+    missing_signers(signed_tx)
+}
+
 async fn execute_dev_inspect(
     context: &mut WalletContext,
     signer: SuiAddress,
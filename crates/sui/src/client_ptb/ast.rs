@@ -33,11 +33,14 @@ pub const WARN_SHADOWS: &str = "warn-shadows";
 pub const GAS_BUDGET: &str = "gas-budget";
 pub const SUMMARY: &str = "summary";
 pub const GAS_COIN: &str = "gas-coin";
+pub const GAS_COINS: &str = "gas-coins";
+pub const GAS_SPONSOR: &str = "gas-sponsor";
 pub const JSON: &str = "json";
 pub const DRY_RUN: &str = "dry-run";
 pub const DEV_INSPECT: &str = "dev-inspect";
 pub const SERIALIZE_UNSIGNED: &str = "serialize-unsigned-transaction";
 pub const SERIALIZE_SIGNED: &str = "serialize-signed-transaction";
+pub const CHECK: &str = "check";
 
 // Types
 pub const U8: &str = "u8";
@@ -73,11 +76,14 @@ pub const COMMANDS: &[&str] = &[
     GAS_BUDGET,
     SUMMARY,
     GAS_COIN,
+    GAS_COINS,
+    GAS_SPONSOR,
     JSON,
     DRY_RUN,
     DEV_INSPECT,
     SERIALIZE_UNSIGNED,
     SERIALIZE_SIGNED,
+    CHECK,
 ];
 
 pub fn is_keyword(s: &str) -> bool {
@@ -115,6 +121,9 @@ pub struct ProgramMetadata {
     pub dry_run_set: bool,
     pub dev_inspect_set: bool,
     pub gas_budget: Option<Spanned<u64>>,
+    pub gas_coins: Option<Spanned<Vec<ObjectID>>>,
+    pub gas_sponsor: Option<Spanned<ParsedAddress>>,
+    pub check_set: bool,
 }
 
 /// A parsed module access consisting of the address, module name, and function name.
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use miette::{miette, LabeledSpan, Severity};
+use miette::{miette, LabeledSpan, NamedSource, Severity};
 use std::fmt;
 use thiserror::Error;
 
@@ -171,13 +171,27 @@ impl<T: Clone> Clone for Spanned<T> {
 impl<T: Copy> Copy for Spanned<T> {}
 
 fn build_error_report(file_string: &str, error: PTBError) -> miette::Report {
+    error_report(file_string, error).with_source_code(file_string.to_string())
+}
+
+/// Like [build_error_reports], for an error located in the file `file_name`.
+pub fn build_file_error_report(
+    file_name: &str,
+    file_string: &str,
+    error: PTBError,
+) -> miette::Report {
+    error_report(file_string, error)
+        .with_source_code(NamedSource::new(file_name, file_string.to_string()))
+}
+
+fn error_report(file_string: &str, error: PTBError) -> miette::Report {
     let PTBError {
         span,
         message,
         help,
         severity,
     } = error;
-    let clamp = |x: usize| x.min(file_string.len() - 1);
+    let clamp = |x: usize| x.min(file_string.len().saturating_sub(1));
     let label = LabeledSpan::at(clamp(span.start)..clamp(span.end), message.clone());
     let error_string = match severity {
         Severity::Advice => "Advice found when processing PTB".to_string(),
@@ -193,7 +207,6 @@ fn build_error_report(file_string: &str, error: PTBError) -> miette::Report {
             error_string
         ),
     }
-    .with_source_code(file_string.to_string())
 }
 
 pub fn build_error_reports(source_string: &str, errors: Vec<PTBError>) -> Vec<miette::Report> {
@@ -44,6 +44,9 @@ struct ProgramParsingState {
     dev_inspect_set: bool,
     gas_object_id: Option<Spanned<ObjectID>>,
     gas_budget: Option<Spanned<u64>>,
+    gas_coins: Option<Spanned<Vec<ObjectID>>>,
+    gas_sponsor: Option<Spanned<ParsedAddress>>,
+    check_set: bool,
 }
 
 impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
@@ -67,6 +70,9 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 dev_inspect_set: false,
                 gas_object_id: None,
                 gas_budget: None,
+                gas_coins: None,
+                gas_sponsor: None,
+                check_set: false,
             },
         })
     }
@@ -115,10 +121,43 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 L(T::Command, A::DEV_INSPECT) => flag!(dev_inspect_set),
                 L(T::Command, A::PREVIEW) => flag!(preview_set),
                 L(T::Command, A::WARN_SHADOWS) => flag!(warn_shadows_set),
+                L(T::Command, A::CHECK) => flag!(check_set),
                 L(T::Command, A::GAS_COIN) => {
                     let specifier = try_!(self.parse_gas_specifier());
                     self.state.gas_object_id = Some(specifier);
                 }
+                L(T::Command, A::GAS_COINS) => {
+                    let coins = try_!(self.parse_gas_coins()).widen_span(sp);
+                    let span = coins.span;
+                    if let Some(other) = self.state.gas_coins.replace(coins) {
+                        self.state.errors.extend([
+                            err!(
+                                other.span,
+                                "Multiple gas coin lists found. Gas coins first set here.",
+                            ),
+                            err!(span => help: {
+                                "PTBs must have at most one list of gas coins set."
+                            },"Gas coins set again here."),
+                        ]);
+                        self.fast_forward_to_next_command();
+                    }
+                }
+                L(T::Command, A::GAS_SPONSOR) => {
+                    let sponsor = try_!(self.parse_gas_sponsor()).widen_span(sp);
+                    let span = sponsor.span;
+                    if let Some(other) = self.state.gas_sponsor.replace(sponsor) {
+                        self.state.errors.extend([
+                            err!(
+                                other.span,
+                                "Multiple gas sponsors found. Gas sponsor first set here.",
+                            ),
+                            err!(span => help: {
+                                "PTBs must have at most one gas sponsor set."
+                            },"Gas sponsor set again here."),
+                        ]);
+                        self.fast_forward_to_next_command();
+                    }
+                }
                 L(T::Command, A::GAS_BUDGET) => {
                     let budget = try_!(self.parse_gas_budget()).widen_span(sp);
                     if let Some(other) = self.state.gas_budget.replace(budget) {
@@ -212,6 +251,9 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     dry_run_set: self.state.dry_run_set,
                     dev_inspect_set: self.state.dev_inspect_set,
                     gas_budget: self.state.gas_budget,
+                    gas_coins: self.state.gas_coins,
+                    gas_sponsor: self.state.gas_sponsor,
+                    check_set: self.state.check_set,
                 },
             ))
         } else {
@@ -378,6 +420,37 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
             .parse_address_literal()?
             .map(|a| ObjectID::from(a.into_inner())))
     }
+
+    /// Parse a list of gas coins.
+    /// The expected format is: `--gas-coins [<address>, ...]`
+    fn parse_gas_coins(&mut self) -> PTBResult<Spanned<Vec<ObjectID>>> {
+        let sp!(sp, args) = self.parse_array()?;
+        let mut coins = vec![];
+        for arg in args {
+            match arg {
+                sp!(_, Argument::Address(a)) => coins.push(ObjectID::from(a.into_inner())),
+                sp!(sp, _) => error!(
+                    sp => help: { "Gas coins are passed as object IDs, e.g. [@0x1, @0x2]" },
+                    "Expected an object ID",
+                ),
+            }
+        }
+        if coins.is_empty() {
+            error!(sp, "Expected at least one gas coin");
+        }
+        Ok(sp.wrap(coins))
+    }
+
+    /// Parse a gas sponsor, given by its address or by the alias of an address in the keystore.
+    /// The expected format is: `--gas-sponsor <address>`
+    fn parse_gas_sponsor(&mut self) -> PTBResult<Spanned<ParsedAddress>> {
+        let sp!(sp, _) = self.expect(Token::At).map_err(|e| {
+            err!(e.span => help: {
+                "Addresses require the character '@' in front"
+            }, "Expected an address")
+        })?;
+        Ok(self.parse_address()?.widen_span(sp))
+    }
 }
 
 /// Methods for parsing arguments and types in commands
@@ -1023,4 +1096,43 @@ mod tests {
         }
         insta::assert_debug_snapshot!(parsed);
     }
+
+    #[test]
+    fn test_parse_gas_sponsor() {
+        let input = "--gas-sponsor @0x5 --gas-coins [@0x1, @0x2] --gas-budget 1";
+        let x = shlex::split(input).unwrap();
+        let parser = ProgramParser::new(x.iter().map(|x| x.as_str())).unwrap();
+        let (_, metadata) = parser.parse().unwrap();
+        assert_eq!(
+            metadata.gas_coins.unwrap().value,
+            vec![ObjectID::from_single_byte(1), ObjectID::from_single_byte(2)]
+        );
+        assert!(matches!(
+            metadata.gas_sponsor.unwrap().value,
+            ParsedAddress::Numerical(_)
+        ));
+
+        let input = "--gas-sponsor @alice --gas-budget 1";
+        let x = shlex::split(input).unwrap();
+        let parser = ProgramParser::new(x.iter().map(|x| x.as_str())).unwrap();
+        let (_, metadata) = parser.parse().unwrap();
+        assert!(matches!(
+            metadata.gas_sponsor.unwrap().value,
+            ParsedAddress::Named(name) if name == "alice"
+        ));
+
+        let inputs = vec![
+            "--gas-sponsor 0x5 --gas-budget 1",
+            "--gas-coins [] --gas-budget 1",
+            "--gas-coins [1, 2] --gas-budget 1",
+            "--gas-coins @0x1 --gas-budget 1",
+            "--gas-sponsor @0x5 --gas-sponsor @0x6 --gas-budget 1",
+            "--gas-coins [@0x1] --gas-coins [@0x2] --gas-budget 1",
+        ];
+        for input in inputs {
+            let x = shlex::split(input).unwrap();
+            let parser = ProgramParser::new(x.iter().map(|x| x.as_str())).unwrap();
+            assert!(parser.parse().is_err(), "Parsed {input:?}");
+        }
+    }
 }
@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    client_commands::{dry_run_or_execute_or_serialize, Opts, OptsWithGas, SuiClientCommandResult},
+    client_commands::{
+        dry_run_or_execute_or_serialize_with_sponsor, Opts, OptsWithGas, SuiClientCommandResult,
+    },
     client_ptb::{
         ast::{ParsedProgram, Program},
         builder::PTBBuilder,
-        error::{build_error_reports, PTBError},
+        error::PTBError,
+        script::PTBScript,
         token::{Lexeme, Token},
     },
     displays::Pretty,
@@ -22,6 +25,7 @@ use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::{wallet_context::WalletContext, SuiClient};
 use sui_types::{
+    base_types::SuiAddress,
     digests::TransactionDigest,
     gas::GasCostSummary,
     transaction::{ProgrammableTransaction, TransactionKind},
@@ -47,20 +51,43 @@ pub struct Summary {
 }
 
 impl PTB {
-    /// Parses and executes the PTB with the sender as the current active address
-    pub async fn execute(self, context: &mut WalletContext) -> Result<(), Error> {
+    /// Parses and executes the PTB with the sender as the current active address. Transactions
+    /// that are serialized or still need signatures are returned rather than printed, like the
+    /// results of other client commands; everything else is printed here.
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, Error> {
         if self.args.is_empty() {
             ptb_description().print_help().unwrap();
-            return Ok(());
+            return Ok(SuiClientCommandResult::NoOutput);
         }
-        let source_string = to_source_string(self.args.clone());
+
+        // Expand script files
+        let script = match PTBScript::expand(self.args) {
+            Ok(script) => script,
+            Err(rendered) => {
+                let suffix = if rendered.len() > 1 { "s" } else { "" };
+                eprintln!("Encountered error{suffix} when loading PTB script:");
+                for e in rendered.iter() {
+                    eprintln!("{:?}", e);
+                }
+                anyhow::bail!("Could not build PTB due to previous error{suffix}");
+            }
+        };
 
         // Tokenize once to detect help flags
-        let tokens = self.args.iter().map(|s| s.as_str());
+        let tokens = script.tokens();
         for sp!(_, lexeme) in Lexer::new(tokens.clone()).into_iter().flatten() {
             match lexeme {
-                Lexeme(Token::Command, "help") => return Ok(ptb_description().print_long_help()?),
-                Lexeme(Token::Flag, "h") => return Ok(ptb_description().print_help()?),
+                Lexeme(Token::Command, "help") => {
+                    ptb_description().print_long_help()?;
+                    return Ok(SuiClientCommandResult::NoOutput);
+                }
+                Lexeme(Token::Flag, "h") => {
+                    ptb_description().print_help()?;
+                    return Ok(SuiClientCommandResult::NoOutput);
+                }
                 lexeme if lexeme.is_terminal() => break,
                 _ => continue,
             }
@@ -73,7 +100,7 @@ impl PTB {
         {
             Err(errors) => {
                 let suffix = if errors.len() > 1 { "s" } else { "" };
-                let rendered = build_error_reports(&source_string, errors);
+                let rendered = script.error_reports(errors);
                 eprintln!("Encountered error{suffix} when parsing PTB:");
                 for e in rendered.iter() {
                     eprintln!("{:?}", e);
@@ -87,6 +114,13 @@ impl PTB {
             !program_metadata.serialize_unsigned_set || !program_metadata.serialize_signed_set,
             "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
         );
+        ensure!(
+            !program_metadata.check_set
+                || !(program_metadata.dev_inspect_set
+                    || program_metadata.serialize_unsigned_set
+                    || program_metadata.serialize_signed_set),
+            "Cannot specify --check together with --dev-inspect or the serialize flags."
+        );
 
         if program_metadata.preview_set {
             println!(
@@ -96,7 +130,7 @@ impl PTB {
                     program_metadata: &program_metadata
                 }
             );
-            return Ok(());
+            return Ok(SuiClientCommandResult::NoOutput);
         }
 
         let client = context.get_client().await?;
@@ -107,7 +141,7 @@ impl PTB {
         if !warnings.is_empty() {
             let suffix = if warnings.len() > 1 { "s" } else { "" };
             eprintln!("Warning{suffix} produced when building PTB:");
-            let rendered = build_error_reports(&source_string, warnings);
+            let rendered = script.error_reports(warnings);
             for e in rendered.iter() {
                 eprintln!("{:?}", e);
             }
@@ -116,7 +150,7 @@ impl PTB {
             Err(errors) => {
                 let suffix = if errors.len() > 1 { "s" } else { "" };
                 eprintln!("Encountered error{suffix} when building PTB:");
-                let rendered = build_error_reports(&source_string, errors);
+                let rendered = script.error_reports(errors);
                 for e in rendered.iter() {
                     eprintln!("{:?}", e);
                 }
@@ -126,10 +160,36 @@ impl PTB {
         };
 
         // get all the metadata needed for executing the PTB: sender, gas, signing tx
-        let gas = program_metadata.gas_object_id.map(|x| x.value);
+        ensure!(
+            program_metadata.gas_object_id.is_none() || program_metadata.gas_coins.is_none(),
+            "Cannot specify both --gas-coin and --gas-coins"
+        );
+        let gas_coins = program_metadata.gas_coins.map(|x| x.value);
+        let gas = program_metadata
+            .gas_object_id
+            .map(|x| x.value)
+            .or_else(|| gas_coins.as_ref().map(|coins| coins[0]));
+
+        let gas_sponsor = match program_metadata.gas_sponsor {
+            Some(sponsor) => {
+                let keystore = &context.config.keystore;
+                let address = sponsor
+                    .value
+                    .into_account_address(&|alias| {
+                        keystore
+                            .get_address_by_alias(alias.to_string())
+                            .ok()
+                            .map(|address| AccountAddress::from(*address))
+                    })
+                    .map_err(|e| anyhow!("Invalid gas sponsor: {e}"))?;
+                Some(SuiAddress::from(address))
+            }
+            None => None,
+        };
 
-        // the sender is the gas object if gas is provided, otherwise the active address
-        let sender = match gas {
+        // the sender is the active address if the gas is sponsored, otherwise the owner of the
+        // gas object if gas is provided, otherwise the active address
+        let sender = match gas.filter(|_| gas_sponsor.is_none()) {
             Some(gas) => context
                 .get_object_owner(&gas)
                 .await
@@ -149,7 +209,7 @@ impl PTB {
         let opts = OptsWithGas {
             gas: program_metadata.gas_object_id.map(|x| x.value),
             rest: Opts {
-                dry_run: program_metadata.dry_run_set,
+                dry_run: program_metadata.dry_run_set || program_metadata.check_set,
                 dev_inspect: program_metadata.dev_inspect_set,
                 gas_budget: program_metadata.gas_budget.map(|x| x.value),
                 serialize_unsigned_transaction: program_metadata.serialize_unsigned_set,
@@ -157,25 +217,45 @@ impl PTB {
             },
         };
 
-        let transaction_response = dry_run_or_execute_or_serialize(
-            sender, tx_kind, context, None, None, opts.gas, opts.rest,
+        let transaction_response = dry_run_or_execute_or_serialize_with_sponsor(
+            sender,
+            tx_kind,
+            context,
+            gas_coins,
+            None,
+            opts.gas,
+            gas_sponsor,
+            opts.rest,
         )
         .await?;
 
         let transaction_response = match transaction_response {
+            SuiClientCommandResult::DryRun(ref response) if program_metadata.check_set => {
+                println!("{}", transaction_response);
+                let status = response.effects.status();
+                ensure!(
+                    status.is_ok(),
+                    "PTB check failed, dry run {}",
+                    Pretty(status)
+                );
+                println!(
+                    "PTB check passed: the PTB was parsed, resolved and dry run successfully."
+                );
+                return Ok(SuiClientCommandResult::NoOutput);
+            }
             SuiClientCommandResult::DryRun(_) => {
                 println!("{}", transaction_response);
-                return Ok(());
+                return Ok(SuiClientCommandResult::NoOutput);
             }
             SuiClientCommandResult::SerializedUnsignedTransaction(_)
-            | SuiClientCommandResult::SerializedSignedTransaction(_) => {
-                println!("{}", transaction_response);
-                return Ok(());
+            | SuiClientCommandResult::SerializedSignedTransaction(_)
+            | SuiClientCommandResult::PartiallySignedTransaction { .. } => {
+                return Ok(transaction_response);
             }
             SuiClientCommandResult::TransactionBlock(response) => response,
             SuiClientCommandResult::DevInspect(response) => {
                 println!("{}", Pretty(&response));
-                return Ok(());
+                return Ok(SuiClientCommandResult::NoOutput);
             }
             _ => anyhow::bail!("Internal error, unexpected response from PTB execution."),
         };
@@ -216,7 +296,7 @@ impl PTB {
             println!("{}", transaction_response);
         }
 
-        Ok(())
+        Ok(SuiClientCommandResult::NoOutput)
     }
 
     /// Exposed for testing
@@ -304,11 +384,48 @@ pub fn ptb_description() -> clap::Command {
             --"dev-inspect"
             "Perform a dev-inspect of the PTB instead of executing it."
         ))
+        .arg(arg!(
+            --"check"
+            "Parse, resolve and dry run the PTB without executing it, failing if the dry run \
+            fails. Useful to validate a script before running it."
+        ))
+        .arg(arg!(
+            --"file" <PATH>
+            "Read commands from a PTB script file."
+        )
+        .long_help(
+            "Read commands from a PTB script file. A script holds commands written as on the \
+            command line, without a shell: words are separated by whitespace and quoted as in a \
+            POSIX shell, and a '#' at the start of a word comments out the rest of the line. \
+            Scripts can include other scripts with --file, and package paths passed to \
+            --publish and --upgrade are relative to the script. \
+            \n\nExamples: \
+            \n --file deploy.ptb --arg recipient=@alice --arg amount=1000 \
+            \n --file transfer.ptb --gas-budget 50000000 --check"
+        ))
+        .arg(arg!(
+            --"arg" <NAME_VALUE>
+            "Pass a parameter to the PTB script files, as name=value. Scripts reference it as \
+            ${name}, which is replaced by its value."
+        ))
         .arg(arg!(
             --"gas-coin" <ID> ...
             "The object ID of the gas coin to use. If not specified, it will try to use the first \
             gas coin that it finds that has at least the requested gas-budget balance."
         ))
+        .arg(arg!(
+            --"gas-coins" <IDS>
+            "The object IDs of the coins to pay for gas with, as an array, e.g. [@0x1, @0x2]. \
+            Cannot be used together with --gas-coin."
+        ))
+        .arg(arg!(
+            --"gas-sponsor" <ADDRESS>
+            "The address (or alias) paying for gas, e.g. @0x1. The PTB is sent by the active \
+            address and needs to be signed by both: if the keystore does not hold both keys, the \
+            partially signed transaction is printed instead of executed, to be signed by the \
+            other party with `sui client add-signature` and executed with \
+            `sui client execute-combined-signed-tx`."
+        ))
         .arg(arg!(
             --"gas-budget" <MIST>
             "An optional gas budget for this PTB (in MIST). If gas budget is not provided, the \
@@ -0,0 +1,748 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! PTB script files.
+//!
+//! A script file holds the commands of a PTB, written as they would be on the command line but
+//! without a shell: words are separated by whitespace, quoted with single or double quotes and
+//! escaped with backslashes as in a POSIX shell, and a `#` at the start of a word comments out the
+//! rest of the line. On top of that:
+//!
+//! - `--file <PATH>` includes another script, relative to the including one. On the command line,
+//!   it includes a script relative to the current directory.
+//! - `${name}` is replaced by the value of the parameter `name`, passed on the command line with
+//!   `--arg name=value`. Parameters are not replaced inside single quotes, and `\$` escapes them.
+//! - Package paths given to `--publish` and `--upgrade` are relative to the script.
+//!
+//! Scripts are expanded into the sequence of tokens the lexer would have received from the shell,
+//! remembering where each byte of these tokens comes from, so that errors found while parsing or
+//! building the PTB are reported at their location in the script files.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter::Peekable,
+    path::{Path, PathBuf},
+    str::CharIndices,
+};
+
+use crate::err;
+
+use super::{
+    error::{build_error_reports, build_file_error_report, PTBError, Span},
+    ptb::to_source_string,
+};
+
+/// Includes a script file.
+pub const FILE: &str = "--file";
+/// Passes a parameter to the script files, as `name=value`.
+pub const ARG: &str = "--arg";
+
+/// A PTB assembled from the command line and the script files it includes.
+pub struct PTBScript {
+    /// The command line (the first source, rendered as by [to_source_string]), followed by the
+    /// script files in the order they were included.
+    sources: Vec<Source>,
+    tokens: Vec<String>,
+    origins: Vec<Origin>,
+}
+
+struct Source {
+    /// The path of a script file, or `None` for the command line.
+    name: Option<String>,
+    contents: String,
+}
+
+/// Where a token comes from.
+struct Origin {
+    /// Offset of the token in the input of the lexer, which separates tokens with one character.
+    offset: usize,
+    source: usize,
+    /// The location of the whole token in its source.
+    span: Span,
+    /// The location in the source of the character each byte of the token was produced by.
+    bytes: Vec<Span>,
+}
+
+/// A word of a script file, after unquoting and parameter substitution.
+struct Word {
+    text: String,
+    span: Span,
+    bytes: Vec<Span>,
+    /// Whether the word is written as is in the script, without quotes, escapes or parameters.
+    /// Only such words can be directives.
+    literal: bool,
+}
+
+struct Expander {
+    script: PTBScript,
+    params: BTreeMap<String, String>,
+    used: BTreeSet<String>,
+    /// Errors, with the source they are located in.
+    errors: Vec<(usize, PTBError)>,
+}
+
+impl PTBScript {
+    /// Expands the script files included by the command line `args`, substituting the parameters
+    /// passed with `--arg`. Without `--file`, the script is the command line itself. Errors are
+    /// returned rendered.
+    pub fn expand(args: Vec<String>) -> Result<Self, Vec<miette::Report>> {
+        let cmdline = Source {
+            name: None,
+            contents: to_source_string(args.clone()),
+        };
+        let mut expander = Expander {
+            script: PTBScript {
+                sources: vec![cmdline],
+                tokens: vec![],
+                origins: vec![],
+            },
+            params: BTreeMap::new(),
+            used: BTreeSet::new(),
+            errors: vec![],
+        };
+
+        // Spans of the arguments in the command line source.
+        let mut spans = Vec::with_capacity(args.len());
+        let mut offset = 0;
+        for arg in &args {
+            spans.push(Span {
+                start: offset,
+                end: offset + arg.len(),
+            });
+            offset += arg.len() + 1;
+        }
+
+        // Parameters are collected first, so that they can be passed anywhere on the command line.
+        // Directives are only recognized in command position: the operand of a directive is
+        // never a directive itself, even if it looks like one.
+        let mut param_spans = BTreeMap::new();
+        let mut rest = vec![];
+        let mut ix = 0;
+        while ix < args.len() {
+            if args[ix] == FILE {
+                rest.extend([ix, ix + 1].into_iter().filter(|i| *i < args.len()));
+                ix += 2;
+                continue;
+            }
+            if args[ix] != ARG {
+                rest.push(ix);
+                ix += 1;
+                continue;
+            }
+
+            let Some(param) = args.get(ix + 1) else {
+                expander.error(0, err!(spans[ix], "Expected a parameter after {ARG}"));
+                break;
+            };
+            let span = spans[ix].widen(spans[ix + 1]);
+            match param.split_once('=') {
+                Some((name, value)) if is_param_name(name) => {
+                    if expander
+                        .params
+                        .insert(name.to_string(), value.to_string())
+                        .is_some()
+                    {
+                        expander.error(0, err!(span, "Parameter '{name}' is passed twice"));
+                    }
+                    param_spans.insert(name.to_string(), span);
+                }
+                _ => expander.error(
+                    0,
+                    err!(spans[ix + 1] => help: {
+                        "Parameters are passed as {ARG} name=value, where the name is made of \
+                        letters, digits, '_' and '-'"
+                    }, "Invalid parameter"),
+                ),
+            }
+            ix += 2;
+        }
+
+        let mut rest = rest.into_iter();
+        while let Some(ix) = rest.next() {
+            if args[ix] != FILE {
+                let bytes = (spans[ix].start..spans[ix].end)
+                    .map(|i| Span {
+                        start: i,
+                        end: i + 1,
+                    })
+                    .collect();
+                expander.push(args[ix].clone(), 0, spans[ix], bytes);
+                continue;
+            }
+
+            let Some(path) = rest.next() else {
+                expander.error(0, err!(spans[ix], "Expected a script file after {FILE}"));
+                break;
+            };
+            expander.include(Path::new(&args[path]), 0, spans[path], &mut vec![]);
+        }
+
+        if expander.errors.is_empty() {
+            for (name, span) in param_spans {
+                if !expander.used.contains(&name) {
+                    expander.error(
+                        0,
+                        err!(span, "Parameter '{name}' is not used by the script"),
+                    );
+                }
+            }
+        }
+
+        let Expander { script, errors, .. } = expander;
+        if errors.is_empty() {
+            Ok(script)
+        } else {
+            Err(errors
+                .into_iter()
+                .map(|(source, error)| script.report(source, error))
+                .collect())
+        }
+    }
+
+    /// The tokens of the PTB, as they would have been received from the shell.
+    pub fn tokens(&self) -> impl Iterator<Item = &str> + Clone {
+        self.tokens.iter().map(String::as_str)
+    }
+
+    /// Renders errors found by the lexer, parser or builder, pointing at their location in the
+    /// command line or script files.
+    pub fn error_reports(&self, errors: Vec<PTBError>) -> Vec<miette::Report> {
+        errors
+            .into_iter()
+            .map(|mut error| {
+                let (source, span) = self.locate(error.span);
+                error.span = span;
+                self.report(source, error)
+            })
+            .collect()
+    }
+
+    fn report(&self, source: usize, error: PTBError) -> miette::Report {
+        let Source { name, contents } = &self.sources[source];
+        match name {
+            Some(name) => build_file_error_report(name, contents, error),
+            None => build_error_reports(contents, vec![error]).remove(0),
+        }
+    }
+
+    /// Maps a span in the input of the lexer to the source and span it was produced from.
+    fn locate(&self, span: Span) -> (usize, Span) {
+        let find = |offset: usize| {
+            let ix = self.origins.partition_point(|o| o.offset <= offset);
+            &self.origins[ix.saturating_sub(1)]
+        };
+
+        if self.origins.is_empty() {
+            return (0, Span { start: 0, end: 0 });
+        }
+
+        let origin = find(span.start);
+        let start = origin.position(span.start - origin.offset, |b| b.start);
+        let end = if span.end > span.start {
+            let last = find(span.end - 1);
+            if last.source == origin.source {
+                last.position(span.end - 1 - last.offset, |b| b.end)
+            } else {
+                origin.span.end
+            }
+        } else {
+            start
+        };
+
+        (
+            origin.source,
+            Span {
+                start,
+                end: end.max(start),
+            },
+        )
+    }
+}
+
+impl Origin {
+    /// Position in the source of the byte at `offset` in the token, or of the end of the token if
+    /// the offset is past it.
+    fn position(&self, offset: usize, side: impl Fn(&Span) -> usize) -> usize {
+        self.bytes.get(offset).map_or(self.span.end, side)
+    }
+}
+
+impl Expander {
+    fn error(&mut self, source: usize, error: PTBError) {
+        self.errors.push((source, error));
+    }
+
+    fn push(&mut self, token: String, source: usize, span: Span, bytes: Vec<Span>) {
+        let offset = self
+            .script
+            .origins
+            .last()
+            .zip(self.script.tokens.last())
+            .map_or(0, |(o, t)| o.offset + t.len() + 1);
+        self.script.tokens.push(token);
+        self.script.origins.push(Origin {
+            offset,
+            source,
+            span,
+            bytes,
+        });
+    }
+
+    /// Expands the script file at `path`, included from `span` in the source `from`. `stack`
+    /// holds the files being expanded.
+    fn include(&mut self, path: &Path, from: usize, span: Span, stack: &mut Vec<PathBuf>) {
+        let contents = match path.canonicalize().and_then(|p| {
+            let contents = std::fs::read_to_string(&p)?;
+            Ok((p, contents))
+        }) {
+            Ok((canonical, contents)) => {
+                if stack.contains(&canonical) {
+                    self.error(
+                        from,
+                        err!(span, "Script file '{}' includes itself", path.display()),
+                    );
+                    return;
+                }
+                stack.push(canonical);
+                contents
+            }
+            Err(e) => {
+                self.error(
+                    from,
+                    err!(span, "Could not read script file '{}': {e}", path.display()),
+                );
+                return;
+            }
+        };
+
+        let source = self.script.sources.len();
+        let words = match split_words(&contents, &self.params, &mut self.used) {
+            Ok(words) => words,
+            Err(errors) => {
+                for e in errors {
+                    self.error(source, e);
+                }
+                vec![]
+            }
+        };
+        self.script.sources.push(Source {
+            name: Some(path.display().to_string()),
+            contents,
+        });
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let mut words = words.into_iter();
+        let mut previous: Option<String> = None;
+        while let Some(word) = words.next() {
+            match word.text.as_str() {
+                FILE if word.literal => match words.next() {
+                    Some(file) => self.include(&dir.join(&file.text), source, file.span, stack),
+                    None => self.error(
+                        source,
+                        err!(word.span, "Expected a script file after {FILE}"),
+                    ),
+                },
+
+                ARG if word.literal => self.error(
+                    source,
+                    err!(word.span => help: {
+                        "Pass parameters to the script on the command line"
+                    }, "Parameters cannot be set in script files"),
+                ),
+
+                // Package paths are relative to the script, rather than to the current directory.
+                text if matches!(previous.as_deref(), Some("--publish" | "--upgrade"))
+                    && Path::new(text).is_relative() =>
+                {
+                    let text = dir.join(text).display().to_string();
+                    let bytes = vec![word.span; text.len()];
+                    self.push(text, source, word.span, bytes);
+                }
+
+                _ => {
+                    previous = Some(word.text.clone());
+                    self.push(word.text, source, word.span, word.bytes);
+                    continue;
+                }
+            }
+            previous = None;
+        }
+
+        stack.pop();
+    }
+}
+
+fn is_param_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Splits the contents of a script file into words, following the quoting rules of a POSIX shell
+/// and substituting `${name}` with the value of parameter `name`.
+fn split_words(
+    contents: &str,
+    params: &BTreeMap<String, String>,
+    used: &mut BTreeSet<String>,
+) -> Result<Vec<Word>, Vec<PTBError>> {
+    WordSplitter {
+        contents,
+        chars: contents.char_indices().peekable(),
+        params,
+        used,
+        errors: vec![],
+    }
+    .split()
+}
+
+struct WordSplitter<'a> {
+    contents: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    params: &'a BTreeMap<String, String>,
+    used: &'a mut BTreeSet<String>,
+    errors: Vec<PTBError>,
+}
+
+impl<'a> WordSplitter<'a> {
+    fn split(mut self) -> Result<Vec<Word>, Vec<PTBError>> {
+        let mut words = vec![];
+        while let Some(&(start, c)) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.chars.next();
+            } else if c == '#' {
+                while self.chars.next_if(|(_, c)| *c != '\n').is_some() {}
+            } else if let Some(word) = self.word(start) {
+                words.push(word);
+            }
+        }
+
+        if self.errors.is_empty() {
+            Ok(words)
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    /// Consumes the word starting at `start`. Returns `None` if it turns out to be empty, which
+    /// happens for line continuations between words.
+    fn word(&mut self, start: usize) -> Option<Word> {
+        let mut word = Word {
+            text: String::new(),
+            span: Span { start, end: start },
+            bytes: vec![],
+            literal: true,
+        };
+        let mut quoted = false;
+
+        while let Some((ix, c)) = self.chars.next_if(|(_, c)| !c.is_whitespace()) {
+            if matches!(c, '\'' | '"' | '\\' | '$') {
+                word.literal = false;
+            }
+            match c {
+                '\'' => {
+                    quoted = true;
+                    self.quoted(&mut word, ix, '\'');
+                }
+                '"' => {
+                    quoted = true;
+                    self.quoted(&mut word, ix, '"');
+                }
+                '\\' => match self.chars.next() {
+                    // Line continuation
+                    Some((_, '\n')) => (),
+                    Some((jx, c)) => {
+                        push(&mut word, c, char_span(ix, '\\').widen(char_span(jx, c)))
+                    }
+                    None => push(&mut word, '\\', char_span(ix, '\\')),
+                },
+                '$' => self.param(&mut word, ix),
+                c => push(&mut word, c, char_span(ix, c)),
+            }
+        }
+
+        word.span.end = self.chars.peek().map_or(self.contents.len(), |(ix, _)| *ix);
+        (quoted || !word.text.is_empty()).then_some(word)
+    }
+
+    /// Consumes a string quoted with `quote`, whose opening quote is at `start`. Only double
+    /// quoted strings support escapes and parameters.
+    fn quoted(&mut self, word: &mut Word, start: usize, quote: char) {
+        loop {
+            match self.chars.next() {
+                Some((_, c)) if c == quote => return,
+                Some((ix, '\\')) if quote == '"' => match self.chars.peek().copied() {
+                    Some((_, '\n')) => {
+                        self.chars.next();
+                    }
+                    Some((jx, c @ ('$' | '`' | '"' | '\\'))) => {
+                        self.chars.next();
+                        push(word, c, char_span(ix, '\\').widen(char_span(jx, c)));
+                    }
+                    _ => push(word, '\\', char_span(ix, '\\')),
+                },
+                Some((ix, '$')) if quote == '"' => self.param(word, ix),
+                Some((ix, c)) => push(word, c, char_span(ix, c)),
+                None => {
+                    let span = Span {
+                        start,
+                        end: self.contents.len(),
+                    };
+                    self.errors.push(err!(span, "Unterminated quote"));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Substitutes the parameter whose `$` is at `start`, if it is followed by `{`.
+    fn param(&mut self, word: &mut Word, start: usize) {
+        if self.chars.next_if(|(_, c)| *c == '{').is_none() {
+            push(word, '$', char_span(start, '$'));
+            return;
+        }
+
+        let Some(end) = self.contents[start..].find('}').map(|end| start + end) else {
+            let span = Span {
+                start,
+                end: self.contents.len(),
+            };
+            self.errors.push(err!(span, "Unterminated parameter"));
+            while self.chars.next().is_some() {}
+            return;
+        };
+        while self.chars.next_if(|(ix, _)| *ix <= end).is_some() {}
+
+        let name = &self.contents[start + 2..end];
+        let span = Span {
+            start,
+            end: end + 1,
+        };
+        match self.params.get(name) {
+            Some(value) => {
+                self.used.insert(name.to_string());
+                word.text.push_str(value);
+                word.bytes.extend(std::iter::repeat(span).take(value.len()));
+            }
+            None => self.errors.push(err!(span => help: {
+                "Pass a value for it with {ARG} {name}=<VALUE>"
+            }, "Missing value for parameter '{name}'")),
+        }
+    }
+}
+
+/// Appends `c` to `word`, as produced by the characters at `span`.
+fn push(word: &mut Word, c: char, span: Span) {
+    word.text.push(c);
+    word.bytes
+        .extend(std::iter::repeat(span).take(c.len_utf8()));
+}
+
+fn char_span(ix: usize, c: char) -> Span {
+    Span {
+        start: ix,
+        end: ix + c.len_utf8(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(contents: &str, params: &[(&str, &str)]) -> Vec<String> {
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        split_words(contents, &params, &mut BTreeSet::new())
+            .unwrap_or_else(|errors| panic!("{errors:?}"))
+            .into_iter()
+            .map(|w| w.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split(
+                "# comment\n--assign x \"vector[1, 2]\" # trailing\n--assign y '\"a b\"' \\\n z",
+                &[]
+            ),
+            vec![
+                "--assign",
+                "x",
+                "vector[1, 2]",
+                "--assign",
+                "y",
+                "\"a b\"",
+                "z"
+            ],
+        );
+        assert_eq!(
+            split("a\\ b \"\\$\\\"\" c#d", &[]),
+            vec!["a b", "$\"", "c#d"]
+        );
+    }
+
+    #[test]
+    fn test_split_words_params() {
+        let params = [("amount", "1000"), ("to", "@0x6")];
+        assert_eq!(
+            split(
+                "--split-coins gas [${amount}] --transfer-objects [x] ${to} '${to}' \"${to}\" $to",
+                &params
+            ),
+            vec![
+                "--split-coins",
+                "gas",
+                "[1000]",
+                "--transfer-objects",
+                "[x]",
+                "@0x6",
+                "${to}",
+                "@0x6",
+                "$to"
+            ],
+        );
+
+        let mut used = BTreeSet::new();
+        let errors = split_words("${amount} ${missing} 'open", &BTreeMap::new(), &mut used)
+            .err()
+            .unwrap();
+        let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Missing value for parameter 'amount'",
+                "Missing value for parameter 'missing'",
+                "Unterminated quote",
+            ]
+        );
+        assert_eq!(errors[1].span, Span { start: 10, end: 20 });
+    }
+
+    #[test]
+    fn test_expand_script_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.ptb");
+        std::fs::create_dir(dir.path().join("lib")).unwrap();
+        std::fs::write(
+            &main,
+            "# Split and transfer\n--file lib/split.ptb\n--transfer-objects [coins] @${to}\n--publish pkg",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("lib/split.ptb"),
+            "--split-coins gas [${amount}]\n--assign coins",
+        )
+        .unwrap();
+
+        let args = |extra: &[&str]| {
+            [FILE, main.to_str().unwrap(), "--dry-run"]
+                .iter()
+                .chain(extra)
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let script = PTBScript::expand(args(&[ARG, "amount=1000", ARG, "to=alice"]))
+            .unwrap_or_else(|e| panic!("{e:?}"));
+        assert_eq!(
+            script.tokens().collect::<Vec<_>>(),
+            vec![
+                "--split-coins".to_string(),
+                "gas".to_string(),
+                "[1000]".to_string(),
+                "--assign".to_string(),
+                "coins".to_string(),
+                "--transfer-objects".to_string(),
+                "[coins]".to_string(),
+                "@alice".to_string(),
+                "--publish".to_string(),
+                dir.path().join("pkg").display().to_string(),
+                "--dry-run".to_string(),
+            ]
+        );
+
+        // `[1000]` comes from `[${amount}]` in the included file.
+        let offset = "--split-coins gas ".len();
+        let (source, span) = script.locate(Span {
+            start: offset,
+            end: offset + "[1000]".len(),
+        });
+        assert_eq!(
+            script.sources[source].name,
+            Some(dir.path().join("lib/split.ptb").display().to_string())
+        );
+        assert_eq!(
+            &script.sources[source].contents[span.start..span.end],
+            "[${amount}]"
+        );
+
+        // Missing and unused parameters are errors.
+        assert!(PTBScript::expand(args(&[ARG, "amount=1000"])).is_err());
+        assert!(PTBScript::expand(args(&[ARG, "amount=1", ARG, "to=a", ARG, "x=1"])).is_err());
+    }
+
+    #[test]
+    fn test_expand_include_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("loop.ptb");
+        std::fs::write(&script, "--file loop.ptb").unwrap();
+        let args = vec![FILE.to_string(), script.display().to_string()];
+        assert!(PTBScript::expand(args).is_err());
+    }
+
+    #[test]
+    fn test_expand_directives_in_command_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.ptb");
+        std::fs::write(
+            &main,
+            "--assign a '--file' --assign b \"--arg\" --assign c \\--file --assign d ${flag}",
+        )
+        .unwrap();
+
+        // Quoted, escaped or substituted words are never directives.
+        let args = vec![
+            FILE.to_string(),
+            main.display().to_string(),
+            ARG.to_string(),
+            "flag=--file".to_string(),
+        ];
+        let script = PTBScript::expand(args).unwrap_or_else(|e| panic!("{e:?}"));
+        assert_eq!(
+            script.tokens().collect::<Vec<_>>(),
+            vec![
+                "--assign", "a", "--file", "--assign", "b", "--arg", "--assign", "c", "--file",
+                "--assign", "d", "--file"
+            ]
+        );
+
+        // Neither is the operand of a directive.
+        let args = vec![FILE.to_string(), ARG.to_string(), "x=1".to_string()];
+        let errors = PTBScript::expand(args).err().unwrap();
+        assert_eq!(errors.len(), 1);
+        let label = errors[0].labels().unwrap().next().unwrap();
+        assert!(
+            label
+                .label()
+                .unwrap()
+                .starts_with("Could not read script file '--arg'"),
+            "{:?}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_expand_command_line() {
+        let args: Vec<String> = ["--assign", "x", "1", "--gas-budget", "1000"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let script = PTBScript::expand(args.clone()).unwrap_or_else(|e| panic!("{e:?}"));
+        assert_eq!(script.tokens().collect::<Vec<_>>(), args);
+        let span = Span { start: 11, end: 14 };
+        assert_eq!(script.locate(span), (0, span));
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     client_ptb::{
-        ast::{GAS_BUDGET, GAS_COIN, JSON, SUMMARY, WARN_SHADOWS},
+        ast::{GAS_BUDGET, GAS_COIN, GAS_COINS, GAS_SPONSOR, JSON, SUMMARY, WARN_SHADOWS},
         ptb::PTBPreview,
     },
     sp,
@@ -30,6 +30,18 @@ impl<'a> Display for PTBPreview<'a> {
         if let Some(gas_coin_id) = self.program_metadata.gas_object_id {
             builder.push_record([GAS_COIN, gas_coin_id.value.to_string().as_str()]);
         }
+        if let Some(gas_coins) = &self.program_metadata.gas_coins {
+            let gas_coins = gas_coins
+                .value
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            builder.push_record([GAS_COINS, format!("[{gas_coins}]").as_str()]);
+        }
+        if let Some(gas_sponsor) = &self.program_metadata.gas_sponsor {
+            builder.push_record([GAS_SPONSOR, gas_sponsor.value.to_string().as_str()]);
+        }
         if self.program_metadata.json_set {
             builder.push_record([JSON, "true"]);
         }
@@ -5,25 +5,32 @@ use std::collections::BTreeSet;
 use std::io::Read;
 use std::net::SocketAddr;
 use std::os::unix::prelude::FileExt;
-use std::{fmt::Write, fs::read_dir, path::PathBuf, str, thread, time::Duration};
+use std::{
+    fmt::Write,
+    fs::read_dir,
+    path::{Path, PathBuf},
+    str, thread,
+    time::Duration,
+};
 
 use std::env;
 #[cfg(not(msim))]
 use std::str::FromStr;
 
 use expect_test::expect;
+use fastcrypto::encoding::{Base64, Encoding};
 use move_package::{lock_file::schema::ManagedPackage, BuildConfig as MoveBuildConfig};
 use serde_json::json;
-use sui::client_ptb::ptb::PTB;
+use sui::client_ptb::{parser::ProgramParser, ptb::PTB, script::PTBScript};
 use sui::key_identity::{get_identity_address, KeyIdentity};
 use sui::sui_commands::IndexerArgs;
 use sui_sdk::SuiClient;
 use sui_test_transaction_builder::batch_make_transfer_transactions;
 use sui_types::object::Owner;
 use sui_types::transaction::{
-    TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
-    TEST_ONLY_GAS_UNIT_FOR_PUBLISH, TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
-    TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    SenderSignedData, TransactionData, TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+    TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
 };
 use tokio::time::sleep;
 
@@ -53,7 +60,8 @@ use sui_swarm_config::genesis_config::{AccountConfig, GenesisConfig};
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{
-    Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
+    AccountKeyPair, Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair,
+    SuiSignatureInner,
 };
 use sui_types::error::SuiObjectResponseError;
 use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
@@ -2864,6 +2872,269 @@ async fn test_execute_signed_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_sponsored_tx_signatures() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let sender = test_cluster.get_address_0();
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let gas = context
+        .get_all_gas_objects_owned_by_address(sponsor)
+        .await?[0];
+    let tx_data = TransactionData::new_transfer_sui_allow_sponsor(
+        sponsor,
+        sender,
+        Some(1),
+        gas,
+        rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        rgp,
+        sponsor,
+    );
+    let tx_bytes = Base64::encode(bcs::to_bytes(&tx_data)?);
+    let encode = |signed_tx: &SenderSignedData| Base64::encode(bcs::to_bytes(signed_tx).unwrap());
+
+    // Each party signs the transaction on its own.
+    let mut signed = vec![];
+    for signer in [sender, sponsor] {
+        let SuiClientCommandResult::PartiallySignedTransaction {
+            signed_tx,
+            missing_signers,
+        } = (SuiClientCommands::AddSignature {
+            tx_bytes: Some(tx_bytes.clone()),
+            signed_tx_bytes: None,
+            address: Some(KeyIdentity::Address(signer)),
+        })
+        .execute(context)
+        .await?
+        else {
+            panic!("Expected a partially signed transaction");
+        };
+        assert_eq!(missing_signers.len(), 1);
+        assert_ne!(missing_signers[0], signer);
+        signed.push(encode(&signed_tx));
+    }
+
+    // The same signer cannot sign twice.
+    assert!(SuiClientCommands::AddSignature {
+        tx_bytes: None,
+        signed_tx_bytes: Some(signed[0].clone()),
+        address: Some(KeyIdentity::Address(sender)),
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    // Adding the missing signature completes the transaction...
+    let SuiClientCommandResult::SerializedSignedTransaction(countersigned) =
+        SuiClientCommands::AddSignature {
+            tx_bytes: None,
+            signed_tx_bytes: Some(signed[0].clone()),
+            address: None,
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a fully signed transaction");
+    };
+    assert_eq!(countersigned.tx_signatures().len(), 2);
+
+    // ...and so does combining the signatures of both parties.
+    let SuiClientCommandResult::SerializedSignedTransaction(combined) =
+        SuiClientCommands::CombineSignedTx {
+            signed_tx_bytes: signed,
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a fully signed transaction");
+    };
+    assert_eq!(combined.tx_signatures().len(), 2);
+
+    let SuiClientCommandResult::TransactionBlock(response) =
+        SuiClientCommands::ExecuteCombinedSignedTx {
+            signed_tx_bytes: encode(&combined),
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a transaction block");
+    };
+    assert!(response.status_ok().unwrap());
+    Ok(())
+}
+
+#[sim_test]
+async fn test_ptb_gas_sponsor() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let gas = context
+        .get_all_gas_objects_owned_by_address(sponsor)
+        .await?[0];
+    let recipient = context.active_address()?;
+
+    // The keystore holds the keys of both the sender and the sponsor, so the PTB is executed.
+    let args = shlex::split(&format!(
+        "--split-coins gas [1000] --assign new_coin --transfer-objects [new_coin] @{recipient} \
+         --gas-coins [@{}] --gas-sponsor @{sponsor} --gas-budget 50000000",
+        gas.0
+    ))
+    .unwrap();
+    SuiClientCommands::PTB(PTB { args })
+        .execute(context)
+        .await?;
+
+    let gas_owner = context.get_object_owner(&gas.0).await?;
+    assert_eq!(gas_owner, sponsor);
+    Ok(())
+}
+
+#[sim_test]
+async fn test_ptb_gas_sponsor_without_key() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let funder = test_cluster.get_address_1();
+    let (sponsor, sponsor_key): (_, AccountKeyPair) = get_key_pair();
+
+    // Fund a sponsor whose key is not in the keystore.
+    let gas = test_cluster
+        .wallet
+        .get_all_gas_objects_owned_by_address(funder)
+        .await?[0];
+    let tx_data = test_cluster
+        .test_transaction_builder_with_gas_object(funder, gas)
+        .await
+        .transfer_sui(None, sponsor)
+        .build();
+    test_cluster.sign_and_execute_transaction(&tx_data).await;
+
+    let context = &mut test_cluster.wallet;
+    let recipient = context.active_address()?;
+    let args = shlex::split(&format!(
+        "--split-coins gas [1000] --assign new_coin --transfer-objects [new_coin] @{recipient} \
+         --gas-coins [@{}] --gas-sponsor @{sponsor} --gas-budget 50000000",
+        gas.0
+    ))
+    .unwrap();
+
+    // The sender signs, and the transaction is handed over to the sponsor...
+    let SuiClientCommandResult::PartiallySignedTransaction {
+        signed_tx,
+        missing_signers,
+    } = SuiClientCommands::PTB(PTB { args })
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a partially signed transaction");
+    };
+    assert_eq!(missing_signers, vec![sponsor]);
+    assert_eq!(signed_tx.tx_signatures().len(), 1);
+
+    // ...who adds their signature...
+    context
+        .config
+        .keystore
+        .add_key(None, SuiKeyPair::Ed25519(sponsor_key))?;
+    let SuiClientCommandResult::SerializedSignedTransaction(signed_tx) =
+        SuiClientCommands::AddSignature {
+            tx_bytes: None,
+            signed_tx_bytes: Some(Base64::encode(bcs::to_bytes(&signed_tx)?)),
+            address: Some(KeyIdentity::Address(sponsor)),
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a fully signed transaction");
+    };
+    assert_eq!(signed_tx.tx_signatures().len(), 2);
+
+    // ...and executes it.
+    let SuiClientCommandResult::TransactionBlock(response) =
+        SuiClientCommands::ExecuteCombinedSignedTx {
+            signed_tx_bytes: Base64::encode(bcs::to_bytes(&signed_tx)?),
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a transaction block");
+    };
+    assert!(response.status_ok().unwrap());
+    assert_eq!(context.get_object_owner(&gas.0).await?, sponsor);
+    Ok(())
+}
+
+#[sim_test]
+async fn test_ptb_script_file() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let context = &mut test_cluster.wallet;
+    let recipient = context.active_address()?;
+
+    let dir = tempfile::tempdir()?;
+    std::fs::write(
+        dir.path().join("split.ptb"),
+        "# Split a coin off the gas coin\n--split-coins gas [${amount}]\n--assign coins\n",
+    )?;
+    let main = dir.path().join("main.ptb");
+    std::fs::write(
+        &main,
+        "--file split.ptb\n--transfer-objects [coins.0] @${to}\n",
+    )?;
+    let bad = dir.path().join("bad.ptb");
+    std::fs::write(
+        &bad,
+        "--file split.ptb\n--move-call sui::coin::${function} coins.0\n",
+    )?;
+
+    let args = |script: &Path, extra: &[String]| {
+        let mut args: Vec<String> = vec![
+            "--file".to_string(),
+            script.display().to_string(),
+            "--arg".to_string(),
+            "amount=1000".to_string(),
+            "--gas-budget".to_string(),
+            "50000000".to_string(),
+            "--check".to_string(),
+        ];
+        args.extend_from_slice(extra);
+        args
+    };
+
+    // The script is expanded with its parameters, and checked without being executed.
+    let result = SuiClientCommands::PTB(PTB {
+        args: args(&main, &["--arg".to_string(), format!("to={recipient}")]),
+    })
+    .execute(context)
+    .await?;
+    assert!(matches!(result, SuiClientCommandResult::NoOutput));
+
+    // A PTB that fails to build is rejected...
+    let bad_args = args(&bad, &["--arg".to_string(), "function=nope".to_string()]);
+    assert!(SuiClientCommands::PTB(PTB {
+        args: bad_args.clone()
+    })
+    .execute(context)
+    .await
+    .is_err());
+
+    // ...with an error pointing at the script file it comes from.
+    let script = PTBScript::expand(bad_args).unwrap_or_else(|e| panic!("{e:?}"));
+    let (program, _) = ProgramParser::new(script.tokens())
+        .and_then(|parser| parser.parse().map_err(|mut e| e.remove(0)))
+        .unwrap_or_else(|e| panic!("{e:?}"));
+    let (result, _) = PTB::build_ptb(program, context, context.get_client().await?).await;
+    let reports = script.error_reports(result.unwrap_err());
+    let label = reports[0].labels().unwrap().next().unwrap();
+    let location = reports[0]
+        .source_code()
+        .unwrap()
+        .read_span(label.inner(), 0, 0)
+        .unwrap();
+    assert_eq!(location.name(), Some(bad.display().to_string().as_str()));
+    let snippet = std::str::from_utf8(location.data())?;
+    assert!(snippet.contains("${function}"), "{snippet}");
+    Ok(())
+}
+
 #[sim_test]
 async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -72,6 +72,37 @@ pub struct Test {
     // Enable tracing for tests
     #[clap(long = "trace-execution", value_name = "PATH")]
     pub trace_execution: Option<Option<String>>,
+
+    /// Run a single test under an interactive, source-level debugger. The test is selected by its
+    /// name, or by a part of its fully qualified (<module_name>::<fn_name>) name. Requires a binary
+    /// built with the `tracing` feature.
+    #[clap(long = "debug", value_name = "TEST_NAME")]
+    pub debug: Option<String>,
+
+    /// Profile the gas used by each test, saving the profiles to PATH (`gas_profiles` by default):
+    /// one speedscope profile per test, a flamegraph of all tests, and a summary of the gas used
+    /// per function and per instruction category. Requires a binary built with the `tracing`
+    /// feature.
+    #[clap(long = "profile", value_name = "PATH")]
+    pub profile: Option<Option<String>>,
+
+    /// Compare the gas profiles against the summary file of an earlier profiled run, and fail if
+    /// any test uses more gas than it did. If there are random tests, `--seed` must be set to the
+    /// seed of the earlier run, so that they are run with the same arguments.
+    #[clap(
+        long = "profile-baseline",
+        value_name = "SUMMARY_FILE",
+        requires = "profile"
+    )]
+    pub profile_baseline: Option<String>,
+
+    /// Percentage by which the gas used by a test may go up before it is reported as a regression.
+    #[clap(
+        long = "profile-threshold",
+        value_name = "PERCENT",
+        default_value = "0"
+    )]
+    pub profile_threshold: f64,
 }
 
 impl Test {
@@ -113,6 +144,10 @@ impl Test {
             seed,
             rand_num_iters,
             trace_execution,
+            debug,
+            profile,
+            profile_baseline,
+            profile_threshold,
         } = self;
         UnitTestingConfig {
             gas_limit,
@@ -124,6 +159,10 @@ impl Test {
             seed,
             rand_num_iters,
             trace_execution,
+            debug,
+            profile,
+            profile_baseline,
+            profile_threshold,
             ..UnitTestingConfig::default_with_bound(None)
         }
     }
@@ -15,7 +15,9 @@ use crate::{
         *,
     },
     editions::Edition,
-    expansion, hlir, interface_generator, naming,
+    expansion, hlir, interface_generator,
+    linters::{custom_lints::CustomLints, LintLevel},
+    naming,
     parser::{self, comments::*, *},
     shared::{
         files::{FilesSourceText, MappedFiles},
@@ -300,6 +302,13 @@ impl Compiler {
         self
     }
 
+    /// Adds the known filters of `lints`, and the visitors of the lints that run at `level`.
+    pub fn add_custom_lints(self, lints: &CustomLints, level: LintLevel) -> Self {
+        let (filter_attr_name, filters) = lints.known_filters();
+        self.add_custom_known_filters(filter_attr_name, filters)
+            .add_visitors(lints.linter_visitors(level))
+    }
+
     /// Sets the PackageConfig for files without a specified package
     pub fn set_default_config(mut self, config: PackageConfig) -> Self {
         assert!(self.default_config.is_none());
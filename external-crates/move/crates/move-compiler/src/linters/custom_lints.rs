@@ -0,0 +1,289 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! An extension point for lints that are not built into the compiler. A custom lint implements
+//! [CustomLint], checking the typed AST through the hooks of the typing visitor, and is
+//! registered in a [CustomLints] set, which is then added to a compiler with
+//! `Compiler::add_custom_lints`.
+//!
+//! Each registered lint is assigned its own diagnostic code, so its warnings can be suppressed by
+//! name with `#[allow(lint(<name>))]`, like those of the built-in lints.
+//!
+//! Lints implemented in Rust cannot be loaded at run time: to run them when building packages, a
+//! tool registers them with `move_package::package_hooks::register_custom_lints` and is rebuilt
+//! with them. Lints that only forbid or require calls can instead be declared in a rules file (see
+//! [super::rules]), which e.g. `sui move build --lint-rules <PATH>` loads without a rebuild.
+
+use std::sync::Arc;
+
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+
+use crate::{
+    command_line::compiler::Visitor,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        warning_filters::{WarningFilter, WarningFilters},
+        Diagnostic, DiagnosticReporter,
+    },
+    expansion::ast::ModuleIdent,
+    parser::ast::FunctionName,
+    shared::CompilationEnv,
+    sui_mode,
+    typing::{
+        ast as T,
+        visitor::{TypingVisitor, TypingVisitorContext},
+    },
+};
+
+use super::{LintLevel, LinterDiagnosticCategory, ALLOW_ATTR_CATEGORY, LINT_WARNING_PREFIX};
+
+/// A lint defined outside of the compiler. The `check_*` hooks are called while visiting the
+/// typed AST, and return `true` to skip visiting the children of the given item, as with the
+/// `visit_*_custom` functions of `TypingVisitorContext`.
+pub trait CustomLint: Send + Sync {
+    /// The name of the lint, used to suppress it with `#[allow(lint(<name>))]`.
+    fn name(&self) -> &str;
+
+    /// The message of the lint's warnings.
+    fn message(&self) -> &str;
+
+    /// The lowest lint level at which the lint runs.
+    fn level(&self) -> LintLevel {
+        LintLevel::Default
+    }
+
+    fn check_module(
+        &self,
+        _context: &mut LintContext,
+        _ident: ModuleIdent,
+        _mdef: &T::ModuleDefinition,
+    ) -> bool {
+        false
+    }
+
+    fn check_function(
+        &self,
+        _context: &mut LintContext,
+        _module: ModuleIdent,
+        _function_name: FunctionName,
+        _fdef: &T::Function,
+    ) -> bool {
+        false
+    }
+
+    fn check_exp(&self, _context: &mut LintContext, _exp: &T::Exp) -> bool {
+        false
+    }
+}
+
+/// Reports the warnings of a custom lint, respecting the warning filters in scope.
+pub struct LintContext<'a> {
+    env: &'a CompilationEnv,
+    reporter: DiagnosticReporter<'a>,
+    info: DiagnosticInfo,
+}
+
+/// A set of custom lints, each with its own diagnostic code.
+#[derive(Clone, Default)]
+pub struct CustomLints {
+    lints: Vec<RegisteredLint>,
+}
+
+#[derive(Clone)]
+struct RegisteredLint {
+    lint: Arc<dyn CustomLint>,
+    name: &'static str,
+    info: DiagnosticInfo,
+}
+
+struct CustomLintVisitor(RegisteredLint);
+
+struct Context<'a> {
+    lint: &'a dyn CustomLint,
+    lint_context: LintContext<'a>,
+}
+
+//**************************************************************************************************
+// impls
+//**************************************************************************************************
+
+impl LintContext<'_> {
+    pub fn env(&self) -> &CompilationEnv {
+        self.env
+    }
+
+    /// A warning of the lint, labelled with `msg` at `loc`.
+    pub fn diag(&self, loc: Loc, msg: impl ToString) -> Diagnostic {
+        Diagnostic::new(
+            self.info.clone(),
+            (loc, msg),
+            std::iter::empty::<(Loc, String)>(),
+            std::iter::empty::<String>(),
+        )
+    }
+
+    pub fn add_diag(&self, diag: Diagnostic) {
+        self.reporter.add_diag(diag);
+    }
+
+    /// Reports a warning of the lint, labelled with `msg` at `loc`.
+    pub fn report(&self, loc: Loc, msg: impl ToString) {
+        self.add_diag(self.diag(loc, msg));
+    }
+}
+
+impl CustomLints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lints.is_empty()
+    }
+
+    /// Registers `lint`. Fails if its name is already used by a built-in lint or another custom
+    /// lint, or if there are no diagnostic codes left.
+    pub fn add(&mut self, lint: impl CustomLint + 'static) -> anyhow::Result<()> {
+        let name = lint.name();
+        anyhow::ensure!(
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+            "Invalid custom lint name '{name}'. \
+            Names must be non-empty and contain only alphanumeric characters and underscores",
+        );
+        // Diagnostic infos and filter names are static, so they are leaked once per lint
+        let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        let message: &'static str = Box::leak(lint.message().to_owned().into_boxed_str());
+        self.register(Arc::new(lint), name, message)
+    }
+
+    /// Registers the lints of `other`, failing on the first one that cannot be registered.
+    pub fn extend(&mut self, other: &CustomLints) -> anyhow::Result<()> {
+        for registered in &other.lints {
+            let RegisteredLint { lint, name, info } = registered;
+            self.register(lint.clone(), *name, info.message())?;
+        }
+        Ok(())
+    }
+
+    fn register(
+        &mut self,
+        lint: Arc<dyn CustomLint>,
+        name: &'static str,
+        message: &'static str,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !is_built_in_lint(name),
+            "The custom lint name '{name}' is already used by a built-in lint",
+        );
+        anyhow::ensure!(
+            self.lints.iter().all(|registered| registered.name != name),
+            "Duplicate custom lint name '{name}'",
+        );
+        let Ok(code) = u8::try_from(self.lints.len() + 1) else {
+            anyhow::bail!("Too many custom lints, at most {} are supported", u8::MAX);
+        };
+        let info = custom(
+            LINT_WARNING_PREFIX,
+            Severity::Warning,
+            LinterDiagnosticCategory::Custom as u8,
+            code,
+            message,
+        );
+        self.lints.push(RegisteredLint { lint, name, info });
+        Ok(())
+    }
+
+    pub fn known_filters(&self) -> (Option<Symbol>, Vec<WarningFilter>) {
+        (
+            Some(ALLOW_ATTR_CATEGORY.into()),
+            self.lints
+                .iter()
+                .map(|registered| {
+                    WarningFilter::code(
+                        Some(LINT_WARNING_PREFIX),
+                        LinterDiagnosticCategory::Custom as u8,
+                        registered.info.code(),
+                        Some(registered.name),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    pub fn linter_visitors(&self, level: LintLevel) -> Vec<Visitor> {
+        self.lints
+            .iter()
+            .filter(|registered| runs_at(registered.lint.level(), level))
+            .map(|registered| CustomLintVisitor(registered.clone()).visitor())
+            .collect()
+    }
+}
+
+/// Whether a lint of level `lint_level` runs when linting at `level`.
+fn runs_at(lint_level: LintLevel, level: LintLevel) -> bool {
+    match level {
+        LintLevel::None => false,
+        LintLevel::Default => lint_level == LintLevel::Default,
+        LintLevel::All => lint_level != LintLevel::None,
+    }
+}
+
+fn is_built_in_lint(name: &str) -> bool {
+    let (_, filters) = super::known_filters();
+    let (_, sui_filters) = sui_mode::linters::known_filters();
+    filters
+        .iter()
+        .chain(&sui_filters)
+        .any(|filter| match filter {
+            WarningFilter::All(_) => false,
+            WarningFilter::Category { name: n, .. } | WarningFilter::Code { name: n, .. } => {
+                *n == Some(name)
+            }
+        })
+}
+
+impl TypingVisitor for CustomLintVisitor {
+    fn visit(&self, env: &CompilationEnv, program: &T::Program) {
+        let RegisteredLint { lint, info, .. } = &self.0;
+        let mut context = Context {
+            lint: lint.as_ref(),
+            lint_context: LintContext {
+                env,
+                reporter: env.diagnostic_reporter_at_top_level(),
+                info: info.clone(),
+            },
+        };
+        context.visit(program);
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn push_warning_filter_scope(&mut self, filters: WarningFilters) {
+        self.lint_context
+            .reporter
+            .push_warning_filter_scope(filters)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.lint_context.reporter.pop_warning_filter_scope()
+    }
+
+    fn visit_module_custom(&mut self, ident: ModuleIdent, mdef: &T::ModuleDefinition) -> bool {
+        self.lint.check_module(&mut self.lint_context, ident, mdef)
+    }
+
+    fn visit_function_custom(
+        &mut self,
+        module: ModuleIdent,
+        function_name: FunctionName,
+        fdef: &T::Function,
+    ) -> bool {
+        self.lint
+            .check_function(&mut self.lint_context, module, function_name, fdef)
+    }
+
+    fn visit_exp_custom(&mut self, exp: &T::Exp) -> bool {
+        self.lint.check_exp(&mut self.lint_context, exp)
+    }
+}
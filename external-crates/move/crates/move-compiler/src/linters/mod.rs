@@ -16,10 +16,12 @@ use crate::{
 pub mod abort_constant;
 pub mod combinable_comparisons;
 pub mod constant_naming;
+pub mod custom_lints;
 pub mod equal_operands;
 pub mod loop_without_exit;
 pub mod meaningless_math_operation;
 pub mod redundant_ref_deref;
+pub mod rules;
 pub mod self_assignment;
 pub mod unnecessary_conditional;
 pub mod unnecessary_unit;
@@ -44,6 +46,7 @@ pub enum LinterDiagnosticCategory {
     Suspicious,
     Deprecated,
     Style,
+    Custom = 98,
     Sui = 99,
 }
 
@@ -0,0 +1,477 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Declarative custom lints, for simple patterns that do not warrant writing a [CustomLint] by
+//! hand. Rules are read from a TOML file with one `[[lint]]` table per rule, for example
+//!
+//! ```toml
+//! [[lint]]
+//! name = "admin_cap_transfer"
+//! message = "'AdminCap' should not be freely transferable"
+//! forbid_call = "sui::transfer::public_transfer"
+//! type_argument = "my_package::admin::AdminCap"
+//!
+//! [[lint]]
+//! name = "entry_without_event"
+//! message = "entry function does not emit an event"
+//! require_call = "sui::event::emit"
+//! in_functions = "entry"
+//! level = "all"
+//! note = "Events are needed to index the actions of the protocol"
+//! ```
+//!
+//! A `forbid_call` rule warns on every call to the function, optionally only when one of its type
+//! arguments is the given type. A `require_call` rule warns on every function of the given kind
+//! (`entry`, `public` or `all`, `entry` by default) that never calls the function. Addresses are
+//! either named addresses or numerical values. Test code is not checked.
+//!
+//! These are the only custom lints that can be added to an existing binary, with `--lint-rules`.
+//! Other patterns need a [CustomLint] implementation, registered by a tool built with it (see
+//! [super::custom_lints]).
+
+use std::{fmt, path::Path};
+
+use anyhow::Context as _;
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+use serde::Deserialize;
+
+use crate::{
+    diagnostics::warning_filters::WarningFilters,
+    expansion::ast::{self as E, ModuleIdent, Visibility},
+    naming::ast as N,
+    parser::ast::{FunctionName, Identifier},
+    shared::NumericalAddress,
+    typing::{
+        ast::{self as T, UnannotatedExp_ as TE},
+        visitor::TypingVisitorContext,
+    },
+};
+
+use super::{
+    custom_lints::{CustomLint, CustomLints, LintContext},
+    LintLevel,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default, rename = "lint")]
+    lints: Vec<RuleDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleDefinition {
+    name: String,
+    message: String,
+    #[serde(default)]
+    level: RuleLevel,
+    note: Option<String>,
+    forbid_call: Option<String>,
+    type_argument: Option<String>,
+    require_call: Option<String>,
+    in_functions: Option<FunctionKind>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RuleLevel {
+    #[default]
+    Default,
+    All,
+}
+
+/// The functions checked by a `require_call` rule.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum FunctionKind {
+    #[default]
+    Entry,
+    Public,
+    All,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AddressPattern {
+    Name(Symbol),
+    Value(NumericalAddress),
+}
+
+/// A module member, e.g. a function or a type, as `<address>::<module>::<member>`.
+#[derive(Debug, Clone)]
+struct MemberPattern {
+    address: AddressPattern,
+    module: Symbol,
+    member: Symbol,
+}
+
+#[derive(Debug)]
+enum RuleKind {
+    ForbidCall {
+        function: MemberPattern,
+        type_argument: Option<MemberPattern>,
+    },
+    RequireCall {
+        function: MemberPattern,
+        in_functions: FunctionKind,
+    },
+}
+
+#[derive(Debug)]
+struct Rule {
+    name: String,
+    message: String,
+    level: LintLevel,
+    note: Option<String>,
+    kind: RuleKind,
+}
+
+/// Looks for a call to `function` in a function body.
+struct CallFinder<'a> {
+    function: &'a MemberPattern,
+    found: bool,
+}
+
+//**************************************************************************************************
+// entry
+//**************************************************************************************************
+
+/// Reads the lint rules in the file at `path`.
+pub fn load_rules(path: &Path) -> anyhow::Result<CustomLints> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Unable to read lint rules {}", path.display()))?;
+    parse_rules(&contents).with_context(|| format!("Invalid lint rules {}", path.display()))
+}
+
+pub fn parse_rules(contents: &str) -> anyhow::Result<CustomLints> {
+    let file: RulesFile = toml::from_str(contents)?;
+    let mut lints = CustomLints::new();
+    for definition in file.lints {
+        let name = definition.name.clone();
+        let rule = Rule::new(definition).with_context(|| format!("Invalid lint rule '{name}'"))?;
+        lints.add(rule)?;
+    }
+    Ok(lints)
+}
+
+//**************************************************************************************************
+// impls
+//**************************************************************************************************
+
+impl Rule {
+    fn new(definition: RuleDefinition) -> anyhow::Result<Self> {
+        let RuleDefinition {
+            name,
+            message,
+            level,
+            note,
+            forbid_call,
+            type_argument,
+            require_call,
+            in_functions,
+        } = definition;
+        let kind = match (forbid_call, require_call) {
+            (Some(function), None) => {
+                anyhow::ensure!(
+                    in_functions.is_none(),
+                    "'in_functions' can only be used with 'require_call'"
+                );
+                RuleKind::ForbidCall {
+                    function: function.parse()?,
+                    type_argument: type_argument.map(|ty| ty.parse()).transpose()?,
+                }
+            }
+            (None, Some(function)) => {
+                anyhow::ensure!(
+                    type_argument.is_none(),
+                    "'type_argument' can only be used with 'forbid_call'"
+                );
+                RuleKind::RequireCall {
+                    function: function.parse()?,
+                    in_functions: in_functions.unwrap_or_default(),
+                }
+            }
+            _ => anyhow::bail!("Expected exactly one of 'forbid_call' or 'require_call'"),
+        };
+        let level = match level {
+            RuleLevel::Default => LintLevel::Default,
+            RuleLevel::All => LintLevel::All,
+        };
+        Ok(Self {
+            name,
+            message,
+            level,
+            note,
+            kind,
+        })
+    }
+
+    fn report(&self, context: &LintContext, loc: Loc, msg: String) {
+        let mut diag = context.diag(loc, msg);
+        if let Some(note) = &self.note {
+            diag.add_note(note);
+        }
+        context.add_diag(diag);
+    }
+}
+
+impl CustomLint for Rule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn level(&self) -> LintLevel {
+        self.level
+    }
+
+    fn check_module(
+        &self,
+        _context: &mut LintContext,
+        _ident: ModuleIdent,
+        mdef: &T::ModuleDefinition,
+    ) -> bool {
+        mdef.attributes.is_test_or_test_only()
+    }
+
+    fn check_function(
+        &self,
+        context: &mut LintContext,
+        _module: ModuleIdent,
+        function_name: FunctionName,
+        fdef: &T::Function,
+    ) -> bool {
+        if fdef.attributes.is_test_or_test_only() || fdef.macro_.is_some() {
+            return true;
+        }
+        let RuleKind::RequireCall {
+            function,
+            in_functions,
+        } = &self.kind
+        else {
+            return false;
+        };
+        let T::FunctionBody_::Defined(seq) = &fdef.body.value else {
+            return true;
+        };
+        if !in_functions.includes(fdef) {
+            return true;
+        }
+        let mut finder = CallFinder {
+            function,
+            found: false,
+        };
+        finder.visit_seq(fdef.body.loc, seq);
+        if !finder.found {
+            let msg = format!("'{function_name}' does not call '{function}'");
+            self.report(context, function_name.loc(), msg);
+        }
+        true
+    }
+
+    fn check_exp(&self, context: &mut LintContext, exp: &T::Exp) -> bool {
+        let RuleKind::ForbidCall {
+            function,
+            type_argument,
+        } = &self.kind
+        else {
+            return false;
+        };
+        let TE::ModuleCall(call) = &exp.exp.value else {
+            return false;
+        };
+        if !function.matches_call(call) {
+            return false;
+        }
+        let msg = match type_argument {
+            None => format!("Call to '{function}'"),
+            Some(ty) if call.type_arguments.iter().any(|t| ty.matches_type(t)) => {
+                format!("Call to '{function}' with type argument '{ty}'")
+            }
+            Some(_) => return false,
+        };
+        self.report(context, exp.exp.loc, msg);
+        false
+    }
+}
+
+impl FunctionKind {
+    fn includes(self, fdef: &T::Function) -> bool {
+        match self {
+            FunctionKind::Entry => fdef.entry.is_some(),
+            FunctionKind::Public => matches!(fdef.visibility, Visibility::Public(_)),
+            FunctionKind::All => true,
+        }
+    }
+}
+
+impl AddressPattern {
+    fn matches(&self, address: &E::Address) -> bool {
+        match (self, address) {
+            (AddressPattern::Value(value), _) => address.is(value),
+            (
+                AddressPattern::Name(name),
+                E::Address::Numerical {
+                    name: Some(sp!(_, n)),
+                    ..
+                }
+                | E::Address::NamedUnassigned(sp!(_, n)),
+            ) => n == name,
+            (AddressPattern::Name(_), E::Address::Numerical { name: None, .. }) => false,
+        }
+    }
+}
+
+impl MemberPattern {
+    fn matches_module(&self, sp!(_, mident): &ModuleIdent) -> bool {
+        self.address.matches(&mident.address) && &mident.module == self.module.as_str()
+    }
+
+    fn matches_call(&self, call: &T::ModuleCall) -> bool {
+        self.matches_module(&call.module) && &call.name == self.member.as_str()
+    }
+
+    fn matches_type(&self, sp!(_, ty): &N::Type) -> bool {
+        match ty {
+            N::Type_::Apply(_, sp!(_, N::TypeName_::ModuleType(mident, name)), _) => {
+                self.matches_module(mident) && name == self.member.as_str()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::str::FromStr for MemberPattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let parts: Vec<_> = s.split("::").collect();
+        let &[address, module, member] = parts.as_slice() else {
+            anyhow::bail!("Expected '<address>::<module>::<member>', found '{s}'");
+        };
+        let address = if address.starts_with(|c: char| c.is_ascii_digit()) {
+            AddressPattern::Value(
+                NumericalAddress::parse_str(address)
+                    .map_err(|e| anyhow::anyhow!("Invalid address '{address}' in '{s}': {e}"))?,
+            )
+        } else {
+            AddressPattern::Name(Symbol::from(address))
+        };
+        anyhow::ensure!(
+            !module.is_empty() && !member.is_empty(),
+            "Expected '<address>::<module>::<member>', found '{s}'"
+        );
+        Ok(Self {
+            address,
+            module: Symbol::from(module),
+            member: Symbol::from(member),
+        })
+    }
+}
+
+impl fmt::Display for MemberPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.address {
+            AddressPattern::Name(name) => write!(f, "{name}")?,
+            AddressPattern::Value(value) => write!(f, "{value}")?,
+        }
+        write!(f, "::{}::{}", self.module, self.member)
+    }
+}
+
+impl TypingVisitorContext for CallFinder<'_> {
+    fn push_warning_filter_scope(&mut self, _filters: WarningFilters) {}
+
+    fn pop_warning_filter_scope(&mut self) {}
+
+    fn visit_exp_custom(&mut self, exp: &T::Exp) -> bool {
+        if let TE::ModuleCall(call) = &exp.exp.value {
+            self.found |= self.function.matches_call(call);
+        }
+        // Once a call is found, the rest of the body is skipped
+        self.found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        let lints = parse_rules(
+            r#"
+            [[lint]]
+            name = "admin_cap_transfer"
+            message = "'AdminCap' should not be freely transferable"
+            forbid_call = "sui::transfer::public_transfer"
+            type_argument = "0x42::admin::AdminCap"
+
+            [[lint]]
+            name = "entry_without_event"
+            message = "entry function does not emit an event"
+            require_call = "sui::event::emit"
+            level = "all"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(lints.linter_visitors(LintLevel::None).len(), 0);
+        assert_eq!(lints.linter_visitors(LintLevel::Default).len(), 1);
+        assert_eq!(lints.linter_visitors(LintLevel::All).len(), 2);
+        assert_eq!(lints.known_filters().1.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let invalid = [
+            // neither forbid_call nor require_call
+            r#"[[lint]]
+            name = "a"
+            message = "m""#,
+            // both forbid_call and require_call
+            r#"[[lint]]
+            name = "a"
+            message = "m"
+            forbid_call = "a::m::f"
+            require_call = "a::m::g""#,
+            // type_argument without forbid_call
+            r#"[[lint]]
+            name = "a"
+            message = "m"
+            require_call = "a::m::f"
+            type_argument = "a::m::T""#,
+            // not a module member
+            r#"[[lint]]
+            name = "a"
+            message = "m"
+            forbid_call = "a::f""#,
+            // unknown field
+            r#"[[lint]]
+            name = "a"
+            message = "m"
+            forbid_call = "a::m::f"
+            forbid_type = "a::m::T""#,
+            // name of a built-in lint
+            r#"[[lint]]
+            name = "share_owned"
+            message = "m"
+            forbid_call = "a::m::f""#,
+            // duplicate name
+            r#"[[lint]]
+            name = "a"
+            message = "m"
+            forbid_call = "a::m::f"
+            [[lint]]
+            name = "a"
+            message = "m"
+            forbid_call = "a::m::g""#,
+        ];
+        for rules in invalid {
+            assert!(parse_rules(rules).is_err(), "{rules}");
+        }
+    }
+}
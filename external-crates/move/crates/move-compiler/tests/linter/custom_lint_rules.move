@@ -0,0 +1,37 @@
+// Custom lints are declared in custom_lint_rules.lint_rules.toml
+module a::admin {
+    struct AdminCap has drop {}
+    struct Other has drop {}
+
+    public fun new_cap(): AdminCap { AdminCap {} }
+    public fun new_other(): Other { Other {} }
+    public fun give<T: drop>(_x: T) {}
+    public fun dangerous() {}
+    public fun log() {}
+}
+
+module a::m {
+    use a::admin::{Self, AdminCap, Other};
+
+    // Should trigger a warning
+    public fun give_cap() {
+        admin::give<AdminCap>(admin::new_cap());
+    }
+
+    public fun give_other() {
+        admin::give<Other>(admin::new_other());
+    }
+
+    fun call_dangerous() {
+        admin::dangerous();
+    }
+
+    public entry fun entry_without_log() {}
+
+    public entry fun entry_with_log() {
+        admin::log();
+    }
+
+    // Only entry functions are required to log
+    public fun public_without_log() {}
+}
@@ -0,0 +1,40 @@
+// Custom lints are declared in suppress_custom_lint_rules.lint_rules.toml
+module a::admin {
+    struct AdminCap has drop {}
+
+    public fun new_cap(): AdminCap { AdminCap {} }
+    public fun give<T: drop>(_x: T) {}
+    public fun dangerous() {}
+}
+
+#[allow(lint(cap_give, dangerous_call, missing_log))]
+module a::m {
+    use a::admin::{Self, AdminCap};
+
+    public fun give_cap() {
+        admin::give<AdminCap>(admin::new_cap());
+    }
+
+    fun call_dangerous() {
+        admin::dangerous();
+    }
+
+    public entry fun entry_without_log() {}
+}
+
+module a::n {
+    use a::admin::{Self, AdminCap};
+
+    #[allow(lint(cap_give))]
+    public fun give_cap() {
+        admin::give<AdminCap>(admin::new_cap());
+    }
+
+    #[allow(lint(dangerous_call))]
+    fun call_dangerous() {
+        admin::dangerous();
+    }
+
+    #[allow(lint(missing_log))]
+    public entry fun entry_without_log() {}
+}
@@ -25,6 +25,7 @@ const TEST_EXT: &str = "unit_test";
 const UNUSED_EXT: &str = "unused";
 const MIGRATION_EXT: &str = "migration";
 const IDE_EXT: &str = "ide";
+const LINT_RULES_EXT: &str = "lint_rules.toml";
 
 const LINTER_DIR: &str = "linter";
 const SUI_MODE_DIR: &str = "sui_mode";
@@ -240,6 +241,12 @@ pub fn run_test_inner(
     if lint {
         compiler = compiler.add_visitors(linters::linter_visitors(LintLevel::All))
     }
+    // A lint test can declare custom lints in a `path.lint_rules.toml` file.
+    let lint_rules_path = path.with_extension(LINT_RULES_EXT);
+    if lint && lint_rules_path.exists() {
+        let custom_lints = linters::rules::load_rules(&lint_rules_path)?;
+        compiler = compiler.add_custom_lints(&custom_lints, LintLevel::All);
+    }
 
     let (files, comments_and_compiler_res) = compiler.run::<PASS_PARSER>()?;
     let diags = move_check_for_errors(comments_and_compiler_res);
@@ -4,6 +4,7 @@
 
 use crate::{
     compilation::package_layout::CompiledPackageLayout,
+    package_hooks,
     resolution::resolution_graph::{Package, Renaming, ResolvedGraph, ResolvedTable},
     source_package::{
         layout::{SourcePackageLayout, REFERENCE_TEMPLATE_FILENAME},
@@ -24,7 +25,7 @@ use move_command_line_common::files::{
 use move_compiler::{
     compiled_unit::{AnnotatedCompiledUnit, CompiledUnit, NamedCompiledModule},
     editions::Flavor,
-    linters,
+    linters::{self, rules},
     shared::{files::MappedFiles, NamedAddressMap, NumericalAddress, PackageConfig, PackagePaths},
     sui_mode::{self},
     Compiler,
@@ -503,6 +504,13 @@ impl CompiledPackage {
         compiler = compiler
             .add_custom_known_filters(filter_attr_name, filters)
             .add_visitors(linters::linter_visitors(lint_level));
+        let mut custom_lints = package_hooks::custom_lints();
+        if let Some(path) = &resolution_graph.build_options.lint_rules {
+            custom_lints.extend(&rules::load_rules(path)?)?;
+        }
+        if !custom_lints.is_empty() {
+            compiler = compiler.add_custom_lints(&custom_lints, lint_level);
+        }
         Ok(BuildResult {
             root_package_name,
             sources_package_paths,
@@ -111,6 +111,11 @@ pub struct BuildConfig {
 
     #[clap(flatten)]
     pub lint_flag: LintFlag,
+
+    /// Additional lints, declared in the rules file at this path. Only declarative rules
+    /// (`forbid_call` and `require_call`) can be loaded this way
+    #[clap(long = move_compiler::command_line::LINT_RULES, global = true, value_name = "PATH")]
+    pub lint_rules: Option<PathBuf>,
 }
 
 #[derive(
@@ -3,6 +3,7 @@
 
 use crate::source_package::parsed_manifest::{OnChainInfo, SourceManifest};
 use anyhow::bail;
+use move_compiler::linters::custom_lints::CustomLints;
 use move_symbol_pool::Symbol;
 use once_cell::sync::Lazy;
 use std::sync::Mutex;
@@ -39,6 +40,19 @@ pub fn register_package_hooks(hooks: Box<dyn PackageHooks + Send + Sync>) {
     *HOOKS.lock().unwrap() = Some(hooks)
 }
 
+static CUSTOM_LINTS: Lazy<Mutex<CustomLints>> = Lazy::new(|| Mutex::new(CustomLints::new()));
+
+/// Registers custom lints, which run along with the built-in lints whenever a package is built in
+/// the process, e.g. by a build of a Move CLI with project-specific lints.
+pub fn register_custom_lints(lints: CustomLints) {
+    *CUSTOM_LINTS.lock().unwrap() = lints
+}
+
+/// Returns the registered custom lints.
+pub(crate) fn custom_lints() -> CustomLints {
+    CUSTOM_LINTS.lock().unwrap().clone()
+}
+
 /// Calls any registered hook to resolve a node dependency. Bails if none is registered.
 pub(crate) fn resolve_on_chain_dependency(
     dep_name: Symbol,
@@ -0,0 +1,656 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! An interactive, source-level debugger for Move unit tests.
+//!
+//! The debugger is a VM tracer: it is notified of every event while the test executes, maps each
+//! instruction back to a line of source using the source maps of the compiled modules, and stops
+//! to read commands from the terminal when it hits a breakpoint or finishes a step. The values of
+//! locals are rebuilt from the effects in the trace by the memory tracer's `TraceState`.
+
+use crate::format_module_id;
+use codespan_reporting::files::Files;
+use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::files::FileHash;
+use move_compiler::{shared::files::MappedFiles, unit_test::TestPlan};
+use move_core_types::{annotated_value::MoveValue, language_storage::ModuleId};
+use move_trace_format::{
+    format::{Effect, Location, RefType, TraceEvent, TraceIndex, TraceValue, TypeTagWithRefs},
+    interface::{Tracer, Writer},
+    memory_tracer::TraceState,
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    sync::Arc,
+};
+
+const HELP: &str = "\
+Commands:
+  s, step              run to the next line, stepping into calls
+  n, next              run to the next line, stepping over calls
+  f, finish            run until the current function returns
+  c, continue          run until the next breakpoint
+  b, break FILE:LINE   set a breakpoint (without arguments, list the breakpoints)
+  d, delete [N]        delete breakpoint N (without arguments, all breakpoints)
+  l, locals            print the locals of the current function
+  p, print PATH        print a local, or a field inside it (e.g. `p pool.balance.value`)
+  bt, backtrace        print the call stack
+  q, quit              stop debugging and run the test to completion
+  h, help              print this message
+An empty line repeats the previous command.";
+
+/// Source information about the modules under test, shared by every test the debugger runs.
+pub struct DebugInfo {
+    mapped_files: MappedFiles,
+    source_maps: BTreeMap<ModuleId, SourceMap>,
+    module_names: BTreeMap<ModuleId, String>,
+}
+
+/// Creates the input the debugger reads commands from, and the output it prints to, for each test
+/// it debugs.
+#[derive(Clone)]
+pub struct DebuggerIo(Arc<dyn Fn() -> DebuggerStreams + Send + Sync>);
+
+pub type DebuggerStreams = (Box<dyn BufRead>, Box<dyn Write>);
+
+/// A frame of the call stack, as seen by the debugger.
+struct DebugFrame {
+    frame_id: TraceIndex,
+    module: ModuleId,
+    function_name: String,
+    function_index: FunctionDefinitionIndex,
+    locals_types: Vec<TypeTagWithRefs>,
+    is_native: bool,
+    // The file, line and pc of the last instruction executed in this frame.
+    position: Option<(FileHash, usize, u16)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum StepMode {
+    // Stop at the next line, in any frame.
+    Step,
+    // Stop at the next line in a frame at most this deep.
+    Next(usize),
+    // Stop as soon as execution is back in a frame shallower than this.
+    Finish(usize),
+    // Only stop at breakpoints.
+    Continue,
+    // Never stop again.
+    Detached,
+}
+
+pub struct Debugger {
+    info: Arc<DebugInfo>,
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+    state: TraceState,
+    frames: Vec<DebugFrame>,
+    breakpoints: BTreeMap<usize, (FileHash, usize)>,
+    next_breakpoint: usize,
+    mode: StepMode,
+    last_command: String,
+    started: bool,
+    // Execution errors are reported once, although every frame they unwind emits them again.
+    error_reported: bool,
+}
+
+impl DebugInfo {
+    pub fn new(test_plan: &TestPlan) -> Self {
+        let source_maps = test_plan
+            .module_info
+            .iter()
+            .map(|(id, module)| (id.clone(), module.source_map.clone()))
+            .collect();
+        let module_names = test_plan
+            .module_info
+            .keys()
+            .map(|id| (id.clone(), format_module_id(&test_plan.module_info, id)))
+            .collect();
+        Self {
+            mapped_files: test_plan.mapped_files.clone(),
+            source_maps,
+            module_names,
+        }
+    }
+
+    /// The file and (1-indexed) line of the instruction at `pc` in the given function.
+    fn line(
+        &self,
+        module: &ModuleId,
+        function_index: FunctionDefinitionIndex,
+        pc: u16,
+    ) -> Option<(FileHash, usize)> {
+        let loc = self
+            .source_maps
+            .get(module)?
+            .get_code_location(function_index, pc)
+            .ok()?;
+        let position = self.mapped_files.start_position_opt(&loc)?;
+        Some((loc.file_hash(), position.user_line()))
+    }
+
+    /// The source name of a parameter or local, or `None` for temporaries introduced by the
+    /// compiler.
+    fn local_name(
+        &self,
+        module: &ModuleId,
+        function_index: FunctionDefinitionIndex,
+        index: usize,
+    ) -> Option<String> {
+        let (name, _) = self
+            .source_maps
+            .get(module)?
+            .get_parameter_or_local_name(function_index, index as u64)
+            .ok()?;
+        if name.starts_with('%') {
+            return None;
+        }
+        // The compiler renames locals apart as `name#depth#color`.
+        Some(name.split('#').next().unwrap_or_default().to_string())
+    }
+
+    fn module_name(&self, module: &ModuleId) -> String {
+        self.module_names
+            .get(module)
+            .cloned()
+            .unwrap_or_else(|| module.short_str_lossless())
+    }
+
+    fn file_name(&self, file: &FileHash) -> String {
+        self.mapped_files
+            .file_name_mapping()
+            .get(file)
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "<unknown>".to_string())
+    }
+
+    fn source_line(&self, file: &FileHash, line: usize) -> Option<String> {
+        let file_id = self.mapped_files.file_hash_to_file_id(file)?;
+        let source = self.mapped_files.files().source(file_id).ok()?;
+        source.lines().nth(line.checked_sub(1)?).map(str::to_string)
+    }
+
+    /// Find the source file a breakpoint refers to, by matching the trailing components of its
+    /// path.
+    fn resolve_file(&self, path: &str) -> Result<FileHash, String> {
+        let matches: Vec<_> = self
+            .mapped_files
+            .file_name_mapping()
+            .iter()
+            .filter(|(_, file_path)| file_path.ends_with(Path::new(path)))
+            .collect();
+        match matches.as_slice() {
+            [] => Err(format!("No source file matches `{path}`")),
+            [(file, _)] => Ok(**file),
+            _ => Err(format!(
+                "`{path}` matches several source files: {}",
+                matches
+                    .iter()
+                    .map(|(_, file_path)| file_path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+}
+
+impl DebuggerIo {
+    pub fn new(io: impl Fn() -> DebuggerStreams + Send + Sync + 'static) -> Self {
+        Self(Arc::new(io))
+    }
+
+    /// Read commands from stdin and print to stdout.
+    pub fn stdio() -> Self {
+        Self::new(|| {
+            (
+                Box::new(BufReader::new(io::stdin())),
+                Box::new(io::stdout()),
+            )
+        })
+    }
+}
+
+impl fmt::Debug for DebuggerIo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebuggerIo").finish_non_exhaustive()
+    }
+}
+
+impl Debugger {
+    pub fn new(info: Arc<DebugInfo>, io: &DebuggerIo) -> Self {
+        let (input, output) = (io.0)();
+        Self::with_io(info, input, output)
+    }
+
+    pub fn with_io(info: Arc<DebugInfo>, input: Box<dyn BufRead>, output: Box<dyn Write>) -> Self {
+        Self {
+            info,
+            input,
+            output,
+            state: TraceState::new(),
+            frames: vec![],
+            breakpoints: BTreeMap::new(),
+            next_breakpoint: 1,
+            mode: StepMode::Step,
+            last_command: String::new(),
+            started: false,
+            error_reported: false,
+        }
+    }
+
+    fn handle_event(&mut self, event: &TraceEvent) -> io::Result<()> {
+        match event {
+            TraceEvent::OpenFrame { frame, .. } => self.frames.push(DebugFrame {
+                frame_id: frame.frame_id,
+                module: frame.module.clone(),
+                function_name: frame.function_name.clone(),
+                function_index: FunctionDefinitionIndex(frame.binary_member_index),
+                locals_types: frame.locals_types.clone(),
+                is_native: frame.is_native,
+                position: None,
+            }),
+            TraceEvent::CloseFrame { .. } => {
+                self.frames.pop();
+            }
+            TraceEvent::Instruction { pc, .. } => self.on_instruction(*pc)?,
+            TraceEvent::Effect(effect) => {
+                if let Effect::ExecutionError(error) = &**effect {
+                    if !self.error_reported && !matches!(self.mode, StepMode::Detached) {
+                        self.error_reported = true;
+                        writeln!(self.output, "Execution failed: {error}")?;
+                        self.stop()?;
+                    }
+                }
+            }
+            TraceEvent::External(_) => (),
+        }
+        Ok(())
+    }
+
+    /// Instruction events are emitted before the effects of the instruction, so stopping here
+    /// shows the locals as they were before the instruction executed.
+    fn on_instruction(&mut self, pc: u16) -> io::Result<()> {
+        let depth = self.frames.len();
+        let Some(frame) = self.frames.last_mut() else {
+            return Ok(());
+        };
+        let Some((file, line)) = self.info.line(&frame.module, frame.function_index, pc) else {
+            return Ok(());
+        };
+        // A new line starts when the line changes, or when a loop jumps back within a line.
+        let new_line = match frame.position {
+            Some((last_file, last_line, last_pc)) => {
+                last_file != file || last_line != line || pc <= last_pc
+            }
+            None => true,
+        };
+        frame.position = Some((file, line, pc));
+
+        let breakpoint = self
+            .breakpoints
+            .iter()
+            .find(|(_, bp)| **bp == (file, line))
+            .map(|(id, _)| *id);
+        let stop = match self.mode {
+            StepMode::Detached => false,
+            StepMode::Finish(d) if depth < d => true,
+            _ if !new_line => false,
+            StepMode::Step => true,
+            StepMode::Next(d) => depth <= d || breakpoint.is_some(),
+            StepMode::Finish(_) | StepMode::Continue => breakpoint.is_some(),
+        };
+        if !stop {
+            return Ok(());
+        }
+        if let Some(id) = breakpoint.filter(|_| new_line) {
+            writeln!(self.output, "Breakpoint {id} hit")?;
+        }
+        self.stop()
+    }
+
+    /// Print where execution stopped and read commands until one of them resumes execution.
+    fn stop(&mut self) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            writeln!(
+                self.output,
+                "Debugging test, type `help` for the list of commands"
+            )?;
+        }
+        self.print_location()?;
+        loop {
+            write!(self.output, "(debug) ")?;
+            self.output.flush()?;
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                // Out of input: let the test run to completion.
+                writeln!(self.output)?;
+                self.mode = StepMode::Detached;
+                return Ok(());
+            }
+            let line = line.trim();
+            if !line.is_empty() {
+                self.last_command = line.to_string();
+            }
+            let command = self.last_command.clone();
+            let (name, arg) = command
+                .split_once(char::is_whitespace)
+                .map(|(name, arg)| (name, arg.trim()))
+                .unwrap_or((command.as_str(), ""));
+
+            let depth = self.frames.len();
+            let report = match name {
+                "s" | "step" => {
+                    self.mode = StepMode::Step;
+                    return Ok(());
+                }
+                "n" | "next" => {
+                    self.mode = StepMode::Next(depth);
+                    return Ok(());
+                }
+                "f" | "finish" => {
+                    self.mode = StepMode::Finish(depth);
+                    return Ok(());
+                }
+                "c" | "continue" => {
+                    self.mode = StepMode::Continue;
+                    return Ok(());
+                }
+                "q" | "quit" => {
+                    self.mode = StepMode::Detached;
+                    return Ok(());
+                }
+                "b" | "break" => self.add_breakpoint(arg),
+                "d" | "delete" => self.delete_breakpoint(arg),
+                "l" | "locals" => self.locals(),
+                "p" | "print" => self.print(arg).unwrap_or_else(|e| e),
+                "bt" | "backtrace" => self.backtrace(),
+                "h" | "help" => HELP.to_string(),
+                "" => continue,
+                _ => format!("Unknown command `{name}`, type `help` for the list of commands"),
+            };
+            writeln!(self.output, "{report}")?;
+        }
+    }
+
+    fn print_location(&mut self) -> io::Result<()> {
+        let Some(frame) = self.frames.last() else {
+            return Ok(());
+        };
+        let function = format!(
+            "{}::{}",
+            self.info.module_name(&frame.module),
+            frame.function_name
+        );
+        let Some((file, line, _)) = frame.position else {
+            return writeln!(self.output, "In {function}");
+        };
+        writeln!(
+            self.output,
+            "{}:{line} in {function}",
+            self.info.file_name(&file)
+        )?;
+        if let Some(source) = self.info.source_line(&file, line) {
+            writeln!(self.output, "{line:>5} | {source}")?;
+        }
+        Ok(())
+    }
+
+    fn add_breakpoint(&mut self, arg: &str) -> String {
+        if arg.is_empty() {
+            if self.breakpoints.is_empty() {
+                return "No breakpoints".to_string();
+            }
+            return self
+                .breakpoints
+                .iter()
+                .map(|(id, (file, line))| format!("{id}: {}:{line}", self.info.file_name(file)))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        let Some((path, line)) = arg
+            .rsplit_once(':')
+            .and_then(|(path, line)| Some((path, line.parse::<usize>().ok()?)))
+        else {
+            return "Expected a breakpoint of the form FILE:LINE".to_string();
+        };
+        match self.info.resolve_file(path) {
+            Ok(file) => {
+                let id = self.next_breakpoint;
+                self.next_breakpoint += 1;
+                self.breakpoints.insert(id, (file, line));
+                format!("Breakpoint {id} at {}:{line}", self.info.file_name(&file))
+            }
+            Err(e) => e,
+        }
+    }
+
+    fn delete_breakpoint(&mut self, arg: &str) -> String {
+        if arg.is_empty() {
+            self.breakpoints.clear();
+            return "Deleted all breakpoints".to_string();
+        }
+        match arg.parse::<usize>() {
+            Ok(id) if self.breakpoints.remove(&id).is_some() => format!("Deleted breakpoint {id}"),
+            _ => format!("No breakpoint `{arg}`"),
+        }
+    }
+
+    /// The live locals of the current frame that have a source name, with their indices and
+    /// values.
+    fn live_locals(&self) -> Vec<(String, usize, &TraceValue)> {
+        let Some(frame) = self.frames.last() else {
+            return vec![];
+        };
+        let Some((locals, _)) = self.state.call_stack.get(&frame.frame_id) else {
+            return vec![];
+        };
+        locals
+            .iter()
+            .filter_map(|(index, value)| {
+                let name = self
+                    .info
+                    .local_name(&frame.module, frame.function_index, *index)?;
+                Some((name, *index, value))
+            })
+            .collect()
+    }
+
+    fn locals(&self) -> String {
+        let Some(frame) = self.frames.last() else {
+            return "No function is executing".to_string();
+        };
+        let locals = self.live_locals();
+        if locals.is_empty() {
+            return "No live locals".to_string();
+        }
+        locals
+            .into_iter()
+            .map(|(name, index, value)| {
+                let type_ = frame
+                    .locals_types
+                    .get(index)
+                    .map(display_type)
+                    .unwrap_or_default();
+                display_binding(&name, Some(type_), self.resolve(value))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Evaluate a path of the form `local.field.field`, where vector elements are selected by
+    /// their index (e.g. `v.0`).
+    fn print(&self, path: &str) -> Result<String, String> {
+        let mut segments = path.split('.').map(str::trim);
+        let root = segments
+            .next()
+            .filter(|root| !root.is_empty())
+            .ok_or("Expected a local to print, e.g. `p pool.balance`")?;
+        let frame = self.frames.last().ok_or("No function is executing")?;
+        // Later locals shadow earlier ones with the same name.
+        let (_, index, value) = self
+            .live_locals()
+            .into_iter()
+            .rev()
+            .find(|(name, _, _)| name == root)
+            .ok_or_else(|| format!("No live local named `{root}`"))?;
+
+        let mut value = self.resolve(value);
+        let mut type_ = frame.locals_types.get(index).map(display_type);
+        for segment in segments {
+            value = field(value, segment)
+                .ok_or_else(|| format!("`{segment}` is not a field of `{path}`"))?;
+            type_ = value_type(value);
+        }
+        Ok(display_binding(path, type_, value))
+    }
+
+    fn backtrace(&self) -> String {
+        self.frames
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, frame)| {
+                let location = match frame.position {
+                    Some((file, line, _)) => format!(" at {}:{line}", self.info.file_name(&file)),
+                    None if frame.is_native => " [native]".to_string(),
+                    None => String::new(),
+                };
+                format!(
+                    "#{i} {}::{}{location}",
+                    self.info.module_name(&frame.module),
+                    frame.function_name
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The value held by `value` or, for references, the current value they point to.
+    fn resolve<'a>(&'a self, value: &'a TraceValue) -> &'a MoveValue {
+        value
+            .location()
+            .and_then(|location| self.resolve_location(location))
+            .unwrap_or_else(|| value.snapshot())
+    }
+
+    fn resolve_location(&self, location: &Location) -> Option<&MoveValue> {
+        match location {
+            Location::Local(frame_id, index) => {
+                let (locals, _) = self.state.call_stack.get(frame_id)?;
+                Some(locals.get(index)?.snapshot())
+            }
+            Location::Indexed(location, offset) => {
+                let value = self.resolve_location(location)?;
+                match value {
+                    MoveValue::Struct(s) => s.fields.get(*offset).map(|(_, v)| v),
+                    MoveValue::Variant(v) => v.fields.get(*offset).map(|(_, v)| v),
+                    MoveValue::Vector(elements) => elements.get(*offset),
+                    _ => None,
+                }
+            }
+            Location::Global(id) => self.state.loaded_state.get(id),
+        }
+    }
+}
+
+impl Tracer for Debugger {
+    fn notify(&mut self, event: &TraceEvent, _writer: Writer<'_>) {
+        self.state.apply_event(event);
+        if let Err(e) = self.handle_event(event) {
+            eprintln!("Debugger I/O failed, running the test to completion: {e}");
+            self.mode = StepMode::Detached;
+        }
+    }
+}
+
+/// Select a field of a struct or variant by name, or an element of a vector by index. Positional
+/// fields can be selected by their position.
+fn field<'a>(value: &'a MoveValue, segment: &str) -> Option<&'a MoveValue> {
+    let fields = match value {
+        MoveValue::Struct(s) => &s.fields,
+        MoveValue::Variant(v) => &v.fields,
+        MoveValue::Vector(elements) => return elements.get(segment.parse::<usize>().ok()?),
+        _ => return None,
+    };
+    let positional = format!("pos{segment}");
+    fields
+        .iter()
+        .find(|(name, _)| name.as_str() == segment || name.as_str() == positional)
+        .map(|(_, v)| v)
+}
+
+fn value_type(value: &MoveValue) -> Option<String> {
+    Some(match value {
+        MoveValue::U8(_) => "u8".to_string(),
+        MoveValue::U16(_) => "u16".to_string(),
+        MoveValue::U32(_) => "u32".to_string(),
+        MoveValue::U64(_) => "u64".to_string(),
+        MoveValue::U128(_) => "u128".to_string(),
+        MoveValue::U256(_) => "u256".to_string(),
+        MoveValue::Bool(_) => "bool".to_string(),
+        MoveValue::Address(_) => "address".to_string(),
+        MoveValue::Signer(_) => "signer".to_string(),
+        MoveValue::Vector(elements) => format!("vector<{}>", value_type(elements.first()?)?),
+        MoveValue::Struct(s) => s.type_.to_string(),
+        MoveValue::Variant(v) => v.type_.to_string(),
+    })
+}
+
+fn display_type(type_: &TypeTagWithRefs) -> String {
+    match type_.ref_type {
+        Some(RefType::Imm) => format!("&{}", type_.type_),
+        Some(RefType::Mut) => format!("&mut {}", type_.type_),
+        None => type_.type_.to_string(),
+    }
+}
+
+fn display_binding(name: &str, type_: Option<String>, value: &MoveValue) -> String {
+    let value = format!("{value:#}").replace('\n', "\n    ");
+    match type_ {
+        Some(type_) => format!("{name}: {type_} = {value}"),
+        None => format!("{name} = {value}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::field;
+    use move_core_types::{
+        account_address::AccountAddress,
+        annotated_value::{MoveStruct, MoveValue},
+        identifier::Identifier,
+        language_storage::StructTag,
+    };
+
+    #[test]
+    fn test_field_paths() {
+        let type_ = StructTag {
+            address: AccountAddress::ONE,
+            module: Identifier::new("m").unwrap(),
+            name: Identifier::new("S").unwrap(),
+            type_params: vec![],
+        };
+        let value = MoveValue::Struct(MoveStruct::new(
+            type_,
+            vec![
+                (Identifier::new("pos0").unwrap(), MoveValue::U8(1)),
+                (
+                    Identifier::new("items").unwrap(),
+                    MoveValue::Vector(vec![MoveValue::U64(2), MoveValue::U64(3)]),
+                ),
+            ],
+        ));
+
+        assert_eq!(field(&value, "0"), Some(&MoveValue::U8(1)));
+        let items = field(&value, "items").unwrap();
+        assert_eq!(field(items, "1"), Some(&MoveValue::U64(3)));
+        assert_eq!(field(items, "2"), None);
+        assert_eq!(field(&value, "missing"), None);
+        assert_eq!(field(&MoveValue::U8(1), "0"), None);
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas profiles of unit tests, and their comparison against the profiles of an earlier run.
+
+use anyhow::{Context, Result};
+use move_vm_profiler::summary::GasSummary;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    path::Path,
+    sync::Mutex,
+};
+
+/// Name of the file summarizing the profiles of all tests, which can be used as a baseline.
+pub const SUMMARY_FILE: &str = "summary.json";
+/// Name of the flamegraph of all tests, in the "folded stacks" format.
+pub const FLAMEGRAPH_FILE: &str = "all_tests.folded";
+
+/// Number of functions listed when reporting a profile or a regression.
+const TOP_FUNCTIONS: usize = 10;
+
+/// Gas profile of a test run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestGasProfile {
+    pub summary: GasSummary,
+    /// The profile in speedscope's format, until it is saved.
+    pub speedscope: Option<String>,
+}
+
+/// Gas profiles of all tests in a run, keyed by the fully qualified names of the tests.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasProfileReport {
+    pub tests: BTreeMap<String, GasSummary>,
+    pub total: GasSummary,
+}
+
+/// A test that uses more gas than it did in the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasRegression {
+    pub test: String,
+    pub baseline: u64,
+    pub current: u64,
+    /// The functions whose own gas went up the most, with their own gas in the baseline and now.
+    pub functions: Vec<(String, u64, u64)>,
+}
+
+impl GasRegression {
+    fn increase_percent(&self) -> f64 {
+        percent(self.current - self.baseline, self.baseline)
+    }
+}
+
+impl GasProfileReport {
+    /// Adds a run of the test `name`. The runs of tests with generated arguments are summed up.
+    pub fn add_run(&mut self, name: String, summary: &GasSummary) {
+        self.total.merge(summary);
+        self.tests.entry(name).or_default().merge(summary);
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Unable to read gas profile {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid gas profile {}", path.display()))
+    }
+
+    /// Writes the summary of the profiles and the flamegraph of all tests to `dir`.
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(SUMMARY_FILE), serde_json::to_string_pretty(self)?)?;
+        std::fs::write(dir.join(FLAMEGRAPH_FILE), self.total.folded_stacks())?;
+        Ok(())
+    }
+
+    /// The tests that use more than `threshold` percent more gas than they did in `baseline`.
+    /// Tests that are not in both reports are not compared.
+    pub fn regressions(&self, baseline: &GasProfileReport, threshold: f64) -> Vec<GasRegression> {
+        let mut regressions = vec![];
+        for (test, current) in &self.tests {
+            let Some(previous) = baseline.tests.get(test) else {
+                continue;
+            };
+            if current.total <= previous.total
+                || percent(current.total - previous.total, previous.total) <= threshold
+            {
+                continue;
+            }
+
+            let names: BTreeSet<_> = current
+                .functions
+                .keys()
+                .chain(previous.functions.keys())
+                .collect();
+            let mut functions: Vec<_> = names
+                .into_iter()
+                .filter_map(|name| {
+                    let before = previous.functions.get(name).map_or(0, |f| f.own);
+                    let after = current.functions.get(name).map_or(0, |f| f.own);
+                    (after > before).then(|| (name.clone(), before, after))
+                })
+                .collect();
+            functions.sort_by_key(|(_, before, after)| std::cmp::Reverse(after - before));
+            functions.truncate(TOP_FUNCTIONS);
+
+            regressions.push(GasRegression {
+                test: test.clone(),
+                baseline: previous.total,
+                current: current.total,
+                functions,
+            });
+        }
+        regressions
+    }
+
+    /// Reports the gas used by all tests, per instruction category and in the functions that use
+    /// the most gas.
+    pub fn report<W: Write>(&self, writer: &Mutex<W>) -> std::io::Result<()> {
+        let mut writer = writer.lock().unwrap();
+        let total = self.total.total;
+        writeln!(writer, "\nGas used by all tests: {total}")?;
+
+        if !self.total.instructions.is_empty() {
+            writeln!(writer, "\nBy instruction category:")?;
+            let mut categories: Vec<_> = self.total.instructions.iter().collect();
+            categories.sort_by_key(|(_, gas)| std::cmp::Reverse(**gas));
+            for (category, gas) in categories {
+                writeln!(
+                    writer,
+                    "  {category:<20} {gas:>12} ({:.1}%)",
+                    percent(*gas, total)
+                )?;
+            }
+        }
+
+        if !self.total.functions.is_empty() {
+            writeln!(
+                writer,
+                "\nFunctions using the most gas (own / including callees):"
+            )?;
+            let mut functions: Vec<_> = self.total.functions.iter().collect();
+            functions.sort_by_key(|(_, gas)| std::cmp::Reverse(gas.own));
+            for (name, gas) in functions.into_iter().take(TOP_FUNCTIONS) {
+                writeln!(
+                    writer,
+                    "  {name} -- {} / {} gas in {} call(s)",
+                    gas.own, gas.total, gas.calls
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Compares the profiles against `baseline`, reporting the tests that use more than
+    /// `threshold` percent more gas than they did. Returns `true` if there are no such tests.
+    pub fn check_regressions<W: Write>(
+        &self,
+        baseline: &GasProfileReport,
+        threshold: f64,
+        writer: &Mutex<W>,
+    ) -> std::io::Result<bool> {
+        let regressions = self.regressions(baseline, threshold);
+        let mut writer = writer.lock().unwrap();
+        let new_tests = self
+            .tests
+            .keys()
+            .filter(|test| !baseline.tests.contains_key(*test))
+            .count();
+        if new_tests > 0 {
+            writeln!(
+                writer,
+                "\n{new_tests} test(s) are not in the gas profile baseline and were not compared"
+            )?;
+        }
+        if regressions.is_empty() {
+            writeln!(writer, "\nNo gas regressions against the baseline")?;
+            return Ok(true);
+        }
+
+        writeln!(writer, "\nGas regressions against the baseline:")?;
+        for regression in &regressions {
+            writeln!(
+                writer,
+                "\n  {}: {} -> {} gas (+{:.1}%)",
+                regression.test,
+                regression.baseline,
+                regression.current,
+                regression.increase_percent()
+            )?;
+            for (name, before, after) in &regression.functions {
+                writeln!(writer, "    {name}: {before} -> {after} own gas")?;
+            }
+        }
+        Ok(false)
+    }
+}
+
+fn percent(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        100.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_vm_profiler::summary::FunctionGas;
+
+    fn summary(total: u64, functions: &[(&str, u64)]) -> GasSummary {
+        GasSummary {
+            total,
+            functions: functions
+                .iter()
+                .map(|(name, own)| {
+                    let gas = FunctionGas {
+                        calls: 1,
+                        total: *own,
+                        own: *own,
+                    };
+                    (name.to_string(), gas)
+                })
+                .collect(),
+            ..GasSummary::default()
+        }
+    }
+
+    fn report(tests: &[(&str, GasSummary)]) -> GasProfileReport {
+        let mut report = GasProfileReport::default();
+        for (name, summary) in tests {
+            report.add_run(name.to_string(), summary);
+        }
+        report
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = report(&[
+            ("m::cheaper", summary(100, &[("m::f", 100)])),
+            ("m::slower", summary(100, &[("m::f", 60), ("m::g", 40)])),
+            ("m::slightly_slower", summary(100, &[("m::f", 100)])),
+        ]);
+        let current = report(&[
+            ("m::cheaper", summary(90, &[("m::f", 90)])),
+            (
+                "m::slower",
+                summary(150, &[("m::f", 70), ("m::g", 30), ("m::h", 50)]),
+            ),
+            ("m::slightly_slower", summary(104, &[("m::f", 104)])),
+            ("m::new", summary(1000, &[("m::f", 1000)])),
+        ]);
+
+        let regressions = current.regressions(&baseline, 5.0);
+        assert_eq!(
+            regressions,
+            vec![GasRegression {
+                test: "m::slower".to_string(),
+                baseline: 100,
+                current: 150,
+                functions: vec![("m::h".to_string(), 0, 50), ("m::f".to_string(), 60, 70),],
+            }]
+        );
+        assert_eq!(current.regressions(&baseline, 0.0).len(), 2);
+        assert_eq!(current.total.total, 1344);
+    }
+}
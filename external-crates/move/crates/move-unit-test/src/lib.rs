@@ -3,11 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod cargo_runner;
+pub mod debugger;
 pub mod extensions;
+pub mod gas_profile;
 pub mod test_reporter;
 pub mod test_runner;
 
-use crate::test_runner::TestRunner;
+use crate::{debugger::DebuggerIo, gas_profile::GasProfileReport, test_runner::TestRunner};
 use anyhow::{bail, Result};
 use clap::*;
 use move_binary_format::CompiledModule;
@@ -17,13 +19,13 @@ use move_compiler::{
     compiled_unit::NamedCompiledModule,
     diagnostics,
     shared::{self, NumericalAddress},
-    unit_test::{self, TestPlan},
+    unit_test::{self, TestArgument, TestPlan},
     Compiler, Flags, PASS_CFGIR,
 };
 use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_test_utils::gas_schedule::CostTable;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex};
+use std::{collections::BTreeMap, io::Write, marker::Send, path::Path, sync::Mutex};
 
 /// The default value bounding the amount of gas consumed in a test.
 const DEFAULT_EXECUTION_BOUND: u64 = 1_000_000;
@@ -34,6 +36,9 @@ const DEFAULT_RAND_ITERS: u64 = 10;
 const RAND_NUM_ITERS_FLAG: &str = "rand-num-iters";
 const SEED_FLAG: &str = "seed";
 const TRACE_FLAG: &str = "trace-execution";
+const DEBUG_FLAG: &str = "debug";
+const PROFILE_FLAG: &str = "profile";
+const PROFILE_BASELINE_FLAG: &str = "profile-baseline";
 
 #[derive(Debug, Parser, Clone)]
 #[clap(author, version, about)]
@@ -126,6 +131,31 @@ pub struct UnitTestingConfig {
     // Enable tracing for tests
     #[clap(long = TRACE_FLAG, value_name = "PATH")]
     pub trace_execution: Option<Option<String>>,
+
+    /// Run a single test under the interactive debugger
+    #[clap(long = DEBUG_FLAG, value_name = "TEST_NAME")]
+    pub debug: Option<String>,
+
+    // Where the debugger reads commands and prints to, the terminal by default.
+    #[clap(skip)]
+    pub debugger_io: Option<DebuggerIo>,
+
+    /// Profile the gas used by tests, saving the profiles to PATH (`gas_profiles` by default)
+    #[clap(long = PROFILE_FLAG, value_name = "PATH")]
+    pub profile: Option<Option<String>>,
+
+    /// Compare the gas profiles against the summary of an earlier profiled run, and fail if a
+    /// test uses more gas than it did. Requires a fixed `--seed` if there are random tests
+    #[clap(long = PROFILE_BASELINE_FLAG, value_name = "SUMMARY_FILE", requires = PROFILE_FLAG)]
+    pub profile_baseline: Option<String>,
+
+    /// Percentage by which the gas used by a test may go up before it is a regression
+    #[clap(
+        long = "profile-threshold",
+        value_name = "PERCENT",
+        default_value = "0"
+    )]
+    pub profile_threshold: f64,
 }
 
 fn format_module_id(
@@ -139,6 +169,68 @@ fn format_module_id(
     }
 }
 
+/// Narrow the test plan down to the test to debug. `name` is either the name of the test, or a part
+/// of its fully qualified `<module>::<test>` name that only this test's name contains.
+fn select_debug_test(test_plan: &mut TestPlan, name: &str) -> Result<()> {
+    let tests: Vec<(ModuleId, String)> = test_plan
+        .module_tests
+        .iter()
+        .flat_map(|(module_id, module_test_plan)| {
+            module_test_plan
+                .tests
+                .keys()
+                .map(move |test_name| (module_id.clone(), test_name.clone()))
+        })
+        .collect();
+    let full_name = |(module_id, test_name): &(ModuleId, String)| {
+        format!(
+            "{}::{}",
+            format_module_id(&test_plan.module_info, module_id),
+            test_name
+        )
+    };
+
+    let exact: Vec<_> = tests
+        .iter()
+        .filter(|&test| test.1 == name || full_name(test) == name)
+        .collect();
+    let matching = if exact.is_empty() {
+        tests
+            .iter()
+            .filter(|&test| full_name(test).contains(name))
+            .collect()
+    } else {
+        exact
+    };
+    let (module_id, test_name) = match matching.as_slice() {
+        [test] => (*test).clone(),
+        [] => bail!("No test matches '{name}'"),
+        _ => bail!(
+            "Several tests match '{name}', pick one of: {}",
+            matching
+                .iter()
+                .map(|&test| full_name(test))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    test_plan.module_tests.retain(|id, _| *id == module_id);
+    for module_test_plan in test_plan.module_tests.values_mut() {
+        module_test_plan.tests.retain(|name, _| *name == test_name);
+    }
+    Ok(())
+}
+
+fn has_random_tests(test_plan: &TestPlan) -> bool {
+    test_plan
+        .module_tests
+        .values()
+        .flat_map(|module_test_plan| module_test_plan.tests.values())
+        .flat_map(|test_case| &test_case.arguments)
+        .any(|arg| matches!(arg, TestArgument::Generate { .. }))
+}
+
 impl UnitTestingConfig {
     /// Create a unit testing config for use with `register_move_unit_tests`
     pub fn default_with_bound(bound: Option<u64>) -> Self {
@@ -158,6 +250,11 @@ impl UnitTestingConfig {
             seed: None,
             deterministic_generation: false,
             trace_execution: None,
+            debug: None,
+            debugger_io: None,
+            profile: None,
+            profile_baseline: None,
+            profile_threshold: 0.0,
         }
     }
 
@@ -229,7 +326,7 @@ impl UnitTestingConfig {
     /// Returns `true` if all unit tests passed. Otherwise, returns `false`.
     pub fn run_and_report_unit_tests<W: Write + Send>(
         &self,
-        test_plan: TestPlan,
+        mut test_plan: TestPlan,
         native_function_table: Option<NativeFunctionTable>,
         cost_table: Option<CostTable>,
         writer: W,
@@ -254,6 +351,20 @@ impl UnitTestingConfig {
             None => DEFAULT_RAND_ITERS,
         };
 
+        // The gas used by a random test depends on its arguments, so comparing it against a
+        // baseline is only meaningful if the arguments are the same in both runs.
+        if self.profile_baseline.is_some()
+            && self.seed.is_none()
+            && !self.deterministic_generation
+            && has_random_tests(&test_plan)
+        {
+            bail!(
+                "Invalid arguments -- '{PROFILE_BASELINE_FLAG}' requires '{SEED_FLAG}' to be set \
+                when there are random tests, so that they are run with the same arguments as in \
+                the baseline."
+            )
+        }
+
         if self.list {
             for (module_id, module_test_plan) in &test_plan.module_tests {
                 for test_name in module_test_plan.tests.keys() {
@@ -268,20 +379,44 @@ impl UnitTestingConfig {
             return Ok((shared_writer.into_inner().unwrap(), true));
         }
 
+        // The debugged test runs once, on a single thread, as it reads commands from the terminal.
+        let (num_threads, rand_num_iters) = match &self.debug {
+            Some(test_name) => {
+                select_debug_test(&mut test_plan, test_name)?;
+                (1, 1)
+            }
+            None => (self.num_threads, rand_num_iters),
+        };
+
         writeln!(shared_writer.lock().unwrap(), "Running Move unit tests")?;
         let trace_location = match &self.trace_execution {
             Some(None) => Some("traces".to_string()),
             Some(Some(path)) => Some(path.clone()),
             None => None,
         };
+        let profile_location = match &self.profile {
+            Some(None) => Some("gas_profiles".to_string()),
+            Some(Some(path)) => Some(path.clone()),
+            None => None,
+        };
+        // Load the baseline before running tests, to fail early if it is not usable.
+        let profile_baseline = self
+            .profile_baseline
+            .as_ref()
+            .map(|path| GasProfileReport::load(Path::new(path)))
+            .transpose()?;
         let mut test_runner = TestRunner::new(
             self.gas_limit.unwrap_or(DEFAULT_EXECUTION_BOUND),
-            self.num_threads,
+            num_threads,
             self.report_stacktrace_on_abort,
             self.seed,
             rand_num_iters,
             self.deterministic_generation,
             trace_location,
+            self.debug
+                .as_ref()
+                .map(|_| self.debugger_io.clone().unwrap_or_else(DebuggerIo::stdio)),
+            profile_location.clone(),
             test_plan,
             native_function_table,
             cost_table,
@@ -297,7 +432,29 @@ impl UnitTestingConfig {
             test_results.report_statistics(&shared_writer, report_type)?;
         }
 
-        let ok = test_results.summarize(&shared_writer)?;
+        let gas_report = profile_location
+            .as_ref()
+            .map(|_| test_results.gas_profile_report());
+
+        let mut ok = test_results.summarize(&shared_writer)?;
+
+        if let (Some(location), Some(gas_report)) = (&profile_location, &gas_report) {
+            gas_report.save(Path::new(location))?;
+            gas_report.report(&shared_writer)?;
+            writeln!(
+                shared_writer.lock().unwrap(),
+                "\nGas profiles saved to {location}. Open them, or the flamegraph of all tests \
+                 ({}), with https://www.speedscope.app",
+                gas_profile::FLAMEGRAPH_FILE,
+            )?;
+            if let Some(baseline) = &profile_baseline {
+                ok &= gas_report.check_regressions(
+                    baseline,
+                    self.profile_threshold,
+                    &shared_writer,
+                )?;
+            }
+        }
 
         let writer = shared_writer.into_inner().unwrap();
         Ok((writer, ok))
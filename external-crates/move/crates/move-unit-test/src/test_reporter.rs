@@ -2,7 +2,10 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::format_module_id;
+use crate::{
+    format_module_id,
+    gas_profile::{GasProfileReport, TestGasProfile},
+};
 use colored::{control, Colorize};
 use move_binary_format::errors::{ExecutionState, Location, VMError};
 use move_command_line_common::error_bitset::ErrorBitset;
@@ -55,6 +58,7 @@ pub struct TestRunInfo {
     pub elapsed_time: Duration,
     pub instructions_executed: u64,
     pub trace: Option<MoveTrace>,
+    pub gas_profile: Option<TestGasProfile>,
 }
 
 type TestRuns<T> = BTreeMap<String, Vec<T>>;
@@ -86,11 +90,13 @@ impl TestRunInfo {
         elapsed_time: Duration,
         instructions_executed: u64,
         trace: Option<MoveTrace>,
+        gas_profile: Option<TestGasProfile>,
     ) -> Self {
         Self {
             elapsed_time,
             instructions_executed,
             trace,
+            gas_profile,
         }
     }
 
@@ -101,6 +107,14 @@ impl TestRunInfo {
             Ok(())
         }
     }
+
+    /// Saves the gas profile in speedscope's format, dropping it from memory.
+    pub fn save_gas_profile(&mut self, path: &str) -> Result<()> {
+        match self.gas_profile.as_mut().and_then(|p| p.speedscope.take()) {
+            Some(speedscope) => write_string_to_file(path, &speedscope),
+            None => Ok(()),
+        }
+    }
 }
 
 impl FailureReason {
@@ -567,6 +581,43 @@ impl TestResults {
         writeln!(writer.lock().unwrap())
     }
 
+    /// Collects the gas profiles of all test runs, passed or failed.
+    pub fn gas_profile_report(&self) -> GasProfileReport {
+        let mut report = GasProfileReport::default();
+        let passed = self
+            .final_statistics
+            .passed
+            .iter()
+            .flat_map(|(module_id, tests)| {
+                tests.iter().flat_map(move |(function_name, runs)| {
+                    runs.iter().map(move |run| (module_id, function_name, run))
+                })
+            });
+        let failed = self
+            .final_statistics
+            .failed
+            .iter()
+            .flat_map(|(module_id, tests)| {
+                tests.iter().flat_map(move |(function_name, failures)| {
+                    failures
+                        .iter()
+                        .map(move |failure| (module_id, function_name, &failure.test_run_info))
+                })
+            });
+        for (module_id, function_name, test_run_info) in passed.chain(failed) {
+            let Some(profile) = &test_run_info.gas_profile else {
+                continue;
+            };
+            let qualified_function_name = format!(
+                "{}::{}",
+                format_module_id(&self.test_plan.module_info, module_id),
+                function_name,
+            );
+            report.add_run(qualified_function_name, &profile.summary);
+        }
+        report
+    }
+
     /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
     pub fn summarize<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
         let num_failed_tests = self
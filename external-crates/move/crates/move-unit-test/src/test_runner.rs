@@ -3,7 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    debugger::{DebugInfo, Debugger, DebuggerIo},
     extensions, format_module_id,
+    gas_profile::TestGasProfile,
     test_reporter::{
         FailureReason, MoveError, TestFailure, TestResults, TestRunInfo, TestStatistics,
     },
@@ -38,7 +40,13 @@ use move_vm_test_utils::{
 };
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    marker::Send,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 
@@ -53,6 +61,8 @@ pub struct SharedTestingConfig {
     num_iters: u64,
     deterministic_generation: bool,
     trace_location: Option<String>,
+    debugger: Option<(Arc<DebugInfo>, DebuggerIo)>,
+    profile_location: Option<String>,
 }
 
 pub struct TestRunner {
@@ -115,6 +125,8 @@ impl TestRunner {
         num_iters: u64,
         deterministic_generation: bool,
         trace_location: Option<String>,
+        debugger_io: Option<DebuggerIo>,
+        profile_location: Option<String>,
         tests: TestPlan,
         // TODO: maybe we should require the clients to always pass in a list of native functions so
         // we don't have to make assumptions about their gas parameters.
@@ -130,8 +142,21 @@ impl TestRunner {
                      feature flag set. Rebuild binary with `--features tracing`"
                 ));
             }
+            if debugger_io.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Debugging tests requires the binary to be compiled with the `tracing` \
+                     feature flag set. Rebuild binary with `--features tracing`"
+                ));
+            }
+            if profile_location.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Profiling tests requires the binary to be compiled with the `tracing` \
+                     feature flag set. Rebuild binary with `--features tracing`"
+                ));
+            }
         };
 
+        let debugger = debugger_io.map(|io| (Arc::new(DebugInfo::new(&tests)), io));
         let modules = tests.module_info.values().map(|info| &info.module);
         let starting_storage_state =
             setup_test_storage(modules, tests.bytecode_deps_modules.iter())?;
@@ -158,6 +183,8 @@ impl TestRunner {
                 num_iters,
                 deterministic_generation,
                 trace_location,
+                debugger,
+                profile_location,
             },
             num_threads,
             tests,
@@ -263,8 +290,13 @@ impl SharedTestingConfig {
         let move_vm = MoveVM::new(self.native_function_table.clone()).unwrap();
         let extensions = extensions::new_extensions();
 
-        let mut move_tracer = MoveTraceBuilder::new();
-        let tracer = if self.trace_location.is_some() {
+        let mut move_tracer = match &self.debugger {
+            Some((info, io)) => {
+                MoveTraceBuilder::new_with_tracer(Box::new(Debugger::new(info.clone(), io)))
+            }
+            None => MoveTraceBuilder::new(),
+        };
+        let tracer = if self.trace_location.is_some() || self.debugger.is_some() {
             Some(&mut move_tracer)
         } else {
             None
@@ -274,12 +306,26 @@ impl SharedTestingConfig {
             move_vm.new_session_with_extensions(&self.starting_storage_state, extensions);
         let mut gas_meter = GasStatus::new(&self.cost_table, Gas::new(self.execution_bound));
         move_vm_profiler::tracing_feature_enabled! {
+            use move_vm_config::runtime::VMProfilerConfig;
             use move_vm_profiler::GasProfiler;
             use move_vm_types::gas::GasMeter;
-            gas_meter.set_profiler(GasProfiler::init_default_cfg(
-                function_name.to_owned(),
-                self.execution_bound,
-            ));
+            let profiler = match &self.profile_location {
+                // The profile is saved along with the other tests' profiles once the test is run.
+                Some(location) => GasProfiler::init(
+                    &Some(VMProfilerConfig {
+                        full_path: location.into(),
+                        track_bytecode_instructions: true,
+                        use_long_function_name: true,
+                    }),
+                    function_name.to_owned(),
+                    self.execution_bound,
+                ),
+                None => GasProfiler::init_default_cfg(
+                    function_name.to_owned(),
+                    self.execution_bound,
+                ),
+            };
+            gas_meter.set_profiler(profiler);
         }
 
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
@@ -309,6 +355,20 @@ impl SharedTestingConfig {
         } else {
             None
         };
+        #[allow(unused_mut)]
+        let mut gas_profile: Option<TestGasProfile> = None;
+        move_vm_profiler::tracing_feature_enabled! {
+            use move_vm_types::gas::GasMeter;
+            if self.profile_location.is_some() {
+                if let Some(profiler) = gas_meter.get_profiler_mut() {
+                    profiler.close();
+                    gas_profile = Some(TestGasProfile {
+                        summary: profiler.summary(),
+                        speedscope: serde_json::to_string(&*profiler).ok(),
+                    });
+                }
+            }
+        }
         let test_run_info = TestRunInfo::new(
             now.elapsed(),
             // TODO(Gas): This doesn't look quite right...
@@ -318,6 +378,7 @@ impl SharedTestingConfig {
                 .unwrap()
                 .into(),
             trace,
+            gas_profile,
         );
         match session.finish_with_extensions().0 {
             Ok((cs, extensions)) => (Ok(cs), Ok(extensions), return_result, test_run_info),
@@ -434,15 +495,12 @@ impl SharedTestingConfig {
         prng_seed: Option<u64>,
         is_last_execution_of_test: bool,
     ) -> bool {
-        let (_cs_result, _ext_result, exec_result, test_run_info) =
+        let (_cs_result, _ext_result, exec_result, mut test_run_info) =
             self.execute_via_move_vm(test_plan, function_name, arguments);
 
-        // Save the trace -- one per test -- for each test that we have traced (and if tracing is
-        // enabled).
-        if let Some(location) = &self.trace_location {
-            let trace_file_location = format!(
-                "{}/{}__{}{}.json",
-                location,
+        let test_file_name = || {
+            format!(
+                "{}__{}{}.json",
                 format_module_id(output.test_info, &output.test_plan.module_id).replace("::", "__"),
                 function_name,
                 if let Some(seed) = prng_seed {
@@ -450,12 +508,29 @@ impl SharedTestingConfig {
                 } else {
                     "".to_string()
                 }
-            );
+            )
+        };
+
+        // Save the trace -- one per test -- for each test that we have traced (and if tracing is
+        // enabled).
+        if let Some(location) = &self.trace_location {
+            let trace_file_location = format!("{}/{}", location, test_file_name());
             if let Err(e) = test_run_info.save_trace(&trace_file_location) {
                 eprintln!("Unable to save trace to {trace_file_location} -- {:?}", e);
             }
         }
 
+        // Likewise for gas profiles.
+        if let Some(location) = &self.profile_location {
+            let profile_file_location = format!("{}/{}", location, test_file_name());
+            if let Err(e) = test_run_info.save_gas_profile(&profile_file_location) {
+                eprintln!(
+                    "Unable to save gas profile to {profile_file_location} -- {:?}",
+                    e
+                );
+            }
+        }
+
         match exec_result {
             Err(err) => {
                 let sub_status = err.sub_status().and_then(|status| {
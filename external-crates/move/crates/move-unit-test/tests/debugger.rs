@@ -0,0 +1,91 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "tracing")]
+
+use move_unit_test::{debugger::DebuggerIo, UnitTestingConfig};
+use std::{
+    io::{self, Cursor, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// Collects what the debugger prints, so that the test can read it once the debugger is done.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_scripted_debugging_session() {
+    let script = "\
+b debugged.move:3
+c
+l
+bt
+c
+p x
+d
+c
+";
+    let output = SharedBuffer::default();
+    let debugger_output = output.clone();
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/sources/debugged.move");
+    let testing_config = UnitTestingConfig {
+        source_files: vec![path.to_string_lossy().to_string()],
+        dep_files: move_stdlib::move_stdlib_files(),
+        debug: Some("sums".to_string()),
+        debugger_io: Some(DebuggerIo::new(move || {
+            (
+                Box::new(Cursor::new(script)),
+                Box::new(debugger_output.clone()),
+            )
+        })),
+        ..UnitTestingConfig::default_with_bound(None)
+            .with_named_addresses(move_stdlib::move_stdlib_named_addresses())
+    };
+    let test_plan = testing_config.build_test_plan().unwrap();
+    let (report, passed) = testing_config
+        .run_and_report_unit_tests(test_plan, None, None, Vec::<u8>::new())
+        .unwrap();
+    let report = String::from_utf8(report).unwrap();
+    assert!(passed, "{report}");
+    assert!(report.contains("0x6::debugged::sums"), "{report}");
+
+    let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+    let expected = [
+        // Execution first stops in the test, where the breakpoint is set.
+        "Debugging test, type `help` for the list of commands",
+        " in 0x6::debugged::sums",
+        "Breakpoint 1 at ",
+        // The breakpoint is hit on the first call...
+        "Breakpoint 1 hit",
+        "debugged.move:3 in 0x6::debugged::add",
+        "    3 |         let sum = x + y;",
+        "x: u64 = 1u64\ny: u64 = 2u64",
+        "#0 0x6::debugged::add at ",
+        "#1 0x6::debugged::sums at ",
+        // ...and again on the second one.
+        "Breakpoint 1 hit",
+        "debugged.move:3 in 0x6::debugged::add",
+        "x: u64 = 3u64",
+        "Deleted all breakpoints",
+    ];
+    let mut rest = output.as_str();
+    for line in expected {
+        let Some(position) = rest.find(line) else {
+            panic!("Expected `{line}` next in the debugger output:\n{output}");
+        };
+        rest = &rest[position + line.len()..];
+    }
+    assert_eq!(output.matches("Breakpoint 1 hit").count(), 2, "{output}");
+}
@@ -0,0 +1,14 @@
+module 0x6::debugged {
+    fun add(x: u64, y: u64): u64 {
+        let sum = x + y;
+        sum
+    }
+
+    #[test]
+    fun sums() {
+        let a = 1;
+        let b = add(a, 2);
+        let c = add(b, 3);
+        assert!(c == 6, 0);
+    }
+}
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 use move_vm_config::runtime::VMProfilerConfig;
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub mod summary;
 
 #[cfg(feature = "tracing")]
 use tracing::info;
@@ -60,6 +62,9 @@ pub struct GasProfiler {
     pub config: Option<VMProfilerConfig>,
     #[serde(skip)]
     finished: bool,
+    /// Frames that are bytecode instructions rather than functions.
+    #[serde(skip)]
+    instruction_frames: BTreeSet<u64>,
 }
 
 #[cfg(feature = "tracing")]
@@ -92,6 +97,7 @@ impl GasProfiler {
             start_gas,
             config: config.clone(),
             finished: false,
+            instruction_frames: BTreeSet::new(),
         };
         profile_open_frame_impl!(
             Some(&mut prof),
@@ -178,6 +184,33 @@ impl GasProfiler {
         self.profiles[0].end_value = start - gas_end;
     }
 
+    #[cfg(feature = "tracing")]
+    pub fn open_instr(&mut self, instr_name: String, gas_start: u64) {
+        if !self.tracks_instructions() {
+            return;
+        }
+        self.open_frame(instr_name.clone(), instr_name.clone(), gas_start);
+        let frame_idx = self.shared.frame_table[&instr_name];
+        self.instruction_frames.insert(frame_idx as u64);
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn close_instr(&mut self, instr_name: String, gas_end: u64) {
+        if !self.tracks_instructions() {
+            return;
+        }
+        self.close_frame(instr_name.clone(), instr_name, gas_end);
+    }
+
+    #[cfg(feature = "tracing")]
+    fn tracks_instructions(&self) -> bool {
+        self.start_gas != 0
+            && self
+                .config
+                .as_ref()
+                .is_some_and(|config| config.track_bytecode_instructions)
+    }
+
     #[cfg(feature = "tracing")]
     pub fn to_file(&self) {
         use std::ffi::{OsStr, OsString};
@@ -218,16 +251,25 @@ impl GasProfiler {
         info!("Gas profile written to file: {}", p.display());
     }
 
+    /// Closes the top level frame without writing the profile to a file, for callers that
+    /// consume the profile themselves. Returns `false` if the profile was already closed.
     #[cfg(feature = "tracing")]
-    pub fn finish(&mut self) {
+    pub fn close(&mut self) -> bool {
         if self.finished {
-            return;
+            return false;
         }
         self.finished = true;
         let end_gas = self.start_gas() - self.profiles[0].end_value;
         let mut q = Some(self);
         profile_close_frame_impl!(&mut q, Self::TOP_LEVEL_FRAME_NAME.to_string(), end_gas);
-        profile_dump_file!(q.unwrap());
+        true
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn finish(&mut self) {
+        if self.close() {
+            profile_dump_file!(self);
+        }
     }
 }
 
@@ -313,11 +355,7 @@ macro_rules! profile_open_instr {
         {
             let gas_rem = $gas_meter.remaining_gas().into();
             if let Some(profiler) = $gas_meter.get_profiler_mut() {
-                if let Some(config) = &profiler.config {
-                    if config.track_bytecode_instructions {
-                        profiler.open_frame($frame_name.clone(), $frame_name, gas_rem)
-                    }
-                }
+                profiler.open_instr($frame_name, gas_rem)
             }
         }
     };
@@ -330,11 +368,7 @@ macro_rules! profile_close_instr {
         {
             let gas_rem = $gas_meter.remaining_gas().into();
             if let Some(profiler) = $gas_meter.get_profiler_mut() {
-                if let Some(config) = &profiler.config {
-                    if config.track_bytecode_instructions {
-                        profiler.close_frame($frame_name.clone(), $frame_name, gas_rem)
-                    }
-                }
+                profiler.close_instr($frame_name, gas_rem)
             }
         }
     };
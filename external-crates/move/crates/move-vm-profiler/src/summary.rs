@@ -0,0 +1,342 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Aggregate views of a gas profile: gas per function, per category of bytecode instruction, and
+//! per call stack (the latter in the "folded stacks" format understood by flamegraph tools).
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "tracing")]
+use crate::GasProfiler;
+
+/// Gas spent in a function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionGas {
+    /// Number of times the function was called.
+    pub calls: u64,
+    /// Gas spent in the function and in the functions it called.
+    pub total: u64,
+    /// Gas spent in the function itself.
+    pub own: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSummary {
+    pub total: u64,
+    pub functions: BTreeMap<String, FunctionGas>,
+    /// Gas spent executing instructions, by instruction category (see [instruction_category]).
+    /// Only populated if instructions were tracked by the profiler.
+    pub instructions: BTreeMap<String, u64>,
+    /// Gas spent in each call stack, keyed by the `;`-separated names of the functions on it,
+    /// outermost first.
+    #[serde(skip)]
+    pub stacks: BTreeMap<String, u64>,
+}
+
+impl GasSummary {
+    pub fn merge(&mut self, other: &GasSummary) {
+        self.total += other.total;
+        for (name, gas) in &other.functions {
+            let entry = self.functions.entry(name.clone()).or_default();
+            entry.calls += gas.calls;
+            entry.total += gas.total;
+            entry.own += gas.own;
+        }
+        for (category, gas) in &other.instructions {
+            *self.instructions.entry(category.clone()).or_default() += gas;
+        }
+        for (stack, gas) in &other.stacks {
+            *self.stacks.entry(stack.clone()).or_default() += gas;
+        }
+    }
+
+    /// The call stacks in the "folded stacks" format: one `stack gas` line per call stack.
+    pub fn folded_stacks(&self) -> String {
+        self.stacks
+            .iter()
+            .filter(|(_, gas)| **gas > 0)
+            .map(|(stack, gas)| format!("{stack} {gas}\n"))
+            .collect()
+    }
+}
+
+/// The category of an instruction, given the name the profiler records it under (its `Debug`
+/// representation, operands included).
+pub fn instruction_category(instruction: &str) -> &'static str {
+    let opcode = instruction
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .next()
+        .unwrap_or(instruction);
+    match opcode {
+        "Branch" | "BrTrue" | "BrFalse" | "VariantSwitch" | "Ret" | "Abort" | "Nop" => {
+            "control_flow"
+        }
+        "LdTrue" | "LdFalse" | "LdU8" | "LdU16" | "LdU32" | "LdU64" | "LdU128" | "LdU256"
+        | "LdConst" => "constants",
+        "CopyLoc" | "MoveLoc" | "StLoc" | "Pop" => "locals",
+        "ImmBorrowLoc"
+        | "MutBorrowLoc"
+        | "ImmBorrowField"
+        | "MutBorrowField"
+        | "ImmBorrowFieldGeneric"
+        | "MutBorrowFieldGeneric"
+        | "ReadRef"
+        | "WriteRef"
+        | "FreezeRef" => "references",
+        "Call" | "CallGeneric" => "calls",
+        "Pack"
+        | "PackGeneric"
+        | "Unpack"
+        | "UnpackGeneric"
+        | "PackVariant"
+        | "PackVariantGeneric"
+        | "UnpackVariant"
+        | "UnpackVariantGeneric"
+        | "UnpackVariantImmRef"
+        | "UnpackVariantGenericImmRef"
+        | "UnpackVariantMutRef"
+        | "UnpackVariantGenericMutRef" => "structs_and_enums",
+        "VecPack" | "VecLen" | "VecImmBorrow" | "VecMutBorrow" | "VecPushBack" | "VecPopBack"
+        | "VecUnpack" | "VecSwap" => "vectors",
+        "Add" | "Sub" | "Mul" | "Div" | "Mod" | "BitOr" | "BitAnd" | "Xor" | "Shl" | "Shr"
+        | "Or" | "And" | "Not" => "arithmetic",
+        "Eq" | "Neq" | "Lt" | "Gt" | "Le" | "Ge" => "comparisons",
+        "CastU8" | "CastU16" | "CastU32" | "CastU64" | "CastU128" | "CastU256" => "casts",
+        _ if opcode.contains("Global")
+            || opcode.starts_with("Exists")
+            || opcode.starts_with("MoveFrom")
+            || opcode.starts_with("MoveTo") =>
+        {
+            "global_storage"
+        }
+        _ => "other",
+    }
+}
+
+#[cfg(feature = "tracing")]
+struct OpenFrame {
+    frame: u64,
+    at: u64,
+    is_instruction: bool,
+    /// For function frames, the call stack leading to (and including) the function.
+    stack: String,
+}
+
+#[cfg(feature = "tracing")]
+fn add(map: &mut BTreeMap<String, u64>, key: &str, gas: u64) {
+    match map.get_mut(key) {
+        Some(total) => *total += gas,
+        None => {
+            map.insert(key.to_string(), gas);
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl GasProfiler {
+    /// Summarizes the profile. Gas spent directly in the top level frame is only counted in the
+    /// total, and a function calling itself recursively only counts towards its total gas once.
+    pub fn summary(&self) -> GasSummary {
+        let profile = &self.profiles[0];
+        let mut summary = GasSummary {
+            total: profile.end_value,
+            ..GasSummary::default()
+        };
+
+        let mut open: Vec<OpenFrame> = vec![];
+        let mut last_at = 0;
+        for event in &profile.events {
+            let spent = event.at - last_at;
+            last_at = event.at;
+            // Attribute the gas to the innermost function, the call stack leading to it and, if
+            // one is executing, the category of the instruction.
+            let mut innermost = open.iter().rev().take(2).skip_while(|f| f.is_instruction);
+            if let (Some(top), Some(function)) = (open.last(), innermost.next()) {
+                if spent > 0 && !function.stack.is_empty() {
+                    if top.is_instruction {
+                        let category = instruction_category(self.frame_name(top.frame));
+                        add(&mut summary.instructions, category, spent);
+                    }
+                    let name = self.frame_name(function.frame);
+                    summary.functions.entry(name.to_string()).or_default().own += spent;
+                    add(&mut summary.stacks, &function.stack, spent);
+                }
+            }
+
+            if event.ty == Self::OPEN_FRAME_IDENT {
+                let is_instruction = self.instruction_frames.contains(&event.frame);
+                let name = self.frame_name(event.frame);
+                let stack = match open.iter().rev().find(|f| !f.is_instruction) {
+                    // The outermost frame is the top level frame, which is left out of stacks.
+                    None => String::new(),
+                    Some(_) if is_instruction => String::new(),
+                    Some(parent) if parent.stack.is_empty() => name.to_string(),
+                    Some(parent) => format!("{};{name}", parent.stack),
+                };
+                open.push(OpenFrame {
+                    frame: event.frame,
+                    at: event.at,
+                    is_instruction,
+                    stack,
+                });
+            } else if open.iter().any(|f| f.frame == event.frame) {
+                // Frames left open by an abort are closed along with their caller.
+                while let Some(closed) = open.pop() {
+                    self.close_summary_frame(&mut summary, &open, &closed, event.at);
+                    if closed.frame == event.frame {
+                        break;
+                    }
+                }
+            }
+        }
+        while let Some(closed) = open.pop() {
+            self.close_summary_frame(&mut summary, &open, &closed, last_at);
+        }
+        summary
+    }
+
+    fn frame_name(&self, frame: u64) -> &str {
+        &self.shared.frames[frame as usize].name
+    }
+
+    fn close_summary_frame(
+        &self,
+        summary: &mut GasSummary,
+        open: &[OpenFrame],
+        closed: &OpenFrame,
+        at: u64,
+    ) {
+        // The outermost frame is the top level frame.
+        if closed.is_instruction || open.is_empty() {
+            return;
+        }
+        let recursive = open.iter().any(|f| f.frame == closed.frame);
+        let name = self.frame_name(closed.frame);
+        let entry = summary.functions.entry(name.to_string()).or_default();
+        entry.calls += 1;
+        if !recursive {
+            entry.total += at - closed.at;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use move_vm_config::runtime::VMProfilerConfig;
+
+    const START_GAS: u64 = 1_000;
+
+    fn profiler(track_bytecode_instructions: bool) -> GasProfiler {
+        let config = VMProfilerConfig {
+            full_path: "gas_profile.json".into(),
+            track_bytecode_instructions,
+            use_long_function_name: false,
+        };
+        GasProfiler::init(&Some(config), "test".to_string(), START_GAS)
+    }
+
+    fn open(profiler: &mut GasProfiler, name: &str, gas_used: u64) {
+        profiler.open_frame(
+            name.to_string(),
+            format!("0x1::m::{name}"),
+            START_GAS - gas_used,
+        );
+    }
+
+    fn close(profiler: &mut GasProfiler, name: &str, gas_used: u64) {
+        profiler.close_frame(
+            name.to_string(),
+            format!("0x1::m::{name}"),
+            START_GAS - gas_used,
+        );
+    }
+
+    fn instruction(profiler: &mut GasProfiler, name: &str, gas_start: u64, gas_end: u64) {
+        profiler.open_instr(name.to_string(), START_GAS - gas_start);
+        profiler.close_instr(name.to_string(), START_GAS - gas_end);
+    }
+
+    fn function_gas(calls: u64, total: u64, own: u64) -> FunctionGas {
+        FunctionGas { calls, total, own }
+    }
+
+    fn to_map<V: Copy>(entries: &[(&str, V)]) -> BTreeMap<String, V> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn nested_calls() {
+        let mut profiler = profiler(true);
+        open(&mut profiler, "outer", 0);
+        instruction(&mut profiler, "LdU64(1)", 0, 10);
+        open(&mut profiler, "inner", 10);
+        instruction(&mut profiler, "Add", 10, 30);
+        close(&mut profiler, "inner", 35);
+        instruction(&mut profiler, "Ret", 35, 40);
+        close(&mut profiler, "outer", 40);
+        profiler.close();
+
+        let summary = profiler.summary();
+        assert_eq!(summary.total, 40);
+        assert_eq!(
+            summary.functions,
+            to_map(&[
+                ("outer", function_gas(1, 40, 15)),
+                ("inner", function_gas(1, 25, 20)),
+            ])
+        );
+        assert_eq!(
+            summary.instructions,
+            to_map(&[("constants", 10), ("arithmetic", 20), ("control_flow", 5)])
+        );
+        assert_eq!(summary.folded_stacks(), "outer 15\nouter;inner 25\n");
+    }
+
+    #[test]
+    fn recursive_calls() {
+        let mut profiler = profiler(false);
+        open(&mut profiler, "fact", 0);
+        open(&mut profiler, "fact", 10);
+        close(&mut profiler, "fact", 30);
+        close(&mut profiler, "fact", 35);
+        profiler.close();
+
+        let summary = profiler.summary();
+        assert_eq!(summary.total, 35);
+        // The recursive call is counted, but its gas is already part of the outer call's total.
+        assert_eq!(
+            summary.functions,
+            to_map(&[("fact", function_gas(2, 35, 35))])
+        );
+        assert!(summary.instructions.is_empty());
+        assert_eq!(summary.folded_stacks(), "fact 15\nfact;fact 20\n");
+    }
+
+    #[test]
+    fn frames_unwound_by_abort() {
+        let mut profiler = profiler(true);
+        open(&mut profiler, "outer", 0);
+        open(&mut profiler, "inner", 10);
+        // Neither the aborting instruction nor the function executing it are closed.
+        profiler.open_instr("Abort".to_string(), START_GAS - 20);
+        close(&mut profiler, "outer", 25);
+        profiler.close();
+
+        let summary = profiler.summary();
+        assert_eq!(summary.total, 25);
+        assert_eq!(
+            summary.functions,
+            to_map(&[
+                ("outer", function_gas(1, 25, 10)),
+                ("inner", function_gas(1, 15, 15)),
+            ])
+        );
+        assert_eq!(summary.instructions, to_map(&[("control_flow", 5)]));
+        assert_eq!(summary.folded_stacks(), "outer 10\nouter;inner 15\n");
+    }
+}